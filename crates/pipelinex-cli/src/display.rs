@@ -1,18 +1,198 @@
 use colored::*;
+use pipelinex_core::analyzer::baseline::BaselineDrift;
+use pipelinex_core::analyzer::dedup::DedupedReport;
+use pipelinex_core::analyzer::grouping::{group_findings, FindingGroup, GroupBy};
 use pipelinex_core::analyzer::report::{format_duration, AnalysisReport, Finding, Severity};
-use pipelinex_core::cost::CostEstimate;
-use pipelinex_core::flaky_detector::{FlakyCategory, FlakyReport};
+use pipelinex_core::cost::{CostBreakdown, CostEstimate, ProviderCostComparison};
+use pipelinex_core::explainer;
+use pipelinex_core::flaky_detector::{FlakyCategory, FlakyReport, TrendDirection};
 use pipelinex_core::linter::{LintReport, LintSeverity};
 use pipelinex_core::optimizer::docker_opt::{DockerAnalysis, DockerSeverity};
-use pipelinex_core::policy::{PolicyReport, PolicySeverity};
+use pipelinex_core::parser::detect::ProviderInfo;
+use pipelinex_core::policy::{PolicyReport, PolicySeverity, RuleStatus};
 use pipelinex_core::runner_sizing::{RunnerSizeClass, RunnerSizingReport};
-use pipelinex_core::simulator::SimulationResult;
+use pipelinex_core::simulator::{
+    self, ConvergenceReport, DistributionShift, SimulationComparison, SimulationResult,
+};
 use pipelinex_core::test_selector::TestSelection;
 use similar::{ChangeTag, TextDiff};
+use std::fmt::Write;
 use std::path::{Path, PathBuf};
 
+/// Row order of `analyze --format table`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum TableSort {
+    /// Longest estimated duration first
+    Duration,
+    /// Most findings first
+    Findings,
+    /// Lowest health score first
+    Health,
+}
+
+/// New and resolved findings relative to an `analyze --baseline` file.
+pub fn format_baseline_drift(drift: &BaselineDrift) -> String {
+    let mut out = String::new();
+    if drift.is_empty() {
+        let _ = writeln!(out, "{} No changes since the baseline", "✓".green());
+        return out;
+    }
+    let sections = [
+        ("New findings", &drift.new, "+".red()),
+        ("Resolved findings", &drift.resolved, "-".green()),
+    ];
+    for (heading, findings, marker) in sections {
+        if findings.is_empty() {
+            continue;
+        }
+        let _ = writeln!(out, "{} ({}):", heading.bold(), findings.len());
+        for drifted in findings {
+            let _ = writeln!(
+                out,
+                "  {} [{}] {}: {}",
+                marker,
+                drifted.finding.severity.symbol(),
+                drifted.source_file,
+                drifted.finding.title
+            );
+        }
+    }
+    out
+}
+
+/// One row per report: jobs, durations, findings by severity and health.
+///
+/// Rows keep discovery order unless `sort` is given.
+pub fn format_report_table(reports: &[AnalysisReport], sort: Option<TableSort>) -> String {
+    let mut rows: Vec<&AnalysisReport> = reports.iter().collect();
+    let health = |r: &AnalysisReport| r.health_score.as_ref().map(|h| h.total_score);
+    match sort {
+        Some(TableSort::Duration) => rows.sort_by(|a, b| {
+            b.total_estimated_duration_secs
+                .total_cmp(&a.total_estimated_duration_secs)
+        }),
+        Some(TableSort::Findings) => rows.sort_by_key(|r| std::cmp::Reverse(r.findings.len())),
+        // Reports without a score go last
+        Some(TableSort::Health) => rows.sort_by(|a, b| {
+            health(a)
+                .unwrap_or(f64::INFINITY)
+                .total_cmp(&health(b).unwrap_or(f64::INFINITY))
+        }),
+        None => {}
+    }
+
+    let count = |r: &AnalysisReport, severity: Severity| {
+        r.findings.iter().filter(|f| f.severity == severity).count()
+    };
+    let header = [
+        "File",
+        "Jobs",
+        "Duration",
+        "Optimized",
+        "Crit",
+        "High",
+        "Med",
+        "Low",
+        "Info",
+        "Health",
+    ]
+    .map(String::from);
+    let mut table: Vec<[String; 10]> = vec![header];
+    for report in rows {
+        table.push([
+            report.source_file.clone(),
+            report.job_count.to_string(),
+            format_duration(report.total_estimated_duration_secs),
+            format_duration(report.optimized_duration_secs),
+            count(report, Severity::Critical).to_string(),
+            count(report, Severity::High).to_string(),
+            count(report, Severity::Medium).to_string(),
+            count(report, Severity::Low).to_string(),
+            count(report, Severity::Info).to_string(),
+            report
+                .health_score
+                .as_ref()
+                .map(|h| format!("{:.0} {}", h.total_score, h.grade.label()))
+                .unwrap_or_else(|| "-".to_string()),
+        ]);
+    }
+
+    let widths: Vec<usize> = (0..10)
+        .map(|col| {
+            table
+                .iter()
+                .map(|row| row[col].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let separator = widths
+        .iter()
+        .map(|w| "-".repeat(*w))
+        .collect::<Vec<_>>()
+        .join("-+-");
+
+    let mut out = String::new();
+    for (i, row) in table.iter().enumerate() {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(col, (cell, width))| {
+                // File and health are text; the rest are numbers
+                if col == 0 || col == 9 {
+                    format!("{:<width$}", cell, width = width)
+                } else {
+                    format!("{:>width$}", cell, width = width)
+                }
+            })
+            .collect();
+        let _ = writeln!(out, "{}", cells.join(" | ").trim_end());
+        if i == 0 {
+            let _ = writeln!(out, "{}", separator);
+        }
+    }
+    out
+}
+
+/// Print the per-job slack table (skipped for single-job pipelines).
+fn print_job_slack(report: &AnalysisReport) {
+    if report.job_slack.len() < 2 {
+        return;
+    }
+
+    println!(" {}", "Job Slack".bold().underline());
+    println!(
+        "   {:<24} {:>10} {:>10} {:>10} {:>10}",
+        "Job".underline(),
+        "Duration".underline(),
+        "Earliest".underline(),
+        "Latest".underline(),
+        "Slack".underline()
+    );
+    for job in &report.job_slack {
+        let slack = if job.slack_secs == 0.0 {
+            "critical".red().to_string()
+        } else {
+            format_duration(job.slack_secs).green().to_string()
+        };
+        println!(
+            "   {:<24} {:>10} {:>10} {:>10} {:>10}",
+            job.job_id,
+            format_duration(job.duration_secs),
+            format_duration(job.earliest_start_secs),
+            format_duration(job.latest_start_secs),
+            slack,
+        );
+    }
+    println!();
+}
+
 /// Print a full analysis report to the terminal.
-pub fn print_analysis_report(report: &AnalysisReport) {
+///
+/// With `group_by`, findings are listed under a header per group with subtotals.
+/// With `explain`, each finding carries a one-line rationale for its category.
+pub fn print_analysis_report(report: &AnalysisReport, group_by: Option<GroupBy>, explain: bool) {
     println!();
     println!(
         "{}",
@@ -23,6 +203,16 @@ pub fn print_analysis_report(report: &AnalysisReport) {
         )
         .bold()
     );
+    if !report.metadata.passes.is_empty() {
+        println!(
+            " {}",
+            format!(
+                "Partial analysis ({} only): the health score and optimized duration ignore skipped passes.",
+                report.metadata.passes.join(", ")
+            )
+            .yellow()
+        );
+    }
     println!();
 
     // Pipeline structure summary
@@ -47,6 +237,8 @@ pub fn print_analysis_report(report: &AnalysisReport) {
     println!(" {} Provider: {}", "|-".dimmed(), report.provider.cyan());
     println!();
 
+    print_job_slack(report);
+
     // Separator
     println!(" {}", "=".repeat(60).dimmed());
     println!();
@@ -57,9 +249,22 @@ pub fn print_analysis_report(report: &AnalysisReport) {
             " {} No significant bottlenecks detected. Your pipeline looks good!",
             "OK".green().bold()
         );
+    } else if let Some(by) = group_by {
+        for group in group_findings(&report.findings, by) {
+            println!(
+                " {} {}",
+                group.label.bold().underline(),
+                group_subtotal(&group).dimmed()
+            );
+            println!();
+            for finding in &group.findings {
+                print_finding(finding, explain);
+                println!();
+            }
+        }
     } else {
         for finding in &report.findings {
-            print_finding(finding);
+            print_finding(finding, explain);
             println!();
         }
     }
@@ -114,6 +319,17 @@ pub fn print_analysis_report(report: &AnalysisReport) {
             format!("{:.0}", health.total_score).bold(),
             health.grade.label().cyan()
         );
+        if let Some(ref weights) = health.weights {
+            println!(
+                " {}   weights: critical -{} / high -{} / medium -{}, caching {}, parallelism {}",
+                "|".dimmed(),
+                weights.critical_penalty,
+                weights.high_penalty,
+                weights.medium_penalty,
+                weights.caching,
+                weights.parallelization
+            );
+        }
     }
     println!();
 
@@ -144,7 +360,12 @@ pub fn print_analysis_report(report: &AnalysisReport) {
     println!();
 }
 
-fn print_finding(finding: &Finding) {
+fn print_finding(finding: &Finding, explain: bool) {
+    print!("{}", format_finding(finding, explain));
+}
+
+/// Render one finding as the indented block used by the text reports.
+fn format_finding(finding: &Finding, explain: bool) -> String {
     let severity_tag = match finding.severity {
         Severity::Critical => format!(" {} ", finding.severity.symbol())
             .on_red()
@@ -169,18 +390,37 @@ fn print_finding(finding: &Finding) {
             .to_string(),
     };
 
-    println!(" {} {}", severity_tag, finding.title.bold());
-    println!("   {} {}", "|".dimmed(), finding.description);
+    let width = output_width();
+    let gutter = format!("   {} ", "|".dimmed());
+
+    let mut out = String::new();
+    let _ = writeln!(out, " {} {}", severity_tag, finding.title.bold());
+    let _ = writeln!(
+        out,
+        "{}{}",
+        gutter,
+        wrap_text(&finding.description, width, &gutter)
+    );
+    if explain {
+        let _ = writeln!(
+            out,
+            "   {} Why it matters: {}",
+            "|".dimmed(),
+            explainer::category_rationale(&finding.category).italic()
+        );
+    }
 
     if let Some(savings) = finding.estimated_savings_secs {
-        println!(
+        let _ = writeln!(
+            out,
             "   {} Estimated savings: {}/run",
             "|".dimmed(),
             format_duration(savings).green()
         );
     }
 
-    println!(
+    let _ = writeln!(
+        out,
         "   {} Confidence: {:.0}%{}",
         "|".dimmed(),
         finding.confidence * 100.0,
@@ -191,10 +431,138 @@ fn print_finding(finding: &Finding) {
         }
     );
 
-    println!("   {} {}", "|".dimmed(), finding.recommendation.dimmed());
+    // Dimmed line by line: the gutter's reset code would end a multi-line span
+    for line in wrap_lines(&finding.recommendation, width - visible_width(&gutter)) {
+        let _ = writeln!(out, "{}{}", gutter, line.dimmed());
+    }
 
     if let Some(cmd) = &finding.fix_command {
-        println!("   {} Fix: {}", "|".dimmed(), cmd.cyan());
+        let _ = writeln!(out, "   {} Fix: {}", "|".dimmed(), cmd.cyan());
+    }
+    if let Some(blame) = &finding.blame {
+        let _ = writeln!(
+            out,
+            "   {} Introduced by: {} <{}> in {} ({})",
+            "|".dimmed(),
+            blame.author,
+            blame.author_email,
+            blame.short_commit().yellow(),
+            blame.summary
+        );
+    }
+    let _ = writeln!(
+        out,
+        "   {} {}",
+        "|".dimmed(),
+        format!("Docs: {}", finding.help_url()).dimmed()
+    );
+    out
+}
+
+/// Width used when stdout is not a terminal or its size is unknown.
+const DEFAULT_OUTPUT_WIDTH: usize = 80;
+
+/// Narrowest width text is reflowed to, however small the terminal.
+const MIN_OUTPUT_WIDTH: usize = 40;
+
+/// Columns available for text output: the terminal width, or 80 when piped.
+fn output_width() -> usize {
+    terminal_size::terminal_size()
+        .map(|(terminal_size::Width(w), _)| w as usize)
+        .unwrap_or(DEFAULT_OUTPUT_WIDTH)
+        .max(MIN_OUTPUT_WIDTH)
+}
+
+/// Greedily wrap `text` to lines of at most `width` characters.
+///
+/// Existing line breaks are kept, and lines starting with whitespace
+/// (indented YAML or command snippets) are passed through untouched. Words
+/// longer than `width` get a line of their own rather than being split.
+fn wrap_lines(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for source in text.lines() {
+        if source.starts_with(char::is_whitespace) || source.chars().count() <= width {
+            lines.push(source.to_string());
+            continue;
+        }
+        let mut line = String::new();
+        for word in source.split_whitespace() {
+            let len = line.chars().count();
+            if len > 0 && len + 1 + word.chars().count() > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Wrap `text` to `width` columns, starting each continuation line with
+/// `indent`.
+///
+/// The first line is assumed to follow an `indent` the caller has already
+/// printed, so every line fits within `width` including the indent. Color
+/// codes in `indent` take up no columns.
+fn wrap_text(text: &str, width: usize, indent: &str) -> String {
+    wrap_lines(text, width.saturating_sub(visible_width(indent))).join(&format!("\n{}", indent))
+}
+
+/// Printed width of `text`, skipping ANSI color sequences.
+fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut in_escape = false;
+    for c in text.chars() {
+        match c {
+            '\x1b' => in_escape = true,
+            'm' if in_escape => in_escape = false,
+            _ if in_escape => {}
+            _ => width += 1,
+        }
+    }
+    width
+}
+
+/// Print findings merged across files by `analyze --dedup`.
+pub fn print_deduped_report(report: &DedupedReport, explain: bool) {
+    println!();
+    println!(
+        "{}",
+        format!(
+            " PipelineX — {} unique finding(s) across {} file(s)",
+            report.findings.len(),
+            report.source_files.len()
+        )
+        .bold()
+    );
+    println!();
+
+    if report.findings.is_empty() {
+        println!(
+            " {} No significant bottlenecks detected. Your pipelines look good!",
+            "OK".green().bold()
+        );
+        println!();
+        return;
+    }
+
+    println!(" {}", "=".repeat(60).dimmed());
+    println!();
+
+    for merged in &report.findings {
+        print_finding(&merged.finding, explain);
+        for file in &merged.files {
+            println!(
+                "   {} {} ({})",
+                "|-".dimmed(),
+                file.file,
+                file.affected_jobs.join(", ")
+            );
+        }
+        println!();
     }
 }
 
@@ -232,6 +600,162 @@ pub fn print_diff(original: &str, optimized: &str, filename: &str) {
     println!();
 }
 
+/// Print a cost comparison of one pipeline across CI provider pricing profiles.
+pub fn print_provider_cost_comparison(
+    file: &Path,
+    rows: &[ProviderCostComparison],
+    runs_per_month: u32,
+) {
+    println!();
+    println!(
+        "{}",
+        format!(" PipelineX Provider Cost Comparison — {}", file.display()).bold()
+    );
+    println!();
+
+    if let Some(first) = rows.first() {
+        println!(
+            " {} Billable minutes/run:       {:.1}",
+            "|-".dimmed(),
+            first.billable_minutes_per_run
+        );
+    }
+    println!(
+        " {} Pipeline runs/month:        {}",
+        "|-".dimmed(),
+        runs_per_month
+    );
+    println!();
+
+    let cheapest = rows
+        .iter()
+        .map(|r| r.monthly_cost)
+        .fold(f64::INFINITY, f64::min);
+
+    println!(
+        "   {:<24} {:>12} {:>14}",
+        "Provider".underline(),
+        "Cost/run".underline(),
+        "Monthly cost".underline()
+    );
+    for row in rows {
+        let monthly = format!("${:.2}", row.monthly_cost);
+        let monthly = if row.monthly_cost == cheapest {
+            monthly.green().to_string()
+        } else {
+            monthly
+        };
+        println!(
+            "   {:<24} {:>12} {:>14}",
+            row.provider,
+            format!("${:.3}", row.cost_per_run),
+            monthly
+        );
+    }
+    println!();
+}
+
+/// Print the per-job cost breakdown for one pipeline file.
+pub fn print_cost_breakdown(file: &Path, breakdown: &CostBreakdown, runs_per_month: u32) {
+    println!();
+    println!(
+        "{}",
+        format!(" PipelineX Per-Job Cost Breakdown — {}", file.display()).bold()
+    );
+    println!();
+    println!(
+        " {} Pipeline runs/month:        {}",
+        "|-".dimmed(),
+        runs_per_month
+    );
+    println!(
+        " {} Monthly compute cost:       {}",
+        "|-".dimmed(),
+        format!("${:.2}", breakdown.monthly_compute_cost).bold()
+    );
+    println!();
+
+    println!(
+        "   {:<24} {:<18} {:>9} {:>10} {:>14} {:>7}",
+        "Job".underline(),
+        "Runner".underline(),
+        "Duration".underline(),
+        "Cost/run".underline(),
+        "Monthly cost".underline(),
+        "Share".underline()
+    );
+    for (i, job) in breakdown.jobs.iter().enumerate() {
+        let duration = if job.instances > 1 {
+            format!("{} x{}", format_duration(job.duration_secs), job.instances)
+        } else {
+            format_duration(job.duration_secs)
+        };
+        let share = if breakdown.monthly_compute_cost > 0.0 {
+            job.monthly_compute_cost / breakdown.monthly_compute_cost * 100.0
+        } else {
+            0.0
+        };
+        let monthly = format!("${:.2}", job.monthly_compute_cost);
+        let monthly = if i == 0 && job.monthly_compute_cost > 0.0 {
+            monthly.red().to_string()
+        } else {
+            monthly
+        };
+        println!(
+            "   {:<24} {:<18} {:>9} {:>10} {:>14} {:>6.1}%",
+            job.job_id,
+            job.runner,
+            duration,
+            format!("${:.3}", job.cost_per_run),
+            monthly,
+            share
+        );
+    }
+    println!();
+}
+
+/// Print the supported providers and which pipeline features each parser models.
+pub fn print_provider_matrix(providers: &[ProviderInfo]) {
+    println!();
+    println!("{}", " PipelineX Supported Providers".bold());
+    println!();
+    println!(
+        "   {:<18} {:<7} {:<7} {:<9} {:<9} {:<9} {}",
+        "Provider".underline(),
+        "Matrix".underline(),
+        "Needs".underline(),
+        "Includes".underline(),
+        "Parallel".underline(),
+        "Services".underline(),
+        "Files".underline()
+    );
+    let mark = |supported: bool| {
+        if supported {
+            "yes".green()
+        } else {
+            "-".dimmed()
+        }
+    };
+    for info in providers {
+        let caps = &info.capabilities;
+        let mut files = info.file_patterns.join(", ");
+        if !info.content_markers.is_empty() {
+            files.push_str(&format!(" containing {}", info.content_markers.join(", ")));
+        }
+        println!(
+            "   {:<18} {:<7} {:<7} {:<9} {:<9} {:<9} {}",
+            info.provider,
+            mark(caps.matrix),
+            mark(caps.needs),
+            mark(caps.includes),
+            mark(caps.parallel),
+            mark(caps.services),
+            files.dimmed()
+        );
+    }
+    println!();
+}
+
 /// Print a cost estimate report.
 pub fn print_cost_report(
     file: &Path,
@@ -315,8 +839,61 @@ pub fn print_cost_report(
     println!();
 }
 
+/// Print how the simulated percentiles settle as the run count grows.
+pub fn print_convergence_report(pipeline_name: &str, report: &ConvergenceReport) {
+    println!();
+    println!(
+        "{}",
+        format!(" PipelineX Simulation Convergence — {}", pipeline_name).bold()
+    );
+    println!();
+    println!(
+        "   {:>7}  {:>16}  {:>16}  {:>16}",
+        "Runs", "p50 (± SE)", "p90 (± SE)", "p99 (± SE)"
+    );
+    for step in &report.steps {
+        let cell =
+            |estimate: f64, error: f64| format!("{} ± {:.0}s", format_duration(estimate), error);
+        let line = format!(
+            "   {:>7}  {:>16}  {:>16}  {:>16}",
+            step.runs,
+            cell(step.p50_secs, step.p50_std_error_secs),
+            cell(step.p90_secs, step.p90_std_error_secs),
+            cell(step.p99_secs, step.p99_std_error_secs)
+        );
+        if step.is_stable(report.tolerance) {
+            println!("{}", line.green());
+        } else {
+            println!("{}", line);
+        }
+    }
+    println!();
+    match report.recommended_runs {
+        Some(runs) => println!(
+            "   Percentiles are stable (standard error within {:.0}%) from {} runs; use --runs {} or more.",
+            report.tolerance * 100.0,
+            runs,
+            runs
+        ),
+        None => println!(
+            "   {}",
+            format!(
+                "Percentiles have not stabilized within {:.0}% yet; try a larger --runs.",
+                report.tolerance * 100.0
+            )
+            .yellow()
+        ),
+    }
+    println!();
+}
+
 /// Print Monte Carlo simulation results.
-pub fn print_simulation_report(pipeline_name: &str, result: &SimulationResult, max_jobs: usize) {
+pub fn print_simulation_report(
+    pipeline_name: &str,
+    result: &SimulationResult,
+    max_jobs: usize,
+    sparkline: bool,
+) {
     println!();
     println!(
         "{}",
@@ -352,8 +929,35 @@ pub fn print_simulation_report(pipeline_name: &str, result: &SimulationResult, m
     );
     println!();
 
+    if let Some(failures) = &result.failures {
+        println!(" {}", "Failures & Reruns".bold().underline());
+        println!(
+            "   Reruns:   {:.1}% of runs aborted at least once",
+            failures.rerun_pct
+        );
+        if failures.failed_pct > 0.0 {
+            println!(
+                "   Failed:   {}",
+                format!(
+                    "{:.1}% still failing after {} attempts",
+                    failures.failed_pct,
+                    simulator::MAX_PIPELINE_ATTEMPTS
+                )
+                .red()
+            );
+        }
+        println!(
+            "   Overhead: {} per run on average",
+            format_duration(failures.mean_overhead_secs).yellow()
+        );
+        println!();
+    }
+
     // Histogram
     println!(" {}", "Timing Histogram".bold().underline());
+    if sparkline {
+        println!("   {}", simulator::sparkline(&result.histogram).blue());
+    }
     for bucket in &result.histogram {
         if bucket.count > 0 {
             let label = format!(
@@ -361,7 +965,7 @@ pub fn print_simulation_report(pipeline_name: &str, result: &SimulationResult, m
                 format_duration(bucket.lower_bound_secs),
                 format_duration(bucket.upper_bound_secs)
             );
-            let bar = "#".repeat(bucket.bar.len()).blue().to_string();
+            let bar = bucket.bar.blue().to_string();
             println!("{} {} {}", label, bar, bucket.count);
         }
     }
@@ -412,6 +1016,162 @@ pub fn print_simulation_report(pipeline_name: &str, result: &SimulationResult, m
     println!();
 }
 
+/// Print original vs optimized simulation percentiles and the per-run savings.
+pub fn print_simulation_comparison(pipeline_name: &str, comparison: &SimulationComparison) {
+    let before = &comparison.original;
+    let after = &comparison.optimized;
+
+    println!();
+    println!(
+        "{}",
+        format!(
+            " PipelineX Simulation — {} (original vs optimized, {} runs)",
+            pipeline_name, before.runs
+        )
+        .bold()
+    );
+    println!();
+
+    println!(
+        "   {:<6} {:>10} {:>10} {:>10}",
+        "".underline(),
+        "Original".underline(),
+        "Optimized".underline(),
+        "Saved".underline()
+    );
+    for (label, original, optimized) in [
+        ("p50", before.p50_duration_secs, after.p50_duration_secs),
+        ("p90", before.p90_duration_secs, after.p90_duration_secs),
+        ("p99", before.p99_duration_secs, after.p99_duration_secs),
+        ("Mean", before.mean_duration_secs, after.mean_duration_secs),
+    ] {
+        let saved = original - optimized;
+        let saved_str = format!("{:>10}", format_signed_duration(saved));
+        println!(
+            "   {:<6} {:>10} {:>10} {}",
+            label,
+            format_duration(original),
+            format_duration(optimized),
+            if saved > 0.0 {
+                saved_str.green()
+            } else {
+                saved_str.normal()
+            }
+        );
+    }
+    println!();
+
+    let delta = &comparison.delta;
+    println!(" {}", "Per-Run Savings".bold().underline());
+    println!("   p50:     {}", format_signed_duration(delta.p50_secs));
+    println!("   p90:     {}", format_signed_duration(delta.p90_secs));
+    println!("   p99:     {}", format_signed_duration(delta.p99_secs));
+    println!(
+        "   Range:   {} to {}",
+        format_signed_duration(delta.min_secs),
+        format_signed_duration(delta.max_secs)
+    );
+    println!();
+}
+
+/// Print how the timing distribution moved relative to a saved simulation.
+pub fn print_distribution_shift(
+    pipeline_name: &str,
+    previous: &SimulationResult,
+    current: &SimulationResult,
+    shift: &DistributionShift,
+) {
+    println!();
+    println!(
+        "{}",
+        format!(
+            " PipelineX Simulation — {} (previous vs current)",
+            pipeline_name
+        )
+        .bold()
+    );
+    println!();
+
+    println!(
+        "   {:<6} {:>10} {:>10} {:>10}",
+        "".underline(),
+        "Previous".underline(),
+        "Current".underline(),
+        "Change".underline()
+    );
+    for (label, before, after, delta) in [
+        (
+            "p50",
+            previous.p50_duration_secs,
+            current.p50_duration_secs,
+            shift.p50_delta_secs,
+        ),
+        (
+            "p90",
+            previous.p90_duration_secs,
+            current.p90_duration_secs,
+            shift.p90_delta_secs,
+        ),
+        (
+            "p99",
+            previous.p99_duration_secs,
+            current.p99_duration_secs,
+            shift.p99_delta_secs,
+        ),
+        (
+            "Mean",
+            previous.mean_duration_secs,
+            current.mean_duration_secs,
+            shift.mean_delta_secs,
+        ),
+    ] {
+        let delta_str = format!("{:>10}", format_signed_duration(delta));
+        println!(
+            "   {:<6} {:>10} {:>10} {}",
+            label,
+            format_duration(before),
+            format_duration(after),
+            if delta < 0.0 {
+                delta_str.green()
+            } else if delta > 0.0 {
+                delta_str.red()
+            } else {
+                delta_str.normal()
+            }
+        );
+    }
+    println!();
+
+    println!(" {}", "Distribution".bold().underline());
+    println!(
+        "   KS statistic: {:.3} (significant above {:.3})",
+        shift.ks_statistic, shift.ks_critical_value
+    );
+    println!("   Overlap:      {:.0}%", shift.overlap * 100.0);
+    if shift.significant {
+        let direction = if shift.mean_delta_secs <= 0.0 {
+            "faster".green().bold()
+        } else {
+            "slower".red().bold()
+        };
+        println!(
+            "   Verdict:      significant shift — pipeline is {}",
+            direction
+        );
+    } else {
+        println!("   Verdict:      within noise");
+    }
+    println!();
+}
+
+fn format_signed_duration(secs: f64) -> String {
+    if secs < 0.0 {
+        format!("-{}", format_duration(-secs))
+    } else {
+        format_duration(secs)
+    }
+}
+
 /// Print Docker analysis results.
 pub fn print_docker_analysis(path: &Path, analysis: &DockerAnalysis) {
     println!();
@@ -446,8 +1206,13 @@ pub fn print_docker_analysis(path: &Path, analysis: &DockerAnalysis) {
                 DockerSeverity::Info => " INFO ".on_blue().white().to_string(),
             };
 
+            let gutter = format!("   {} ", "|".dimmed());
             println!(" {} {}", tag, finding.title.bold());
-            println!("   {} {}", "|".dimmed(), finding.description);
+            println!(
+                "{}{}",
+                gutter,
+                wrap_text(&finding.description, output_width(), &gutter)
+            );
             if let Some(line) = finding.line_number {
                 println!("   {} Line: {}", "|".dimmed(), line);
             }
@@ -485,7 +1250,15 @@ pub fn print_test_selection(selection: &TestSelection) {
     } else {
         for (i, file) in selection.changed_files.iter().enumerate() {
             if i < 10 {
-                println!(" {} {}", "|-".dimmed(), file.display());
+                match selection.languages.iter().find(|l| &l.file == file) {
+                    Some(lang) => println!(
+                        " {} {} {}",
+                        "|-".dimmed(),
+                        file.display(),
+                        format!("({})", lang.language).dimmed()
+                    ),
+                    None => println!(" {} {}", "|-".dimmed(), file.display()),
+                }
             } else if i == 10 {
                 println!(
                     " {} ... ({} more files)",
@@ -527,6 +1300,15 @@ pub fn print_test_selection(selection: &TestSelection) {
         println!();
     }
 
+    // Framework commands
+    if !selection.test_commands.is_empty() {
+        println!(" {}", "Test Commands".bold().underline());
+        for command in &selection.test_commands {
+            println!(" {} {}", "|-".dimmed(), command.cyan());
+        }
+        println!();
+    }
+
     // Selection ratio
     println!(" {}", "Selection Summary".bold().underline());
     if selection.selection_ratio > 0.0 {
@@ -630,6 +1412,8 @@ pub fn print_flaky_report(report: &FlakyReport, files: &[PathBuf]) {
     );
     println!();
 
+    print_flaky_trends(report);
+
     if report.flaky_tests.is_empty() {
         println!(
             " {} {}",
@@ -741,6 +1525,51 @@ pub fn print_flaky_report(report: &FlakyReport, files: &[PathBuf]) {
     println!();
 }
 
+/// Print the per-test trend section of a multi-batch flaky report.
+fn print_flaky_trends(report: &FlakyReport) {
+    let Some(trends) = &report.trends else {
+        return;
+    };
+
+    println!(" {}", "Trends".bold().underline());
+    if trends.is_empty() {
+        println!(" {} No failures in any batch", "|-".dimmed());
+    }
+    for trend in trends.iter().take(15) {
+        let rates: Vec<String> = trend
+            .failure_rates
+            .iter()
+            .map(|r| match r {
+                Some(rate) => format!("{:.0}%", rate * 100.0),
+                None => "-".to_string(),
+            })
+            .collect();
+        let direction = match trend.direction {
+            TrendDirection::Worsening => "worsening".red().bold(),
+            TrendDirection::Improving => "improving".green(),
+            TrendDirection::Stable => "stable".normal(),
+        };
+        println!(
+            " {} {} {} ({})",
+            "|-".dimmed(),
+            trend.name,
+            direction,
+            rates.join(" -> ").dimmed()
+        );
+    }
+    if trends.len() > 15 {
+        println!(" {} ... and {} more", "|-".dimmed(), trends.len() - 15);
+    }
+    if let Some(new_tests) = report.new_flaky_tests.as_ref().filter(|t| !t.is_empty()) {
+        println!(
+            " {} Newly failing in the latest batch: {}",
+            "|-".dimmed(),
+            new_tests.join(", ").yellow()
+        );
+    }
+    println!();
+}
+
 /// Print runner right-sizing recommendations.
 pub fn print_runner_sizing_report(source: &Path, report: &RunnerSizingReport) {
     println!();
@@ -847,8 +1676,26 @@ fn rank(class: RunnerSizeClass) -> u8 {
     }
 }
 
+/// "(2 findings, 1m 30s/run)" for a group header.
+fn group_subtotal(group: &FindingGroup) -> String {
+    let count = group.findings.len();
+    let plural = if count == 1 { "" } else { "s" };
+    if group.savings_secs > 0.0 {
+        format!(
+            "({} finding{}, {}/run)",
+            count,
+            plural,
+            format_duration(group.savings_secs)
+        )
+    } else {
+        format!("({} finding{})", count, plural)
+    }
+}
+
 /// Generate markdown formatted analysis report.
-pub fn format_markdown_report(report: &AnalysisReport) -> String {
+///
+/// With `group_by`, the findings table is split into one table per group.
+pub fn format_markdown_report(report: &AnalysisReport, group_by: Option<GroupBy>) -> String {
     let mut md = String::new();
 
     md.push_str(&format!(
@@ -869,29 +1716,22 @@ pub fn format_markdown_report(report: &AnalysisReport) -> String {
 
     if !report.findings.is_empty() {
         md.push_str("## Findings\n\n");
-        md.push_str("| Severity | Finding | Savings | Auto-fixable |\n");
-        md.push_str("|----------|---------|---------|-------------|\n");
-
-        for finding in &report.findings {
-            let severity_icon = match finding.severity {
-                Severity::Critical => "🔴 CRITICAL",
-                Severity::High => "🟡 HIGH",
-                Severity::Medium => "🔵 MEDIUM",
-                Severity::Low => "⚪ LOW",
-                Severity::Info => "ℹ️ INFO",
-            };
-            let savings = finding
-                .estimated_savings_secs
-                .map(|s| format!("{}/run", format_duration(s)))
-                .unwrap_or_else(|| "—".into());
-            let fixable = if finding.auto_fixable { "✅" } else { "—" };
-
-            md.push_str(&format!(
-                "| {} | {} | {} | {} |\n",
-                severity_icon, finding.title, savings, fixable
-            ));
+        match group_by {
+            Some(by) => {
+                for group in group_findings(&report.findings, by) {
+                    md.push_str(&format!(
+                        "**{}** {}\n\n",
+                        group.label,
+                        group_subtotal(&group)
+                    ));
+                    push_findings_table(&mut md, &group.findings);
+                }
+            }
+            None => {
+                let findings: Vec<&Finding> = report.findings.iter().collect();
+                push_findings_table(&mut md, &findings);
+            }
         }
-        md.push('\n');
 
         md.push_str("### Details\n\n");
         for finding in &report.findings {
@@ -940,6 +1780,32 @@ pub fn format_markdown_report(report: &AnalysisReport) -> String {
     md
 }
 
+fn push_findings_table(md: &mut String, findings: &[&Finding]) {
+    md.push_str("| Severity | Finding | Savings | Auto-fixable |\n");
+    md.push_str("|----------|---------|---------|-------------|\n");
+
+    for finding in findings {
+        let severity_icon = match finding.severity {
+            Severity::Critical => "🔴 CRITICAL",
+            Severity::High => "🟡 HIGH",
+            Severity::Medium => "🔵 MEDIUM",
+            Severity::Low => "⚪ LOW",
+            Severity::Info => "ℹ️ INFO",
+        };
+        let savings = finding
+            .estimated_savings_secs
+            .map(|s| format!("{}/run", format_duration(s)))
+            .unwrap_or_else(|| "—".into());
+        let fixable = if finding.auto_fixable { "✅" } else { "—" };
+
+        md.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            severity_icon, finding.title, savings, fixable
+        ));
+    }
+    md.push('\n');
+}
+
 /// Print lint report to terminal.
 pub fn print_lint_report(report: &LintReport) {
     println!();
@@ -995,11 +1861,19 @@ pub fn print_lint_report(report: &LintReport) {
             "0".to_string()
         },
     );
+    let fixable = report.findings.iter().filter(|f| f.fixable).count();
+    if fixable > 0 {
+        println!(
+            " {} can be fixed automatically with {}",
+            fixable,
+            "pipelinex lint --fix".cyan()
+        );
+    }
     println!();
 }
 
 /// Print policy check report to terminal.
-pub fn print_policy_report(report: &PolicyReport) {
+pub fn print_policy_report(report: &PolicyReport, include_passing: bool) {
     println!();
     println!(
         "{}",
@@ -1007,6 +1881,24 @@ pub fn print_policy_report(report: &PolicyReport) {
     );
     println!();
 
+    if include_passing && !report.evaluated_rules.is_empty() {
+        println!(" Rules evaluated:");
+        for result in &report.evaluated_rules {
+            let status = match result.status {
+                RuleStatus::Passed => result.status.symbol().green().bold(),
+                RuleStatus::Failed => result.status.symbol().red().bold(),
+                RuleStatus::NotApplicable => result.status.symbol().dimmed(),
+            };
+            println!(
+                "   {:<5} {} {}",
+                status,
+                result.rule,
+                format!("({})", result.detail).dimmed()
+            );
+        }
+        println!();
+    }
+
     if report.violations.is_empty() {
         println!(" {} All policy checks passed!", "PASS".green().bold());
         println!();
@@ -1020,10 +1912,15 @@ pub fn print_policy_report(report: &PolicyReport) {
         };
 
         println!(
-            " {} [{}] {}",
+            " {} [{}] {}{}",
             tag,
             violation.rule.dimmed(),
-            violation.message
+            violation.message,
+            if violation.in_baseline {
+                " (in baseline)".dimmed().to_string()
+            } else {
+                String::new()
+            }
         );
         if !violation.affected_jobs.is_empty() {
             println!(
@@ -1077,7 +1974,7 @@ pub fn print_security_report(findings: &[Finding], source_file: &str) {
     println!();
 
     for finding in findings {
-        print_finding(finding);
+        print_finding(finding, false);
         println!();
     }
 
@@ -1474,3 +2371,142 @@ pub fn print_whatif_result(result: &WhatIfResult) {
 
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pipelinex_core::analyzer::report::FindingCategory;
+
+    #[test]
+    fn test_no_color_output_has_no_escape_sequences() {
+        let finding = Finding {
+            severity: Severity::Critical,
+            category: FindingCategory::MissingCache,
+            title: "No dependency caching for npm/yarn/pnpm".into(),
+            description: "Job 'build' runs 'npm ci' without caching node_modules.".into(),
+            affected_jobs: vec!["build".into()],
+            recommendation: "Add actions/cache for node_modules.".into(),
+            fix_command: Some("pipelinex optimize --apply cache".into()),
+            estimated_savings_secs: Some(150.0),
+            confidence: 0.95,
+            auto_fixable: true,
+            documentation_url: None,
+            blame: None,
+        };
+
+        colored::control::set_override(false);
+        let plain = format_finding(&finding, false);
+        assert!(!plain.contains('\x1b'));
+        assert!(plain.contains(" CRITICAL  No dependency caching"));
+
+        colored::control::set_override(true);
+        assert!(format_finding(&finding, false).contains('\x1b'));
+        colored::control::unset_override();
+    }
+
+    #[test]
+    fn test_wrap_text_keeps_continuation_indent() {
+        let text = "Cache node_modules keyed on the lockfile hash so installs \
+            are restored instead of downloaded on every run.";
+        let wrapped = wrap_text(text, 40, "   | ");
+        let lines: Vec<&str> = wrapped.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "Cache node_modules keyed on the",
+                "   | lockfile hash so installs are",
+                "   | restored instead of downloaded on",
+                "   | every run.",
+            ]
+        );
+        // The first line leaves room for the indent the caller printed
+        assert!(lines.iter().all(|l| l.len() <= 40));
+        assert!(lines[0].len() <= 35);
+
+        // Indented snippets and existing breaks are left alone
+        let snippet =
+            "Add a cache step:\n\n  - uses: actions/cache@v4 with a very long trailing comment";
+        assert_eq!(wrap_text(snippet, 30, "> "), snippet.replace('\n', "\n> "));
+
+        // Escape codes in the indent take no columns
+        assert_eq!(visible_width("   \x1b[2m|\x1b[0m "), 5);
+    }
+
+    #[test]
+    fn test_report_table_has_one_row_per_file() {
+        let report = |file: &str, duration: f64, findings: usize| {
+            let dag = pipelinex_core::parser::github::GitHubActionsParser::parse(
+                "on: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n    steps:\n      - run: make\n",
+                file.to_string(),
+            )
+            .unwrap();
+            let mut report = pipelinex_core::analyzer::analyze(&dag);
+            report.total_estimated_duration_secs = duration;
+            report.findings.truncate(findings);
+            report
+        };
+        let reports = vec![report("ci.yml", 120.0, 0), report("release.yml", 900.0, 1)];
+
+        let table = format_report_table(&reports, None);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 2 + reports.len());
+        let columns: Vec<&str> = lines[0].split(" | ").map(str::trim).collect();
+        assert_eq!(
+            columns,
+            vec![
+                "File",
+                "Jobs",
+                "Duration",
+                "Optimized",
+                "Crit",
+                "High",
+                "Med",
+                "Low",
+                "Info",
+                "Health"
+            ]
+        );
+        assert!(lines[1].chars().all(|c| c == '-' || c == '+'));
+        for line in &lines[2..] {
+            assert_eq!(line.split(" | ").count(), columns.len());
+        }
+        assert!(lines[2].starts_with("ci.yml "));
+        assert!(lines[2].contains(" 2:00 "));
+
+        let by_duration = format_report_table(&reports, Some(TableSort::Duration));
+        assert!(by_duration
+            .lines()
+            .nth(2)
+            .unwrap()
+            .starts_with("release.yml"));
+    }
+
+    #[test]
+    fn test_explain_adds_category_rationale() {
+        for category in [
+            FindingCategory::MissingCache,
+            FindingCategory::SerialBottleneck,
+            FindingCategory::Security,
+        ] {
+            let finding = Finding {
+                severity: Severity::High,
+                category: category.clone(),
+                title: "title".into(),
+                description: "description".into(),
+                affected_jobs: vec!["build".into()],
+                recommendation: "recommendation".into(),
+                fix_command: None,
+                estimated_savings_secs: None,
+                confidence: 0.9,
+                auto_fixable: false,
+                documentation_url: None,
+                blame: None,
+            };
+            let rationale = explainer::category_rationale(&category);
+            let explained = format_finding(&finding, true);
+            assert!(explained.contains("Why it matters: "), "{}", explained);
+            assert!(explained.contains(rationale), "{}", explained);
+            assert!(!format_finding(&finding, false).contains("Why it matters"));
+        }
+    }
+}
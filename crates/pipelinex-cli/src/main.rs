@@ -3,27 +3,35 @@ mod display;
 use anyhow::{Context, Result};
 use clap::{CommandFactory, Parser, Subcommand};
 use pipelinex_core::analyzer;
-use pipelinex_core::flaky_detector::FlakyDetector;
+use pipelinex_core::analyzer::conditions::EvaluationContext;
+use pipelinex_core::analyzer::grouping::GroupBy;
+use pipelinex_core::analyzer::passes::{parse_pass_ids, select_passes, AnalyzerPass};
+use pipelinex_core::analyzer::report::format_duration;
+use pipelinex_core::analyzer::sarif::{load_sarif_options, SarifOptions};
+use pipelinex_core::discovery::{walk_pipeline_files, DEFAULT_WALK_DEPTH};
+use pipelinex_core::duration_model::{DurationModel, HeuristicModel, HistoricalModel};
+use pipelinex_core::flaky_detector::{self, FlakyDetector, QuarantineFormat};
 use pipelinex_core::github_actions_to_gitlab_ci;
+use pipelinex_core::health_score::{load_health_weights, HealthWeights};
+use pipelinex_core::linter::LintSeverity;
 use pipelinex_core::multi_repo::{analyze_multi_repo, RepoPipeline};
-use pipelinex_core::optimizer::Optimizer;
-use pipelinex_core::parser::argo::ArgoWorkflowsParser;
-use pipelinex_core::parser::aws_codepipeline::AwsCodePipelineParser;
-use pipelinex_core::parser::azure::AzurePipelinesParser;
-use pipelinex_core::parser::bitbucket::BitbucketParser;
-use pipelinex_core::parser::buildkite::BuildkiteParser;
-use pipelinex_core::parser::circleci::CircleCIParser;
-use pipelinex_core::parser::drone::DroneParser;
-use pipelinex_core::parser::github::GitHubActionsParser;
-use pipelinex_core::parser::gitlab::GitLabCIParser;
-use pipelinex_core::parser::jenkins::JenkinsParser;
-use pipelinex_core::parser::tekton::TektonParser;
+use pipelinex_core::optimizer::selection::{parse_transform_ids, TransformSelection};
+use pipelinex_core::optimizer::{available_transforms, Optimizer, Transform};
+use pipelinex_core::parser::detect::{
+    parse_pipeline_file, parse_pipeline_file_as, rule_for_provider,
+};
+use pipelinex_core::parser::render::parse_rendered_file;
 use pipelinex_core::plugins;
 use pipelinex_core::profile_runner_sizing;
 use pipelinex_core::providers::GitHubClient;
-use pipelinex_core::test_selector::TestSelector;
+use pipelinex_core::redact::RedactionPolicy;
+use pipelinex_core::simulator::{FailureModel, HistogramOptions};
+use pipelinex_core::test_selector::{self, TestSelector, TestSelectorConfig};
+use pipelinex_core::timing::SpanTimer;
+use pipelinex_core::VerificationOutcome;
 use std::io::{IsTerminal, Write};
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use std::time::Instant;
 
 #[derive(Parser)]
@@ -36,8 +44,62 @@ use std::time::Instant;
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Maximum directory depth to search when a directory is given as input
+    #[arg(long, global = true, default_value_t = DEFAULT_WALK_DEPTH)]
+    max_depth: usize,
+
+    /// Increase log verbosity (-v for debug, -vv for trace)
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Only log errors; status messages are suppressed
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Disable colored output (also off when NO_COLOR is set or stdout is not a terminal)
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Parse every input with this provider's parser instead of auto-detecting
+    /// (github, gitlab, jenkins, circleci, azure, aws, bitbucket, buildkite, drone, tekton, argo)
+    #[arg(long, global = true)]
+    provider: Option<String>,
+
+    /// Pipe each pipeline file through this shell command before parsing
+    /// (for templated configs such as `ci.yml.j2`). The file content arrives on
+    /// stdin and its path as `$1`; the command must print the rendered YAML.
+    #[arg(long, global = true, value_name = "COMMAND")]
+    render: Option<String>,
+
+    /// PipelineX config file; its `[health]` table sets health score weights
+    /// (defaults to `.pipelinex/config.toml` when present)
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<PathBuf>,
+
+    /// TOML file of command → duration estimates layered over the built-in
+    /// heuristics (e.g. to calibrate `npm ci` for your runners)
+    #[arg(long, global = true, value_name = "FILE")]
+    estimates: Option<PathBuf>,
 }
 
+/// Depth limit for directory inputs, set once from `--max-depth`.
+static MAX_DEPTH: OnceLock<usize> = OnceLock::new();
+
+/// Parser forced by `--provider`, bypassing detection.
+static PROVIDER: OnceLock<String> = OnceLock::new();
+
+/// Preprocessing command set by `--render`.
+static RENDER: OnceLock<String> = OnceLock::new();
+
+/// Health score weights from the config file's `[health]` table.
+static HEALTH_WEIGHTS: OnceLock<HealthWeights> = OnceLock::new();
+
+/// SARIF level and score mapping from the config file's `[sarif]` table.
+static SARIF_OPTIONS: OnceLock<SarifOptions> = OnceLock::new();
+
+const DEFAULT_CONFIG_PATH: &str = ".pipelinex/config.toml";
+
 #[derive(Subcommand)]
 enum Commands {
     /// Analyze pipeline configuration for bottlenecks and optimization opportunities
@@ -46,10 +108,14 @@ enum Commands {
         #[arg(default_value = ".github/workflows/")]
         path: PathBuf,
 
-        /// Output format (text, json, sarif, html, markdown)
+        /// Output format (text, json, jsonl, sarif, html, markdown, github, csv, table)
         #[arg(short, long, default_value = "text")]
         format: String,
 
+        /// Row order for --format table
+        #[arg(long, value_enum)]
+        sort: Option<display::TableSort>,
+
         /// Disable all network calls (offline mode for air-gapped environments)
         #[arg(long)]
         offline: bool,
@@ -58,9 +124,67 @@ enum Commands {
         #[arg(long)]
         redact: bool,
 
+        /// Redact according to this TOML policy: paths, repo ids, env references
+        /// and job names become stable placeholders such as `<path:1>` (implies --redact)
+        #[arg(long, value_name = "FILE")]
+        redaction_policy: Option<PathBuf>,
+
         /// Sign the JSON output with an Ed25519 private key (hex or file path)
         #[arg(long)]
         sign: Option<String>,
+
+        /// Only analyze jobs that would run for this event (e.g., push, pull_request)
+        #[arg(long)]
+        event: Option<String>,
+
+        /// Branch or tag the event runs against, used with --event (e.g., main, refs/tags/v1)
+        #[arg(long = "ref", requires = "event")]
+        git_ref: Option<String>,
+
+        /// CSV of measured job durations (job_name,duration_secs) to use instead of heuristics
+        #[arg(long)]
+        timings_csv: Option<PathBuf>,
+
+        /// Collapse findings repeated across files into one entry listing each file (text, json)
+        #[arg(long)]
+        dedup: bool,
+
+        /// Group findings with subtotals: category, severity or job (text, markdown)
+        #[arg(long, conflicts_with = "dedup")]
+        group_by: Option<String>,
+
+        /// Run only these analyzer passes (comma-separated: critical-path, cache, docker,
+        /// lockfile, parallel, waste, runner, failure-handling, versions, plugins, security)
+        #[arg(long, value_delimiter = ',')]
+        only: Vec<String>,
+
+        /// Skip these analyzer passes (same names as --only)
+        #[arg(long, value_delimiter = ',')]
+        skip: Vec<String>,
+
+        /// Run the security scanners and include their findings in the report
+        #[arg(long)]
+        include_security: bool,
+
+        /// Add a one-line "why it matters" rationale to each finding (text)
+        #[arg(long)]
+        explain: bool,
+
+        /// Check this policy file and include violations in the report (fails on errors)
+        #[arg(long, value_name = "FILE")]
+        include_policy: Option<PathBuf>,
+
+        /// Also write the findings as a canonical baseline file for later comparison
+        #[arg(long, value_name = "FILE")]
+        baseline_save: Option<PathBuf>,
+
+        /// Compare against a baseline from --baseline-save; fails if there are new findings
+        #[arg(long, value_name = "FILE")]
+        baseline: Option<PathBuf>,
+
+        /// Attribute each finding to the last commit and author that touched its jobs
+        #[arg(long, conflicts_with_all = ["redact", "redaction_policy"])]
+        git_blame: bool,
     },
 
     /// Generate an optimized pipeline configuration
@@ -75,6 +199,14 @@ enum Commands {
         /// Show diff between original and optimized
         #[arg(long)]
         diff: bool,
+
+        /// Apply only these transforms (comma-separated: cache, parallelize, path-filter, concurrency, shallow-clone)
+        #[arg(long, value_delimiter = ',', conflicts_with = "interactive")]
+        select: Vec<String>,
+
+        /// Choose which transforms to apply from a prompt
+        #[arg(short, long)]
+        interactive: bool,
     },
 
     /// Show diff between current and optimized pipeline
@@ -103,6 +235,10 @@ enum Commands {
         /// Skip PR creation and only create branch with optimized config
         #[arg(long)]
         no_pr: bool,
+
+        /// Seconds to allow each git command and GitHub API request before giving up
+        #[arg(long, default_value = "120", value_name = "SECS")]
+        timeout: u64,
     },
 
     /// Estimate CI/CD costs and potential savings
@@ -122,6 +258,27 @@ enum Commands {
         /// Average fully-loaded developer hourly rate in USD
         #[arg(long, default_value = "150")]
         hourly_rate: f64,
+
+        /// Compare monthly compute cost across CI providers (GitHub hosted, GitLab SaaS, self-hosted)
+        #[arg(long)]
+        compare_providers: bool,
+
+        /// TOML file of `[[profile]]` pricing profiles to compare instead of the built-in ones
+        #[arg(long, requires = "compare_providers")]
+        pricing: Option<PathBuf>,
+
+        /// Attribute monthly compute cost to each job, most expensive first
+        #[arg(long, conflicts_with = "compare_providers")]
+        breakdown: bool,
+
+        /// Output format for --breakdown (text, json)
+        #[arg(short, long, default_value = "text")]
+        format: String,
+
+        /// Derive runs per month from `on.schedule` crons for scheduled workflows
+        /// (added to --runs-per-month when the workflow has other triggers too)
+        #[arg(long)]
+        from_schedule: bool,
     },
 
     /// Generate a visual pipeline DAG diagram
@@ -129,13 +286,21 @@ enum Commands {
         /// Path to workflow file
         path: PathBuf,
 
-        /// Output format (mermaid, dot, ascii)
+        /// Output format (mermaid, dot, ascii, critical-path)
         #[arg(short, long, default_value = "mermaid")]
         format: String,
 
         /// Output file path (prints to stdout if not specified)
         #[arg(short, long)]
         output: Option<PathBuf>,
+
+        /// Render only this job and its neighborhood
+        #[arg(long)]
+        focus: Option<String>,
+
+        /// Dependency hops to include around --focus, upstream and downstream
+        #[arg(long, default_value_t = 1, requires = "focus")]
+        depth: usize,
     },
 
     /// Run Monte Carlo simulation of pipeline timing
@@ -151,17 +316,66 @@ enum Commands {
         #[arg(long, default_value = "0.15")]
         variance: f64,
 
-        /// Output format (text, json)
+        /// Output format (text, json, html)
         #[arg(short, long, default_value = "text")]
         format: String,
 
         /// Maximum number of jobs to display in text output
-        #[arg(long, default_value_t = 12)]
+        #[arg(long, default_value_t = 12, conflicts_with = "compare_optimized")]
         top_jobs: usize,
 
         /// Disable progress output for long simulations
-        #[arg(long)]
+        #[arg(long, conflicts_with = "compare_optimized")]
         no_progress: bool,
+
+        /// Also simulate the optimized pipeline and report the improvement
+        #[arg(long)]
+        compare_optimized: bool,
+
+        /// Compare against a previous `simulate --format json` result and report the distribution shift
+        #[arg(long, value_name = "PREV_JSON", conflicts_with = "compare_optimized")]
+        compare_file: Option<PathBuf>,
+
+        /// Number of histogram buckets
+        #[arg(
+            long,
+            default_value_t = 20,
+            value_parser = clap::value_parser!(u16).range(1..),
+            conflicts_with = "compare_optimized"
+        )]
+        buckets: u16,
+
+        /// Width in characters of the longest histogram bar
+        #[arg(long, default_value_t = 40, conflicts_with = "compare_optimized")]
+        bar_width: usize,
+
+        /// Draw the histogram with Unicode block characters
+        #[arg(long, conflicts_with = "compare_optimized")]
+        unicode: bool,
+
+        /// Failure probability of one attempt of a job, as JOB=P (repeatable)
+        #[arg(long, value_name = "JOB=P", conflicts_with = "compare_optimized")]
+        failure_rate: Vec<String>,
+
+        /// Failure probability of jobs without a --failure-rate
+        #[arg(long, value_name = "P", conflicts_with = "compare_optimized")]
+        default_failure_rate: Option<f64>,
+
+        /// Take job failure rates from a `history --format json` result
+        #[arg(
+            long,
+            value_name = "HISTORY_JSON",
+            conflicts_with = "compare_optimized"
+        )]
+        failure_history: Option<PathBuf>,
+
+        /// Retries of a failed job before the pipeline aborts and is rerun
+        #[arg(long, default_value_t = 0, conflicts_with = "compare_optimized")]
+        retries: u32,
+
+        /// Simulate at doubling run counts up to --runs and report how the percentiles converge
+        #[arg(long, conflicts_with_all = ["compare_optimized", "compare_file"])]
+        convergence: bool,
     },
 
     /// Analyze a Dockerfile for optimization opportunities
@@ -196,11 +410,19 @@ enum Commands {
         /// Output format (text, json, yaml)
         #[arg(short, long, default_value = "text")]
         format: String,
+
+        /// Custom path-to-test rules (default: .pipelinex/test-map.toml in the repo, if present)
+        #[arg(long)]
+        test_map: Option<PathBuf>,
+
+        /// Also select tests for files that import a changed file (JS/TS, Python, Go)
+        #[arg(long)]
+        deps: bool,
     },
 
-    /// Detect flaky tests from JUnit XML reports
+    /// Detect flaky tests from JUnit, NUnit3, xUnit or TRX reports
     Flaky {
-        /// Paths to JUnit XML files or directory containing them
+        /// Paths to test report files (.xml, .trx) or directories containing them
         paths: Vec<PathBuf>,
 
         /// Minimum runs required to detect flakiness
@@ -214,6 +436,14 @@ enum Commands {
         /// Output format (text, json)
         #[arg(short, long, default_value = "text")]
         format: String,
+
+        /// Print a quarantine file for the flaky tests instead of the report (pytest, jest, list)
+        #[arg(long, value_name = "FORMAT")]
+        emit_quarantine: Option<String>,
+
+        /// Treat each path as one build's batch and report per-test trends (batches are ordered by file modification time)
+        #[arg(long)]
+        trend: bool,
     },
 
     /// Fetch and analyze workflow run history from GitHub
@@ -255,6 +485,10 @@ enum Commands {
         /// Output format (text, json, yaml)
         #[arg(short, long, default_value = "text")]
         format: String,
+
+        /// Re-parse the migrated config and fail if jobs or dependencies were lost
+        #[arg(long)]
+        validate: bool,
     },
 
     /// Analyze orchestration patterns across multiple repositories
@@ -325,6 +559,14 @@ enum Commands {
         /// Output format for analysis
         #[arg(short, long, default_value = "text")]
         format: String,
+
+        /// Serve the HTML report over HTTP and reload it in the browser on change
+        #[arg(long)]
+        serve: bool,
+
+        /// Port for --serve (bound on 127.0.0.1)
+        #[arg(long, default_value = "8080", requires = "serve")]
+        port: u16,
     },
 
     /// Lint CI config for syntax errors, deprecations, and typos
@@ -333,9 +575,17 @@ enum Commands {
         #[arg(default_value = ".github/workflows/")]
         path: PathBuf,
 
-        /// Output format (text, json)
+        /// Output format (text, json, github)
         #[arg(short, long, default_value = "text")]
         format: String,
+
+        /// Apply safe automatic fixes (deprecated action versions, retired runners) in place
+        #[arg(long)]
+        fix: bool,
+
+        /// With --fix, print the fixed file to stdout instead of writing it
+        #[arg(long, requires = "fix")]
+        dry_run: bool,
     },
 
     /// Run security scan on pipeline configs (secrets, permissions, injection, supply chain)
@@ -344,7 +594,7 @@ enum Commands {
         #[arg(default_value = ".github/workflows/")]
         path: PathBuf,
 
-        /// Output format (text, json)
+        /// Output format (text, json, sarif, github)
         #[arg(short, long, default_value = "text")]
         format: String,
     },
@@ -368,6 +618,24 @@ enum Commands {
         /// Output format (text, json)
         #[arg(short, long, default_value = "text")]
         format: String,
+
+        /// Write per-phase and per-file timings here as folded stacks
+        /// (for inferno-flamegraph or flamegraph.pl)
+        #[arg(long, value_name = "FILE")]
+        profile_output: Option<PathBuf>,
+
+        /// Analyze only N pseudo-randomly chosen pipeline files and
+        /// extrapolate the totals
+        #[arg(
+            long,
+            value_name = "N",
+            value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+        )]
+        sample: Option<usize>,
+
+        /// Seed for --sample; the same seed picks the same files
+        #[arg(long, default_value = "0", requires = "sample")]
+        seed: u64,
     },
 
     /// Generate a CI Software Bill of Materials (CycloneDX SBOM)
@@ -386,9 +654,48 @@ enum Commands {
         /// Path to workflow file
         path: PathBuf,
 
-        /// Output format (markdown, json, url)
+        /// Output format (markdown, json, url, endpoint)
         #[arg(short, long, default_value = "markdown")]
         format: String,
+
+        /// Also emit a "saves $X/mo" badge for the recoverable monthly cost
+        #[arg(long)]
+        savings: bool,
+
+        /// Pipeline runs per month (for the savings badge)
+        #[arg(long, default_value = "500", requires = "savings")]
+        runs_per_month: u32,
+
+        /// Team size (for the savings badge)
+        #[arg(long, default_value = "10", requires = "savings")]
+        team_size: u32,
+
+        /// Developer hourly rate in USD (for the savings badge)
+        #[arg(long, default_value = "150", requires = "savings")]
+        hourly_rate: f64,
+    },
+
+    /// One-paragraph executive summary per workflow (duration, findings, grade, recoverable cost)
+    Summary {
+        /// Path to workflow file or directory
+        #[arg(default_value = ".github/workflows/")]
+        path: PathBuf,
+
+        /// Output format (text, json)
+        #[arg(short, long, default_value = "text")]
+        format: String,
+
+        /// Pipeline runs per month (for the recoverable cost)
+        #[arg(long, default_value = "500")]
+        runs_per_month: u32,
+
+        /// Team size (for the recoverable cost)
+        #[arg(long, default_value = "10")]
+        team_size: u32,
+
+        /// Developer hourly rate in USD (for the recoverable cost)
+        #[arg(long, default_value = "150")]
+        hourly_rate: f64,
     },
 
     /// Ed25519 key management for report signing
@@ -403,8 +710,13 @@ enum Commands {
         report: PathBuf,
 
         /// Public key (hex string) or path to key file
+        #[arg(long, required_unless_present = "keyring", conflicts_with = "keyring")]
+        key: Option<String>,
+
+        /// Trusted keys: a file with one hex public key per line, or a directory
+        /// of `*.pub` / `public.key` files. Succeeds if any key verifies.
         #[arg(long)]
-        key: String,
+        keyring: Option<PathBuf>,
     },
 
     /// Start MCP (Model Context Protocol) server for AI tool integration
@@ -437,6 +749,13 @@ enum Commands {
         #[arg(short, long, default_value = "text")]
         format: String,
     },
+
+    /// List supported CI providers, their file patterns and parser capabilities
+    ListProviders {
+        /// Output format (text, json)
+        #[arg(short, long, default_value = "text")]
+        format: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -447,6 +766,24 @@ enum KeysCommands {
         #[arg(default_value = ".pipelinex")]
         path: PathBuf,
     },
+
+    /// Replace the keypair with a new one, archiving the old keys with a timestamp
+    Rotate {
+        /// Directory holding private.key and public.key
+        #[arg(default_value = ".pipelinex")]
+        path: PathBuf,
+    },
+
+    /// Import an existing Ed25519 private key (hex seed or PKCS#8 PEM/DER)
+    Import {
+        /// Private key file to import
+        #[arg(long)]
+        private: PathBuf,
+
+        /// Directory to write private.key and public.key to
+        #[arg(default_value = ".pipelinex")]
+        path: PathBuf,
+    },
 }
 
 #[derive(Subcommand)]
@@ -464,6 +801,15 @@ enum PolicyCommands {
         /// Output format (text, json)
         #[arg(short, long, default_value = "text")]
         format: String,
+
+        /// List every evaluated rule with its status, not just violations
+        #[arg(long)]
+        include_passing: bool,
+
+        /// Previous `policy check --format json` output; violations already in
+        /// it are reported as warnings and only new ones fail the check
+        #[arg(long)]
+        baseline: Option<PathBuf>,
     },
 
     /// Generate a starter policy file
@@ -498,16 +844,136 @@ enum PluginCommands {
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
+    init_logging(cli.verbose, cli.quiet);
+    if !color_enabled(cli.no_color) {
+        colored::control::set_override(false);
+    }
+    let _ = MAX_DEPTH.set(cli.max_depth);
+    if let Some(provider) = cli.provider {
+        let rule = rule_for_provider(&provider)?;
+        let _ = PROVIDER.set(rule.provider.to_string());
+    }
+    if let Some(render) = cli.render {
+        let _ = RENDER.set(render);
+    }
+    if let Some(path) = cli.estimates {
+        let table = pipelinex_core::estimation::EstimateTable::from_file(&path)?;
+        let _ = pipelinex_core::estimation::install(table);
+    }
+    let config_path = cli
+        .config
+        .or_else(|| Some(PathBuf::from(DEFAULT_CONFIG_PATH)).filter(|p| p.is_file()));
+    if let Some(weights) = config_path
+        .as_deref()
+        .map(load_health_weights)
+        .transpose()?
+        .flatten()
+    {
+        let _ = HEALTH_WEIGHTS.set(weights);
+    }
+    if let Some(options) = config_path
+        .as_deref()
+        .map(load_sarif_options)
+        .transpose()?
+        .flatten()
+    {
+        let _ = SARIF_OPTIONS.set(options);
+    }
 
     match cli.command {
         Commands::Analyze {
             path,
             format,
+            sort,
             offline: _offline,
             redact,
+            redaction_policy,
             sign,
-        } => cmd_analyze(&path, &format, redact, sign.as_deref()),
-        Commands::Optimize { path, output, diff } => cmd_optimize(&path, output.as_deref(), diff),
+            event,
+            git_ref,
+            timings_csv,
+            dedup,
+            group_by,
+            only,
+            skip,
+            include_security,
+            explain,
+            include_policy,
+            baseline_save,
+            baseline,
+            git_blame,
+        } => {
+            let skip = parse_pass_ids(&skip)?;
+            let mut passes = select_passes(&parse_pass_ids(&only)?, &skip);
+            if include_security
+                && !skip.contains(&AnalyzerPass::Security)
+                && !passes.contains(&AnalyzerPass::Security)
+            {
+                passes.push(AnalyzerPass::Security);
+            }
+            let policy = include_policy
+                .map(|policy_path| {
+                    pipelinex_core::load_policy(&policy_path).with_context(|| {
+                        format!("Failed to load policy from '{}'", policy_path.display())
+                    })
+                })
+                .transpose()?;
+            let redaction = match redaction_policy {
+                Some(path) => Some(pipelinex_core::redact::load_redaction_policy(&path)?),
+                None if redact => Some(RedactionPolicy::default()),
+                None => None,
+            };
+            let group_by = group_by
+                .map(|id| {
+                    GroupBy::from_id(&id).ok_or_else(|| {
+                        let known: Vec<_> = GroupBy::ALL.iter().map(|g| g.id()).collect();
+                        anyhow::anyhow!(
+                            "Unknown --group-by '{}'. Expected one of: {}",
+                            id,
+                            known.join(", ")
+                        )
+                    })
+                })
+                .transpose()?;
+            let context = event.map(|e| EvaluationContext::new(&e, git_ref.as_deref()));
+            let model: Box<dyn DurationModel> = match timings_csv {
+                Some(csv) => Box::new(HistoricalModel::from_csv_file(&csv)?),
+                None => Box::new(HeuristicModel),
+            };
+            cmd_analyze(
+                &path,
+                &format,
+                redaction.as_ref(),
+                sign.as_deref(),
+                context.as_ref(),
+                model.as_ref(),
+                dedup,
+                group_by,
+                &passes,
+                policy.as_ref(),
+                explain,
+                baseline_save.as_deref(),
+                baseline.as_deref(),
+                sort,
+                git_blame,
+            )
+        }
+        Commands::Optimize {
+            path,
+            output,
+            diff,
+            select,
+            interactive,
+        } => {
+            let selection = if interactive {
+                TransformChoice::Interactive
+            } else if select.is_empty() {
+                TransformChoice::All
+            } else {
+                TransformChoice::Only(parse_transform_ids(&select)?)
+            };
+            cmd_optimize(&path, output.as_deref(), diff, selection)
+        }
         Commands::Diff { path } => cmd_diff(&path),
         Commands::Apply {
             path,
@@ -515,18 +981,40 @@ async fn main() -> Result<()> {
             base,
             token,
             no_pr,
-        } => cmd_apply(&path, repo.as_deref(), &base, token, no_pr).await,
+            timeout,
+        } => {
+            let timeout = std::time::Duration::from_secs(timeout);
+            cmd_apply(&path, repo.as_deref(), &base, token, no_pr, timeout).await
+        }
         Commands::Cost {
             path,
             runs_per_month,
             team_size,
             hourly_rate,
-        } => cmd_cost(&path, runs_per_month, team_size, hourly_rate),
+            compare_providers,
+            pricing,
+            breakdown,
+            format,
+            from_schedule,
+        } => {
+            if format != "text" && !breakdown {
+                anyhow::bail!("--format is only supported with --breakdown");
+            }
+            if compare_providers {
+                cmd_cost_compare(&path, runs_per_month, from_schedule, pricing.as_deref())
+            } else if breakdown {
+                cmd_cost_breakdown(&path, runs_per_month, from_schedule, &format)
+            } else {
+                cmd_cost(&path, runs_per_month, from_schedule, team_size, hourly_rate)
+            }
+        }
         Commands::Graph {
             path,
             format,
             output,
-        } => cmd_graph(&path, &format, output.as_deref()),
+            focus,
+            depth,
+        } => cmd_graph(&path, &format, output.as_deref(), focus.as_deref(), depth),
         Commands::Simulate {
             path,
             runs,
@@ -534,7 +1022,49 @@ async fn main() -> Result<()> {
             format,
             top_jobs,
             no_progress,
-        } => cmd_simulate(&path, runs, variance, &format, top_jobs, no_progress),
+            compare_optimized,
+            compare_file,
+            buckets,
+            bar_width,
+            unicode,
+            failure_rate,
+            default_failure_rate,
+            failure_history,
+            retries,
+            convergence,
+        } => {
+            let histogram = HistogramOptions {
+                buckets: buckets as usize,
+                bar_width,
+                unicode,
+            };
+            let failures = failure_model(
+                &failure_rate,
+                default_failure_rate,
+                failure_history.as_deref(),
+                retries,
+            )?;
+            if compare_optimized {
+                cmd_simulate_compare(&path, runs, variance, &format)
+            } else if convergence {
+                cmd_simulate_convergence(&path, runs, variance, &format, &failures)
+            } else if let Some(previous) = compare_file {
+                cmd_simulate_shift(
+                    &path, &previous, runs, variance, &format, &histogram, &failures,
+                )
+            } else {
+                cmd_simulate(
+                    &path,
+                    runs,
+                    variance,
+                    &format,
+                    top_jobs,
+                    no_progress,
+                    &histogram,
+                    &failures,
+                )
+            }
+        }
         Commands::Docker {
             path,
             optimize,
@@ -545,13 +1075,31 @@ async fn main() -> Result<()> {
             head,
             repo,
             format,
-        } => cmd_select_tests(&base, &head, repo.as_deref(), &format),
+            test_map,
+            deps,
+        } => cmd_select_tests(
+            &base,
+            &head,
+            repo.as_deref(),
+            &format,
+            test_map.as_deref(),
+            deps,
+        ),
         Commands::Flaky {
             paths,
             min_runs,
             threshold,
             format,
-        } => cmd_flaky(&paths, min_runs, threshold, &format),
+            emit_quarantine,
+            trend,
+        } => cmd_flaky(
+            &paths,
+            min_runs,
+            threshold,
+            &format,
+            emit_quarantine.as_deref(),
+            trend,
+        ),
         Commands::History {
             repo,
             workflow,
@@ -564,7 +1112,8 @@ async fn main() -> Result<()> {
             to,
             output,
             format,
-        } => cmd_migrate(&path, &to, output.as_deref(), &format),
+            validate,
+        } => cmd_migrate(&path, &to, output.as_deref(), &format, validate),
         Commands::MultiRepo { path, format } => cmd_multi_repo(&path, &format),
         Commands::RightSize { path, format } => cmd_right_size(&path, &format),
         Commands::Plugins { command } => cmd_plugins(command),
@@ -579,19 +1128,69 @@ async fn main() -> Result<()> {
             file_b,
             format,
         } => cmd_compare(&file_a, &file_b, &format),
-        Commands::Watch { path, format } => cmd_watch(&path, &format),
-        Commands::Lint { path, format } => cmd_lint(&path, &format),
+        Commands::Watch {
+            path,
+            format,
+            serve,
+            port,
+        } => cmd_watch(&path, &format, serve.then_some(port)),
+        Commands::Lint {
+            path,
+            format,
+            fix,
+            dry_run,
+        } => {
+            if fix {
+                cmd_lint_fix(&path, dry_run)
+            } else {
+                cmd_lint(&path, &format)
+            }
+        }
         Commands::Security { path, format } => cmd_security(&path, &format),
         Commands::Policy { command } => cmd_policy(command),
         Commands::Monorepo {
             path,
             depth,
             format,
-        } => cmd_monorepo_discover(&path, depth, &format),
+            profile_output,
+            sample,
+            seed,
+        } => {
+            let mut timer = SpanTimer::new();
+            let sample = sample.map(|size| (size, seed));
+            let result = cmd_monorepo_discover(&path, depth, &format, sample, &mut timer);
+            if let Some(profile_path) = profile_output {
+                std::fs::write(&profile_path, timer.to_folded()).with_context(|| {
+                    format!("Failed to write profile to '{}'", profile_path.display())
+                })?;
+            }
+            result
+        }
         Commands::Sbom { path, output } => cmd_sbom(&path, output.as_deref()),
-        Commands::Badge { path, format } => cmd_badge(&path, &format),
+        Commands::Badge {
+            path,
+            format,
+            savings,
+            runs_per_month,
+            team_size,
+            hourly_rate,
+        } => {
+            let savings = savings.then_some((runs_per_month, team_size, hourly_rate));
+            cmd_badge(&path, &format, savings)
+        }
+        Commands::Summary {
+            path,
+            format,
+            runs_per_month,
+            team_size,
+            hourly_rate,
+        } => cmd_summary(&path, &format, runs_per_month, team_size, hourly_rate),
         Commands::Keys { command } => cmd_keys(command),
-        Commands::Verify { report, key } => cmd_verify(&report, &key),
+        Commands::Verify {
+            report,
+            key,
+            keyring,
+        } => cmd_verify(&report, key.as_deref(), keyring.as_deref()),
         Commands::McpServer => {
             pipelinex_core::mcp::run_stdio_server()?;
             Ok(())
@@ -606,98 +1205,58 @@ async fn main() -> Result<()> {
             modify,
             format,
         } => cmd_whatif(&path, &modify, &format),
+        Commands::ListProviders { format } => cmd_list_providers(&format),
     }
 }
 
-/// Detect CI provider from file path and parse accordingly.
-fn parse_pipeline(path: &std::path::Path) -> Result<pipelinex_core::PipelineDag> {
-    let filename = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-    let path_str = path.to_string_lossy().to_lowercase();
-
-    if filename == ".gitlab-ci.yml" || filename == ".gitlab-ci.yaml" || path_str.contains("gitlab")
-    {
-        GitLabCIParser::parse_file(path)
-            .with_context(|| format!("Failed to parse GitLab CI file: {}", path.display()))
-    } else if filename == "Jenkinsfile"
-        || filename.ends_with(".jenkinsfile")
-        || filename.ends_with(".groovy")
-        || path_str.contains("jenkins")
-    {
-        JenkinsParser::parse_file(path)
-            .with_context(|| format!("Failed to parse Jenkinsfile: {}", path.display()))
-    } else if path_str.contains("circleci") || path_str.contains(".circleci") {
-        CircleCIParser::parse_file(path)
-            .with_context(|| format!("Failed to parse CircleCI config: {}", path.display()))
-    } else if filename == "azure-pipelines.yml"
-        || filename == "azure-pipelines.yaml"
-        || path_str.contains("azure-pipelines")
-    {
-        AzurePipelinesParser::parse_file(path)
-            .with_context(|| format!("Failed to parse Azure Pipelines file: {}", path.display()))
-    } else if filename == "codepipeline.json"
-        || filename == "codepipeline.yaml"
-        || filename == "codepipeline.yml"
-        || filename == "pipeline.json" && path_str.contains("codepipeline")
-        || path_str.contains("aws-codepipeline")
-    {
-        AwsCodePipelineParser::parse_file(path)
-            .with_context(|| format!("Failed to parse AWS CodePipeline file: {}", path.display()))
-    } else if filename == "bitbucket-pipelines.yml"
-        || filename == "bitbucket-pipelines.yaml"
-        || path_str.contains("bitbucket")
-    {
-        BitbucketParser::parse_file(path)
-            .with_context(|| format!("Failed to parse Bitbucket Pipelines: {}", path.display()))
-    } else if (filename == "pipeline.yml" || filename == "pipeline.yaml")
-        && path_str.contains(".buildkite")
-        || path_str.contains("buildkite")
-    {
-        BuildkiteParser::parse_file(path)
-            .with_context(|| format!("Failed to parse Buildkite pipeline: {}", path.display()))
-    } else if filename == ".drone.yml"
-        || filename == ".drone.yaml"
-        || filename == ".woodpecker.yml"
-        || filename == ".woodpecker.yaml"
-        || path_str.contains("drone")
-        || path_str.contains("woodpecker")
-    {
-        DroneParser::parse_file(path)
-            .with_context(|| format!("Failed to parse Drone CI file: {}", path.display()))
-    } else if path_has_token(&path_str, "tekton") || is_tekton_content(path) {
-        TektonParser::parse_file(path)
-            .with_context(|| format!("Failed to parse Tekton file: {}", path.display()))
-    } else if path_has_token(&path_str, "argo")
-        || path_has_token(&path_str, "argoproj")
-        || is_argo_content(path)
-    {
-        ArgoWorkflowsParser::parse_file(path)
-            .with_context(|| format!("Failed to parse Argo Workflows file: {}", path.display()))
-    } else {
-        // Default to GitHub Actions
-        GitHubActionsParser::parse_file(path)
-            .with_context(|| format!("Failed to parse GitHub Actions file: {}", path.display()))
-    }
+/// Whether display output may use ANSI colors: not with `--no-color` or
+/// `NO_COLOR`, and not when piped unless `CLICOLOR_FORCE` asks for it.
+fn color_enabled(no_color_flag: bool) -> bool {
+    !no_color_flag
+        && std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty())
+        && (std::io::stdout().is_terminal()
+            || std::env::var_os("CLICOLOR_FORCE").is_some_and(|v| !v.is_empty() && v != "0"))
 }
 
-/// Check if file content looks like a Tekton resource.
-fn is_tekton_content(path: &Path) -> bool {
-    std::fs::read_to_string(path)
-        .map(|c| c.contains("tekton.dev") || (c.contains("kind: Pipeline") && c.contains("tasks:")))
-        .unwrap_or(false)
-}
+/// Route status messages through `log`, always to stderr so stdout carries only
+/// command output (JSON, SARIF, CSV, ...). `RUST_LOG` overrides the flags.
+fn init_logging(verbose: u8, quiet: bool) {
+    let level = match (quiet, verbose) {
+        (true, _) => log::LevelFilter::Error,
+        (false, 0) => log::LevelFilter::Info,
+        (false, 1) => log::LevelFilter::Debug,
+        (false, _) => log::LevelFilter::Trace,
+    };
 
-/// Check if file content looks like an Argo Workflows resource.
-fn is_argo_content(path: &Path) -> bool {
-    std::fs::read_to_string(path)
-        .map(|c| {
-            c.contains("argoproj.io") || (c.contains("kind: Workflow") && c.contains("entrypoint:"))
+    env_logger::Builder::new()
+        .filter_level(level)
+        .parse_default_env()
+        .target(env_logger::Target::Stderr)
+        .format(|buf, record| match record.level() {
+            log::Level::Info => writeln!(buf, "{}", record.args()),
+            level => writeln!(buf, "{}: {}", level.as_str().to_lowercase(), record.args()),
         })
-        .unwrap_or(false)
+        .init();
 }
 
-fn path_has_token(path: &str, token: &str) -> bool {
-    path.split(|c: char| !c.is_ascii_alphanumeric())
-        .any(|part| part.eq_ignore_ascii_case(token))
+/// Detect CI provider from file path and parse accordingly.
+fn parse_pipeline(path: &std::path::Path) -> Result<pipelinex_core::PipelineDag> {
+    if let Some(render) = RENDER.get() {
+        return Ok(parse_rendered_file(
+            path,
+            render,
+            PROVIDER.get().map(String::as_str),
+        )?);
+    }
+    match PROVIDER.get() {
+        Some(provider) => Ok(parse_pipeline_file_as(path, provider)?),
+        None => Ok(parse_pipeline_file(path)?),
+    }
+}
+
+/// Analyze with the configured health score weights, if any.
+fn analyze_dag(dag: &pipelinex_core::PipelineDag) -> pipelinex_core::AnalysisReport {
+    analyzer::analyze_with_weights(dag, HEALTH_WEIGHTS.get())
 }
 
 fn discover_workflow_files(path: &Path) -> Result<Vec<PathBuf>> {
@@ -706,21 +1265,8 @@ fn discover_workflow_files(path: &Path) -> Result<Vec<PathBuf>> {
     }
 
     if path.is_dir() {
-        let pattern = format!("{}/**/*.yml", path.display());
-        let mut files: Vec<PathBuf> = glob::glob(&pattern)
-            .context("Failed to read glob pattern")?
-            .chain(
-                glob::glob(&format!("{}/**/*.yaml", path.display()))
-                    .context("Failed to read glob pattern")?,
-            )
-            .chain(
-                glob::glob(&format!("{}/**/*.json", path.display()))
-                    .context("Failed to read glob pattern")?,
-            )
-            .filter_map(|r| r.ok())
-            .collect();
-        files.sort();
-        return Ok(files);
+        let max_depth = MAX_DEPTH.get().copied().unwrap_or(DEFAULT_WALK_DEPTH);
+        return walk_pipeline_files(path, max_depth);
     }
 
     anyhow::bail!("Path '{}' does not exist", path.display());
@@ -818,7 +1364,43 @@ fn discover_repo_pipeline_files(repo_root: &Path) -> Result<Vec<PathBuf>> {
     Ok(files)
 }
 
-fn cmd_analyze(path: &Path, format: &str, redact: bool, sign_key: Option<&str>) -> Result<()> {
+#[allow(clippy::too_many_arguments)]
+fn cmd_analyze(
+    path: &Path,
+    format: &str,
+    redaction: Option<&RedactionPolicy>,
+    sign_key: Option<&str>,
+    context: Option<&EvaluationContext>,
+    model: &dyn DurationModel,
+    dedup: bool,
+    group_by: Option<GroupBy>,
+    passes: &[AnalyzerPass],
+    policy: Option<&pipelinex_core::policy::PolicyConfig>,
+    explain: bool,
+    baseline_save: Option<&Path>,
+    baseline: Option<&Path>,
+    sort: Option<display::TableSort>,
+    git_blame: bool,
+) -> Result<()> {
+    if sort.is_some() && format != "table" {
+        anyhow::bail!("--sort supports the table format, not '{}'", format);
+    }
+    if explain && format != "text" {
+        anyhow::bail!("--explain supports the text format, not '{}'", format);
+    }
+    if dedup && !matches!(format, "text" | "json") {
+        anyhow::bail!(
+            "--dedup supports the text and json formats, not '{}'",
+            format
+        );
+    }
+    if group_by.is_some() && !matches!(format, "text" | "markdown" | "md") {
+        anyhow::bail!(
+            "--group-by supports the text and markdown formats, not '{}'",
+            format
+        );
+    }
+
     let files = discover_workflow_files(path)?;
 
     if files.is_empty() {
@@ -829,12 +1411,75 @@ fn cmd_analyze(path: &Path, format: &str, redact: bool, sign_key: Option<&str>)
         );
     }
 
+    let mut collected_reports = Vec::new();
+    let mut baseline_reports = Vec::new();
+    let mut policy_failed = false;
+
     for file in &files {
-        let dag = parse_pipeline(file)?;
-        let mut report = analyzer::analyze(&dag);
+        let mut dag = parse_pipeline(file)?;
+        pipelinex_core::duration_model::apply_duration_model(&mut dag, model);
+        if !dag.external_includes.is_empty() && format == "text" {
+            let locations: Vec<&str> = dag
+                .external_includes
+                .iter()
+                .map(|include| include.location.as_str())
+                .collect();
+            println!(
+                "Skipping {} unresolved include(s) whose jobs are not analyzed: {}",
+                locations.len(),
+                locations.join(", ")
+            );
+        }
+
+        if let Some(ctx) = context {
+            let pruned = analyzer::conditions::prune_unreachable_jobs(&dag, ctx);
+            let excluded = analyzer::conditions::excluded_jobs(&dag, &pruned);
+            if !excluded.is_empty() && format == "text" {
+                println!(
+                    "Excluding {} job(s) that do not run on '{}': {}",
+                    excluded.len(),
+                    ctx.event,
+                    excluded.join(", ")
+                );
+            }
+            dag = pruned;
+        }
+
+        let mut report =
+            analyzer::analyze_passes(&dag, HEALTH_WEIGHTS.get(), passes).with_metadata();
+        if let Some(policy) = policy {
+            let policy_report = pipelinex_core::check_policy(&dag, policy);
+            policy_failed |= !policy_report.passed;
+            report.merge_findings(policy_report.violations.iter().map(|v| v.to_finding()));
+        }
+        if git_blame {
+            match pipelinex_core::blame::FileBlame::load(file) {
+                Some(blame) => {
+                    let content = std::fs::read_to_string(file)?;
+                    pipelinex_core::blame::attribute_findings(
+                        &blame,
+                        &content,
+                        &mut report.findings,
+                    );
+                }
+                None => log::warn!(
+                    "'{}' is not tracked in a git repository; skipping --git-blame",
+                    file.display()
+                ),
+            }
+        }
 
-        if redact {
-            report = pipelinex_core::redact::redact_report(&report);
+        if let Some(policy) = redaction {
+            report = pipelinex_core::redact::redact_report_with(&report, policy)?;
+        }
+
+        if baseline_save.is_some() || baseline.is_some() {
+            baseline_reports.push(report.clone());
+        }
+
+        if dedup {
+            collected_reports.push(report);
+            continue;
         }
 
         match format {
@@ -848,8 +1493,21 @@ fn cmd_analyze(path: &Path, format: &str, redact: bool, sign_key: Option<&str>)
                     println!("{}", json);
                 }
             }
+            "jsonl" => {
+                let line = report.to_json_line()?;
+                if let Some(key) = sign_key {
+                    let key_hex = read_key_material(key)?;
+                    let signed = pipelinex_core::sign_report(&line, &key_hex)?;
+                    println!("{}", serde_json::to_string(&signed)?);
+                } else {
+                    println!("{}", line);
+                }
+            }
             "sarif" => {
-                let sarif = pipelinex_core::analyzer::sarif::to_sarif(&report);
+                let sarif = pipelinex_core::analyzer::sarif::to_sarif_with_options(
+                    &report,
+                    SARIF_OPTIONS.get().unwrap_or(&SarifOptions::default()),
+                );
                 let json = serde_json::to_string_pretty(&sarif)?;
                 println!("{}", json);
             }
@@ -859,14 +1517,63 @@ fn cmd_analyze(path: &Path, format: &str, redact: bool, sign_key: Option<&str>)
                 println!("{}", html);
             }
             "markdown" | "md" => {
-                print!("{}", display::format_markdown_report(&report));
+                print!("{}", display::format_markdown_report(&report, group_by));
+            }
+            "github" => {
+                let content = std::fs::read_to_string(file).unwrap_or_default();
+                for line in pipelinex_core::annotations::annotate_findings(
+                    &report.source_file,
+                    &report.findings,
+                    &content,
+                ) {
+                    println!("{}", line);
+                }
             }
+            "csv" | "table" => collected_reports.push(report),
             _ => {
-                display::print_analysis_report(&report);
+                display::print_analysis_report(&report, group_by, explain);
             }
         }
     }
 
+    if dedup {
+        let deduped = analyzer::dedup::dedup_reports(&collected_reports);
+        if format == "json" {
+            let json = serde_json::to_string_pretty(&deduped)?;
+            if let Some(key) = sign_key {
+                let key_hex = read_key_material(key)?;
+                let signed = pipelinex_core::sign_report(&json, &key_hex)?;
+                println!("{}", serde_json::to_string_pretty(&signed)?);
+            } else {
+                println!("{}", json);
+            }
+        } else {
+            display::print_deduped_report(&deduped, explain);
+        }
+    } else if format == "csv" {
+        print!("{}", analyzer::csv_report::to_csv(&collected_reports));
+    } else if format == "table" {
+        print!("{}", display::format_report_table(&collected_reports, sort));
+    }
+
+    if let Some(baseline_path) = baseline_save {
+        let baseline = analyzer::baseline::Baseline::from_reports(&baseline_reports);
+        analyzer::baseline::save_baseline(baseline_path, &baseline)?;
+        log::info!("Baseline written to {}", baseline_path.display());
+    }
+
+    if let Some(baseline_path) = baseline {
+        let drift = analyzer::baseline::load_baseline(baseline_path)?.compare(&baseline_reports);
+        eprint!("{}", display::format_baseline_drift(&drift));
+        if !drift.new.is_empty() {
+            anyhow::bail!("{} new finding(s) since the baseline", drift.new.len());
+        }
+    }
+
+    if policy_failed {
+        anyhow::bail!("Policy check failed");
+    }
+
     Ok(())
 }
 
@@ -886,7 +1593,19 @@ fn read_key_material(key_or_path: &str) -> Result<String> {
     }
 }
 
-fn cmd_optimize(path: &PathBuf, output: Option<&std::path::Path>, show_diff: bool) -> Result<()> {
+/// Which optimizer transforms `optimize` should apply.
+enum TransformChoice {
+    All,
+    Only(Vec<Transform>),
+    Interactive,
+}
+
+fn cmd_optimize(
+    path: &PathBuf,
+    output: Option<&std::path::Path>,
+    show_diff: bool,
+    choice: TransformChoice,
+) -> Result<()> {
     if !path.is_file() {
         anyhow::bail!(
             "'{}' is not a file. Optimize requires a single workflow file.",
@@ -895,8 +1614,13 @@ fn cmd_optimize(path: &PathBuf, output: Option<&std::path::Path>, show_diff: boo
     }
 
     let dag = parse_pipeline(path)?;
-    let report = analyzer::analyze(&dag);
-    let optimized = Optimizer::optimize(path, &report)?;
+    let report = analyze_dag(&dag);
+    let transforms = match choice {
+        TransformChoice::All => Transform::ALL.to_vec(),
+        TransformChoice::Only(transforms) => transforms,
+        TransformChoice::Interactive => prompt_transforms(&report)?,
+    };
+    let optimized = Optimizer::optimize_selected(path, &report, &transforms)?;
 
     if show_diff {
         let original = std::fs::read_to_string(path)?;
@@ -917,8 +1641,53 @@ fn cmd_optimize(path: &PathBuf, output: Option<&std::path::Path>, show_diff: boo
     Ok(())
 }
 
+/// Let the user toggle each applicable transform on stderr/stdin.
+fn prompt_transforms(report: &pipelinex_core::AnalysisReport) -> Result<Vec<Transform>> {
+    if !std::io::stdin().is_terminal() || !std::io::stderr().is_terminal() {
+        anyhow::bail!(
+            "--interactive needs a terminal. Use --select instead, e.g. `--select cache,concurrency`."
+        );
+    }
+
+    let mut selection = TransformSelection::new(available_transforms(report));
+    if selection.options().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let stdin = std::io::stdin();
+    loop {
+        eprintln!();
+        for (i, option) in selection.options().iter().enumerate() {
+            let mark = if selection.is_selected(i) { "x" } else { " " };
+            eprintln!(
+                "  [{}] {}. {} — {} (~{} saved)",
+                mark,
+                i + 1,
+                option.transform.id(),
+                option.transform.description(),
+                format_duration(option.estimated_savings_secs)
+            );
+            for title in &option.findings {
+                eprintln!("         {}", title);
+            }
+        }
+        eprint!("\nToggle numbers/ids, `all` or `none`; press Enter to apply: ");
+        std::io::stderr().flush()?;
+
+        let mut line = String::new();
+        if stdin.read_line(&mut line)? == 0 {
+            anyhow::bail!("Selection aborted");
+        }
+        match selection.handle_input(&line) {
+            Ok(true) => return Ok(selection.selected()),
+            Ok(false) => {}
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+}
+
 fn cmd_diff(path: &PathBuf) -> Result<()> {
-    cmd_optimize(path, None, true)
+    cmd_optimize(path, None, true, TransformChoice::All)
 }
 
 async fn cmd_apply(
@@ -927,15 +1696,12 @@ async fn cmd_apply(
     base_branch: &str,
     token: Option<String>,
     no_pr: bool,
+    timeout: std::time::Duration,
 ) -> Result<()> {
-    use std::process::Command;
-
     // Verify we're in a git repository
-    let git_check = Command::new("git")
-        .args(["rev-parse", "--git-dir"])
-        .output();
+    let git_check = git_output(&["rev-parse", "--git-dir"], timeout)?;
 
-    if git_check.is_err() || !git_check.unwrap().status.success() {
+    if !git_check.status.success() {
         anyhow::bail!(
             "Not in a git repository. Please run this command from within a git repository."
         );
@@ -951,9 +1717,7 @@ async fn cmd_apply(
         r.to_string()
     } else {
         // Try to detect from git remote
-        let output = Command::new("git")
-            .args(["remote", "get-url", "origin"])
-            .output()
+        let output = git_output(&["remote", "get-url", "origin"], timeout)
             .context("Failed to get git remote origin")?;
 
         if !output.status.success() {
@@ -979,14 +1743,14 @@ async fn cmd_apply(
         repo
     };
 
-    println!("🔍 Analyzing pipeline: {}", path.display());
+    log::info!("🔍 Analyzing pipeline: {}", path.display());
 
     // Parse and optimize the pipeline
     let dag = parse_pipeline(path)?;
-    let report = analyzer::analyze(&dag);
+    let report = analyze_dag(&dag);
 
     if report.findings.is_empty() {
-        println!("✅ No optimization opportunities found!");
+        log::info!("✅ No optimization opportunities found!");
         return Ok(());
     }
 
@@ -999,43 +1763,33 @@ async fn cmd_apply(
         .unwrap_or("config");
     let branch_name = format!("pipelinex-optimize-{}", filename);
 
-    println!("🌿 Creating branch: {}", branch_name);
+    log::info!("🌿 Creating branch: {}", branch_name);
 
     // Check if branch already exists
-    let branch_exists = Command::new("git")
-        .args(["rev-parse", "--verify", &branch_name])
-        .output()
-        .ok()
-        .map(|o| o.status.success())
-        .unwrap_or(false);
+    let branch_exists = git_output(&["rev-parse", "--verify", &branch_name], timeout)?
+        .status
+        .success();
 
     if branch_exists {
-        println!(
+        log::warn!(
             "⚠️  Branch {} already exists. Switching to it...",
             branch_name
         );
-        Command::new("git")
-            .args(["checkout", &branch_name])
-            .status()
+        run_git(&["checkout", &branch_name], timeout)
             .context("Failed to checkout existing branch")?;
     } else {
         // Create and checkout new branch
-        Command::new("git")
-            .args(["checkout", "-b", &branch_name])
-            .status()
+        run_git(&["checkout", "-b", &branch_name], timeout)
             .context("Failed to create new branch")?;
     }
 
     // Write optimized config
-    println!("📝 Writing optimized configuration...");
+    log::info!("📝 Writing optimized configuration...");
     std::fs::write(path, &optimized_content).context("Failed to write optimized configuration")?;
 
     // Commit changes
-    println!("💾 Committing changes...");
-    Command::new("git")
-        .args(["add", path.to_str().unwrap()])
-        .status()
-        .context("Failed to git add")?;
+    log::info!("💾 Committing changes...");
+    run_git(&["add", path.to_str().unwrap()], timeout).context("Failed to git add")?;
 
     let commit_msg = format!(
         "chore: optimize {} with PipelineX\n\n\
@@ -1052,17 +1806,11 @@ async fn cmd_apply(
         report.optimized_duration_secs
     );
 
-    Command::new("git")
-        .args(["commit", "-m", &commit_msg])
-        .status()
-        .context("Failed to commit changes")?;
+    run_git(&["commit", "-m", &commit_msg], timeout).context("Failed to commit changes")?;
 
     // Push to remote
-    println!("⬆️  Pushing to remote...");
-    Command::new("git")
-        .args(["push", "-u", "origin", &branch_name])
-        .status()
-        .context("Failed to push branch")?;
+    log::info!("⬆️  Pushing to remote...");
+    run_git(&["push", "-u", "origin", &branch_name], timeout).context("Failed to push branch")?;
 
     if no_pr {
         println!("✅ Branch created and pushed. Run with --no-pr=false to create a PR.");
@@ -1070,7 +1818,7 @@ async fn cmd_apply(
     }
 
     // Create pull request
-    println!("🔀 Creating pull request...");
+    log::info!("🔀 Creating pull request...");
 
     let parts: Vec<&str> = repo_name.split('/').collect();
     if parts.len() != 2 {
@@ -1081,7 +1829,7 @@ async fn cmd_apply(
     }
     let (owner, repo) = (parts[0], parts[1]);
 
-    let client = GitHubClient::new(Some(github_token))?;
+    let client = GitHubClient::with_timeout(Some(github_token), timeout)?;
 
     let pr_title = format!("⚡ Optimize {} with PipelineX", filename);
     let pr_body = format!(
@@ -1123,7 +1871,83 @@ async fn cmd_apply(
     Ok(())
 }
 
-fn cmd_cost(path: &Path, runs_per_month: u32, team_size: u32, hourly_rate: f64) -> Result<()> {
+/// Run `git` with inherited stdio, killing it after `timeout`.
+fn run_git(args: &[&str], timeout: std::time::Duration) -> Result<std::process::ExitStatus> {
+    let mut child = std::process::Command::new("git")
+        .args(args)
+        .spawn()
+        .context("Failed to run git")?;
+    pipelinex_core::process::wait_with_timeout(&mut child, timeout)
+        .map_err(|error| git_error(args, error, timeout))
+}
+
+/// Run `git` and capture its output, killing it after `timeout`.
+fn git_output(args: &[&str], timeout: std::time::Duration) -> Result<std::process::Output> {
+    pipelinex_core::process::output_with_timeout(
+        std::process::Command::new("git").args(args),
+        timeout,
+    )
+    .map_err(|error| git_error(args, error, timeout))
+}
+
+fn git_error(args: &[&str], error: std::io::Error, timeout: std::time::Duration) -> anyhow::Error {
+    if error.kind() == std::io::ErrorKind::TimedOut {
+        anyhow::anyhow!(
+            "`git {}` did not finish within {}s (raise the limit with --timeout)",
+            args.first().copied().unwrap_or_default(),
+            timeout.as_secs()
+        )
+    } else {
+        anyhow::Error::new(error).context("Failed to run git")
+    }
+}
+
+/// Runs per month to cost `dag` at.
+///
+/// With `--from-schedule`, a workflow with `on.schedule` crons runs at their
+/// frequency, plus `runs_per_month` if it has other triggers as well.
+fn effective_runs_per_month(
+    file: &Path,
+    dag: &pipelinex_core::PipelineDag,
+    runs_per_month: u32,
+    from_schedule: bool,
+) -> Result<u32> {
+    if !from_schedule {
+        return Ok(runs_per_month);
+    }
+    let Some(scheduled) = pipelinex_core::cost::scheduled_runs_per_month(dag)
+        .with_context(|| format!("Invalid schedule in '{}'", file.display()))?
+    else {
+        return Ok(runs_per_month);
+    };
+
+    let scheduled = scheduled.round().max(1.0) as u32;
+    let has_other_triggers = dag.triggers.iter().any(|t| t.event != "schedule");
+    let total = if has_other_triggers {
+        scheduled.saturating_add(runs_per_month)
+    } else {
+        scheduled
+    };
+    log::info!(
+        "{}: {} scheduled runs/month from cron{}",
+        file.display(),
+        scheduled,
+        if has_other_triggers {
+            format!(" + {} from other triggers", runs_per_month)
+        } else {
+            String::new()
+        }
+    );
+    Ok(total)
+}
+
+fn cmd_cost(
+    path: &Path,
+    runs_per_month: u32,
+    from_schedule: bool,
+    team_size: u32,
+    hourly_rate: f64,
+) -> Result<()> {
     let files = discover_workflow_files(path)?;
 
     if files.is_empty() {
@@ -1132,7 +1956,8 @@ fn cmd_cost(path: &Path, runs_per_month: u32, team_size: u32, hourly_rate: f64)
 
     for file in &files {
         let dag = parse_pipeline(file)?;
-        let report = analyzer::analyze(&dag);
+        let runs_per_month = effective_runs_per_month(file, &dag, runs_per_month, from_schedule)?;
+        let report = analyze_dag(&dag);
 
         let runner_type = dag
             .graph
@@ -1156,16 +1981,83 @@ fn cmd_cost(path: &Path, runs_per_month: u32, team_size: u32, hourly_rate: f64)
     Ok(())
 }
 
-fn cmd_graph(path: &Path, format: &str, output: Option<&std::path::Path>) -> Result<()> {
+fn cmd_cost_breakdown(
+    path: &Path,
+    runs_per_month: u32,
+    from_schedule: bool,
+    format: &str,
+) -> Result<()> {
+    let files = discover_workflow_files(path)?;
+
+    if files.is_empty() {
+        anyhow::bail!("No workflow files found at '{}'", path.display());
+    }
+
+    let pricing = pipelinex_core::cost::RunnerPricing::default();
+    for file in &files {
+        let dag = parse_pipeline(file)?;
+        let runs_per_month = effective_runs_per_month(file, &dag, runs_per_month, from_schedule)?;
+        let breakdown = pipelinex_core::cost::cost_breakdown(&dag, runs_per_month, &pricing);
+
+        match format {
+            "json" => println!("{}", serde_json::to_string_pretty(&breakdown)?),
+            _ => display::print_cost_breakdown(file, &breakdown, runs_per_month),
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_cost_compare(
+    path: &Path,
+    runs_per_month: u32,
+    from_schedule: bool,
+    pricing: Option<&Path>,
+) -> Result<()> {
+    let files = discover_workflow_files(path)?;
+
+    if files.is_empty() {
+        anyhow::bail!("No workflow files found at '{}'", path.display());
+    }
+
+    let profiles = match pricing {
+        Some(pricing) => pipelinex_core::cost::load_pricing_profiles(pricing)?,
+        None => pipelinex_core::cost::ProviderPricingProfile::defaults(),
+    };
+    if profiles.is_empty() {
+        anyhow::bail!("No pricing profiles to compare");
+    }
+
+    for file in &files {
+        let dag = parse_pipeline(file)?;
+        let runs_per_month = effective_runs_per_month(file, &dag, runs_per_month, from_schedule)?;
+        let rows = pipelinex_core::cost::compare_provider_costs(&dag, runs_per_month, &profiles);
+        display::print_provider_cost_comparison(file, &rows, runs_per_month);
+    }
+
+    Ok(())
+}
+
+fn cmd_graph(
+    path: &Path,
+    format: &str,
+    output: Option<&std::path::Path>,
+    focus: Option<&str>,
+    depth: usize,
+) -> Result<()> {
     if !path.is_file() {
         anyhow::bail!("'{}' is not a file.", path.display());
     }
 
-    let dag = parse_pipeline(path)?;
+    let mut dag = parse_pipeline(path)?;
+    if let Some(job) = focus {
+        dag = pipelinex_core::graph::focus_subgraph(&dag, job, depth)?;
+    }
 
     let content = match format {
         "dot" | "graphviz" => pipelinex_core::graph::to_dot(&dag),
         "ascii" | "text" => pipelinex_core::graph::to_ascii(&dag),
+        "critical-path" => pipelinex_core::graph::to_critical_path_text(&dag),
         _ => pipelinex_core::graph::to_mermaid(&dag),
     };
 
@@ -1182,6 +2074,7 @@ fn cmd_graph(path: &Path, format: &str, output: Option<&std::path::Path>) -> Res
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn cmd_simulate(
     path: &Path,
     runs: usize,
@@ -1189,6 +2082,8 @@ fn cmd_simulate(
     format: &str,
     top_jobs: usize,
     no_progress: bool,
+    histogram: &HistogramOptions,
+    failures: &FailureModel,
 ) -> Result<()> {
     if !path.is_file() {
         anyhow::bail!("'{}' is not a file.", path.display());
@@ -1196,8 +2091,11 @@ fn cmd_simulate(
 
     let dag = parse_pipeline(path)?;
     let start = Instant::now();
-    let show_progress =
-        format != "json" && !no_progress && runs >= 5000 && std::io::stderr().is_terminal();
+    let show_progress = !matches!(format, "json" | "html")
+        && !no_progress
+        && runs >= 5000
+        && std::io::stderr().is_terminal()
+        && log::log_enabled!(log::Level::Info);
 
     let result = if show_progress {
         eprintln!(
@@ -1206,18 +2104,20 @@ fn cmd_simulate(
         );
         let mut last_pct = 0usize;
         let mut stderr = std::io::stderr();
-        let result = pipelinex_core::simulator::simulate_with_progress(
+        let result = pipelinex_core::simulator::simulate_with_failures(
             &dag,
             runs,
             variance,
-            |completed, total| {
+            histogram,
+            failures,
+            Some(|completed: usize, total: usize| {
                 let pct = completed.saturating_mul(100) / total.max(1);
                 if pct != last_pct {
                     last_pct = pct;
                     eprint!("\r  Progress: {:>3}% ({}/{})", pct, completed, total);
                     let _ = stderr.flush();
                 }
-            },
+            }),
         );
         eprintln!(
             "\r  Completed in {:.2}s{}",
@@ -1226,22 +2126,181 @@ fn cmd_simulate(
         );
         result
     } else {
-        pipelinex_core::simulator::simulate(&dag, runs, variance)
+        pipelinex_core::simulator::simulate_with_failures(
+            &dag,
+            runs,
+            variance,
+            histogram,
+            failures,
+            None::<fn(usize, usize)>,
+        )
     };
 
     match format {
         "json" => {
-            let json = serde_json::to_string_pretty(&result)?;
-            println!("{}", json);
-        }
-        _ => {
-            display::print_simulation_report(&dag.name, &result, top_jobs.max(1));
-            if result.job_stats.len() > top_jobs.max(1) {
-                println!(
-                    "Tip: use --format json for complete per-job stats or increase --top-jobs."
-                );
-            }
+            let json = serde_json::to_string_pretty(&result)?;
+            println!("{}", json);
+        }
+        "html" => {
+            println!(
+                "{}",
+                pipelinex_core::simulator::generate_simulation_html(&dag.name, &result)
+            );
+        }
+        _ => {
+            display::print_simulation_report(
+                &dag.name,
+                &result,
+                top_jobs.max(1),
+                histogram.unicode,
+            );
+            if result.job_stats.len() > top_jobs.max(1) {
+                println!(
+                    "Tip: use --format json for complete per-job stats or increase --top-jobs."
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_simulate_compare(path: &Path, runs: usize, variance: f64, format: &str) -> Result<()> {
+    if !path.is_file() {
+        anyhow::bail!("'{}' is not a file.", path.display());
+    }
+
+    let dag = parse_pipeline(path)?;
+    let content = std::fs::read_to_string(path)?;
+    let optimized = Optimizer::optimize_dag(&content, &dag)?;
+    let comparison = pipelinex_core::simulator::compare(&dag, &optimized, runs, variance);
+
+    match format {
+        "json" => {
+            let json = serde_json::to_string_pretty(&comparison)?;
+            println!("{}", json);
+        }
+        _ => display::print_simulation_comparison(&dag.name, &comparison),
+    }
+
+    Ok(())
+}
+
+/// Build the simulator's failure model from `--failure-history`, then
+/// `--failure-rate JOB=P` overrides and `--default-failure-rate`.
+fn failure_model(
+    rates: &[String],
+    default_rate: Option<f64>,
+    history: Option<&Path>,
+    retries: u32,
+) -> Result<FailureModel> {
+    let in_range = |p: f64, flag: &str| -> Result<f64> {
+        if !(0.0..=1.0).contains(&p) {
+            anyhow::bail!("{} {} must be between 0 and 1", flag, p);
+        }
+        Ok(p)
+    };
+    let probability = |value: &str, flag: &str| -> Result<f64> {
+        let p: f64 = value
+            .trim()
+            .parse()
+            .with_context(|| format!("{} '{}' is not a number", flag, value))?;
+        in_range(p, flag)
+    };
+
+    let mut model = match history {
+        Some(path) => {
+            let content = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let stats: pipelinex_core::providers::github_api::PipelineStatistics =
+                serde_json::from_str(&content).with_context(|| {
+                    format!("{} is not a `history --format json` result", path.display())
+                })?;
+            FailureModel::from_history(&stats)
+        }
+        None => FailureModel::default(),
+    };
+    for rate in rates {
+        let (job, value) = rate
+            .split_once('=')
+            .with_context(|| format!("--failure-rate '{}' must be JOB=P", rate))?;
+        model.job_failure_rates.insert(
+            job.trim().to_string(),
+            probability(value, "--failure-rate")?,
+        );
+    }
+    if let Some(rate) = default_rate {
+        model.default_failure_rate = in_range(rate, "--default-failure-rate")?;
+    }
+    model.retries = retries;
+    Ok(model)
+}
+
+fn cmd_simulate_convergence(
+    path: &Path,
+    runs: usize,
+    variance: f64,
+    format: &str,
+    failures: &FailureModel,
+) -> Result<()> {
+    if !path.is_file() {
+        anyhow::bail!("'{}' is not a file.", path.display());
+    }
+
+    let dag = parse_pipeline(path)?;
+    let report = pipelinex_core::simulator::convergence(&dag, runs, variance, failures);
+
+    match format {
+        "json" => println!("{}", serde_json::to_string_pretty(&report)?),
+        _ => display::print_convergence_report(&dag.name, &report),
+    }
+    Ok(())
+}
+
+fn cmd_simulate_shift(
+    path: &Path,
+    previous_path: &Path,
+    runs: usize,
+    variance: f64,
+    format: &str,
+    histogram: &HistogramOptions,
+    failures: &FailureModel,
+) -> Result<()> {
+    if !path.is_file() {
+        anyhow::bail!("'{}' is not a file.", path.display());
+    }
+
+    let content = std::fs::read_to_string(previous_path)
+        .with_context(|| format!("Failed to read {}", previous_path.display()))?;
+    let previous: pipelinex_core::simulator::SimulationResult = serde_json::from_str(&content)
+        .with_context(|| {
+            format!(
+                "{} is not a `simulate --format json` result",
+                previous_path.display()
+            )
+        })?;
+
+    let dag = parse_pipeline(path)?;
+    let current = pipelinex_core::simulator::simulate_with_failures(
+        &dag,
+        runs,
+        variance,
+        histogram,
+        failures,
+        None::<fn(usize, usize)>,
+    );
+    let shift = pipelinex_core::simulator::compare_results(&previous, &current);
+
+    match format {
+        "json" => {
+            let output = serde_json::json!({
+                "previous": previous,
+                "current": current,
+                "shift": shift,
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
         }
+        _ => display::print_distribution_shift(&dag.name, &previous, &current, &shift),
     }
 
     Ok(())
@@ -1279,61 +2338,30 @@ fn cmd_select_tests(
     head: &str,
     repo: Option<&std::path::Path>,
     format: &str,
+    test_map: Option<&Path>,
+    follow_imports: bool,
 ) -> Result<()> {
-    let selector = TestSelector::new();
+    let default_map = repo
+        .unwrap_or(Path::new("."))
+        .join(test_selector::DEFAULT_TEST_MAP_PATH);
+    let map_path = match test_map {
+        Some(path) => Some(path.to_path_buf()),
+        None => default_map.is_file().then_some(default_map),
+    };
+    let config = TestSelectorConfig {
+        test_map: match map_path {
+            Some(path) => test_selector::load_test_map(&path)?,
+            None => Vec::new(),
+        },
+        follow_imports,
+        ..TestSelectorConfig::default()
+    };
+    let selector = TestSelector::with_config(config);
     let selection = selector.select_from_git_diff(base, head, repo)?;
 
     match format {
-        "json" => {
-            #[derive(serde::Serialize)]
-            struct Output {
-                changed_files: Vec<String>,
-                selected_tests: Vec<String>,
-                test_patterns: Vec<String>,
-                selection_ratio: f64,
-                reasoning: Vec<String>,
-            }
-
-            let output = Output {
-                changed_files: selection
-                    .changed_files
-                    .iter()
-                    .map(|p| p.display().to_string())
-                    .collect(),
-                selected_tests: selection.selected_tests,
-                test_patterns: selection.test_patterns,
-                selection_ratio: selection.selection_ratio,
-                reasoning: selection.reasoning,
-            };
-
-            let json = serde_json::to_string_pretty(&output)?;
-            println!("{}", json);
-        }
-        "yaml" => {
-            #[derive(serde::Serialize)]
-            struct Output {
-                changed_files: Vec<String>,
-                selected_tests: Vec<String>,
-                test_patterns: Vec<String>,
-                selection_ratio: f64,
-                reasoning: Vec<String>,
-            }
-
-            let output = Output {
-                changed_files: selection
-                    .changed_files
-                    .iter()
-                    .map(|p| p.display().to_string())
-                    .collect(),
-                selected_tests: selection.selected_tests,
-                test_patterns: selection.test_patterns,
-                selection_ratio: selection.selection_ratio,
-                reasoning: selection.reasoning,
-            };
-
-            let yaml = serde_yaml::to_string(&output)?;
-            println!("{}", yaml);
-        }
+        "json" => println!("{}", serde_json::to_string_pretty(&selection)?),
+        "yaml" => println!("{}", serde_yaml::to_string(&selection)?),
         _ => {
             display::print_test_selection(&selection);
         }
@@ -1342,36 +2370,69 @@ fn cmd_select_tests(
     Ok(())
 }
 
-fn cmd_flaky(paths: &[PathBuf], min_runs: usize, threshold: f64, format: &str) -> Result<()> {
+fn cmd_flaky(
+    paths: &[PathBuf],
+    min_runs: usize,
+    threshold: f64,
+    format: &str,
+    emit_quarantine: Option<&str>,
+    trend: bool,
+) -> Result<()> {
+    let quarantine = emit_quarantine
+        .map(|id| {
+            QuarantineFormat::from_id(id).ok_or_else(|| {
+                let known: Vec<_> = QuarantineFormat::ALL.iter().map(|f| f.id()).collect();
+                anyhow::anyhow!(
+                    "Unknown quarantine format '{}'. Expected one of: {}",
+                    id,
+                    known.join(", ")
+                )
+            })
+        })
+        .transpose()?;
+
     if paths.is_empty() {
-        anyhow::bail!("No paths provided. Specify JUnit XML files or directories.");
+        anyhow::bail!("No paths provided. Specify test report files or directories.");
+    }
+    if trend && paths.len() < 2 {
+        anyhow::bail!("--trend needs at least two paths, one per batch");
     }
 
-    // Collect all JUnit XML files
+    let detector = FlakyDetector::with_config(min_runs, threshold);
     let mut junit_files = Vec::new();
-    for path in paths {
-        if path.is_file() {
-            if path.extension().and_then(|e| e.to_str()) == Some("xml") {
-                junit_files.push(path.clone());
+    let report = if trend {
+        let mut batches = Vec::new();
+        for path in paths {
+            let files = collect_junit_files(path)?;
+            if files.is_empty() {
+                anyhow::bail!("No test reports found in '{}'", path.display());
             }
-        } else if path.is_dir() {
-            // Find all XML files in directory
-            let pattern = format!("{}/**/*.xml", path.display());
-            let files: Vec<PathBuf> = glob::glob(&pattern)
-                .context("Failed to read glob pattern")?
-                .filter_map(|r| r.ok())
-                .collect();
+            let timestamp = files
+                .iter()
+                .filter_map(|f| std::fs::metadata(f).and_then(|m| m.modified()).ok())
+                .filter_map(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_secs())
+                .max()
+                .unwrap_or(0);
+            batches.push(detector.load_batch(timestamp, &files)?);
             junit_files.extend(files);
         }
-    }
+        detector.analyze_batches(batches)?
+    } else {
+        for path in paths {
+            junit_files.extend(collect_junit_files(path)?);
+        }
+        if junit_files.is_empty() {
+            anyhow::bail!("No test reports found in provided paths");
+        }
+        detector.analyze_junit_files(&junit_files)?
+    };
 
-    if junit_files.is_empty() {
-        anyhow::bail!("No JUnit XML files found in provided paths");
+    if let Some(quarantine) = quarantine {
+        print!("{}", flaky_detector::quarantine_file(&report, quarantine));
+        return Ok(());
     }
 
-    let detector = FlakyDetector::with_config(min_runs, threshold);
-    let report = detector.analyze_junit_files(&junit_files)?;
-
     match format {
         "json" => {
             let json = serde_json::to_string_pretty(&report)?;
@@ -1385,6 +2446,37 @@ fn cmd_flaky(paths: &[PathBuf], min_runs: usize, threshold: f64, format: &str) -
     Ok(())
 }
 
+/// Test report files at `path`: the file itself, or every `*.xml` and `*.trx`
+/// below a directory. XML found by the scan that is not a test report (such as
+/// coverage.xml) is skipped with a warning.
+fn collect_junit_files(path: &Path) -> Result<Vec<PathBuf>> {
+    let is_report =
+        |p: &Path| matches!(p.extension().and_then(|e| e.to_str()), Some("xml" | "trx"));
+    if path.is_file() {
+        return Ok(if is_report(path) {
+            vec![path.to_path_buf()]
+        } else {
+            Vec::new()
+        });
+    }
+    if !path.is_dir() {
+        return Ok(Vec::new());
+    }
+    let pattern = format!("{}/**/*", path.display());
+    Ok(glob::glob(&pattern)
+        .context("Failed to read glob pattern")?
+        .filter_map(|r| r.ok())
+        .filter(|p| p.is_file() && is_report(p))
+        .filter(|p| match flaky_detector::formats::unrecognized_root(p) {
+            Some(root) => {
+                log::warn!("Skipping {}: <{}> is not a test report", p.display(), root);
+                false
+            }
+            None => true,
+        })
+        .collect())
+}
+
 async fn cmd_history(
     repo: &str,
     workflow: &str,
@@ -1409,13 +2501,10 @@ async fn cmd_history(
     // Get token from argument or environment
     let api_token = token.or_else(|| std::env::var("GITHUB_TOKEN").ok());
 
-    if format != "json" {
-        println!("🔍 Analyzing workflow run history...");
-        println!("   Repository: {}/{}", owner, repo_name);
-        println!("   Workflow: {}", workflow_file);
-        println!("   Runs to analyze: {}", runs);
-        println!();
-    }
+    log::info!("🔍 Analyzing workflow run history...");
+    log::info!("   Repository: {}/{}", owner, repo_name);
+    log::info!("   Workflow: {}", workflow_file);
+    log::info!("   Runs to analyze: {}", runs);
 
     // Create GitHub API client
     let client = GitHubClient::new(api_token).context("Failed to create GitHub API client")?;
@@ -1444,19 +2533,25 @@ fn cmd_migrate(
     target_provider: &str,
     output: Option<&std::path::Path>,
     format: &str,
+    validate: bool,
 ) -> Result<()> {
     if !path.is_file() {
         anyhow::bail!("'{}' is not a file.", path.display());
     }
 
     let dag = parse_pipeline(path)?;
-    let migration = match target_provider {
+    let mut migration = match target_provider {
         "gitlab" | "gitlab-ci" => github_actions_to_gitlab_ci(&dag)?,
         other => anyhow::bail!(
             "Unsupported migration target '{}'. Supported targets: gitlab-ci",
             other
         ),
     };
+    if validate {
+        migration.validation = Some(pipelinex_core::migration::validate_gitlab_migration(
+            &dag, &migration,
+        ));
+    }
 
     if let Some(out_path) = output {
         std::fs::write(out_path, &migration.yaml)?;
@@ -1487,6 +2582,13 @@ fn cmd_migrate(
                 }
             }
 
+            if let Some(validation) = &migration.validation {
+                println!("Validation:");
+                for line in migration_validation_lines(validation) {
+                    println!("{}", line);
+                }
+            }
+
             match output {
                 Some(out_path) => {
                     println!("Migrated config written to {}", out_path.display());
@@ -1499,7 +2601,46 @@ fn cmd_migrate(
         }
     }
 
-    Ok(())
+    match &migration.validation {
+        Some(validation) if !validation.is_lossless() => {
+            if format == "yaml" {
+                // stdout carries the YAML; explain the failure on stderr
+                eprintln!("Validation:");
+                for line in migration_validation_lines(validation) {
+                    eprintln!("{}", line);
+                }
+            }
+            anyhow::bail!("Migrated config failed round-trip validation")
+        }
+        _ => Ok(()),
+    }
+}
+
+fn migration_validation_lines(
+    validation: &pipelinex_core::migration::MigrationValidation,
+) -> Vec<String> {
+    let mut lines = Vec::new();
+    match &validation.error {
+        Some(error) => lines.push(format!("  Re-parse: FAILED ({})", error)),
+        None => {
+            lines.push("  Re-parse: ok".to_string());
+            lines.push(format!(
+                "  Jobs: {} -> {}",
+                validation.source_jobs, validation.migrated_jobs
+            ));
+            lines.push(format!(
+                "  Dependencies: {} -> {}",
+                validation.source_edges, validation.migrated_edges
+            ));
+        }
+    }
+    for job in &validation.missing_jobs {
+        lines.push(format!("  - missing job: {}", job));
+    }
+    for edge in &validation.lost_dependencies {
+        lines.push(format!("  - lost dependency: {}", edge));
+    }
+    lines
 }
 
 fn cmd_multi_repo(path: &Path, format: &str) -> Result<()> {
@@ -1703,6 +2844,14 @@ hourly_rate = 150.0
 security_scan = true
 # Enable lint checking
 lint = true
+
+# Health score weights (uncomment to override the defaults)
+# [health]
+# critical_penalty = 15.0
+# high_penalty = 8.0
+# medium_penalty = 3.0
+# caching = 0.15
+# parallelization = 0.20
 "#,
         primary_provider,
     );
@@ -1737,8 +2886,8 @@ fn cmd_compare(file_a: &Path, file_b: &Path, format: &str) -> Result<()> {
 
     let dag_a = parse_pipeline(file_a)?;
     let dag_b = parse_pipeline(file_b)?;
-    let report_a = analyzer::analyze(&dag_a);
-    let report_b = analyzer::analyze(&dag_b);
+    let report_a = analyze_dag(&dag_a);
+    let report_b = analyze_dag(&dag_b);
 
     match format {
         "json" => {
@@ -1776,7 +2925,7 @@ fn cmd_compare(file_a: &Path, file_b: &Path, format: &str) -> Result<()> {
     Ok(())
 }
 
-fn cmd_watch(path: &Path, format: &str) -> Result<()> {
+fn cmd_watch(path: &Path, format: &str, serve_port: Option<u16>) -> Result<()> {
     use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
     use std::sync::mpsc;
     use std::time::{Duration, Instant};
@@ -1798,8 +2947,19 @@ fn cmd_watch(path: &Path, format: &str) -> Result<()> {
     );
     println!();
 
-    // Do an initial analysis
-    let _ = run_analysis_for_watch(path, &format);
+    // Seed the content hashes so the first no-op save is recognised as one
+    let mut hashes = pipelinex_core::watch::ContentHashes::new();
+    let initial_files = discover_workflow_files(path).unwrap_or_default();
+    hashes.changed_files(&initial_files);
+
+    let live = match serve_port {
+        Some(port) => Some(start_live_server(path, port)?),
+        None => {
+            // Do an initial analysis
+            let _ = run_analysis_for_watch(&initial_files, &format);
+            None
+        }
+    };
 
     let (tx, rx) = mpsc::channel::<Result<Event, notify::Error>>();
     let mut watcher =
@@ -1815,13 +2975,31 @@ fn cmd_watch(path: &Path, format: &str) -> Result<()> {
     for event in rx {
         match event {
             Ok(event) => {
-                let is_relevant = event.paths.iter().any(|p| {
-                    let ext = p.extension().and_then(|e| e.to_str());
-                    matches!(ext, Some("yml") | Some("yaml") | Some("json"))
-                });
-
-                if is_relevant && last_run.elapsed() > debounce {
+                let touched: Vec<PathBuf> = event
+                    .paths
+                    .into_iter()
+                    .filter(|p| {
+                        let ext = p.extension().and_then(|e| e.to_str());
+                        matches!(ext, Some("yml") | Some("yaml") | Some("json"))
+                    })
+                    .collect();
+
+                if !touched.is_empty() && last_run.elapsed() > debounce {
+                    let files = match discover_workflow_files(path) {
+                        Ok(files) => files,
+                        Err(e) => {
+                            log::warn!("{:#}", e);
+                            continue;
+                        }
+                    };
+                    let Some(changed) = changed_watch_files(&mut hashes, &files, &touched) else {
+                        continue;
+                    };
                     last_run = Instant::now();
+                    if let Some(live) = &live {
+                        refresh_live_reports(path, live);
+                        continue;
+                    }
                     // Clear screen
                     print!("\x1b[2J\x1b[H");
                     println!(
@@ -1829,11 +3007,14 @@ fn cmd_watch(path: &Path, format: &str) -> Result<()> {
                         chrono::Local::now().format("%H:%M:%S")
                     );
                     println!();
-                    let _ = run_analysis_for_watch(path, &format);
+                    // Deleted pipelines have nothing left to analyse
+                    let changed: Vec<PathBuf> =
+                        changed.into_iter().filter(|f| f.exists()).collect();
+                    let _ = run_analysis_for_watch(&changed, &format);
                 }
             }
             Err(e) => {
-                eprintln!("Watch error: {:?}", e);
+                log::error!("Watch error: {:?}", e);
             }
         }
     }
@@ -1841,27 +3022,98 @@ fn cmd_watch(path: &Path, format: &str) -> Result<()> {
     Ok(())
 }
 
-fn run_analysis_for_watch(path: &Path, format: &str) -> Result<()> {
-    let files = discover_workflow_files(path)?;
-    for file in &files {
+type SharedReports = std::sync::Arc<std::sync::Mutex<pipelinex_core::live_report::LiveReports>>;
+
+/// Render the initial reports and start the `watch --serve` HTTP server.
+fn start_live_server(path: &Path, port: u16) -> Result<SharedReports> {
+    use pipelinex_core::live_report::{self, LiveReports};
+
+    let listener = std::net::TcpListener::bind(("127.0.0.1", port))
+        .with_context(|| format!("Failed to bind 127.0.0.1:{}", port))?;
+    let reports: SharedReports = std::sync::Arc::new(std::sync::Mutex::new(LiveReports::new()));
+    refresh_live_reports(path, &reports);
+
+    let server_reports = std::sync::Arc::clone(&reports);
+    std::thread::spawn(move || {
+        if let Err(e) = live_report::serve(listener, server_reports) {
+            log::error!("Report server stopped: {}", e);
+        }
+    });
+
+    println!("Serving live report at http://127.0.0.1:{}/", port);
+    Ok(reports)
+}
+
+/// Re-render the HTML reports for `watch --serve`; browsers reload on the next poll.
+fn refresh_live_reports(path: &Path, reports: &SharedReports) {
+    let files = match discover_workflow_files(path) {
+        Ok(files) => files,
+        Err(e) => {
+            log::warn!("{:#}", e);
+            return;
+        }
+    };
+    let render = |file: &Path| -> std::result::Result<String, String> {
+        let dag = parse_pipeline(file).map_err(|e| format!("{:#}", e))?;
+        let report = analyze_dag(&dag);
+        Ok(analyzer::html_report::generate_html_report(&report, &dag))
+    };
+
+    let Ok(mut reports) = reports.lock() else {
+        return;
+    };
+    if reports.refresh(&files, render) {
+        println!(
+            "[{}] Report updated (version {})",
+            chrono::Local::now().format("%H:%M:%S"),
+            reports.version()
+        );
+    }
+}
+
+/// Pipeline files to re-analyse after a watch event, or `None` when no
+/// content changed. A changed file that is not itself a pipeline (e.g. a
+/// local GitLab include) can affect any of them, so all are returned.
+fn changed_watch_files(
+    hashes: &mut pipelinex_core::watch::ContentHashes,
+    files: &[PathBuf],
+    touched: &[PathBuf],
+) -> Option<Vec<PathBuf>> {
+    let absolute = |p: &Path| std::path::absolute(p).unwrap_or_else(|_| p.to_path_buf());
+    let pipelines: std::collections::HashSet<PathBuf> = files.iter().map(|f| absolute(f)).collect();
+    let others: Vec<PathBuf> = touched
+        .iter()
+        .filter(|p| !pipelines.contains(&absolute(p)))
+        .cloned()
+        .collect();
+
+    let changed = hashes.changed_files(files);
+    if !hashes.changed_files(&others).is_empty() {
+        return Some(files.to_vec());
+    }
+    (!changed.is_empty()).then_some(changed)
+}
+
+fn run_analysis_for_watch(files: &[PathBuf], format: &str) -> Result<()> {
+    for file in files {
         match parse_pipeline(file) {
             Ok(dag) => {
-                let report = analyzer::analyze(&dag);
+                let report = analyze_dag(&dag);
                 match format {
                     "json" => {
                         let json = serde_json::to_string_pretty(&report)?;
                         println!("{}", json);
                     }
                     "markdown" | "md" => {
-                        print!("{}", display::format_markdown_report(&report));
+                        print!("{}", display::format_markdown_report(&report, None));
                     }
                     _ => {
-                        display::print_analysis_report(&report);
+                        display::print_analysis_report(&report, None, false);
                     }
                 }
             }
             Err(e) => {
-                eprintln!("Error parsing {}: {}", file.display(), e);
+                log::error!("Error parsing {}: {}", file.display(), e);
             }
         }
     }
@@ -1893,6 +3145,11 @@ fn cmd_lint(path: &Path, format: &str) -> Result<()> {
                 let json = serde_json::to_string_pretty(&report)?;
                 println!("{}", json);
             }
+            "github" => {
+                for line in pipelinex_core::annotations::annotate_lint(&report, &content) {
+                    println!("{}", line);
+                }
+            }
             _ => {
                 display::print_lint_report(&report);
             }
@@ -1906,6 +3163,59 @@ fn cmd_lint(path: &Path, format: &str) -> Result<()> {
     Ok(())
 }
 
+fn cmd_lint_fix(path: &Path, dry_run: bool) -> Result<()> {
+    let files = discover_workflow_files(path)?;
+
+    if files.is_empty() {
+        anyhow::bail!("No workflow files found at '{}'", path.display());
+    }
+
+    let mut has_errors = false;
+
+    for file in &files {
+        let content = std::fs::read_to_string(file)
+            .with_context(|| format!("Failed to read '{}'", file.display()))?;
+
+        let dag = parse_pipeline(file)?;
+        let report = pipelinex_core::linter::lint(&content, &dag);
+        let outcome = pipelinex_core::linter::fix::apply_fixes(&content, &report);
+
+        if dry_run {
+            print!("{}", outcome.content);
+        } else if outcome.changed() {
+            std::fs::write(file, &outcome.content)
+                .with_context(|| format!("Failed to write '{}'", file.display()))?;
+        }
+
+        for (fix, count) in &outcome.applied {
+            log::info!(
+                "{}: {} {} -> {} ({} occurrence{})",
+                file.display(),
+                if dry_run { "would fix" } else { "fixed" },
+                fix.find,
+                fix.replace,
+                count,
+                if *count == 1 { "" } else { "s" }
+            );
+        }
+        for finding in &outcome.manual {
+            has_errors |= finding.severity == LintSeverity::Error;
+            log::warn!(
+                "{}: needs manual attention: [{}] {}",
+                file.display(),
+                finding.rule_id,
+                finding.message
+            );
+        }
+    }
+
+    if has_errors {
+        anyhow::bail!("Lint errors remain that cannot be fixed automatically");
+    }
+
+    Ok(())
+}
+
 fn cmd_security(path: &Path, format: &str) -> Result<()> {
     let files = discover_workflow_files(path)?;
 
@@ -1922,6 +3232,24 @@ fn cmd_security(path: &Path, format: &str) -> Result<()> {
                 let json = serde_json::to_string_pretty(&findings)?;
                 println!("{}", json);
             }
+            "sarif" => {
+                let sarif = pipelinex_core::analyzer::sarif::findings_to_sarif_with_options(
+                    &findings,
+                    &file.display().to_string(),
+                    SARIF_OPTIONS.get().unwrap_or(&SarifOptions::default()),
+                );
+                println!("{}", serde_json::to_string_pretty(&sarif)?);
+            }
+            "github" => {
+                let content = std::fs::read_to_string(file).unwrap_or_default();
+                for line in pipelinex_core::annotations::annotate_findings(
+                    &file.display().to_string(),
+                    &findings,
+                    &content,
+                ) {
+                    println!("{}", line);
+                }
+            }
             _ => {
                 display::print_security_report(&findings, &file.display().to_string());
             }
@@ -1947,10 +3275,16 @@ fn cmd_policy(command: PolicyCommands) -> Result<()> {
             path,
             policy: policy_path,
             format,
+            include_passing,
+            baseline,
         } => {
             let policy = pipelinex_core::load_policy(&policy_path).with_context(|| {
                 format!("Failed to load policy from '{}'", policy_path.display())
             })?;
+            let baseline = baseline
+                .as_deref()
+                .map(pipelinex_core::policy::load_policy_baseline)
+                .transpose()?;
 
             let files = discover_workflow_files(&path)?;
             if files.is_empty() {
@@ -1961,7 +3295,10 @@ fn cmd_policy(command: PolicyCommands) -> Result<()> {
 
             for file in &files {
                 let dag = parse_pipeline(file)?;
-                let report = pipelinex_core::check_policy(&dag, &policy);
+                let mut report = pipelinex_core::check_policy(&dag, &policy);
+                if let Some(baseline) = &baseline {
+                    pipelinex_core::policy::apply_baseline(&mut report, baseline);
+                }
 
                 if !report.passed {
                     any_failed = true;
@@ -1973,7 +3310,7 @@ fn cmd_policy(command: PolicyCommands) -> Result<()> {
                         println!("{}", json);
                     }
                     _ => {
-                        display::print_policy_report(&report);
+                        display::print_policy_report(&report, include_passing);
                     }
                 }
             }
@@ -1987,8 +3324,17 @@ fn cmd_policy(command: PolicyCommands) -> Result<()> {
     }
 }
 
-fn cmd_monorepo_discover(path: &Path, max_depth: usize, format: &str) -> Result<()> {
-    let discovered = pipelinex_core::discovery::discover_monorepo(path, max_depth)?;
+/// Phases are charged to `timer` under a `discover` root frame.
+fn cmd_monorepo_discover(
+    path: &Path,
+    max_depth: usize,
+    format: &str,
+    sample: Option<(usize, u64)>,
+    timer: &mut SpanTimer,
+) -> Result<()> {
+    let mut discovered = timer.time(&["discover", "scan"], || {
+        pipelinex_core::discovery::discover_monorepo(path, max_depth)
+    })?;
 
     if discovered.is_empty() {
         anyhow::bail!(
@@ -1998,7 +3344,23 @@ fn cmd_monorepo_discover(path: &Path, max_depth: usize, format: &str) -> Result<
         );
     }
 
-    let summary = pipelinex_core::discovery::aggregate_discovery(path, &discovered);
+    let mut summary = timer.time(&["discover", "aggregate"], || {
+        pipelinex_core::discovery::aggregate_discovery(path, &discovered)
+    });
+    let package_count = summary.packages.len();
+
+    if let Some((size, seed)) = sample {
+        let population = discovered.len();
+        discovered = pipelinex_core::discovery::sample_pipelines(&discovered, size, seed);
+        summary = pipelinex_core::discovery::aggregate_discovery(path, &discovered);
+        summary.total_pipeline_files = population;
+        summary.sample = Some(pipelinex_core::discovery::SampleInfo {
+            sampled: discovered.len(),
+            population,
+            seed,
+        });
+    }
+    let sample_info = summary.sample;
 
     if format == "json" {
         println!("{}", serde_json::to_string_pretty(&summary)?);
@@ -2008,9 +3370,14 @@ fn cmd_monorepo_discover(path: &Path, max_depth: usize, format: &str) -> Result<
     println!("PipelineX Monorepo Discovery — {}", path.display());
     println!(
         "  Found {} pipeline files across {} packages",
-        summary.total_pipeline_files,
-        summary.packages.len()
+        summary.total_pipeline_files, package_count
     );
+    if let Some(info) = &sample_info {
+        println!(
+            "  Sample: analyzing {} of {} files (seed {}); totals are extrapolated",
+            info.sampled, info.population, info.seed
+        );
+    }
     println!();
 
     // Now analyze each discovered file
@@ -2018,9 +3385,12 @@ fn cmd_monorepo_discover(path: &Path, max_depth: usize, format: &str) -> Result<
     let mut total_jobs = 0;
 
     for pipeline in &discovered {
-        match parse_pipeline(&pipeline.file_path) {
+        let file = pipeline.relative_path.as_str();
+        match timer.time(&["discover", "parse", file], || {
+            parse_pipeline(&pipeline.file_path)
+        }) {
             Ok(dag) => {
-                let report = analyzer::analyze(&dag);
+                let report = timer.time(&["discover", "analyze", file], || analyze_dag(&dag));
                 total_findings += report.findings.len();
                 total_jobs += report.job_count;
                 println!(
@@ -2047,6 +3417,15 @@ fn cmd_monorepo_discover(path: &Path, max_depth: usize, format: &str) -> Result<
         total_findings,
         discovered.len()
     );
+    if let Some(info) = &sample_info {
+        let scale = info.scale();
+        println!(
+            "  Estimated for all {} files: ~{:.0} jobs, ~{:.0} findings",
+            info.population,
+            total_jobs as f64 * scale,
+            total_findings as f64 * scale
+        );
+    }
     println!();
 
     Ok(())
@@ -2085,29 +3464,96 @@ fn cmd_sbom(path: &Path, output: Option<&std::path::Path>) -> Result<()> {
     Ok(())
 }
 
-fn cmd_badge(path: &Path, format: &str) -> Result<()> {
+fn cmd_summary(
+    path: &Path,
+    format: &str,
+    runs_per_month: u32,
+    team_size: u32,
+    hourly_rate: f64,
+) -> Result<()> {
+    let files = discover_workflow_files(path)?;
+    if files.is_empty() {
+        anyhow::bail!("No workflow files found at '{}'", path.display());
+    }
+
+    let mut summaries = Vec::new();
+    for file in &files {
+        let dag = parse_pipeline(file)?;
+        let report = analyze_dag(&dag);
+        let params = pipelinex_core::badge::SavingsParams {
+            runs_per_month,
+            hourly_rate,
+            team_size,
+            runner_type: dag
+                .graph
+                .node_weights()
+                .next()
+                .map(|j| j.runs_on.clone())
+                .unwrap_or_else(|| "ubuntu-latest".to_string()),
+        };
+        summaries.push(pipelinex_core::summary::executive_summary(&report, &params));
+    }
+
+    match format {
+        "json" => println!("{}", serde_json::to_string_pretty(&summaries)?),
+        "text" => {
+            let paragraphs: Vec<&str> = summaries.iter().map(|s| s.paragraph.as_str()).collect();
+            println!("{}", paragraphs.join("\n\n"));
+        }
+        other => anyhow::bail!("Unknown summary format '{}'. Expected text or json", other),
+    }
+    Ok(())
+}
+
+/// `savings` is `(runs_per_month, team_size, hourly_rate)` when `--savings` is set.
+fn cmd_badge(path: &Path, format: &str, savings: Option<(u32, u32, f64)>) -> Result<()> {
     if !path.is_file() {
         anyhow::bail!("'{}' is not a file.", path.display());
     }
 
     let dag = parse_pipeline(path)?;
-    let report = analyzer::analyze(&dag);
-    let badge = pipelinex_core::badge::generate_badge(&report);
+    let report = analyze_dag(&dag);
+    let savings = savings.map(|(runs_per_month, team_size, hourly_rate)| {
+        pipelinex_core::badge::SavingsParams {
+            runs_per_month,
+            hourly_rate,
+            team_size,
+            runner_type: dag
+                .graph
+                .node_weights()
+                .next()
+                .map(|j| j.runs_on.clone())
+                .unwrap_or_else(|| "ubuntu-latest".to_string()),
+        }
+    });
+    let badge = pipelinex_core::badge::generate_badge(&report, savings.as_ref());
 
     match format {
         "json" => {
             println!("{}", serde_json::to_string_pretty(&badge)?);
         }
+        "endpoint" => {
+            println!("{}", serde_json::to_string_pretty(&badge.endpoint())?);
+        }
         "url" => {
             println!("{}", badge.shields_url);
+            if let Some(savings) = &badge.savings {
+                println!("{}", savings.shields_url);
+            }
         }
         _ => {
             println!("{}", badge.markdown);
+            if let Some(savings) = &badge.savings {
+                println!("{}", savings.markdown);
+            }
             println!();
             println!(
                 "  Score: {}/100 ({}) | {:.0}% optimized",
                 badge.score, badge.grade, badge.optimization_pct
             );
+            if let Some(savings) = &badge.savings {
+                println!("  Recoverable: ${:.0}/month", savings.monthly_savings_usd);
+            }
             println!();
             println!("  Add the line above to your README.md");
         }
@@ -2119,53 +3565,160 @@ fn cmd_badge(path: &Path, format: &str) -> Result<()> {
 fn cmd_keys(command: KeysCommands) -> Result<()> {
     match command {
         KeysCommands::Generate { path } => {
-            std::fs::create_dir_all(&path)?;
-
             let (private_key, public_key) = pipelinex_core::generate_keypair()?;
+            let (private_path, public_path) = write_keypair(&path, &private_key, &public_key)?;
 
-            let private_path = path.join("private.key");
-            let public_path = path.join("public.key");
+            println!("Ed25519 keypair generated:");
+            print_key_usage(&private_path, &public_path);
 
-            std::fs::write(&private_path, &private_key)?;
-            std::fs::write(&public_path, &public_key)?;
+            Ok(())
+        }
+        KeysCommands::Rotate { path } => {
+            let archived = archive_keypair(&path)?;
+            let (private_key, public_key) = pipelinex_core::generate_keypair()?;
+            let (private_path, public_path) = write_keypair(&path, &private_key, &public_key)?;
 
-            println!("Ed25519 keypair generated:");
-            println!("  Private key: {}", private_path.display());
-            println!("  Public key:  {}", public_path.display());
-            println!();
-            println!(
-                "Sign reports:  pipelinex analyze ci.yml --format json --sign {}",
-                private_path.display()
-            );
-            println!(
-                "Verify:        pipelinex verify report.json --key {}",
-                public_path.display()
-            );
-            println!();
-            println!("Keep your private key secure. Share only the public key.");
+            println!("Ed25519 keypair rotated:");
+            match &archived {
+                Some(dir) => println!("  Old keys archived to: {}", dir.display()),
+                None => println!("  No existing keypair found; generated a fresh one."),
+            }
+            print_key_usage(&private_path, &public_path);
+            if let Some(dir) = archived {
+                println!();
+                println!("Next steps:");
+                println!("  1. Update the signing secret in CI with the new private key.");
+                println!("  2. Distribute the new public key to everyone who verifies reports.");
+                println!(
+                    "  3. Keep {} until reports signed with the old key are no longer needed.",
+                    dir.join("public.key").display()
+                );
+            }
+
+            Ok(())
+        }
+        KeysCommands::Import { private, path } => {
+            let data = std::fs::read(&private)
+                .with_context(|| format!("Failed to read key file: {}", private.display()))?;
+            let (private_key, public_key) = pipelinex_core::signing::import_private_key(&data)
+                .with_context(|| format!("Cannot import {}", private.display()))?;
+
+            let archived = archive_keypair(&path)?;
+            let (private_path, public_path) = write_keypair(&path, &private_key, &public_key)?;
+
+            println!("Ed25519 key imported from {}:", private.display());
+            if let Some(dir) = archived {
+                println!("  Previous keys archived to: {}", dir.display());
+            }
+            print_key_usage(&private_path, &public_path);
 
             Ok(())
         }
     }
 }
 
-fn cmd_verify(report_path: &Path, key: &str) -> Result<()> {
-    let content = std::fs::read_to_string(report_path)
-        .with_context(|| format!("Failed to read report: {}", report_path.display()))?;
+fn write_keypair(dir: &Path, private_key: &str, public_key: &str) -> Result<(PathBuf, PathBuf)> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create directory '{}'", dir.display()))?;
+
+    let private_path = dir.join("private.key");
+    let public_path = dir.join("public.key");
+    std::fs::write(&private_path, private_key)?;
+    std::fs::write(&public_path, public_key)?;
 
-    let signed: pipelinex_core::signing::SignedReport =
-        serde_json::from_str(&content).context("Failed to parse signed report JSON")?;
+    Ok((private_path, public_path))
+}
 
-    let public_key = read_key_material(key)?;
+/// Move an existing keypair to `<dir>/archive/<UTC timestamp>/`.
+///
+/// Returns the archive directory, or `None` when there was nothing to archive.
+fn archive_keypair(dir: &Path) -> Result<Option<PathBuf>> {
+    let existing: Vec<_> = ["private.key", "public.key"]
+        .iter()
+        .map(|name| dir.join(name))
+        .filter(|p| p.exists())
+        .collect();
+    if existing.is_empty() {
+        return Ok(None);
+    }
+
+    let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let archive = dir.join("archive").join(stamp);
+    std::fs::create_dir_all(&archive)
+        .with_context(|| format!("Failed to create archive '{}'", archive.display()))?;
+    for file in existing {
+        let target = archive.join(file.file_name().unwrap_or_default());
+        std::fs::rename(&file, &target).with_context(|| {
+            format!(
+                "Failed to archive {} to {}",
+                file.display(),
+                target.display()
+            )
+        })?;
+    }
 
-    let valid = pipelinex_core::verify_report(&signed, &public_key)?;
+    Ok(Some(archive))
+}
 
-    if valid {
-        println!("Signature VALID — report is authentic and untampered.");
-        std::process::exit(0);
-    } else {
-        println!("Signature INVALID — report may have been tampered with!");
-        std::process::exit(1);
+fn print_key_usage(private_path: &Path, public_path: &Path) {
+    println!("  Private key: {}", private_path.display());
+    println!("  Public key:  {}", public_path.display());
+    println!();
+    println!(
+        "Sign reports:  pipelinex analyze ci.yml --format json --sign {}",
+        private_path.display()
+    );
+    println!(
+        "Verify:        pipelinex verify report.json --key {}",
+        public_path.display()
+    );
+    println!();
+    println!("Keep your private key secure. Share only the public key.");
+}
+
+fn cmd_verify(report_path: &Path, key: Option<&str>, keyring: Option<&Path>) -> Result<()> {
+    if let Some(keyring) = keyring {
+        let content = std::fs::read_to_string(report_path)
+            .with_context(|| format!("Failed to read report: {}", report_path.display()))?;
+        let signed: pipelinex_core::signing::SignedReport =
+            serde_json::from_str(&content).context("Failed to parse signed report JSON")?;
+
+        let keys = pipelinex_core::load_keyring(keyring)?;
+        return match pipelinex_core::verify_with_keyring(&signed, &keys)? {
+            Some(entry) => {
+                println!(
+                    "Signature VALID — signed by key {} ({}).",
+                    entry.key_id,
+                    entry.source.display()
+                );
+                Ok(())
+            }
+            None => {
+                println!(
+                    "Signature INVALID — no key in {} ({} keys) verifies this report.",
+                    keyring.display(),
+                    keys.len()
+                );
+                anyhow::bail!("Signature verification failed")
+            }
+        };
+    }
+
+    let public_key = read_key_material(key.unwrap_or_default())?;
+
+    match pipelinex_core::verify_report_file(report_path, &public_key)? {
+        VerificationOutcome::Valid => {
+            println!("Signature VALID — report is authentic and untampered.");
+            Ok(())
+        }
+        VerificationOutcome::Invalid => {
+            println!("Signature INVALID — report may have been tampered with!");
+            anyhow::bail!("Signature verification failed")
+        }
+        VerificationOutcome::KeyMismatch => {
+            println!("Signature INVALID — report was signed by a different key.");
+            anyhow::bail!("Signature verification failed")
+        }
     }
 }
 
@@ -2179,7 +3732,7 @@ async fn cmd_explain(path: &Path, format: &str, runs_per_month: u32) -> Result<(
 
     for file in &files {
         let dag = parse_pipeline(file)?;
-        let report = analyzer::analyze(&dag);
+        let report = analyze_dag(&dag);
 
         if report.findings.is_empty() {
             println!("No findings to explain for {}", file.display());
@@ -2271,6 +3824,17 @@ fn cmd_whatif(path: &Path, modifications: &[String], format: &str) -> Result<()>
     Ok(())
 }
 
+fn cmd_list_providers(format: &str) -> Result<()> {
+    use pipelinex_core::parser::detect::PROVIDERS;
+
+    match format {
+        "json" => println!("{}", serde_json::to_string_pretty(PROVIDERS)?),
+        "text" => display::print_provider_matrix(PROVIDERS),
+        other => anyhow::bail!("Unknown format '{}' (expected text or json)", other),
+    }
+    Ok(())
+}
+
 fn cmd_plugins(command: PluginCommands) -> Result<()> {
     match command {
         PluginCommands::Scaffold { path } => {
@@ -2289,11 +3853,19 @@ fn cmd_plugins(command: PluginCommands) -> Result<()> {
             struct Output {
                 analyzers: Vec<String>,
                 optimizers: Vec<String>,
+                /// Handshake results of enabled analyzers that answer `--capabilities`
+                capabilities: std::collections::BTreeMap<String, plugins::PluginCapabilities>,
             }
 
             let output = Output {
                 analyzers: loaded.analyzers.iter().map(|p| p.id.clone()).collect(),
                 optimizers: loaded.optimizers.iter().map(|p| p.id.clone()).collect(),
+                capabilities: loaded
+                    .analyzers
+                    .iter()
+                    .filter(|p| p.enabled)
+                    .filter_map(|p| plugins::plugin_capabilities(p).map(|c| (p.id.clone(), c)))
+                    .collect(),
             };
 
             if format == "json" {
@@ -2303,8 +3875,34 @@ fn cmd_plugins(command: PluginCommands) -> Result<()> {
                 if output.analyzers.is_empty() {
                     println!("  (none)");
                 } else {
-                    for id in output.analyzers {
-                        println!("  - {}", id);
+                    for id in &output.analyzers {
+                        let Some(caps) = output.capabilities.get(id) else {
+                            println!("  - {}", id);
+                            continue;
+                        };
+                        let providers = if caps.providers.is_empty() {
+                            "all".to_string()
+                        } else {
+                            caps.providers.join(", ")
+                        };
+                        println!(
+                            "  - {} {}(schema v{}; providers: {}; categories: {})",
+                            id,
+                            caps.version
+                                .as_deref()
+                                .map(|v| format!("{} ", v))
+                                .unwrap_or_default(),
+                            caps.input_schema_version,
+                            providers,
+                            if caps.categories.is_empty() {
+                                "unspecified".to_string()
+                            } else {
+                                caps.categories.join(", ")
+                            }
+                        );
+                        if let Some(warning) = caps.protocol_mismatch() {
+                            println!("    warning: {}", warning);
+                        }
                     }
                 }
 
@@ -0,0 +1,70 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+fn fixture(path: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../tests/fixtures")
+        .join(path)
+}
+
+#[test]
+fn test_verbose_json_output_keeps_logs_off_stdout() {
+    let output = Command::new(env!("CARGO_BIN_EXE_pipelinex"))
+        .arg("analyze")
+        .arg(fixture("github-actions/unoptimized-fullstack.yml"))
+        .args(["--format", "json", "-vv"])
+        .env_remove("RUST_LOG")
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let report: serde_json::Value = serde_json::from_str(&stdout)
+        .unwrap_or_else(|e| panic!("stdout is not JSON ({}): {}", e, stdout));
+    assert_eq!(report["provider"], "github-actions");
+}
+
+#[test]
+fn test_flaky_directory_scan_skips_non_test_xml() {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("flaky-mixed-reports");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("junit.xml"),
+        r#"<testsuite name="api"><testcase classname="api" name="login" time="0.5"/></testsuite>"#,
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("coverage.xml"),
+        r#"<coverage line-rate="0.8"><packages/></coverage>"#,
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_pipelinex"))
+        .arg("flaky")
+        .arg(&dir)
+        .args(["--format", "json", "--min-runs", "1"])
+        .output()
+        .unwrap();
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{}", stderr);
+    assert!(
+        stderr.contains("<coverage> is not a test report"),
+        "{}",
+        stderr
+    );
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["total_tests"], 1);
+
+    // A non-report named explicitly is still an error
+    let explicit = Command::new(env!("CARGO_BIN_EXE_pipelinex"))
+        .arg("flaky")
+        .arg(dir.join("coverage.xml"))
+        .output()
+        .unwrap();
+    assert!(!explicit.status.success());
+}
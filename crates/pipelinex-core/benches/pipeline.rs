@@ -0,0 +1,54 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use pipelinex_core::analyzer::{self, critical_path};
+use pipelinex_core::simulator;
+use pipelinex_core::synthetic::{github_workflow_yaml, layered_dag};
+use pipelinex_core::GitHubActionsParser;
+
+fn bench_parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse_github");
+    for jobs in [10, 100] {
+        let yaml = github_workflow_yaml(jobs, 10);
+        group.bench_with_input(BenchmarkId::from_parameter(jobs), &yaml, |b, yaml| {
+            b.iter(|| GitHubActionsParser::parse(black_box(yaml), "synthetic.yml".to_string()))
+        });
+    }
+    group.finish();
+}
+
+fn bench_analyze(c: &mut Criterion) {
+    let yaml = github_workflow_yaml(100, 10);
+    let dag = GitHubActionsParser::parse(&yaml, "synthetic.yml".to_string()).unwrap();
+    c.bench_function("analyze_100_jobs", |b| {
+        b.iter(|| analyzer::analyze(black_box(&dag)))
+    });
+}
+
+fn bench_simulate(c: &mut Criterion) {
+    let dag = layered_dag(10, 5);
+    let mut group = c.benchmark_group("simulate");
+    group.sample_size(10);
+    group.bench_function("10k_runs_50_jobs", |b| {
+        b.iter(|| simulator::simulate(black_box(&dag), 10_000, 0.15))
+    });
+    group.finish();
+}
+
+fn bench_critical_path(c: &mut Criterion) {
+    let mut group = c.benchmark_group("critical_path_wide");
+    for width in [50, 200] {
+        let dag = layered_dag(width, 3);
+        group.bench_with_input(BenchmarkId::from_parameter(width), &dag, |b, dag| {
+            b.iter(|| critical_path::find_critical_path(black_box(dag)).1)
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_parse,
+    bench_analyze,
+    bench_simulate,
+    bench_critical_path
+);
+criterion_main!(benches);
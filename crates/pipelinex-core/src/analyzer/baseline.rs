@@ -0,0 +1,309 @@
+//! Saved analysis baselines (`analyze --baseline-save`).
+//!
+//! A baseline records which findings each file had at one point in time, in a
+//! canonical order, so a later analysis (`analyze --baseline`) can be compared
+//! against it without tripping over volatile fields such as timestamps,
+//! durations or savings.
+
+use crate::analyzer::report::{AnalysisReport, Finding, FindingCategory, ReportMetadata, Severity};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::path::{Component, Path, PathBuf};
+
+/// Baseline file format version.
+pub const BASELINE_VERSION: u32 = 1;
+
+/// Findings of a set of analyzed files, plus when and by what they were recorded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Baseline {
+    pub version: u32,
+    /// Provenance only; ignored when comparing.
+    pub metadata: ReportMetadata,
+    /// One entry per analyzed file, sorted by path.
+    pub files: Vec<BaselineFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BaselineFile {
+    pub source_file: String,
+    /// Sorted by fingerprint, then affected jobs.
+    pub findings: Vec<BaselineFinding>,
+}
+
+/// The stable parts of a finding.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaselineFinding {
+    /// [`baseline_fingerprint`]: category and title, numbers masked.
+    pub fingerprint: String,
+    pub affected_jobs: Vec<String>,
+    pub category: FindingCategory,
+    pub severity: Severity,
+    pub title: String,
+}
+
+/// A finding present on only one side of a comparison.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DriftedFinding {
+    pub source_file: String,
+    #[serde(flatten)]
+    pub finding: BaselineFinding,
+}
+
+/// Difference between a baseline and a fresh analysis.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct BaselineDrift {
+    /// Findings not in the baseline.
+    pub new: Vec<DriftedFinding>,
+    /// Baseline findings no longer reported.
+    pub resolved: Vec<DriftedFinding>,
+}
+
+impl BaselineDrift {
+    pub fn is_empty(&self) -> bool {
+        self.new.is_empty() && self.resolved.is_empty()
+    }
+}
+
+impl Baseline {
+    /// Canonical baseline of `reports`, carrying the first report's metadata.
+    pub fn from_reports(reports: &[AnalysisReport]) -> Self {
+        let mut files: Vec<BaselineFile> = reports
+            .iter()
+            .map(|report| {
+                let mut findings: Vec<BaselineFinding> = report
+                    .findings
+                    .iter()
+                    .map(|f| {
+                        let mut affected_jobs = f.affected_jobs.clone();
+                        affected_jobs.sort();
+                        BaselineFinding {
+                            fingerprint: baseline_fingerprint(f),
+                            affected_jobs,
+                            category: f.category.clone(),
+                            severity: f.severity,
+                            title: f.title.clone(),
+                        }
+                    })
+                    .collect();
+                findings.sort_by(|a, b| {
+                    (&a.fingerprint, &a.affected_jobs).cmp(&(&b.fingerprint, &b.affected_jobs))
+                });
+                BaselineFile {
+                    source_file: normalize_source(&report.source_file),
+                    findings,
+                }
+            })
+            .collect();
+        files.sort_by(|a, b| a.source_file.cmp(&b.source_file));
+
+        Self {
+            version: BASELINE_VERSION,
+            metadata: reports
+                .first()
+                .map(|r| r.metadata.clone())
+                .unwrap_or_default(),
+            files,
+        }
+    }
+
+    /// Findings that appeared or disappeared since the baseline.
+    ///
+    /// Findings are matched on file, fingerprint and affected jobs; metadata,
+    /// severity changes and savings estimates are ignored. Paths are compared
+    /// normalized, so `./ci.yml` and `ci.yml` are the same file.
+    pub fn compare(&self, reports: &[AnalysisReport]) -> BaselineDrift {
+        let current = Self::from_reports(reports);
+        let keys = |baseline: &Baseline| -> BTreeSet<(String, String, Vec<String>)> {
+            baseline
+                .files
+                .iter()
+                .flat_map(|file| {
+                    file.findings.iter().map(|f| {
+                        (
+                            normalize_source(&file.source_file),
+                            f.fingerprint.clone(),
+                            f.affected_jobs.clone(),
+                        )
+                    })
+                })
+                .collect()
+        };
+        let before = keys(self);
+        let after = keys(&current);
+
+        let only_in = |baseline: &Baseline, other: &BTreeSet<_>| -> Vec<DriftedFinding> {
+            baseline
+                .files
+                .iter()
+                .flat_map(|file| {
+                    file.findings
+                        .iter()
+                        .filter(|f| {
+                            !other.contains(&(
+                                normalize_source(&file.source_file),
+                                f.fingerprint.clone(),
+                                f.affected_jobs.clone(),
+                            ))
+                        })
+                        .map(|f| DriftedFinding {
+                            source_file: file.source_file.clone(),
+                            finding: f.clone(),
+                        })
+                })
+                .collect()
+        };
+
+        BaselineDrift {
+            new: only_in(&current, &before),
+            resolved: only_in(self, &after),
+        }
+    }
+}
+
+/// `path` with `.` components and repeated separators dropped, using `/`, so
+/// equivalent spellings of a file key the same baseline entry.
+fn normalize_source(path: &str) -> String {
+    Path::new(path)
+        .components()
+        .filter(|c| !matches!(c, Component::CurDir))
+        .collect::<PathBuf>()
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// Identity of a finding across runs: its category and its title with every
+/// number masked, so "'build' dominates the critical path (62.5%)" matches
+/// the same finding at 70%. Affected jobs are matched separately.
+pub fn baseline_fingerprint(finding: &Finding) -> String {
+    let mut title = String::with_capacity(finding.title.len());
+    let mut in_number = false;
+    for c in finding.title.chars() {
+        let numeric = c.is_ascii_digit() || (in_number && c == '.');
+        if numeric && !in_number {
+            title.push('#');
+        } else if !numeric {
+            title.push(c);
+        }
+        in_number = numeric;
+    }
+
+    let mut hasher = Sha256::new();
+    hasher.update(format!("{:?}", finding.category));
+    hasher.update([0]);
+    hasher.update(title);
+    hex::encode(&hasher.finalize()[..8])
+}
+
+/// Write `baseline` as pretty-printed JSON.
+pub fn save_baseline(path: &Path, baseline: &Baseline) -> anyhow::Result<()> {
+    let json = serde_json::to_string_pretty(baseline)?;
+    std::fs::write(path, json + "\n")
+        .map_err(|e| anyhow::anyhow!("Failed to write baseline '{}': {}", path.display(), e))
+}
+
+/// Load a baseline written by `analyze --baseline-save`.
+pub fn load_baseline(path: &Path) -> anyhow::Result<Baseline> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read baseline '{}': {}", path.display(), e))?;
+    let baseline: Baseline = serde_json::from_str(&content)
+        .map_err(|e| anyhow::anyhow!("Failed to parse baseline '{}': {}", path.display(), e))?;
+    if baseline.version != BASELINE_VERSION {
+        anyhow::bail!(
+            "Baseline '{}' has version {}; this pipelinex reads version {}",
+            path.display(),
+            baseline.version,
+            BASELINE_VERSION
+        );
+    }
+    Ok(baseline)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer;
+    use crate::parser::github::GitHubActionsParser;
+
+    const WORKFLOW: &str = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - run: npm ci
+      - run: npm run build
+  test:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - run: npm ci
+      - run: npm test
+"#;
+
+    fn analyze() -> AnalysisReport {
+        let dag = GitHubActionsParser::parse(WORKFLOW, "ci.yml".to_string()).unwrap();
+        analyzer::analyze(&dag)
+    }
+
+    #[test]
+    fn test_saved_baseline_has_no_drift_against_reanalysis() {
+        let first = analyze();
+        assert!(!first.findings.is_empty());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("baseline.json");
+        save_baseline(&path, &Baseline::from_reports(std::slice::from_ref(&first))).unwrap();
+        let baseline = load_baseline(&path).unwrap();
+        assert_eq!(baseline.files[0].findings.len(), first.findings.len());
+
+        // A later run differs in timestamp (and possibly order), not in findings
+        let mut again = analyze();
+        again.metadata.generated_at = "2000-01-01T00:00:00Z".to_string();
+        again.findings.reverse();
+        assert!(baseline.compare(&[again.clone()]).is_empty());
+
+        let fixed = again.findings.remove(0);
+        let drift = baseline.compare(&[again]);
+        assert!(drift.new.is_empty());
+        assert_eq!(drift.resolved.len(), 1);
+        assert_eq!(drift.resolved[0].finding.title, fixed.title);
+    }
+
+    #[test]
+    fn test_path_spelling_is_not_drift() {
+        let mut dotted = analyze();
+        dotted.source_file = "./.github//workflows/ci.yml".to_string();
+        let baseline = Baseline::from_reports(&[dotted]);
+        assert_eq!(baseline.files[0].source_file, ".github/workflows/ci.yml");
+
+        let mut plain = analyze();
+        plain.source_file = ".github/workflows/ci.yml".to_string();
+        assert!(baseline.compare(&[plain]).is_empty());
+    }
+
+    #[test]
+    fn test_changed_numbers_in_titles_are_not_drift() {
+        let baseline = Baseline::from_reports(&[analyze()]);
+
+        let mut again = analyze();
+        for finding in &mut again.findings {
+            finding.title = finding.title.replace("100.0%", "87.5%");
+            finding.recommendation.push_str(" (now 42s faster)");
+            finding.estimated_savings_secs = Some(1.0);
+        }
+        assert!(again.findings.iter().any(|f| f.title.contains("87.5%")));
+        assert!(baseline.compare(&[again]).is_empty());
+
+        let finding = |title: &str| Finding::builder(FindingCategory::MissingCache, title).build();
+        assert_eq!(
+            baseline_fingerprint(&finding("'build' takes 12.5 min")),
+            baseline_fingerprint(&finding("'build' takes 9 min"))
+        );
+        assert_ne!(
+            baseline_fingerprint(&finding("No dependency caching for Go")),
+            baseline_fingerprint(&finding("No dependency caching for Gradle"))
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use crate::analyzer::report::{Finding, FindingCategory, Severity};
-use crate::parser::dag::PipelineDag;
+use crate::parser::dag::{JobNode, PipelineDag};
 use regex::Regex;
 
 /// Detect missing dependency caches in the pipeline.
@@ -38,6 +38,8 @@ pub fn detect_missing_caches(dag: &PipelineDag) -> Vec<Finding> {
                         estimated_savings_secs: Some(150.0), // ~2.5 min
                         confidence: 0.95,
                         auto_fixable: true,
+                        documentation_url: None,
+                        blame: None,
                     });
                 }
 
@@ -59,6 +61,8 @@ pub fn detect_missing_caches(dag: &PipelineDag) -> Vec<Finding> {
                         estimated_savings_secs: Some(90.0),
                         confidence: 0.93,
                         auto_fixable: true,
+                        documentation_url: None,
+                        blame: None,
                     });
                 }
 
@@ -81,65 +85,98 @@ pub fn detect_missing_caches(dag: &PipelineDag) -> Vec<Finding> {
                         estimated_savings_secs: Some(240.0),
                         confidence: 0.95,
                         auto_fixable: true,
+                        documentation_url: None,
+                        blame: None,
                     });
                 }
+            }
+        }
 
-                // gradle/maven
-                if !has_cache_action && is_gradle_or_maven(&cmd) {
-                    findings.push(Finding {
-                        severity: Severity::High,
-                        category: FindingCategory::MissingCache,
-                        title: "No dependency caching for Gradle/Maven".to_string(),
-                        description: format!(
-                            "Job '{}' runs a Gradle/Maven build without caching ~/.gradle or ~/.m2.",
-                            job.id,
-                        ),
-                        affected_jobs: vec![job.id.clone()],
-                        recommendation: "Add actions/cache for Gradle/Maven dependencies."
-                            .to_string(),
-                        fix_command: Some("pipelinex optimize --apply cache".to_string()),
-                        estimated_savings_secs: Some(120.0),
-                        confidence: 0.90,
-                        auto_fixable: true,
-                    });
-                }
+        findings.extend(
+            BUILD_TOOL_CACHES
+                .iter()
+                .filter(|tool| runs_tool(job, tool) && !caches_tool(job, tool))
+                .map(|tool| missing_build_tool_cache(job, tool)),
+        );
+    }
 
-                // Docker build without layer caching
-                if is_docker_build(&cmd) {
-                    let has_docker_cache = job.steps.iter().any(|s| {
-                        s.uses
-                            .as_ref()
-                            .is_some_and(|u| u.starts_with("docker/build-push-action"))
-                    });
-                    if !has_docker_cache && !cmd.contains("--cache-from") {
-                        findings.push(Finding {
-                            severity: Severity::High,
-                            category: FindingCategory::DockerOptimization,
-                            title: "Docker build has no layer caching".to_string(),
-                            description: format!(
-                                "Job '{}' runs docker build without layer caching. \
-                                Every build starts from scratch.",
-                                job.id,
-                            ),
-                            affected_jobs: vec![job.id.clone()],
-                            recommendation:
-                                "Use docker/build-push-action with cache-from/cache-to, \
-                                or add --cache-from flag."
-                                    .to_string(),
-                            fix_command: Some("pipelinex optimize --apply docker".to_string()),
-                            estimated_savings_secs: Some(240.0),
-                            confidence: 0.88,
-                            auto_fixable: true,
-                        });
-                    }
-                }
+    findings
+}
+
+/// Flag caches whose key never changes when dependencies do.
+///
+/// A key like `node-modules` restores the same entry forever, so jobs run
+/// against stale dependencies. Only GitHub Actions and GitLab CI are checked,
+/// since those parsers record the key as written.
+pub fn detect_static_cache_keys(dag: &PipelineDag) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    if dag.provider != "github-actions" && dag.provider != "gitlab-ci" {
+        return findings;
+    }
+
+    for job in dag.graph.node_weights() {
+        for cache in &job.caches {
+            if !is_static_cache_key(&cache.key_pattern) {
+                continue;
             }
+            let example = if dag.provider == "gitlab-ci" {
+                "key: { files: [package-lock.json] }"
+            } else {
+                "key: ${{ runner.os }}-deps-${{ hashFiles('**/package-lock.json') }}"
+            };
+            findings.push(Finding {
+                severity: Severity::Medium,
+                category: FindingCategory::IneffectiveCache,
+                title: format!("Cache key '{}' never invalidates", cache.key_pattern),
+                description: format!(
+                    "Job '{}' caches '{}' under key '{}', which does not include a hash \
+                    of any lockfile. The first saved entry is restored on every run, \
+                    so dependency updates are served stale or reinstalled from scratch.",
+                    job.id, cache.path, cache.key_pattern
+                ),
+                affected_jobs: vec![job.id.clone()],
+                recommendation: format!(
+                    "Use a content-addressed key derived from the lockfile, e.g. `{}`, \
+                    with a prefix-only restore key as fallback.",
+                    example
+                ),
+                fix_command: None,
+                estimated_savings_secs: None,
+                confidence: 0.8,
+                auto_fixable: false,
+                documentation_url: None,
+                blame: None,
+            });
         }
     }
 
     findings
 }
 
+/// Whether a cache key is a fixed string with no lockfile hash.
+///
+/// Keys that hash files, or embed a per-run value (commit SHA, run ID, step
+/// output), change on their own and are not reported. Placeholder keys from
+/// parsers that could not read one are skipped too.
+fn is_static_cache_key(key: &str) -> bool {
+    const DYNAMIC_MARKERS: &[&str] = &[
+        "hashfiles(",
+        "checksum",
+        "files:",
+        "steps.",
+        "github.sha",
+        "github.run_id",
+        "ci_commit_sha",
+        "ci_pipeline_id",
+        "ci_job_id",
+    ];
+    let key = key.trim().to_lowercase();
+    if key.is_empty() || key == "detected" || key == "default" {
+        return false;
+    }
+    !DYNAMIC_MARKERS.iter().any(|marker| key.contains(marker))
+}
+
 fn is_npm_install(cmd: &str) -> bool {
     let re = Regex::new(r"(npm\s+(ci|install)|yarn\s+install|pnpm\s+install)").unwrap();
     re.is_match(cmd)
@@ -153,15 +190,191 @@ fn is_cargo_build(cmd: &str) -> bool {
     cmd.contains("cargo build") || cmd.contains("cargo test") || cmd.contains("cargo clippy")
 }
 
-fn is_gradle_or_maven(cmd: &str) -> bool {
-    cmd.contains("./gradlew")
-        || cmd.contains("gradle ")
-        || cmd.contains("mvn ")
-        || cmd.contains("./mvnw")
+/// A build tool with a well-known dependency cache.
+pub(crate) struct BuildToolCache {
+    name: &'static str,
+    /// Commands that run the tool, matched as whole words at the start of a
+    /// (lowercased) shell command.
+    commands: &'static [&'static str],
+    /// Directories to cache, as written in the recommendation.
+    paths: &'static [&'static str],
+    /// Substrings of a configured cache path that already cover the tool.
+    cached_paths: &'static [&'static str],
+    /// `hashFiles` arguments for a lockfile-based key.
+    key_files: &'static str,
+    /// `actions/setup-java`-style `cache:` input value that enables caching.
+    setup_cache_input: Option<&'static str>,
+    /// Actions that cache the tool's directories on their own.
+    caching_actions: &'static [&'static str],
+    savings_secs: f64,
+}
+
+const BUILD_TOOL_CACHES: &[BuildToolCache] = &[
+    BuildToolCache {
+        name: "Gradle",
+        commands: &["./gradlew", "gradlew", "gradle"],
+        paths: &["~/.gradle/caches", "~/.gradle/wrapper"],
+        cached_paths: &[".gradle"],
+        key_files: "'**/*.gradle*', '**/gradle-wrapper.properties'",
+        setup_cache_input: Some("gradle"),
+        caching_actions: &["gradle/actions/setup-gradle", "gradle/gradle-build-action"],
+        savings_secs: 120.0,
+    },
+    BuildToolCache {
+        name: "Maven",
+        commands: &["mvn", "./mvnw", "mvnw"],
+        paths: &["~/.m2/repository"],
+        cached_paths: &[".m2"],
+        key_files: "'**/pom.xml'",
+        setup_cache_input: Some("maven"),
+        caching_actions: &[],
+        savings_secs: 120.0,
+    },
+    BuildToolCache {
+        name: "Go",
+        commands: &[
+            "go build",
+            "go test",
+            "go mod download",
+            "go vet",
+            "go install",
+        ],
+        paths: &["~/go/pkg/mod", "~/.cache/go-build"],
+        cached_paths: &["go/pkg/mod", "go-build", "gocache", "gomodcache"],
+        key_files: "'**/go.sum'",
+        setup_cache_input: None,
+        caching_actions: &[],
+        savings_secs: 90.0,
+    },
+];
+
+/// The [`BUILD_TOOL_CACHES`] entry named `name` (`gradle`, `maven`, `go`), for
+/// the policy's `require_cache` rule.
+pub(crate) fn build_tool(name: &str) -> Option<&'static BuildToolCache> {
+    BUILD_TOOL_CACHES
+        .iter()
+        .find(|tool| tool.name.eq_ignore_ascii_case(name))
+}
+
+pub(crate) fn runs_tool(job: &JobNode, tool: &BuildToolCache) -> bool {
+    job.steps.iter().any(|step| {
+        step.run.as_deref().is_some_and(|run| {
+            let cmd = run.to_lowercase();
+            let runs = shell_commands(&cmd).any(|segment| {
+                tool.commands
+                    .iter()
+                    .any(|c| starts_with_command(segment, c))
+            });
+            runs
+        })
+    })
+}
+
+/// The individual commands of a script: its lines, split on `&&`, `||`, `;` and `|`.
+pub(crate) fn shell_commands(script: &str) -> impl Iterator<Item = &str> {
+    script
+        .split(['\n', ';', '|', '&'])
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+}
+
+/// Whether `segment` runs `command`: it is the first word(s), not a prefix
+/// of a longer word (`gradle` doesn't match `gradlew`).
+fn starts_with_command(segment: &str, command: &str) -> bool {
+    segment
+        .strip_prefix(command)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+}
+
+/// Whether the job already caches the tool's directories, through a cache
+/// path, a setup action's `cache:` input, or an action that caches them.
+pub(crate) fn caches_tool(job: &JobNode, tool: &BuildToolCache) -> bool {
+    let by_path = job.caches.iter().any(|cache| {
+        let path = cache.path.to_lowercase();
+        tool.cached_paths.iter().any(|p| path.contains(p))
+    });
+    by_path
+        || job.steps.iter().any(|step| {
+            let Some(uses) = step.uses.as_deref() else {
+                return false;
+            };
+            let cache_input = step.with.get("cache").map(|c| c.trim().to_lowercase());
+            if tool.caching_actions.iter().any(|a| uses.starts_with(a)) {
+                return true;
+            }
+            if let Some(input) = tool.setup_cache_input {
+                if uses.starts_with("actions/setup-java") && cache_input.as_deref() == Some(input) {
+                    return true;
+                }
+            }
+            // setup-go caches modules and the build cache by default from v4
+            tool.name == "Go"
+                && uses.starts_with("actions/setup-go")
+                && match cache_input.as_deref() {
+                    Some("false") => false,
+                    Some("true") => true,
+                    _ => action_major_version(uses).is_some_and(|v| v >= 4),
+                }
+        })
+}
+
+/// Major version of `owner/action@vN...`, if the ref is a version tag.
+fn action_major_version(uses: &str) -> Option<u32> {
+    let reference = uses.split_once('@')?.1;
+    let digits: String = reference
+        .strip_prefix('v')?
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok()
 }
 
-fn is_docker_build(cmd: &str) -> bool {
-    cmd.contains("docker build") || cmd.contains("docker buildx")
+fn missing_build_tool_cache(job: &JobNode, tool: &BuildToolCache) -> Finding {
+    let key_prefix = tool.name.to_lowercase();
+    let mut recommendation = format!(
+        "Cache {} keyed on a hash of the build files",
+        tool.paths.join(" and ")
+    );
+    if let Some(input) = tool.setup_cache_input {
+        recommendation.push_str(&format!(
+            " (actions/setup-java does this with `cache: {}`)",
+            input
+        ));
+    } else if tool.name == "Go" {
+        recommendation.push_str(" (actions/setup-go@v4 and later do this by default)");
+    }
+    recommendation.push_str(&format!(
+        ":\n\n  - uses: actions/cache@v4\n    with:\n      path: |\n{}      \
+        key: ${{{{ runner.os }}}}-{}-${{{{ hashFiles({}) }}}}\n      \
+        restore-keys: ${{{{ runner.os }}}}-{}-",
+        tool.paths
+            .iter()
+            .map(|p| format!("        {}\n", p))
+            .collect::<String>(),
+        key_prefix,
+        tool.key_files,
+        key_prefix
+    ));
+
+    Finding::builder(
+        FindingCategory::MissingCache,
+        format!("No dependency caching for {}", tool.name),
+    )
+    .severity(Severity::High)
+    .description(format!(
+        "Job '{}' runs a {} build without caching {}, so dependencies are downloaded \
+        and compiled from scratch on every run.",
+        job.id,
+        tool.name,
+        tool.paths.join(" or ")
+    ))
+    .affected_jobs([job.id.as_str()])
+    .recommendation(recommendation)
+    .fix_command("pipelinex optimize --apply cache")
+    .savings_secs(Some(tool.savings_secs))
+    .confidence(0.90)
+    .auto_fixable(true)
+    .build()
 }
 
 #[cfg(test)]
@@ -212,4 +425,173 @@ jobs:
             .collect();
         assert!(npm_findings.is_empty());
     }
+
+    #[test]
+    fn test_gradle_build_without_cache_is_flagged() {
+        let yaml = r#"
+name: CI
+on: push
+jobs:
+  jvm:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - uses: actions/setup-java@v4
+        with:
+          distribution: temurin
+          java-version: 21
+      - run: ./gradlew build
+      - run: ./gradlew test
+  cached-jvm:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/setup-java@v4
+        with:
+          distribution: temurin
+          java-version: 21
+          cache: maven
+      - run: mvn -B package
+  go:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/setup-go@v3
+      - run: go build ./...
+  go-cached:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/setup-go@v5
+      - run: go test ./...
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let findings = detect_missing_caches(&dag);
+        assert_eq!(findings.len(), 2, "{:#?}", findings);
+
+        let gradle = findings
+            .iter()
+            .find(|f| f.title == "No dependency caching for Gradle")
+            .unwrap();
+        assert_eq!(gradle.affected_jobs, vec!["jvm"]);
+        assert_eq!(gradle.category, FindingCategory::MissingCache);
+        assert!(gradle.recommendation.contains("~/.gradle/caches"));
+        assert!(gradle
+            .recommendation
+            .contains("hashFiles('**/*.gradle*', '**/gradle-wrapper.properties')"));
+
+        let go = findings
+            .iter()
+            .find(|f| f.title == "No dependency caching for Go")
+            .unwrap();
+        assert_eq!(go.affected_jobs, vec!["go"]);
+        assert!(go.recommendation.contains("~/.cache/go-build"));
+    }
+
+    #[test]
+    fn test_cargo_job_is_not_mistaken_for_go_or_jvm() {
+        let yaml = r#"
+name: CI
+on: push
+jobs:
+  rust:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - run: cargo build --release
+      - run: cargo test && cargo install cargo-audit
+      - run: echo "see gradle docs" | tee notes.txt
+  go:
+    runs-on: ubuntu-latest
+    steps:
+      - run: cd cmd && go build ./...
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let findings = detect_missing_caches(&dag);
+
+        for tool in ["Gradle", "Maven"] {
+            let title = format!("No dependency caching for {}", tool);
+            assert!(
+                !findings.iter().any(|f| f.title == title),
+                "{:#?}",
+                findings
+            );
+        }
+        let go = findings
+            .iter()
+            .find(|f| f.title == "No dependency caching for Go")
+            .unwrap();
+        assert_eq!(go.affected_jobs, vec!["go"]);
+    }
+
+    #[test]
+    fn test_static_cache_key_flagged() {
+        let yaml = r#"
+name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - uses: actions/cache@v4
+        with:
+          path: node_modules
+          key: node-modules
+      - run: npm ci
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let findings = detect_static_cache_keys(&dag);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Medium);
+        assert_eq!(findings[0].category, FindingCategory::IneffectiveCache);
+        assert!(findings[0].title.contains("node-modules"));
+
+        let gitlab = r#"
+build:
+  script: npm ci
+  cache:
+    key: deps
+    paths: [node_modules/]
+"#;
+        let dag =
+            crate::parser::gitlab::GitLabCIParser::parse(gitlab, ".gitlab-ci.yml".to_string())
+                .unwrap();
+        assert_eq!(detect_static_cache_keys(&dag).len(), 1);
+    }
+
+    #[test]
+    fn test_hashed_cache_key_not_flagged() {
+        let yaml = r#"
+name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - uses: actions/cache@v4
+        with:
+          path: node_modules
+          key: ${{ runner.os }}-node-${{ hashFiles('**/package-lock.json') }}
+          restore-keys: |
+            ${{ runner.os }}-node-
+      - run: npm ci
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let cache = &dag.get_job("build").unwrap().caches[0];
+        assert_eq!(cache.path, "node_modules");
+        assert_eq!(cache.restore_keys, vec!["${{ runner.os }}-node-"]);
+        assert!(detect_static_cache_keys(&dag).is_empty());
+
+        let gitlab = r#"
+build:
+  script: npm ci
+  cache:
+    key:
+      files: [package-lock.json]
+    paths: [node_modules/]
+"#;
+        let dag =
+            crate::parser::gitlab::GitLabCIParser::parse(gitlab, ".gitlab-ci.yml".to_string())
+                .unwrap();
+        assert!(detect_static_cache_keys(&dag).is_empty());
+    }
 }
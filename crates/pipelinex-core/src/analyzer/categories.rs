@@ -0,0 +1,283 @@
+//! Canonical metadata for every [`FindingCategory`].
+//!
+//! Renderers (text, SARIF, HTML, `--explain`) and plugins look categories up
+//! here instead of keeping their own tables, so a new category is added in
+//! one place.
+
+use crate::analyzer::report::{FindingCategory, Severity};
+
+/// Display and documentation details for a finding category.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategoryMetadata {
+    pub category: FindingCategory,
+    /// Stable snake_case id, used by plugins and in documentation anchors.
+    pub id: &'static str,
+    /// Human-readable name.
+    pub label: &'static str,
+    /// Severity a finding gets unless its analyzer decides otherwise.
+    pub default_severity: Severity,
+    /// Where to read more: the category's section of `docs/FINDINGS.md`.
+    pub help_url: &'static str,
+    /// One-line "why this matters", shown by `analyze --explain`.
+    pub rationale: &'static str,
+}
+
+/// Every category, in declaration order.
+pub const CATEGORIES: &[CategoryMetadata] = &[
+    CategoryMetadata {
+        category: FindingCategory::CriticalPath,
+        id: "critical_path",
+        label: "Critical Path Bottleneck",
+        default_severity: Severity::High,
+        help_url: "https://github.com/mackeh/PipelineX/blob/main/docs/FINDINGS.md#critical_path",
+        rationale: "The critical path sets the wall-clock time of every run; shortening anything else does not make the pipeline finish sooner.",
+    },
+    CategoryMetadata {
+        category: FindingCategory::MissingCache,
+        id: "missing_cache",
+        label: "Missing Dependency Cache",
+        default_severity: Severity::High,
+        help_url: "https://github.com/mackeh/PipelineX/blob/main/docs/FINDINGS.md#missing_cache",
+        rationale: "Without a cache, every run downloads and installs the same dependencies again, usually the slowest part of a job.",
+    },
+    CategoryMetadata {
+        category: FindingCategory::IneffectiveCache,
+        id: "ineffective_cache",
+        label: "Ineffective Cache Key",
+        default_severity: Severity::Medium,
+        help_url: "https://github.com/mackeh/PipelineX/blob/main/docs/FINDINGS.md#ineffective_cache",
+        rationale: "A cache whose key never changes restores stale dependencies, so builds drift from the lockfile or miss the cache entirely.",
+    },
+    CategoryMetadata {
+        category: FindingCategory::SerialBottleneck,
+        id: "serial_bottleneck",
+        label: "Serial Bottleneck",
+        default_severity: Severity::High,
+        help_url: "https://github.com/mackeh/PipelineX/blob/main/docs/FINDINGS.md#serial_bottleneck",
+        rationale: "Jobs that wait on work they do not use add their predecessor's full duration to every run.",
+    },
+    CategoryMetadata {
+        category: FindingCategory::SerialChain,
+        id: "serial_chain",
+        label: "Serial Dependency Chain",
+        default_severity: Severity::Medium,
+        help_url: "https://github.com/mackeh/PipelineX/blob/main/docs/FINDINGS.md#serial_chain",
+        rationale: "A long chain of dependent jobs runs one at a time even where the jobs could overlap.",
+    },
+    CategoryMetadata {
+        category: FindingCategory::MissingPathFilter,
+        id: "missing_path_filter",
+        label: "Missing Path Filter",
+        default_severity: Severity::Medium,
+        help_url: "https://github.com/mackeh/PipelineX/blob/main/docs/FINDINGS.md#missing_path_filter",
+        rationale: "Without path filters, documentation-only changes trigger the full pipeline and burn runner minutes.",
+    },
+    CategoryMetadata {
+        category: FindingCategory::ShallowClone,
+        id: "shallow_clone",
+        label: "Full Git Clone",
+        default_severity: Severity::Low,
+        help_url: "https://github.com/mackeh/PipelineX/blob/main/docs/FINDINGS.md#shallow_clone",
+        rationale: "Fetching the full git history costs time on every job, and most jobs only need the latest commit.",
+    },
+    CategoryMetadata {
+        category: FindingCategory::RedundantSteps,
+        id: "redundant_steps",
+        label: "Redundant Steps",
+        default_severity: Severity::Low,
+        help_url: "https://github.com/mackeh/PipelineX/blob/main/docs/FINDINGS.md#redundant_steps",
+        rationale: "Repeating the same setup or upload across jobs pays for identical work several times per run.",
+    },
+    CategoryMetadata {
+        category: FindingCategory::DockerOptimization,
+        id: "docker_optimization",
+        label: "Docker Build Optimization",
+        default_severity: Severity::High,
+        help_url: "https://github.com/mackeh/PipelineX/blob/main/docs/FINDINGS.md#docker_optimization",
+        rationale: "Image builds without layer reuse rebuild every layer from scratch on ephemeral runners.",
+    },
+    CategoryMetadata {
+        category: FindingCategory::MatrixOptimization,
+        id: "matrix_optimization",
+        label: "Matrix Strategy Optimization",
+        default_severity: Severity::Medium,
+        help_url: "https://github.com/mackeh/PipelineX/blob/main/docs/FINDINGS.md#matrix_optimization",
+        rationale: "Each matrix leg is a full job, so unneeded combinations multiply runner cost and queue time.",
+    },
+    CategoryMetadata {
+        category: FindingCategory::FlakyTest,
+        id: "flaky_test",
+        label: "Flaky Test",
+        default_severity: Severity::High,
+        help_url: "https://github.com/mackeh/PipelineX/blob/main/docs/FINDINGS.md#flaky_test",
+        rationale: "Flaky tests force re-runs and teach the team to ignore red builds.",
+    },
+    CategoryMetadata {
+        category: FindingCategory::ConcurrencyControl,
+        id: "concurrency_control",
+        label: "Missing Concurrency Control",
+        default_severity: Severity::Low,
+        help_url: "https://github.com/mackeh/PipelineX/blob/main/docs/FINDINGS.md#concurrency_control",
+        rationale: "Without concurrency control, superseded runs keep going after a newer push and occupy runners.",
+    },
+    CategoryMetadata {
+        category: FindingCategory::ArtifactReuse,
+        id: "artifact_reuse",
+        label: "Missing Artifact Reuse",
+        default_severity: Severity::Medium,
+        help_url: "https://github.com/mackeh/PipelineX/blob/main/docs/FINDINGS.md#artifact_reuse",
+        rationale: "Rebuilding what an earlier job already produced repeats work that could be passed along as an artifact.",
+    },
+    CategoryMetadata {
+        category: FindingCategory::RunnerSizing,
+        id: "runner_sizing",
+        label: "Runner Right-Sizing",
+        default_severity: Severity::Medium,
+        help_url: "https://github.com/mackeh/PipelineX/blob/main/docs/FINDINGS.md#runner_sizing",
+        rationale: "A runner that does not match the job's load either wastes money or slows the job down.",
+    },
+    CategoryMetadata {
+        category: FindingCategory::RunnerPlatform,
+        id: "runner_platform",
+        label: "Costly Runner Platform",
+        default_severity: Severity::Medium,
+        help_url: "https://github.com/mackeh/PipelineX/blob/main/docs/FINDINGS.md#runner_platform",
+        rationale: "macOS and Windows runners bill at a multiple of Linux, so jobs that do not need them cost more for no benefit.",
+    },
+    CategoryMetadata {
+        category: FindingCategory::UnlockedInstall,
+        id: "unlocked_install",
+        label: "Non-Deterministic Install",
+        default_severity: Severity::Medium,
+        help_url: "https://github.com/mackeh/PipelineX/blob/main/docs/FINDINGS.md#unlocked_install",
+        rationale: "Installs that ignore the lockfile resolve different versions from run to run, so builds are slower and not reproducible.",
+    },
+    CategoryMetadata {
+        category: FindingCategory::FailureHandling,
+        id: "failure_handling",
+        label: "Failure Handling",
+        default_severity: Severity::Medium,
+        help_url: "https://github.com/mackeh/PipelineX/blob/main/docs/FINDINGS.md#failure_handling",
+        rationale: "Failure settings decide whether problems surface early or get hidden, and whether a single failure wastes the rest of the run.",
+    },
+    CategoryMetadata {
+        category: FindingCategory::ReleaseOnlyWork,
+        id: "release_only_work",
+        label: "Release-Only Work on Every Push",
+        default_severity: Severity::Medium,
+        help_url: "https://github.com/mackeh/PipelineX/blob/main/docs/FINDINGS.md#release_only_work",
+        rationale: "End-to-end and integration suites cost the most per run, and running them on every push pays that price for changes that will never ship on their own.",
+    },
+    CategoryMetadata {
+        category: FindingCategory::Maintainability,
+        id: "maintainability",
+        label: "Maintainability",
+        default_severity: Severity::Low,
+        help_url: "https://github.com/mackeh/PipelineX/blob/main/docs/FINDINGS.md#maintainability",
+        rationale: "Settings copied across jobs drift apart when only some copies get updated, so jobs quietly stop testing the same thing.",
+    },
+    CategoryMetadata {
+        category: FindingCategory::Security,
+        id: "security",
+        label: "Security",
+        default_severity: Severity::High,
+        help_url: "https://github.com/mackeh/PipelineX/blob/main/docs/FINDINGS.md#security",
+        rationale: "CI jobs hold credentials and publish artifacts, so weaknesses here are a path into production.",
+    },
+    CategoryMetadata {
+        category: FindingCategory::Policy,
+        id: "policy",
+        label: "Policy Violation",
+        default_severity: Severity::Medium,
+        help_url: "https://github.com/mackeh/PipelineX/blob/main/docs/FINDINGS.md#policy",
+        rationale: "The pipeline breaks a rule your team agreed on in its PipelineX policy file.",
+    },
+    CategoryMetadata {
+        category: FindingCategory::CustomPlugin,
+        id: "custom_plugin",
+        label: "Custom Plugin",
+        default_severity: Severity::Info,
+        help_url: "https://github.com/mackeh/PipelineX/blob/main/docs/FINDINGS.md#custom_plugin",
+        rationale: "Reported by a custom analyzer plugin configured for this repository.",
+    },
+];
+
+/// Metadata for `category`.
+pub fn category_metadata(category: &FindingCategory) -> &'static CategoryMetadata {
+    CATEGORIES
+        .iter()
+        .find(|m| m.category == *category)
+        .expect("every FindingCategory has an entry in CATEGORIES")
+}
+
+impl FindingCategory {
+    /// The category with id `id`, ignoring case and underscores
+    /// (`missing_cache`, `MissingCache` and `missingcache` all match).
+    pub fn from_id(id: &str) -> Option<Self> {
+        let normalize = |s: &str| s.replace('_', "").to_lowercase();
+        let id = normalize(id.trim());
+        CATEGORIES
+            .iter()
+            .find(|m| normalize(m.id) == id)
+            .map(|m| m.category.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_every_category_has_metadata() {
+        use FindingCategory::*;
+        let all = [
+            CriticalPath,
+            MissingCache,
+            IneffectiveCache,
+            SerialBottleneck,
+            SerialChain,
+            MissingPathFilter,
+            ShallowClone,
+            RedundantSteps,
+            DockerOptimization,
+            MatrixOptimization,
+            FlakyTest,
+            ConcurrencyControl,
+            ArtifactReuse,
+            RunnerSizing,
+            RunnerPlatform,
+            UnlockedInstall,
+            FailureHandling,
+            ReleaseOnlyWork,
+            Maintainability,
+            Security,
+            Policy,
+            CustomPlugin,
+        ];
+        for category in &all {
+            assert_eq!(category_metadata(category).category, *category);
+        }
+        let ids: HashSet<_> = CATEGORIES.iter().map(|m| m.id).collect();
+        assert_eq!(ids.len(), all.len());
+        assert_eq!(CATEGORIES.len(), all.len());
+
+        for meta in CATEGORIES {
+            // Lookup round-trips, so no category is listed under another's entry
+            assert_eq!(category_metadata(&meta.category), meta);
+            assert_eq!(
+                FindingCategory::from_id(meta.id),
+                Some(meta.category.clone())
+            );
+            assert!(!meta.label.is_empty());
+            assert!(!meta.rationale.is_empty());
+            assert!(meta.help_url.ends_with(&format!("#{}", meta.id)));
+        }
+
+        assert_eq!(
+            FindingCategory::from_id("MissingCache"),
+            Some(FindingCategory::MissingCache)
+        );
+        assert_eq!(FindingCategory::from_id("unknown"), None);
+    }
+}
@@ -0,0 +1,753 @@
+use crate::parser::dag::{JobNode, PipelineDag};
+use petgraph::Direction;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+
+/// The event context a pipeline is analyzed under (e.g. a `pull_request` against `main`).
+#[derive(Debug, Clone)]
+pub struct EvaluationContext {
+    /// Triggering event, using GitHub naming (`push`, `pull_request`, `schedule`, ...).
+    pub event: String,
+    /// Branch or tag being built. Accepts short names (`main`) or full refs (`refs/tags/v1`).
+    pub git_ref: Option<String>,
+}
+
+impl EvaluationContext {
+    pub fn new(event: &str, git_ref: Option<&str>) -> Self {
+        Self {
+            event: event.to_string(),
+            git_ref: git_ref.map(String::from),
+        }
+    }
+
+    fn is_pull_request(&self) -> bool {
+        matches!(
+            self.event.as_str(),
+            "pull_request" | "merge_request" | "merge_request_event"
+        )
+    }
+
+    fn full_ref(&self) -> Option<String> {
+        self.git_ref.as_ref().map(|r| {
+            if r.starts_with("refs/") {
+                r.clone()
+            } else {
+                format!("refs/heads/{}", r)
+            }
+        })
+    }
+
+    fn short_ref(&self) -> Option<String> {
+        self.git_ref.as_ref().map(|r| {
+            r.trim_start_matches("refs/heads/")
+                .trim_start_matches("refs/tags/")
+                .to_string()
+        })
+    }
+
+    fn is_tag(&self) -> bool {
+        self.git_ref
+            .as_ref()
+            .is_some_and(|r| r.starts_with("refs/tags/"))
+    }
+}
+
+/// Result of evaluating an expression. `Unknown` covers anything that depends on
+/// runtime state we can't see statically (secrets, outputs, event payloads).
+#[derive(Debug, Clone, PartialEq)]
+enum Val {
+    Str(String),
+    Bool(bool),
+    Null,
+    Unknown,
+}
+
+impl Val {
+    fn truthy(&self) -> Option<bool> {
+        match self {
+            Val::Str(s) => Some(!s.is_empty()),
+            Val::Bool(b) => Some(*b),
+            Val::Null => Some(false),
+            Val::Unknown => None,
+        }
+    }
+
+    fn from_truthy(value: Option<bool>) -> Val {
+        value.map(Val::Bool).unwrap_or(Val::Unknown)
+    }
+}
+
+/// Evaluate a job condition (`if:` for GitHub Actions, `rules: if:` for GitLab).
+///
+/// Returns `Some(false)` only when the condition is provably false in `ctx`; anything
+/// that can't be decided statically yields `None` so the job is kept.
+pub fn evaluate_condition(
+    expr: &str,
+    provider: &str,
+    ctx: &EvaluationContext,
+    vars: &HashMap<String, String>,
+) -> Option<bool> {
+    let expr = expr.trim();
+    let expr = expr
+        .strip_prefix("${{")
+        .and_then(|e| e.strip_suffix("}}"))
+        .unwrap_or(expr);
+
+    let tokens = tokenize(expr)?;
+    let mut parser = ExprParser {
+        tokens,
+        pos: 0,
+        resolver: Resolver {
+            provider,
+            ctx,
+            vars,
+        },
+    };
+    let value = parser.parse_or()?;
+    if parser.pos != parser.tokens.len() {
+        return None;
+    }
+    value.truthy()
+}
+
+/// Return a copy of the DAG without jobs that cannot run in `ctx`.
+///
+/// A job is pruned when its own condition is provably false, or when it needs a
+/// pruned job and doesn't opt back in with `always()`.
+pub fn prune_unreachable_jobs(dag: &PipelineDag, ctx: &EvaluationContext) -> PipelineDag {
+    let mut excluded: HashSet<String> = HashSet::new();
+
+    if let Ok(order) = petgraph::algo::toposort(&dag.graph, None) {
+        for idx in order {
+            let job = &dag.graph[idx];
+            let runs_always = job
+                .condition
+                .as_ref()
+                .is_some_and(|c| c.contains("always()"));
+
+            let upstream_excluded = dag
+                .graph
+                .neighbors_directed(idx, Direction::Incoming)
+                .any(|dep| excluded.contains(&dag.graph[dep].id));
+
+            if !is_job_reachable(dag, job, ctx) || (upstream_excluded && !runs_always) {
+                excluded.insert(job.id.clone());
+            }
+        }
+    }
+
+    let mut pruned = PipelineDag::new(
+        dag.name.clone(),
+        dag.source_file.clone(),
+        dag.provider.clone(),
+    );
+    pruned.triggers = dag.triggers.clone();
+    pruned.env = dag.env.clone();
+    pruned.concurrency = dag.concurrency.clone();
+    pruned.permissions = dag.permissions.clone();
+    pruned.external_includes = dag.external_includes.clone();
+
+    for job in dag.graph.node_weights() {
+        if !excluded.contains(&job.id) {
+            let mut job = job.clone();
+            job.needs.retain(|n| !excluded.contains(n));
+            pruned.add_job(job);
+        }
+    }
+
+    for edge in dag.graph.raw_edges() {
+        let from = &dag.graph[edge.source()].id;
+        let to = &dag.graph[edge.target()].id;
+        if let (Some(&a), Some(&b)) = (pruned.node_map.get(from), pruned.node_map.get(to)) {
+            pruned.graph.add_edge(a, b, edge.weight);
+        }
+    }
+
+    pruned
+}
+
+/// Job IDs present in `original` but absent from `pruned`.
+pub fn excluded_jobs(original: &PipelineDag, pruned: &PipelineDag) -> Vec<String> {
+    original
+        .job_ids()
+        .into_iter()
+        .filter(|id| pruned.get_job(id).is_none())
+        .collect()
+}
+
+fn is_job_reachable(dag: &PipelineDag, job: &JobNode, ctx: &EvaluationContext) -> bool {
+    let Some(condition) = &job.condition else {
+        return true;
+    };
+
+    let mut vars = dag.env.clone();
+    vars.extend(
+        job.env
+            .iter()
+            .filter(|(k, _)| !k.starts_with("__"))
+            .map(|(k, v)| (k.clone(), v.clone())),
+    );
+
+    evaluate_condition(condition, &dag.provider, ctx, &vars) != Some(false)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Str(String),
+    Ident(String),
+    Var(String),
+    Regex(String),
+    Eq,
+    Ne,
+    Match,
+    NotMatch,
+    And,
+    Or,
+    Not,
+    LParen,
+    RParen,
+    Comma,
+}
+
+fn tokenize(input: &str) -> Option<Vec<Token>> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '\'' | '"' => {
+                let quote = c;
+                let mut value = String::new();
+                i += 1;
+                loop {
+                    let ch = *chars.get(i)?;
+                    if ch == quote {
+                        // GitHub escapes a single quote by doubling it.
+                        if quote == '\'' && chars.get(i + 1) == Some(&'\'') {
+                            value.push('\'');
+                            i += 2;
+                            continue;
+                        }
+                        i += 1;
+                        break;
+                    }
+                    value.push(ch);
+                    i += 1;
+                }
+                tokens.push(Token::Str(value));
+            }
+            '/' if matches!(tokens.last(), Some(Token::Match | Token::NotMatch)) => {
+                let mut pattern = String::new();
+                i += 1;
+                loop {
+                    let ch = *chars.get(i)?;
+                    if ch == '\\' && chars.get(i + 1) == Some(&'/') {
+                        pattern.push('/');
+                        i += 2;
+                        continue;
+                    }
+                    if ch == '/' {
+                        i += 1;
+                        break;
+                    }
+                    pattern.push(ch);
+                    i += 1;
+                }
+                if chars.get(i) == Some(&'i') {
+                    pattern = format!("(?i){}", pattern);
+                    i += 1;
+                }
+                tokens.push(Token::Regex(pattern));
+            }
+            '$' => {
+                i += 1;
+                let braced = chars.get(i) == Some(&'{');
+                if braced {
+                    i += 1;
+                }
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                if start == i {
+                    return None;
+                }
+                tokens.push(Token::Var(chars[start..i].iter().collect()));
+                if braced {
+                    if chars.get(i) != Some(&'}') {
+                        return None;
+                    }
+                    i += 1;
+                }
+            }
+            '=' | '!' | '&' | '|' => {
+                let next = chars.get(i + 1).copied();
+                let token = match (c, next) {
+                    ('=', Some('=')) => Token::Eq,
+                    ('=', Some('~')) => Token::Match,
+                    ('!', Some('=')) => Token::Ne,
+                    ('!', Some('~')) => Token::NotMatch,
+                    ('&', Some('&')) => Token::And,
+                    ('|', Some('|')) => Token::Or,
+                    ('!', _) => {
+                        tokens.push(Token::Not);
+                        i += 1;
+                        continue;
+                    }
+                    _ => return None,
+                };
+                tokens.push(token);
+                i += 2;
+            }
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            ',' => {
+                tokens.push(Token::Comma);
+                i += 1;
+            }
+            c if c.is_ascii_alphanumeric() || c == '_' => {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_ascii_alphanumeric() || matches!(chars[i], '_' | '-' | '.'))
+                {
+                    i += 1;
+                }
+                tokens.push(Token::Ident(chars[start..i].iter().collect()));
+            }
+            _ => return None,
+        }
+    }
+
+    Some(tokens)
+}
+
+struct Resolver<'a> {
+    provider: &'a str,
+    ctx: &'a EvaluationContext,
+    vars: &'a HashMap<String, String>,
+}
+
+impl Resolver<'_> {
+    fn context_value(&self, path: &str) -> Val {
+        let ctx = self.ctx;
+        let pr = ctx.is_pull_request();
+        match path {
+            "github.event_name" => Val::Str(ctx.event.clone()),
+            // Pull request runs build a synthetic merge ref, never a branch ref.
+            "github.ref" if pr => Val::Str("refs/pull/0/merge".to_string()),
+            "github.ref" => opt(ctx.full_ref()),
+            "github.ref_name" if pr => Val::Unknown,
+            "github.ref_name" => opt(ctx.short_ref()),
+            "github.ref_type" if pr => Val::Str("branch".to_string()),
+            "github.ref_type" => match ctx.git_ref {
+                Some(_) if ctx.is_tag() => Val::Str("tag".to_string()),
+                Some(_) => Val::Str("branch".to_string()),
+                None => Val::Unknown,
+            },
+            "github.head_ref" if pr => opt(ctx.short_ref()),
+            "github.head_ref" | "github.base_ref" => Val::Str(String::new()),
+            _ => Val::Unknown,
+        }
+    }
+
+    fn variable(&self, name: &str) -> Val {
+        let ctx = self.ctx;
+        let pr = ctx.is_pull_request();
+        match name {
+            "CI_PIPELINE_SOURCE" => Val::Str(match ctx.event.as_str() {
+                "pull_request" | "merge_request" => "merge_request_event".to_string(),
+                other => other.to_string(),
+            }),
+            "CI_COMMIT_BRANCH" if pr || ctx.is_tag() => Val::Null,
+            "CI_COMMIT_BRANCH" | "CI_COMMIT_REF_NAME" => opt(ctx.short_ref()),
+            "CI_COMMIT_TAG" if pr => Val::Null,
+            "CI_COMMIT_TAG" => match ctx.git_ref {
+                Some(_) if ctx.is_tag() => opt(ctx.short_ref()),
+                Some(_) => Val::Null,
+                None => Val::Unknown,
+            },
+            "DRONE_BUILD_EVENT" => Val::Str(
+                match ctx.event.as_str() {
+                    "pull_request" | "merge_request" => "pull_request",
+                    "schedule" => "cron",
+                    "push" if ctx.is_tag() => "tag",
+                    other => other,
+                }
+                .to_string(),
+            ),
+            // Pull requests report their target branch, which the context doesn't carry.
+            "DRONE_BRANCH" if pr || ctx.is_tag() => Val::Unknown,
+            "DRONE_BRANCH" => opt(ctx.short_ref()),
+            "CI_MERGE_REQUEST_SOURCE_BRANCH_NAME" if pr => opt(ctx.short_ref()),
+            "CI_MERGE_REQUEST_ID"
+            | "CI_MERGE_REQUEST_IID"
+            | "CI_MERGE_REQUEST_SOURCE_BRANCH_NAME"
+            | "CI_MERGE_REQUEST_TARGET_BRANCH_NAME"
+                if !pr =>
+            {
+                Val::Null
+            }
+            _ => self
+                .vars
+                .get(name)
+                .filter(|v| !v.contains("${{") && !v.contains('$'))
+                .map(|v| Val::Str(v.clone()))
+                .unwrap_or(Val::Unknown),
+        }
+    }
+
+    fn call(&self, name: &str, args: &[Val]) -> Val {
+        let strings = || -> Option<(String, String)> {
+            match (args.first()?, args.get(1)?) {
+                (Val::Str(a), Val::Str(b)) => Some((a.to_lowercase(), b.to_lowercase())),
+                _ => None,
+            }
+        };
+        match name.to_lowercase().as_str() {
+            "success" | "always" => Val::Bool(true),
+            // Whether an earlier job fails or is cancelled is only known at run time
+            "failure" | "cancelled" => Val::Unknown,
+            "startswith" => strings()
+                .map(|(a, b)| Val::Bool(a.starts_with(&b)))
+                .unwrap_or(Val::Unknown),
+            "endswith" => strings()
+                .map(|(a, b)| Val::Bool(a.ends_with(&b)))
+                .unwrap_or(Val::Unknown),
+            "contains" => strings()
+                .map(|(a, b)| Val::Bool(a.contains(&b)))
+                .unwrap_or(Val::Unknown),
+            _ => Val::Unknown,
+        }
+    }
+}
+
+fn opt(value: Option<String>) -> Val {
+    value.map(Val::Str).unwrap_or(Val::Unknown)
+}
+
+struct ExprParser<'a> {
+    tokens: Vec<Token>,
+    pos: usize,
+    resolver: Resolver<'a>,
+}
+
+impl ExprParser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Option<Val> {
+        let mut left = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            let right = self.parse_and()?;
+            left = Val::from_truthy(match (left.truthy(), right.truthy()) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (Some(false), Some(false)) => Some(false),
+                _ => None,
+            });
+        }
+        Some(left)
+    }
+
+    fn parse_and(&mut self) -> Option<Val> {
+        let mut left = self.parse_comparison()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            let right = self.parse_comparison()?;
+            left = Val::from_truthy(match (left.truthy(), right.truthy()) {
+                (Some(false), _) | (_, Some(false)) => Some(false),
+                (Some(true), Some(true)) => Some(true),
+                _ => None,
+            });
+        }
+        Some(left)
+    }
+
+    fn parse_comparison(&mut self) -> Option<Val> {
+        let left = self.parse_unary()?;
+        match self.peek() {
+            Some(Token::Eq) | Some(Token::Ne) => {
+                let negate = self.next() == Some(Token::Ne);
+                let right = self.parse_unary()?;
+                Some(match compare(&left, &right, self.resolver.provider) {
+                    Some(eq) => Val::Bool(eq != negate),
+                    None => Val::Unknown,
+                })
+            }
+            Some(Token::Match) | Some(Token::NotMatch) => {
+                let negate = self.next() == Some(Token::NotMatch);
+                let Some(Token::Regex(pattern)) = self.next() else {
+                    return None;
+                };
+                let re = Regex::new(&pattern).ok()?;
+                Some(match left {
+                    Val::Str(s) => Val::Bool(re.is_match(&s) != negate),
+                    Val::Null => Val::Bool(negate),
+                    _ => Val::Unknown,
+                })
+            }
+            _ => Some(left),
+        }
+    }
+
+    fn parse_unary(&mut self) -> Option<Val> {
+        if self.peek() == Some(&Token::Not) {
+            self.pos += 1;
+            let value = self.parse_unary()?;
+            return Some(Val::from_truthy(value.truthy().map(|b| !b)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Option<Val> {
+        match self.next()? {
+            Token::Str(s) => Some(Val::Str(s)),
+            Token::Var(name) => Some(self.resolver.variable(&name)),
+            Token::LParen => {
+                let value = self.parse_or()?;
+                (self.next()? == Token::RParen).then_some(value)
+            }
+            Token::Ident(ident) => match ident.as_str() {
+                "true" => Some(Val::Bool(true)),
+                "false" => Some(Val::Bool(false)),
+                "null" => Some(Val::Null),
+                _ if self.peek() == Some(&Token::LParen) => {
+                    self.pos += 1;
+                    let mut args = Vec::new();
+                    if self.peek() != Some(&Token::RParen) {
+                        loop {
+                            args.push(self.parse_or()?);
+                            if self.peek() == Some(&Token::Comma) {
+                                self.pos += 1;
+                            } else {
+                                break;
+                            }
+                        }
+                    }
+                    (self.next()? == Token::RParen).then(|| self.resolver.call(&ident, &args))
+                }
+                _ if ident.chars().all(|c| c.is_ascii_digit() || c == '.') => Some(Val::Str(ident)),
+                _ => Some(self.resolver.context_value(&ident)),
+            },
+            _ => None,
+        }
+    }
+}
+
+fn compare(left: &Val, right: &Val, provider: &str) -> Option<bool> {
+    match (left, right) {
+        (Val::Unknown, _) | (_, Val::Unknown) => None,
+        // GitHub Actions compares strings case-insensitively; GitLab does not.
+        (Val::Str(a), Val::Str(b)) if provider == "github-actions" => {
+            Some(a.eq_ignore_ascii_case(b))
+        }
+        (a, b) => Some(a == b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::github::GitHubActionsParser;
+    use crate::parser::gitlab::GitLabCIParser;
+
+    fn eval_github(expr: &str, ctx: &EvaluationContext) -> Option<bool> {
+        evaluate_condition(expr, "github-actions", ctx, &HashMap::new())
+    }
+
+    #[test]
+    fn test_github_ref_comparisons() {
+        let push_main = EvaluationContext::new("push", Some("main"));
+        let pr = EvaluationContext::new("pull_request", Some("feature/x"));
+
+        assert_eq!(
+            eval_github("github.ref == 'refs/heads/main'", &push_main),
+            Some(true)
+        );
+        assert_eq!(
+            eval_github("${{ github.ref == 'refs/heads/main' }}", &pr),
+            Some(false)
+        );
+        assert_eq!(
+            eval_github("github.event_name != 'pull_request'", &pr),
+            Some(false)
+        );
+        assert_eq!(
+            eval_github("startsWith(github.ref, 'refs/tags/')", &push_main),
+            Some(false)
+        );
+        assert_eq!(
+            eval_github(
+                "github.event_name == 'push' && github.ref_name == 'main'",
+                &push_main
+            ),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_unknown_expressions_are_undecided() {
+        let pr = EvaluationContext::new("pull_request", None);
+        assert_eq!(eval_github("secrets.DEPLOY_KEY != ''", &pr), None);
+        assert_eq!(
+            eval_github("needs.build.outputs.changed == 'true'", &pr),
+            None
+        );
+        assert_eq!(eval_github("github.event['weird'", &pr), None);
+        // A provably-false clause still decides a conjunction.
+        assert_eq!(
+            eval_github(
+                "needs.build.result == 'success' && github.event_name == 'push'",
+                &pr
+            ),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn test_gitlab_rule_expressions() {
+        let mr = EvaluationContext::new("pull_request", Some("feature/x"));
+        let push_main = EvaluationContext::new("push", Some("main"));
+        let vars = HashMap::new();
+
+        let main_only = "$CI_COMMIT_BRANCH == \"main\"";
+        assert_eq!(
+            evaluate_condition(main_only, "gitlab-ci", &mr, &vars),
+            Some(false)
+        );
+        assert_eq!(
+            evaluate_condition(main_only, "gitlab-ci", &push_main, &vars),
+            Some(true)
+        );
+        assert_eq!(
+            evaluate_condition(
+                "$CI_PIPELINE_SOURCE == \"merge_request_event\"",
+                "gitlab-ci",
+                &mr,
+                &vars
+            ),
+            Some(true)
+        );
+        assert_eq!(
+            evaluate_condition("$CI_COMMIT_TAG", "gitlab-ci", &push_main, &vars),
+            Some(false)
+        );
+        assert_eq!(
+            evaluate_condition(
+                "$CI_COMMIT_REF_NAME =~ /^release\\/.*$/",
+                "gitlab-ci",
+                &push_main,
+                &vars
+            ),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn test_main_only_job_excluded_for_pull_request() {
+        let yaml = r#"
+name: CI
+on: [push, pull_request]
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm run build
+  deploy:
+    needs: build
+    if: github.ref == 'refs/heads/main'
+    runs-on: ubuntu-latest
+    steps:
+      - run: ./deploy.sh
+  notify:
+    needs: deploy
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo done
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+
+        let pr = EvaluationContext::new("pull_request", None);
+        let pruned = prune_unreachable_jobs(&dag, &pr);
+        assert_eq!(pruned.job_ids(), vec!["build".to_string()]);
+        assert_eq!(
+            excluded_jobs(&dag, &pruned),
+            vec!["deploy".to_string(), "notify".to_string()]
+        );
+
+        let push_main = EvaluationContext::new("push", Some("main"));
+        let kept = prune_unreachable_jobs(&dag, &push_main);
+        assert_eq!(kept.job_count(), 3);
+        assert_eq!(kept.graph.edge_count(), 2);
+    }
+
+    #[test]
+    fn test_failure_notification_job_is_kept() {
+        let yaml = r#"
+on: pull_request
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm run build
+  notify:
+    needs: build
+    if: failure()
+    runs-on: ubuntu-latest
+    steps:
+      - run: ./notify.sh
+  cleanup:
+    needs: build
+    if: cancelled() && github.ref == 'refs/heads/main'
+    runs-on: ubuntu-latest
+    steps:
+      - run: ./cleanup.sh
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+
+        let pr = EvaluationContext::new("pull_request", None);
+        let pruned = prune_unreachable_jobs(&dag, &pr);
+        assert_eq!(
+            pruned.job_ids(),
+            vec!["build".to_string(), "notify".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_gitlab_rules_pruned_for_merge_requests() {
+        let yaml = r#"
+stages: [build, deploy]
+build:
+  stage: build
+  script:
+    - make build
+deploy:
+  stage: deploy
+  rules:
+    - if: $CI_COMMIT_BRANCH == "main"
+  script:
+    - make deploy
+"#;
+        let dag = GitLabCIParser::parse(yaml, ".gitlab-ci.yml".to_string()).unwrap();
+        let mr = EvaluationContext::new("merge_request_event", Some("feature/x"));
+        let pruned = prune_unreachable_jobs(&dag, &mr);
+        assert!(pruned.get_job("build").is_some());
+        assert!(pruned.get_job("deploy").is_none());
+    }
+}
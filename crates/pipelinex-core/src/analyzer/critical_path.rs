@@ -1,4 +1,4 @@
-use crate::analyzer::report::{Finding, FindingCategory, Severity};
+use crate::analyzer::report::{Finding, FindingCategory, JobSlack, Severity};
 use crate::parser::dag::{JobNode, PipelineDag};
 use petgraph::graph::NodeIndex;
 use petgraph::Direction;
@@ -25,7 +25,7 @@ pub fn find_critical_path(dag: &PipelineDag) -> (Vec<&JobNode>, f64) {
 
     // Forward pass: compute longest path to each node
     for &node in &topo {
-        let node_duration = graph[node].estimated_duration_secs;
+        let node_duration = graph[node].wall_clock_secs();
         let dist_to_node = longest_dist[&node] + node_duration;
 
         for neighbor in graph.neighbors_directed(node, Direction::Outgoing) {
@@ -39,8 +39,8 @@ pub fn find_critical_path(dag: &PipelineDag) -> (Vec<&JobNode>, f64) {
     // Find the leaf node with the longest total path
     let leaves = dag.leaf_jobs();
     let end_node = leaves.into_iter().max_by(|a, b| {
-        let da = longest_dist[a] + graph[*a].estimated_duration_secs;
-        let db = longest_dist[b] + graph[*b].estimated_duration_secs;
+        let da = longest_dist[a] + graph[*a].wall_clock_secs();
+        let db = longest_dist[b] + graph[*b].wall_clock_secs();
         da.partial_cmp(&db).unwrap_or(std::cmp::Ordering::Equal)
     });
 
@@ -49,7 +49,7 @@ pub fn find_critical_path(dag: &PipelineDag) -> (Vec<&JobNode>, f64) {
         None => return (Vec::new(), 0.0),
     };
 
-    let total_duration = longest_dist[&end_node] + graph[end_node].estimated_duration_secs;
+    let total_duration = longest_dist[&end_node] + graph[end_node].wall_clock_secs();
 
     // Backtrack to build the critical path
     let mut path = vec![end_node];
@@ -65,6 +65,55 @@ pub fn find_critical_path(dag: &PipelineDag) -> (Vec<&JobNode>, f64) {
     (critical_jobs, total_duration)
 }
 
+/// Compute earliest/latest start times and slack for every job (CPM forward and backward pass).
+pub fn compute_job_slack(dag: &PipelineDag) -> Vec<JobSlack> {
+    let graph = &dag.graph;
+    let topo = match petgraph::algo::toposort(graph, None) {
+        Ok(t) => t,
+        Err(_) => return Vec::new(),
+    };
+
+    // Forward pass: earliest start is the latest finish among dependencies.
+    let mut earliest: HashMap<NodeIndex, f64> = HashMap::new();
+    for &node in &topo {
+        let start = graph
+            .neighbors_directed(node, Direction::Incoming)
+            .map(|dep| earliest[&dep] + graph[dep].wall_clock_secs())
+            .fold(0.0, f64::max);
+        earliest.insert(node, start);
+    }
+
+    let project_end = topo
+        .iter()
+        .map(|n| earliest[n] + graph[*n].wall_clock_secs())
+        .fold(0.0, f64::max);
+
+    // Backward pass: latest start is the earliest latest-start among dependents, minus duration.
+    let mut latest: HashMap<NodeIndex, f64> = HashMap::new();
+    for &node in topo.iter().rev() {
+        let finish = graph
+            .neighbors_directed(node, Direction::Outgoing)
+            .map(|next| latest[&next])
+            .fold(project_end, f64::min);
+        latest.insert(node, finish - graph[node].wall_clock_secs());
+    }
+
+    topo.iter()
+        .map(|node| {
+            let job = &graph[*node];
+            let slack = (latest[node] - earliest[node]).max(0.0);
+            JobSlack {
+                job_id: job.id.clone(),
+                duration_secs: job.wall_clock_secs(),
+                earliest_start_secs: earliest[node],
+                latest_start_secs: latest[node],
+                // Round away floating-point noise so critical jobs read as exactly zero.
+                slack_secs: if slack < 1e-6 { 0.0 } else { slack },
+            }
+        })
+        .collect()
+}
+
 /// Generate findings based on critical path analysis.
 pub fn analyze_critical_path(
     dag: &PipelineDag,
@@ -79,12 +128,12 @@ pub fn analyze_critical_path(
 
     // Find the single biggest bottleneck on the critical path
     if let Some(bottleneck) = critical_path.iter().max_by(|a, b| {
-        a.estimated_duration_secs
-            .partial_cmp(&b.estimated_duration_secs)
+        a.wall_clock_secs()
+            .partial_cmp(&b.wall_clock_secs())
             .unwrap()
     }) {
         let pct = if total_duration > 0.0 {
-            bottleneck.estimated_duration_secs / total_duration * 100.0
+            bottleneck.wall_clock_secs() / total_duration * 100.0
         } else {
             0.0
         };
@@ -100,7 +149,10 @@ pub fn analyze_critical_path(
                 description: format!(
                     "Job '{}' takes {:.0}s ({:.1}% of the {:.0}s critical path). \
                     This is the single biggest opportunity to reduce pipeline time.",
-                    bottleneck.id, bottleneck.estimated_duration_secs, pct, total_duration,
+                    bottleneck.id,
+                    bottleneck.wall_clock_secs(),
+                    pct,
+                    total_duration,
                 ),
                 affected_jobs: vec![bottleneck.id.clone()],
                 recommendation: format!(
@@ -112,9 +164,11 @@ pub fn analyze_critical_path(
                     "pipelinex optimize --apply shard --job {}",
                     bottleneck.id
                 )),
-                estimated_savings_secs: Some(bottleneck.estimated_duration_secs * 0.5),
+                estimated_savings_secs: Some(bottleneck.wall_clock_secs() * 0.5),
                 confidence: 0.85,
                 auto_fixable: false,
+                documentation_url: None,
+                blame: None,
             });
         }
     }
@@ -149,6 +203,8 @@ pub fn analyze_critical_path(
             estimated_savings_secs: Some((total_duration - theoretical_min) * 0.3),
             confidence: 0.7,
             auto_fixable: false,
+            documentation_url: None,
+            blame: None,
         });
     }
 
@@ -188,4 +244,83 @@ jobs:
         assert_eq!(path[1].id, "b");
         assert_eq!(path[2].id, "c");
     }
+
+    #[test]
+    fn test_job_slack_diamond() {
+        // a -> {fast, slow} -> d; `fast` can slip by the difference in branch durations.
+        let yaml = r#"
+name: CI
+on: push
+jobs:
+  a:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo a
+  fast:
+    needs: a
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+  slow:
+    needs: a
+    runs-on: ubuntu-latest
+    steps:
+      - run: cargo build --release
+  d:
+    needs: [fast, slow]
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo d
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let slack = compute_job_slack(&dag);
+        let get = |id: &str| slack.iter().find(|s| s.job_id == id).unwrap();
+
+        let fast = dag.get_job("fast").unwrap().estimated_duration_secs;
+        let slow = dag.get_job("slow").unwrap().estimated_duration_secs;
+        assert!(slow > fast);
+        assert_eq!(get("fast").slack_secs, slow - fast);
+        assert_eq!(get("slow").slack_secs, 0.0);
+        assert_eq!(get("a").slack_secs, 0.0);
+        assert_eq!(get("d").slack_secs, 0.0);
+        assert_eq!(
+            get("fast").earliest_start_secs,
+            get("slow").earliest_start_secs
+        );
+    }
+
+    #[test]
+    fn test_max_parallel_runs_matrix_in_waves() {
+        let workflow = |max_parallel: &str| {
+            format!(
+                r#"
+on: push
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    strategy:
+      {}
+      matrix:
+        os: [ubuntu-latest, macos-latest, windows-latest]
+        node: [18, 20]
+    steps:
+      - run: npm test
+"#,
+                max_parallel
+            )
+        };
+        let unbounded = GitHubActionsParser::parse(&workflow(""), "ci.yml".into()).unwrap();
+        let capped =
+            GitHubActionsParser::parse(&workflow("max-parallel: 2"), "ci.yml".into()).unwrap();
+
+        let matrix = capped.get_job("test").unwrap().matrix.as_ref().unwrap();
+        assert_eq!(matrix.max_parallel, Some(2));
+        assert_eq!(matrix.waves(), 3);
+
+        let (_, fully_parallel) = find_critical_path(&unbounded);
+        let (_, in_waves) = find_critical_path(&capped);
+        assert!(fully_parallel > 0.0);
+        assert_eq!(in_waves, fully_parallel * 3.0);
+        assert_eq!(compute_job_slack(&capped)[0].duration_secs, in_waves);
+    }
 }
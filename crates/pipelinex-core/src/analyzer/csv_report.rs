@@ -0,0 +1,83 @@
+use crate::analyzer::report::AnalysisReport;
+
+/// Column header for the findings CSV.
+pub const CSV_HEADER: &str =
+    "file,severity,category,title,affected_jobs,estimated_savings_secs,confidence,auto_fixable";
+
+/// Render findings from one or more reports as CSV (RFC 4180), one row per finding.
+///
+/// Affected jobs are joined with `;` so the column stays a single cell.
+pub fn to_csv(reports: &[AnalysisReport]) -> String {
+    let mut out = String::from(CSV_HEADER);
+    out.push('\n');
+
+    for report in reports {
+        for finding in &report.findings {
+            let row = [
+                escape_field(&report.source_file),
+                finding.severity.symbol().to_string(),
+                format!("{:?}", finding.category),
+                escape_field(&finding.title),
+                escape_field(&finding.affected_jobs.join(";")),
+                finding
+                    .estimated_savings_secs
+                    .map(|s| format!("{:.0}", s))
+                    .unwrap_or_default(),
+                format!("{:.2}", finding.confidence),
+                finding.auto_fixable.to_string(),
+            ];
+            out.push_str(&row.join(","));
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+/// Quote a field if it contains a delimiter, quote, or line break.
+fn escape_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer;
+    use crate::parser::github::GitHubActionsParser;
+
+    #[test]
+    fn test_csv_header_and_quoting() {
+        let yaml = r#"
+name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - run: npm ci
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let mut report = analyzer::analyze(&dag);
+        assert!(!report.findings.is_empty());
+        report.findings[0].title = "Slow, \"cold\" install".to_string();
+
+        let csv = to_csv(&[report]);
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some(CSV_HEADER));
+        let row = lines.next().unwrap();
+        assert!(row.starts_with("ci.yml,"));
+        assert!(row.contains(",\"Slow, \"\"cold\"\" install\","));
+    }
+
+    #[test]
+    fn test_escape_field() {
+        assert_eq!(escape_field("plain"), "plain");
+        assert_eq!(escape_field("a,b"), "\"a,b\"");
+        assert_eq!(escape_field("line\nbreak"), "\"line\nbreak\"");
+    }
+}
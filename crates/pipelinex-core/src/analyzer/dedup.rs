@@ -0,0 +1,159 @@
+//! Collapsing identical findings across files (`analyze --dedup`).
+
+use crate::analyzer::report::{AnalysisReport, Finding};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// One file a deduplicated finding was reported in.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FindingFile {
+    pub file: String,
+    pub affected_jobs: Vec<String>,
+}
+
+/// A finding reported by one or more files.
+///
+/// The flattened finding is the first occurrence; `affected_jobs` on it is
+/// cleared in favour of the per-file lists in `files`, and
+/// `estimated_savings_secs` is the sum over all occurrences.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DedupedFinding {
+    pub fingerprint: String,
+    #[serde(flatten)]
+    pub finding: Finding,
+    pub files: Vec<FindingFile>,
+}
+
+/// Findings from several reports with duplicates merged.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DedupedReport {
+    /// Every analyzed file, including those with no findings.
+    pub source_files: Vec<String>,
+    pub findings: Vec<DedupedFinding>,
+}
+
+/// Stable identity of a finding: its category, title and recommendation.
+///
+/// Affected jobs and savings are deliberately left out, since they are what
+/// differs between copies of the same pattern.
+pub fn finding_fingerprint(finding: &Finding) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(format!("{:?}", finding.category));
+    hasher.update([0]);
+    hasher.update(&finding.title);
+    hasher.update([0]);
+    hasher.update(&finding.recommendation);
+    hex::encode(&hasher.finalize()[..8])
+}
+
+/// Merge the findings of `reports`, keeping first-seen order.
+pub fn dedup_reports(reports: &[AnalysisReport]) -> DedupedReport {
+    let mut findings: Vec<DedupedFinding> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+
+    for report in reports {
+        for finding in &report.findings {
+            let occurrence = FindingFile {
+                file: report.source_file.clone(),
+                affected_jobs: finding.affected_jobs.clone(),
+            };
+            let fingerprint = finding_fingerprint(finding);
+            match index.get(&fingerprint) {
+                Some(&i) => {
+                    let merged = &mut findings[i];
+                    if let Some(secs) = finding.estimated_savings_secs {
+                        *merged.finding.estimated_savings_secs.get_or_insert(0.0) += secs;
+                    }
+                    merged.finding.confidence = merged.finding.confidence.max(finding.confidence);
+                    match merged.files.iter_mut().find(|f| f.file == occurrence.file) {
+                        Some(existing) => {
+                            for job in occurrence.affected_jobs {
+                                if !existing.affected_jobs.contains(&job) {
+                                    existing.affected_jobs.push(job);
+                                }
+                            }
+                        }
+                        None => merged.files.push(occurrence),
+                    }
+                }
+                None => {
+                    index.insert(fingerprint.clone(), findings.len());
+                    let mut finding = finding.clone();
+                    finding.affected_jobs.clear();
+                    findings.push(DedupedFinding {
+                        fingerprint,
+                        finding,
+                        files: vec![occurrence],
+                    });
+                }
+            }
+        }
+    }
+
+    DedupedReport {
+        source_files: reports.iter().map(|r| r.source_file.clone()).collect(),
+        findings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer;
+    use crate::parser::github::GitHubActionsParser;
+
+    const WORKFLOW: &str = r#"
+name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - run: npm ci
+"#;
+
+    #[test]
+    fn test_identical_findings_are_merged() {
+        let a = GitHubActionsParser::parse(WORKFLOW, "a.yml".to_string()).unwrap();
+        let b = GitHubActionsParser::parse(WORKFLOW, "b.yml".to_string()).unwrap();
+        let reports = vec![analyzer::analyze(&a), analyzer::analyze(&b)];
+        let per_file = reports[0].findings.len();
+        assert!(per_file > 0);
+
+        let deduped = dedup_reports(&reports);
+        assert_eq!(deduped.source_files, vec!["a.yml", "b.yml"]);
+        assert_eq!(deduped.findings.len(), per_file);
+
+        for merged in &deduped.findings {
+            let files: Vec<_> = merged.files.iter().map(|f| f.file.as_str()).collect();
+            assert_eq!(files, vec!["a.yml", "b.yml"]);
+            assert!(merged.finding.affected_jobs.is_empty());
+        }
+        let savings = reports[0].findings[0].estimated_savings_secs.unwrap();
+        assert_eq!(
+            deduped.findings[0].finding.estimated_savings_secs,
+            Some(savings * 2.0)
+        );
+
+        let json = serde_json::to_value(&deduped).unwrap();
+        assert!(json["findings"][0]["title"].is_string());
+        assert_eq!(json["findings"][0]["files"][1]["file"], "b.yml");
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_jobs_and_savings() {
+        let dag = GitHubActionsParser::parse(WORKFLOW, "a.yml".to_string()).unwrap();
+        let report = analyzer::analyze(&dag);
+        let original = &report.findings[0];
+
+        let mut moved = original.clone();
+        moved.affected_jobs = vec!["other".to_string()];
+        moved.estimated_savings_secs = Some(1.0);
+        assert_eq!(finding_fingerprint(original), finding_fingerprint(&moved));
+
+        moved.title.push_str(" (changed)");
+        assert_ne!(finding_fingerprint(original), finding_fingerprint(&moved));
+    }
+}
@@ -0,0 +1,266 @@
+use crate::analyzer::report::{Finding, FindingCategory};
+use crate::parser::dag::{JobNode, PipelineDag, StepInfo};
+
+/// Share of a cold image build that layer reuse typically skips: base image
+/// pulls and dependency layers are restored, only the application layers rebuild.
+const LAYER_REUSE_RATIO: f64 = 0.7;
+
+/// Duration assumed for a docker build step with no estimate.
+const DEFAULT_BUILD_SECS: f64 = 300.0;
+
+/// Detect image builds in CI steps that never reuse layers from a previous run.
+///
+/// Covers `docker build` / `docker buildx build` commands without `--cache-from`
+/// or `--cache-to`, and `docker/build-push-action` steps without `cache-from` /
+/// `cache-to` inputs. Ephemeral runners start with an empty layer store, so
+/// these builds are cold every time.
+pub fn detect_missing_registry_cache(dag: &PipelineDag) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for job in dag.graph.node_weights() {
+        for step in &job.steps {
+            if let Some(run) = &step.run {
+                if let Some(finding) = uncached_build_command(dag, job, step, run) {
+                    findings.push(finding);
+                }
+            } else if let Some(finding) = uncached_build_push_action(job, step) {
+                findings.push(finding);
+            }
+        }
+    }
+
+    findings
+}
+
+fn uncached_build_command(
+    dag: &PipelineDag,
+    job: &JobNode,
+    step: &StepInfo,
+    run: &str,
+) -> Option<Finding> {
+    let line = run.lines().find(|l| is_docker_build(l))?;
+    if run.contains("--cache-from") || run.contains("--cache-to") {
+        return None;
+    }
+
+    let cache_ref = format!("{}:buildcache", image_repository(line));
+    let command = with_registry_cache(line.trim().trim_end_matches('\\').trim(), &cache_ref);
+    let alternative = if dag.provider == "github-actions" {
+        " On GitHub Actions, `docker/build-push-action` with `cache-from: type=gha` and \
+        `cache-to: type=gha,mode=max` avoids a registry round-trip."
+    } else {
+        ""
+    };
+
+    Some(
+        Finding::builder(
+            FindingCategory::DockerOptimization,
+            format!("Docker build in '{}' has no registry layer cache", job.id),
+        )
+        .description(format!(
+            "Job '{}' runs `{}` without --cache-from or --cache-to. CI runners start \
+            with an empty layer store, so every base image pull and dependency layer \
+            is rebuilt from scratch.",
+            job.id,
+            line.trim()
+        ))
+        .affected_jobs([job.id.as_str()])
+        .recommendation(format!(
+            "Build with buildx and keep the layer cache in your registry (`{}`), \
+            exporting all intermediate layers with `mode=max`.{}",
+            cache_ref, alternative
+        ))
+        .fix_command(command)
+        .savings_secs(Some(layer_reuse_savings(step)))
+        .confidence(0.85)
+        .build(),
+    )
+}
+
+fn uncached_build_push_action(job: &JobNode, step: &StepInfo) -> Option<Finding> {
+    let uses = step.uses.as_deref()?;
+    if !uses.starts_with("docker/build-push-action") {
+        return None;
+    }
+    if step.with.contains_key("cache-from") || step.with.contains_key("cache-to") {
+        return None;
+    }
+
+    Some(
+        Finding::builder(
+            FindingCategory::DockerOptimization,
+            format!("'{}' in '{}' has no layer cache", uses, job.id),
+        )
+        .description(format!(
+            "Job '{}' builds an image with {} but sets neither cache-from nor \
+            cache-to, so buildx starts from an empty cache on every run.",
+            job.id, uses
+        ))
+        .affected_jobs([job.id.as_str()])
+        .recommendation(
+            "Add the GitHub Actions cache backend to the step inputs:\n\
+            \n  with:\n    cache-from: type=gha\n    cache-to: type=gha,mode=max\n\
+            \nor use `type=registry,ref=<image>:buildcache` to share the cache across \
+            repositories and CI systems.",
+        )
+        .savings_secs(Some(layer_reuse_savings(step)))
+        .confidence(0.85)
+        .build(),
+    )
+}
+
+/// Whether `line` runs `docker build` or `docker buildx build`, matched as
+/// whole words so `docker builder prune` and the like don't count.
+fn is_docker_build(line: &str) -> bool {
+    let line = line.to_lowercase();
+    let words: Vec<&str> = line.split_whitespace().collect();
+    words.iter().enumerate().any(|(i, word)| {
+        *word == "docker"
+            && match words.get(i + 1) {
+                Some(&"build") => true,
+                Some(&"buildx") => words.get(i + 2) == Some(&"build"),
+                _ => false,
+            }
+    })
+}
+
+fn layer_reuse_savings(step: &StepInfo) -> f64 {
+    step.estimated_duration_secs.unwrap_or(DEFAULT_BUILD_SECS) * LAYER_REUSE_RATIO
+}
+
+/// Repository of the first `-t`/`--tag` in a build command, without its tag.
+fn image_repository(line: &str) -> String {
+    let mut tokens = line.split_whitespace();
+    while let Some(token) = tokens.next() {
+        let image = match token {
+            "-t" | "--tag" => tokens.next(),
+            _ => token
+                .strip_prefix("--tag=")
+                .or_else(|| token.strip_prefix("-t=")),
+        };
+        if let Some(image) = image {
+            let image = image.trim_matches(|c| c == '"' || c == '\'');
+            // A ':' after the last '/' starts the tag; earlier ones are a registry port
+            let name_start = image.rfind('/').map(|i| i + 1).unwrap_or(0);
+            return match image[name_start..].find(':') {
+                Some(i) => image[..name_start + i].to_string(),
+                None => image.to_string(),
+            };
+        }
+    }
+    "<registry>/<image>".to_string()
+}
+
+/// Rewrite `docker build ...` as a buildx build reading and writing `cache_ref`.
+fn with_registry_cache(line: &str, cache_ref: &str) -> String {
+    let flags = format!(
+        "--cache-from type=registry,ref={0} --cache-to type=registry,ref={0},mode=max",
+        cache_ref
+    );
+    for prefix in ["docker buildx build", "docker build"] {
+        if let Some(i) = line.find(prefix) {
+            return format!(
+                "{}docker buildx build {}{}",
+                &line[..i],
+                flags,
+                &line[i + prefix.len()..]
+            );
+        }
+    }
+    format!("docker buildx build {} .", flags)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::github::GitHubActionsParser;
+
+    #[test]
+    fn test_docker_build_without_cache_recommends_registry_cache() {
+        let yaml = r#"
+on: push
+jobs:
+  image:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - run: docker build -t ghcr.io/acme/app:${{ github.sha }} .
+  cached:
+    runs-on: ubuntu-latest
+    steps:
+      - run: |
+          docker buildx build \
+            --cache-from type=gha --cache-to type=gha,mode=max \
+            -t app .
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let findings = detect_missing_registry_cache(&dag);
+        assert_eq!(findings.len(), 1);
+
+        let finding = &findings[0];
+        assert_eq!(finding.affected_jobs, vec!["image"]);
+        assert_eq!(finding.category, FindingCategory::DockerOptimization);
+        assert_eq!(
+            finding.fix_command.as_deref(),
+            Some(
+                "docker buildx build \
+                --cache-from type=registry,ref=ghcr.io/acme/app:buildcache \
+                --cache-to type=registry,ref=ghcr.io/acme/app:buildcache,mode=max \
+                -t ghcr.io/acme/app:${{ github.sha }} ."
+            )
+        );
+        assert_eq!(
+            finding.estimated_savings_secs,
+            Some(300.0 * LAYER_REUSE_RATIO)
+        );
+        assert!(finding.recommendation.contains("type=gha"));
+    }
+
+    #[test]
+    fn test_build_push_action_cache_inputs() {
+        let yaml = r#"
+on: push
+jobs:
+  cold:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: docker/build-push-action@v6
+        with:
+          push: true
+          tags: ghcr.io/acme/app:latest
+  warm:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: docker/build-push-action@v6
+        with:
+          cache-from: type=gha
+          cache-to: type=gha,mode=max
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let findings = detect_missing_registry_cache(&dag);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].affected_jobs, vec!["cold"]);
+        assert!(findings[0]
+            .recommendation
+            .contains("cache-to: type=gha,mode=max"));
+    }
+
+    #[test]
+    fn test_is_docker_build_matches_whole_words() {
+        assert!(is_docker_build("docker build -t app ."));
+        assert!(is_docker_build("sudo docker buildx build --push ."));
+        assert!(!is_docker_build("docker builder prune -af"));
+        assert!(!is_docker_build("docker buildx create --use"));
+        assert!(!is_docker_build("docker buildx bake"));
+    }
+
+    #[test]
+    fn test_image_repository() {
+        assert_eq!(image_repository("docker build -t app:1.0 ."), "app");
+        assert_eq!(
+            image_repository("docker build --tag=localhost:5000/team/app ."),
+            "localhost:5000/team/app"
+        );
+        assert_eq!(image_repository("docker build ."), "<registry>/<image>");
+    }
+}
@@ -0,0 +1,124 @@
+//! Best-effort `env:` expansion in `run:` commands before analysis.
+
+use crate::parser::dag::PipelineDag;
+use regex::Regex;
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// Nested references (`A=$B`) are followed this many levels deep.
+const MAX_PASSES: usize = 3;
+
+/// Expand `$NAME` and `${NAME}` in every step's `run:` text using the job's
+/// `env:` over the workflow's, so command-based detectors see
+/// `run: $INSTALL_CMD` with `INSTALL_CMD: npm ci` as `npm ci`.
+///
+/// Only literal values are substituted: anything containing an expression
+/// (`${{ ... }}`), command substitution or a secret reference stays as written,
+/// as do variables the pipeline does not define. Borrows `dag` unchanged when
+/// nothing expands.
+pub fn expand_run_env(dag: &PipelineDag) -> Cow<'_, PipelineDag> {
+    let reference =
+        Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)\}|\$([A-Za-z_][A-Za-z0-9_]*)").unwrap();
+    let mut expanded: Option<PipelineDag> = None;
+
+    for idx in dag.graph.node_indices() {
+        let job = &dag.graph[idx];
+        let mut env: HashMap<&str, &str> = literal_values(&dag.env).collect();
+        env.extend(literal_values(&job.env));
+        if env.is_empty() {
+            continue;
+        }
+
+        for (step_index, step) in job.steps.iter().enumerate() {
+            let Some(run) = &step.run else { continue };
+            let mut text = run.clone();
+            for _ in 0..MAX_PASSES {
+                let next = reference.replace_all(&text, |caps: &regex::Captures| {
+                    let name = caps.get(1).or_else(|| caps.get(2)).unwrap().as_str();
+                    match env.get(name) {
+                        Some(value) => value.to_string(),
+                        None => caps[0].to_string(),
+                    }
+                });
+                if next == text {
+                    break;
+                }
+                text = next.into_owned();
+            }
+
+            if text != *run {
+                let dag = expanded.get_or_insert_with(|| dag.clone());
+                dag.graph[idx].steps[step_index].run = Some(text);
+            }
+        }
+    }
+
+    match expanded {
+        Some(dag) => Cow::Owned(dag),
+        None => Cow::Borrowed(dag),
+    }
+}
+
+fn literal_values(env: &HashMap<String, String>) -> impl Iterator<Item = (&str, &str)> {
+    env.iter()
+        .filter(|(name, value)| !name.starts_with("__") && is_literal(value))
+        .map(|(name, value)| (name.as_str(), value.as_str()))
+}
+
+fn is_literal(value: &str) -> bool {
+    !value.contains("${{")
+        && !value.contains("$(")
+        && !value.contains('`')
+        && !value.to_lowercase().contains("secret")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::cache_detector::detect_missing_caches;
+    use crate::analyzer::report::FindingCategory;
+    use crate::parser::github::GitHubActionsParser;
+
+    #[test]
+    fn test_env_indirected_install_is_expanded() {
+        let yaml = r#"
+on: push
+env:
+  INSTALL_CMD: npm ci
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    env:
+      TOKEN: ${{ secrets.NPM_TOKEN }}
+      BUILD: npm run ${TARGET}
+      TARGET: build
+    steps:
+      - uses: actions/checkout@v4
+      - run: $INSTALL_CMD
+      - run: $BUILD && echo $TOKEN $UNDEFINED
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        assert!(detect_missing_caches(&dag).is_empty());
+
+        let expanded = expand_run_env(&dag);
+        let steps = &expanded.get_job("build").unwrap().steps;
+        assert_eq!(steps[1].run.as_deref(), Some("npm ci"));
+        assert_eq!(
+            steps[2].run.as_deref(),
+            Some("npm run build && echo $TOKEN $UNDEFINED")
+        );
+
+        let findings = detect_missing_caches(&expanded);
+        assert!(findings
+            .iter()
+            .any(|f| f.category == FindingCategory::MissingCache));
+    }
+
+    #[test]
+    fn test_no_env_borrows_dag() {
+        let yaml =
+            "on: push\njobs:\n  a:\n    runs-on: ubuntu-latest\n    steps: [{ run: echo $HOME }]\n";
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        assert!(matches!(expand_run_env(&dag), Cow::Borrowed(_)));
+    }
+}
@@ -0,0 +1,206 @@
+use crate::analyzer::report::{Finding, FindingCategory, Severity};
+use crate::parser::dag::{JobNode, PipelineDag};
+
+/// Matrices with at least this many legs are candidates for `fail-fast: false`.
+const LONG_MATRIX_MIN_LEGS: usize = 4;
+/// ...and at least this much total runner time across all legs.
+const LONG_MATRIX_MIN_RUNNER_SECS: f64 = 1800.0;
+
+/// Detect failure-handling settings that hide failures or waste matrix runs.
+pub fn detect_failure_handling(dag: &PipelineDag) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for job in dag.graph.node_weights() {
+        if job.continue_on_error && !is_experimental(job) {
+            findings.push(continue_on_error_finding(job, dag.provider == "gitlab-ci"));
+        }
+        // Only GitHub Actions cancels sibling matrix legs on failure; GitLab
+        // `parallel:` jobs already run to completion independently
+        if dag.provider != "github-actions" {
+            continue;
+        }
+        if let Some(finding) = fail_fast_finding(job) {
+            findings.push(finding);
+        }
+    }
+
+    findings
+}
+
+/// Jobs named as experimental (e.g. `test-experimental`) are expected to fail sometimes.
+fn is_experimental(job: &JobNode) -> bool {
+    job.id.to_lowercase().contains("experimental")
+        || job.name.to_lowercase().contains("experimental")
+}
+
+fn continue_on_error_finding(job: &JobNode, gitlab: bool) -> Finding {
+    let setting = if gitlab {
+        "allow_failure"
+    } else {
+        "continue-on-error"
+    };
+    let recommendation = if gitlab {
+        "Remove `allow_failure: true` and fix or quarantine the failing checks. To tolerate \
+            only known failures, use `allow_failure: exit_codes: [...]`."
+    } else {
+        "Remove `continue-on-error: true` and fix or quarantine the failing \
+            checks. To allow failures only for experimental legs, use an expression such as \
+            `continue-on-error: ${{ matrix.experimental }}`."
+    };
+    Finding {
+        severity: Severity::Medium,
+        category: FindingCategory::FailureHandling,
+        title: format!("Job '{}' has {}: true", job.id, setting),
+        description: format!(
+            "Job '{}' is marked {}, so the workflow reports success even \
+            when it fails. This hides failures: regressions in this job go unnoticed \
+            until something downstream breaks.",
+            job.id, setting
+        ),
+        affected_jobs: vec![job.id.clone()],
+        recommendation: recommendation.to_string(),
+        fix_command: None,
+        estimated_savings_secs: None,
+        confidence: 0.80,
+        auto_fixable: false,
+        documentation_url: None,
+        blame: None,
+    }
+}
+
+fn fail_fast_finding(job: &JobNode) -> Option<Finding> {
+    let matrix = job.matrix.as_ref()?;
+    // GitHub Actions defaults `fail-fast` to true
+    if matrix.fail_fast == Some(false) || job.continue_on_error {
+        return None;
+    }
+    let legs = matrix.total_combinations;
+    let runner_secs = job.estimated_duration_secs * legs as f64;
+    if legs < LONG_MATRIX_MIN_LEGS || runner_secs < LONG_MATRIX_MIN_RUNNER_SECS {
+        return None;
+    }
+
+    let setting = if matrix.fail_fast == Some(true) {
+        "fail-fast: true"
+    } else {
+        "the default fail-fast"
+    };
+    Some(Finding {
+        severity: Severity::Low,
+        category: FindingCategory::FailureHandling,
+        title: format!(
+            "Matrix in '{}' cancels {} legs on first failure",
+            job.id, legs
+        ),
+        description: format!(
+            "Job '{}' runs {} matrix legs (~{:.0} runner-minutes) with {}. One failing leg \
+            cancels the rest, throwing away their partial work and hiding whether the \
+            failure is specific to one configuration, so the whole matrix usually has to be \
+            re-run.",
+            job.id,
+            legs,
+            runner_secs / 60.0,
+            setting
+        ),
+        affected_jobs: vec![job.id.clone()],
+        recommendation: "Set `fail-fast: false` under `strategy:` so every leg reports its \
+            own result:\n\n  strategy:\n    fail-fast: false"
+            .to_string(),
+        fix_command: None,
+        estimated_savings_secs: None,
+        confidence: 0.60,
+        auto_fixable: false,
+        documentation_url: None,
+        blame: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::github::GitHubActionsParser;
+    use crate::parser::gitlab::GitLabCIParser;
+
+    #[test]
+    fn test_continue_on_error_flagged() {
+        let yaml = r#"
+on: push
+jobs:
+  lint:
+    runs-on: ubuntu-latest
+    continue-on-error: true
+    steps: [{ run: npm run lint }]
+  experimental:
+    runs-on: ubuntu-latest
+    continue-on-error: true
+    steps: [{ run: npm test }]
+  matrix-opt-in:
+    runs-on: ubuntu-latest
+    continue-on-error: ${{ matrix.experimental }}
+    steps: [{ run: npm test }]
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let findings = detect_failure_handling(&dag);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].affected_jobs, vec!["lint"]);
+        assert_eq!(findings[0].severity, Severity::Medium);
+        assert!(findings[0].description.contains("hides failures"));
+    }
+
+    #[test]
+    fn test_fail_fast_matrix_flagged() {
+        let yaml = r#"
+on: push
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    strategy:
+      fail-fast: true
+      matrix:
+        os: [ubuntu-latest, macos-latest, windows-latest]
+        node: [18, 20]
+    steps:
+      - uses: actions/checkout@v4
+      - run: npm ci
+      - run: npm test
+  test-no-ff:
+    runs-on: ubuntu-latest
+    strategy:
+      fail-fast: false
+      matrix:
+        os: [ubuntu-latest, macos-latest, windows-latest]
+        node: [18, 20]
+    steps:
+      - uses: actions/checkout@v4
+      - run: npm ci
+      - run: npm test
+"#;
+        let mut dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        for job in dag.graph.node_weights_mut() {
+            job.estimated_duration_secs = 600.0;
+        }
+        let test = dag.get_job("test").unwrap();
+        assert_eq!(test.matrix.as_ref().unwrap().fail_fast, Some(true));
+
+        let findings = detect_failure_handling(&dag);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].affected_jobs, vec!["test"]);
+        assert!(findings[0].recommendation.contains("fail-fast: false"));
+    }
+
+    #[test]
+    fn test_gitlab_parallel_job_gets_no_fail_fast_advice() {
+        let yaml = r#"
+test:
+  parallel: 40
+  script: [npm ci, npm test]
+"#;
+        let dag = GitLabCIParser::parse(yaml, ".gitlab-ci.yml".to_string()).unwrap();
+        let job = dag.get_job("test").unwrap();
+        let legs = job.matrix.as_ref().unwrap().total_combinations;
+        assert!(legs >= LONG_MATRIX_MIN_LEGS);
+        assert!(job.estimated_duration_secs * legs as f64 >= LONG_MATRIX_MIN_RUNNER_SECS);
+
+        assert!(detect_failure_handling(&dag).is_empty());
+    }
+}
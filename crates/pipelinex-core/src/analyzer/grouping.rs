@@ -0,0 +1,190 @@
+//! Grouping findings for display (`analyze --group-by`).
+
+use crate::analyzer::report::{Finding, Severity};
+
+/// Group key for [`group_findings`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    Category,
+    Severity,
+    Job,
+}
+
+impl GroupBy {
+    pub const ALL: [GroupBy; 3] = [GroupBy::Category, GroupBy::Severity, GroupBy::Job];
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            GroupBy::Category => "category",
+            GroupBy::Severity => "severity",
+            GroupBy::Job => "job",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        let id = id.trim().to_lowercase();
+        Self::ALL.into_iter().find(|g| g.id() == id)
+    }
+}
+
+/// Findings sharing one group key, with subtotals.
+#[derive(Debug, Clone)]
+pub struct FindingGroup<'a> {
+    pub label: String,
+    pub findings: Vec<&'a Finding>,
+    /// Sum of `estimated_savings_secs` over the group's findings.
+    pub savings_secs: f64,
+}
+
+/// Label for findings without affected jobs when grouping by job.
+pub const PIPELINE_WIDE: &str = "(pipeline-wide)";
+
+/// Bucket `findings` by `by`.
+///
+/// Severity groups run from critical to info; category and job groups follow
+/// the order in which they first appear. A finding affecting several jobs is
+/// listed, with its full savings, under each of them, so job subtotals can add
+/// up to more than the report total.
+pub fn group_findings(findings: &[Finding], by: GroupBy) -> Vec<FindingGroup<'_>> {
+    let mut groups: Vec<FindingGroup> = Vec::new();
+
+    for finding in findings {
+        let labels = match by {
+            GroupBy::Category => vec![finding.category.label().to_string()],
+            GroupBy::Severity => vec![finding.severity.symbol().to_string()],
+            GroupBy::Job if finding.affected_jobs.is_empty() => vec![PIPELINE_WIDE.to_string()],
+            GroupBy::Job => finding.affected_jobs.clone(),
+        };
+
+        for label in labels {
+            let index = match groups.iter().position(|g| g.label == label) {
+                Some(index) => index,
+                None => {
+                    groups.push(FindingGroup {
+                        label,
+                        findings: Vec::new(),
+                        savings_secs: 0.0,
+                    });
+                    groups.len() - 1
+                }
+            };
+            let group = &mut groups[index];
+            group.findings.push(finding);
+            group.savings_secs += finding.estimated_savings_secs.unwrap_or(0.0);
+        }
+    }
+
+    if by == GroupBy::Severity {
+        groups.sort_by_key(|g| std::cmp::Reverse(severity_rank(&g.label)));
+    }
+
+    groups
+}
+
+fn severity_rank(symbol: &str) -> u8 {
+    [
+        Severity::Critical,
+        Severity::High,
+        Severity::Medium,
+        Severity::Low,
+        Severity::Info,
+    ]
+    .iter()
+    .find(|s| s.symbol() == symbol)
+    .map(|s| s.priority())
+    .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::report::FindingCategory;
+
+    fn finding(
+        severity: Severity,
+        category: FindingCategory,
+        jobs: &[&str],
+        savings: Option<f64>,
+    ) -> Finding {
+        Finding {
+            severity,
+            category,
+            title: "test".into(),
+            description: "test".into(),
+            affected_jobs: jobs.iter().map(|j| j.to_string()).collect(),
+            recommendation: "test".into(),
+            fix_command: None,
+            estimated_savings_secs: savings,
+            confidence: 0.9,
+            auto_fixable: false,
+            documentation_url: None,
+            blame: None,
+        }
+    }
+
+    #[test]
+    fn test_group_findings_buckets_and_subtotals() {
+        let findings = vec![
+            finding(
+                Severity::Medium,
+                FindingCategory::MissingCache,
+                &["build"],
+                Some(60.0),
+            ),
+            finding(
+                Severity::High,
+                FindingCategory::SerialBottleneck,
+                &["test", "build"],
+                Some(120.0),
+            ),
+            finding(
+                Severity::Medium,
+                FindingCategory::MissingCache,
+                &["test"],
+                Some(30.0),
+            ),
+            finding(
+                Severity::Low,
+                FindingCategory::ConcurrencyControl,
+                &[],
+                None,
+            ),
+        ];
+
+        let by_category = group_findings(&findings, GroupBy::Category);
+        let summary: Vec<_> = by_category
+            .iter()
+            .map(|g| (g.label.as_str(), g.findings.len(), g.savings_secs))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Missing Dependency Cache", 2, 90.0),
+                ("Serial Bottleneck", 1, 120.0),
+                ("Missing Concurrency Control", 1, 0.0),
+            ]
+        );
+
+        let by_severity = group_findings(&findings, GroupBy::Severity);
+        let labels: Vec<_> = by_severity.iter().map(|g| g.label.as_str()).collect();
+        assert_eq!(labels, vec!["HIGH", "MEDIUM", "LOW"]);
+        assert_eq!(by_severity[1].savings_secs, 90.0);
+
+        let by_job = group_findings(&findings, GroupBy::Job);
+        let summary: Vec<_> = by_job
+            .iter()
+            .map(|g| (g.label.as_str(), g.findings.len(), g.savings_secs))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("build", 2, 180.0),
+                ("test", 2, 150.0),
+                (PIPELINE_WIDE, 1, 0.0),
+            ]
+        );
+
+        assert_eq!(GroupBy::from_id("Severity"), Some(GroupBy::Severity));
+        assert_eq!(GroupBy::from_id("file"), None);
+    }
+}
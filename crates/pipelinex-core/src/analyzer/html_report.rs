@@ -1,4 +1,4 @@
-use crate::analyzer::report::AnalysisReport;
+use crate::analyzer::report::{AnalysisReport, Finding};
 use crate::parser::dag::PipelineDag;
 
 /// Generate a self-contained HTML report with interactive visualizations.
@@ -6,8 +6,12 @@ use crate::parser::dag::PipelineDag;
 pub fn generate_html_report(report: &AnalysisReport, dag: &PipelineDag) -> String {
     let critical_path_json =
         serde_json::to_string(&report.critical_path).unwrap_or_else(|_| "[]".to_string());
-    let findings_json =
-        serde_json::to_string(&report.findings).unwrap_or_else(|_| "[]".to_string());
+    // Reports loaded from older JSON may lack documentation links
+    let mut findings = report.findings.clone();
+    findings
+        .iter_mut()
+        .for_each(Finding::fill_documentation_url);
+    let findings_json = serde_json::to_string(&findings).unwrap_or_else(|_| "[]".to_string());
 
     // Generate DAG data for visualization
     let dag_nodes = generate_dag_nodes_json(dag);
@@ -324,6 +328,7 @@ pub fn generate_html_report(report: &AnalysisReport, dag: &PipelineDag) -> Strin
                         <span>💾 Savings: ${{f.estimated_savings_secs ? Math.floor(f.estimated_savings_secs / 60) + ' min' : 'N/A'}}</span>
                         <span>🎯 Confidence: ${{Math.round(f.confidence * 100)}}%</span>
                         ${{f.auto_fixable ? '<span>🔧 Auto-fixable</span>' : ''}}
+                        ${{f.documentation_url ? `<a href="${{f.documentation_url}}" target="_blank" rel="noopener" style="color: var(--accent-color);">📖 Learn more</a>` : ''}}
                     </div>
                     ${{f.recommendation ? `<div style="margin-top: 0.75rem; padding: 0.75rem; background: var(--bg-primary); border-radius: 0.25rem; font-size: 0.875rem;"><strong>💡 Recommendation:</strong> ${{f.recommendation}}</div>` : ''}}
                 </div>
@@ -497,10 +502,39 @@ fn format_duration(secs: f64) -> String {
     }
 }
 
-fn escape_html(s: &str) -> String {
+pub(crate) fn escape_html(s: &str) -> String {
     s.replace('&', "&amp;")
         .replace('<', "&lt;")
         .replace('>', "&gt;")
         .replace('"', "&quot;")
         .replace('\'', "&#x27;")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer;
+    use crate::parser::github::GitHubActionsParser;
+
+    #[test]
+    fn test_html_report_links_finding_documentation() {
+        let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - run: npm ci
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let mut report = analyzer::analyze(&dag);
+        // As if loaded from a report written before the field existed
+        report.findings[0].documentation_url = None;
+        let url = report.findings[0].help_url().to_string();
+
+        let html = generate_html_report(&report, &dag);
+        assert!(html.contains(&format!("\"documentation_url\":\"{}\"", url)));
+        assert!(html.contains("<a href=\"${f.documentation_url}\""));
+    }
+}
@@ -0,0 +1,192 @@
+use crate::analyzer::report::{Finding, FindingCategory, Severity};
+use crate::parser::dag::{JobNode, PipelineDag, StepInfo};
+
+/// Packaging tools whose upgrade is routine and not a reproducibility concern.
+const PIP_BOOTSTRAP: &[&str] = &["pip", "setuptools", "wheel"];
+
+/// An install command that ignores (or has no) lockfile.
+struct UnlockedInstall {
+    command: String,
+    replacement: &'static str,
+    reason: &'static str,
+    savings_secs: Option<f64>,
+}
+
+/// Detect dependency installs that don't honour a lockfile: `npm install`,
+/// `yarn install` without `--frozen-lockfile` / `--immutable`, and
+/// `pip install` of unpinned packages without a requirements or constraints file.
+pub fn detect_unlocked_installs(dag: &PipelineDag) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for job in dag.graph.node_weights() {
+        for step in &job.steps {
+            let Some(run) = &step.run else { continue };
+            let install = run
+                .lines()
+                .flat_map(|line| line.split("&&"))
+                .flat_map(|cmd| cmd.split(';'))
+                .find_map(|cmd| unlocked_install(cmd.trim()));
+            if let Some(install) = install {
+                findings.push(unlocked_install_finding(job, step, install));
+            }
+        }
+    }
+
+    findings
+}
+
+fn unlocked_install(command: &str) -> Option<UnlockedInstall> {
+    let mut tokens: Vec<&str> = command.split_whitespace().collect();
+    // `python -m pip install ...`
+    if tokens.len() > 2 && tokens[0].starts_with("python") && tokens[1] == "-m" {
+        tokens.drain(..2);
+    }
+    let (tool, rest) = match tokens.as_slice() {
+        [tool, sub, rest @ ..] if matches!(*sub, "install" | "i") => (*tool, rest),
+        _ => return None,
+    };
+    let flags: Vec<&str> = rest
+        .iter()
+        .copied()
+        .filter(|t| t.starts_with('-'))
+        .collect();
+    let args: Vec<&str> = rest
+        .iter()
+        .copied()
+        .filter(|t| !t.starts_with('-'))
+        .collect();
+
+    match tool {
+        // `npm install <pkg>` adds a package on purpose; only a bare install is at issue
+        "npm" if args.is_empty() && !flags.iter().any(|f| matches!(*f, "-g" | "--global")) => {
+            Some(UnlockedInstall {
+                command: command.to_string(),
+                replacement: "npm ci",
+                reason: "`npm install` may rewrite package-lock.json and resolve newer \
+                    versions than the lockfile records; `npm ci` installs exactly the \
+                    locked tree and skips the resolution step",
+                savings_secs: Some(15.0),
+            })
+        }
+        "yarn"
+            if args.is_empty()
+                && !flags
+                    .iter()
+                    .any(|f| matches!(*f, "--frozen-lockfile" | "--immutable")) =>
+        {
+            Some(UnlockedInstall {
+                command: command.to_string(),
+                replacement:
+                    "yarn install --frozen-lockfile (Yarn 1) or yarn install --immutable (Yarn 2+)",
+                reason: "without `--frozen-lockfile` Yarn silently updates yarn.lock when it \
+                    disagrees with package.json",
+                savings_secs: Some(10.0),
+            })
+        }
+        "pip" | "pip3" => {
+            let locked = flags.iter().any(|f| {
+                matches!(
+                    *f,
+                    "-r" | "--requirement" | "-c" | "--constraint" | "--require-hashes"
+                ) || f.starts_with("--requirement=")
+                    || f.starts_with("--constraint=")
+            });
+            let unpinned = args.iter().any(|arg| {
+                let name = arg
+                    .split(['[', '=', '<', '>', '~', '!'])
+                    .next()
+                    .unwrap_or(arg);
+                !arg.contains("==")
+                    && !PIP_BOOTSTRAP.contains(&name.to_lowercase().as_str())
+                    && !arg.starts_with('.')
+                    && !arg.contains('/')
+                    && !arg.ends_with(".txt")
+            });
+            (!locked && unpinned).then(|| UnlockedInstall {
+                command: command.to_string(),
+                replacement: "pip install -r requirements.txt (with pinned versions) or pip install -c constraints.txt <packages>",
+                reason: "unpinned packages resolve to whatever is newest at install time",
+                savings_secs: None,
+            })
+        }
+        _ => None,
+    }
+}
+
+fn unlocked_install_finding(job: &JobNode, step: &StepInfo, install: UnlockedInstall) -> Finding {
+    Finding {
+        severity: Severity::Low,
+        category: FindingCategory::UnlockedInstall,
+        title: format!(
+            "Non-deterministic install `{}` in '{}'",
+            install.command, job.id
+        ),
+        description: format!(
+            "Step '{}' in job '{}' runs `{}`: {}. Two runs of the same commit can \
+            install different dependency versions, so builds are not reproducible.",
+            step.name, job.id, install.command, install.reason
+        ),
+        affected_jobs: vec![job.id.clone()],
+        recommendation: format!("Use `{}` instead.", install.replacement),
+        fix_command: None,
+        estimated_savings_secs: install.savings_secs,
+        confidence: 0.80,
+        auto_fixable: false,
+        documentation_url: None,
+        blame: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::github::GitHubActionsParser;
+
+    fn flagged_commands(steps: &str) -> Vec<String> {
+        let yaml = format!(
+            "on: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n    steps:\n{}",
+            steps
+        );
+        let dag = GitHubActionsParser::parse(&yaml, "ci.yml".to_string()).unwrap();
+        detect_unlocked_installs(&dag)
+            .into_iter()
+            .map(|f| f.title)
+            .collect()
+    }
+
+    #[test]
+    fn test_npm_install_flagged_npm_ci_not() {
+        let flagged = flagged_commands(
+            "      - run: npm install\n      - run: npm ci\n      - run: npm install -g pnpm\n",
+        );
+        assert_eq!(flagged.len(), 1);
+        assert!(flagged[0].contains("`npm install`"));
+    }
+
+    #[test]
+    fn test_yarn_frozen_lockfile_not_flagged() {
+        let flagged = flagged_commands(
+            "      - run: yarn install --frozen-lockfile\n      - run: yarn install --immutable\n",
+        );
+        assert!(flagged.is_empty());
+        assert_eq!(flagged_commands("      - run: yarn install\n").len(), 1);
+    }
+
+    #[test]
+    fn test_pip_unpinned_packages_flagged() {
+        assert_eq!(
+            flagged_commands("      - run: pip install requests pytest\n").len(),
+            1
+        );
+        assert_eq!(
+            flagged_commands("      - run: python -m pip install flask\n").len(),
+            1
+        );
+        let locked = flagged_commands(
+            "      - run: python -m pip install --upgrade pip && pip install -r requirements.txt\n\
+             \x20     - run: pip install black==24.1.0\n\
+             \x20     - run: pip install -c constraints.txt requests\n",
+        );
+        assert!(locked.is_empty(), "{:?}", locked);
+    }
+}
@@ -1,47 +1,121 @@
+pub mod baseline;
 pub mod cache_detector;
+pub mod categories;
+pub mod conditions;
 pub mod critical_path;
+pub mod csv_report;
+pub mod dedup;
+pub mod docker_cache;
+pub mod env_expansion;
+pub mod failure_handling;
+pub mod grouping;
 pub mod html_report;
+pub mod lockfile;
 pub mod parallel_finder;
+pub mod passes;
+pub mod release_gating;
 pub mod report;
+pub mod runner_os;
 pub mod runner_sizer;
 pub mod sarif;
+pub mod version_pinning;
 pub mod waste_detector;
 
+use crate::health_score::{HealthScoreCalculator, HealthWeights};
 use crate::parser::dag::PipelineDag;
+use passes::AnalyzerPass;
 use report::{AnalysisReport, Finding};
 
 /// Run all analyzers on a pipeline DAG and produce a unified report.
 pub fn analyze(dag: &PipelineDag) -> AnalysisReport {
+    analyze_with_weights(dag, None)
+}
+
+/// Like [`analyze`], scoring health with a custom weight profile.
+pub fn analyze_with_weights(dag: &PipelineDag, weights: Option<&HealthWeights>) -> AnalysisReport {
+    analyze_passes(dag, weights, &AnalyzerPass::DEFAULT)
+}
+
+/// Like [`analyze_with_weights`], running only the given passes. Critical path
+/// and health metrics are always computed; skipped passes just add no findings,
+/// so a report missing any default pass lists its passes in
+/// [`metadata.passes`](report::ReportMetadata::passes) to mark it partial.
+/// Security findings are merged in after scoring, as with `--include-security`.
+pub fn analyze_passes(
+    dag: &PipelineDag,
+    weights: Option<&HealthWeights>,
+    passes: &[AnalyzerPass],
+) -> AnalysisReport {
+    let original = dag;
+    // Command-based detectors should see `$INSTALL_CMD` as the command it names
+    let expanded = env_expansion::expand_run_env(dag);
+    let dag = expanded.as_ref();
     let mut findings = Vec::new();
+    let enabled = |pass: AnalyzerPass| passes.contains(&pass);
 
     // Critical path analysis
     let (critical_path, critical_path_duration) = critical_path::find_critical_path(dag);
-    findings.extend(critical_path::analyze_critical_path(
-        dag,
-        &critical_path,
-        critical_path_duration,
-    ));
+    if enabled(AnalyzerPass::CriticalPath) {
+        findings.extend(critical_path::analyze_critical_path(
+            dag,
+            &critical_path,
+            critical_path_duration,
+        ));
+    }
 
     // Cache detection
-    findings.extend(cache_detector::detect_missing_caches(dag));
+    if enabled(AnalyzerPass::Cache) {
+        findings.extend(cache_detector::detect_missing_caches(dag));
+        findings.extend(cache_detector::detect_static_cache_keys(dag));
+    }
+    if enabled(AnalyzerPass::Docker) {
+        findings.extend(docker_cache::detect_missing_registry_cache(dag));
+    }
+    if enabled(AnalyzerPass::Lockfile) {
+        findings.extend(lockfile::detect_unlocked_installs(dag));
+    }
 
     // Parallelization opportunities
-    findings.extend(parallel_finder::find_parallelization_opportunities(dag));
+    if enabled(AnalyzerPass::Parallel) {
+        findings.extend(parallel_finder::find_parallelization_opportunities(dag));
+    }
 
     // Waste detection
-    findings.extend(waste_detector::detect_waste(dag));
+    if enabled(AnalyzerPass::Waste) {
+        findings.extend(waste_detector::detect_waste(dag));
+        findings.extend(release_gating::detect_release_only_jobs(dag));
+    }
 
     // Runner right-sizing recommendations
-    findings.extend(runner_sizer::detect_runner_right_sizing(dag));
+    if enabled(AnalyzerPass::Runner) {
+        findings.extend(runner_sizer::detect_runner_right_sizing(dag));
+        findings.extend(runner_os::detect_costly_runner_os(dag));
+    }
+
+    // continue-on-error / fail-fast misuse
+    if enabled(AnalyzerPass::FailureHandling) {
+        findings.extend(failure_handling::detect_failure_handling(dag));
+    }
+
+    // Tool versions hardcoded in several jobs
+    if enabled(AnalyzerPass::Versions) {
+        findings.extend(version_pinning::detect_repeated_versions(dag));
+    }
 
     // Optional external analyzer plugins (manifest-driven).
-    findings.extend(crate::plugins::run_external_analyzer_plugins(dag));
+    if enabled(AnalyzerPass::Plugins) {
+        findings.extend(crate::plugins::run_external_analyzer_plugins(dag));
+    }
+
+    findings
+        .iter_mut()
+        .for_each(Finding::fill_documentation_url);
 
     // Sort findings by severity (critical first)
     findings.sort_by_key(|f| std::cmp::Reverse(f.severity.priority()));
 
     let total_duration = critical_path_duration;
-    let estimated_optimized = estimate_optimized_duration(&findings, total_duration);
+    let estimated_optimized = estimate_optimized_duration(dag, &findings, total_duration);
 
     // Calculate health score
     let critical_count = findings
@@ -57,7 +131,7 @@ pub fn analyze(dag: &PipelineDag) -> AnalysisReport {
         .filter(|f| f.severity == report::Severity::Medium)
         .count();
 
-    let calculator = crate::health_score::HealthScoreCalculator::new();
+    let calculator = HealthScoreCalculator::new(weights.cloned());
     let health_score = calculator.calculate(
         total_duration,
         estimated_optimized,
@@ -69,7 +143,7 @@ pub fn analyze(dag: &PipelineDag) -> AnalysisReport {
         medium_count,
     );
 
-    AnalysisReport {
+    let mut report = AnalysisReport {
         pipeline_name: dag.name.clone(),
         source_file: dag.source_file.clone(),
         provider: dag.provider.clone(),
@@ -82,7 +156,18 @@ pub fn analyze(dag: &PipelineDag) -> AnalysisReport {
         optimized_duration_secs: estimated_optimized,
         findings,
         health_score: Some(health_score),
+        job_slack: critical_path::compute_job_slack(dag),
+        metadata: report::ReportMetadata::new(&dag.provider),
+    };
+    if !AnalyzerPass::DEFAULT.iter().all(|pass| enabled(*pass)) {
+        report.metadata.passes = passes.iter().map(|pass| pass.id().to_string()).collect();
     }
+
+    // Security scanners see the workflow as written
+    if enabled(AnalyzerPass::Security) {
+        report.merge_findings(crate::security::scan(original));
+    }
+    report
 }
 
 fn detect_has_caching(findings: &[report::Finding]) -> bool {
@@ -92,11 +177,211 @@ fn detect_has_caching(findings: &[report::Finding]) -> bool {
         .any(|f| matches!(f.category, report::FindingCategory::MissingCache))
 }
 
-fn estimate_optimized_duration(findings: &[Finding], current_duration: f64) -> f64 {
-    let total_savings: f64 = findings
-        .iter()
-        .filter_map(|f| f.estimated_savings_secs)
-        .sum();
+/// Project the wall-clock duration after applying every finding.
+///
+/// Each finding's effect is applied to a copy of the DAG — job-local savings shrink
+/// the affected jobs, removable dependencies drop their edge — and the critical
+/// path is recomputed, so savings on jobs off the critical path don't count.
+fn estimate_optimized_duration(
+    dag: &PipelineDag,
+    findings: &[Finding],
+    current_duration: f64,
+) -> f64 {
+    let mut optimized = dag.clone();
+    let original: std::collections::HashMap<String, f64> = dag
+        .graph
+        .node_weights()
+        .map(|j| (j.id.clone(), j.estimated_duration_secs))
+        .collect();
+
+    for finding in findings {
+        let Some(savings) = finding.estimated_savings_secs else {
+            continue;
+        };
+        match finding.category {
+            // Summaries of other findings or run-avoidance/compute savings,
+            // none of which shorten a single run's wall clock. Chain
+            // restructurings overlap the per-edge serial bottleneck findings.
+            report::FindingCategory::CriticalPath
+            | report::FindingCategory::SerialChain
+            | report::FindingCategory::MissingPathFilter
+            | report::FindingCategory::ConcurrencyControl
+            | report::FindingCategory::MatrixOptimization
+            | report::FindingCategory::ReleaseOnlyWork => {}
+            report::FindingCategory::SerialBottleneck if finding.affected_jobs.len() == 2 => {
+                let (job, dep) = (&finding.affected_jobs[0], &finding.affected_jobs[1]);
+                if let (Some(&from), Some(&to)) =
+                    (optimized.node_map.get(dep), optimized.node_map.get(job))
+                {
+                    if let Some(edge) = optimized.graph.find_edge(from, to) {
+                        optimized.graph.remove_edge(edge);
+                    }
+                }
+            }
+            _ => {
+                let jobs: Vec<_> = finding
+                    .affected_jobs
+                    .iter()
+                    .filter_map(|id| optimized.node_map.get(id).copied())
+                    .collect();
+                let per_job = savings / jobs.len().max(1) as f64;
+                for idx in jobs {
+                    let job = &mut optimized.graph[idx];
+                    // Keep 20% of each job: checkout, setup and the work itself remain.
+                    let floor = original.get(&job.id).copied().unwrap_or(0.0) * 0.2;
+                    job.estimated_duration_secs =
+                        (job.estimated_duration_secs - per_job).max(floor);
+                }
+            }
+        }
+    }
+
+    let (_, optimized_duration) = critical_path::find_critical_path(&optimized);
     // Don't go below 20% of original (there's always some irreducible time)
-    (current_duration - total_savings).max(current_duration * 0.2)
+    optimized_duration
+        .min(current_duration)
+        .max(current_duration * 0.2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::github::GitHubActionsParser;
+
+    #[test]
+    fn test_off_critical_path_savings_do_not_reduce_duration() {
+        // `lint` gets a cache finding but runs in parallel with the much longer `e2e`.
+        let yaml = r#"
+name: CI
+on: push
+jobs:
+  lint:
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm ci
+      - run: npm run lint
+  e2e:
+    runs-on: ubuntu-latest
+    steps:
+      - run: cargo build --release
+      - run: cargo build --tests
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let report = analyze(&dag);
+        assert_eq!(report.critical_path, vec!["e2e".to_string()]);
+        assert!(report.findings.iter().any(|f| matches!(
+            f.category,
+            report::FindingCategory::MissingCache
+        ) && f.affected_jobs == vec!["lint".to_string()]));
+
+        let lint_only: Vec<Finding> = report
+            .findings
+            .iter()
+            .filter(|f| f.affected_jobs == vec!["lint".to_string()])
+            .cloned()
+            .collect();
+        assert!(!lint_only.is_empty());
+        let projected =
+            estimate_optimized_duration(&dag, &lint_only, report.critical_path_duration_secs);
+        assert_eq!(projected, report.critical_path_duration_secs);
+    }
+
+    #[test]
+    fn test_only_cache_pass_reports_only_cache_findings() {
+        let yaml = r#"
+name: CI
+on: push
+jobs:
+  lint:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - run: npm ci
+      - run: npm run lint
+  test:
+    needs: lint
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - run: npm install
+      - run: npm test
+  image:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - run: docker build -t ghcr.io/acme/app:latest .
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let full = analyze(&dag);
+        let has = |report: &AnalysisReport, category: report::FindingCategory| {
+            report.findings.iter().any(|f| f.category == category)
+        };
+        assert!(has(&full, report::FindingCategory::DockerOptimization));
+        assert!(has(&full, report::FindingCategory::UnlockedInstall));
+        assert!(!has(&full, report::FindingCategory::Security));
+
+        let cache_only = analyze_passes(&dag, None, &[AnalyzerPass::Cache]);
+        assert!(!cache_only.findings.is_empty());
+        assert!(cache_only
+            .findings
+            .iter()
+            .all(|f| f.category == report::FindingCategory::MissingCache));
+        assert_eq!(cache_only.critical_path, full.critical_path);
+        // Scored on one pass only, so the report says so
+        assert_eq!(cache_only.metadata.passes, vec!["cache"]);
+        assert!(full.metadata.passes.is_empty());
+
+        let skipped = analyze_passes(
+            &dag,
+            None,
+            &passes::select_passes(&[], &[AnalyzerPass::Cache]),
+        );
+        assert!(!has(&skipped, report::FindingCategory::MissingCache));
+        assert!(has(&skipped, report::FindingCategory::DockerOptimization));
+
+        let security = analyze_passes(&dag, None, &[AnalyzerPass::Security]);
+        assert!(!security.findings.is_empty());
+        assert!(security
+            .findings
+            .iter()
+            .all(|f| f.category == report::FindingCategory::Security));
+    }
+
+    #[test]
+    fn test_report_metadata_populated() {
+        let yaml =
+            "on: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n    steps: [{ run: make }]\n";
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let report = analyze(&dag);
+
+        // Analysis itself is pure: no clock or git lookups
+        assert_eq!(report.metadata.tool_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(report.metadata.provider, "github-actions");
+        assert!(report.metadata.generated_at.is_empty());
+        assert_eq!(report.metadata.git_commit, None);
+
+        let metadata = report.with_metadata().metadata;
+        let generated = chrono::DateTime::parse_from_rfc3339(&metadata.generated_at).unwrap();
+        assert!((chrono::Utc::now() - generated.with_timezone(&chrono::Utc)).num_minutes() < 5);
+        if let Some(commit) = metadata.git_commit {
+            assert!(commit.chars().all(|c| c.is_ascii_hexdigit()), "{}", commit);
+        }
+    }
+
+    #[test]
+    fn test_critical_path_savings_reduce_duration() {
+        let yaml = r#"
+name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm ci
+      - run: npm run build
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let report = analyze(&dag);
+        assert!(report.optimized_duration_secs < report.critical_path_duration_secs);
+    }
 }
@@ -1,6 +1,9 @@
+use crate::analyzer::critical_path::compute_job_slack;
 use crate::analyzer::report::{Finding, FindingCategory, Severity};
-use crate::parser::dag::PipelineDag;
+use crate::parser::dag::{JobNode, PipelineDag};
+use petgraph::graph::NodeIndex;
 use petgraph::Direction;
+use std::collections::HashMap;
 
 /// Find jobs that are serialized but could potentially run in parallel.
 pub fn find_parallelization_opportunities(dag: &PipelineDag) -> Vec<Finding> {
@@ -45,6 +48,8 @@ pub fn find_parallelization_opportunities(dag: &PipelineDag) -> Vec<Finding> {
                     estimated_savings_secs: Some(savings),
                     confidence: 0.80,
                     auto_fixable: true,
+                    documentation_url: None,
+                    blame: None,
                 });
             }
         }
@@ -83,6 +88,219 @@ pub fn find_parallelization_opportunities(dag: &PipelineDag) -> Vec<Finding> {
                 estimated_savings_secs: Some(savings),
                 confidence: 0.85,
                 auto_fixable: true,
+                documentation_url: None,
+                blame: None,
+            });
+        }
+    }
+
+    findings.extend(find_unconsumed_dependencies(dag));
+    findings.extend(find_restructurable_chains(dag));
+
+    findings
+}
+
+/// Chains shorter than this are covered by the per-edge findings.
+const MIN_CHAIN_LEN: usize = 3;
+
+/// Flag linear `needs` chains (each job's only dependency is the previous one)
+/// in which some links only impose ordering.
+///
+/// Each job is re-attached to the earlier chain jobs it takes artifacts or
+/// outputs from; deploy jobs keep every earlier job as a gate. Savings are the
+/// serial time this removes from the chain. Like
+/// [`find_unconsumed_dependencies`], this needs parser data flow (GitHub Actions).
+fn find_restructurable_chains(dag: &PipelineDag) -> Vec<Finding> {
+    if dag.provider != "github-actions" {
+        return Vec::new();
+    }
+
+    let mut findings = Vec::new();
+    for chain in linear_chains(dag) {
+        let jobs: Vec<&JobNode> = chain.iter().map(|&i| &dag.graph[i]).collect();
+        if jobs.len() < MIN_CHAIN_LEN {
+            continue;
+        }
+
+        let needs: Vec<Vec<usize>> = (0..jobs.len())
+            .map(|i| {
+                (0..i)
+                    .filter(|&j| {
+                        classify_job(jobs[i]) == JobType::Deploy
+                            || jobs[i]
+                                .data_flow
+                                .consumes_from(&jobs[j].id, &jobs[j].data_flow)
+                    })
+                    .collect()
+            })
+            .collect();
+        let ordering_only = (1..jobs.len()).any(|i| !needs[i].contains(&(i - 1)));
+        if !ordering_only {
+            continue;
+        }
+
+        let mut finish = vec![0.0f64; jobs.len()];
+        for i in 0..jobs.len() {
+            let start = needs[i].iter().map(|&j| finish[j]).fold(0.0, f64::max);
+            finish[i] = start + jobs[i].estimated_duration_secs;
+        }
+        let serial: f64 = jobs.iter().map(|j| j.estimated_duration_secs).sum();
+        let restructured = finish.iter().copied().fold(0.0, f64::max);
+        let savings = serial - restructured;
+        if savings <= 0.0 {
+            continue;
+        }
+
+        let ids: Vec<&str> = jobs.iter().map(|j| j.id.as_str()).collect();
+        let layout: Vec<String> = needs
+            .iter()
+            .enumerate()
+            .map(|(i, deps)| {
+                let deps: Vec<&str> = deps.iter().map(|&j| ids[j]).collect();
+                format!("  {}: needs: [{}]", ids[i], deps.join(", "))
+            })
+            .collect();
+
+        findings.push(Finding {
+            severity: Severity::Medium,
+            category: FindingCategory::SerialChain,
+            title: format!("Serial chain {} can be restructured", ids.join(" -> ")),
+            description: format!(
+                "Jobs {} run strictly one after another (~{:.0}s), but not every link \
+                passes artifacts or outputs: some jobs only wait for ordering. Depending \
+                only on the jobs whose data they use shortens the chain to ~{:.0}s.",
+                ids.join(" -> "),
+                serial,
+                restructured
+            ),
+            affected_jobs: ids.iter().map(|id| id.to_string()).collect(),
+            recommendation: format!(
+                "Restructure `needs` so each job depends on the jobs it takes data from \
+                (deploy jobs keep every check as a gate):\n\n{}",
+                layout.join("\n")
+            ),
+            fix_command: None,
+            estimated_savings_secs: Some(savings),
+            confidence: 0.65,
+            auto_fixable: false,
+            documentation_url: None,
+            blame: None,
+        });
+    }
+
+    findings
+}
+
+/// Maximal paths whose inner links are the only edge out of the earlier job
+/// and the only edge into the later one.
+fn linear_chains(dag: &PipelineDag) -> Vec<Vec<NodeIndex>> {
+    let single_link = |idx: NodeIndex| -> Option<NodeIndex> {
+        let mut next = dag.graph.neighbors_directed(idx, Direction::Outgoing);
+        let succ = next.next()?;
+        if next.next().is_some() {
+            return None;
+        }
+        let preds = dag
+            .graph
+            .neighbors_directed(succ, Direction::Incoming)
+            .count();
+        (preds == 1).then_some(succ)
+    };
+
+    let mut chains = Vec::new();
+    for start in dag.graph.node_indices() {
+        let continues_chain = dag
+            .graph
+            .neighbors_directed(start, Direction::Incoming)
+            .any(|pred| single_link(pred) == Some(start));
+        if continues_chain {
+            continue;
+        }
+        let mut chain = vec![start];
+        let mut current = start;
+        while let Some(next) = single_link(current) {
+            if chain.contains(&next) {
+                break;
+            }
+            chain.push(next);
+            current = next;
+        }
+        chains.push(chain);
+    }
+    chains
+}
+
+/// Flag `needs` entries whose upstream job hands nothing to the dependent.
+///
+/// Only applies where the parser tracks data flow (GitHub Actions). Upstream
+/// lint/test jobs are treated as intentional gates, and dependencies the
+/// dependent references through `needs.<job>` are kept. Savings are how much
+/// earlier the dependent could start without the edge.
+fn find_unconsumed_dependencies(dag: &PipelineDag) -> Vec<Finding> {
+    if dag.provider != "github-actions" {
+        return Vec::new();
+    }
+
+    let slack = compute_job_slack(dag);
+    let finish: HashMap<&str, f64> = slack
+        .iter()
+        .map(|s| (s.job_id.as_str(), s.earliest_start_secs + s.duration_secs))
+        .collect();
+
+    let mut findings = Vec::new();
+    for idx in dag.graph.node_indices() {
+        let job = &dag.graph[idx];
+        let deps: Vec<_> = dag
+            .graph
+            .neighbors_directed(idx, Direction::Incoming)
+            .map(|d| &dag.graph[d])
+            .collect();
+
+        for dep in &deps {
+            if matches!(classify_job(dep), JobType::Lint | JobType::Test)
+                || is_likely_false_dependency(dep, job)
+                || job.data_flow.consumes_from(&dep.id, &dep.data_flow)
+            {
+                continue;
+            }
+            // Without any data flow on either side we can't tell ordering from data
+            let dep_produces =
+                !dep.data_flow.outputs.is_empty() || !dep.data_flow.artifacts_uploaded.is_empty();
+            if !dep_produces && !job.data_flow.consumes_anything() {
+                continue;
+            }
+
+            let other_deps_finish = deps
+                .iter()
+                .filter(|d| d.id != dep.id)
+                .map(|d| finish.get(d.id.as_str()).copied().unwrap_or(0.0))
+                .fold(0.0f64, f64::max);
+            let savings = finish.get(dep.id.as_str()).copied().unwrap_or(0.0) - other_deps_finish;
+            if savings <= 0.0 {
+                continue;
+            }
+
+            findings.push(Finding {
+                severity: Severity::Medium,
+                category: FindingCategory::SerialBottleneck,
+                title: format!("'{}' waits on unrelated job '{}'", job.id, dep.id),
+                description: format!(
+                    "Job '{}' needs '{}' but never downloads its artifacts or reads its \
+                    outputs, so it starts ~{:.0}s later than its other dependencies allow.",
+                    job.id, dep.id, savings
+                ),
+                affected_jobs: vec![job.id.clone(), dep.id.clone()],
+                recommendation: format!(
+                    "Remove '{}' from the `needs` list of '{}' unless the ordering is \
+                    intentional (in that case reference `needs.{}.result` to make it explicit).",
+                    dep.id, job.id, dep.id
+                ),
+                fix_command: None,
+                estimated_savings_secs: Some(savings),
+                confidence: 0.70,
+                auto_fixable: false,
+                documentation_url: None,
+                blame: None,
             });
         }
     }
@@ -202,4 +420,155 @@ jobs:
             .iter()
             .any(|f| matches!(f.category, FindingCategory::SerialBottleneck)));
     }
+
+    const DEPLOY_WORKFLOW: &str = r#"
+name: Release
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: cargo build --release
+      - uses: actions/upload-artifact@v4
+        with:
+          name: binary
+          path: target/release/app
+  changelog:
+    runs-on: ubuntu-latest
+    steps:
+      - run: ./scripts/generate-changelog.sh
+      - run: ./scripts/render-notes.sh
+      - run: ./scripts/publish-notes.sh
+      - run: ./scripts/check-links.sh
+      - run: ./scripts/notify.sh
+      - run: ./scripts/archive.sh
+      - run: ./scripts/index.sh
+      - run: ./scripts/sync.sh
+      - run: ./scripts/tag.sh
+      - run: ./scripts/verify.sh
+      - run: ./scripts/upload.sh
+  deploy:
+    needs: [build, changelog]
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/download-artifact@v4
+        with:
+          name: binary
+      - run: ./deploy.sh
+"#;
+
+    #[test]
+    fn test_unconsumed_dependency_flagged() {
+        let dag = GitHubActionsParser::parse(DEPLOY_WORKFLOW, "release.yml".to_string()).unwrap();
+        let findings = find_unconsumed_dependencies(&dag);
+        assert_eq!(findings.len(), 1, "{:?}", findings);
+        let finding = &findings[0];
+        assert_eq!(finding.affected_jobs, vec!["deploy", "changelog"]);
+        let build = dag.get_job("build").unwrap().estimated_duration_secs;
+        let changelog = dag.get_job("changelog").unwrap().estimated_duration_secs;
+        assert_eq!(finding.estimated_savings_secs, Some(changelog - build));
+    }
+
+    #[test]
+    fn test_explicit_needs_reference_is_kept() {
+        let yaml = DEPLOY_WORKFLOW.replace(
+            "      - run: ./deploy.sh",
+            "      - run: ./deploy.sh\n        if: needs.changelog.result == 'success'",
+        );
+        let dag = GitHubActionsParser::parse(&yaml, "release.yml".to_string()).unwrap();
+        assert!(find_unconsumed_dependencies(&dag).is_empty());
+    }
+
+    #[test]
+    fn test_linear_chain_restructured() {
+        let yaml = r#"
+on: push
+jobs:
+  lint:
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm run lint
+  build:
+    needs: lint
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm run build
+      - uses: actions/upload-artifact@v4
+        with:
+          name: dist
+  test:
+    needs: build
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/download-artifact@v4
+        with:
+          name: dist
+      - run: npm test
+  deploy:
+    needs: test
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/download-artifact@v4
+        with:
+          name: dist
+      - run: ./deploy.sh
+"#;
+        let mut dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        for job in dag.graph.node_weights_mut() {
+            job.estimated_duration_secs = match job.id.as_str() {
+                "lint" => 120.0,
+                "build" => 300.0,
+                "test" => 240.0,
+                _ => 60.0,
+            };
+        }
+
+        let findings = find_restructurable_chains(&dag);
+        assert_eq!(findings.len(), 1, "{:?}", findings);
+        let finding = &findings[0];
+        assert_eq!(
+            finding.affected_jobs,
+            vec!["lint", "build", "test", "deploy"]
+        );
+        // lint runs alongside build instead of before it
+        assert_eq!(finding.estimated_savings_secs, Some(120.0));
+        assert!(finding.recommendation.contains("build: needs: []"));
+        assert!(finding.recommendation.contains("test: needs: [build]"));
+        assert!(finding
+            .recommendation
+            .contains("deploy: needs: [lint, build, test]"));
+    }
+
+    #[test]
+    fn test_chain_with_data_on_every_link_not_flagged() {
+        let yaml = r#"
+on: push
+jobs:
+  compile:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+      - uses: actions/upload-artifact@v4
+        with: { name: bin }
+  package:
+    needs: compile
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/download-artifact@v4
+        with: { name: bin }
+      - run: make package
+      - uses: actions/upload-artifact@v4
+        with: { name: pkg }
+  publish:
+    needs: package
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/download-artifact@v4
+        with: { name: pkg }
+      - run: make publish
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "release.yml".to_string()).unwrap();
+        assert_eq!(linear_chains(&dag).iter().map(Vec::len).max(), Some(3));
+        assert!(find_restructurable_chains(&dag).is_empty());
+    }
 }
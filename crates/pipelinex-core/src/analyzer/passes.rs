@@ -0,0 +1,142 @@
+//! Choosing which analyzer passes run (`analyze --only` / `--skip`).
+
+use anyhow::Result;
+
+/// A group of detectors run by [`analyze_passes`](super::analyze_passes).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnalyzerPass {
+    CriticalPath,
+    Cache,
+    Docker,
+    Lockfile,
+    Parallel,
+    Waste,
+    Runner,
+    FailureHandling,
+    Versions,
+    Plugins,
+    /// The security scanners ([`crate::security::scan`]); off unless selected.
+    Security,
+}
+
+impl AnalyzerPass {
+    pub const ALL: [AnalyzerPass; 11] = [
+        AnalyzerPass::CriticalPath,
+        AnalyzerPass::Cache,
+        AnalyzerPass::Docker,
+        AnalyzerPass::Lockfile,
+        AnalyzerPass::Parallel,
+        AnalyzerPass::Waste,
+        AnalyzerPass::Runner,
+        AnalyzerPass::FailureHandling,
+        AnalyzerPass::Versions,
+        AnalyzerPass::Plugins,
+        AnalyzerPass::Security,
+    ];
+
+    /// Passes run when none are selected: all but [`AnalyzerPass::Security`],
+    /// which `analyze` only runs with `--include-security` or `--only security`.
+    pub const DEFAULT: [AnalyzerPass; 10] = [
+        AnalyzerPass::CriticalPath,
+        AnalyzerPass::Cache,
+        AnalyzerPass::Docker,
+        AnalyzerPass::Lockfile,
+        AnalyzerPass::Parallel,
+        AnalyzerPass::Waste,
+        AnalyzerPass::Runner,
+        AnalyzerPass::FailureHandling,
+        AnalyzerPass::Versions,
+        AnalyzerPass::Plugins,
+    ];
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            AnalyzerPass::CriticalPath => "critical-path",
+            AnalyzerPass::Cache => "cache",
+            AnalyzerPass::Docker => "docker",
+            AnalyzerPass::Lockfile => "lockfile",
+            AnalyzerPass::Parallel => "parallel",
+            AnalyzerPass::Waste => "waste",
+            AnalyzerPass::Runner => "runner",
+            AnalyzerPass::FailureHandling => "failure-handling",
+            AnalyzerPass::Versions => "versions",
+            AnalyzerPass::Plugins => "plugins",
+            AnalyzerPass::Security => "security",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        let id = id.trim().to_lowercase();
+        Self::ALL.into_iter().find(|p| p.id() == id)
+    }
+}
+
+/// Parse an `--only` / `--skip` list such as `cache,parallel` into passes.
+pub fn parse_pass_ids(ids: &[String]) -> Result<Vec<AnalyzerPass>> {
+    let mut passes = Vec::new();
+    for id in ids.iter().flat_map(|s| s.split(',')) {
+        if id.trim().is_empty() {
+            continue;
+        }
+        let pass = AnalyzerPass::from_id(id).ok_or_else(|| {
+            let known: Vec<_> = AnalyzerPass::ALL.iter().map(|p| p.id()).collect();
+            anyhow::anyhow!(
+                "Unknown analyzer pass '{}'. Expected one of: {}",
+                id.trim(),
+                known.join(", ")
+            )
+        })?;
+        if !passes.contains(&pass) {
+            passes.push(pass);
+        }
+    }
+    Ok(passes)
+}
+
+/// The passes to run: `only` (or the [`DEFAULT`](AnalyzerPass::DEFAULT) passes
+/// when empty) minus `skip`.
+pub fn select_passes(only: &[AnalyzerPass], skip: &[AnalyzerPass]) -> Vec<AnalyzerPass> {
+    let candidates: &[AnalyzerPass] = if only.is_empty() {
+        &AnalyzerPass::DEFAULT
+    } else {
+        &AnalyzerPass::ALL
+    };
+    candidates
+        .iter()
+        .copied()
+        .filter(|pass| only.is_empty() || only.contains(pass))
+        .filter(|pass| !skip.contains(pass))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_select_passes() {
+        let only = parse_pass_ids(&["cache, parallel".into(), "cache".into()]).unwrap();
+        assert_eq!(only, vec![AnalyzerPass::Cache, AnalyzerPass::Parallel]);
+        assert_eq!(
+            select_passes(&only, &[AnalyzerPass::Parallel]),
+            vec![AnalyzerPass::Cache]
+        );
+
+        let skip = parse_pass_ids(&["plugins".into()]).unwrap();
+        let selected = select_passes(&[], &skip);
+        assert_eq!(selected.len(), AnalyzerPass::DEFAULT.len() - 1);
+        assert!(!selected.contains(&AnalyzerPass::Plugins));
+        assert!(!selected.contains(&AnalyzerPass::Security));
+
+        let security = parse_pass_ids(&["security".into()]).unwrap();
+        assert_eq!(select_passes(&security, &[]), vec![AnalyzerPass::Security]);
+
+        let err = parse_pass_ids(&["cahce".into()]).unwrap_err().to_string();
+        assert!(err.contains("Unknown analyzer pass 'cahce'"), "{}", err);
+        assert!(
+            err.contains("critical-path, cache, docker, lockfile"),
+            "{}",
+            err
+        );
+    }
+}
@@ -0,0 +1,247 @@
+use crate::analyzer::report::{Finding, FindingCategory};
+use crate::cost::DEFAULT_RUNS_PER_MONTH;
+use crate::parser::dag::{JobNode, PipelineDag};
+
+/// Runs per month of a suite gated to releases or a nightly schedule.
+const RELEASE_RUNS_PER_MONTH: u32 = 30;
+
+/// Job ids and names that mark a release-grade test suite.
+const HEAVY_JOB_NAMES: &[&str] = &[
+    "e2e",
+    "end-to-end",
+    "end2end",
+    "integration",
+    "acceptance",
+    "system-test",
+    "system_test",
+    "regression",
+    "soak",
+    "load-test",
+    "load_test",
+    "perf-test",
+    "performance",
+];
+
+/// Commands that drive browser, device or load test suites.
+const HEAVY_COMMANDS: &[&str] = &[
+    "cypress run",
+    "playwright test",
+    "npx playwright",
+    "wdio run",
+    "testcafe",
+    "detox test",
+    "selenium",
+    "k6 run",
+    "locust",
+    "jmeter",
+    "test:e2e",
+    "test:integration",
+    "run e2e",
+    "run integration",
+];
+
+/// Triggers that fire for ordinary development pushes.
+const EVERY_CHANGE_EVENTS: &[&str] = &["push", "pull_request", "merge_request"];
+
+/// Detect release-grade test suites (e2e, integration, load) that run on
+/// every push or pull request.
+///
+/// Such suites are usually only needed before a release; running them on
+/// every change multiplies their cost by the push rate. A job is skipped when
+/// it already has a condition, GitLab `rules` (including `only`/`except` refs
+/// and `when: manual`), or a path filter.
+pub fn detect_release_only_jobs(dag: &PipelineDag) -> Vec<Finding> {
+    let Some(event) = every_change_trigger(dag) else {
+        return Vec::new();
+    };
+
+    dag.graph
+        .node_weights()
+        .filter(|job| is_ungated(job))
+        .filter_map(|job| heavy_reason(job).map(|reason| finding(dag, job, event, &reason)))
+        .collect()
+}
+
+/// The first trigger that runs the pipeline for ordinary branch pushes or PRs.
+fn every_change_trigger(dag: &PipelineDag) -> Option<&str> {
+    dag.triggers
+        .iter()
+        .filter(|t| EVERY_CHANGE_EVENTS.contains(&t.event.as_str()))
+        // A push trigger with tag filters and no branches only fires for tags
+        .find(|t| !(t.tags.is_some() && t.branches.is_none()))
+        .map(|t| t.event.as_str())
+}
+
+fn is_ungated(job: &JobNode) -> bool {
+    job.condition.is_none()
+        && job.rules.is_empty()
+        && job.paths_filter.is_none()
+        && job.paths_ignore.is_none()
+}
+
+/// Why `job` looks like a release-grade suite, if it does.
+fn heavy_reason(job: &JobNode) -> Option<String> {
+    let names = [job.id.to_lowercase(), job.name.to_lowercase()];
+    if let Some(marker) = HEAVY_JOB_NAMES
+        .iter()
+        .find(|m| names.iter().any(|n| n.contains(*m)))
+    {
+        return Some(format!("its name mentions '{}'", marker));
+    }
+
+    job.steps.iter().find_map(|step| {
+        let run = step.run.as_deref()?.to_lowercase();
+        HEAVY_COMMANDS
+            .iter()
+            .find(|c| run.contains(*c))
+            .map(|c| format!("it runs `{}`", c))
+    })
+}
+
+fn finding(dag: &PipelineDag, job: &JobNode, event: &str, reason: &str) -> Finding {
+    let push_runs = DEFAULT_RUNS_PER_MONTH;
+    let skipped_runs = push_runs.saturating_sub(RELEASE_RUNS_PER_MONTH);
+    let monthly_secs = job.estimated_duration_secs * skipped_runs as f64;
+    // Savings are per run: the job's share of the runs it no longer takes part in
+    let savings_per_run = job.estimated_duration_secs * skipped_runs as f64 / push_runs as f64;
+
+    Finding::builder(
+        FindingCategory::ReleaseOnlyWork,
+        format!("'{}' runs a release-grade suite on every {}", job.id, event),
+    )
+    .description(format!(
+        "Job '{}' looks like an end-to-end or integration suite ({}) and runs \
+        unconditionally on every `{}`. At {} runs/month, gating it to releases or a \
+        nightly schedule ({} runs/month) skips {} runs and about {:.0} runner-minutes \
+        per month ({:.0}s × ({} − {})).",
+        job.id,
+        reason,
+        event,
+        push_runs,
+        RELEASE_RUNS_PER_MONTH,
+        skipped_runs,
+        monthly_secs / 60.0,
+        job.estimated_duration_secs,
+        push_runs,
+        RELEASE_RUNS_PER_MONTH
+    ))
+    .affected_jobs([job.id.as_str()])
+    .recommendation(gating_recommendation(dag, job))
+    .savings_secs(Some(savings_per_run))
+    .confidence(0.6)
+    .build()
+}
+
+fn gating_recommendation(dag: &PipelineDag, job: &JobNode) -> String {
+    match dag.provider.as_str() {
+        "github-actions" => format!(
+            "Run '{}' only for release tags, a nightly schedule, manual dispatch, or PRs \
+            labelled for it:\n\
+            \n  if: >-\n    startsWith(github.ref, 'refs/tags/')\n    \
+            || github.event_name == 'schedule'\n    \
+            || github.event_name == 'workflow_dispatch'\n    \
+            || contains(github.event.pull_request.labels.*.name, 'run-e2e')\n\
+            \nand add `schedule: [{{cron: '0 3 * * *'}}]` and `workflow_dispatch:` to `on:`. \
+            Keep a fast smoke subset on every push if regressions must be caught early.",
+            job.id
+        ),
+        "gitlab-ci" => format!(
+            "Give '{}' rules that run it for tags and scheduled pipelines, and manually \
+            otherwise:\n\
+            \n  rules:\n    - if: $CI_COMMIT_TAG\n    \
+            - if: $CI_PIPELINE_SOURCE == \"schedule\"\n    \
+            - when: manual\n      allow_failure: true",
+            job.id
+        ),
+        _ => format!(
+            "Run '{}' only for release tags, on a nightly schedule, or as a manual step, \
+            and keep a fast smoke subset on every push.",
+            job.id
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::github::GitHubActionsParser;
+    use crate::parser::gitlab::GitLabCIParser;
+
+    #[test]
+    fn test_e2e_job_on_push_gets_gating_recommendation() {
+        let yaml = r#"
+on: [push, pull_request]
+jobs:
+  unit:
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm test
+  e2e:
+    runs-on: ubuntu-latest
+    steps:
+      - run: npx playwright test
+  nightly-integration:
+    runs-on: ubuntu-latest
+    if: github.event_name == 'schedule'
+    steps:
+      - run: npm run test:integration
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let findings = detect_release_only_jobs(&dag);
+        assert_eq!(findings.len(), 1);
+
+        let finding = &findings[0];
+        assert_eq!(finding.category, FindingCategory::ReleaseOnlyWork);
+        assert_eq!(finding.affected_jobs, vec!["e2e"]);
+        assert!(finding.title.contains("every push"));
+        assert!(finding
+            .recommendation
+            .contains("startsWith(github.ref, 'refs/tags/')"));
+        let duration = dag.get_job("e2e").unwrap().estimated_duration_secs;
+        let expected = duration * (500.0 - 30.0) / 500.0;
+        assert!((finding.estimated_savings_secs.unwrap() - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_gitlab_tag_only_and_manual_jobs_are_not_flagged() {
+        let yaml = r#"
+stages: [test]
+e2e:
+  stage: test
+  script: [npx cypress run]
+e2e-tags:
+  stage: test
+  script: [npx cypress run]
+  only: [tags]
+e2e-manual:
+  stage: test
+  script: [npx cypress run]
+  when: manual
+e2e-delayed:
+  stage: test
+  script: [npx cypress run]
+  when: delayed
+  start_in: 1 hour
+"#;
+        let dag = GitLabCIParser::parse(yaml, ".gitlab-ci.yml".to_string()).unwrap();
+        let findings = detect_release_only_jobs(&dag);
+        assert_eq!(findings.len(), 1, "{:#?}", findings);
+        assert_eq!(findings[0].affected_jobs, vec!["e2e"]);
+    }
+
+    #[test]
+    fn test_tag_only_push_is_not_flagged() {
+        let yaml = r#"
+on:
+  push:
+    tags: ['v*']
+jobs:
+  e2e:
+    runs-on: ubuntu-latest
+    steps:
+      - run: npx cypress run
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "release.yml".to_string()).unwrap();
+        assert!(detect_release_only_jobs(&dag).is_empty());
+    }
+}
@@ -1,5 +1,11 @@
+use crate::analyzer::categories::category_metadata;
+use crate::blame::BlameInfo;
 use crate::health_score::HealthScore;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
 
 /// Severity level for analysis findings.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -48,7 +54,9 @@ impl Severity {
 pub enum FindingCategory {
     CriticalPath,
     MissingCache,
+    IneffectiveCache,
     SerialBottleneck,
+    SerialChain,
     MissingPathFilter,
     ShallowClone,
     RedundantSteps,
@@ -58,26 +66,19 @@ pub enum FindingCategory {
     ConcurrencyControl,
     ArtifactReuse,
     RunnerSizing,
+    RunnerPlatform,
+    UnlockedInstall,
+    FailureHandling,
+    ReleaseOnlyWork,
+    Maintainability,
+    Security,
+    Policy,
     CustomPlugin,
 }
 
 impl FindingCategory {
     pub fn label(&self) -> &str {
-        match self {
-            FindingCategory::CriticalPath => "Critical Path Bottleneck",
-            FindingCategory::MissingCache => "Missing Dependency Cache",
-            FindingCategory::SerialBottleneck => "Serial Bottleneck",
-            FindingCategory::MissingPathFilter => "Missing Path Filter",
-            FindingCategory::ShallowClone => "Full Git Clone",
-            FindingCategory::RedundantSteps => "Redundant Steps",
-            FindingCategory::DockerOptimization => "Docker Build Optimization",
-            FindingCategory::MatrixOptimization => "Matrix Strategy Optimization",
-            FindingCategory::FlakyTest => "Flaky Test",
-            FindingCategory::ConcurrencyControl => "Missing Concurrency Control",
-            FindingCategory::ArtifactReuse => "Missing Artifact Reuse",
-            FindingCategory::RunnerSizing => "Runner Right-Sizing",
-            FindingCategory::CustomPlugin => "Custom Plugin",
-        }
+        category_metadata(self).label
     }
 }
 
@@ -94,9 +95,35 @@ pub struct Finding {
     pub estimated_savings_secs: Option<f64>,
     pub confidence: f64,
     pub auto_fixable: bool,
+    /// Where to read more; the category's help page unless overridden.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub documentation_url: Option<String>,
+    /// Last commit touching the affected jobs (`analyze --git-blame`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blame: Option<BlameInfo>,
 }
 
 impl Finding {
+    /// Start a finding with the category's default severity; see [`FindingBuilder`].
+    pub fn builder(category: FindingCategory, title: impl Into<String>) -> FindingBuilder {
+        FindingBuilder::new(category, title)
+    }
+
+    /// [`documentation_url`](Self::documentation_url), or the category's help page.
+    pub fn help_url(&self) -> &str {
+        self.documentation_url
+            .as_deref()
+            .unwrap_or_else(|| category_metadata(&self.category).help_url)
+    }
+
+    /// Set [`documentation_url`](Self::documentation_url) from the category
+    /// registry if the producer left it empty.
+    pub fn fill_documentation_url(&mut self) {
+        if self.documentation_url.is_none() {
+            self.documentation_url = Some(self.help_url().to_string());
+        }
+    }
+
     pub fn savings_display(&self) -> String {
         match self.estimated_savings_secs {
             Some(secs) => format_duration(secs),
@@ -105,6 +132,100 @@ impl Finding {
     }
 }
 
+/// Confidence a [`FindingBuilder`] uses unless told otherwise.
+pub const DEFAULT_CONFIDENCE: f64 = 0.8;
+
+/// Builds a [`Finding`] with consistent defaults and bounds.
+///
+/// Severity defaults to the category's
+/// [`default_severity`](crate::analyzer::categories::CategoryMetadata::default_severity).
+/// Confidence is clamped to `0.0..=1.0`, and negative or non-finite savings
+/// are dropped rather than reported.
+#[derive(Debug, Clone)]
+pub struct FindingBuilder {
+    finding: Finding,
+}
+
+impl FindingBuilder {
+    pub fn new(category: FindingCategory, title: impl Into<String>) -> Self {
+        Self {
+            finding: Finding {
+                severity: category_metadata(&category).default_severity,
+                documentation_url: Some(category_metadata(&category).help_url.to_string()),
+                category,
+                title: title.into(),
+                description: String::new(),
+                affected_jobs: Vec::new(),
+                recommendation: String::new(),
+                fix_command: None,
+                estimated_savings_secs: None,
+                confidence: DEFAULT_CONFIDENCE,
+                auto_fixable: false,
+                blame: None,
+            },
+        }
+    }
+
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.finding.severity = severity;
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.finding.description = description.into();
+        self
+    }
+
+    pub fn recommendation(mut self, recommendation: impl Into<String>) -> Self {
+        self.finding.recommendation = recommendation.into();
+        self
+    }
+
+    pub fn affected_jobs<I, S>(mut self, jobs: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.finding.affected_jobs = jobs.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn fix_command(mut self, command: impl Into<String>) -> Self {
+        self.finding.fix_command = Some(command.into());
+        self
+    }
+
+    pub fn savings_secs(mut self, secs: Option<f64>) -> Self {
+        self.finding.estimated_savings_secs = secs.filter(|s| s.is_finite() && *s >= 0.0);
+        self
+    }
+
+    /// Set the confidence, clamped to `0.0..=1.0`; NaN counts as no confidence.
+    pub fn confidence(mut self, confidence: f64) -> Self {
+        self.finding.confidence = if confidence.is_nan() {
+            0.0
+        } else {
+            confidence.clamp(0.0, 1.0)
+        };
+        self
+    }
+
+    pub fn auto_fixable(mut self, auto_fixable: bool) -> Self {
+        self.finding.auto_fixable = auto_fixable;
+        self
+    }
+
+    /// Point at a page other than the category's help page.
+    pub fn documentation_url(mut self, url: impl Into<String>) -> Self {
+        self.finding.documentation_url = Some(url.into());
+        self
+    }
+
+    pub fn build(self) -> Finding {
+        self.finding
+    }
+}
+
 /// The complete analysis report for a pipeline.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalysisReport {
@@ -120,9 +241,144 @@ pub struct AnalysisReport {
     pub optimized_duration_secs: f64,
     pub findings: Vec<Finding>,
     pub health_score: Option<HealthScore>,
+    /// Per-job schedule slack from a critical path method (CPM) pass, in topological order.
+    #[serde(default)]
+    pub job_slack: Vec<JobSlack>,
+    /// Which tool produced the report, when, and from which commit.
+    #[serde(default)]
+    pub metadata: ReportMetadata,
+}
+
+/// Provenance of an analysis report.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReportMetadata {
+    /// pipelinex-core version that produced the report.
+    pub tool_version: String,
+    /// RFC 3339 timestamp of the analysis; empty until the report is stamped.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub generated_at: String,
+    /// Commit of the repository containing the analyzed file, if resolvable.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_commit: Option<String>,
+    pub provider: String,
+    /// Analyzer passes that ran, when some default pass was skipped. The
+    /// health score and optimized duration then cover only these passes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub passes: Vec<String>,
+}
+
+/// CI variables holding the commit being built, checked when `git` can't tell.
+const COMMIT_ENV_VARS: &[&str] = &[
+    "GITHUB_SHA",
+    "CI_COMMIT_SHA",
+    "BUILDKITE_COMMIT",
+    "CIRCLE_SHA1",
+    "BITBUCKET_COMMIT",
+    "DRONE_COMMIT_SHA",
+    "BUILD_SOURCEVERSION",
+    "GIT_COMMIT",
+];
+
+impl ReportMetadata {
+    /// Metadata naming the tool and provider. The time and commit stay empty
+    /// until [`stamp`](Self::stamp), so analysis output is deterministic.
+    pub fn new(provider: &str) -> Self {
+        Self {
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+            provider: provider.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Record the current time and the commit of the repository holding
+    /// `source_file`. Runs `git`.
+    pub fn stamp(&mut self, source_file: &str) {
+        self.generated_at = chrono::Utc::now().to_rfc3339();
+        self.git_commit = resolve_git_commit(Path::new(source_file));
+    }
+}
+
+/// HEAD of the repository containing `source_file`, falling back to the CI
+/// commit variables. Lookups are cached per directory, since `analyze` runs
+/// once per file.
+fn resolve_git_commit(source_file: &Path) -> Option<String> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Option<String>>>> = OnceLock::new();
+
+    let dir = match source_file.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    let cache = CACHE.get_or_init(Default::default);
+    if let Some(commit) = cache.lock().ok()?.get(&dir) {
+        return commit.clone();
+    }
+
+    let commit = git_head(&dir).or_else(|| {
+        COMMIT_ENV_VARS
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|sha| !sha.trim().is_empty())
+    });
+    if let Ok(mut cache) = cache.lock() {
+        cache.insert(dir, commit.clone());
+    }
+    commit
+}
+
+fn git_head(dir: &Path) -> Option<String> {
+    if !dir.is_dir() {
+        return None;
+    }
+    let output = crate::process::output_with_timeout(
+        std::process::Command::new("git")
+            .args(["rev-parse", "HEAD"])
+            .current_dir(dir),
+        Duration::from_secs(5),
+    )
+    .ok()?;
+    let sha = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (output.status.success() && !sha.is_empty()).then_some(sha)
+}
+
+/// CPM timing for a single job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobSlack {
+    pub job_id: String,
+    pub duration_secs: f64,
+    pub earliest_start_secs: f64,
+    pub latest_start_secs: f64,
+    /// How long the job can be delayed without extending the pipeline. Zero on the critical path.
+    pub slack_secs: f64,
 }
 
 impl AnalysisReport {
+    /// The report stamped with the analysis time and the git commit of its
+    /// source file. [`analyze`](crate::analyzer::analyze) leaves both out, so
+    /// callers that want provenance opt in here.
+    pub fn with_metadata(mut self) -> Self {
+        self.metadata.stamp(&self.source_file);
+        self
+    }
+
+    /// The report as one line of newline-delimited JSON (no trailing newline).
+    ///
+    /// Compact serialization escapes line breaks inside strings, so the result
+    /// never spans lines.
+    pub fn to_json_line(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    /// Fold findings from another pass (security scan, policy check) into the
+    /// report, keeping the list ordered by severity.
+    pub fn merge_findings(&mut self, findings: impl IntoIterator<Item = Finding>) {
+        self.findings.extend(findings.into_iter().map(|mut f| {
+            f.fill_documentation_url();
+            f
+        }));
+        self.findings
+            .sort_by_key(|f| std::cmp::Reverse(f.severity.priority()));
+    }
+
     pub fn potential_improvement_pct(&self) -> f64 {
         if self.total_estimated_duration_secs == 0.0 {
             return 0.0;
@@ -172,3 +428,40 @@ pub fn format_duration(secs: f64) -> String {
         format!("{}s", seconds)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_defaults_and_clamps() {
+        let finding = Finding::builder(FindingCategory::ShallowClone, "Full clone")
+            .affected_jobs(["build"])
+            .confidence(1.7)
+            .savings_secs(Some(-5.0))
+            .build();
+        assert_eq!(finding.severity, Severity::Low);
+        assert_eq!(finding.affected_jobs, vec!["build"]);
+        assert_eq!(finding.confidence, 1.0);
+        assert_eq!(finding.estimated_savings_secs, None);
+
+        let finding = Finding::builder(FindingCategory::Security, "Token in log")
+            .severity(Severity::Critical)
+            .confidence(-0.2)
+            .savings_secs(Some(30.0))
+            .build();
+        assert_eq!(finding.severity, Severity::Critical);
+        assert_eq!(finding.confidence, 0.0);
+        assert_eq!(finding.estimated_savings_secs, Some(30.0));
+
+        let unset = Finding::builder(FindingCategory::Policy, "Rule").build();
+        assert_eq!(unset.confidence, DEFAULT_CONFIDENCE);
+        assert_eq!(
+            Finding::builder(FindingCategory::Policy, "Rule")
+                .confidence(f64::NAN)
+                .build()
+                .confidence,
+            0.0
+        );
+    }
+}
@@ -0,0 +1,253 @@
+use crate::analyzer::report::{Finding, FindingCategory, Severity};
+use crate::cost::{RunnerPricing, DEFAULT_RUNS_PER_MONTH};
+use crate::parser::dag::{JobNode, PipelineDag};
+
+/// Commands, files and actions that only work on a macOS runner.
+const MACOS_MARKERS: &[&str] = &[
+    "xcodebuild",
+    "xcrun",
+    "xcode-select",
+    "simctl",
+    "fastlane",
+    "pod install",
+    "carthage",
+    "codesign",
+    "notarytool",
+    "productbuild",
+    "hdiutil",
+    ".app",
+    ".ipa",
+    ".xcodeproj",
+    ".xcworkspace",
+    "setup-xcode",
+    "apple-actions/",
+];
+
+/// Commands, files and actions that only work on a Windows runner.
+const WINDOWS_MARKERS: &[&str] = &[
+    "msbuild",
+    "signtool",
+    "choco ",
+    "vsdevcmd",
+    "vcvars",
+    "wix",
+    "iscc",
+    "cmd /c",
+    "cmd.exe",
+    ".exe",
+    ".msi",
+    ".bat",
+    ".sln",
+    "c:\\",
+    "setup-msbuild",
+];
+
+#[derive(Clone, Copy)]
+enum RunnerOs {
+    MacOs,
+    Windows,
+}
+
+impl RunnerOs {
+    fn of(runs_on: &str) -> Option<Self> {
+        let runs_on = runs_on.to_lowercase();
+        if runs_on.contains("macos") {
+            Some(RunnerOs::MacOs)
+        } else if runs_on.contains("windows") {
+            Some(RunnerOs::Windows)
+        } else {
+            None
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            RunnerOs::MacOs => "macOS",
+            RunnerOs::Windows => "Windows",
+        }
+    }
+
+    fn markers(self) -> &'static [&'static str] {
+        match self {
+            RunnerOs::MacOs => MACOS_MARKERS,
+            RunnerOs::Windows => WINDOWS_MARKERS,
+        }
+    }
+
+    /// Job ids or names that say the job targets this OS on purpose.
+    fn named_in(self, text: &str) -> bool {
+        let text = text.to_lowercase();
+        match self {
+            RunnerOs::MacOs => ["macos", "mac", "ios", "osx", "darwin", "xcode", "apple"]
+                .iter()
+                .any(|w| text.contains(w)),
+            RunnerOs::Windows => ["windows", "win", "msvc"].iter().any(|w| text.contains(w)),
+        }
+    }
+}
+
+/// Detect jobs on macOS or Windows runners whose steps would run just as well
+/// on Linux.
+///
+/// Linux runners bill at a fraction of the price (macOS at 1/10, Windows at
+/// 1/2). Jobs that run platform-specific tooling, or whose name says they
+/// target the platform, are left alone, as are runners chosen by expression
+/// (usually a cross-platform matrix).
+pub fn detect_costly_runner_os(dag: &PipelineDag) -> Vec<Finding> {
+    let pricing = RunnerPricing::default();
+
+    dag.graph
+        .node_weights()
+        .filter_map(|job| {
+            if job.runs_on.contains("${{") {
+                return None;
+            }
+            let os = RunnerOs::of(&job.runs_on)?;
+            if os.named_in(&job.id) || os.named_in(&job.name) || needs_platform(job, os) {
+                return None;
+            }
+            Some(linux_candidate_finding(job, os, &pricing))
+        })
+        .collect()
+}
+
+fn needs_platform(job: &JobNode, os: RunnerOs) -> bool {
+    job.steps.iter().any(|step| {
+        [
+            step.run.as_deref(),
+            step.uses.as_deref(),
+            Some(step.name.as_str()),
+        ]
+        .into_iter()
+        .flatten()
+        .map(str::to_lowercase)
+        .any(|text| os.markers().iter().any(|m| text.contains(m)))
+    })
+}
+
+fn linux_candidate_finding(job: &JobNode, os: RunnerOs, pricing: &RunnerPricing) -> Finding {
+    let instances = job
+        .matrix
+        .as_ref()
+        .map(|m| m.total_combinations.max(1))
+        .unwrap_or(1);
+    let rate_delta = pricing.rate_per_min(&job.runs_on) - pricing.linux_per_min;
+    let monthly_delta = job.estimated_duration_secs / 60.0
+        * instances as f64
+        * rate_delta
+        * DEFAULT_RUNS_PER_MONTH as f64;
+    let multiple = pricing.rate_per_min(&job.runs_on) / pricing.linux_per_min;
+
+    Finding {
+        severity: match os {
+            RunnerOs::MacOs => Severity::Medium,
+            RunnerOs::Windows => Severity::Low,
+        },
+        category: FindingCategory::RunnerPlatform,
+        title: format!(
+            "Job '{}' runs on {} ({}) without platform-specific steps",
+            job.id,
+            os.label(),
+            job.runs_on
+        ),
+        description: format!(
+            "Job '{}' uses a {} runner, billed at {:.0}x the Linux rate, but none of its \
+            steps use {}-only tooling. At {} runs/month that is about ${:.2}/month more \
+            than the same job on Linux.",
+            job.id,
+            os.label(),
+            multiple,
+            os.label(),
+            DEFAULT_RUNS_PER_MONTH,
+            monthly_delta
+        ),
+        affected_jobs: vec![job.id.clone()],
+        recommendation: format!(
+            "Run '{}' on `ubuntu-latest` unless it must be tested on {}; save ~${:.2}/month. \
+            Use `pipelinex cost --breakdown --runs-per-month <N>` to recompute for your \
+            actual run frequency.",
+            job.id,
+            os.label(),
+            monthly_delta
+        ),
+        fix_command: None,
+        estimated_savings_secs: None,
+        confidence: 0.60,
+        auto_fixable: false,
+        documentation_url: None,
+        blame: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::github::GitHubActionsParser;
+
+    #[test]
+    fn test_macos_runner_without_xcode_flagged() {
+        let yaml = r#"
+on: push
+jobs:
+  unit:
+    runs-on: macos-14
+    steps:
+      - uses: actions/checkout@v4
+      - run: npm ci
+      - run: npm test
+  app:
+    runs-on: macos-14
+    steps:
+      - uses: actions/checkout@v4
+      - run: xcodebuild -scheme App test
+  lint:
+    runs-on: windows-latest
+    steps:
+      - run: npm run lint
+"#;
+        let mut dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        for job in dag.graph.node_weights_mut() {
+            job.estimated_duration_secs = 600.0;
+        }
+
+        let findings = detect_costly_runner_os(&dag);
+        let jobs: Vec<_> = findings
+            .iter()
+            .map(|f| (f.affected_jobs[0].as_str(), f.severity))
+            .collect();
+        assert_eq!(jobs.len(), 2);
+        assert!(jobs.contains(&("unit", Severity::Medium)));
+        assert!(jobs.contains(&("lint", Severity::Low)));
+
+        let unit = findings
+            .iter()
+            .find(|f| f.affected_jobs[0] == "unit")
+            .unwrap();
+        // 10 min x (0.08 - 0.008) $/min x 500 runs
+        assert!(
+            unit.description.contains("$360.00/month"),
+            "{}",
+            unit.description
+        );
+        assert_eq!(unit.category, FindingCategory::RunnerPlatform);
+    }
+
+    #[test]
+    fn test_platform_named_and_matrix_jobs_not_flagged() {
+        let yaml = r#"
+on: push
+jobs:
+  test-macos:
+    runs-on: macos-latest
+    steps: [{ run: npm test }]
+  test:
+    runs-on: ${{ matrix.os }}
+    strategy:
+      matrix:
+        os: [ubuntu-latest, macos-latest]
+    steps: [{ run: npm test }]
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        assert!(detect_costly_runner_os(&dag).is_empty());
+    }
+}
@@ -74,6 +74,8 @@ pub fn detect_runner_right_sizing(dag: &PipelineDag) -> Vec<Finding> {
             estimated_savings_secs: savings,
             confidence: recommendation.confidence,
             auto_fixable: false,
+            documentation_url: None,
+            blame: None,
         });
     }
 
@@ -1,22 +1,202 @@
-use crate::analyzer::report::{AnalysisReport, Finding, Severity};
+use crate::analyzer::report::{AnalysisReport, Finding, FindingCategory, Severity};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::path::Path;
+
+/// SARIF result `level`; GitHub code scanning buckets alerts by it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SarifLevel {
+    Error,
+    Warning,
+    Note,
+    None,
+}
+
+impl SarifLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SarifLevel::Error => "error",
+            SarifLevel::Warning => "warning",
+            SarifLevel::Note => "note",
+            SarifLevel::None => "none",
+        }
+    }
+}
+
+/// SARIF level and `security-severity` score for one [`Severity`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SeverityMapping {
+    pub level: SarifLevel,
+    /// CVSS-style score from 0.0 to 10.0; GitHub ranks security alerts by it.
+    #[serde(default)]
+    pub score: Option<f64>,
+}
+
+impl SeverityMapping {
+    const fn new(level: SarifLevel, score: Option<f64>) -> Self {
+        Self { level, score }
+    }
+}
+
+/// How findings map to SARIF levels and `security-severity` scores, set by
+/// the `[sarif]` table of the config file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SarifOptions {
+    pub critical: SeverityMapping,
+    pub high: SeverityMapping,
+    pub medium: SeverityMapping,
+    pub low: SeverityMapping,
+    pub info: SeverityMapping,
+    /// Score every finding, not only Security and Policy ones.
+    pub score_all_findings: bool,
+}
+
+impl Default for SarifOptions {
+    fn default() -> Self {
+        Self {
+            critical: SeverityMapping::new(SarifLevel::Error, Some(9.5)),
+            high: SeverityMapping::new(SarifLevel::Error, Some(8.0)),
+            medium: SeverityMapping::new(SarifLevel::Warning, Some(5.5)),
+            low: SeverityMapping::new(SarifLevel::Note, Some(3.0)),
+            info: SeverityMapping::new(SarifLevel::Note, None),
+            score_all_findings: false,
+        }
+    }
+}
+
+impl SarifOptions {
+    pub fn mapping(&self, severity: Severity) -> SeverityMapping {
+        match severity {
+            Severity::Critical => self.critical,
+            Severity::High => self.high,
+            Severity::Medium => self.medium,
+            Severity::Low => self.low,
+            Severity::Info => self.info,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ConfigFile {
+    sarif: Option<SarifConfig>,
+}
+
+/// The `[sarif]` table as written: anything left out keeps its default.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct SarifConfig {
+    critical: MappingConfig,
+    high: MappingConfig,
+    medium: MappingConfig,
+    low: MappingConfig,
+    info: MappingConfig,
+    score_all_findings: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct MappingConfig {
+    level: Option<SarifLevel>,
+    score: Option<f64>,
+}
+
+impl MappingConfig {
+    fn over(&self, default: SeverityMapping) -> SeverityMapping {
+        SeverityMapping {
+            level: self.level.unwrap_or(default.level),
+            score: self.score.or(default.score),
+        }
+    }
+}
+
+impl SarifConfig {
+    fn into_options(self) -> SarifOptions {
+        let default = SarifOptions::default();
+        SarifOptions {
+            critical: self.critical.over(default.critical),
+            high: self.high.over(default.high),
+            medium: self.medium.over(default.medium),
+            low: self.low.over(default.low),
+            info: self.info.over(default.info),
+            score_all_findings: self
+                .score_all_findings
+                .unwrap_or(default.score_all_findings),
+        }
+    }
+}
+
+/// Load the `[sarif]` mapping from a PipelineX config file.
+///
+/// Returns `Ok(None)` when the file has no `[sarif]` table. Severities, and
+/// fields of a severity, that the table leaves out keep their defaults.
+pub fn load_sarif_options(path: &Path) -> Result<Option<SarifOptions>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+    let config: ConfigFile = toml::from_str(&content)
+        .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+
+    let options = config.sarif.map(SarifConfig::into_options);
+    if let Some(options) = &options {
+        for severity in [
+            Severity::Critical,
+            Severity::High,
+            Severity::Medium,
+            Severity::Low,
+            Severity::Info,
+        ] {
+            if let Some(score) = options.mapping(severity).score {
+                if !(0.0..=10.0).contains(&score) {
+                    anyhow::bail!(
+                        "Invalid [sarif] score {} for {:?} in {}: must be between 0.0 and 10.0",
+                        score,
+                        severity,
+                        path.display()
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(options)
+}
 
 /// Generate a SARIF 2.1.0 report from an analysis report.
 /// SARIF (Static Analysis Results Interchange Format) is consumed by
 /// GitHub Code Scanning, VS Code, and other tools.
 pub fn to_sarif(report: &AnalysisReport) -> serde_json::Value {
-    let rules: Vec<serde_json::Value> = report
-        .findings
+    to_sarif_with_options(report, &SarifOptions::default())
+}
+
+/// [`to_sarif`] with a custom severity mapping.
+pub fn to_sarif_with_options(report: &AnalysisReport, options: &SarifOptions) -> serde_json::Value {
+    findings_to_sarif_with_options(&report.findings, &report.source_file, options)
+}
+
+/// Generate a SARIF 2.1.0 report from a bare finding list, e.g. the output of
+/// [`security::scan`](crate::security::scan), located in `source_file`.
+pub fn findings_to_sarif(findings: &[Finding], source_file: &str) -> serde_json::Value {
+    findings_to_sarif_with_options(findings, source_file, &SarifOptions::default())
+}
+
+/// [`findings_to_sarif`] with a custom severity mapping.
+pub fn findings_to_sarif_with_options(
+    findings: &[Finding],
+    source_file: &str,
+    options: &SarifOptions,
+) -> serde_json::Value {
+    let rules: Vec<serde_json::Value> = findings
         .iter()
         .enumerate()
-        .map(|(i, f)| sarif_rule(i, f))
+        .map(|(i, f)| sarif_rule(i, f, options))
         .collect();
 
-    let results: Vec<serde_json::Value> = report
-        .findings
+    let results: Vec<serde_json::Value> = findings
         .iter()
         .enumerate()
-        .map(|(i, f)| sarif_result(i, f, &report.source_file))
+        .map(|(i, f)| sarif_result(i, f, source_file, options))
         .collect();
 
     json!({
@@ -40,14 +220,10 @@ pub fn to_sarif(report: &AnalysisReport) -> serde_json::Value {
     })
 }
 
-fn sarif_rule(index: usize, finding: &Finding) -> serde_json::Value {
-    let level = match finding.severity {
-        Severity::Critical | Severity::High => "error",
-        Severity::Medium => "warning",
-        Severity::Low | Severity::Info => "note",
-    };
+fn sarif_rule(index: usize, finding: &Finding, options: &SarifOptions) -> serde_json::Value {
+    let mapping = options.mapping(finding.severity);
 
-    json!({
+    let mut rule = json!({
         "id": format!("PX{:03}", index + 1),
         "name": finding.category.label(),
         "shortDescription": {
@@ -56,29 +232,55 @@ fn sarif_rule(index: usize, finding: &Finding) -> serde_json::Value {
         "fullDescription": {
             "text": finding.description.clone(),
         },
-        "helpUri": "https://github.com/mackeh/PipelineX#what-it-detects",
+        "helpUri": finding.help_url(),
         "defaultConfiguration": {
-            "level": level,
+            "level": mapping.level.as_str(),
         },
         "properties": {
             "category": finding.category.label(),
             "confidence": finding.confidence,
             "autoFixable": finding.auto_fixable,
             "estimatedSavingsSeconds": finding.estimated_savings_secs,
+            "tags": rule_tags(finding),
         }
-    })
+    });
+
+    // GitHub code scanning ranks security alerts by this CVSS-style score
+    if is_security(finding) || options.score_all_findings {
+        if let Some(score) = mapping.score {
+            rule["properties"]["security-severity"] = json!(format!("{:.1}", score));
+        }
+    }
+
+    rule
+}
+
+fn is_security(finding: &Finding) -> bool {
+    matches!(
+        finding.category,
+        FindingCategory::Security | FindingCategory::Policy
+    )
 }
 
-fn sarif_result(index: usize, finding: &Finding, source_file: &str) -> serde_json::Value {
-    let level = match finding.severity {
-        Severity::Critical | Severity::High => "error",
-        Severity::Medium => "warning",
-        Severity::Low | Severity::Info => "note",
-    };
+fn rule_tags(finding: &Finding) -> Vec<&str> {
+    let mut tags = vec![finding.category.label()];
+    if is_security(finding) {
+        tags.push("security");
+    } else {
+        tags.push("ci-performance");
+    }
+    tags
+}
 
+fn sarif_result(
+    index: usize,
+    finding: &Finding,
+    source_file: &str,
+    options: &SarifOptions,
+) -> serde_json::Value {
     let mut result = json!({
         "ruleId": format!("PX{:03}", index + 1),
-        "level": level,
+        "level": options.mapping(finding.severity).level.as_str(),
         "message": {
             "text": format!("{}\n\nRecommendation: {}", finding.description, finding.recommendation),
         },
@@ -159,4 +361,118 @@ jobs:
         assert_eq!(runs[0]["tool"]["driver"]["name"], "PipelineX");
         assert!(runs[0]["results"].is_array());
     }
+
+    #[test]
+    fn test_security_findings_sarif() {
+        let yaml = r#"
+on: issues
+jobs:
+  triage:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "${{ github.event.issue.title }}"
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "triage.yml".to_string()).unwrap();
+        let findings = crate::security::scan(&dag);
+        let sarif = findings_to_sarif(&findings, "triage.yml");
+
+        let run = &sarif["runs"][0];
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), findings.len());
+        let index = findings
+            .iter()
+            .position(|f| f.title.starts_with("Expression injection"))
+            .unwrap();
+        assert_eq!(results[index]["level"], "error");
+        assert_eq!(
+            results[index]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "triage.yml"
+        );
+
+        let rule = &run["tool"]["driver"]["rules"][index];
+        assert_eq!(rule["id"], results[index]["ruleId"]);
+        let tags = rule["properties"]["tags"].as_array().unwrap();
+        assert!(tags.contains(&json!("security")));
+        assert_eq!(rule["properties"]["security-severity"], "9.5");
+        assert_eq!(
+            rule["helpUri"],
+            "https://github.com/mackeh/PipelineX/blob/main/docs/FINDINGS.md#security"
+        );
+        assert_eq!(
+            findings[index].documentation_url.as_deref(),
+            rule["helpUri"].as_str()
+        );
+    }
+
+    #[test]
+    fn test_sarif_options_map_levels_and_scores() {
+        let finding = |severity, category| {
+            Finding::builder(category, "finding")
+                .severity(severity)
+                .build()
+        };
+        let findings = vec![
+            finding(Severity::Medium, FindingCategory::Security),
+            finding(Severity::Low, FindingCategory::Security),
+            finding(Severity::Medium, FindingCategory::MissingCache),
+        ];
+
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("config.toml");
+        std::fs::write(
+            &config,
+            "[sarif]\nscore_all_findings = true\n\n[sarif.medium]\nlevel = \"warning\"\nscore = 6.5\n\n[sarif.low]\nlevel = \"warning\"\nscore = 4.0\n",
+        )
+        .unwrap();
+        let options = load_sarif_options(&config).unwrap().unwrap();
+        assert_eq!(
+            options.medium,
+            SeverityMapping::new(SarifLevel::Warning, Some(6.5))
+        );
+        // Tables left out keep their defaults
+        assert_eq!(options.critical, SarifOptions::default().critical);
+
+        // So do fields left out of a table
+        std::fs::write(
+            &config,
+            "[sarif.medium]\nscore = 6.0\n\n[sarif.low]\nlevel = \"warning\"\n",
+        )
+        .unwrap();
+        let partial = load_sarif_options(&config).unwrap().unwrap();
+        assert_eq!(
+            partial.medium,
+            SeverityMapping::new(SarifLevel::Warning, Some(6.0))
+        );
+        assert_eq!(
+            partial.low,
+            SeverityMapping::new(SarifLevel::Warning, Some(3.0))
+        );
+
+        let sarif = findings_to_sarif_with_options(&findings, "ci.yml", &options);
+        let run = &sarif["runs"][0];
+        let levels: Vec<&str> = run["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["level"].as_str().unwrap())
+            .collect();
+        assert_eq!(levels, vec!["warning", "warning", "warning"]);
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(rules[0]["properties"]["security-severity"], "6.5");
+        assert_eq!(rules[1]["properties"]["security-severity"], "4.0");
+        assert_eq!(rules[1]["defaultConfiguration"]["level"], "warning");
+        assert_eq!(rules[2]["properties"]["security-severity"], "6.5");
+
+        // Defaults: Low is a note and only security findings are scored
+        let sarif = findings_to_sarif(&findings, "ci.yml");
+        let run = &sarif["runs"][0];
+        assert_eq!(run["results"][0]["level"], "warning");
+        assert_eq!(run["results"][1]["level"], "note");
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(rules[0]["properties"]["security-severity"], "5.5");
+        assert!(rules[2]["properties"].get("security-severity").is_none());
+
+        std::fs::write(&config, "[sarif.high]\nlevel = \"error\"\nscore = 12.0\n").unwrap();
+        assert!(load_sarif_options(&config).is_err());
+    }
 }
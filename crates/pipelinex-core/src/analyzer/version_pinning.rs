@@ -0,0 +1,142 @@
+use crate::analyzer::report::{Finding, FindingCategory, Severity};
+use crate::parser::dag::PipelineDag;
+
+/// A version literal is flagged once it appears in at least this many steps...
+const MIN_REPEATS: usize = 3;
+/// ...spread over at least this many jobs.
+const MIN_JOBS: usize = 2;
+
+/// Detect the same tool version (`node-version: 20`, `go-version: 1.22`)
+/// hardcoded in several jobs, where one edit should update them all.
+///
+/// Only GitHub Actions `with:` inputs named `*-version` / `*_version` are
+/// considered; expressions such as `${{ matrix.node }}` are already
+/// centralized.
+pub fn detect_repeated_versions(dag: &PipelineDag) -> Vec<Finding> {
+    if dag.provider != "github-actions" {
+        return Vec::new();
+    }
+
+    // (input, version) -> jobs using it, in DAG order, and how many steps
+    let mut usages: Vec<((String, String), Vec<String>, usize)> = Vec::new();
+    for job in dag.graph.node_weights() {
+        for step in &job.steps {
+            for (key, value) in &step.with {
+                let value = value.trim();
+                if !is_version_input(key) || value.is_empty() || value.contains("${{") {
+                    continue;
+                }
+                let usage_key = (key.clone(), value.to_string());
+                match usages.iter_mut().find(|(k, _, _)| *k == usage_key) {
+                    Some((_, jobs, steps)) => {
+                        if !jobs.contains(&job.id) {
+                            jobs.push(job.id.clone());
+                        }
+                        *steps += 1;
+                    }
+                    None => usages.push((usage_key, vec![job.id.clone()], 1)),
+                }
+            }
+        }
+    }
+
+    usages
+        .into_iter()
+        .filter(|(_, jobs, steps)| *steps >= MIN_REPEATS && jobs.len() >= MIN_JOBS)
+        .map(|((key, version), jobs, steps)| finding(&key, &version, jobs, steps))
+        .collect()
+}
+
+fn is_version_input(key: &str) -> bool {
+    let key = key.to_lowercase();
+    key.ends_with("-version") || key.ends_with("_version")
+}
+
+fn finding(key: &str, version: &str, jobs: Vec<String>, steps: usize) -> Finding {
+    let env_name = key.to_uppercase().replace('-', "_");
+    Finding::builder(
+        FindingCategory::Maintainability,
+        format!("`{}: {}` is repeated in {} jobs", key, version, jobs.len()),
+    )
+    .severity(Severity::Low)
+    .description(format!(
+        "{} steps across jobs {} pin `{}: {}` separately. Upgrading means editing \
+        every copy, and a missed one leaves jobs testing against different versions.",
+        steps,
+        jobs.join(", "),
+        key,
+        version
+    ))
+    .affected_jobs(jobs)
+    .recommendation(format!(
+        "Define the version once in the workflow-level `env` and reference it from each step:\n\n  \
+        env:\n    {env}: '{version}'\n\n  \
+        with:\n    {key}: ${{{{ env.{env} }}}}",
+        env = env_name,
+        version = version,
+        key = key
+    ))
+    .confidence(0.9)
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::github::GitHubActionsParser;
+
+    #[test]
+    fn test_repeated_node_version_is_flagged() {
+        let yaml = r#"
+on: push
+jobs:
+  lint:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/setup-node@v4
+        with:
+          node-version: 20
+      - run: npm run lint
+  test:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/setup-node@v4
+        with:
+          node-version: 20
+      - run: npm test
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/setup-node@v4
+        with:
+          node-version: 20
+      - uses: actions/setup-go@v5
+        with:
+          go-version: '1.22'
+      - run: npm run build
+  matrix:
+    runs-on: ubuntu-latest
+    strategy:
+      matrix:
+        node: [18, 20]
+    steps:
+      - uses: actions/setup-node@v4
+        with:
+          node-version: ${{ matrix.node }}
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let findings = detect_repeated_versions(&dag);
+
+        assert_eq!(findings.len(), 1);
+        let finding = &findings[0];
+        assert_eq!(finding.category, FindingCategory::Maintainability);
+        assert_eq!(finding.severity, Severity::Low);
+        assert_eq!(finding.title, "`node-version: 20` is repeated in 3 jobs");
+        assert_eq!(finding.affected_jobs, vec!["lint", "test", "build"]);
+        assert!(finding.recommendation.contains("NODE_VERSION: '20'"));
+        assert!(finding
+            .recommendation
+            .contains("node-version: ${{ env.NODE_VERSION }}"));
+        assert!(finding.estimated_savings_secs.is_none());
+    }
+}
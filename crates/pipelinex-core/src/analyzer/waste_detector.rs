@@ -1,5 +1,9 @@
+use crate::analyzer::cache_detector::shell_commands;
 use crate::analyzer::report::{Finding, FindingCategory, Severity};
-use crate::parser::dag::PipelineDag;
+use crate::cost::DEFAULT_RUNS_PER_MONTH;
+use crate::parser::dag::{JobNode, PipelineDag, StepInfo};
+use regex::Regex;
+use std::collections::BTreeSet;
 
 /// Detect various forms of waste in the pipeline configuration.
 pub fn detect_waste(dag: &PipelineDag) -> Vec<Finding> {
@@ -8,22 +12,88 @@ pub fn detect_waste(dag: &PipelineDag) -> Vec<Finding> {
     findings.extend(detect_missing_path_filters(dag));
     findings.extend(detect_full_git_clone(dag));
     findings.extend(detect_redundant_checkouts(dag));
+    findings.extend(detect_dead_artifacts(dag));
     findings.extend(detect_missing_concurrency(dag));
     findings.extend(detect_matrix_bloat(dag));
+    findings.extend(detect_redundant_toolchain_installs(dag));
+    findings.extend(detect_duplicate_steps(dag));
 
     findings
 }
 
-/// Detect workflows that don't use path-based filtering.
+/// Detect workflows that don't use path-based filtering, or whose filters match everything.
 fn detect_missing_path_filters(dag: &PipelineDag) -> Vec<Finding> {
     let mut findings = Vec::new();
 
-    let has_path_filter = dag
+    // GitLab scopes jobs rather than the pipeline, via `rules: changes:`, so
+    // without a trigger filter every job needs its own
+    let has_trigger_filter = dag
         .triggers
         .iter()
         .any(|t| t.paths.is_some() || t.paths_ignore.is_some());
+    let unscoped_jobs: Vec<String> = if has_trigger_filter {
+        Vec::new()
+    } else {
+        dag.graph
+            .node_weights()
+            .filter(|j| j.paths_filter.is_none() && j.paths_ignore.is_none())
+            .map(|j| j.id.clone())
+            .collect()
+    };
+
+    let broad_filters: Vec<&str> = dag
+        .triggers
+        .iter()
+        .filter_map(|t| t.paths.as_ref())
+        .flatten()
+        .map(String::as_str)
+        .collect();
+    let only_broad_filters = !broad_filters.is_empty()
+        && broad_filters.iter().all(|p| is_catch_all_pattern(p))
+        && dag.triggers.iter().all(|t| t.paths_ignore.is_none());
+
+    if only_broad_filters && dag.job_count() > 1 {
+        let scoped = infer_relevant_paths(dag);
+        let suggestion = if scoped.is_empty() {
+            "        - 'src/**'".to_string()
+        } else {
+            scoped
+                .iter()
+                .map(|p| format!("        - '{}'", p))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
 
-    if !has_path_filter && dag.job_count() > 1 {
+        findings.push(Finding {
+            severity: Severity::Medium,
+            category: FindingCategory::MissingPathFilter,
+            title: "Path filter matches every file".to_string(),
+            description: format!(
+                "The trigger's `paths` filter ({}) matches every file in the repository, \
+                so it is functionally the same as having no filter. Every change runs the \
+                full pipeline.",
+                broad_filters
+                    .iter()
+                    .map(|p| format!("'{}'", p))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            affected_jobs: dag.job_ids(),
+            recommendation: format!(
+                "Scope the filter to the directories this workflow builds:\n\
+                \n  on:\n    push:\n      paths:\n{}",
+                suggestion
+            ),
+            fix_command: None,
+            estimated_savings_secs: None,
+            confidence: if scoped.is_empty() { 0.70 } else { 0.80 },
+            auto_fixable: false,
+            documentation_url: None,
+            blame: None,
+        });
+    }
+
+    if !unscoped_jobs.is_empty() && dag.job_count() > 1 {
         findings.push(Finding {
             severity: Severity::Medium,
             category: FindingCategory::MissingPathFilter,
@@ -32,7 +102,7 @@ fn detect_missing_path_filters(dag: &PipelineDag) -> Vec<Finding> {
                 documentation-only or config-only changes. Adding paths-ignore for docs/, \
                 *.md, and similar patterns can eliminate unnecessary runs."
                 .to_string(),
-            affected_jobs: dag.job_ids(),
+            affected_jobs: unscoped_jobs,
             recommendation: "Add a `paths-ignore` filter to skip the pipeline for \
                 non-code changes:\n\
                 \n  on:\n    push:\n      paths-ignore:\n        - 'docs/**'\n        \
@@ -42,14 +112,80 @@ fn detect_missing_path_filters(dag: &PipelineDag) -> Vec<Finding> {
             estimated_savings_secs: None,
             confidence: 0.85,
             auto_fixable: true,
+            documentation_url: None,
+            blame: None,
         });
     }
 
     findings
 }
 
+fn is_catch_all_pattern(pattern: &str) -> bool {
+    matches!(
+        pattern.trim().trim_start_matches("./"),
+        "**" | "*" | "**/*" | "**/**" | "/**"
+    )
+}
+
+/// Infer the package directories a workflow operates on from its `run`
+/// commands and their working directories.
+fn infer_relevant_paths(dag: &PipelineDag) -> Vec<String> {
+    let dir_re = Regex::new(
+        r"(?:\bcd\s+|--prefix[= ]|\s-C\s+|--manifest-path[= ])([A-Za-z0-9_][A-Za-z0-9_./-]*)",
+    )
+    .unwrap();
+
+    let mut dirs = BTreeSet::new();
+    for job in dag.graph.node_weights() {
+        for step in &job.steps {
+            let Some(run) = &step.run else {
+                continue;
+            };
+            let working_dir = job
+                .step_working_directory(step)
+                .map(|d| d.trim_start_matches("./").trim_end_matches('/'))
+                .filter(|d| is_package_dir(d));
+            if let Some(working_dir) = working_dir {
+                dirs.insert(format!("{}/**", working_dir));
+            }
+            for caps in dir_re.captures_iter(run) {
+                let mut dir = caps[1].trim_end_matches('/').to_string();
+                if dir.ends_with(".toml") || dir.ends_with(".json") {
+                    dir = dir
+                        .rsplit_once('/')
+                        .map(|(d, _)| d.to_string())
+                        .unwrap_or_default();
+                }
+                if is_package_dir(&dir) {
+                    // Relative commands resolve against the step's working directory
+                    let dir = match working_dir {
+                        Some(base) => format!("{}/{}", base, dir),
+                        None => dir,
+                    };
+                    dirs.insert(format!("{}/**", dir));
+                }
+            }
+        }
+    }
+
+    dirs.into_iter().collect()
+}
+
+/// A repository-relative directory below the root, without expressions.
+fn is_package_dir(dir: &str) -> bool {
+    !dir.is_empty()
+        && dir != "."
+        && !dir.starts_with("..")
+        && !dir.starts_with('/')
+        && !dir.contains("${{")
+}
+
 /// Detect full git clones (missing fetch-depth: 1).
 fn detect_full_git_clone(dag: &PipelineDag) -> Vec<Finding> {
+    if dag.provider == "gitlab-ci" {
+        return detect_gitlab_clone_depth(dag).into_iter().collect();
+    }
+
     let mut findings = Vec::new();
 
     for job in dag.graph.node_weights() {
@@ -77,6 +213,8 @@ fn detect_full_git_clone(dag: &PipelineDag) -> Vec<Finding> {
                         estimated_savings_secs: Some(30.0),
                         confidence: 0.80,
                         auto_fixable: true,
+                        documentation_url: None,
+                        blame: None,
                     });
                     break; // Only report once per job
                 }
@@ -87,6 +225,82 @@ fn detect_full_git_clone(dag: &PipelineDag) -> Vec<Finding> {
     findings
 }
 
+/// GitLab's default "Git shallow clone" depth for new projects.
+const GITLAB_DEFAULT_GIT_DEPTH: u32 = 20;
+
+/// GitLab already clones shallowly (depth 20) unless `GIT_DEPTH` says
+/// otherwise, so only an explicit `GIT_DEPTH: "0"` (full history) or a depth
+/// above the default is worth flagging.
+fn detect_gitlab_clone_depth(dag: &PipelineDag) -> Option<Finding> {
+    if dag.env.get("GIT_STRATEGY").is_some_and(|s| s == "none") {
+        return None;
+    }
+
+    let mut full = Vec::new();
+    let mut deep = Vec::new();
+    for job in dag.graph.node_weights() {
+        if job.env.get("GIT_STRATEGY").is_some_and(|s| s == "none") {
+            continue;
+        }
+        let depth = job
+            .env
+            .get("GIT_DEPTH")
+            .or_else(|| dag.env.get("GIT_DEPTH"))
+            .and_then(|d| d.trim().parse::<u32>().ok());
+        match depth {
+            Some(0) => full.push(job.id.clone()),
+            Some(d) if d > GITLAB_DEFAULT_GIT_DEPTH => deep.push(job.id.clone()),
+            _ => {}
+        }
+    }
+    if full.is_empty() && deep.is_empty() {
+        return None;
+    }
+
+    let full_clone = !full.is_empty();
+    // Per job, matching the per-checkout findings for GitHub Actions
+    let savings = 30.0 * full.len() as f64 + 10.0 * deep.len() as f64;
+    let description = if full_clone {
+        format!(
+            "{} job(s) set `GIT_DEPTH: \"0\"`, so they fetch the full git history. \
+            For large repos this adds 30s-3min per job.",
+            full.len()
+        )
+    } else {
+        format!(
+            "{} job(s) set `GIT_DEPTH` above GitLab's default of {}, fetching more \
+            history than most jobs need.",
+            deep.len(),
+            GITLAB_DEFAULT_GIT_DEPTH
+        )
+    };
+    let mut affected = full;
+    affected.extend(deep);
+    Some(Finding {
+        severity: if full_clone {
+            Severity::Medium
+        } else {
+            Severity::Low
+        },
+        category: FindingCategory::ShallowClone,
+        title: "Consider shallow clone via GIT_DEPTH".to_string(),
+        description,
+        estimated_savings_secs: Some(savings),
+        affected_jobs: affected,
+        recommendation: format!(
+            "Drop the `GIT_DEPTH` override to use GitLab's default depth of {}, or \
+            set a smaller depth, unless jobs need full git history (e.g., for \
+            changelog generation).",
+            GITLAB_DEFAULT_GIT_DEPTH
+        ),
+        fix_command: None,
+        confidence: if full_clone { 0.80 } else { 0.50 },
+        auto_fixable: false,
+        documentation_url: None,
+        blame: None,
+    })
+}
+
 /// Detect multiple jobs all independently checking out code and installing deps.
 fn detect_redundant_checkouts(dag: &PipelineDag) -> Vec<Finding> {
     let mut findings = Vec::new();
@@ -133,39 +347,313 @@ fn detect_redundant_checkouts(dag: &PipelineDag) -> Vec<Finding> {
             estimated_savings_secs: Some(120.0),
             confidence: 0.75,
             auto_fixable: false,
+            documentation_url: None,
+            blame: None,
         });
     }
 
     findings
 }
 
-/// Detect missing concurrency controls.
+/// Duration assumed for a duplicated step with no estimate.
+const DEFAULT_DUPLICATE_STEP_SECS: f64 = 10.0;
+
+/// Detect steps repeated verbatim within one job, typically from copy-paste.
+///
+/// Checkouts are compared by the repository, ref and path they check out, so
+/// a job pulling in a second repository (`with.repository`) is not flagged.
+/// Other steps must match exactly: action, inputs, command and directory.
+/// Providers that run each script line as a step in one shell (GitLab,
+/// Jenkins, Buildkite) don't track `cd`, so steps after a directory change
+/// never match steps before it.
+fn detect_duplicate_steps(dag: &PipelineDag) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for job in dag.graph.node_weights() {
+        let mut shell_dir = 0;
+        let mut seen: Vec<(String, usize, &StepInfo)> = Vec::new();
+        for step in &job.steps {
+            let key = step_identity(job, step).map(|key| format!("{}\0{}", key, shell_dir));
+            if step.run.as_deref().is_some_and(changes_directory) {
+                shell_dir += 1;
+            }
+            let Some(key) = key else {
+                continue;
+            };
+            match seen.iter_mut().find(|(k, _, _)| *k == key) {
+                Some((_, count, _)) => *count += 1,
+                None => seen.push((key, 1, step)),
+            }
+        }
+
+        for (_, count, step) in seen.into_iter().filter(|(_, count, _)| *count > 1) {
+            let is_checkout = step
+                .uses
+                .as_deref()
+                .is_some_and(|u| u.starts_with("actions/checkout"));
+            let label = step
+                .uses
+                .clone()
+                .or_else(|| {
+                    step.run
+                        .as_deref()
+                        .and_then(|r| r.lines().next())
+                        .map(str::to_string)
+                })
+                .unwrap_or_else(|| step.name.clone());
+            let extra = count - 1;
+
+            let description = if is_checkout {
+                format!(
+                    "Job '{}' checks out the same repository {} times. Every checkout \
+                    after the first clones the code again without changing the workspace.",
+                    job.id, count
+                )
+            } else {
+                format!(
+                    "Job '{}' contains {} identical copies of the step `{}`. Repeated \
+                    steps usually come from copy-paste and redo the same work.",
+                    job.id, count, label
+                )
+            };
+            let savings = step
+                .estimated_duration_secs
+                .unwrap_or(DEFAULT_DUPLICATE_STEP_SECS)
+                * extra as f64;
+
+            findings.push(
+                Finding::builder(
+                    FindingCategory::RedundantSteps,
+                    format!("'{}' runs `{}` {} times", job.id, label, count),
+                )
+                .severity(if is_checkout {
+                    Severity::Medium
+                } else {
+                    Severity::Low
+                })
+                .description(description)
+                .affected_jobs([job.id.as_str()])
+                .recommendation(format!(
+                    "Remove the {} duplicate `{}` step{} from '{}'.",
+                    extra,
+                    label,
+                    if extra == 1 { "" } else { "s" },
+                    job.id
+                ))
+                .savings_secs(Some(savings))
+                .confidence(if is_checkout { 0.85 } else { 0.7 })
+                .build(),
+            );
+        }
+    }
+
+    findings
+}
+
+/// What makes two steps in `job` the same work, or `None` for steps with no action or command.
+///
+/// Steps with different `env:` run differently (`TARGET=staging` vs
+/// `TARGET=production`), so the step's env is part of its identity.
+fn step_identity(job: &JobNode, step: &StepInfo) -> Option<String> {
+    let dir = job.step_working_directory(step).unwrap_or("");
+    let env: BTreeSet<_> = step.env.iter().collect();
+    match (&step.uses, &step.run) {
+        (Some(uses), _) if uses.starts_with("actions/checkout") => {
+            let input = |key: &str| step.with.get(key).map(String::as_str).unwrap_or("");
+            Some(format!(
+                "checkout\0{}\0{}\0{}",
+                input("repository"),
+                input("ref"),
+                input("path")
+            ))
+        }
+        (Some(uses), _) => {
+            let inputs: BTreeSet<_> = step.with.iter().collect();
+            Some(format!("uses\0{}\0{:?}\0{}\0{:?}", uses, inputs, dir, env))
+        }
+        (None, Some(run)) => Some(format!("run\0{}\0{}\0{:?}", run.trim(), dir, env)),
+        (None, None) => None,
+    }
+}
+
+/// Whether a script changes the shell's directory (`cd`, `pushd`, `popd`).
+fn changes_directory(script: &str) -> bool {
+    let script = script.to_lowercase();
+    let changes = shell_commands(&script).any(|command| {
+        let word = command.split_whitespace().next().unwrap_or("");
+        matches!(word, "cd" | "pushd" | "popd")
+    });
+    changes
+}
+
+/// Upload time assumed for an artifact step with no estimate.
+const DEFAULT_UPLOAD_SECS: f64 = 15.0;
+
+/// Detect `upload-artifact` steps whose artifact no job in the workflow downloads.
+///
+/// Reusable workflows (`workflow_call`) are skipped: their callers may be the
+/// consumers. Shell steps that download artifacts (`gh run download`) count as
+/// downloading everything.
+fn detect_dead_artifacts(dag: &PipelineDag) -> Vec<Finding> {
+    if dag.triggers.iter().any(|t| t.event == "workflow_call") {
+        return Vec::new();
+    }
+
+    let mut downloaded: Vec<&str> = Vec::new();
+    for job in dag.graph.node_weights() {
+        downloaded.extend(
+            job.data_flow
+                .artifacts_downloaded
+                .iter()
+                .map(String::as_str),
+        );
+        if job.steps.iter().any(|s| {
+            s.run
+                .as_ref()
+                .is_some_and(|r| r.contains("gh run download"))
+        }) {
+            downloaded.push("*");
+        }
+    }
+
+    let mut findings = Vec::new();
+    for job in dag.graph.node_weights() {
+        for step in &job.steps {
+            let Some(uses) = &step.uses else { continue };
+            if !uses.starts_with("actions/upload-artifact") {
+                continue;
+            }
+            let name = step
+                .with
+                .get("name")
+                .map(String::as_str)
+                .unwrap_or("artifact");
+            if downloaded.iter().any(|d| artifact_names_match(name, d)) {
+                continue;
+            }
+
+            findings.push(Finding {
+                severity: Severity::Low,
+                category: FindingCategory::RedundantSteps,
+                title: format!("Artifact '{}' from '{}' is never downloaded", name, job.id),
+                description: format!(
+                    "Job '{}' uploads artifact '{}', but no job in this workflow downloads \
+                    it. Every run pays the upload time and artifact storage for output \
+                    nothing consumes.",
+                    job.id, name
+                ),
+                affected_jobs: vec![job.id.clone()],
+                recommendation: format!(
+                    "Remove the upload step, or download '{}' with actions/download-artifact \
+                    in the job that needs it. If it is kept for people to inspect, upload it \
+                    only on failure (`if: failure()`) or shorten `retention-days`.",
+                    name
+                ),
+                fix_command: None,
+                estimated_savings_secs: Some(
+                    step.estimated_duration_secs.unwrap_or(DEFAULT_UPLOAD_SECS),
+                ),
+                confidence: 0.60,
+                auto_fixable: false,
+                documentation_url: None,
+                blame: None,
+            });
+        }
+    }
+
+    findings
+}
+
+/// Whether a download of `downloaded` can fetch the artifact uploaded as `uploaded`.
+///
+/// Names built from expressions (`dist-${{ matrix.os }}`) are compared on the
+/// text before the first expression.
+fn artifact_names_match(uploaded: &str, downloaded: &str) -> bool {
+    if downloaded == "*" || uploaded == downloaded {
+        return true;
+    }
+    let static_prefix = |name: &str| name.find("${{").map(|i| name[..i].to_string());
+    match (static_prefix(uploaded), static_prefix(downloaded)) {
+        (Some(up), Some(down)) => up.starts_with(&down) || down.starts_with(&up),
+        (Some(up), None) => downloaded.starts_with(&up),
+        (None, Some(down)) => uploaded.starts_with(&down),
+        (None, None) => false,
+    }
+}
+
+/// Share of push-triggered runs that a newer push to the same ref supersedes
+/// before they finish.
+const SUPERSEDED_RUN_SHARE: f64 = 0.2;
+
+/// Share of a superseded run's runner time already spent when the newer push lands.
+const SUPERSEDED_RUN_PROGRESS: f64 = 0.5;
+
+/// Detect push-triggered workflows whose superseded runs are never cancelled.
+///
+/// The waste is estimated from the runner time of one run (matrix instances
+/// included), [`SUPERSEDED_RUN_SHARE`] and [`SUPERSEDED_RUN_PROGRESS`], at
+/// [`DEFAULT_RUNS_PER_MONTH`] runs.
 fn detect_missing_concurrency(dag: &PipelineDag) -> Vec<Finding> {
     // For workflows triggered by push to the same branch, concurrent runs can queue up
     let has_push_trigger = dag.triggers.iter().any(|t| t.event == "push");
-
-    if has_push_trigger {
-        return vec![Finding {
-            severity: Severity::Low,
-            category: FindingCategory::ConcurrencyControl,
-            title: "No concurrency control configured".to_string(),
-            description: "This workflow triggers on push but has no concurrency settings. \
-                Rapid pushes can cause multiple runs to queue, wasting compute on \
-                already-superseded commits."
-                .to_string(),
-            affected_jobs: dag.job_ids(),
-            recommendation: "Add concurrency controls to cancel in-progress runs:\n\
-                \n  concurrency:\n    group: ${{ github.workflow }}-${{ github.ref }}\n    \
-                cancel-in-progress: true"
-                .to_string(),
-            fix_command: None,
-            estimated_savings_secs: None,
-            confidence: 0.70,
-            auto_fixable: true,
-        }];
+    if !has_push_trigger
+        || dag
+            .concurrency
+            .as_ref()
+            .is_some_and(|c| c.cancel_in_progress)
+    {
+        return Vec::new();
     }
 
-    Vec::new()
+    let runner_secs: f64 = dag
+        .graph
+        .node_weights()
+        .map(|job| {
+            let instances = job
+                .matrix
+                .as_ref()
+                .map(|m| m.total_combinations.max(1))
+                .unwrap_or(1);
+            job.estimated_duration_secs * instances as f64
+        })
+        .sum();
+    let wasted_per_run = runner_secs * SUPERSEDED_RUN_SHARE * SUPERSEDED_RUN_PROGRESS;
+    let wasted_minutes = wasted_per_run * DEFAULT_RUNS_PER_MONTH as f64 / 60.0;
+
+    let title = if dag.concurrency.is_some() {
+        "Concurrency group does not cancel superseded runs"
+    } else {
+        "No concurrency control configured"
+    };
+    let recommendation = if dag.provider == "gitlab-ci" {
+        "Mark jobs `interruptible: true` so GitLab auto-cancels redundant pipelines \
+        on the same branch:\n\n  default:\n    interruptible: true"
+    } else {
+        "Add concurrency controls to cancel in-progress runs:\n\
+        \n  concurrency:\n    group: ${{ github.workflow }}-${{ github.ref }}\n    \
+        cancel-in-progress: true"
+    };
+
+    vec![Finding::builder(FindingCategory::ConcurrencyControl, title)
+        .description(format!(
+            "This workflow triggers on push but never cancels superseded runs, so rapid \
+            pushes keep runners busy on commits that are already outdated. Assuming {:.0}% \
+            of {} runs/month are superseded about {:.0}% of the way through, that wastes \
+            about {:.0} runner-minutes per month ({:.0}s of jobs per run × {} × {}).",
+            SUPERSEDED_RUN_SHARE * 100.0,
+            DEFAULT_RUNS_PER_MONTH,
+            SUPERSEDED_RUN_PROGRESS * 100.0,
+            wasted_minutes,
+            runner_secs,
+            SUPERSEDED_RUN_SHARE,
+            SUPERSEDED_RUN_PROGRESS
+        ))
+        .affected_jobs(dag.job_ids())
+        .recommendation(recommendation)
+        .savings_secs(Some(wasted_per_run))
+        .confidence(0.70)
+        .auto_fixable(true)
+        .build()]
 }
 
 /// Detect overly large matrix strategies.
@@ -199,6 +687,8 @@ fn detect_matrix_bloat(dag: &PipelineDag) -> Vec<Finding> {
                     ),
                     confidence: 0.75,
                     auto_fixable: false,
+                    documentation_url: None,
+                    blame: None,
                 });
             }
         }
@@ -207,10 +697,337 @@ fn detect_matrix_bloat(dag: &PipelineDag) -> Vec<Finding> {
     findings
 }
 
+/// Tools preinstalled on common GitHub-hosted runner images, with their default versions.
+/// `None` means the tool is present but its version isn't relevant (apt packages).
+struct RunnerImage {
+    labels: &'static [&'static str],
+    tools: &'static [(&'static str, Option<&'static str>)],
+}
+
+const APT_PREINSTALLED: &[&str] = &[
+    "git",
+    "curl",
+    "wget",
+    "jq",
+    "zip",
+    "unzip",
+    "make",
+    "gcc",
+    "g++",
+    "tar",
+    "openssl",
+    "ca-certificates",
+    "rsync",
+    "docker.io",
+];
+
+const RUNNER_IMAGES: &[RunnerImage] = &[
+    RunnerImage {
+        labels: &["ubuntu-latest", "ubuntu-24.04"],
+        tools: &[
+            ("node", Some("20")),
+            ("python", Some("3.12")),
+            ("java", Some("17")),
+            ("go", Some("1.22")),
+            ("apt", None),
+        ],
+    },
+    RunnerImage {
+        labels: &["ubuntu-22.04"],
+        tools: &[
+            ("node", Some("18")),
+            ("python", Some("3.10")),
+            ("java", Some("11")),
+            ("go", Some("1.21")),
+            ("apt", None),
+        ],
+    },
+    RunnerImage {
+        labels: &["windows-latest", "windows-2022"],
+        tools: &[
+            ("node", Some("18")),
+            ("python", Some("3.9")),
+            ("java", Some("8")),
+        ],
+    },
+    RunnerImage {
+        labels: &["macos-latest", "macos-14"],
+        tools: &[
+            ("node", Some("20")),
+            ("python", Some("3.12")),
+            ("java", Some("21")),
+        ],
+    },
+];
+
+/// setup-* actions and the `with:` input that selects the version.
+const SETUP_ACTIONS: &[(&str, &str, &str)] = &[
+    ("actions/setup-node", "node", "node-version"),
+    ("actions/setup-python", "python", "python-version"),
+    ("actions/setup-java", "java", "java-version"),
+    ("actions/setup-go", "go", "go-version"),
+];
+
+/// Detect setup actions and apt installs for tools the runner image already provides.
+fn detect_redundant_toolchain_installs(dag: &PipelineDag) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for job in dag.graph.node_weights() {
+        let Some(image) = RUNNER_IMAGES
+            .iter()
+            .find(|img| img.labels.contains(&job.runs_on.trim()))
+        else {
+            continue;
+        };
+
+        for step in &job.steps {
+            if let Some(finding) = redundant_setup_action(job, step, image) {
+                findings.push(finding);
+            }
+            if let Some(finding) = redundant_apt_install(job, step, image) {
+                findings.push(finding);
+            }
+        }
+    }
+
+    findings
+}
+
+fn redundant_setup_action(job: &JobNode, step: &StepInfo, image: &RunnerImage) -> Option<Finding> {
+    let uses = step.uses.as_deref()?;
+    let (action, tool, input) = SETUP_ACTIONS
+        .iter()
+        .find(|(action, _, _)| uses.starts_with(&format!("{}@", action)))?;
+    let requested = step.with.get(*input)?;
+    let default = image
+        .tools
+        .iter()
+        .find(|(name, _)| name == tool)
+        .and_then(|(_, version)| *version)?;
+
+    // The action also configures dependency caching; removing it would lose that.
+    let caches = match step.with.get("cache").map(String::as_str) {
+        Some("false") | Some("") => false,
+        Some(_) => true,
+        None => *tool == "go", // setup-go caches by default
+    };
+    if caches || !version_satisfied_by(requested, default) {
+        return None;
+    }
+
+    Some(Finding {
+        severity: Severity::Low,
+        category: FindingCategory::RedundantSteps,
+        title: format!(
+            "'{}' installs {} {} already on {}",
+            job.id, tool, requested, job.runs_on
+        ),
+        description: format!(
+            "Job '{}' runs {} for {} {}, but the {} image already provides {} {}. \
+            The setup step downloads and configures a toolchain that is already on PATH.",
+            job.id, uses, tool, requested, job.runs_on, tool, default,
+        ),
+        affected_jobs: vec![job.id.clone()],
+        recommendation: format!(
+            "Remove the `{}` step, or keep it only if you need a version other than the \
+            preinstalled {} {}.",
+            action, tool, default,
+        ),
+        fix_command: None,
+        estimated_savings_secs: Some(10.0),
+        confidence: 0.6,
+        auto_fixable: false,
+        documentation_url: None,
+        blame: None,
+    })
+}
+
+fn redundant_apt_install(job: &JobNode, step: &StepInfo, image: &RunnerImage) -> Option<Finding> {
+    if !image.tools.iter().any(|(name, _)| *name == "apt") {
+        return None;
+    }
+    let run = step.run.as_deref()?;
+    let install_re = Regex::new(r"\bapt(?:-get)?\s+install\s+([^\n;&|]+)").unwrap();
+
+    let redundant: Vec<&str> = install_re
+        .captures_iter(run)
+        .flat_map(|caps| {
+            caps.get(1)
+                .map(|m| m.as_str())
+                .unwrap_or_default()
+                .split_whitespace()
+        })
+        .filter(|pkg| !pkg.starts_with('-'))
+        .filter(|pkg| APT_PREINSTALLED.contains(pkg))
+        .collect();
+    if redundant.is_empty() {
+        return None;
+    }
+
+    Some(Finding {
+        severity: Severity::Low,
+        category: FindingCategory::RedundantSteps,
+        title: format!("'{}' apt-installs preinstalled packages", job.id),
+        description: format!(
+            "Job '{}' installs [{}] with apt, but {} already ships {}.",
+            job.id,
+            redundant.join(", "),
+            job.runs_on,
+            if redundant.len() == 1 { "it" } else { "them" },
+        ),
+        affected_jobs: vec![job.id.clone()],
+        recommendation: format!(
+            "Drop [{}] from the install list; if nothing else is installed, remove the \
+            `apt-get update`/`install` step entirely.",
+            redundant.join(", ")
+        ),
+        fix_command: None,
+        estimated_savings_secs: Some(15.0),
+        confidence: 0.7,
+        auto_fixable: false,
+        documentation_url: None,
+        blame: None,
+    })
+}
+
+/// Whether a requested version spec (`20`, `20.x`, `3.12`) is met by the image default.
+/// Fully pinned versions more specific than the default are treated as deliberate.
+fn version_satisfied_by(requested: &str, default: &str) -> bool {
+    let requested = requested
+        .trim()
+        .trim_start_matches('v')
+        .trim_end_matches(".x")
+        .trim_end_matches(".*");
+    if requested.is_empty() || !requested.starts_with(|c: char| c.is_ascii_digit()) {
+        return false;
+    }
+    let default_parts: Vec<&str> = default.split('.').collect();
+    let requested_parts: Vec<&str> = requested.split('.').collect();
+    requested_parts.len() <= default_parts.len()
+        && requested_parts
+            .iter()
+            .zip(&default_parts)
+            .all(|(r, d)| r == d)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::parser::github::GitHubActionsParser;
+    use crate::parser::gitlab::GitLabCIParser;
+
+    #[test]
+    fn test_missing_concurrency_quantifies_superseded_runs() {
+        let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm ci
+      - run: npm run build
+  test:
+    runs-on: ubuntu-latest
+    strategy:
+      matrix:
+        node: [18, 20]
+    steps:
+      - run: npm test
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let findings = detect_missing_concurrency(&dag);
+        assert_eq!(findings.len(), 1);
+
+        let finding = &findings[0];
+        assert_eq!(finding.category, FindingCategory::ConcurrencyControl);
+        let build = dag.get_job("build").unwrap().estimated_duration_secs;
+        let test = dag.get_job("test").unwrap().estimated_duration_secs;
+        let per_run = (build + 2.0 * test) * 0.2 * 0.5;
+        assert!((finding.estimated_savings_secs.unwrap() - per_run).abs() < 1e-9);
+        assert!(finding.description.contains(&format!(
+            "about {:.0} runner-minutes per month",
+            per_run * 500.0 / 60.0
+        )));
+
+        let cancelling = yaml.replace(
+            "on: push\n",
+            "on: push\nconcurrency:\n  group: ci-${{ github.ref }}\n  cancel-in-progress: true\n",
+        );
+        let dag = GitHubActionsParser::parse(&cancelling, "ci.yml".to_string()).unwrap();
+        assert!(detect_missing_concurrency(&dag).is_empty());
+
+        let queued_only = yaml.replace("on: push\n", "on: push\nconcurrency: ci\n");
+        let dag = GitHubActionsParser::parse(&queued_only, "ci.yml".to_string()).unwrap();
+        assert_eq!(
+            detect_missing_concurrency(&dag)[0].title,
+            "Concurrency group does not cancel superseded runs"
+        );
+    }
+
+    #[test]
+    fn test_dead_artifact_flagged() {
+        let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm run build
+      - uses: actions/upload-artifact@v4
+        with:
+          name: dist
+          path: dist/
+      - uses: actions/upload-artifact@v4
+        with:
+          name: coverage
+          path: coverage/
+  deploy:
+    needs: build
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/download-artifact@v4
+        with:
+          name: dist
+      - run: ./deploy.sh
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let findings = detect_dead_artifacts(&dag);
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].title.contains("'coverage'"));
+        assert_eq!(findings[0].severity, Severity::Low);
+        assert_eq!(findings[0].affected_jobs, vec!["build"]);
+        assert_eq!(findings[0].estimated_savings_secs, Some(15.0));
+    }
+
+    #[test]
+    fn test_consumed_artifacts_not_flagged() {
+        let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    strategy:
+      matrix:
+        os: [linux, macos]
+    steps:
+      - uses: actions/upload-artifact@v4
+        with:
+          name: dist-${{ matrix.os }}
+          path: dist/
+  release:
+    needs: build
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/download-artifact@v4
+        with:
+          name: dist-linux
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        assert!(detect_dead_artifacts(&dag).is_empty());
+
+        assert!(artifact_names_match("dist", "*"));
+        assert!(!artifact_names_match("dist", "docs"));
+    }
 
     #[test]
     fn test_detect_missing_path_filter() {
@@ -263,4 +1080,290 @@ jobs:
             .iter()
             .any(|f| matches!(f.category, FindingCategory::MissingPathFilter)));
     }
+
+    #[test]
+    fn test_catch_all_path_filter_flagged_with_scoped_recommendation() {
+        let yaml = r#"
+name: CI
+on:
+  push:
+    paths: ['**']
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - run: cd packages/app && npm ci && npm run build
+  test:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - run: npm --prefix packages/app test
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let findings = detect_waste(&dag);
+        let finding = findings
+            .iter()
+            .find(|f| matches!(f.category, FindingCategory::MissingPathFilter))
+            .expect("catch-all filter should be flagged");
+        assert_eq!(finding.title, "Path filter matches every file");
+        assert!(finding.recommendation.contains("- 'packages/app/**'"));
+    }
+
+    #[test]
+    fn test_path_filter_recommendation_uses_working_directory() {
+        let yaml = r#"
+name: CI
+on:
+  push:
+    paths: ['**']
+defaults:
+  run:
+    working-directory: services/api
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    steps:
+      - run: cargo test
+      - run: cd worker && cargo test
+  lint:
+    runs-on: ubuntu-latest
+    steps:
+      - run: cargo clippy
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let finding = detect_waste(&dag)
+            .into_iter()
+            .find(|f| matches!(f.category, FindingCategory::MissingPathFilter))
+            .expect("catch-all filter should be flagged");
+        assert!(finding.recommendation.contains("- 'services/api/**'"));
+        assert!(finding
+            .recommendation
+            .contains("- 'services/api/worker/**'"));
+    }
+
+    #[test]
+    fn test_duplicate_checkout_flagged() {
+        let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - run: npm ci
+      - uses: actions/checkout@v4
+      - run: npm run build
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let findings = detect_duplicate_steps(&dag);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(
+            findings[0].title,
+            "'build' runs `actions/checkout@v4` 2 times"
+        );
+        assert_eq!(findings[0].category, FindingCategory::RedundantSteps);
+        let checkout_secs = dag.get_job("build").unwrap().steps[0]
+            .estimated_duration_secs
+            .unwrap();
+        assert_eq!(findings[0].estimated_savings_secs, Some(checkout_secs));
+    }
+
+    #[test]
+    fn test_checkouts_of_different_repos_not_flagged() {
+        let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - uses: actions/checkout@v4
+        with:
+          repository: acme/shared-config
+          path: shared-config
+      - run: make
+      - run: make
+        working-directory: shared-config
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        assert!(detect_duplicate_steps(&dag).is_empty());
+    }
+
+    #[test]
+    fn test_same_command_with_different_env_not_flagged() {
+        let yaml = r#"
+on: push
+jobs:
+  deploy:
+    runs-on: ubuntu-latest
+    steps:
+      - run: ./deploy.sh
+        env:
+          TARGET: staging
+      - run: ./deploy.sh
+        env:
+          TARGET: production
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        assert!(detect_duplicate_steps(&dag).is_empty());
+    }
+
+    #[test]
+    fn test_gitlab_install_in_two_directories_not_flagged() {
+        let yaml = r#"
+install:
+  script:
+    - cd frontend
+    - npm ci
+    - cd ../backend
+    - npm ci
+"#;
+        let dag = GitLabCIParser::parse(yaml, ".gitlab-ci.yml".to_string()).unwrap();
+        assert!(detect_duplicate_steps(&dag).is_empty());
+
+        let repeated = GitLabCIParser::parse(
+            "install:\n  script:\n    - cd frontend\n    - npm ci\n    - npm ci\n",
+            ".gitlab-ci.yml".to_string(),
+        )
+        .unwrap();
+        assert_eq!(detect_duplicate_steps(&repeated).len(), 1);
+    }
+
+    #[test]
+    fn test_gitlab_clone_depth_only_flags_explicit_deep_clones() {
+        let default_depth =
+            GitLabCIParser::parse("build:\n  script: [make]\n", ".gitlab-ci.yml".to_string())
+                .unwrap();
+        assert!(detect_gitlab_clone_depth(&default_depth).is_none());
+
+        let yaml = r#"
+variables:
+  GIT_DEPTH: "50"
+build:
+  script: [make]
+release:
+  variables:
+    GIT_DEPTH: "0"
+  script: [./release.sh]
+lint:
+  variables:
+    GIT_DEPTH: "5"
+  script: [make lint]
+"#;
+        let dag = GitLabCIParser::parse(yaml, ".gitlab-ci.yml".to_string()).unwrap();
+        let finding = detect_gitlab_clone_depth(&dag).unwrap();
+        assert_eq!(finding.severity, Severity::Medium);
+        assert_eq!(finding.affected_jobs, vec!["release", "build"]);
+    }
+
+    #[test]
+    fn test_gitlab_rules_changes_count_as_path_filter() {
+        let yaml = r#"
+build:
+  script: [make]
+  rules:
+    - changes: [src/**]
+test:
+  script: [make test]
+"#;
+        let dag = GitLabCIParser::parse(yaml, ".gitlab-ci.yml".to_string()).unwrap();
+        // `build` is scoped, `test` still runs on every change
+        let findings = detect_missing_path_filters(&dag);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].affected_jobs, vec!["test"]);
+
+        let scoped = GitLabCIParser::parse(
+            "build:\n  script: [make]\n  rules:\n    - changes: [src/**]\n\
+             test:\n  script: [make test]\n  rules:\n    - changes: [src/**, tests/**]\n",
+            ".gitlab-ci.yml".to_string(),
+        )
+        .unwrap();
+        assert!(detect_missing_path_filters(&scoped).is_empty());
+
+        let unscoped = GitLabCIParser::parse(
+            "build:\n  script: [make]\ntest:\n  script: [make test]\n",
+            ".gitlab-ci.yml".to_string(),
+        )
+        .unwrap();
+        assert_eq!(detect_missing_path_filters(&unscoped).len(), 1);
+    }
+
+    #[test]
+    fn test_scoped_path_filter_not_flagged() {
+        let yaml = r#"
+name: CI
+on:
+  push:
+    paths: ['packages/app/**']
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm run build
+  test:
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm test
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let findings = detect_waste(&dag);
+        assert!(!findings
+            .iter()
+            .any(|f| matches!(f.category, FindingCategory::MissingPathFilter)));
+    }
+
+    #[test]
+    fn test_redundant_toolchain_installs() {
+        let yaml = r#"
+name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/setup-node@v4
+        with:
+          node-version: 20
+      - run: sudo apt-get update && sudo apt-get install -y git libpq-dev
+  cached:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/setup-node@v4
+        with:
+          node-version: 20.x
+          cache: npm
+  pinned:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/setup-node@v4
+        with:
+          node-version: 22
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let findings = detect_redundant_toolchain_installs(&dag);
+
+        assert_eq!(findings.len(), 2);
+        assert!(findings
+            .iter()
+            .all(|f| f.affected_jobs == vec!["build".to_string()]));
+        assert!(findings
+            .iter()
+            .any(|f| f.title.contains("installs node 20")));
+        let apt = findings
+            .iter()
+            .find(|f| f.title.contains("apt-installs"))
+            .unwrap();
+        assert!(apt.description.contains("[git]"));
+    }
+
+    #[test]
+    fn test_version_satisfied_by() {
+        assert!(version_satisfied_by("20", "20"));
+        assert!(version_satisfied_by("20.x", "20"));
+        assert!(version_satisfied_by("3.12", "3.12"));
+        assert!(!version_satisfied_by("20.11.1", "20"));
+        assert!(!version_satisfied_by("lts/*", "20"));
+        assert!(!version_satisfied_by("3.11", "3.12"));
+    }
 }
@@ -0,0 +1,246 @@
+use crate::analyzer::report::{Finding, Severity};
+use crate::linter::{LintReport, LintSeverity};
+
+/// GitHub Actions annotation level for a workflow command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnotationLevel {
+    Error,
+    Warning,
+    Notice,
+}
+
+impl AnnotationLevel {
+    pub fn command(&self) -> &str {
+        match self {
+            AnnotationLevel::Error => "error",
+            AnnotationLevel::Warning => "warning",
+            AnnotationLevel::Notice => "notice",
+        }
+    }
+
+    pub fn from_severity(severity: Severity) -> Self {
+        match severity {
+            Severity::Critical | Severity::High => AnnotationLevel::Error,
+            Severity::Medium => AnnotationLevel::Warning,
+            Severity::Low | Severity::Info => AnnotationLevel::Notice,
+        }
+    }
+
+    pub fn from_lint_severity(severity: LintSeverity) -> Self {
+        match severity {
+            LintSeverity::Error => AnnotationLevel::Error,
+            LintSeverity::Warning => AnnotationLevel::Warning,
+            LintSeverity::Info => AnnotationLevel::Notice,
+        }
+    }
+}
+
+/// Format a single GitHub Actions workflow command, e.g.
+/// `::warning file=ci.yml,line=12,title=Missing cache::Job 'build' ...`.
+pub fn format_workflow_command(
+    level: AnnotationLevel,
+    file: &str,
+    line: Option<usize>,
+    title: &str,
+    message: &str,
+) -> String {
+    let mut properties = vec![format!("file={}", escape_property(file))];
+    if let Some(line) = line {
+        properties.push(format!("line={}", line));
+    }
+    properties.push(format!("title={}", escape_property(title)));
+
+    format!(
+        "::{} {}::{}",
+        level.command(),
+        properties.join(","),
+        escape_data(message)
+    )
+}
+
+/// Emit one workflow command per finding, anchored to the first affected job's line.
+pub fn annotate_findings(source_file: &str, findings: &[Finding], content: &str) -> Vec<String> {
+    findings
+        .iter()
+        .map(|finding| {
+            let line = finding
+                .affected_jobs
+                .first()
+                .and_then(|job| locate_job_line(content, job));
+            format_workflow_command(
+                AnnotationLevel::from_severity(finding.severity),
+                source_file,
+                line,
+                &finding.title,
+                &format!("{}\n\n{}", finding.description, finding.recommendation),
+            )
+        })
+        .collect()
+}
+
+/// Emit one workflow command per lint finding.
+pub fn annotate_lint(report: &LintReport, content: &str) -> Vec<String> {
+    report
+        .findings
+        .iter()
+        .map(|finding| {
+            let line = finding
+                .location
+                .as_deref()
+                .and_then(|loc| locate_lint_location(content, loc));
+            let message = match &finding.suggestion {
+                Some(suggestion) => format!("{}\n\n{}", finding.message, suggestion),
+                None => finding.message.clone(),
+            };
+            format_workflow_command(
+                AnnotationLevel::from_lint_severity(finding.severity),
+                &report.source_file,
+                line,
+                &finding.rule_id,
+                &message,
+            )
+        })
+        .collect()
+}
+
+/// Find the 1-based line on which a job is declared.
+///
+/// Prefers a key nested under a top-level `jobs:` block (GitHub Actions, CircleCI)
+/// and falls back to the first matching key anywhere (GitLab CI top-level jobs).
+pub fn locate_job_line(content: &str, job_id: &str) -> Option<usize> {
+    let mut in_jobs = false;
+    let mut fallback = None;
+
+    for (idx, line) in content.lines().enumerate() {
+        let indent = line.len() - line.trim_start().len();
+        let trimmed = line.trim_start();
+
+        if indent == 0 && !trimmed.is_empty() && !trimmed.starts_with('#') {
+            in_jobs = trimmed.starts_with("jobs:");
+        }
+
+        if is_key(trimmed, job_id) {
+            if in_jobs && indent > 0 {
+                return Some(idx + 1);
+            }
+            fallback.get_or_insert(idx + 1);
+        }
+    }
+
+    fallback
+}
+
+/// Resolve a lint `location` (`line 12`, `jobs.build.steps`, `deploy.stage`) to a line.
+fn locate_lint_location(content: &str, location: &str) -> Option<usize> {
+    if let Some(line) = location.strip_prefix("line ") {
+        return line.trim().parse().ok();
+    }
+    let mut parts = location.split('.');
+    match parts.next()? {
+        "jobs" => locate_job_line(content, parts.next()?),
+        "top-level" => None,
+        key => locate_job_line(content, key),
+    }
+}
+
+fn is_key(trimmed: &str, key: &str) -> bool {
+    [
+        format!("{}:", key),
+        format!("\"{}\":", key),
+        format!("'{}':", key),
+    ]
+    .iter()
+    .any(|candidate| {
+        trimmed
+            .strip_prefix(candidate.as_str())
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with([' ', '#']))
+    })
+}
+
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::report::FindingCategory;
+
+    const WORKFLOW: &str = "name: CI\non: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n    steps:\n      - run: npm ci\n  test:\n    needs: build\n    runs-on: ubuntu-latest\n";
+
+    fn make_finding(severity: Severity, job: &str) -> Finding {
+        Finding {
+            severity,
+            category: FindingCategory::MissingCache,
+            title: "No dependency caching for npm/yarn/pnpm".to_string(),
+            description: "Job 'build' runs 'npm ci' without caching, 100% cold".to_string(),
+            affected_jobs: vec![job.to_string()],
+            recommendation: "Add actions/cache".to_string(),
+            fix_command: None,
+            estimated_savings_secs: Some(150.0),
+            confidence: 0.95,
+            auto_fixable: true,
+            documentation_url: None,
+            blame: None,
+        }
+    }
+
+    #[test]
+    fn test_workflow_command_format() {
+        let lines = annotate_findings(
+            ".github/workflows/ci.yml",
+            &[make_finding(Severity::Medium, "build")],
+            WORKFLOW,
+        );
+        assert_eq!(
+            lines[0],
+            "::warning file=.github/workflows/ci.yml,line=4,\
+             title=No dependency caching for npm/yarn/pnpm::\
+             Job 'build' runs 'npm ci' without caching, 100%25 cold%0A%0AAdd actions/cache"
+        );
+    }
+
+    #[test]
+    fn test_severity_levels() {
+        let lines = annotate_findings(
+            "ci.yml",
+            &[
+                make_finding(Severity::Critical, "test"),
+                make_finding(Severity::Info, "missing"),
+            ],
+            WORKFLOW,
+        );
+        assert!(lines[0].starts_with("::error file=ci.yml,line=8,"));
+        assert!(lines[1].starts_with("::notice file=ci.yml,title="));
+    }
+
+    #[test]
+    fn test_property_escaping() {
+        let line = format_workflow_command(
+            AnnotationLevel::Error,
+            "a,b.yml",
+            None,
+            "Title: with, punctuation",
+            "msg",
+        );
+        assert_eq!(
+            line,
+            "::error file=a%2Cb.yml,title=Title%3A with%2C punctuation::msg"
+        );
+    }
+
+    #[test]
+    fn test_locate_gitlab_job_line() {
+        let content = "stages:\n  - build\n\nbuild-job:\n  stage: build\n  script:\n    - make\n";
+        assert_eq!(locate_job_line(content, "build-job"), Some(4));
+        assert_eq!(locate_lint_location(content, "build-job.stage"), Some(4));
+        assert_eq!(locate_lint_location(content, "line 6"), Some(6));
+    }
+}
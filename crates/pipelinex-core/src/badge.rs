@@ -1,4 +1,5 @@
 use crate::analyzer::report::{AnalysisReport, Severity};
+use crate::cost::estimate_costs;
 use serde::{Deserialize, Serialize};
 
 /// Health score result for badge generation.
@@ -10,10 +11,59 @@ pub struct BadgeInfo {
     pub optimization_pct: f64,
     pub markdown: String,
     pub shields_url: String,
+    /// Monthly savings badge, when savings parameters were given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub savings: Option<SavingsBadge>,
+}
+
+impl BadgeInfo {
+    /// The shields.io endpoint document for this badge.
+    pub fn endpoint(&self) -> ShieldsEndpoint {
+        ShieldsEndpoint {
+            schema_version: 1,
+            label: "PipelineX".to_string(),
+            message: format!("{} | {}/100", self.grade, self.score),
+            color: self.color.clone(),
+        }
+    }
+}
+
+/// JSON read by a shields.io `endpoint` badge, for hosting the badge data yourself.
+///
+/// See <https://shields.io/badges/endpoint-badge>.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShieldsEndpoint {
+    pub schema_version: u8,
+    pub label: String,
+    pub message: String,
+    pub color: String,
+}
+
+/// Inputs for the monthly savings badge, as for `pipelinex cost`.
+#[derive(Debug, Clone)]
+pub struct SavingsParams {
+    pub runs_per_month: u32,
+    pub hourly_rate: f64,
+    pub team_size: u32,
+    /// Runner label used to pick the per-minute compute rate
+    pub runner_type: String,
+}
+
+/// A "saves $X/mo" badge.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavingsBadge {
+    /// Recoverable compute cost plus developer waiting time, in USD per month
+    pub monthly_savings_usd: f64,
+    pub markdown: String,
+    pub shields_url: String,
 }
 
 /// Calculate a pipeline health score and generate badge info.
-pub fn generate_badge(report: &AnalysisReport) -> BadgeInfo {
+///
+/// With `savings`, also builds a badge for the recoverable monthly cost, computed
+/// the same way as the "Recoverable Savings" section of `pipelinex cost`.
+pub fn generate_badge(report: &AnalysisReport, savings: Option<&SavingsParams>) -> BadgeInfo {
     let score = calculate_score(report);
     let grade = score_to_grade(score);
     let color = grade_to_color(&grade);
@@ -47,6 +97,41 @@ pub fn generate_badge(report: &AnalysisReport) -> BadgeInfo {
         optimization_pct,
         markdown,
         shields_url,
+        savings: savings.map(|params| savings_badge(report, params)),
+    }
+}
+
+fn savings_badge(report: &AnalysisReport, params: &SavingsParams) -> SavingsBadge {
+    let estimate = estimate_costs(
+        report.total_estimated_duration_secs,
+        report.optimized_duration_secs,
+        params.runs_per_month,
+        &params.runner_type,
+        params.hourly_rate,
+        params.team_size.max(1),
+    );
+    let monthly_savings_usd =
+        (estimate.monthly_compute_cost + estimate.monthly_opportunity_cost) * estimate.waste_ratio;
+
+    let shields_url = format!(
+        "https://img.shields.io/badge/{}-{}-{}",
+        url_encode("PipelineX"),
+        url_encode(&format!("saves ${:.0}/mo", monthly_savings_usd)),
+        if monthly_savings_usd >= 1.0 {
+            "brightgreen"
+        } else {
+            "lightgrey"
+        }
+    );
+    let markdown = format!(
+        "[![PipelineX savings]({})](https://github.com/mackeh/PipelineX)",
+        shields_url
+    );
+
+    SavingsBadge {
+        monthly_savings_usd,
+        markdown,
+        shields_url,
     }
 }
 
@@ -90,12 +175,19 @@ fn grade_to_color(grade: &str) -> String {
     }
 }
 
+/// Encode text for a shields.io static badge path segment.
+///
+/// `%` goes first so later escapes are not double-encoded; `-` and `_` are
+/// doubled because shields uses them as separators.
 fn url_encode(s: &str) -> String {
-    s.replace(' ', "%20")
+    s.replace('%', "%25")
+        .replace('-', "--")
+        .replace('_', "__")
+        .replace(' ', "%20")
         .replace(':', "%3A")
         .replace('|', "%7C")
         .replace('/', "%2F")
-        .replace('%', "%25")
+        .replace('$', "%24")
 }
 
 #[cfg(test)]
@@ -117,13 +209,15 @@ mod tests {
             optimized_duration_secs: 150.0,
             findings,
             health_score: None,
+            job_slack: Vec::new(),
+            metadata: Default::default(),
         }
     }
 
     #[test]
     fn test_perfect_score() {
         let report = make_report(vec![]);
-        let badge = generate_badge(&report);
+        let badge = generate_badge(&report, None);
         assert_eq!(badge.score, 100);
         assert_eq!(badge.grade, "A+");
         assert_eq!(badge.color, "brightgreen");
@@ -142,8 +236,10 @@ mod tests {
             estimated_savings_secs: None,
             confidence: 0.9,
             auto_fixable: false,
+            documentation_url: None,
+            blame: None,
         }]);
-        let badge = generate_badge(&report);
+        let badge = generate_badge(&report, None);
         assert_eq!(badge.score, 75);
         assert_eq!(badge.grade, "B");
     }
@@ -151,15 +247,70 @@ mod tests {
     #[test]
     fn test_badge_markdown() {
         let report = make_report(vec![]);
-        let badge = generate_badge(&report);
+        let badge = generate_badge(&report, None);
         assert!(badge.markdown.contains("shields.io"));
         assert!(badge.markdown.contains("PipelineX"));
     }
 
+    #[test]
+    fn test_endpoint_json() {
+        let report = make_report(vec![]);
+        let endpoint = generate_badge(&report, None).endpoint();
+        let json = serde_json::to_value(&endpoint).unwrap();
+        assert_eq!(json["schemaVersion"], 1);
+        assert_eq!(json["label"], "PipelineX");
+        assert_eq!(json["message"], "A+ | 100/100");
+        assert_eq!(json["color"], "brightgreen");
+
+        let report = make_report(vec![Finding {
+            severity: Severity::Critical,
+            category: FindingCategory::MissingCache,
+            title: "test".into(),
+            description: "test".into(),
+            affected_jobs: vec![],
+            recommendation: "test".into(),
+            fix_command: None,
+            estimated_savings_secs: None,
+            confidence: 0.9,
+            auto_fixable: false,
+            documentation_url: None,
+            blame: None,
+        }]);
+        let endpoint = generate_badge(&report, None).endpoint();
+        assert_eq!(endpoint.message, "B | 75/100");
+        assert_eq!(endpoint.color, "yellowgreen");
+    }
+
     #[test]
     fn test_optimization_pct() {
         let report = make_report(vec![]);
-        let badge = generate_badge(&report);
+        let badge = generate_badge(&report, None);
         assert!((badge.optimization_pct - 50.0).abs() < 0.1);
     }
+
+    #[test]
+    fn test_savings_badge_encodes_dollar_figure() {
+        let report = make_report(vec![]);
+        let params = SavingsParams {
+            runs_per_month: 1000,
+            hourly_rate: 100.0,
+            team_size: 10,
+            runner_type: "ubuntu-latest".to_string(),
+        };
+        let badge = generate_badge(&report, Some(&params));
+        let savings = badge.savings.unwrap();
+
+        // 300s -> 150s: half of $40 compute + 83.3h x $100 waiting time
+        assert!((savings.monthly_savings_usd - 4186.67).abs() < 0.01);
+        assert!(savings
+            .shields_url
+            .ends_with("/PipelineX-saves%20%244187%2Fmo-brightgreen"));
+        assert!(savings.markdown.contains(&savings.shields_url));
+
+        // The health badge itself is unchanged and not double-encoded
+        assert!(badge
+            .shields_url
+            .contains("PipelineX%3A%20A+%20%7C%20100%2F100"));
+        assert!(generate_badge(&report, None).savings.is_none());
+    }
 }
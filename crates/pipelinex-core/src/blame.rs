@@ -0,0 +1,234 @@
+//! Attributing findings to the commits that last changed the affected jobs
+//! (`analyze --git-blame`).
+
+use crate::analyzer::report::Finding;
+use crate::annotations::locate_job_line;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+/// How long `git blame` may take for one file.
+const BLAME_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The commit that last touched a region of a workflow file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlameInfo {
+    pub author: String,
+    pub author_email: String,
+    pub commit: String,
+    /// Author time, seconds since the Unix epoch.
+    pub timestamp: i64,
+    pub summary: String,
+}
+
+impl BlameInfo {
+    pub fn short_commit(&self) -> &str {
+        &self.commit[..self.commit.len().min(7)]
+    }
+}
+
+/// `git blame` output for a file: the commit of each line, in order.
+/// Uncommitted lines are `None`.
+#[derive(Debug, Clone, Default)]
+pub struct FileBlame {
+    lines: Vec<Option<BlameInfo>>,
+}
+
+impl FileBlame {
+    /// Blame `path`, or `None` if it is not tracked in a git repository or
+    /// git is unavailable.
+    pub fn load(path: &Path) -> Option<Self> {
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let output = crate::process::output_with_timeout(
+            Command::new("git")
+                .args(["blame", "--line-porcelain", "--"])
+                .arg(path.file_name()?)
+                .current_dir(dir),
+            BLAME_TIMEOUT,
+        )
+        .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(Self::parse(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    /// Parse `git blame --line-porcelain` output.
+    pub fn parse(porcelain: &str) -> Self {
+        let mut lines = Vec::new();
+        let mut current: Option<BlameInfo> = None;
+
+        for line in porcelain.lines() {
+            if line.starts_with('\t') {
+                // The line's content ends each entry
+                let info = current.take();
+                let committed = info.filter(|i| i.commit.bytes().any(|b| b != b'0'));
+                lines.push(committed);
+                continue;
+            }
+            let Some(info) = current.as_mut() else {
+                let commit = line.split_whitespace().next().unwrap_or_default();
+                current = Some(BlameInfo {
+                    author: String::new(),
+                    author_email: String::new(),
+                    commit: commit.to_string(),
+                    timestamp: 0,
+                    summary: String::new(),
+                });
+                continue;
+            };
+            if let Some((key, value)) = line.split_once(' ') {
+                match key {
+                    "author" => info.author = value.to_string(),
+                    "author-mail" => info.author_email = value.trim_matches(['<', '>']).to_string(),
+                    "author-time" => info.timestamp = value.parse().unwrap_or(0),
+                    "summary" => info.summary = value.to_string(),
+                    _ => {}
+                }
+            }
+        }
+
+        Self { lines }
+    }
+
+    /// Most recent commit among 1-based lines `start..=end`.
+    pub fn latest_in(&self, start: usize, end: usize) -> Option<&BlameInfo> {
+        self.lines
+            .get(start.saturating_sub(1)..end.min(self.lines.len()))?
+            .iter()
+            .flatten()
+            .max_by_key(|info| info.timestamp)
+    }
+}
+
+/// 1-based, inclusive line range of a job's block in `content`: from its key
+/// to the last line before the next key at the same or a shallower indent.
+pub fn job_line_range(content: &str, job_id: &str) -> Option<(usize, usize)> {
+    let start = locate_job_line(content, job_id)?;
+    let lines: Vec<&str> = content.lines().collect();
+    let indent_of = |line: &str| line.len() - line.trim_start().len();
+    let job_indent = indent_of(lines[start - 1]);
+
+    let mut end = start;
+    for (idx, line) in lines.iter().enumerate().skip(start) {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if indent_of(line) <= job_indent {
+            break;
+        }
+        end = idx + 1;
+    }
+    Some((start, end))
+}
+
+/// Set each finding's [`blame`](Finding::blame) to the latest commit touching
+/// any of its affected jobs. Findings without affected jobs are left alone.
+pub fn attribute_findings(blame: &FileBlame, content: &str, findings: &mut [Finding]) {
+    for finding in findings {
+        finding.blame = finding
+            .affected_jobs
+            .iter()
+            .filter_map(|job| job_line_range(content, job))
+            .filter_map(|(start, end)| blame.latest_in(start, end))
+            .max_by_key(|info| info.timestamp)
+            .cloned();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::report::FindingCategory;
+
+    const WORKFLOW: &str = "on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm ci
+
+  test:
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm test
+";
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .env("GIT_CONFIG_GLOBAL", "/dev/null")
+            .env("GIT_CONFIG_SYSTEM", "/dev/null")
+            .output()
+            .unwrap();
+        assert!(status.status.success(), "git {:?}: {:?}", args, status);
+    }
+
+    #[test]
+    fn test_job_line_range() {
+        assert_eq!(job_line_range(WORKFLOW, "build"), Some((3, 6)));
+        assert_eq!(job_line_range(WORKFLOW, "test"), Some((8, 11)));
+        assert_eq!(job_line_range(WORKFLOW, "deploy"), None);
+    }
+
+    #[test]
+    fn test_findings_are_attributed_to_the_author_of_the_job() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        git(repo, &["init", "-q"]);
+        let identity = |name: &str| {
+            [
+                "-c".to_string(),
+                format!("user.name={}", name),
+                "-c".to_string(),
+                format!("user.email={}@example.com", name.to_lowercase()),
+            ]
+        };
+
+        let path = repo.join("ci.yml");
+        let (first, _) = WORKFLOW.split_at(WORKFLOW.find("\n  test:").unwrap() + 1);
+        std::fs::write(&path, first).unwrap();
+        git(repo, &["add", "ci.yml"]);
+        let mut commit: Vec<String> = identity("Alice").to_vec();
+        commit.extend(["commit", "-q", "-m", "Add build job"].map(String::from));
+        git(repo, &commit.iter().map(String::as_str).collect::<Vec<_>>());
+
+        std::fs::write(&path, WORKFLOW).unwrap();
+        git(repo, &["add", "ci.yml"]);
+        let mut commit: Vec<String> = identity("Bob").to_vec();
+        commit.extend(["commit", "-q", "-m", "Add test job"].map(String::from));
+        git(repo, &commit.iter().map(String::as_str).collect::<Vec<_>>());
+
+        let blame = FileBlame::load(&path).expect("file is tracked");
+        let mut findings = vec![
+            Finding::builder(FindingCategory::MissingCache, "build")
+                .affected_jobs(["build"])
+                .build(),
+            Finding::builder(FindingCategory::MissingCache, "test")
+                .affected_jobs(["test"])
+                .build(),
+            Finding::builder(FindingCategory::ConcurrencyControl, "workflow").build(),
+        ];
+        attribute_findings(&blame, WORKFLOW, &mut findings);
+
+        let build = findings[0].blame.as_ref().unwrap();
+        assert_eq!(build.author, "Alice");
+        assert_eq!(build.author_email, "alice@example.com");
+        assert_eq!(build.summary, "Add build job");
+        assert_eq!(build.short_commit().len(), 7);
+        assert_eq!(findings[1].blame.as_ref().unwrap().author, "Bob");
+        assert!(findings[2].blame.is_none());
+
+        // Outside a repository there is nothing to attribute
+        let outside = tempfile::tempdir().unwrap();
+        let untracked = outside.path().join("ci.yml");
+        std::fs::write(&untracked, WORKFLOW).unwrap();
+        assert!(FileBlame::load(&untracked).is_none());
+    }
+}
@@ -1,5 +1,12 @@
+mod schedule;
+
+pub use schedule::{cron_runs_per_month, scheduled_runs_per_month};
+
 use serde::{Deserialize, Serialize};
 
+/// Runs per month assumed where no run frequency is given (the `cost` command's default).
+pub const DEFAULT_RUNS_PER_MONTH: u32 = 500;
+
 /// GitHub Actions pricing per minute by runner type.
 #[derive(Debug, Clone)]
 pub struct RunnerPricing {
@@ -18,6 +25,20 @@ impl Default for RunnerPricing {
     }
 }
 
+impl RunnerPricing {
+    /// Per-minute rate for a runner label (`runs-on` value).
+    pub fn rate_per_min(&self, runner_type: &str) -> f64 {
+        let runner = runner_type.to_lowercase();
+        if runner.contains("macos") {
+            self.macos_per_min
+        } else if runner.contains("windows") {
+            self.windows_per_min
+        } else {
+            self.linux_per_min
+        }
+    }
+}
+
 /// Cost estimate for a pipeline run.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CostEstimate {
@@ -37,13 +58,7 @@ pub fn estimate_costs(
     developer_hourly_rate: f64,
     team_size: u32,
 ) -> CostEstimate {
-    let pricing = RunnerPricing::default();
-
-    let rate_per_min = match runner_type {
-        r if r.contains("macos") => pricing.macos_per_min,
-        r if r.contains("windows") => pricing.windows_per_min,
-        _ => pricing.linux_per_min,
-    };
+    let rate_per_min = RunnerPricing::default().rate_per_min(runner_type);
 
     let duration_min = duration_secs / 60.0;
     let compute_cost_per_run = duration_min * rate_per_min;
@@ -70,3 +85,337 @@ pub fn estimate_costs(
         waste_ratio,
     }
 }
+
+/// Compute cost attributed to one job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobCost {
+    pub job_id: String,
+    pub runner: String,
+    /// Billed instances per run (matrix combinations, or 1).
+    pub instances: usize,
+    pub duration_secs: f64,
+    pub rate_per_min: f64,
+    pub cost_per_run: f64,
+    pub monthly_compute_cost: f64,
+}
+
+/// Per-job compute cost attribution for one pipeline (`cost --breakdown`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CostBreakdown {
+    /// Jobs, most expensive first.
+    pub jobs: Vec<JobCost>,
+    pub cost_per_run: f64,
+    pub monthly_compute_cost: f64,
+}
+
+/// Attribute monthly compute cost to each job: duration × runner rate ×
+/// matrix instances × runs per month.
+///
+/// Unlike [`estimate_costs`], which prices the critical path on one runner,
+/// every job is billed at its own runner's rate, so the total is the pipeline's
+/// billed compute rather than its wall-clock time.
+pub fn cost_breakdown(
+    dag: &crate::parser::dag::PipelineDag,
+    runs_per_month: u32,
+    pricing: &RunnerPricing,
+) -> CostBreakdown {
+    let mut jobs: Vec<JobCost> = dag
+        .graph
+        .node_weights()
+        .map(|job| {
+            let instances = job
+                .matrix
+                .as_ref()
+                .map(|m| m.total_combinations.max(1))
+                .unwrap_or(1);
+            let rate_per_min = pricing.rate_per_min(&job.runs_on);
+            let cost_per_run = job.estimated_duration_secs / 60.0 * rate_per_min * instances as f64;
+            JobCost {
+                job_id: job.id.clone(),
+                runner: job.runs_on.clone(),
+                instances,
+                duration_secs: job.estimated_duration_secs,
+                rate_per_min,
+                cost_per_run,
+                monthly_compute_cost: cost_per_run * runs_per_month as f64,
+            }
+        })
+        .collect();
+    jobs.sort_by(|a, b| {
+        b.monthly_compute_cost
+            .total_cmp(&a.monthly_compute_cost)
+            .then_with(|| a.job_id.cmp(&b.job_id))
+    });
+
+    CostBreakdown {
+        cost_per_run: jobs.iter().map(|j| j.cost_per_run).sum(),
+        monthly_compute_cost: jobs.iter().map(|j| j.monthly_compute_cost).sum(),
+        jobs,
+    }
+}
+
+/// How a CI platform bills for compute.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "billing", rename_all = "snake_case")]
+pub enum BillingModel {
+    /// Billed per runner minute; hosted platforms round each job up to a whole minute.
+    PerMinute {
+        rate_per_min: f64,
+        #[serde(default)]
+        round_up_per_job: bool,
+    },
+    /// Flat hourly cost of a self-hosted runner, billed on actual machine time.
+    FlatHourly { rate_per_hour: f64 },
+}
+
+/// Pricing profile for one CI platform.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderPricingProfile {
+    pub name: String,
+    #[serde(flatten)]
+    pub billing: BillingModel,
+}
+
+impl ProviderPricingProfile {
+    /// GitHub-hosted Linux runners.
+    pub fn github_hosted() -> Self {
+        Self {
+            name: "GitHub hosted".to_string(),
+            billing: BillingModel::PerMinute {
+                rate_per_min: RunnerPricing::default().linux_per_min,
+                round_up_per_job: true,
+            },
+        }
+    }
+
+    /// GitLab.com SaaS Linux runners (compute minutes at overage pricing).
+    pub fn gitlab_saas() -> Self {
+        Self {
+            name: "GitLab SaaS".to_string(),
+            billing: BillingModel::PerMinute {
+                rate_per_min: 0.010,
+                round_up_per_job: false,
+            },
+        }
+    }
+
+    /// Self-hosted runner fleet at a flat hourly machine cost.
+    pub fn self_hosted(rate_per_hour: f64) -> Self {
+        Self {
+            name: "Self-hosted".to_string(),
+            billing: BillingModel::FlatHourly { rate_per_hour },
+        }
+    }
+
+    /// The built-in profiles compared by default.
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self::github_hosted(),
+            Self::gitlab_saas(),
+            Self::self_hosted(0.25),
+        ]
+    }
+
+    /// Cost of one pipeline run given the duration of every billed job instance.
+    pub fn cost_per_run(&self, job_durations_secs: &[f64]) -> f64 {
+        match &self.billing {
+            BillingModel::PerMinute {
+                rate_per_min,
+                round_up_per_job,
+            } => {
+                job_durations_secs
+                    .iter()
+                    .map(|secs| {
+                        let minutes = secs / 60.0;
+                        if *round_up_per_job {
+                            minutes.ceil()
+                        } else {
+                            minutes
+                        }
+                    })
+                    .sum::<f64>()
+                    * rate_per_min
+            }
+            BillingModel::FlatHourly { rate_per_hour } => {
+                job_durations_secs.iter().sum::<f64>() / 3600.0 * rate_per_hour
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct PricingFile {
+    profile: Vec<ProviderPricingProfile>,
+}
+
+/// Parse pricing profiles from TOML (`[[profile]]` tables).
+pub fn parse_pricing_profiles(content: &str) -> anyhow::Result<Vec<ProviderPricingProfile>> {
+    let file: PricingFile =
+        toml::from_str(content).map_err(|e| anyhow::anyhow!("Invalid pricing file: {}", e))?;
+    Ok(file.profile)
+}
+
+/// Load pricing profiles from a TOML file.
+pub fn load_pricing_profiles(
+    path: &std::path::Path,
+) -> anyhow::Result<Vec<ProviderPricingProfile>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read pricing file {}: {}", path.display(), e))?;
+    parse_pricing_profiles(&content)
+}
+
+/// Estimated cost of a pipeline under one provider's pricing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderCostComparison {
+    pub provider: String,
+    pub billable_minutes_per_run: f64,
+    pub cost_per_run: f64,
+    pub monthly_cost: f64,
+}
+
+/// Estimate the same pipeline's compute cost on each pricing profile.
+///
+/// Every job (and every matrix combination) is billed separately; durations come
+/// from the DAG, so only the pricing differs between rows.
+pub fn compare_provider_costs(
+    dag: &crate::parser::dag::PipelineDag,
+    runs_per_month: u32,
+    profiles: &[ProviderPricingProfile],
+) -> Vec<ProviderCostComparison> {
+    let job_durations: Vec<f64> = dag
+        .graph
+        .node_weights()
+        .flat_map(|job| {
+            let instances = job
+                .matrix
+                .as_ref()
+                .map(|m| m.total_combinations.max(1))
+                .unwrap_or(1);
+            std::iter::repeat_n(job.estimated_duration_secs, instances)
+        })
+        .collect();
+    let billable_minutes_per_run = job_durations.iter().sum::<f64>() / 60.0;
+
+    profiles
+        .iter()
+        .map(|profile| {
+            let cost_per_run = profile.cost_per_run(&job_durations);
+            ProviderCostComparison {
+                provider: profile.name.clone(),
+                billable_minutes_per_run,
+                cost_per_run,
+                monthly_cost: cost_per_run * runs_per_month as f64,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::github::GitHubActionsParser;
+
+    const WORKFLOW: &str = r#"
+name: CI
+on: push
+jobs:
+  lint:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - run: npm ci
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - run: cargo build --release
+"#;
+
+    #[test]
+    fn test_self_hosted_differs_from_github_per_minute() {
+        let dag = GitHubActionsParser::parse(WORKFLOW, "ci.yml".to_string()).unwrap();
+        let rows = compare_provider_costs(
+            &dag,
+            1000,
+            &[
+                ProviderPricingProfile::github_hosted(),
+                ProviderPricingProfile::self_hosted(0.25),
+            ],
+        );
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(
+            rows[0].billable_minutes_per_run,
+            rows[1].billable_minutes_per_run
+        );
+        assert!(rows[0].monthly_cost > 0.0);
+        assert_ne!(rows[0].monthly_cost, rows[1].monthly_cost);
+    }
+
+    #[test]
+    fn test_cost_breakdown_attributes_per_job() {
+        let yaml = r#"
+on: push
+jobs:
+  lint:
+    runs-on: ubuntu-latest
+    steps: [{ run: npm run lint }]
+  build:
+    runs-on: ubuntu-latest
+    steps: [{ run: cargo build --release }]
+  ios:
+    runs-on: macos-14
+    steps: [{ run: xcodebuild test }]
+"#;
+        let mut dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        for job in dag.graph.node_weights_mut() {
+            job.estimated_duration_secs = match job.id.as_str() {
+                "lint" => 60.0,
+                "build" => 900.0,
+                _ => 300.0,
+            };
+        }
+
+        let breakdown = cost_breakdown(&dag, 100, &RunnerPricing::default());
+        let order: Vec<_> = breakdown.jobs.iter().map(|j| j.job_id.as_str()).collect();
+        // 5 macOS minutes at 10x the Linux rate outweigh 15 Linux minutes
+        assert_eq!(order, vec!["ios", "build", "lint"]);
+        assert!((breakdown.jobs[0].monthly_compute_cost - 5.0 * 0.08 * 100.0).abs() < 1e-9);
+
+        let sum: f64 = breakdown.jobs.iter().map(|j| j.monthly_compute_cost).sum();
+        assert!((sum - breakdown.monthly_compute_cost).abs() < 1e-9);
+        assert!((breakdown.monthly_compute_cost - (0.4 + 0.12 + 0.008) * 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_per_job_rounding() {
+        let github = ProviderPricingProfile::github_hosted();
+        let gitlab = ProviderPricingProfile::gitlab_saas();
+        // Two 61s jobs bill as 4 minutes on GitHub but ~2.03 minutes on GitLab.
+        assert!((github.cost_per_run(&[61.0, 61.0]) - 4.0 * 0.008).abs() < 1e-9);
+        assert!((gitlab.cost_per_run(&[61.0, 61.0]) - 122.0 / 60.0 * 0.010).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_profiles_deserialize_from_toml() {
+        let profiles = parse_pricing_profiles(
+            r#"
+[[profile]]
+name = "Buildkite agents"
+billing = "flat_hourly"
+rate_per_hour = 0.40
+
+[[profile]]
+name = "CircleCI"
+billing = "per_minute"
+rate_per_min = 0.006
+"#,
+        )
+        .unwrap();
+        assert_eq!(profiles.len(), 2);
+        assert!(matches!(
+            profiles[0].billing,
+            BillingModel::FlatHourly { rate_per_hour } if rate_per_hour == 0.40
+        ));
+    }
+}
@@ -0,0 +1,196 @@
+//! Run frequency of scheduled (cron) workflows.
+
+use crate::parser::dag::PipelineDag;
+
+/// Days per month for each month of a common year.
+const MONTH_DAYS: [u32; 12] = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+
+const MONTH_NAMES: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+const WEEKDAY_NAMES: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// Average number of runs per month of a five-field cron expression.
+///
+/// The expression is evaluated over a common year (365 days) and divided by
+/// twelve, so `0 * * * *` gives 730 and `0 3 * * *` about 30.4. Like cron,
+/// a day matches on day-of-month *or* day-of-week when both are restricted.
+pub fn cron_runs_per_month(expression: &str) -> anyhow::Result<f64> {
+    let fields: Vec<&str> = expression.split_whitespace().collect();
+    let [minute, hour, day, month, weekday] = fields[..] else {
+        anyhow::bail!(
+            "Cron expression '{}' must have 5 fields (minute hour day month weekday)",
+            expression
+        );
+    };
+
+    let minutes = parse_field(minute, 0, 59, &[])?;
+    let hours = parse_field(hour, 0, 23, &[])?;
+    let days = parse_field(day, 1, 31, &[])?;
+    let months = parse_field(month, 1, 12, &MONTH_NAMES)?;
+    // 7 is an alias for Sunday
+    let mut weekdays = parse_field(weekday, 0, 7, &WEEKDAY_NAMES)?;
+    if weekdays[7] {
+        weekdays[0] = true;
+    }
+    // As in cron, a field starting with `*` (even `*/2`) doesn't count as a
+    // restriction when combining day-of-month and day-of-week
+    let day_restricted = !day.starts_with('*');
+    let weekday_restricted = !weekday.starts_with('*');
+
+    let runs_per_day = (count(&minutes) * count(&hours)) as f64;
+    // January 1st of a common year, taken to be a Monday; over a whole year
+    // the starting weekday shifts the count by at most one day per weekday.
+    let mut weekday_of = 1;
+    let mut matching_days = 0u32;
+    for (month_index, &length) in MONTH_DAYS.iter().enumerate() {
+        for day_of_month in 1..=length {
+            if months[month_index + 1] {
+                let by_day = days[day_of_month as usize];
+                let by_weekday = weekdays[weekday_of];
+                let matches = match (day_restricted, weekday_restricted) {
+                    (true, true) => by_day || by_weekday,
+                    (true, false) => by_day,
+                    (false, true) => by_weekday,
+                    (false, false) => true,
+                };
+                if matches {
+                    matching_days += 1;
+                }
+            }
+            weekday_of = (weekday_of + 1) % 7;
+        }
+    }
+
+    Ok(runs_per_day * matching_days as f64 / 12.0)
+}
+
+/// Scheduled runs per month of `dag`, summed over its cron triggers, or
+/// `None` when the pipeline has no schedule.
+pub fn scheduled_runs_per_month(dag: &PipelineDag) -> anyhow::Result<Option<f64>> {
+    let crons: Vec<&str> = dag
+        .triggers
+        .iter()
+        .flat_map(|t| t.schedules.iter().map(String::as_str))
+        .collect();
+    if crons.is_empty() {
+        return Ok(None);
+    }
+    let mut total = 0.0;
+    for cron in crons {
+        total += cron_runs_per_month(cron)?;
+    }
+    Ok(Some(total))
+}
+
+/// Which values in `min..=max` a cron field allows, indexed by value.
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str]) -> anyhow::Result<Vec<bool>> {
+    let mut allowed = vec![false; max as usize + 1];
+    let value = |token: &str| -> anyhow::Result<u32> {
+        let lower = token.to_lowercase();
+        if let Some(i) = names.iter().position(|n| *n == lower) {
+            // Month names start at 1, weekday names at 0
+            return Ok(i as u32 + min.min(1));
+        }
+        let n: u32 = token
+            .parse()
+            .map_err(|_| anyhow::anyhow!("Invalid cron value '{}' in '{}'", token, field))?;
+        if n < min || n > max {
+            anyhow::bail!("Cron value {} in '{}' is outside {}-{}", n, field, min, max);
+        }
+        Ok(n)
+    };
+
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .ok()
+                    .filter(|s| *s > 0)
+                    .ok_or_else(|| anyhow::anyhow!("Invalid cron step in '{}'", field))?;
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((a, b)) => (value(a)?, value(b)?),
+                // `5/15` means from 5 to the end of the range
+                None if step > 1 => (value(range)?, max),
+                None => {
+                    let v = value(range)?;
+                    (v, v)
+                }
+            },
+        };
+        if start > end {
+            anyhow::bail!("Invalid cron range '{}'", range);
+        }
+        for v in (start..=end).step_by(step as usize) {
+            allowed[v as usize] = true;
+        }
+    }
+    Ok(allowed)
+}
+
+fn count(allowed: &[bool]) -> usize {
+    allowed.iter().filter(|a| **a).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::github::GitHubActionsParser;
+
+    fn runs(expression: &str) -> f64 {
+        cron_runs_per_month(expression).unwrap()
+    }
+
+    #[test]
+    fn test_cron_expressions_to_monthly_runs() {
+        assert_eq!(runs("0 * * * *"), 730.0);
+        assert!((runs("0 3 * * *") - 365.0 / 12.0).abs() < 1e-9);
+        assert!((runs("*/15 * * * *") - 4.0 * 730.0).abs() < 1e-9);
+        assert!((runs("30 2 * * 1") - 52.0 / 12.0).abs() < 0.1);
+        assert!((runs("0 9 * * MON-FRI") - 261.0 / 12.0).abs() < 0.1);
+        assert_eq!(runs("0 0 1 * *"), 1.0);
+        assert_eq!(runs("0 0 1 1,7 *"), 2.0 / 12.0);
+        // Sunday as 0 or 7
+        assert_eq!(runs("0 0 * * 0"), runs("0 0 * * 7"));
+        // A stepped `*` day leaves the weekday alone to decide
+        assert_eq!(runs("30 2 */2 * 1"), runs("30 2 * * 1"));
+
+        assert!(cron_runs_per_month("0 * * *").is_err());
+        assert!(cron_runs_per_month("61 * * * *").is_err());
+        assert!(cron_runs_per_month("*/0 * * * *").is_err());
+    }
+
+    #[test]
+    fn test_scheduled_runs_sum_workflow_crons() {
+        let yaml = r#"
+on:
+  schedule:
+    - cron: '0 3 * * *'
+    - cron: '0 * * * *'
+  workflow_dispatch:
+jobs:
+  nightly:
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm test
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "nightly.yml".to_string()).unwrap();
+        let total = scheduled_runs_per_month(&dag).unwrap().unwrap();
+        assert!((total - (730.0 + 365.0 / 12.0)).abs() < 1e-9);
+
+        let push = GitHubActionsParser::parse(
+            "on: push\njobs:\n  a:\n    runs-on: ubuntu-latest\n    steps:\n      - run: make\n",
+            "ci.yml".to_string(),
+        )
+        .unwrap();
+        assert!(scheduled_runs_per_month(&push).unwrap().is_none());
+    }
+}
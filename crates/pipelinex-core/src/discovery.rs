@@ -1,6 +1,16 @@
 use anyhow::{Context, Result};
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
+/// Default depth limit for [`walk_pipeline_files`].
+pub const DEFAULT_WALK_DEPTH: usize = 10;
+
+/// Directories never descended into when walking for pipeline files.
+const WALK_SKIP_DIRS: &[&str] = &[".git", "target", "node_modules", "vendor", "__pycache__"];
+
+/// File extensions treated as candidate pipeline files.
+const WALK_EXTENSIONS: &[&str] = &["yml", "yaml", "json"];
+
 /// Result of discovering CI configs across a monorepo.
 #[derive(Debug, Clone)]
 pub struct DiscoveredPipeline {
@@ -15,6 +25,28 @@ pub struct MonorepoDiscovery {
     pub root: String,
     pub packages: Vec<PackageInfo>,
     pub total_pipeline_files: usize,
+    /// Set when only a sample of the discovered files is listed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample: Option<SampleInfo>,
+}
+
+/// How a [`sample_pipelines`] subset relates to the full discovery.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+pub struct SampleInfo {
+    pub sampled: usize,
+    pub population: usize,
+    pub seed: u64,
+}
+
+impl SampleInfo {
+    /// Multiplier that extrapolates a sample total to the whole population.
+    pub fn scale(&self) -> f64 {
+        if self.sampled == 0 {
+            0.0
+        } else {
+            self.population as f64 / self.sampled as f64
+        }
+    }
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -110,6 +142,69 @@ fn walk_dirs(
     Ok(())
 }
 
+/// Collect candidate pipeline files (`.yml`, `.yaml`, `.json`) under `root`.
+///
+/// Descends at most `max_depth` directory levels below `root`, skips build and
+/// dependency directories, and follows symlinked directories only once so
+/// symlink cycles cannot cause an endless walk. Results are sorted.
+pub fn walk_pipeline_files(root: &Path, max_depth: usize) -> Result<Vec<PathBuf>> {
+    if !root.is_dir() {
+        anyhow::bail!("'{}' is not a directory", root.display());
+    }
+
+    let mut visited = HashSet::new();
+    let mut files = Vec::new();
+    walk_files(root, 0, max_depth, &mut visited, &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+fn walk_files(
+    dir: &Path,
+    depth: usize,
+    max_depth: usize,
+    visited: &mut HashSet<PathBuf>,
+    files: &mut Vec<PathBuf>,
+) -> Result<()> {
+    // Canonical paths identify directories reached through different symlinks.
+    let canonical = dir
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve directory '{}'", dir.display()))?;
+    if !visited.insert(canonical) {
+        return Ok(());
+    }
+
+    let mut entries: Vec<_> = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read directory '{}'", dir.display()))?
+        .filter_map(|e| e.ok())
+        .collect();
+    entries.sort_by_key(|e| e.file_name());
+
+    for entry in entries {
+        let path = entry.path();
+        // `metadata` follows symlinks; dangling links are skipped.
+        let Ok(metadata) = std::fs::metadata(&path) else {
+            continue;
+        };
+
+        if metadata.is_dir() {
+            let name = entry.file_name();
+            if depth < max_depth && !WALK_SKIP_DIRS.contains(&name.to_string_lossy().as_ref()) {
+                walk_files(&path, depth + 1, max_depth, visited, files)?;
+            }
+        } else if metadata.is_file()
+            && path
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| WALK_EXTENSIONS.contains(&e))
+        {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
 fn discover_at_path(dir: &Path, root: &Path, results: &mut Vec<DiscoveredPipeline>) -> Result<()> {
     let package_name = infer_package_name(dir, root);
 
@@ -205,6 +300,40 @@ fn infer_package_name(dir: &Path, root: &Path) -> String {
         .to_string()
 }
 
+/// Pick `size` of `pipelines` pseudo-randomly, keeping their original order.
+///
+/// Each file is ranked by a SHA-256 of the seed and its relative path, so the
+/// same seed always selects the same files regardless of walk order or
+/// platform, and adding a file to the repository only displaces a sampled one
+/// if it outranks it.
+pub fn sample_pipelines(
+    pipelines: &[DiscoveredPipeline],
+    size: usize,
+    seed: u64,
+) -> Vec<DiscoveredPipeline> {
+    use sha2::{Digest, Sha256};
+
+    if size >= pipelines.len() {
+        return pipelines.to_vec();
+    }
+
+    let mut ranked: Vec<(Vec<u8>, usize)> = pipelines
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let mut hasher = Sha256::new();
+            hasher.update(seed.to_le_bytes());
+            hasher.update(p.relative_path.as_bytes());
+            (hasher.finalize().to_vec(), i)
+        })
+        .collect();
+    ranked.sort();
+
+    let mut chosen: Vec<usize> = ranked.into_iter().take(size).map(|(_, i)| i).collect();
+    chosen.sort_unstable();
+    chosen.into_iter().map(|i| pipelines[i].clone()).collect()
+}
+
 /// Aggregate discovery results into a structured report.
 pub fn aggregate_discovery(root: &Path, pipelines: &[DiscoveredPipeline]) -> MonorepoDiscovery {
     let mut packages: std::collections::HashMap<String, Vec<String>> =
@@ -235,6 +364,7 @@ pub fn aggregate_discovery(root: &Path, pipelines: &[DiscoveredPipeline]) -> Mon
         root: root.display().to_string(),
         packages: package_infos,
         total_pipeline_files: pipelines.len(),
+        sample: None,
     }
 }
 
@@ -274,4 +404,69 @@ mod tests {
         let name = infer_package_name(tmp.path(), tmp.path());
         assert_eq!(name, "(root)");
     }
+
+    #[test]
+    fn test_walk_respects_depth_and_skips_dependency_dirs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        fs::create_dir_all(root.join(".github/workflows")).unwrap();
+        fs::write(root.join(".github/workflows/ci.yml"), "on: push").unwrap();
+        fs::create_dir_all(root.join("a/b/c")).unwrap();
+        fs::write(root.join("a/b/c/deep.yml"), "on: push").unwrap();
+        fs::create_dir_all(root.join("node_modules/pkg")).unwrap();
+        fs::write(root.join("node_modules/pkg/ci.yml"), "on: push").unwrap();
+        fs::write(root.join("README.md"), "# readme").unwrap();
+
+        let files = walk_pipeline_files(root, 2).unwrap();
+        assert_eq!(files, vec![root.join(".github/workflows/ci.yml")]);
+
+        let files = walk_pipeline_files(root, 3).unwrap();
+        assert_eq!(files.len(), 2);
+        assert!(files.contains(&root.join("a/b/c/deep.yml")));
+    }
+
+    #[test]
+    fn test_sample_is_stable_for_a_seed() {
+        let pipelines: Vec<DiscoveredPipeline> = (0..200)
+            .map(|i| DiscoveredPipeline {
+                package_name: format!("pkg-{i}"),
+                file_path: PathBuf::from(format!("/repo/pkg-{i}/.gitlab-ci.yml")),
+                relative_path: format!("pkg-{i}/.gitlab-ci.yml"),
+            })
+            .collect();
+        let paths = |sample: &[DiscoveredPipeline]| {
+            sample
+                .iter()
+                .map(|p| p.relative_path.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let first = sample_pipelines(&pipelines, 10, 42);
+        assert_eq!(first.len(), 10);
+        assert_eq!(paths(&first), paths(&sample_pipelines(&pipelines, 10, 42)));
+
+        // Discovery order does not matter
+        let mut reversed = pipelines.clone();
+        reversed.reverse();
+        let mut from_reversed = paths(&sample_pipelines(&reversed, 10, 42));
+        from_reversed.reverse();
+        assert_eq!(paths(&first), from_reversed);
+
+        assert_ne!(paths(&first), paths(&sample_pipelines(&pipelines, 10, 7)));
+        assert_eq!(sample_pipelines(&pipelines, 500, 42).len(), 200);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_walk_terminates_on_symlink_cycle() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        fs::create_dir_all(root.join("pkgs/app")).unwrap();
+        fs::write(root.join("pkgs/app/ci.yml"), "on: push").unwrap();
+        // pkgs/app/loop -> pkgs, forming a cycle.
+        std::os::unix::fs::symlink(root.join("pkgs"), root.join("pkgs/app/loop")).unwrap();
+
+        let files = walk_pipeline_files(root, 50).unwrap();
+        assert_eq!(files, vec![root.join("pkgs/app/ci.yml")]);
+    }
 }
@@ -0,0 +1,204 @@
+use crate::parser::dag::{JobNode, PipelineDag};
+use crate::providers::github_api::PipelineStatistics;
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Source of per-job duration estimates used by the analyzer.
+pub trait DurationModel {
+    /// Estimated duration of a job in seconds.
+    fn job_duration(&self, job: &JobNode) -> f64;
+}
+
+/// Default model: keep the command-based heuristics computed by the parsers.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeuristicModel;
+
+impl DurationModel for HeuristicModel {
+    fn job_duration(&self, job: &JobNode) -> f64 {
+        job.estimated_duration_secs
+    }
+}
+
+/// Model backed by measured job durations (job name → mean seconds).
+///
+/// Jobs without history fall back to the heuristic estimate.
+#[derive(Debug, Clone, Default)]
+pub struct HistoricalModel {
+    means: HashMap<String, f64>,
+}
+
+impl HistoricalModel {
+    pub fn new(means: HashMap<String, f64>) -> Self {
+        Self {
+            means: means
+                .into_iter()
+                .map(|(name, secs)| (normalize_name(&name), secs))
+                .collect(),
+        }
+    }
+
+    /// Build a model from `pipelinex history` statistics.
+    pub fn from_statistics(stats: &PipelineStatistics) -> Self {
+        Self::new(
+            stats
+                .job_timings
+                .iter()
+                .filter(|t| !t.durations_sec.is_empty())
+                .map(|t| (t.job_name.clone(), t.avg_duration_sec))
+                .collect(),
+        )
+    }
+
+    /// Build a model from CSV rows of `job_name,duration_secs`.
+    ///
+    /// A header row is optional and repeated job names are averaged.
+    pub fn from_csv(content: &str) -> Result<Self> {
+        let mut samples: HashMap<String, Vec<f64>> = HashMap::new();
+        let mut first_row = true;
+
+        for (idx, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let is_first_row = std::mem::replace(&mut first_row, false);
+            let Some((name, secs)) = line.rsplit_once(',') else {
+                anyhow::bail!("Line {}: expected `job_name,duration_secs`", idx + 1);
+            };
+            let name = name.trim().trim_matches('"');
+            let secs: f64 = match secs.trim().parse() {
+                Ok(v) => v,
+                Err(_) if is_first_row => continue, // header row
+                Err(_) => anyhow::bail!("Line {}: invalid duration '{}'", idx + 1, secs.trim()),
+            };
+            samples.entry(normalize_name(name)).or_default().push(secs);
+        }
+
+        Ok(Self::new(
+            samples
+                .into_iter()
+                .map(|(name, values)| {
+                    let mean = values.iter().sum::<f64>() / values.len() as f64;
+                    (name, mean)
+                })
+                .collect(),
+        ))
+    }
+
+    /// Load a CSV timings file from disk.
+    pub fn from_csv_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read timings file: {}", path.display()))?;
+        Self::from_csv(&content)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.means.is_empty()
+    }
+
+    /// Measured mean for a job, matching on job name, then ID, then the matrix-stripped name.
+    pub fn lookup(&self, job: &JobNode) -> Option<f64> {
+        [job.name.as_str(), job.id.as_str()]
+            .iter()
+            .find_map(|key| {
+                let key = normalize_name(key);
+                self.means.get(&key).or_else(|| {
+                    // GitHub reports matrix jobs as "test (18, ubuntu)"
+                    key.split(" (").next().and_then(|base| self.means.get(base))
+                })
+            })
+            .copied()
+    }
+}
+
+impl DurationModel for HistoricalModel {
+    fn job_duration(&self, job: &JobNode) -> f64 {
+        self.lookup(job)
+            .unwrap_or_else(|| HeuristicModel.job_duration(job))
+    }
+}
+
+/// Re-estimate every job in the DAG with the given model.
+pub fn apply_duration_model(dag: &mut PipelineDag, model: &dyn DurationModel) {
+    for job in dag.graph.node_weights_mut() {
+        job.estimated_duration_secs = model.job_duration(job);
+    }
+}
+
+fn normalize_name(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer;
+    use crate::parser::github::GitHubActionsParser;
+
+    const WORKFLOW: &str = r#"
+name: CI
+on: push
+jobs:
+  install:
+    name: Install deps
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm ci
+  test:
+    needs: install
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm test
+"#;
+
+    #[test]
+    fn test_heuristic_model_keeps_parser_estimates() {
+        let mut dag = GitHubActionsParser::parse(WORKFLOW, "ci.yml".to_string()).unwrap();
+        let before = dag.get_job("install").unwrap().estimated_duration_secs;
+        apply_duration_model(&mut dag, &HeuristicModel);
+        assert_eq!(
+            dag.get_job("install").unwrap().estimated_duration_secs,
+            before
+        );
+    }
+
+    #[test]
+    fn test_historical_model_overrides_named_job() {
+        let model =
+            HistoricalModel::from_csv("job_name,duration_secs\nInstall deps,20\ninstall deps,40\n")
+                .unwrap();
+        let mut dag = GitHubActionsParser::parse(WORKFLOW, "ci.yml".to_string()).unwrap();
+        let test_before = dag.get_job("test").unwrap().estimated_duration_secs;
+
+        apply_duration_model(&mut dag, &model);
+
+        assert_eq!(
+            dag.get_job("install").unwrap().estimated_duration_secs,
+            30.0
+        );
+        // No history for `test`, so the heuristic estimate is kept.
+        assert_eq!(
+            dag.get_job("test").unwrap().estimated_duration_secs,
+            test_before
+        );
+
+        let report = analyzer::analyze(&dag);
+        assert_eq!(report.critical_path_duration_secs, 30.0 + test_before);
+    }
+
+    #[test]
+    fn test_matrix_job_names_match_base_name() {
+        let mut means = HashMap::new();
+        means.insert("test".to_string(), 90.0);
+        let model = HistoricalModel::new(means);
+        let job = JobNode::new("unit".into(), "test (18, ubuntu-latest)".into());
+        assert_eq!(model.lookup(&job), Some(90.0));
+    }
+
+    #[test]
+    fn test_csv_rejects_bad_rows() {
+        assert!(HistoricalModel::from_csv("build,20\ntest,abc\n").is_err());
+        assert!(HistoricalModel::from_csv("build 20\n").is_err());
+    }
+}
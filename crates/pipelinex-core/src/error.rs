@@ -0,0 +1,120 @@
+use std::path::{Path, PathBuf};
+
+/// Errors returned by the public parsing, routing and signing APIs.
+///
+/// Library consumers can match on the variant; the CLI converts these into
+/// `anyhow` errors for display.
+#[derive(Debug, thiserror::Error)]
+pub enum PipelineError {
+    /// A pipeline file could not be read.
+    #[error("Failed to read {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// The file was read but is not a valid pipeline for the provider.
+    #[error("Failed to parse {provider} pipeline: {detail}")]
+    Parse { provider: String, detail: String },
+
+    /// No parser exists for the requested provider.
+    #[error("Unsupported provider '{0}'")]
+    UnsupportedProvider(String),
+
+    /// Job dependencies form a cycle, so there is no valid execution order.
+    #[error("Dependency cycle between jobs: {}", jobs.join(", "))]
+    Cycle { jobs: Vec<String> },
+
+    /// A dependency refers to a job that is not in the DAG.
+    #[error("Job '{0}' not found in DAG")]
+    UnknownJob(String),
+
+    /// A `--render` preprocessing command failed or produced unusable output.
+    #[error("Renderer `{command}` failed for {}: {detail}", path.display())]
+    Render {
+        command: String,
+        path: PathBuf,
+        detail: String,
+    },
+
+    /// Key handling, signing or verification failed.
+    #[error("Signing error: {0}")]
+    Signing(String),
+}
+
+impl PipelineError {
+    /// Wrap a parser failure, keeping the full cause chain in `detail`.
+    pub fn parse(provider: &str, source_file: &str, err: anyhow::Error) -> Self {
+        PipelineError::Parse {
+            provider: provider.to_string(),
+            detail: format!("{}: {:#}", source_file, err),
+        }
+    }
+}
+
+/// Read a pipeline file, mapping failures to [`PipelineError::Io`].
+pub(crate) fn read_pipeline_file(path: &Path) -> Result<String, PipelineError> {
+    std::fs::read_to_string(path).map_err(|source| PipelineError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::detect::parse_pipeline_file_as;
+    use crate::parser::github::GitHubActionsParser;
+    use crate::parser::gitlab::GitLabCIParser;
+
+    #[test]
+    fn test_malformed_yaml_is_parse_error() {
+        let err = GitHubActionsParser::parse("jobs: [unclosed", "ci.yml".to_string()).unwrap_err();
+        match err {
+            PipelineError::Parse { provider, detail } => {
+                assert_eq!(provider, "github-actions");
+                assert!(detail.starts_with("ci.yml: "));
+            }
+            other => panic!("expected Parse, got {:?}", other),
+        }
+
+        assert!(matches!(
+            GitLabCIParser::parse("stages: {", ".gitlab-ci.yml".to_string()),
+            Err(PipelineError::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn test_missing_file_is_io_error() {
+        let err = GitHubActionsParser::parse_file(Path::new("/nonexistent/ci.yml")).unwrap_err();
+        assert!(matches!(err, PipelineError::Io { .. }));
+    }
+
+    #[test]
+    fn test_unknown_provider_is_unsupported() {
+        let err = parse_pipeline_file_as(Path::new("ci.yml"), "teamcity").unwrap_err();
+        assert!(matches!(err, PipelineError::UnsupportedProvider(p) if p == "teamcity"));
+    }
+
+    #[test]
+    fn test_dependency_cycle_is_reported() {
+        let yaml = r#"
+on: push
+jobs:
+  a:
+    needs: b
+    runs-on: ubuntu-latest
+    steps: [{ run: echo a }]
+  b:
+    needs: a
+    runs-on: ubuntu-latest
+    steps: [{ run: echo b }]
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        match dag.topological_order() {
+            Err(PipelineError::Cycle { jobs }) => assert_eq!(jobs, vec!["a", "b"]),
+            other => panic!("expected Cycle, got {:?}", other.map(|o| o.len())),
+        }
+    }
+}
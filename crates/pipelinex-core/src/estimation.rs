@@ -0,0 +1,243 @@
+//! Command → duration heuristics shared by the parsers.
+//!
+//! Parsers estimate how long a shell step takes by matching its command
+//! against [`EstimateTable`]. The built-in table can be extended or overridden
+//! from a TOML file (`--estimates`), so teams can calibrate the figures for
+//! their own runners:
+//!
+//! ```toml
+//! default_secs = 45
+//!
+//! [[commands]]
+//! patterns = ["npm ci", "npm install"]
+//! secs = 90
+//! ```
+//!
+//! Rules from a file are tried before the built-in ones; the first rule with a
+//! pattern contained in the (lowercased) command wins.
+
+use crate::parser::dag::StepInfo;
+use serde::Deserialize;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Estimate for a step that runs no command (checkout, cache restore, ...).
+const NON_COMMAND_STEP_SECS: f64 = 10.0;
+
+/// Estimate for a command no rule matches.
+pub const DEFAULT_COMMAND_SECS: f64 = 30.0;
+
+/// Built-in rules, most specific first.
+const DEFAULT_RULES: &[(&[&str], f64)] = &[
+    (
+        &["npm ci", "npm install", "yarn install", "pnpm install"],
+        180.0,
+    ),
+    (&["pip install"], 120.0),
+    (&["bundle install"], 150.0),
+    (&["composer install"], 90.0),
+    (&["dotnet restore"], 120.0),
+    (&["cargo build", "go build"], 300.0),
+    (
+        &["npm run build", "yarn build", "pnpm build", "dotnet build"],
+        240.0,
+    ),
+    (
+        &[
+            "npm test",
+            "pytest",
+            "cargo test",
+            "go test",
+            "jest",
+            "rspec",
+            "dotnet test",
+        ],
+        300.0,
+    ),
+    (&["lint", "clippy", "rubocop", "flake8"], 60.0),
+    (&["docker build"], 300.0),
+    (&["docker push"], 60.0),
+    (&["deploy", "kubectl", "helm", "terraform"], 120.0),
+    (&["apt-get", "apk add"], 45.0),
+];
+
+/// One rule: any of `patterns` appearing in a command means `secs`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CommandEstimate {
+    pub patterns: Vec<String>,
+    pub secs: f64,
+}
+
+/// Ordered command rules plus the fallback estimate.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct EstimateTable {
+    #[serde(default = "default_command_secs")]
+    pub default_secs: f64,
+    #[serde(default)]
+    pub commands: Vec<CommandEstimate>,
+}
+
+fn default_command_secs() -> f64 {
+    DEFAULT_COMMAND_SECS
+}
+
+impl Default for EstimateTable {
+    fn default() -> Self {
+        Self {
+            default_secs: DEFAULT_COMMAND_SECS,
+            commands: DEFAULT_RULES
+                .iter()
+                .map(|(patterns, secs)| CommandEstimate {
+                    patterns: patterns.iter().map(|p| p.to_string()).collect(),
+                    secs: *secs,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl EstimateTable {
+    /// Parse a TOML estimates file and layer its rules over the built-in table.
+    pub fn from_toml(content: &str) -> anyhow::Result<Self> {
+        let custom: EstimateTable = toml::from_str(content)?;
+        if let Some(rule) = custom
+            .commands
+            .iter()
+            .find(|r| !r.secs.is_finite() || r.secs < 0.0)
+        {
+            anyhow::bail!(
+                "Estimate for {:?} must be a non-negative number of seconds",
+                rule.patterns
+            );
+        }
+
+        let mut commands: Vec<CommandEstimate> = custom
+            .commands
+            .into_iter()
+            .map(|mut rule| {
+                for pattern in &mut rule.patterns {
+                    *pattern = pattern.to_lowercase();
+                }
+                rule
+            })
+            .collect();
+        commands.extend(Self::default().commands);
+        Ok(Self {
+            default_secs: custom.default_secs,
+            commands,
+        })
+    }
+
+    /// Read an estimates file (see the module docs for the format).
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read estimates '{}': {}", path.display(), e))?;
+        Self::from_toml(&content)
+            .map_err(|e| anyhow::anyhow!("Failed to parse estimates '{}': {}", path.display(), e))
+    }
+
+    /// Seconds for the first rule matching `cmd`, if any.
+    pub fn match_command(&self, cmd: &str) -> Option<f64> {
+        let cmd = cmd.to_lowercase();
+        self.commands
+            .iter()
+            .find(|rule| rule.patterns.iter().any(|p| cmd.contains(p.as_str())))
+            .map(|rule| rule.secs)
+    }
+
+    /// Estimated seconds for `cmd`, falling back to `default_secs`.
+    pub fn command_secs(&self, cmd: &str) -> f64 {
+        self.match_command(cmd).unwrap_or(self.default_secs)
+    }
+
+    /// Estimated seconds for a job's steps, once a rule recognises at least
+    /// one of their commands. `None` leaves the job to the parser's own
+    /// guess (Bitbucket, CircleCI and Jenkins go by the job name).
+    pub fn steps_secs(&self, steps: &[StepInfo]) -> Option<f64> {
+        let recognised = steps
+            .iter()
+            .filter_map(|s| s.run.as_deref())
+            .any(|run| self.match_command(run).is_some());
+        recognised.then(|| {
+            steps
+                .iter()
+                .map(|s| {
+                    s.run
+                        .as_deref()
+                        .map_or(NON_COMMAND_STEP_SECS, |run| self.command_secs(run))
+                })
+                .sum()
+        })
+    }
+}
+
+static ESTIMATES: OnceLock<EstimateTable> = OnceLock::new();
+
+/// Make `table` the process-wide table used by the parsers.
+///
+/// Only the first call takes effect; later calls return the rejected table.
+pub fn install(table: EstimateTable) -> Result<(), EstimateTable> {
+    ESTIMATES.set(table)
+}
+
+/// The installed table, or the built-in one.
+pub fn estimates() -> &'static EstimateTable {
+    ESTIMATES.get_or_init(EstimateTable::default)
+}
+
+/// Estimated seconds for a shell command using the process-wide table.
+pub fn command_secs(cmd: &str) -> f64 {
+    estimates().command_secs(cmd)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimates_file_overrides_npm_ci() {
+        let default = EstimateTable::default();
+        assert_eq!(default.command_secs("npm ci"), 180.0);
+        assert_eq!(default.command_secs("echo hi"), DEFAULT_COMMAND_SECS);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("estimates.toml");
+        std::fs::write(
+            &path,
+            "default_secs = 45\n\n[[commands]]\npatterns = [\"NPM CI\"]\nsecs = 75\n",
+        )
+        .unwrap();
+        let custom = EstimateTable::from_file(&path).unwrap();
+
+        assert_eq!(custom.command_secs("npm ci --prefer-offline"), 75.0);
+        // Built-in rules still apply to everything else
+        assert_eq!(custom.command_secs("npm install"), 180.0);
+        assert_eq!(custom.command_secs("echo hi"), 45.0);
+
+        assert!(EstimateTable::from_toml("[[commands]]\npatterns = [\"x\"]\nsecs = -1\n").is_err());
+    }
+
+    #[test]
+    fn test_steps_secs_needs_a_recognised_command() {
+        let step = |run: Option<&str>| StepInfo {
+            name: "step".into(),
+            uses: None,
+            run: run.map(String::from),
+            estimated_duration_secs: None,
+            with: Default::default(),
+            working_directory: None,
+            env: Default::default(),
+        };
+        let table =
+            EstimateTable::from_toml("[[commands]]\npatterns = [\"gradlew\"]\nsecs = 600\n")
+                .unwrap();
+
+        let steps = [
+            step(None),
+            step(Some("./gradlew assemble")),
+            step(Some("echo done")),
+        ];
+        assert_eq!(table.steps_secs(&steps), Some(10.0 + 600.0 + 30.0));
+        assert_eq!(table.steps_secs(&[step(Some("./custom.sh"))]), None);
+    }
+}
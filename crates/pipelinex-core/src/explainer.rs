@@ -2,7 +2,8 @@
 //!
 //! Supports Anthropic Claude, OpenAI, and template-based fallback.
 
-use crate::analyzer::report::{Finding, Severity};
+use crate::analyzer::categories::category_metadata;
+use crate::analyzer::report::{Finding, FindingCategory, Severity};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 
@@ -318,6 +319,11 @@ impl PipelineContext {
     }
 }
 
+/// One-line "why this matters" for a finding category, used by `analyze --explain`.
+pub fn category_rationale(category: &FindingCategory) -> &'static str {
+    category_metadata(category).rationale
+}
+
 /// Format explanations for terminal display.
 pub fn format_explanations(explanations: &[Explanation]) -> String {
     let mut out = String::new();
@@ -350,6 +356,8 @@ mod tests {
             estimated_savings_secs: Some(150.0),
             confidence: 0.95,
             auto_fixable: true,
+            documentation_url: None,
+            blame: None,
         }
     }
 
@@ -396,6 +404,20 @@ mod tests {
         assert_eq!(explanations.len(), 2);
     }
 
+    #[test]
+    fn test_category_rationale_is_distinct() {
+        let categories = [
+            FindingCategory::MissingCache,
+            FindingCategory::SerialBottleneck,
+            FindingCategory::ShallowClone,
+            FindingCategory::Security,
+        ];
+        let rationales: std::collections::HashSet<_> =
+            categories.iter().map(category_rationale).collect();
+        assert_eq!(rationales.len(), categories.len());
+        assert!(category_rationale(&FindingCategory::MissingCache).contains("cache"));
+    }
+
     #[test]
     fn test_format_explanations() {
         let explanations = vec![Explanation {
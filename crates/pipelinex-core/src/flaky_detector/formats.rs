@@ -0,0 +1,420 @@
+//! Test report parsers for the flaky detector.
+//!
+//! The root element decides the format: JUnit (`testsuites`/`testsuite`),
+//! NUnit3 (`test-run`), xUnit v2 (`assemblies`/`assembly`) or Visual Studio
+//! TRX (`TestRun`). All of them are normalized to [`TestResult`], named
+//! `class::test` where the format records a class.
+
+use super::{TestResult, TestStatus};
+use anyhow::{Context, Result};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A parsed XML element; only what the report formats need is kept.
+#[derive(Debug, Default)]
+struct Element {
+    name: String,
+    attributes: HashMap<String, String>,
+    children: Vec<Element>,
+    text: String,
+}
+
+impl Element {
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attributes.get(name).map(String::as_str)
+    }
+
+    fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|c| c.name == name)
+    }
+
+    /// Every descendant (not including `self`) with the given name, in document order.
+    fn descendants<'a>(&'a self, name: &str, out: &mut Vec<&'a Element>) {
+        for child in &self.children {
+            if child.name == name {
+                out.push(child);
+            }
+            child.descendants(name, out);
+        }
+    }
+
+    /// Text of the child at `path`, e.g. `["Output", "ErrorInfo", "Message"]`.
+    fn text_at(&self, path: &[&str]) -> Option<String> {
+        let element = path.iter().try_fold(self, |el, name| el.child(name))?;
+        let text = element.text.trim();
+        (!text.is_empty()).then(|| text.to_string())
+    }
+}
+
+fn start_element(start: &BytesStart) -> Result<Element> {
+    let name = String::from_utf8_lossy(start.local_name().as_ref()).into_owned();
+    let mut attributes = HashMap::new();
+    for attr in start.attributes() {
+        let attr = attr?;
+        let key = String::from_utf8_lossy(attr.key.local_name().as_ref()).into_owned();
+        attributes.insert(key, attr.unescape_value()?.into_owned());
+    }
+    Ok(Element {
+        name,
+        attributes,
+        ..Element::default()
+    })
+}
+
+fn parse_document(content: &str) -> Result<Element> {
+    let mut reader = Reader::from_str(content);
+    let mut stack: Vec<Element> = Vec::new();
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(start) => stack.push(start_element(&start)?),
+            Event::Empty(start) => {
+                let element = start_element(&start)?;
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(element),
+                    None => return Ok(element),
+                }
+            }
+            Event::End(_) => {
+                let element = stack.pop().context("Unbalanced closing tag")?;
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(element),
+                    None => return Ok(element),
+                }
+            }
+            Event::Text(text) => {
+                if let Some(current) = stack.last_mut() {
+                    current.text.push_str(&text.unescape()?);
+                }
+            }
+            Event::CData(data) => {
+                if let Some(current) = stack.last_mut() {
+                    current
+                        .text
+                        .push_str(&String::from_utf8_lossy(&data.into_inner()));
+                }
+            }
+            Event::Eof => anyhow::bail!("No root element"),
+            _ => {}
+        }
+    }
+}
+
+/// Parse a test report file in any supported format.
+pub fn parse_test_report(path: &Path) -> Result<Vec<TestResult>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read test report: {}", path.display()))?;
+    parse_test_report_str(&content)
+        .with_context(|| format!("Failed to parse test report: {}", path.display()))
+}
+
+/// Parse test report XML, detecting the format from the root element.
+pub fn parse_test_report_str(content: &str) -> Result<Vec<TestResult>> {
+    let root = parse_document(content)?;
+    match root.name.as_str() {
+        "testsuites" | "testsuite" => Ok(parse_junit(&root)),
+        "test-run" => Ok(parse_nunit3(&root)),
+        "assemblies" | "assembly" => Ok(parse_xunit(&root)),
+        "TestRun" => Ok(parse_trx(&root)),
+        other => anyhow::bail!(
+            "Unrecognized test report root element <{}> (expected JUnit, NUnit3, xUnit or TRX)",
+            other
+        ),
+    }
+}
+
+/// The root element of `path` when it is well-formed XML in none of the
+/// supported formats (a coverage or lint report, say), so directory scans can
+/// skip it. `None` for reports and for files that are not readable XML.
+pub fn unrecognized_root(path: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let mut reader = Reader::from_str(&content);
+    loop {
+        match reader.read_event().ok()? {
+            Event::Start(start) | Event::Empty(start) => {
+                let root = String::from_utf8_lossy(start.local_name().as_ref()).into_owned();
+                return (!is_report_root(&root)).then_some(root);
+            }
+            Event::Eof => return None,
+            _ => {}
+        }
+    }
+}
+
+fn is_report_root(name: &str) -> bool {
+    matches!(
+        name,
+        "testsuites" | "testsuite" | "test-run" | "assemblies" | "assembly" | "TestRun"
+    )
+}
+
+fn qualified_name(class: Option<&str>, name: &str) -> String {
+    match class.filter(|c| !c.is_empty()) {
+        Some(class) => format!("{}::{}", class, name),
+        None => name.to_string(),
+    }
+}
+
+fn seconds_to_ms(value: Option<&str>) -> u64 {
+    value
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .map(|secs| (secs * 1000.0) as u64)
+        .unwrap_or(0)
+}
+
+fn result(name: String, status: TestStatus, duration_ms: u64, error: Option<String>) -> TestResult {
+    TestResult {
+        name,
+        status,
+        duration_ms,
+        timestamp: 0,
+        error_message: error,
+    }
+}
+
+fn parse_junit(root: &Element) -> Vec<TestResult> {
+    let mut cases = Vec::new();
+    if root.name == "testcase" {
+        cases.push(root);
+    }
+    root.descendants("testcase", &mut cases);
+
+    cases
+        .into_iter()
+        .filter_map(|case| {
+            let name = qualified_name(case.attr("classname"), case.attr("name")?);
+            let failure = case.child("failure").or_else(|| case.child("error"));
+            let (status, error) = match failure {
+                Some(f) => (
+                    TestStatus::Failed,
+                    f.attr("message")
+                        .map(str::to_string)
+                        .or_else(|| f.text_at(&[])),
+                ),
+                None if case.child("skipped").is_some() => (TestStatus::Skipped, None),
+                None => (TestStatus::Passed, None),
+            };
+            Some(result(
+                name,
+                status,
+                seconds_to_ms(case.attr("time")),
+                error,
+            ))
+        })
+        .collect()
+}
+
+fn parse_nunit3(root: &Element) -> Vec<TestResult> {
+    let mut cases = Vec::new();
+    root.descendants("test-case", &mut cases);
+
+    cases
+        .into_iter()
+        .filter_map(|case| {
+            let name = match (case.attr("classname"), case.attr("methodname")) {
+                (Some(class), Some(method)) => qualified_name(Some(class), method),
+                _ => case.attr("fullname").or(case.attr("name"))?.to_string(),
+            };
+            let status = match case.attr("result") {
+                Some("Passed") => TestStatus::Passed,
+                Some("Failed") => TestStatus::Failed,
+                _ => TestStatus::Skipped,
+            };
+            let error = (status == TestStatus::Failed)
+                .then(|| case.text_at(&["failure", "message"]))
+                .flatten();
+            Some(result(
+                name,
+                status,
+                seconds_to_ms(case.attr("duration")),
+                error,
+            ))
+        })
+        .collect()
+}
+
+fn parse_xunit(root: &Element) -> Vec<TestResult> {
+    let mut tests = Vec::new();
+    root.descendants("test", &mut tests);
+
+    tests
+        .into_iter()
+        .filter_map(|test| {
+            let name = match (test.attr("type"), test.attr("method")) {
+                (Some(class), Some(method)) => qualified_name(Some(class), method),
+                _ => test.attr("name")?.to_string(),
+            };
+            let status = match test.attr("result") {
+                Some("Pass") => TestStatus::Passed,
+                Some("Fail") => TestStatus::Failed,
+                _ => TestStatus::Skipped,
+            };
+            let error = (status == TestStatus::Failed)
+                .then(|| test.text_at(&["failure", "message"]))
+                .flatten();
+            Some(result(
+                name,
+                status,
+                seconds_to_ms(test.attr("time")),
+                error,
+            ))
+        })
+        .collect()
+}
+
+/// TRX durations are `hh:mm:ss.fffffff`.
+fn trx_duration_ms(value: Option<&str>) -> u64 {
+    let Some(value) = value else { return 0 };
+    let secs = value
+        .split(':')
+        .try_fold(0.0, |acc, part| {
+            part.parse::<f64>().ok().map(|p| acc * 60.0 + p)
+        })
+        .unwrap_or(0.0);
+    (secs * 1000.0) as u64
+}
+
+fn parse_trx(root: &Element) -> Vec<TestResult> {
+    // Class names live on the test definitions, keyed by test id.
+    let mut definitions = Vec::new();
+    root.descendants("UnitTest", &mut definitions);
+    let classes: HashMap<&str, &str> = definitions
+        .into_iter()
+        .filter_map(|def| {
+            let class = def.child("TestMethod")?.attr("className")?;
+            // `className` may be assembly-qualified: "Ns.Class, Assembly, Version=..."
+            Some((
+                def.attr("id")?,
+                class.split(',').next().unwrap_or(class).trim(),
+            ))
+        })
+        .collect();
+
+    let mut results = Vec::new();
+    root.descendants("UnitTestResult", &mut results);
+
+    results
+        .into_iter()
+        .filter_map(|r| {
+            let class = r.attr("testId").and_then(|id| classes.get(id).copied());
+            let name = qualified_name(class, r.attr("testName")?);
+            let status = match r.attr("outcome") {
+                Some("Passed") => TestStatus::Passed,
+                Some("Failed" | "Error" | "Timeout" | "Aborted") => TestStatus::Failed,
+                _ => TestStatus::Skipped,
+            };
+            let error = (status == TestStatus::Failed)
+                .then(|| r.text_at(&["Output", "ErrorInfo", "Message"]))
+                .flatten();
+            Some(result(
+                name,
+                status,
+                trx_duration_ms(r.attr("duration")),
+                error,
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(results: &[TestResult]) -> (usize, usize, usize) {
+        let count = |s: TestStatus| results.iter().filter(|r| r.status == s).count();
+        (
+            count(TestStatus::Passed),
+            count(TestStatus::Failed),
+            count(TestStatus::Skipped),
+        )
+    }
+
+    #[test]
+    fn test_junit_keeps_every_testcase() {
+        let xml = r#"<?xml version="1.0"?>
+<testsuite name="api">
+  <testcase classname="api" name="login" time="0.5"/>
+  <testcase classname="api" name="logout"><failure message="timeout">stack</failure></testcase>
+  <testcase classname="api" name="refresh"><skipped/></testcase>
+</testsuite>"#;
+        let results = parse_test_report_str(xml).unwrap();
+        assert_eq!(counts(&results), (1, 1, 1));
+        assert_eq!(results[0].name, "api::login");
+        assert_eq!(results[0].duration_ms, 500);
+        assert_eq!(results[1].error_message.as_deref(), Some("timeout"));
+    }
+
+    #[test]
+    fn test_nunit3_report() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<test-run id="0" testcasecount="4" result="Failed">
+  <test-suite type="Assembly" name="Shop.Tests.dll">
+    <test-suite type="TestFixture" name="CartTests" fullname="Shop.Tests.CartTests">
+      <test-case id="1" name="AddsItem" fullname="Shop.Tests.CartTests.AddsItem"
+                 methodname="AddsItem" classname="Shop.Tests.CartTests" result="Passed" duration="0.012"/>
+      <test-case id="2" name="Checkout" fullname="Shop.Tests.CartTests.Checkout"
+                 methodname="Checkout" classname="Shop.Tests.CartTests" result="Failed" duration="1.5">
+        <failure><message><![CDATA[Expected 3 but was 2]]></message></failure>
+      </test-case>
+      <test-case id="3" name="Refund" fullname="Shop.Tests.CartTests.Refund" result="Passed" duration="0.1"/>
+      <test-case id="4" name="Slow" fullname="Shop.Tests.CartTests.Slow" result="Skipped"/>
+    </test-suite>
+  </test-suite>
+</test-run>"#;
+        let results = parse_test_report_str(xml).unwrap();
+        assert_eq!(counts(&results), (2, 1, 1));
+        assert_eq!(results[1].name, "Shop.Tests.CartTests::Checkout");
+        assert_eq!(results[1].duration_ms, 1500);
+        assert_eq!(
+            results[1].error_message.as_deref(),
+            Some("Expected 3 but was 2")
+        );
+        assert_eq!(results[2].name, "Shop.Tests.CartTests.Refund");
+    }
+
+    #[test]
+    fn test_trx_report() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<TestRun id="1" name="run" xmlns="http://microsoft.com/schemas/VisualStudio/TeamTest/2010">
+  <Results>
+    <UnitTestResult testId="a" testName="Adds" outcome="Passed" duration="00:00:00.2500000"/>
+    <UnitTestResult testId="b" testName="Divides" outcome="Failed" duration="00:00:01.0000000">
+      <Output><ErrorInfo><Message>DivideByZeroException</Message></ErrorInfo></Output>
+    </UnitTestResult>
+    <UnitTestResult testId="c" testName="Rounds" outcome="Passed" duration="00:01:00"/>
+    <UnitTestResult testId="d" testName="Later" outcome="NotExecuted"/>
+  </Results>
+  <TestDefinitions>
+    <UnitTest id="a" name="Adds"><TestMethod className="Calc.Tests.MathTests, Calc.Tests" name="Adds"/></UnitTest>
+    <UnitTest id="b" name="Divides"><TestMethod className="Calc.Tests.MathTests" name="Divides"/></UnitTest>
+  </TestDefinitions>
+</TestRun>"#;
+        let results = parse_test_report_str(xml).unwrap();
+        assert_eq!(counts(&results), (2, 1, 1));
+        assert_eq!(results[0].name, "Calc.Tests.MathTests::Adds");
+        assert_eq!(results[0].duration_ms, 250);
+        assert_eq!(
+            results[1].error_message.as_deref(),
+            Some("DivideByZeroException")
+        );
+        assert_eq!(results[2].name, "Rounds");
+        assert_eq!(results[2].duration_ms, 60_000);
+    }
+
+    #[test]
+    fn test_xunit_and_unknown_roots() {
+        let xml = r#"<assemblies><assembly name="A.dll"><collection>
+  <test name="A.T.One" type="A.T" method="One" time="0.1" result="Pass"/>
+  <test name="A.T.Two" type="A.T" method="Two" time="0.2" result="Fail">
+    <failure><message>boom</message></failure>
+  </test>
+</collection></assembly></assemblies>"#;
+        let results = parse_test_report_str(xml).unwrap();
+        assert_eq!(counts(&results), (1, 1, 0));
+        assert_eq!(results[1].name, "A.T::Two");
+
+        assert!(parse_test_report_str("<coverage/>").is_err());
+    }
+}
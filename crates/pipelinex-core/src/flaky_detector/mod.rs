@@ -0,0 +1,641 @@
+pub mod formats;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Represents a test execution result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestResult {
+    pub name: String,
+    pub status: TestStatus,
+    pub duration_ms: u64,
+    pub timestamp: u64,
+    pub error_message: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TestStatus {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+/// Represents a flaky test detection result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlakyTest {
+    pub name: String,
+    pub flakiness_score: f64,
+    pub total_runs: usize,
+    pub failures: usize,
+    pub passes: usize,
+    pub failure_rate: f64,
+    pub recent_failures: Vec<String>,
+    pub category: FlakyCategory,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FlakyCategory {
+    /// Test fails <50% of the time (intermittent)
+    Intermittent,
+    /// Test alternates between pass/fail without code changes
+    Unstable,
+    /// Test fails only in specific environments
+    EnvironmentSensitive,
+    /// Test has timing dependencies
+    TimingDependent,
+}
+
+/// Flaky test detection report.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FlakyReport {
+    pub total_tests: usize,
+    pub flaky_tests: Vec<FlakyTest>,
+    pub flakiness_ratio: f64,
+    pub confidence: String,
+    /// Per-test failure-rate trend, when several batches were analyzed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trends: Option<Vec<FlakyTrend>>,
+    /// Tests that only started failing in the latest batch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_flaky_tests: Option<Vec<String>>,
+}
+
+/// Test results collected at one point in time, e.g. one build's JUnit files.
+#[derive(Debug, Clone)]
+pub struct TestBatch {
+    /// Unix timestamp; batches are analyzed oldest first
+    pub timestamp: u64,
+    pub results: Vec<TestResult>,
+}
+
+/// Direction of a test's failure rate between its first and latest batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrendDirection {
+    Improving,
+    Stable,
+    Worsening,
+}
+
+/// How one test's failure rate moved across batches.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlakyTrend {
+    pub name: String,
+    /// Failure rate in each batch, oldest first; `None` where the test did not run
+    pub failure_rates: Vec<Option<f64>>,
+    /// Latest minus first observed failure rate
+    pub change: f64,
+    pub direction: TrendDirection,
+}
+
+/// Failure-rate changes smaller than this count as stable.
+const TREND_TOLERANCE: f64 = 0.05;
+
+/// Output format for `flaky --emit-quarantine`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuarantineFormat {
+    /// A `-k 'not (...)'` expression for pytest
+    Pytest,
+    /// A Jest config fragment with `testPathIgnorePatterns`
+    Jest,
+    /// One test name per line
+    List,
+}
+
+impl QuarantineFormat {
+    pub const ALL: [QuarantineFormat; 3] = [
+        QuarantineFormat::Pytest,
+        QuarantineFormat::Jest,
+        QuarantineFormat::List,
+    ];
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            QuarantineFormat::Pytest => "pytest",
+            QuarantineFormat::Jest => "jest",
+            QuarantineFormat::List => "list",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        let id = id.trim().to_lowercase();
+        Self::ALL.into_iter().find(|f| f.id() == id)
+    }
+}
+
+/// Render the flaky tests of `report` as a quarantine file in `format`.
+///
+/// Names are `classname::name` as read from JUnit. pytest selects on the test
+/// name, so parametrization suffixes such as `[1-2]` are dropped. Jest ignores
+/// files rather than tests, so it gets the classname, which jest-junit can be
+/// configured to set to the test file path.
+pub fn quarantine_file(report: &FlakyReport, format: QuarantineFormat) -> String {
+    let mut names: Vec<&str> = report.flaky_tests.iter().map(|t| t.name.as_str()).collect();
+    names.sort_unstable();
+    names.dedup();
+
+    match format {
+        QuarantineFormat::List => names.iter().map(|n| format!("{}\n", n)).collect(),
+        QuarantineFormat::Pytest => {
+            let mut tests: Vec<&str> = names
+                .iter()
+                .map(|n| {
+                    let test = n.rsplit("::").next().unwrap_or(n);
+                    test.split('[').next().unwrap_or(test)
+                })
+                .filter(|t| !t.is_empty())
+                .collect();
+            tests.sort_unstable();
+            tests.dedup();
+            if tests.is_empty() {
+                String::new()
+            } else {
+                format!("-k 'not ({})'\n", tests.join(" or "))
+            }
+        }
+        QuarantineFormat::Jest => {
+            let mut patterns: Vec<String> = names
+                .iter()
+                .map(|n| regex::escape(n.split("::").next().unwrap_or(n)))
+                .collect();
+            patterns.sort_unstable();
+            patterns.dedup();
+            let config = serde_json::json!({ "testPathIgnorePatterns": patterns });
+            format!(
+                "{}\n",
+                serde_json::to_string_pretty(&config).unwrap_or_default()
+            )
+        }
+    }
+}
+
+/// Flaky test detector engine.
+pub struct FlakyDetector {
+    /// Minimum runs required to detect flakiness
+    min_runs: usize,
+    /// Threshold for considering a test flaky (0.0-1.0)
+    flaky_threshold: f64,
+}
+
+impl Default for FlakyDetector {
+    fn default() -> Self {
+        Self {
+            min_runs: 10,
+            flaky_threshold: 0.3,
+        }
+    }
+}
+
+impl FlakyDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create detector with custom thresholds.
+    pub fn with_config(min_runs: usize, flaky_threshold: f64) -> Self {
+        Self {
+            min_runs,
+            flaky_threshold,
+        }
+    }
+
+    /// Analyze test results from report files (JUnit, NUnit3, xUnit v2 or TRX XML).
+    pub fn analyze_junit_files(&self, paths: &[PathBuf]) -> Result<FlakyReport> {
+        let mut test_history: HashMap<String, Vec<TestResult>> = HashMap::new();
+
+        for path in paths {
+            let results = formats::parse_test_report(path)?;
+            for result in results {
+                test_history
+                    .entry(result.name.clone())
+                    .or_default()
+                    .push(result);
+            }
+        }
+
+        self.analyze_test_history(&test_history)
+    }
+
+    /// Parse the report files of one batch, stamping every result with `timestamp`.
+    pub fn load_batch(&self, timestamp: u64, paths: &[PathBuf]) -> Result<TestBatch> {
+        let mut results = Vec::new();
+        for path in paths {
+            results.extend(formats::parse_test_report(path)?);
+        }
+        for result in &mut results {
+            result.timestamp = timestamp;
+        }
+        Ok(TestBatch { timestamp, results })
+    }
+
+    /// Analyze several batches together and report how each test trends.
+    ///
+    /// Flakiness is detected over all results combined, as with
+    /// [`analyze_junit_files`](Self::analyze_junit_files). Trends cover every
+    /// test that failed at least once, comparing its first and latest observed
+    /// failure rates.
+    pub fn analyze_batches(&self, mut batches: Vec<TestBatch>) -> Result<FlakyReport> {
+        batches.sort_by_key(|b| b.timestamp);
+
+        let mut history: HashMap<String, Vec<TestResult>> = HashMap::new();
+        // test name -> (failures, passes) per batch
+        let mut per_batch: HashMap<String, Vec<(usize, usize)>> = HashMap::new();
+        for (i, batch) in batches.iter().enumerate() {
+            for result in &batch.results {
+                history
+                    .entry(result.name.clone())
+                    .or_default()
+                    .push(result.clone());
+                let counts = per_batch
+                    .entry(result.name.clone())
+                    .or_insert_with(|| vec![(0, 0); batches.len()]);
+                match result.status {
+                    TestStatus::Failed => counts[i].0 += 1,
+                    TestStatus::Passed => counts[i].1 += 1,
+                    TestStatus::Skipped => {}
+                }
+            }
+        }
+
+        let mut report = self.analyze_test_history(&history)?;
+        let mut trends = Vec::new();
+        let mut new_flaky = Vec::new();
+
+        for (name, counts) in per_batch {
+            if counts.iter().all(|(failures, _)| *failures == 0) {
+                continue;
+            }
+            let failure_rates: Vec<Option<f64>> = counts
+                .iter()
+                .map(|&(failures, passes)| {
+                    let runs = failures + passes;
+                    (runs > 0).then(|| failures as f64 / runs as f64)
+                })
+                .collect();
+            let observed: Vec<f64> = failure_rates.iter().flatten().copied().collect();
+            let (Some(first), Some(last)) = (observed.first(), observed.last()) else {
+                continue;
+            };
+
+            if let Some((latest, earlier)) = counts.split_last() {
+                if latest.0 > 0 && earlier.iter().all(|(failures, _)| *failures == 0) {
+                    new_flaky.push(name.clone());
+                }
+            }
+
+            let change = last - first;
+            let direction = if change > TREND_TOLERANCE {
+                TrendDirection::Worsening
+            } else if change < -TREND_TOLERANCE {
+                TrendDirection::Improving
+            } else {
+                TrendDirection::Stable
+            };
+            trends.push(FlakyTrend {
+                name,
+                failure_rates,
+                change,
+                direction,
+            });
+        }
+
+        // Worst regressions first
+        trends.sort_by(|a, b| {
+            b.change
+                .partial_cmp(&a.change)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        new_flaky.sort();
+
+        report.trends = Some(trends);
+        report.new_flaky_tests = Some(new_flaky);
+        Ok(report)
+    }
+
+    /// Analyze test history to detect flakiness.
+    fn analyze_test_history(
+        &self,
+        history: &HashMap<String, Vec<TestResult>>,
+    ) -> Result<FlakyReport> {
+        let mut flaky_tests = Vec::new();
+        let total_tests = history.len();
+
+        for (test_name, results) in history {
+            if results.len() < self.min_runs {
+                continue;
+            }
+
+            let failures = results
+                .iter()
+                .filter(|r| r.status == TestStatus::Failed)
+                .count();
+            let passes = results
+                .iter()
+                .filter(|r| r.status == TestStatus::Passed)
+                .count();
+            let total_runs = failures + passes;
+
+            if total_runs == 0 {
+                continue;
+            }
+
+            let failure_rate = failures as f64 / total_runs as f64;
+
+            // Detect flakiness: test has both passes and failures
+            if failures > 0 && passes > 0 && failure_rate < 0.9 {
+                let flakiness_score = self.calculate_flakiness_score(results);
+                let category = self.categorize_flakiness(results, failure_rate);
+
+                let recent_failures: Vec<String> = results
+                    .iter()
+                    .filter(|r| r.status == TestStatus::Failed)
+                    .filter_map(|r| r.error_message.clone())
+                    .take(3)
+                    .collect();
+
+                if flakiness_score >= self.flaky_threshold {
+                    flaky_tests.push(FlakyTest {
+                        name: test_name.clone(),
+                        flakiness_score,
+                        total_runs,
+                        failures,
+                        passes,
+                        failure_rate,
+                        recent_failures,
+                        category,
+                    });
+                }
+            }
+        }
+
+        // Sort by flakiness score descending
+        flaky_tests.sort_by(|a, b| {
+            b.flakiness_score
+                .partial_cmp(&a.flakiness_score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let flakiness_ratio = if total_tests > 0 {
+            flaky_tests.len() as f64 / total_tests as f64
+        } else {
+            0.0
+        };
+
+        let confidence = if history.values().all(|v| v.len() >= 20) {
+            "High".to_string()
+        } else if history.values().all(|v| v.len() >= 10) {
+            "Medium".to_string()
+        } else {
+            "Low".to_string()
+        };
+
+        Ok(FlakyReport {
+            total_tests,
+            flaky_tests,
+            flakiness_ratio,
+            confidence,
+            trends: None,
+            new_flaky_tests: None,
+        })
+    }
+
+    /// Calculate flakiness score (0.0 = stable, 1.0 = extremely flaky).
+    fn calculate_flakiness_score(&self, results: &[TestResult]) -> f64 {
+        if results.len() < 2 {
+            return 0.0;
+        }
+
+        // Count transitions between pass/fail states
+        let mut transitions = 0;
+        for window in results.windows(2) {
+            if window[0].status != window[1].status {
+                transitions += 1;
+            }
+        }
+
+        let transition_rate = transitions as f64 / (results.len() - 1) as f64;
+
+        // Calculate failure clustering (lower clustering = more flaky)
+        let failures = results
+            .iter()
+            .filter(|r| r.status == TestStatus::Failed)
+            .count();
+        let failure_rate = failures as f64 / results.len() as f64;
+
+        // Flakiness combines transition rate and failure rate
+        // Perfect flakiness: 50% failure rate with high transitions
+        let failure_variance = ((failure_rate - 0.5).abs() - 0.5).abs();
+
+        // Score: higher transitions + mid-range failure rate = higher flakiness
+        (transition_rate * 0.7 + failure_variance * 0.3).min(1.0)
+    }
+
+    /// Categorize the type of flakiness.
+    fn categorize_flakiness(&self, results: &[TestResult], failure_rate: f64) -> FlakyCategory {
+        // Check for timing patterns (failures with varying durations)
+        let durations: Vec<u64> = results.iter().map(|r| r.duration_ms).collect();
+        let avg_duration = durations.iter().sum::<u64>() as f64 / durations.len() as f64;
+        let duration_variance = durations
+            .iter()
+            .map(|d| (*d as f64 - avg_duration).powi(2))
+            .sum::<f64>()
+            / durations.len() as f64;
+        let duration_stddev = duration_variance.sqrt();
+
+        if duration_stddev > avg_duration * 0.5 {
+            return FlakyCategory::TimingDependent;
+        }
+
+        // Check for alternating pattern
+        let mut alternations = 0;
+        for window in results.windows(2) {
+            if window[0].status != window[1].status {
+                alternations += 1;
+            }
+        }
+        let alternation_rate = alternations as f64 / (results.len() - 1) as f64;
+
+        if alternation_rate > 0.6 {
+            return FlakyCategory::Unstable;
+        }
+
+        // Check for error message patterns (environment issues)
+        let error_messages: Vec<&str> = results
+            .iter()
+            .filter_map(|r| r.error_message.as_deref())
+            .collect();
+
+        if error_messages.iter().any(|msg| {
+            msg.contains("timeout") || msg.contains("connection") || msg.contains("network")
+        }) {
+            return FlakyCategory::EnvironmentSensitive;
+        }
+
+        if failure_rate < 0.5 {
+            FlakyCategory::Intermittent
+        } else {
+            FlakyCategory::Unstable
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_result(name: &str, status: TestStatus) -> TestResult {
+        TestResult {
+            name: name.to_string(),
+            status,
+            duration_ms: 100,
+            timestamp: 0,
+            error_message: None,
+        }
+    }
+
+    #[test]
+    fn test_flakiness_score_stable_test() {
+        let detector = FlakyDetector::new();
+        let results = vec![
+            create_test_result("test", TestStatus::Passed),
+            create_test_result("test", TestStatus::Passed),
+            create_test_result("test", TestStatus::Passed),
+        ];
+
+        let score = detector.calculate_flakiness_score(&results);
+        assert!(score < 0.3, "Stable test should have low flakiness score");
+    }
+
+    #[test]
+    fn test_flakiness_score_alternating() {
+        let detector = FlakyDetector::new();
+        let results = vec![
+            create_test_result("test", TestStatus::Passed),
+            create_test_result("test", TestStatus::Failed),
+            create_test_result("test", TestStatus::Passed),
+            create_test_result("test", TestStatus::Failed),
+            create_test_result("test", TestStatus::Passed),
+            create_test_result("test", TestStatus::Failed),
+        ];
+
+        let score = detector.calculate_flakiness_score(&results);
+        assert!(
+            score > 0.6,
+            "Alternating test should have high flakiness score"
+        );
+    }
+
+    #[test]
+    fn test_categorize_timing_dependent() {
+        let detector = FlakyDetector::new();
+        let mut results = vec![
+            create_test_result("test", TestStatus::Passed),
+            create_test_result("test", TestStatus::Passed),
+        ];
+        results[0].duration_ms = 100;
+        results[1].duration_ms = 1000;
+
+        let category = detector.categorize_flakiness(&results, 0.0);
+        assert_eq!(category, FlakyCategory::TimingDependent);
+    }
+
+    fn flaky(name: &str) -> FlakyTest {
+        FlakyTest {
+            name: name.to_string(),
+            flakiness_score: 0.8,
+            total_runs: 10,
+            failures: 5,
+            passes: 5,
+            failure_rate: 0.5,
+            recent_failures: vec![],
+            category: FlakyCategory::Unstable,
+        }
+    }
+
+    #[test]
+    fn test_quarantine_pytest_expression() {
+        let report = FlakyReport {
+            total_tests: 10,
+            flaky_tests: vec![
+                flaky("tests.test_api::test_login"),
+                flaky("tests.test_db::test_retry[postgres]"),
+                flaky("tests.test_db::test_retry[sqlite]"),
+            ],
+            flakiness_ratio: 0.3,
+            confidence: "Medium".to_string(),
+            trends: None,
+            new_flaky_tests: None,
+        };
+
+        assert_eq!(
+            quarantine_file(&report, QuarantineFormat::Pytest),
+            "-k 'not (test_login or test_retry)'\n"
+        );
+        assert_eq!(
+            quarantine_file(&report, QuarantineFormat::List)
+                .lines()
+                .count(),
+            3
+        );
+
+        let jest: serde_json::Value =
+            serde_json::from_str(&quarantine_file(&report, QuarantineFormat::Jest)).unwrap();
+        assert_eq!(
+            jest["testPathIgnorePatterns"],
+            serde_json::json!(["tests\\.test_api", "tests\\.test_db"])
+        );
+        assert_eq!(
+            QuarantineFormat::from_id("PyTest"),
+            Some(QuarantineFormat::Pytest)
+        );
+        assert_eq!(QuarantineFormat::from_id("junit"), None);
+    }
+
+    fn batch(timestamp: u64, runs: &[(&str, usize, usize)]) -> TestBatch {
+        let mut results = Vec::new();
+        for &(name, failures, passes) in runs {
+            for _ in 0..failures {
+                results.push(create_test_result(name, TestStatus::Failed));
+            }
+            for _ in 0..passes {
+                results.push(create_test_result(name, TestStatus::Passed));
+            }
+        }
+        TestBatch { timestamp, results }
+    }
+
+    #[test]
+    fn test_trend_flags_worsening_and_new_tests() {
+        let detector = FlakyDetector::with_config(2, 0.0);
+        let older = batch(100, &[("api::login", 1, 9), ("db::retry", 5, 5)]);
+        let newer = batch(
+            200,
+            &[
+                ("api::login", 6, 4),
+                ("db::retry", 1, 9),
+                ("ui::render", 3, 7),
+            ],
+        );
+
+        // Batches are ordered by timestamp, not argument order
+        let report = detector.analyze_batches(vec![newer, older]).unwrap();
+        let trends = report.trends.unwrap();
+
+        let login = trends.iter().find(|t| t.name == "api::login").unwrap();
+        assert_eq!(login.direction, TrendDirection::Worsening);
+        assert_eq!(login.failure_rates, vec![Some(0.1), Some(0.6)]);
+        assert!((login.change - 0.5).abs() < 1e-9);
+        assert_eq!(trends[0].name, "api::login");
+
+        let retry = trends.iter().find(|t| t.name == "db::retry").unwrap();
+        assert_eq!(retry.direction, TrendDirection::Improving);
+
+        let render = trends.iter().find(|t| t.name == "ui::render").unwrap();
+        assert_eq!(render.failure_rates, vec![None, Some(0.3)]);
+        assert_eq!(render.direction, TrendDirection::Stable);
+        assert_eq!(report.new_flaky_tests, Some(vec!["ui::render".to_string()]));
+    }
+}
@@ -1,6 +1,63 @@
 use crate::analyzer::report::format_duration;
+use crate::error::PipelineError;
 use crate::parser::dag::PipelineDag;
+use petgraph::graph::NodeIndex;
 use petgraph::Direction;
+use std::collections::{HashSet, VecDeque};
+
+/// Extract the neighborhood of `job_id`: its ancestors and descendants within
+/// `depth` dependency hops, plus the edges between them.
+///
+/// Ancestors and descendants are searched separately, so siblings that merely
+/// share a dependency are not included. The result renders with any of the
+/// graph formats.
+pub fn focus_subgraph(
+    dag: &PipelineDag,
+    job_id: &str,
+    depth: usize,
+) -> Result<PipelineDag, PipelineError> {
+    let start = *dag
+        .node_map
+        .get(job_id)
+        .ok_or_else(|| PipelineError::UnknownJob(job_id.to_string()))?;
+
+    let mut keep: HashSet<NodeIndex> = HashSet::from([start]);
+    for direction in [Direction::Incoming, Direction::Outgoing] {
+        let mut queue = VecDeque::from([(start, 0)]);
+        let mut seen = HashSet::from([start]);
+        while let Some((idx, hops)) = queue.pop_front() {
+            if hops == depth {
+                continue;
+            }
+            for next in dag.graph.neighbors_directed(idx, direction) {
+                if seen.insert(next) {
+                    keep.insert(next);
+                    queue.push_back((next, hops + 1));
+                }
+            }
+        }
+    }
+
+    let mut focused = PipelineDag::new(
+        dag.name.clone(),
+        dag.source_file.clone(),
+        dag.provider.clone(),
+    );
+    focused.triggers = dag.triggers.clone();
+    focused.env = dag.env.clone();
+    // Node order follows the original DAG so output stays stable.
+    for idx in dag.graph.node_indices().filter(|idx| keep.contains(idx)) {
+        focused.add_job(dag.graph[idx].clone());
+    }
+    for edge in dag.graph.edge_indices() {
+        let (from, to) = dag.graph.edge_endpoints(edge).unwrap();
+        if keep.contains(&from) && keep.contains(&to) {
+            focused.add_dependency(&dag.graph[from].id, &dag.graph[to].id)?;
+        }
+    }
+
+    Ok(focused)
+}
 
 /// Generate a Mermaid flowchart diagram from a Pipeline DAG.
 pub fn to_mermaid(dag: &PipelineDag) -> String {
@@ -61,8 +118,7 @@ pub fn to_dot(dag: &PipelineDag) -> String {
 
     let roots = dag.root_jobs();
     let leaves = dag.leaf_jobs();
-
-    for idx in dag.graph.node_indices() {
+    let node_line = |idx: NodeIndex, indent: &str| {
         let job = &dag.graph[idx];
         let duration = format_duration(job.estimated_duration_secs);
         let label = format!("{}\\n{}", job.name, duration);
@@ -76,10 +132,33 @@ pub fn to_dot(dag: &PipelineDag) -> String {
         };
 
         let font_color = "#ffffff";
-        lines.push(format!(
-            "    {} [label=\"{}\", fillcolor=\"{}\", fontcolor=\"{}\"];",
-            job.id, label, color, font_color
-        ));
+        format!(
+            "{}{} [label=\"{}\", fillcolor=\"{}\", fontcolor=\"{}\"];",
+            indent, job.id, label, color, font_color
+        )
+    };
+
+    let clusters = stage_clusters(dag);
+    let clustered: HashSet<NodeIndex> = clusters
+        .iter()
+        .flat_map(|(_, jobs)| jobs.iter().copied())
+        .collect();
+
+    for (i, (stage, jobs)) in clusters.iter().enumerate() {
+        // The index keeps ids unique when stages differ only in punctuation
+        lines.push(format!("    subgraph cluster_{}_{} {{", i, dot_id(stage)));
+        lines.push(format!("        label=\"{}\";", stage.replace('"', "\\\"")));
+        lines.push("        style=\"rounded,dashed\";".to_string());
+        lines.push("        color=\"#94a3b8\";".to_string());
+        for &idx in jobs {
+            lines.push(node_line(idx, "        "));
+        }
+        lines.push("    }".to_string());
+    }
+    for idx in dag.graph.node_indices() {
+        if !clustered.contains(&idx) {
+            lines.push(node_line(idx, "    "));
+        }
     }
 
     lines.push(String::new());
@@ -96,6 +175,40 @@ pub fn to_dot(dag: &PipelineDag) -> String {
     lines.join("\n")
 }
 
+/// Jobs grouped by their declared stage (GitLab `stage:`, Azure and AWS
+/// CodePipeline stages), ordered by the earliest topological level of each
+/// stage's jobs. Empty when the pipeline has no stages.
+fn stage_clusters(dag: &PipelineDag) -> Vec<(String, Vec<NodeIndex>)> {
+    let levels = crate::migration::compute_stage_indexes(dag);
+    let mut clusters: Vec<(String, Vec<NodeIndex>)> = Vec::new();
+    for idx in dag.graph.node_indices() {
+        let Some(stage) = dag.graph[idx].env.get("__stage") else {
+            continue;
+        };
+        match clusters.iter_mut().find(|(name, _)| name == stage) {
+            Some((_, jobs)) => jobs.push(idx),
+            None => clusters.push((stage.clone(), vec![idx])),
+        }
+    }
+
+    let level_of = |jobs: &[NodeIndex]| {
+        jobs.iter()
+            .map(|idx| levels.get(&dag.graph[*idx].id).copied().unwrap_or(0))
+            .min()
+            .unwrap_or(0)
+    };
+    // Stable, so stages on the same level keep their declaration order
+    clusters.sort_by_key(|(_, jobs)| level_of(jobs));
+    clusters
+}
+
+/// `name` reduced to the characters allowed in an unquoted DOT identifier.
+fn dot_id(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
 /// Generate an ASCII art representation of the Pipeline DAG.
 pub fn to_ascii(dag: &PipelineDag) -> String {
     let mut lines = Vec::new();
@@ -182,6 +295,52 @@ pub fn to_ascii(dag: &PipelineDag) -> String {
     lines.join("\n")
 }
 
+/// Plain-text table of the critical path: each job in order with its
+/// duration, the cumulative time and its share of the total.
+pub fn to_critical_path_text(dag: &PipelineDag) -> String {
+    let (path, total) = crate::analyzer::critical_path::find_critical_path(dag);
+    let mut lines = vec![
+        format!(
+            "Critical path: {} ({} of {} jobs)",
+            dag.name,
+            path.len(),
+            dag.job_count()
+        ),
+        String::new(),
+    ];
+    if path.is_empty() {
+        lines.push("No jobs.".to_string());
+        return lines.join("\n");
+    }
+
+    let width = path.iter().map(|j| j.id.len()).max().unwrap_or(0).max(3);
+    lines.push(format!(
+        "  {:>3}  {:<width$}  {:>9}  {:>10}  {:>6}",
+        "#", "Job", "Duration", "Cumulative", "Share"
+    ));
+    let mut cumulative = 0.0;
+    for (i, job) in path.iter().enumerate() {
+        cumulative += job.estimated_duration_secs;
+        let share = if total > 0.0 {
+            job.estimated_duration_secs / total * 100.0
+        } else {
+            0.0
+        };
+        lines.push(format!(
+            "  {:>3}  {:<width$}  {:>9}  {:>10}  {:>5.1}%",
+            i + 1,
+            job.id,
+            format_duration(job.estimated_duration_secs),
+            format_duration(cumulative),
+            share
+        ));
+    }
+    lines.push(String::new());
+    lines.push(format!("Total: {}", format_duration(total)));
+
+    lines.join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -232,4 +391,158 @@ jobs:
         assert!(dot.contains("digraph"));
         assert!(dot.contains("build -> deploy"));
     }
+
+    #[test]
+    fn test_dot_output_clusters_stages() {
+        let yaml = r#"
+stages: [build, test, deploy]
+compile:
+  stage: build
+  script: [make]
+unit:
+  stage: test
+  script: [make test]
+lint:
+  stage: test
+  script: [make lint]
+release:
+  stage: deploy
+  script: [make release]
+"#;
+        let dag = crate::parser::gitlab::GitLabCIParser::parse(yaml, ".gitlab-ci.yml".to_string())
+            .unwrap();
+        let dot = to_dot(&dag);
+
+        let cluster = |id: &str| {
+            let start = dot
+                .find(&format!("subgraph cluster_{} {{", id))
+                .unwrap_or_else(|| panic!("no cluster {}", id));
+            let end = start + dot[start..].find("\n    }").unwrap();
+            dot[start..end].to_string()
+        };
+        let build = cluster("0_build");
+        let test = cluster("1_test");
+        let deploy = cluster("2_deploy");
+        assert!(build.contains("label=\"build\"") && build.contains("compile [label="));
+        assert!(test.contains("unit [label=") && test.contains("lint [label="));
+        assert!(!test.contains("compile [label="));
+        assert!(deploy.contains("release [label="));
+        assert_eq!(dot.matches("subgraph cluster_").count(), 3);
+        assert!(dot.find("cluster_0_build").unwrap() < dot.find("cluster_2_deploy").unwrap());
+        assert!(dot.contains("compile -> unit"));
+
+        // Providers without stages keep the flat graph
+        let flat = GitHubActionsParser::parse(
+            "on: push\njobs:\n  a:\n    runs-on: ubuntu-latest\n    steps:\n      - run: make\n",
+            "ci.yml".to_string(),
+        )
+        .unwrap();
+        assert!(!to_dot(&flat).contains("subgraph"));
+    }
+
+    #[test]
+    fn test_dot_cluster_ids_stay_unique() {
+        let yaml = r#"
+stages: [build-x, build_x]
+a:
+  stage: build-x
+  script: [make a]
+b:
+  stage: build_x
+  script: [make b]
+"#;
+        let dag = crate::parser::gitlab::GitLabCIParser::parse(yaml, ".gitlab-ci.yml".to_string())
+            .unwrap();
+        let dot = to_dot(&dag);
+
+        assert!(dot.contains("subgraph cluster_0_build_x {"));
+        assert!(dot.contains("subgraph cluster_1_build_x {"));
+        assert!(dot.contains("label=\"build-x\";") && dot.contains("label=\"build_x\";"));
+    }
+
+    #[test]
+    fn test_focus_subgraph_limits_depth() {
+        // a -> b -> c -> d -> e, plus an unrelated branch x -> y and a sibling b -> s
+        let yaml = r#"
+name: CI
+on: push
+jobs:
+  a: { runs-on: ubuntu-latest, steps: [{ run: echo a }] }
+  b: { needs: a, runs-on: ubuntu-latest, steps: [{ run: echo b }] }
+  c: { needs: b, runs-on: ubuntu-latest, steps: [{ run: echo c }] }
+  d: { needs: c, runs-on: ubuntu-latest, steps: [{ run: echo d }] }
+  e: { needs: d, runs-on: ubuntu-latest, steps: [{ run: echo e }] }
+  s: { needs: b, runs-on: ubuntu-latest, steps: [{ run: echo s }] }
+  x: { runs-on: ubuntu-latest, steps: [{ run: echo x }] }
+  y: { needs: x, runs-on: ubuntu-latest, steps: [{ run: echo y }] }
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+
+        let focused = focus_subgraph(&dag, "c", 1).unwrap();
+        let mut ids = focused.job_ids();
+        ids.sort();
+        assert_eq!(ids, vec!["b", "c", "d"]);
+        assert_eq!(focused.graph.edge_count(), 2);
+
+        let mermaid = to_mermaid(&focused);
+        assert!(mermaid.contains("b --> c"));
+        assert!(mermaid.contains("c --> d"));
+        assert!(!mermaid.contains("a --> b"));
+
+        let mut wider = focus_subgraph(&dag, "c", 2).unwrap().job_ids();
+        wider.sort();
+        assert_eq!(wider, vec!["a", "b", "c", "d", "e"]);
+
+        assert!(matches!(
+            focus_subgraph(&dag, "missing", 1),
+            Err(PipelineError::UnknownJob(_))
+        ));
+    }
+
+    #[test]
+    fn test_critical_path_text_lists_jobs_in_order() {
+        let yaml = r#"
+name: CI
+on: push
+jobs:
+  lint:
+    runs-on: ubuntu-latest
+    steps: [{ run: npm run lint }]
+  build:
+    runs-on: ubuntu-latest
+    steps: [{ run: npm run build }]
+  test:
+    needs: build
+    runs-on: ubuntu-latest
+    steps: [{ run: npm test }]
+  deploy:
+    needs: [lint, test]
+    runs-on: ubuntu-latest
+    steps: [{ run: ./deploy.sh }]
+"#;
+        let mut dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        for job in dag.graph.node_weights_mut() {
+            job.estimated_duration_secs = match job.id.as_str() {
+                "build" => 300.0,
+                "test" => 240.0,
+                "deploy" => 60.0,
+                _ => 30.0,
+            };
+        }
+
+        let text = to_critical_path_text(&dag);
+        let rows: Vec<Vec<&str>> = text
+            .lines()
+            .map(str::split_whitespace)
+            .map(Iterator::collect)
+            .filter(|cols: &Vec<&str>| cols.first().is_some_and(|c| c.parse::<usize>().is_ok()))
+            .collect();
+        let jobs: Vec<&str> = rows.iter().map(|r| r[1]).collect();
+        assert_eq!(jobs, vec!["build", "test", "deploy"]);
+        assert_eq!(rows[0][2], "5:00");
+        assert_eq!(rows[1][3], "9:00");
+        assert_eq!(rows[2][3], "10:00");
+        assert!(text.contains("Critical path: CI (3 of 4 jobs)"));
+        assert!(text.ends_with("Total: 10:00"));
+    }
 }
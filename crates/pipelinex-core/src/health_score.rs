@@ -1,4 +1,6 @@
+use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 
 /// Pipeline health score calculator
 ///
@@ -11,17 +13,29 @@ use serde::{Deserialize, Serialize};
 /// - Flaky test count
 #[derive(Debug, Clone)]
 pub struct HealthScoreCalculator {
-    weights: HealthScoreWeights,
+    weights: HealthWeights,
 }
 
-/// Configurable weights for health score components
-#[derive(Debug, Clone)]
-pub struct HealthScoreWeights {
+/// Configurable weights for health score components.
+///
+/// Loaded from the `[health]` table of `.pipelinex/config.toml`; omitted keys
+/// keep their defaults, which reproduce the built-in scoring.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HealthWeights {
     pub duration_efficiency: f64,
     pub success_rate: f64,
+    /// Weight of the parallelism factor (max parallel jobs / total jobs).
     pub parallelization: f64,
+    /// Weight of the caching component, i.e. the bonus for having caches.
     pub caching: f64,
     pub issue_severity: f64,
+    /// Points deducted from the issue component per critical finding.
+    pub critical_penalty: f64,
+    /// Points deducted per high-severity finding.
+    pub high_penalty: f64,
+    /// Points deducted per medium-severity finding.
+    pub medium_penalty: f64,
 }
 
 /// Health score result with detailed breakdown
@@ -42,6 +56,10 @@ pub struct HealthScore {
 
     /// Recommendations for improvement
     pub recommendations: Vec<String>,
+
+    /// Weight profile used, present when it differs from the defaults
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weights: Option<HealthWeights>,
 }
 
 /// Health grade categories
@@ -54,7 +72,7 @@ pub enum HealthGrade {
     Critical,  // 0-39
 }
 
-impl Default for HealthScoreWeights {
+impl Default for HealthWeights {
     fn default() -> Self {
         Self {
             duration_efficiency: 0.25,
@@ -62,19 +80,59 @@ impl Default for HealthScoreWeights {
             parallelization: 0.20,
             caching: 0.15,
             issue_severity: 0.10,
+            critical_penalty: 15.0,
+            high_penalty: 8.0,
+            medium_penalty: 3.0,
         }
     }
 }
 
-impl HealthScoreCalculator {
-    pub fn new() -> Self {
-        Self {
-            weights: HealthScoreWeights::default(),
+#[derive(Debug, Deserialize)]
+struct ConfigFile {
+    health: Option<HealthWeights>,
+}
+
+/// Load the `[health]` weight profile from a PipelineX config file.
+///
+/// Returns `Ok(None)` when the file has no `[health]` table.
+pub fn load_health_weights(path: &Path) -> Result<Option<HealthWeights>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+    let config: ConfigFile = toml::from_str(&content)
+        .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+
+    if let Some(weights) = &config.health {
+        let values = [
+            ("duration_efficiency", weights.duration_efficiency),
+            ("success_rate", weights.success_rate),
+            ("parallelization", weights.parallelization),
+            ("caching", weights.caching),
+            ("issue_severity", weights.issue_severity),
+            ("critical_penalty", weights.critical_penalty),
+            ("high_penalty", weights.high_penalty),
+            ("medium_penalty", weights.medium_penalty),
+        ];
+        for (key, value) in values {
+            if !value.is_finite() || value < 0.0 {
+                anyhow::bail!(
+                    "Invalid [health] weight '{}' = {} in {}: must be a non-negative number",
+                    key,
+                    value,
+                    path.display()
+                );
+            }
         }
     }
 
-    pub fn with_weights(weights: HealthScoreWeights) -> Self {
-        Self { weights }
+    Ok(config.health)
+}
+
+impl HealthScoreCalculator {
+    /// Create a calculator, using the default weights when `weights` is `None`.
+    pub fn new(weights: Option<HealthWeights>) -> Self {
+        Self {
+            weights: weights.unwrap_or_default(),
+        }
     }
 
     /// Calculate health score from pipeline metrics
@@ -112,9 +170,9 @@ impl HealthScoreCalculator {
 
         // Issue score (0-100) - deduct points for issues
         let issue_score = 100.0
-            - (critical_issues as f64 * 15.0)
-            - (high_issues as f64 * 8.0)
-            - (medium_issues as f64 * 3.0);
+            - (critical_issues as f64 * self.weights.critical_penalty)
+            - (high_issues as f64 * self.weights.high_penalty)
+            - (medium_issues as f64 * self.weights.medium_penalty);
         let issue_score = issue_score.max(0.0);
 
         // Calculate weighted total
@@ -144,6 +202,7 @@ impl HealthScoreCalculator {
             issue_score,
             grade,
             recommendations,
+            weights: (self.weights != HealthWeights::default()).then(|| self.weights.clone()),
         }
     }
 
@@ -233,7 +292,7 @@ impl HealthScoreCalculator {
 
 impl Default for HealthScoreCalculator {
     fn default() -> Self {
-        Self::new()
+        Self::new(None)
     }
 }
 
@@ -277,7 +336,7 @@ mod tests {
 
     #[test]
     fn test_perfect_score() {
-        let calculator = HealthScoreCalculator::new();
+        let calculator = HealthScoreCalculator::new(None);
         let score = calculator.calculate(
             300.0, // duration
             300.0, // optimal
@@ -295,7 +354,7 @@ mod tests {
 
     #[test]
     fn test_poor_score() {
-        let calculator = HealthScoreCalculator::new();
+        let calculator = HealthScoreCalculator::new(None);
         let score = calculator.calculate(
             1800.0, // 30 min duration
             300.0,  // 5 min optimal
@@ -315,6 +374,45 @@ mod tests {
         assert!(!score.recommendations.is_empty());
     }
 
+    #[test]
+    fn test_higher_critical_penalty_lowers_score() {
+        let default_score =
+            HealthScoreCalculator::new(None).calculate(600.0, 400.0, 0.95, 0.5, true, 2, 0, 0);
+        let strict = HealthWeights {
+            critical_penalty: 40.0,
+            ..HealthWeights::default()
+        };
+        let strict_score = HealthScoreCalculator::new(Some(strict.clone()))
+            .calculate(600.0, 400.0, 0.95, 0.5, true, 2, 0, 0);
+
+        assert!(strict_score.total_score < default_score.total_score);
+        assert!(strict_score.issue_score < default_score.issue_score);
+        assert_eq!(strict_score.weights, Some(strict));
+        assert!(default_score.weights.is_none());
+    }
+
+    #[test]
+    fn test_load_health_weights_from_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(
+            &path,
+            "[general]\nprovider = \"github-actions\"\n\n[health]\ncritical_penalty = 25.0\ncaching = 0.05\n",
+        )
+        .unwrap();
+
+        let weights = load_health_weights(&path).unwrap().unwrap();
+        assert_eq!(weights.critical_penalty, 25.0);
+        assert_eq!(weights.caching, 0.05);
+        assert_eq!(weights.high_penalty, HealthWeights::default().high_penalty);
+
+        std::fs::write(&path, "[general]\nprovider = \"gitlab-ci\"\n").unwrap();
+        assert!(load_health_weights(&path).unwrap().is_none());
+
+        std::fs::write(&path, "[health]\nhigh_penalty = -1.0\n").unwrap();
+        assert!(load_health_weights(&path).is_err());
+    }
+
     #[test]
     fn test_grade_assignment() {
         assert_eq!(
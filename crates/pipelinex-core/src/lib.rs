@@ -1,12 +1,18 @@
 pub mod analyzer;
+pub mod annotations;
 pub mod badge;
+pub mod blame;
 pub mod cost;
 pub mod discovery;
+pub mod duration_model;
+pub mod error;
+pub mod estimation;
 pub mod explainer;
 pub mod flaky_detector;
 pub mod graph;
 pub mod health_score;
 pub mod linter;
+pub mod live_report;
 pub mod mcp;
 pub mod migration;
 pub mod multi_repo;
@@ -14,6 +20,7 @@ pub mod optimizer;
 pub mod parser;
 pub mod plugins;
 pub mod policy;
+pub mod process;
 pub mod providers;
 pub mod redact;
 pub mod runner_sizing;
@@ -21,10 +28,15 @@ pub mod sbom;
 pub mod security;
 pub mod signing;
 pub mod simulator;
+pub mod summary;
+pub mod synthetic;
 pub mod test_selector;
+pub mod timing;
+pub mod watch;
 pub mod whatif;
 
 pub use analyzer::report::{AnalysisReport, Finding, Severity};
+pub use error::PipelineError;
 pub use flaky_detector::{FlakyCategory, FlakyDetector, FlakyReport, FlakyTest};
 pub use linter::{lint, LintReport};
 pub use migration::{github_actions_to_gitlab_ci, MigrationResult};
@@ -49,5 +61,8 @@ pub use policy::{check_policy, load_policy, PolicyConfig, PolicyReport};
 pub use runner_sizing::{profile_pipeline as profile_runner_sizing, RunnerSizingReport};
 pub use sbom::generate_sbom;
 pub use security::scan as security_scan;
-pub use signing::{generate_keypair, sign_report, verify_report};
+pub use signing::{
+    generate_keypair, load_keyring, sign_report, verify_report, verify_report_file,
+    verify_with_keyring, VerificationOutcome,
+};
 pub use test_selector::{TestSelection, TestSelector, TestSelectorConfig};
@@ -1,4 +1,4 @@
-use super::{LintFinding, LintSeverity};
+use super::{LintFinding, LintFix, LintSeverity};
 use crate::parser::dag::PipelineDag;
 
 struct DeprecationRule {
@@ -6,68 +6,92 @@ struct DeprecationRule {
     message: &'static str,
     suggestion: &'static str,
     severity: LintSeverity,
+    /// Drop-in replacement for `pattern`, if upgrading needs no other changes.
+    fix: Option<&'static str>,
 }
 
+/// Retired GitHub-hosted runner images and their replacements.
+const RETIRED_RUNNERS: &[(&str, &str)] = &[
+    ("ubuntu-18.04", "ubuntu-latest"),
+    ("ubuntu-20.04", "ubuntu-latest"),
+    ("windows-2019", "windows-latest"),
+    ("macos-10.15", "macos-latest"),
+    ("macos-11", "macos-latest"),
+    ("macos-12", "macos-latest"),
+];
+
 const GITHUB_DEPRECATIONS: &[DeprecationRule] = &[
     DeprecationRule {
         pattern: "actions/checkout@v2",
         message: "actions/checkout@v2 is deprecated",
         suggestion: "Upgrade to actions/checkout@v4",
         severity: LintSeverity::Warning,
+        fix: Some("actions/checkout@v4"),
     },
     DeprecationRule {
         pattern: "actions/checkout@v3",
         message: "actions/checkout@v3 is outdated",
         suggestion: "Upgrade to actions/checkout@v4",
         severity: LintSeverity::Info,
+        fix: Some("actions/checkout@v4"),
     },
     DeprecationRule {
         pattern: "actions/setup-node@v2",
         message: "actions/setup-node@v2 is deprecated",
         suggestion: "Upgrade to actions/setup-node@v4",
         severity: LintSeverity::Warning,
+        fix: Some("actions/setup-node@v4"),
     },
     DeprecationRule {
         pattern: "actions/setup-node@v3",
         message: "actions/setup-node@v3 is outdated",
         suggestion: "Upgrade to actions/setup-node@v4",
         severity: LintSeverity::Info,
+        fix: Some("actions/setup-node@v4"),
     },
     DeprecationRule {
         pattern: "actions/setup-python@v2",
         message: "actions/setup-python@v2 is deprecated",
         suggestion: "Upgrade to actions/setup-python@v5",
         severity: LintSeverity::Warning,
+        fix: Some("actions/setup-python@v5"),
     },
+    // Artifact actions v4 changed upload/download semantics, so these upgrades
+    // are not auto-fixable.
     DeprecationRule {
         pattern: "actions/upload-artifact@v2",
         message: "actions/upload-artifact@v2 is deprecated and uses Node 12",
         suggestion: "Upgrade to actions/upload-artifact@v4",
         severity: LintSeverity::Warning,
+        fix: None,
     },
     DeprecationRule {
         pattern: "actions/upload-artifact@v3",
         message: "actions/upload-artifact@v3 is outdated",
         suggestion: "Upgrade to actions/upload-artifact@v4",
         severity: LintSeverity::Info,
+        fix: None,
     },
     DeprecationRule {
         pattern: "actions/download-artifact@v2",
         message: "actions/download-artifact@v2 is deprecated",
         suggestion: "Upgrade to actions/download-artifact@v4",
         severity: LintSeverity::Warning,
+        fix: None,
     },
     DeprecationRule {
         pattern: "actions/download-artifact@v3",
         message: "actions/download-artifact@v3 is outdated",
         suggestion: "Upgrade to actions/download-artifact@v4",
         severity: LintSeverity::Info,
+        fix: None,
     },
     DeprecationRule {
         pattern: "actions/cache@v2",
         message: "actions/cache@v2 is deprecated",
         suggestion: "Upgrade to actions/cache@v4",
         severity: LintSeverity::Warning,
+        fix: Some("actions/cache@v4"),
     },
 ];
 
@@ -77,12 +101,14 @@ const GITLAB_DEPRECATIONS: &[DeprecationRule] = &[
         message: "The 'only' keyword is deprecated in GitLab CI",
         suggestion: "Use 'rules:' syntax instead",
         severity: LintSeverity::Warning,
+        fix: None,
     },
     DeprecationRule {
         pattern: "except:",
         message: "The 'except' keyword is deprecated in GitLab CI",
         suggestion: "Use 'rules:' syntax instead",
         severity: LintSeverity::Warning,
+        fix: None,
     },
 ];
 
@@ -110,12 +136,42 @@ pub fn check_deprecations(dag: &PipelineDag) -> Vec<LintFinding> {
                             ),
                             suggestion: Some(rule.suggestion.to_string()),
                             location: Some(format!("jobs.{}.steps", node.id)),
+                            fixable: rule.fix.is_some(),
+                            fix: rule.fix.map(|replace| LintFix {
+                                find: rule.pattern.to_string(),
+                                replace: replace.to_string(),
+                                key: None,
+                            }),
                         });
                     }
                 }
             }
         }
 
+        if dag.provider == "github-actions" {
+            if let Some((retired, replacement)) = RETIRED_RUNNERS
+                .iter()
+                .find(|(retired, _)| node.runs_on == *retired)
+            {
+                findings.push(LintFinding {
+                    severity: LintSeverity::Warning,
+                    rule_id: "PLX-LINT-RUNNER-RETIRED".to_string(),
+                    message: format!(
+                        "Job '{}' uses retired runner image '{}'; jobs will not be scheduled",
+                        node.id, retired
+                    ),
+                    suggestion: Some(format!("Switch to '{}'", replacement)),
+                    location: Some(format!("jobs.{}.runs-on", node.id)),
+                    fixable: true,
+                    fix: Some(LintFix {
+                        find: retired.to_string(),
+                        replace: replacement.to_string(),
+                        key: Some("runs-on".to_string()),
+                    }),
+                });
+            }
+        }
+
         // Check runner deprecation: suggest pinned version instead of -latest
         if dag.provider == "github-actions" && node.runs_on.ends_with("-latest") {
             findings.push(LintFinding {
@@ -130,6 +186,8 @@ pub fn check_deprecations(dag: &PipelineDag) -> Vec<LintFinding> {
                     node.runs_on.replace("-latest", "-24.04")
                 )),
                 location: Some(format!("jobs.{}.runs-on", node.id)),
+                fixable: false,
+                fix: None,
             });
         }
     }
@@ -141,6 +199,7 @@ pub fn check_deprecations(dag: &PipelineDag) -> Vec<LintFinding> {
 mod tests {
     use super::*;
     use crate::parser::dag::{JobNode, PipelineDag, StepInfo};
+    use std::collections::HashMap;
 
     #[test]
     fn test_detect_deprecated_checkout_v2() {
@@ -151,6 +210,9 @@ mod tests {
             uses: Some("actions/checkout@v2".into()),
             run: None,
             estimated_duration_secs: None,
+            with: HashMap::new(),
+            working_directory: None,
+            env: HashMap::new(),
         });
         dag.add_job(job);
 
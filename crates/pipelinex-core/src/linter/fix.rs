@@ -0,0 +1,200 @@
+//! Applying safe lint fixes (`lint --fix`).
+
+use super::{LintFinding, LintFix, LintReport};
+use regex::{Captures, Regex};
+
+/// Result of applying fixes to a file's content.
+#[derive(Debug, Clone)]
+pub struct FixOutcome {
+    /// Content with every fix applied.
+    pub content: String,
+    /// Fixes that changed the content, with the number of occurrences replaced.
+    pub applied: Vec<(LintFix, usize)>,
+    /// Findings that need manual attention.
+    pub manual: Vec<LintFinding>,
+}
+
+impl FixOutcome {
+    pub fn changed(&self) -> bool {
+        !self.applied.is_empty()
+    }
+}
+
+/// Apply the substitutions of all fixable findings to `content`.
+///
+/// Only whole tokens are replaced (`actions/checkout@v2` also covers
+/// `@v2.3.4`, but not `@v20`), fixes scoped to a key only touch that key's
+/// values, and everything else in the file is left byte-for-byte unchanged.
+pub fn apply_fixes(content: &str, report: &LintReport) -> FixOutcome {
+    let mut fixes: Vec<&LintFix> = Vec::new();
+    let mut manual = Vec::new();
+    for finding in &report.findings {
+        match &finding.fix {
+            Some(fix) if finding.fixable => {
+                if !fixes.contains(&fix) {
+                    fixes.push(fix);
+                }
+            }
+            _ => manual.push(finding.clone()),
+        }
+    }
+
+    let mut content = content.to_string();
+    let mut applied = Vec::new();
+    for fix in fixes {
+        let (fixed, count) = replace_token(&content, fix);
+        if count > 0 {
+            content = fixed;
+            applied.push((fix.clone(), count));
+        }
+    }
+
+    FixOutcome {
+        content,
+        applied,
+        manual,
+    }
+}
+
+fn replace_token(content: &str, fix: &LintFix) -> (String, usize) {
+    const BOUNDARY: &str = r"[^\w./@-]";
+    let pattern = format!(
+        r"(^|{b}){token}(?:\.\d+)*($|{b})",
+        b = BOUNDARY,
+        token = regex::escape(&fix.find)
+    );
+    let re = Regex::new(&format!("(?m){}", pattern)).expect("escaped token is a valid regex");
+
+    let mut count = 0;
+    let mut replace = |text: &str| {
+        re.replace_all(text, |caps: &Captures| {
+            count += 1;
+            format!("{}{}{}", &caps[1], fix.replace, &caps[2])
+        })
+        .into_owned()
+    };
+
+    let Some(key) = &fix.key else {
+        let replaced = replace(content);
+        return (replaced, count);
+    };
+
+    let replaced = content
+        .split_inclusive('\n')
+        .map(|line| match key_value_start(line, key) {
+            Some(start) => {
+                // Leave a trailing comment alone.
+                let end = line[start..]
+                    .find(" #")
+                    .map_or(line.len(), |offset| start + offset);
+                format!(
+                    "{}{}{}",
+                    &line[..start],
+                    replace(&line[start..end]),
+                    &line[end..]
+                )
+            }
+            None => line.to_string(),
+        })
+        .collect();
+    (replaced, count)
+}
+
+/// Byte offset of the value in a `key:` (or `- key:`) line.
+fn key_value_start(line: &str, key: &str) -> Option<usize> {
+    let trimmed = line.trim_start();
+    let trimmed = trimmed.strip_prefix("- ").unwrap_or(trimmed).trim_start();
+    let value = trimmed.strip_prefix(key)?.strip_prefix(':')?;
+    Some(line.len() - value.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linter::{lint, LintSeverity};
+    use crate::parser::github::GitHubActionsParser;
+
+    #[test]
+    fn test_fix_rewrites_deprecated_action_only() {
+        // Missing `on:` is a schema error that must be left alone.
+        let yaml = r#"name: CI
+jobs:
+  build:
+    runs-on: ubuntu-18.04
+    steps:
+      - uses: actions/checkout@v2   # keep this comment
+      - uses: "actions/checkout@v2.3.4"
+      - uses: actions/upload-artifact@v3
+        with:
+          name: dist
+      - run: echo actions/checkout@v20
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let report = lint(yaml, &dag);
+        assert!(report
+            .findings
+            .iter()
+            .any(|f| f.rule_id == "PLX-LINT-SCHEMA-001" && !f.fixable));
+
+        let outcome = apply_fixes(yaml, &report);
+        assert!(outcome.changed());
+        assert!(outcome
+            .content
+            .contains("- uses: actions/checkout@v4   # keep this comment"));
+        assert!(outcome.content.contains(r#"- uses: "actions/checkout@v4""#));
+        assert!(outcome.content.contains("runs-on: ubuntu-latest"));
+        assert!(outcome.content.contains("actions/upload-artifact@v3"));
+        assert!(outcome.content.contains("echo actions/checkout@v20"));
+        assert!(!outcome.content.lines().any(|l| l.starts_with("on:")));
+
+        let checkout = outcome
+            .applied
+            .iter()
+            .find(|(fix, _)| fix.find == "actions/checkout@v2")
+            .unwrap();
+        assert_eq!(checkout.1, 2);
+        assert!(outcome
+            .manual
+            .iter()
+            .any(|f| f.rule_id == "PLX-LINT-SCHEMA-001" && f.severity == LintSeverity::Error));
+        assert!(outcome
+            .manual
+            .iter()
+            .any(|f| f.message.contains("upload-artifact@v3")));
+    }
+
+    #[test]
+    fn test_runner_fix_only_rewrites_runs_on() {
+        let yaml = r#"on: push
+# Moved off ubuntu-18.04 in the build job only.
+jobs:
+  build:
+    runs-on: ubuntu-18.04 # was ubuntu-18.04
+    steps:
+      - uses: actions/checkout@v4
+      - run: echo "built on ubuntu-18.04"
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let outcome = apply_fixes(yaml, &lint(yaml, &dag));
+        let runner = outcome
+            .applied
+            .iter()
+            .find(|(fix, _)| fix.find == "ubuntu-18.04")
+            .unwrap();
+        assert_eq!(runner.1, 1);
+        assert!(outcome
+            .content
+            .contains("runs-on: ubuntu-latest # was ubuntu-18.04"));
+        assert!(outcome.content.contains("# Moved off ubuntu-18.04"));
+        assert!(outcome.content.contains(r#"echo "built on ubuntu-18.04""#));
+    }
+
+    #[test]
+    fn test_no_fixable_findings_leaves_content() {
+        let yaml = "on: push\njobs:\n  build:\n    runs-on: ubuntu-24.04\n    steps:\n      - uses: actions/checkout@v4\n";
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let outcome = apply_fixes(yaml, &lint(yaml, &dag));
+        assert!(!outcome.changed());
+        assert_eq!(outcome.content, yaml);
+    }
+}
@@ -1,4 +1,6 @@
 pub mod deprecation;
+pub mod fix;
+pub mod outputs;
 pub mod schema;
 pub mod typo;
 
@@ -31,6 +33,23 @@ pub struct LintFinding {
     pub message: String,
     pub suggestion: Option<String>,
     pub location: Option<String>,
+    /// Whether `lint --fix` can correct this finding safely.
+    #[serde(default)]
+    pub fixable: bool,
+    /// Text substitution applied by `lint --fix`; set only when `fixable`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fix: Option<LintFix>,
+}
+
+/// A mechanical, formatting-preserving substitution for a lint finding.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintFix {
+    /// Token to replace, e.g. `actions/checkout@v2` or `ubuntu-18.04`.
+    pub find: String,
+    pub replace: String,
+    /// Restrict the substitution to values of this YAML key, e.g. `runs-on`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
 }
 
 /// Complete lint report.
@@ -68,6 +87,9 @@ pub fn lint(content: &str, dag: &PipelineDag) -> LintReport {
     // Typo detection on raw YAML content
     findings.extend(typo::check_typos(content, &dag.provider));
 
+    // `needs.<job>.outputs` references
+    findings.extend(outputs::check_output_references(dag));
+
     // Schema validation
     findings.extend(schema::validate_schema(content, &dag.provider));
 
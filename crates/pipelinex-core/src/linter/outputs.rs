@@ -0,0 +1,118 @@
+use super::{LintFinding, LintSeverity};
+use crate::parser::dag::PipelineDag;
+
+/// Check `needs.<job>.…` expressions against each job's `needs` list.
+///
+/// GitHub Actions only exposes the context of jobs listed in `needs`; a
+/// reference to any other job silently evaluates to an empty string. A
+/// reference to an output the upstream job never declares is empty too.
+pub fn check_output_references(dag: &PipelineDag) -> Vec<LintFinding> {
+    let mut findings = Vec::new();
+    if dag.provider != "github-actions" {
+        return findings;
+    }
+
+    for node in dag.graph.node_weights() {
+        for referenced in &node.data_flow.needs_refs {
+            if node.needs.contains(referenced) {
+                continue;
+            }
+            let expression = node
+                .data_flow
+                .output_refs
+                .iter()
+                .find(|r| &r.job == referenced)
+                .map(|r| format!("needs.{}.outputs.{}", r.job, r.output))
+                .unwrap_or_else(|| format!("needs.{}", referenced));
+            findings.push(LintFinding {
+                severity: LintSeverity::Error,
+                rule_id: "PLX-LINT-NEEDS-REF".to_string(),
+                message: format!(
+                    "Job '{}' reads `{}` but does not list '{}' in needs; the expression is always empty",
+                    node.id, expression, referenced
+                ),
+                suggestion: Some(format!("Add '{}' to jobs.{}.needs", referenced, node.id)),
+                location: Some(format!("jobs.{}", node.id)),
+                fixable: false,
+                fix: None,
+            });
+        }
+
+        for reference in &node.data_flow.output_refs {
+            if !node.needs.contains(&reference.job) {
+                continue;
+            }
+            let Some(upstream) = dag.get_job(&reference.job) else {
+                continue;
+            };
+            if upstream.data_flow.outputs.contains(&reference.output) {
+                continue;
+            }
+            findings.push(LintFinding {
+                severity: LintSeverity::Warning,
+                rule_id: "PLX-LINT-OUTPUT-UNDECLARED".to_string(),
+                message: format!(
+                    "Job '{}' reads output '{}' which job '{}' does not declare",
+                    node.id, reference.output, reference.job
+                ),
+                suggestion: Some(format!(
+                    "Declare '{}' under jobs.{}.outputs or fix the name",
+                    reference.output, reference.job
+                )),
+                location: Some(format!("jobs.{}", node.id)),
+                fixable: false,
+                fix: None,
+            });
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::github::GitHubActionsParser;
+
+    #[test]
+    fn test_reference_to_job_not_in_needs_is_flagged() {
+        let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    outputs:
+      version: ${{ steps.v.outputs.version }}
+    steps:
+      - id: v
+        run: echo "version=1.0" >> "$GITHUB_OUTPUT"
+  test:
+    runs-on: ubuntu-latest
+    needs: build
+    steps:
+      - run: echo ${{ needs.build.outputs.version }} ${{ needs.build.outputs.sha }}
+  deploy:
+    runs-on: ubuntu-latest
+    needs: test
+    steps:
+      - run: ./deploy.sh ${{ needs.build.outputs.version }}
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let findings = check_output_references(&dag);
+        assert_eq!(findings.len(), 2);
+
+        let missing = findings
+            .iter()
+            .find(|f| f.rule_id == "PLX-LINT-NEEDS-REF")
+            .unwrap();
+        assert_eq!(missing.severity, LintSeverity::Error);
+        assert!(missing.message.contains("Job 'deploy'"));
+        assert!(missing.message.contains("needs.build.outputs.version"));
+
+        let undeclared = findings
+            .iter()
+            .find(|f| f.rule_id == "PLX-LINT-OUTPUT-UNDECLARED")
+            .unwrap();
+        assert!(undeclared.message.contains("output 'sha'"));
+    }
+}
@@ -1,4 +1,5 @@
 use super::{LintFinding, LintSeverity};
+use crate::parser::json5;
 
 /// Basic schema validation for CI configs.
 pub fn validate_schema(content: &str, provider: &str) -> Vec<LintFinding> {
@@ -7,12 +8,32 @@ pub fn validate_schema(content: &str, provider: &str) -> Vec<LintFinding> {
     match provider {
         "github-actions" => findings.extend(validate_github_actions(content)),
         "gitlab-ci" => findings.extend(validate_gitlab_ci(content)),
+        "aws-codepipeline" | "argo-workflows" => findings.extend(validate_strict_json(content)),
         _ => {}
     }
 
     findings
 }
 
+/// JSON inputs that only parsed after stripping comments or trailing commas.
+fn validate_strict_json(content: &str) -> Option<LintFinding> {
+    if !json5::looks_like_json(content) || json5::is_strict_json(content) {
+        return None;
+    }
+    Some(LintFinding {
+        severity: LintSeverity::Warning,
+        rule_id: "PLX-LINT-JSON5".to_string(),
+        message: "File is not strict JSON (comments or trailing commas); it was parsed leniently"
+            .to_string(),
+        suggestion: Some(
+            "Remove comments and trailing commas so other JSON tools accept the file".to_string(),
+        ),
+        location: None,
+        fixable: false,
+        fix: None,
+    })
+}
+
 fn validate_github_actions(content: &str) -> Vec<LintFinding> {
     let mut findings = Vec::new();
 
@@ -27,6 +48,8 @@ fn validate_github_actions(content: &str) -> Vec<LintFinding> {
                 message: format!("Invalid YAML: {}", e),
                 suggestion: None,
                 location: None,
+                fixable: false,
+                fix: None,
             });
             return findings;
         }
@@ -45,6 +68,8 @@ fn validate_github_actions(content: &str) -> Vec<LintFinding> {
             message: "Missing required 'on' trigger block".to_string(),
             suggestion: Some("Add 'on:' with push/pull_request triggers".to_string()),
             location: Some("top-level".to_string()),
+            fixable: false,
+            fix: None,
         });
     }
 
@@ -56,6 +81,8 @@ fn validate_github_actions(content: &str) -> Vec<LintFinding> {
             message: "Missing required 'jobs' block".to_string(),
             suggestion: Some("Add 'jobs:' block with at least one job".to_string()),
             location: Some("top-level".to_string()),
+            fixable: false,
+            fix: None,
         });
     }
 
@@ -73,6 +100,8 @@ fn validate_github_actions(content: &str) -> Vec<LintFinding> {
                     ),
                     suggestion: Some("Add 'runs-on: ubuntu-latest' or equivalent".to_string()),
                     location: Some(format!("jobs.{}", job_name)),
+                    fixable: false,
+                    fix: None,
                 });
             }
         }
@@ -94,6 +123,8 @@ fn validate_gitlab_ci(content: &str) -> Vec<LintFinding> {
                 message: format!("Invalid YAML: {}", e),
                 suggestion: None,
                 location: None,
+                fixable: false,
+                fix: None,
             });
             return findings;
         }
@@ -132,6 +163,8 @@ fn validate_gitlab_ci(content: &str) -> Vec<LintFinding> {
                         ),
                         suggestion: Some("Add a 'stages:' block listing all stages".to_string()),
                         location: Some(format!("{}.stage", key_str)),
+                        fixable: false,
+                        fix: None,
                     });
                 }
             }
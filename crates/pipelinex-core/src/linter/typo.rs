@@ -156,6 +156,8 @@ pub fn check_typos(content: &str, provider: &str) -> Vec<LintFinding> {
                     message: format!("Possible typo: '{}' — did you mean '{}'?", key, suggestion),
                     suggestion: Some(format!("Replace '{}' with '{}'", key, suggestion)),
                     location: Some(format!("line {}", line_num + 1)),
+                    fixable: false,
+                    fix: None,
                 });
             }
         }
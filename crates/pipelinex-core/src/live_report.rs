@@ -0,0 +1,286 @@
+//! Live-reloading HTML reports for `watch --serve`.
+//!
+//! [`LiveReports`] holds the rendered pages and a version counter that is
+//! bumped whenever a re-render changes them. Each page polls `/version` and
+//! reloads when the number moves, so no websocket or extra dependency is
+//! needed. [`serve`] is a minimal HTTP/1.1 loop over `std::net`.
+
+use crate::analyzer::html_report::escape_html;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// How often pages poll for a new version, in milliseconds.
+const POLL_INTERVAL_MS: u64 = 1000;
+
+/// Rendered reports for the watched files.
+#[derive(Debug, Default)]
+pub struct LiveReports {
+    pages: Vec<(PathBuf, String)>,
+    version: u64,
+}
+
+/// An HTTP response produced by [`LiveReports::respond`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl LiveReports {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Re-render every file with `render`, which returns a full HTML report.
+    ///
+    /// Render errors become an error page, so a broken edit shows up in the
+    /// browser instead of leaving the last good report in place. Returns
+    /// `true`, and bumps the version, when any page changed.
+    pub fn refresh<F>(&mut self, files: &[PathBuf], render: F) -> bool
+    where
+        F: Fn(&Path) -> Result<String, String>,
+    {
+        let pages: Vec<_> = files
+            .iter()
+            .map(|file| {
+                let html = render(file).unwrap_or_else(|error| error_page(file, &error));
+                (file.clone(), html)
+            })
+            .collect();
+
+        if pages == self.pages {
+            return false;
+        }
+        self.pages = pages;
+        self.version += 1;
+        true
+    }
+
+    /// Route a request target: `/` (the report, or an index for several
+    /// files), `/report/<n>`, and `/version`.
+    pub fn respond(&self, target: &str) -> Response {
+        let path = target.split('?').next().unwrap_or(target);
+        match path {
+            "/version" => Response {
+                status: 200,
+                content_type: "text/plain; charset=utf-8",
+                body: self.version.to_string(),
+            },
+            "/" if self.pages.len() == 1 => self.page(0),
+            "/" => Response {
+                status: 200,
+                content_type: "text/html; charset=utf-8",
+                body: with_live_reload(&self.index_page(), self.version),
+            },
+            _ => match path
+                .strip_prefix("/report/")
+                .and_then(|n| n.parse::<usize>().ok())
+            {
+                Some(n) if n < self.pages.len() => self.page(n),
+                _ => Response {
+                    status: 404,
+                    content_type: "text/plain; charset=utf-8",
+                    body: "Not found".to_string(),
+                },
+            },
+        }
+    }
+
+    fn page(&self, index: usize) -> Response {
+        Response {
+            status: 200,
+            content_type: "text/html; charset=utf-8",
+            body: with_live_reload(&self.pages[index].1, self.version),
+        }
+    }
+
+    fn index_page(&self) -> String {
+        let links: String = self
+            .pages
+            .iter()
+            .enumerate()
+            .map(|(i, (path, _))| {
+                format!(
+                    "<li><a href=\"/report/{}\">{}</a></li>\n",
+                    i,
+                    escape_html(&path.display().to_string())
+                )
+            })
+            .collect();
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head><meta charset=\"UTF-8\"><title>PipelineX Live Reports</title></head>\n<body>\n<h1>PipelineX Live Reports</h1>\n<ul>\n{}</ul>\n</body>\n</html>\n",
+            links
+        )
+    }
+}
+
+/// Insert the polling script that reloads the page when the version changes.
+pub fn with_live_reload(html: &str, version: u64) -> String {
+    let script = format!(
+        "<script>\n(function () {{\n    var version = \"{}\";\n    setInterval(function () {{\n        fetch('/version', {{ cache: 'no-store' }})\n            .then(function (r) {{ return r.text(); }})\n            .then(function (v) {{ if (v !== version) location.reload(); }})\n            .catch(function () {{}});\n    }}, {});\n}})();\n</script>\n",
+        version, POLL_INTERVAL_MS
+    );
+    match html.rfind("</body>") {
+        Some(pos) => format!("{}{}{}", &html[..pos], script, &html[pos..]),
+        None => format!("{}{}", html, script),
+    }
+}
+
+fn error_page(file: &Path, error: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head><meta charset=\"UTF-8\"><title>PipelineX - error</title></head>\n<body>\n<h1>Could not analyze {}</h1>\n<pre>{}</pre>\n</body>\n</html>\n",
+        escape_html(&file.display().to_string()),
+        escape_html(error)
+    )
+}
+
+/// Serve `reports` until the listener fails. Each connection gets its own thread.
+pub fn serve(listener: TcpListener, reports: Arc<Mutex<LiveReports>>) -> std::io::Result<()> {
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let reports = Arc::clone(&reports);
+        std::thread::spawn(move || {
+            // A client that disconnects mid-request is not worth reporting.
+            let _ = handle_connection(stream, &reports);
+        });
+    }
+    Ok(())
+}
+
+fn handle_connection(stream: TcpStream, reports: &Mutex<LiveReports>) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut reader = BufReader::new(&stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Drain headers; the body of a GET is ignored.
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let target = parts.next().unwrap_or("/");
+
+    let response = if method == "GET" {
+        reports
+            .lock()
+            .map(|r| r.respond(target))
+            .unwrap_or_else(|_| Response {
+                status: 500,
+                content_type: "text/plain; charset=utf-8",
+                body: "Report state unavailable".to_string(),
+            })
+    } else {
+        Response {
+            status: 405,
+            content_type: "text/plain; charset=utf-8",
+            body: "Method not allowed".to_string(),
+        }
+    };
+
+    let reason = match response.status {
+        200 => "OK",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Internal Server Error",
+    };
+    let mut stream = &stream;
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+        response.status,
+        reason,
+        response.content_type,
+        response.body.len()
+    )?;
+    stream.write_all(response.body.as_bytes())?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer;
+    use crate::analyzer::html_report::generate_html_report;
+    use crate::parser::github::GitHubActionsParser;
+    use std::io::Read;
+
+    fn render(path: &Path) -> Result<String, String> {
+        let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let dag = GitHubActionsParser::parse(&content, path.display().to_string())
+            .map_err(|e| e.to_string())?;
+        Ok(generate_html_report(&analyzer::analyze(&dag), &dag))
+    }
+
+    #[test]
+    fn test_refresh_rerenders_on_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ci.yml");
+        let files = vec![path.clone()];
+        std::fs::write(
+            &path,
+            "name: Before\non: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n    steps:\n      - run: make\n",
+        )
+        .unwrap();
+
+        let mut reports = LiveReports::new();
+        assert!(reports.refresh(&files, render));
+        assert_eq!(reports.version(), 1);
+        let page = reports.respond("/");
+        assert_eq!(page.status, 200);
+        assert!(page.body.contains("Before"));
+        assert!(page.body.contains("fetch('/version'"));
+
+        // Nothing changed on disk: same version, browsers keep the page.
+        assert!(!reports.refresh(&files, render));
+        assert_eq!(reports.respond("/version").body, "1");
+
+        std::fs::write(
+            &path,
+            "name: After\non: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n    steps:\n      - run: make\n",
+        )
+        .unwrap();
+        assert!(reports.refresh(&files, render));
+        assert_eq!(reports.respond("/version").body, "2");
+        assert!(reports.respond("/").body.contains("After"));
+
+        std::fs::write(&path, "jobs: [broken").unwrap();
+        assert!(reports.refresh(&files, render));
+        let page = reports.respond("/report/0");
+        assert!(page.body.contains("Could not analyze"));
+        assert_eq!(reports.respond("/report/7").status, 404);
+    }
+
+    #[test]
+    fn test_serve_over_http() {
+        let mut reports = LiveReports::new();
+        reports.refresh(&[PathBuf::from("a.yml"), PathBuf::from("b.yml")], |p| {
+            Ok(format!("<html><body>{}</body></html>", p.display()))
+        });
+        let reports = Arc::new(Mutex::new(reports));
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || serve(listener, reports));
+
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream
+            .write_all(b"GET /report/1 HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("b.yml"));
+        assert!(response.contains("location.reload()"));
+    }
+}
@@ -12,6 +12,93 @@ pub struct MigrationResult {
     pub converted_jobs: usize,
     pub warnings: Vec<String>,
     pub yaml: String,
+    /// Round-trip check of `yaml`, when requested (`migrate --validate`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validation: Option<MigrationValidation>,
+}
+
+/// Result of re-parsing migrated YAML with the target provider's parser.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationValidation {
+    pub parses: bool,
+    /// Parser error when `parses` is false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub source_jobs: usize,
+    pub migrated_jobs: usize,
+    pub source_edges: usize,
+    pub migrated_edges: usize,
+    /// Source jobs absent from the re-parsed pipeline.
+    pub missing_jobs: Vec<String>,
+    /// Source dependencies (`from -> to`) absent from the re-parsed pipeline.
+    pub lost_dependencies: Vec<String>,
+}
+
+impl MigrationValidation {
+    /// Parses, and every source job and dependency survived. Extra
+    /// dependencies implied by GitLab stage ordering are not a loss.
+    pub fn is_lossless(&self) -> bool {
+        self.parses && self.missing_jobs.is_empty() && self.lost_dependencies.is_empty()
+    }
+}
+
+/// Re-parse `result.yaml` with [`GitLabCIParser`](crate::parser::gitlab::GitLabCIParser)
+/// and compare its jobs and dependency edges with `source`.
+pub fn validate_gitlab_migration(
+    source: &PipelineDag,
+    result: &MigrationResult,
+) -> MigrationValidation {
+    let source_edges = dependency_edges(source);
+    let mut validation = MigrationValidation {
+        parses: false,
+        error: None,
+        source_jobs: source.job_count(),
+        migrated_jobs: 0,
+        source_edges: source_edges.len(),
+        migrated_edges: 0,
+        missing_jobs: Vec::new(),
+        lost_dependencies: Vec::new(),
+    };
+
+    let migrated = match crate::parser::gitlab::GitLabCIParser::parse(
+        &result.yaml,
+        ".gitlab-ci.yml".to_string(),
+    ) {
+        Ok(dag) => dag,
+        Err(e) => {
+            validation.error = Some(e.to_string());
+            return validation;
+        }
+    };
+    let migrated_edges = dependency_edges(&migrated);
+
+    validation.parses = true;
+    validation.migrated_jobs = migrated.job_count();
+    validation.migrated_edges = migrated_edges.len();
+    validation.missing_jobs = source
+        .job_ids()
+        .into_iter()
+        .filter(|id| migrated.get_job(id).is_none())
+        .collect();
+    validation.missing_jobs.sort();
+    validation.lost_dependencies = source_edges
+        .difference(&migrated_edges)
+        .map(|(from, to)| format!("{} -> {}", from, to))
+        .collect();
+    validation
+}
+
+fn dependency_edges(dag: &PipelineDag) -> std::collections::BTreeSet<(String, String)> {
+    dag.graph
+        .raw_edges()
+        .iter()
+        .map(|edge| {
+            (
+                dag.graph[edge.source()].id.clone(),
+                dag.graph[edge.target()].id.clone(),
+            )
+        })
+        .collect()
 }
 
 /// Convert a GitHub Actions DAG into a GitLab CI YAML file.
@@ -32,6 +119,7 @@ pub fn github_actions_to_gitlab_ci(dag: &PipelineDag) -> Result<MigrationResult>
         converted_jobs: dag.job_count(),
         warnings,
         yaml,
+        validation: None,
     })
 }
 
@@ -139,7 +227,9 @@ fn render_gitlab_yaml(dag: &PipelineDag, warnings: &mut Vec<String>) -> Result<S
     Ok(yaml)
 }
 
-fn compute_stage_indexes(dag: &PipelineDag) -> HashMap<String, usize> {
+/// Topological level of every job: 0 for jobs without `needs`, otherwise one
+/// more than the deepest dependency.
+pub(crate) fn compute_stage_indexes(dag: &PipelineDag) -> HashMap<String, usize> {
     fn visit(job_id: &str, dag: &PipelineDag, memo: &mut HashMap<String, usize>) -> usize {
         if let Some(depth) = memo.get(job_id) {
             return *depth;
@@ -306,7 +396,7 @@ fn convert_workflow_triggers(
 fn to_string_map_value(data: &HashMap<String, String>) -> Value {
     let mut map = Mapping::new();
     let mut entries = data.iter().collect::<Vec<_>>();
-    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+    entries.sort_by_key(|(a, _)| *a);
     for (key, value) in entries {
         map.insert(
             Value::String((*key).clone()),
@@ -371,6 +461,53 @@ jobs:
             .is_some());
     }
 
+    #[test]
+    fn migrated_yaml_round_trips_through_gitlab_parser() {
+        let workflow = r#"
+on: push
+jobs:
+  lint:
+    runs-on: ubuntu-latest
+    steps: [{ run: npm run lint }]
+  unit:
+    runs-on: ubuntu-latest
+    steps: [{ run: npm test }]
+  build:
+    needs: [lint, unit]
+    runs-on: ubuntu-latest
+    steps: [{ run: npm run build }]
+  deploy:
+    needs: build
+    runs-on: ubuntu-latest
+    steps: [{ run: ./deploy.sh }]
+"#;
+        let dag = GitHubActionsParser::parse(workflow, "ci.yml".to_string()).unwrap();
+        let result = github_actions_to_gitlab_ci(&dag).unwrap();
+        let validation = validate_gitlab_migration(&dag, &result);
+
+        assert!(validation.parses, "{:?}", validation.error);
+        assert_eq!(validation.migrated_jobs, 4);
+        assert_eq!(validation.migrated_jobs, validation.source_jobs);
+        assert_eq!(validation.source_edges, 3);
+        assert!(validation.is_lossless(), "{:?}", validation);
+    }
+
+    #[test]
+    fn validation_reports_lost_jobs() {
+        let dag = GitHubActionsParser::parse(
+            "on: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n    steps: [{ run: make }]\n",
+            "ci.yml".to_string(),
+        )
+        .unwrap();
+        let mut result = github_actions_to_gitlab_ci(&dag).unwrap();
+        result.yaml = result.yaml.replace("build:", "renamed:");
+
+        let validation = validate_gitlab_migration(&dag, &result);
+        assert!(validation.parses);
+        assert_eq!(validation.missing_jobs, vec!["build"]);
+        assert!(!validation.is_lossless());
+    }
+
     #[test]
     fn fails_for_non_github_provider() {
         let dag = PipelineDag::new(
@@ -397,6 +534,9 @@ jobs:
             uses: Some("actions/setup-node@v4".to_string()),
             run: None,
             estimated_duration_secs: Some(5.0),
+            with: HashMap::new(),
+            working_directory: None,
+            env: HashMap::new(),
         });
         dag.add_job(job);
 
@@ -0,0 +1,437 @@
+//! GitLab CI counterparts of the optimizer transforms.
+//!
+//! GitLab has no cache or checkout steps: caches are job-level `cache:` blocks,
+//! ordering comes from `stages:` unless a job lists `needs:`, and clone depth
+//! is the `GIT_DEPTH` variable.
+
+use crate::analyzer::report::{AnalysisReport, FindingCategory};
+use crate::parser::gitlab::GitLabCIParser;
+use serde_yaml::{Mapping, Value};
+use std::collections::HashSet;
+
+/// `GIT_DEPTH` set by the shallow-clone transform. A depth of 1 fails jobs
+/// whose commit is no longer the branch tip when they start; a few commits of
+/// slack avoids that at negligible cost.
+const SHALLOW_CLONE_DEPTH: &str = "10";
+
+/// Cache block and supporting variables for one package manager.
+struct CacheSpec {
+    /// Lockfiles hashed into the cache key (GitLab allows at most two).
+    key_files: &'static [&'static str],
+    paths: &'static [&'static str],
+    /// Variables pointing the tool's cache inside the project directory, since
+    /// GitLab can only cache paths under `$CI_PROJECT_DIR`.
+    variables: &'static [(&'static str, &'static str)],
+}
+
+const NPM: CacheSpec = CacheSpec {
+    key_files: &["package-lock.json"],
+    paths: &[".npm/"],
+    variables: &[("npm_config_cache", "$CI_PROJECT_DIR/.npm")],
+};
+const YARN: CacheSpec = CacheSpec {
+    key_files: &["yarn.lock"],
+    paths: &[".yarn-cache/"],
+    variables: &[("YARN_CACHE_FOLDER", "$CI_PROJECT_DIR/.yarn-cache")],
+};
+const PNPM: CacheSpec = CacheSpec {
+    key_files: &["pnpm-lock.yaml"],
+    paths: &[".pnpm-store/"],
+    variables: &[("npm_config_store_dir", "$CI_PROJECT_DIR/.pnpm-store")],
+};
+const PIP: CacheSpec = CacheSpec {
+    key_files: &["requirements.txt"],
+    paths: &[".cache/pip/"],
+    variables: &[("PIP_CACHE_DIR", "$CI_PROJECT_DIR/.cache/pip")],
+};
+const CARGO: CacheSpec = CacheSpec {
+    key_files: &["Cargo.lock"],
+    paths: &[".cargo/registry/", "target/"],
+    variables: &[("CARGO_HOME", "$CI_PROJECT_DIR/.cargo")],
+};
+const MAVEN: CacheSpec = CacheSpec {
+    key_files: &["pom.xml"],
+    paths: &[".m2/repository/"],
+    variables: &[(
+        "MAVEN_OPTS",
+        "-Dmaven.repo.local=$CI_PROJECT_DIR/.m2/repository",
+    )],
+};
+const GRADLE: CacheSpec = CacheSpec {
+    key_files: &["build.gradle", "build.gradle.kts"],
+    paths: &[".gradle/caches/", ".gradle/wrapper/"],
+    variables: &[("GRADLE_USER_HOME", "$CI_PROJECT_DIR/.gradle")],
+};
+
+/// Add a lockfile-keyed `cache:` block to jobs flagged for missing caches.
+///
+/// Jobs that already have a cache, directly, through `extends:` or from a
+/// global `cache:` / `default: cache:`, are left alone.
+pub fn apply_cache_optimizations(yaml: &mut Value, report: &AnalysisReport) {
+    if yaml.get("cache").is_some() || yaml.get("default").and_then(|d| d.get("cache")).is_some() {
+        return;
+    }
+
+    for finding in &report.findings {
+        if finding.category != FindingCategory::MissingCache {
+            continue;
+        }
+        for job_id in &finding.affected_jobs {
+            if has_inherited_cache(yaml, job_id) {
+                continue;
+            }
+            let Some(job) = yaml
+                .get_mut(job_id.as_str())
+                .and_then(|j| j.as_mapping_mut())
+            else {
+                continue;
+            };
+            if job.contains_key("cache") {
+                continue;
+            }
+            let Some(spec) = cache_spec(&finding.title, &script_text(job)) else {
+                continue;
+            };
+            inject_cache(job, spec);
+        }
+    }
+}
+
+/// Replace stage ordering with explicit `needs:` for jobs flagged as waiting
+/// on a job they do not use.
+///
+/// A job without `needs:` waits for the whole previous stage; it gets a `needs:`
+/// list of those jobs minus the false dependency, plus the jobs the dependency
+/// itself waited for, so artifacts from earlier stages still arrive. An
+/// existing list just loses the entry. An empty list is kept: `needs: []`
+/// starts the job immediately.
+pub fn apply_needs_optimizations(yaml: &mut Value, report: &AnalysisReport) {
+    for finding in &report.findings {
+        if finding.category != FindingCategory::SerialBottleneck
+            || !finding.title.contains("unnecessarily")
+            || finding.affected_jobs.len() < 2
+        {
+            continue;
+        }
+        let (dependent, dependency) = (&finding.affected_jobs[0], &finding.affected_jobs[1]);
+
+        let needs = match yaml.get(dependent.as_str()).and_then(|j| j.get("needs")) {
+            Some(Value::Sequence(needs)) => needs.clone(),
+            // `needs` in an unexpected shape: leave it to the user
+            Some(_) => continue,
+            None => {
+                let mut needs = previous_stage_jobs(yaml, dependent);
+                if needs.contains(dependency) {
+                    for upstream in upstream_jobs(yaml, dependency) {
+                        if !needs.contains(&upstream) {
+                            needs.push(upstream);
+                        }
+                    }
+                }
+                needs.into_iter().map(Value::String).collect()
+            }
+        };
+        let needs: Vec<Value> = needs
+            .into_iter()
+            .filter(|entry| need_name(entry) != Some(dependency.as_str()))
+            .collect();
+
+        if let Some(job) = yaml
+            .get_mut(dependent.as_str())
+            .and_then(|j| j.as_mapping_mut())
+        {
+            job.insert(Value::String("needs".to_string()), Value::Sequence(needs));
+        }
+    }
+}
+
+/// Pin `GIT_DEPTH` in the top-level `variables:` when clone depth was flagged.
+pub fn apply_shallow_clone(yaml: &mut Value, report: &AnalysisReport) {
+    if !report
+        .findings
+        .iter()
+        .any(|f| f.category == FindingCategory::ShallowClone)
+    {
+        return;
+    }
+    let Some(root) = yaml.as_mapping_mut() else {
+        return;
+    };
+
+    let key = Value::String("variables".to_string());
+    if !root.get(&key).is_some_and(Value::is_mapping) {
+        root.insert(key.clone(), Value::Mapping(Mapping::new()));
+    }
+    if let Some(variables) = root.get_mut(&key).and_then(|v| v.as_mapping_mut()) {
+        let depth = Value::String("GIT_DEPTH".to_string());
+        if !variables.contains_key(&depth) {
+            variables.insert(depth, Value::String(SHALLOW_CLONE_DEPTH.to_string()));
+        }
+    }
+}
+
+fn cache_spec(finding_title: &str, script: &str) -> Option<&'static CacheSpec> {
+    let script = script.to_lowercase();
+    if finding_title.contains("npm") {
+        Some(if script.contains("pnpm") {
+            &PNPM
+        } else if script.contains("yarn") {
+            &YARN
+        } else {
+            &NPM
+        })
+    } else if finding_title.contains("pip") {
+        Some(&PIP)
+    } else if finding_title.contains("Cargo") {
+        Some(&CARGO)
+    } else if finding_title.contains("Gradle") || finding_title.contains("Maven") {
+        Some(if script.contains("mvn") {
+            &MAVEN
+        } else {
+            &GRADLE
+        })
+    } else {
+        None
+    }
+}
+
+fn inject_cache(job: &mut Mapping, spec: &CacheSpec) {
+    let strings = |items: &[&str]| {
+        Value::Sequence(items.iter().map(|s| Value::String(s.to_string())).collect())
+    };
+
+    let mut key = Mapping::new();
+    key.insert(Value::String("files".to_string()), strings(spec.key_files));
+    let mut cache = Mapping::new();
+    cache.insert(Value::String("key".to_string()), Value::Mapping(key));
+    cache.insert(Value::String("paths".to_string()), strings(spec.paths));
+    job.insert(Value::String("cache".to_string()), Value::Mapping(cache));
+
+    let key = Value::String("variables".to_string());
+    if !job.get(&key).is_some_and(Value::is_mapping) {
+        job.insert(key.clone(), Value::Mapping(Mapping::new()));
+    }
+    if let Some(variables) = job.get_mut(&key).and_then(|v| v.as_mapping_mut()) {
+        for (name, value) in spec.variables {
+            let name = Value::String(name.to_string());
+            if !variables.contains_key(&name) {
+                variables.insert(name, Value::String(value.to_string()));
+            }
+        }
+    }
+}
+
+/// Whether a template the job `extends` defines a cache.
+fn has_inherited_cache(yaml: &Value, job_id: &str) -> bool {
+    let templates: Vec<&str> = match yaml.get(job_id).and_then(|j| j.get("extends")) {
+        Some(Value::String(name)) => vec![name.as_str()],
+        Some(Value::Sequence(names)) => names.iter().filter_map(|n| n.as_str()).collect(),
+        _ => Vec::new(),
+    };
+    templates.into_iter().any(|name| {
+        yaml.get(name).and_then(|t| t.get("cache")).is_some() || has_inherited_cache(yaml, name)
+    })
+}
+
+fn script_text(job: &Mapping) -> String {
+    ["before_script", "script"]
+        .iter()
+        .filter_map(|key| job.get(*key))
+        .flat_map(|v| match v {
+            Value::Sequence(lines) => lines.iter().filter_map(|l| l.as_str()).collect(),
+            Value::String(line) => vec![line.as_str()],
+            _ => Vec::new(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Jobs in the nearest earlier stage that has any, which a job without
+/// `needs:` waits for.
+fn previous_stage_jobs(yaml: &Value, job_id: &str) -> Vec<String> {
+    let Some(root) = yaml.as_mapping() else {
+        return Vec::new();
+    };
+    let stage_of = |job: &Value| {
+        job.get("stage")
+            .and_then(|s| s.as_str())
+            .unwrap_or("test")
+            .to_string()
+    };
+    let jobs: Vec<(String, String)> = root
+        .iter()
+        .filter_map(|(key, value)| {
+            let key = key.as_str()?;
+            GitLabCIParser::is_job_entry(key, value).then(|| (key.to_string(), stage_of(value)))
+        })
+        .collect();
+
+    let stages = GitLabCIParser::parse_stages(yaml);
+    let Some(stage) = yaml.get(job_id).map(stage_of) else {
+        return Vec::new();
+    };
+    let Some(position) = stages.iter().position(|s| *s == stage) else {
+        return Vec::new();
+    };
+    let populated: HashSet<&str> = jobs.iter().map(|(_, s)| s.as_str()).collect();
+    let Some(previous) = stages[..position]
+        .iter()
+        .rev()
+        .find(|s| populated.contains(s.as_str()))
+    else {
+        return Vec::new();
+    };
+
+    jobs.into_iter()
+        .filter(|(_, s)| s == previous)
+        .map(|(id, _)| id)
+        .collect()
+}
+
+/// Jobs `job_id` waits for: its `needs:` list, or else the previous stage.
+fn upstream_jobs(yaml: &Value, job_id: &str) -> Vec<String> {
+    match yaml.get(job_id).and_then(|j| j.get("needs")) {
+        Some(Value::Sequence(needs)) => needs
+            .iter()
+            .filter_map(need_name)
+            .map(str::to_string)
+            .collect(),
+        Some(_) => Vec::new(),
+        None => previous_stage_jobs(yaml, job_id),
+    }
+}
+
+/// Job named by a `needs:` entry, either `job` or `{ job: job, artifacts: ... }`.
+fn need_name(entry: &Value) -> Option<&str> {
+    match entry {
+        Value::String(name) => Some(name),
+        Value::Mapping(map) => map.get("job").and_then(|j| j.as_str()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::optimizer::Optimizer;
+    use crate::parser::gitlab::GitLabCIParser;
+
+    const PIPELINE: &str = r#"
+stages: [lint, test, deploy]
+lint:
+  stage: lint
+  script: [npm run lint]
+test:
+  stage: test
+  script:
+    - npm ci
+    - npm test
+deploy:
+  stage: deploy
+  script: [./deploy.sh]
+"#;
+
+    #[test]
+    fn test_gitlab_optimize_adds_cache_and_needs() {
+        let dag = GitLabCIParser::parse(PIPELINE, ".gitlab-ci.yml".to_string()).unwrap();
+        let report = crate::analyzer::analyze(&dag);
+        let optimized = Optimizer::optimize_content(PIPELINE, &report).unwrap();
+        let yaml: Value = serde_yaml::from_str(&optimized).unwrap();
+
+        let test = &yaml["test"];
+        assert_eq!(test["cache"]["key"]["files"][0], "package-lock.json");
+        assert_eq!(test["cache"]["paths"][0], ".npm/");
+        assert_eq!(
+            test["variables"]["npm_config_cache"],
+            "$CI_PROJECT_DIR/.npm"
+        );
+        // Lint no longer gates the tests
+        assert_eq!(test["needs"], Value::Sequence(Vec::new()));
+        assert!(yaml["deploy"].get("needs").is_none());
+        // GitLab's default clone depth is already shallow
+        assert!(yaml.get("variables").is_none());
+        // No GitHub Actions constructs leak in
+        assert!(yaml.get("concurrency").is_none());
+
+        let reparsed = GitLabCIParser::parse(&optimized, ".gitlab-ci.yml".to_string()).unwrap();
+        let test_idx = reparsed.node_map["test"];
+        assert_eq!(
+            reparsed
+                .graph
+                .neighbors_directed(test_idx, petgraph::Direction::Incoming)
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_stage_dependencies_become_explicit_needs() {
+        let yaml = r#"
+stages: [prepare, build]
+lint:
+  stage: prepare
+  script: [make lint]
+codegen:
+  stage: prepare
+  script: [make generate]
+build:
+  stage: build
+  script: [make build]
+"#;
+        let dag = GitLabCIParser::parse(yaml, ".gitlab-ci.yml".to_string()).unwrap();
+        let report = crate::analyzer::analyze(&dag);
+        let mut value: Value = serde_yaml::from_str(yaml).unwrap();
+        apply_needs_optimizations(&mut value, &report);
+
+        assert_eq!(
+            value["build"]["needs"],
+            Value::Sequence(vec![Value::String("codegen".to_string())])
+        );
+    }
+
+    #[test]
+    fn test_needs_keep_earlier_stage_producers() {
+        let needs_of = |yaml: &str, job: &str| {
+            let dag = GitLabCIParser::parse(yaml, ".gitlab-ci.yml".to_string()).unwrap();
+            let report = crate::analyzer::analyze(&dag);
+            let mut value: Value = serde_yaml::from_str(yaml).unwrap();
+            apply_needs_optimizations(&mut value, &report);
+            value[job]["needs"].clone()
+        };
+        let compile_only = Value::Sequence(vec![Value::String("compile".to_string())]);
+
+        // The empty `lint` stage is skipped: `test` still waits on `build`
+        let empty_middle = r#"
+stages: [build, lint, test]
+compile:
+  stage: build
+  script: [make build]
+  artifacts:
+    paths: [dist/]
+style:
+  stage: build
+  script: [make lint]
+test:
+  stage: test
+  script: [make test]
+"#;
+        assert_eq!(needs_of(empty_middle, "test"), compile_only);
+
+        // Dropping `lint` must not drop the `build` stage it waited for
+        let populated_middle = r#"
+stages: [build, lint, test]
+compile:
+  stage: build
+  script: [make build]
+  artifacts:
+    paths: [dist/]
+lint:
+  stage: lint
+  script: [make lint]
+test:
+  stage: test
+  script: [make test]
+"#;
+        assert_eq!(needs_of(populated_middle, "test"), compile_only);
+    }
+}
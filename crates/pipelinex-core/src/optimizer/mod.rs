@@ -1,9 +1,13 @@
 pub mod cache_gen;
 pub mod docker_opt;
+pub mod gitlab_gen;
 pub mod parallel_gen;
+pub mod selection;
 pub mod shard_gen;
 
-use crate::analyzer::report::AnalysisReport;
+use crate::analyzer::report::{AnalysisReport, FindingCategory};
+use crate::parser::dag::PipelineDag;
+use crate::parser::detect::parse_content_as;
 use anyhow::Result;
 use serde_yaml::Value;
 use std::path::Path;
@@ -14,41 +18,181 @@ pub struct Optimizer;
 impl Optimizer {
     /// Generate an optimized workflow YAML from the original file and analysis report.
     pub fn optimize(original_path: &Path, report: &AnalysisReport) -> Result<String> {
-        let content = std::fs::read_to_string(original_path)?;
-        let mut yaml: Value = serde_yaml::from_str(&content)?;
+        Self::optimize_selected(original_path, report, &Transform::ALL)
+    }
 
-        // Apply cache optimizations
-        cache_gen::apply_cache_optimizations(&mut yaml, report);
+    /// Generate an optimized version from YAML string content.
+    pub fn optimize_content(content: &str, report: &AnalysisReport) -> Result<String> {
+        Self::optimize_content_selected(content, report, &Transform::ALL)
+    }
 
-        // Apply parallelization optimizations
-        parallel_gen::apply_parallel_optimizations(&mut yaml, report);
+    /// Like [`Optimizer::optimize`], applying only the given transforms.
+    pub fn optimize_selected(
+        original_path: &Path,
+        report: &AnalysisReport,
+        transforms: &[Transform],
+    ) -> Result<String> {
+        let content = std::fs::read_to_string(original_path)?;
+        Self::optimize_content_selected(&content, report, transforms)
+    }
 
-        // Apply path filter optimization
-        apply_path_filter(&mut yaml, report);
+    /// Like [`Optimizer::optimize_content`], applying only the given transforms.
+    pub fn optimize_content_selected(
+        content: &str,
+        report: &AnalysisReport,
+        transforms: &[Transform],
+    ) -> Result<String> {
+        let mut yaml: Value = serde_yaml::from_str(content)?;
 
-        // Apply concurrency optimization
-        apply_concurrency(&mut yaml, report);
+        // Apply in canonical order regardless of selection order
+        for transform in Transform::ALL {
+            if transforms.contains(&transform) {
+                transform.apply(&mut yaml, report);
+            }
+        }
 
-        // Apply shallow clone optimization
-        apply_shallow_clone(&mut yaml, report);
+        let applied = if transforms.len() == Transform::ALL.len() {
+            report.findings.len()
+        } else {
+            report
+                .findings
+                .iter()
+                .filter(|f| transforms.iter().any(|t| t.category() == f.category))
+                .count()
+        };
 
         let result = serde_yaml::to_string(&yaml)?;
-        Ok(add_optimization_header(&result, report))
+        Ok(add_optimization_header(&result, report, applied))
     }
 
-    /// Generate an optimized version from YAML string content.
-    pub fn optimize_content(content: &str, report: &AnalysisReport) -> Result<String> {
-        let mut yaml: Value = serde_yaml::from_str(content)?;
+    /// Optimize a pipeline's source and re-parse the result with the same provider.
+    pub fn optimize_dag(content: &str, dag: &PipelineDag) -> Result<PipelineDag> {
+        let report = crate::analyzer::analyze(dag);
+        let optimized = Self::optimize_content(content, &report)?;
+        Ok(parse_content_as(
+            &optimized,
+            &dag.source_file,
+            &dag.provider,
+        )?)
+    }
+}
 
-        cache_gen::apply_cache_optimizations(&mut yaml, report);
-        parallel_gen::apply_parallel_optimizations(&mut yaml, report);
-        apply_path_filter(&mut yaml, report);
-        apply_concurrency(&mut yaml, report);
-        apply_shallow_clone(&mut yaml, report);
+/// A single rewrite the optimizer can apply, driven by one finding category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Transform {
+    Cache,
+    Parallelize,
+    PathFilter,
+    Concurrency,
+    ShallowClone,
+}
 
-        let result = serde_yaml::to_string(&yaml)?;
-        Ok(add_optimization_header(&result, report))
+impl Transform {
+    /// Every transform, in the order the optimizer applies them.
+    pub const ALL: [Transform; 5] = [
+        Transform::Cache,
+        Transform::Parallelize,
+        Transform::PathFilter,
+        Transform::Concurrency,
+        Transform::ShallowClone,
+    ];
+
+    /// Identifier accepted by `optimize --select`.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Transform::Cache => "cache",
+            Transform::Parallelize => "parallelize",
+            Transform::PathFilter => "path-filter",
+            Transform::Concurrency => "concurrency",
+            Transform::ShallowClone => "shallow-clone",
+        }
     }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        let id = id.trim().to_lowercase();
+        Self::ALL.into_iter().find(|t| t.id() == id)
+    }
+
+    /// What the transform changes in the workflow.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Transform::Cache => "Add dependency cache steps",
+            Transform::Parallelize => "Remove unnecessary `needs:` dependencies",
+            Transform::PathFilter => "Add `paths-ignore` to push/pull_request triggers",
+            Transform::Concurrency => "Cancel superseded runs with a concurrency group",
+            Transform::ShallowClone => "Use `fetch-depth: 1` on checkout",
+        }
+    }
+
+    /// The finding category this transform addresses.
+    pub fn category(&self) -> FindingCategory {
+        match self {
+            Transform::Cache => FindingCategory::MissingCache,
+            Transform::Parallelize => FindingCategory::SerialBottleneck,
+            Transform::PathFilter => FindingCategory::MissingPathFilter,
+            Transform::Concurrency => FindingCategory::ConcurrencyControl,
+            Transform::ShallowClone => FindingCategory::ShallowClone,
+        }
+    }
+
+    fn apply(&self, yaml: &mut Value, report: &AnalysisReport) {
+        if report.provider == "gitlab-ci" {
+            self.apply_gitlab(yaml, report);
+            return;
+        }
+        match self {
+            Transform::Cache => cache_gen::apply_cache_optimizations(yaml, report),
+            Transform::Parallelize => parallel_gen::apply_parallel_optimizations(yaml, report),
+            Transform::PathFilter => apply_path_filter(yaml, report),
+            Transform::Concurrency => apply_concurrency(yaml, report),
+            Transform::ShallowClone => apply_shallow_clone(yaml, report),
+        }
+    }
+
+    fn apply_gitlab(&self, yaml: &mut Value, report: &AnalysisReport) {
+        match self {
+            Transform::Cache => gitlab_gen::apply_cache_optimizations(yaml, report),
+            Transform::Parallelize => gitlab_gen::apply_needs_optimizations(yaml, report),
+            Transform::ShallowClone => gitlab_gen::apply_shallow_clone(yaml, report),
+            // GitLab has no `on:` triggers or `concurrency:` key; `rules: changes`
+            // and `interruptible` need per-job judgement, so these stay manual.
+            Transform::PathFilter | Transform::Concurrency => {}
+        }
+    }
+}
+
+/// A transform that has at least one finding to act on.
+#[derive(Debug, Clone)]
+pub struct TransformOption {
+    pub transform: Transform,
+    /// Titles of the findings the transform addresses.
+    pub findings: Vec<String>,
+    pub estimated_savings_secs: f64,
+}
+
+/// Transforms applicable to a report, in application order.
+pub fn available_transforms(report: &AnalysisReport) -> Vec<TransformOption> {
+    Transform::ALL
+        .into_iter()
+        .filter_map(|transform| {
+            let findings: Vec<_> = report
+                .findings
+                .iter()
+                .filter(|f| f.category == transform.category())
+                .collect();
+            if findings.is_empty() {
+                return None;
+            }
+            Some(TransformOption {
+                transform,
+                findings: findings.iter().map(|f| f.title.clone()).collect(),
+                estimated_savings_secs: findings
+                    .iter()
+                    .filter_map(|f| f.estimated_savings_secs)
+                    .sum(),
+            })
+        })
+        .collect()
 }
 
 fn apply_path_filter(yaml: &mut Value, report: &AnalysisReport) {
@@ -171,7 +315,7 @@ fn apply_shallow_clone(yaml: &mut Value, report: &AnalysisReport) {
     }
 }
 
-fn add_optimization_header(yaml: &str, report: &AnalysisReport) -> String {
+fn add_optimization_header(yaml: &str, report: &AnalysisReport, applied: usize) -> String {
     format!(
         "# Optimized by PipelineX v0.1.0\n\
          # Original: {}\n\
@@ -182,7 +326,7 @@ fn add_optimization_header(yaml: &str, report: &AnalysisReport) -> String {
          \n{}",
         report.source_file,
         report.potential_improvement_pct(),
-        report.findings.len(),
+        applied,
         yaml
     )
 }
@@ -0,0 +1,160 @@
+//! Choosing which optimizer transforms to apply (`optimize --select` / `--interactive`).
+
+use super::{Transform, TransformOption};
+use anyhow::Result;
+
+/// Parse a `--select` list such as `cache,concurrency` into transforms.
+pub fn parse_transform_ids(ids: &[String]) -> Result<Vec<Transform>> {
+    let mut transforms = Vec::new();
+    for id in ids.iter().flat_map(|s| s.split(',')) {
+        if id.trim().is_empty() {
+            continue;
+        }
+        let transform = Transform::from_id(id).ok_or_else(|| {
+            let known: Vec<_> = Transform::ALL.iter().map(|t| t.id()).collect();
+            anyhow::anyhow!(
+                "Unknown transform '{}'. Expected one of: {}",
+                id.trim(),
+                known.join(", ")
+            )
+        })?;
+        if !transforms.contains(&transform) {
+            transforms.push(transform);
+        }
+    }
+    Ok(transforms)
+}
+
+/// Toggle state for the interactive picker. Every option starts selected.
+#[derive(Debug, Clone)]
+pub struct TransformSelection {
+    options: Vec<TransformOption>,
+    selected: Vec<bool>,
+}
+
+impl TransformSelection {
+    pub fn new(options: Vec<TransformOption>) -> Self {
+        let selected = vec![true; options.len()];
+        Self { options, selected }
+    }
+
+    pub fn options(&self) -> &[TransformOption] {
+        &self.options
+    }
+
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.selected.get(index).copied().unwrap_or(false)
+    }
+
+    /// Apply one line of user input.
+    ///
+    /// Accepts 1-based option numbers or transform ids (comma or space separated)
+    /// to toggle, `all`, `none`, or an empty line to finish. Returns `true` when done.
+    pub fn handle_input(&mut self, input: &str) -> Result<bool> {
+        let input = input.trim();
+        match input.to_lowercase().as_str() {
+            "" => return Ok(true),
+            "all" | "a" => {
+                self.selected.fill(true);
+                return Ok(false);
+            }
+            "none" | "n" => {
+                self.selected.fill(false);
+                return Ok(false);
+            }
+            _ => {}
+        }
+
+        let mut toggles = Vec::new();
+        for token in input.split([',', ' ']).filter(|t| !t.is_empty()) {
+            let index = match token.parse::<usize>() {
+                Ok(n) if (1..=self.options.len()).contains(&n) => n - 1,
+                Ok(n) => anyhow::bail!("No option {} (choose 1-{})", n, self.options.len()),
+                Err(_) => Transform::from_id(token)
+                    .and_then(|t| self.options.iter().position(|o| o.transform == t))
+                    .ok_or_else(|| anyhow::anyhow!("'{}' is not an available transform", token))?,
+            };
+            toggles.push(index);
+        }
+
+        // Validate the whole line before changing anything
+        for index in toggles {
+            self.selected[index] = !self.selected[index];
+        }
+        Ok(false)
+    }
+
+    /// Selected transforms, in option order.
+    pub fn selected(&self) -> Vec<Transform> {
+        self.options
+            .iter()
+            .zip(&self.selected)
+            .filter(|(_, &on)| on)
+            .map(|(o, _)| o.transform)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer;
+    use crate::optimizer::{available_transforms, Optimizer};
+    use crate::parser::github::GitHubActionsParser;
+
+    const WORKFLOW: &str = r#"
+name: CI
+on:
+  push:
+    branches: [main]
+  pull_request:
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - uses: actions/setup-node@v4
+      - run: npm ci
+      - run: npm run build
+"#;
+
+    #[test]
+    fn test_toggle_input_maps_to_transforms() {
+        let dag = GitHubActionsParser::parse(WORKFLOW, "ci.yml".to_string()).unwrap();
+        let report = analyzer::analyze(&dag);
+        let options = available_transforms(&report);
+        let ids: Vec<_> = options.iter().map(|o| o.transform).collect();
+        assert!(ids.contains(&Transform::Cache));
+        assert!(ids.contains(&Transform::Concurrency));
+
+        let mut selection = TransformSelection::new(options);
+        assert_eq!(selection.selected(), ids);
+
+        assert!(!selection.handle_input("none").unwrap());
+        assert!(selection.selected().is_empty());
+
+        assert!(!selection.handle_input("cache").unwrap());
+        assert_eq!(selection.selected(), vec![Transform::Cache]);
+
+        assert!(selection.handle_input("99").is_err());
+        assert!(selection.handle_input("1, bogus").is_err());
+        assert_eq!(selection.selected(), vec![Transform::Cache]);
+        assert!(selection.handle_input("").unwrap());
+
+        let optimized =
+            Optimizer::optimize_content_selected(WORKFLOW, &report, &selection.selected()).unwrap();
+        assert!(optimized.contains("actions/cache"));
+        assert!(!optimized.contains("cancel-in-progress"));
+        assert!(!optimized.contains("fetch-depth"));
+    }
+
+    #[test]
+    fn test_parse_transform_ids() {
+        let ids = vec!["cache,concurrency".to_string(), "cache".to_string()];
+        assert_eq!(
+            parse_transform_ids(&ids).unwrap(),
+            vec![Transform::Cache, Transform::Concurrency]
+        );
+        assert!(parse_transform_ids(&["paths".to_string()]).is_err());
+    }
+}
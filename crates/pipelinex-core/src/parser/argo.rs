@@ -1,4 +1,7 @@
+use crate::error::{read_pipeline_file, PipelineError};
 use crate::parser::dag::*;
+use crate::parser::detect::{ProviderCapabilities, ProviderInfo};
+use crate::parser::json5;
 use anyhow::{Context, Result};
 use serde::Deserialize;
 use serde_yaml::Value;
@@ -6,23 +9,101 @@ use std::collections::HashMap;
 use std::path::Path;
 
 /// Parser for Argo Workflows and WorkflowTemplate CRDs.
+///
+/// A file may hold several `---`-separated documents. The Workflow (or else a
+/// WorkflowTemplate) becomes the DAG, and `templateRef`s to WorkflowTemplates
+/// defined in the same file are resolved to those templates.
 pub struct ArgoWorkflowsParser;
 
+/// Templates a task or step can refer to.
+struct Templates<'a> {
+    /// `spec.templates` of the document being parsed, by name.
+    local: HashMap<&'a str, &'a Value>,
+    /// Templates of every WorkflowTemplate in the file, by (template name, template).
+    referenced: HashMap<(&'a str, &'a str), &'a Value>,
+}
+
+impl<'a> Templates<'a> {
+    /// The template a task/step runs, via `template:` or `templateRef:`.
+    fn resolve(&self, task: &Value) -> Option<&'a Value> {
+        if let Some(name) = task.get("template").and_then(|v| v.as_str()) {
+            return self.local.get(name).copied();
+        }
+        let template_ref = task.get("templateRef")?;
+        let workflow_template = template_ref.get("name")?.as_str()?;
+        let template = template_ref.get("template")?.as_str()?;
+        self.referenced.get(&(workflow_template, template)).copied()
+    }
+}
+
+/// `name` / `template` of a task or step's template reference, for placeholders.
+fn template_label(task: &Value) -> String {
+    if let Some(name) = task.get("template").and_then(|v| v.as_str()) {
+        return name.to_string();
+    }
+    let template_ref = task.get("templateRef");
+    let part = |key: &str| {
+        template_ref
+            .and_then(|r| r.get(key))
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+    };
+    if template_ref.is_some() {
+        format!("{}/{}", part("name"), part("template"))
+    } else {
+        String::new()
+    }
+}
+
+fn spec_templates(doc: &Value) -> impl Iterator<Item = (&str, &Value)> {
+    doc.get("spec")
+        .and_then(|spec| spec.get("templates"))
+        .and_then(|v| v.as_sequence())
+        .into_iter()
+        .flatten()
+        .filter_map(|t| Some((t.get("name")?.as_str()?, t)))
+}
+
 impl ArgoWorkflowsParser {
-    pub fn parse_file(path: &Path) -> Result<PipelineDag> {
-        let content = std::fs::read_to_string(path)
-            .with_context(|| format!("Failed to read Argo Workflows file: {}", path.display()))?;
+    /// Registry entry listed by `list-providers`.
+    pub const INFO: ProviderInfo = ProviderInfo {
+        provider: "argo-workflows",
+        name: "Argo Workflows",
+        file_patterns: &["*.yml", "*.yaml"],
+        content_markers: &["argoproj.io"],
+        capabilities: ProviderCapabilities {
+            matrix: false,
+            needs: true,
+            includes: false,
+            parallel: true,
+            services: false,
+        },
+    };
+
+    pub fn parse_file(path: &Path) -> Result<PipelineDag, PipelineError> {
+        let content = read_pipeline_file(path)?;
         Self::parse(&content, path.to_string_lossy().to_string())
     }
 
-    pub fn parse_content(content: &str, source_name: &str) -> Result<PipelineDag> {
+    pub fn parse_content(content: &str, source_name: &str) -> Result<PipelineDag, PipelineError> {
         Self::parse(content, source_name.to_string())
     }
 
-    pub fn parse(content: &str, source_file: String) -> Result<PipelineDag> {
-        let docs: Vec<Value> = serde_yaml::Deserializer::from_str(content)
-            .map(Value::deserialize)
-            .collect::<std::result::Result<Vec<_>, _>>()
+    pub fn parse(content: &str, source_file: String) -> Result<PipelineDag, PipelineError> {
+        Self::parse_source(content, source_file.clone())
+            .map_err(|e| PipelineError::parse("argo-workflows", &source_file, e))
+    }
+
+    fn parse_source(content: &str, source_file: String) -> Result<PipelineDag> {
+        let docs = Self::documents(content)
+            .or_else(|err| {
+                // JSON with comments or trailing commas; `lint` warns about it
+                if json5::looks_like_json(content) {
+                    Self::documents(&json5::normalize(content)).map_err(|_| err)
+                } else {
+                    Err(err)
+                }
+            })
             .context("Failed to parse YAML")?;
 
         let Some(selected) = docs.iter().max_by_key(|doc| Self::document_priority(doc)) else {
@@ -33,10 +114,35 @@ impl ArgoWorkflowsParser {
             anyhow::bail!("No Argo Workflow/Template document found");
         }
 
-        Self::parse_document(selected, source_file)
+        let referenced = docs
+            .iter()
+            .filter(|doc| {
+                matches!(
+                    doc.get("kind").and_then(|v| v.as_str()),
+                    Some("WorkflowTemplate" | "ClusterWorkflowTemplate")
+                )
+            })
+            .filter_map(|doc| {
+                let name = doc.get("metadata")?.get("name")?.as_str()?;
+                Some(spec_templates(doc).map(move |(template, value)| ((name, template), value)))
+            })
+            .flatten()
+            .collect();
+
+        Self::parse_document(selected, source_file, referenced)
     }
 
-    fn parse_document(yaml: &Value, source_file: String) -> Result<PipelineDag> {
+    fn documents(content: &str) -> std::result::Result<Vec<Value>, serde_yaml::Error> {
+        serde_yaml::Deserializer::from_str(content)
+            .map(Value::deserialize)
+            .collect()
+    }
+
+    fn parse_document<'a>(
+        yaml: &'a Value,
+        source_file: String,
+        referenced: HashMap<(&'a str, &'a str), &'a Value>,
+    ) -> Result<PipelineDag> {
         let metadata = yaml.get("metadata").unwrap_or(yaml);
         let name = metadata
             .get("name")
@@ -52,18 +158,10 @@ impl ArgoWorkflowsParser {
         };
 
         // Collect all templates for reference
-        let empty_vec = Vec::new();
-        let templates: HashMap<String, &Value> = spec
-            .get("templates")
-            .and_then(|v| v.as_sequence())
-            .unwrap_or(&empty_vec)
-            .iter()
-            .filter_map(|t| {
-                t.get("name")
-                    .and_then(|v| v.as_str())
-                    .map(|name| (name.to_string(), t))
-            })
-            .collect();
+        let templates = Templates {
+            local: spec_templates(yaml).collect(),
+            referenced,
+        };
 
         // Find the entrypoint template
         let entrypoint = spec
@@ -71,11 +169,11 @@ impl ArgoWorkflowsParser {
             .and_then(|v| v.as_str())
             .unwrap_or("");
 
-        if let Some(entry_template) = templates.get(entrypoint) {
+        if let Some(entry_template) = templates.local.get(entrypoint) {
             Self::process_template(entry_template, &templates, &mut dag)?;
         } else {
             // If no entrypoint, try to process all templates
-            for template in templates.values() {
+            for template in templates.local.values() {
                 Self::process_template(template, &templates, &mut dag)?;
             }
         }
@@ -107,7 +205,7 @@ impl ArgoWorkflowsParser {
 
     fn process_template(
         template: &Value,
-        all_templates: &HashMap<String, &Value>,
+        all_templates: &Templates,
         dag: &mut PipelineDag,
     ) -> Result<()> {
         // DAG template
@@ -135,7 +233,7 @@ impl ArgoWorkflowsParser {
 
     fn process_dag_template(
         dag_spec: &Value,
-        all_templates: &HashMap<String, &Value>,
+        all_templates: &Templates,
         dag: &mut PipelineDag,
     ) -> Result<()> {
         let tasks = dag_spec
@@ -152,17 +250,20 @@ impl ArgoWorkflowsParser {
                 .unwrap_or("unnamed")
                 .to_string();
 
-            let template_ref = task.get("template").and_then(|v| v.as_str()).unwrap_or("");
+            let template_ref = template_label(task);
 
-            let mut job = if let Some(tmpl) = all_templates.get(template_ref) {
+            let mut job = if let Some(tmpl) = all_templates.resolve(task) {
                 Self::template_to_job(tmpl, &task_name)?
             } else {
                 let mut j = JobNode::new(task_name.clone(), task_name.clone());
                 j.steps.push(StepInfo {
                     name: format!("template: {}", template_ref),
-                    uses: Some(template_ref.to_string()),
+                    uses: Some(template_ref),
                     run: None,
                     estimated_duration_secs: Some(60.0),
+                    with: HashMap::new(),
+                    working_directory: None,
+                    env: HashMap::new(),
                 });
                 j.estimated_duration_secs = 60.0;
                 j
@@ -220,7 +321,7 @@ impl ArgoWorkflowsParser {
 
     fn process_steps_template(
         steps_spec: &Value,
-        all_templates: &HashMap<String, &Value>,
+        all_templates: &Templates,
         dag: &mut PipelineDag,
     ) -> Result<()> {
         let step_groups = steps_spec.as_sequence().unwrap_or(&Vec::new()).clone();
@@ -240,9 +341,7 @@ impl ArgoWorkflowsParser {
 
                 let unique_name = format!("step-{}-{}", group_idx, step_name);
 
-                let template_ref = step.get("template").and_then(|v| v.as_str()).unwrap_or("");
-
-                let mut job = if let Some(tmpl) = all_templates.get(template_ref) {
+                let mut job = if let Some(tmpl) = all_templates.resolve(step) {
                     let mut j = Self::template_to_job(tmpl, &unique_name)?;
                     j.name = step_name;
                     j
@@ -291,6 +390,10 @@ impl ArgoWorkflowsParser {
                 .get("image")
                 .and_then(|v| v.as_str())
                 .unwrap_or("unknown");
+            job.container_image = container
+                .get("image")
+                .and_then(|v| v.as_str())
+                .map(String::from);
 
             let command = container
                 .get("command")
@@ -324,6 +427,9 @@ impl ArgoWorkflowsParser {
                 uses: Some(image.to_string()),
                 run,
                 estimated_duration_secs: Some(Self::estimate_duration(image, template_name)),
+                with: HashMap::new(),
+                working_directory: None,
+                env: HashMap::new(),
             });
         }
 
@@ -343,6 +449,9 @@ impl ArgoWorkflowsParser {
                 uses: Some(image.to_string()),
                 run: source,
                 estimated_duration_secs: Some(Self::estimate_duration(image, template_name)),
+                with: HashMap::new(),
+                working_directory: None,
+                env: HashMap::new(),
             });
         }
 
@@ -585,4 +694,55 @@ spec:
         assert_eq!(dag.job_count(), 1);
         assert!(dag.get_job("hello").is_some());
     }
+
+    #[test]
+    fn test_parse_argo_resolves_template_refs_in_same_file() {
+        let yaml = r#"
+apiVersion: argoproj.io/v1alpha1
+kind: Workflow
+metadata:
+  name: ci
+spec:
+  entrypoint: main
+  templates:
+    - name: main
+      dag:
+        tasks:
+          - name: compile
+            templateRef:
+              name: toolchain
+              template: cargo-build
+          - name: publish
+            dependencies: [compile]
+            templateRef:
+              name: elsewhere
+              template: push
+---
+apiVersion: argoproj.io/v1alpha1
+kind: WorkflowTemplate
+metadata:
+  name: toolchain
+spec:
+  templates:
+    - name: cargo-build
+      container:
+        image: rust:1.80
+        command: [cargo, build, --release]
+"#;
+        let dag = ArgoWorkflowsParser::parse(yaml, "ci.yaml".into()).unwrap();
+        assert_eq!(dag.name, "ci");
+
+        let compile = dag.get_job("compile").unwrap();
+        assert_eq!(compile.container_image.as_deref(), Some("rust:1.80"));
+        assert_eq!(
+            compile.steps[0].run.as_deref(),
+            Some("cargo build --release")
+        );
+        assert_eq!(compile.estimated_duration_secs, 300.0);
+
+        // Unresolvable references keep a placeholder step
+        let publish = dag.get_job("publish").unwrap();
+        assert_eq!(publish.steps[0].name, "template: elsewhere/push");
+        assert_eq!(publish.needs, vec!["compile"]);
+    }
 }
@@ -1,7 +1,9 @@
+use crate::error::{read_pipeline_file, PipelineError};
 use crate::parser::dag::*;
+use crate::parser::detect::{ProviderCapabilities, ProviderInfo};
 use anyhow::{Context, Result};
 use serde_yaml::Value;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 
 /// Parser for AWS CodePipeline definitions (JSON or YAML).
@@ -14,19 +16,39 @@ use std::path::Path;
 pub struct AwsCodePipelineParser;
 
 impl AwsCodePipelineParser {
+    /// Registry entry listed by `list-providers`.
+    pub const INFO: ProviderInfo = ProviderInfo {
+        provider: "aws-codepipeline",
+        name: "AWS CodePipeline",
+        file_patterns: &["codepipeline.json", "codepipeline.yml", "codepipeline.yaml"],
+        content_markers: &[],
+        capabilities: ProviderCapabilities {
+            matrix: false,
+            needs: false,
+            includes: false,
+            parallel: true,
+            services: false,
+        },
+    };
+
     /// Parse an AWS CodePipeline file.
-    pub fn parse_file(path: &Path) -> Result<PipelineDag> {
-        let content = std::fs::read_to_string(path)
-            .with_context(|| format!("Failed to read AWS CodePipeline file: {}", path.display()))?;
+    pub fn parse_file(path: &Path) -> Result<PipelineDag, PipelineError> {
+        let content = read_pipeline_file(path)?;
         Self::parse(&content, path.to_string_lossy().to_string())
     }
 
     /// Parse AWS CodePipeline content into a Pipeline DAG.
     ///
     /// Note: We use `serde_yaml::Value` intentionally because YAML parser can also decode JSON.
-    pub fn parse(content: &str, source_file: String) -> Result<PipelineDag> {
-        let parsed: Value =
-            serde_yaml::from_str(content).context("Failed to parse pipeline data")?;
+    /// JSON with comments or trailing commas is accepted too; `lint` warns about it.
+    pub fn parse(content: &str, source_file: String) -> Result<PipelineDag, PipelineError> {
+        Self::parse_source(content, source_file.clone())
+            .map_err(|e| PipelineError::parse("aws-codepipeline", &source_file, e))
+    }
+
+    fn parse_source(content: &str, source_file: String) -> Result<PipelineDag> {
+        let parsed: Value = crate::parser::json5::from_str_lenient(content)
+            .context("Failed to parse pipeline data")?;
         let pipeline = parsed.get("pipeline").unwrap_or(&parsed);
 
         let name = pipeline
@@ -68,22 +90,21 @@ impl AwsCodePipelineParser {
                 let action_id =
                     format!("{}-{}", sanitize_id(&stage_name), sanitize_id(&action_name));
 
-                let run_order = action_value
-                    .get("runOrder")
-                    .and_then(|v| v.as_u64())
-                    .unwrap_or(1) as u32;
+                let run_order = parse_run_order(action_value.get("runOrder"));
                 run_order_groups
                     .entry(run_order)
                     .or_default()
                     .push(action_id.clone());
 
-                let job = Self::parse_action(
+                let mut job = Self::parse_action(
                     &action_id,
                     &action_name,
                     &stage_name,
                     action_idx,
                     action_value,
                 );
+                job.env
+                    .insert("__run_order".to_string(), run_order.to_string());
                 dag.add_job(job);
                 all_ids.push(action_id);
             }
@@ -96,7 +117,9 @@ impl AwsCodePipelineParser {
         }
 
         // Dependencies:
-        // 1) Inside a stage, actions with higher runOrder depend on all lower runOrder actions.
+        // 1) Inside a stage, actions sharing a runOrder run in parallel; actions with a
+        //    higher runOrder depend on all lower runOrder actions, so a stage takes the
+        //    sum over run orders of the slowest action in each.
         // 2) Stage N+1 actions depend on all actions in stage N.
         for stage in &stage_actions {
             let sorted_orders: Vec<u32> = stage.run_order_groups.keys().copied().collect();
@@ -218,6 +241,9 @@ impl AwsCodePipelineParser {
             uses: Some(format!("{}::{}", owner, provider)),
             run: Some(step_run),
             estimated_duration_secs: Some(estimate_action_duration(category, provider)),
+            with: HashMap::new(),
+            working_directory: None,
+            env: HashMap::new(),
         });
 
         job.estimated_duration_secs = job
@@ -256,6 +282,17 @@ fn parse_artifacts(value: Option<&Value>) -> Vec<String> {
         .collect()
 }
 
+/// `runOrder` defaults to 1 and may be written as a number or a numeric string.
+fn parse_run_order(value: Option<&Value>) -> u32 {
+    value
+        .and_then(|v| {
+            v.as_u64()
+                .or_else(|| v.as_str().and_then(|s| s.trim().parse().ok()))
+        })
+        .map(|order| order.max(1) as u32)
+        .unwrap_or(1)
+}
+
 fn estimate_action_duration(category: &str, provider: &str) -> f64 {
     let category = category.to_lowercase();
     let provider = provider.to_lowercase();
@@ -309,6 +346,50 @@ fn sanitize_id(value: &str) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_json_with_comments_and_trailing_commas() {
+        let config = r#"{
+  // generated by the deploy tooling
+  "pipeline": {
+    "name": "LenientPipeline",
+    "stages": [
+      {
+        "name": "Source",
+        "actions": [
+          {
+            "name": "SourceAction",
+            "actionTypeId": { "category": "Source", "owner": "AWS", "provider": "CodeCommit", "version": "1" },
+            "outputArtifacts": [{ "name": "SourceOutput" },],
+            "runOrder": 1,
+          },
+        ],
+      },
+      {
+        "name": "Build", /* single action */
+        "actions": [
+          {
+            "name": "Build",
+            "actionTypeId": { "category": "Build", "owner": "AWS", "provider": "CodeBuild", "version": "1" },
+            "inputArtifacts": [{ "name": "SourceOutput" }],
+            "runOrder": 1,
+          },
+        ],
+      },
+    ],
+  },
+}"#;
+        let dag = AwsCodePipelineParser::parse(config, "pipeline.json".to_string()).unwrap();
+        assert_eq!(dag.name, "LenientPipeline");
+        assert_eq!(dag.job_count(), 2);
+
+        let report = crate::linter::lint(config, &dag);
+        assert!(report
+            .findings
+            .iter()
+            .any(|f| f.rule_id == "PLX-LINT-JSON5"
+                && f.severity == crate::linter::LintSeverity::Warning));
+    }
+
     #[test]
     fn test_parse_stage_action_dependencies() {
         let config = r#"
@@ -384,4 +465,66 @@ pipeline:
             .get("output_artifacts")
             .is_some_and(|v| v.contains("Src")));
     }
+
+    #[test]
+    fn test_run_order_parallel_and_serial_edges() {
+        let config = r#"
+pipeline:
+  name: Deploy
+  stages:
+    - name: Source
+      actions:
+        - name: Fetch
+          actionTypeId: { category: Source, owner: AWS, provider: S3 }
+    - name: Deploy
+      actions:
+        - name: DeployApi
+          actionTypeId: { category: Deploy, owner: AWS, provider: CodeDeploy }
+          runOrder: 1
+        - name: DeployWorker
+          actionTypeId: { category: Deploy, owner: AWS, provider: CloudFormation }
+          runOrder: "1"
+        - name: SmokeTest
+          actionTypeId: { category: Invoke, owner: AWS, provider: Lambda }
+          runOrder: 2
+"#;
+
+        let dag = AwsCodePipelineParser::parse(config, "codepipeline.yml".to_string()).unwrap();
+        let idx = |id: &str| dag.node_map[id];
+
+        // Same runOrder: parallel siblings, no edge either way.
+        assert!(dag
+            .graph
+            .find_edge(idx("deploy-deployapi"), idx("deploy-deployworker"))
+            .is_none());
+        assert!(dag
+            .graph
+            .find_edge(idx("deploy-deployworker"), idx("deploy-deployapi"))
+            .is_none());
+
+        // Higher runOrder waits for both.
+        for parallel in ["deploy-deployapi", "deploy-deployworker"] {
+            assert!(dag
+                .graph
+                .find_edge(idx(parallel), idx("deploy-smoketest"))
+                .is_some());
+            assert!(dag
+                .graph
+                .find_edge(idx("source-fetch"), idx(parallel))
+                .is_some());
+        }
+        assert_eq!(
+            dag.get_job("deploy-deployworker").unwrap().env["__run_order"],
+            "1"
+        );
+
+        // Stage time is max(runOrder 1) + runOrder 2, after the source stage.
+        let duration = |id: &str| dag.get_job(id).unwrap().estimated_duration_secs;
+        let expected = duration("source-fetch")
+            + duration("deploy-deployapi").max(duration("deploy-deployworker"))
+            + duration("deploy-smoketest");
+        let (_, total) = crate::analyzer::critical_path::find_critical_path(&dag);
+        assert_eq!(total, expected);
+        assert_eq!(dag.max_parallelism(), 2);
+    }
 }
@@ -1,4 +1,6 @@
+use crate::error::{read_pipeline_file, PipelineError};
 use crate::parser::dag::*;
+use crate::parser::detect::{ProviderCapabilities, ProviderInfo};
 use anyhow::{Context, Result};
 use serde_yaml::Value;
 use std::collections::{HashMap, HashSet};
@@ -13,15 +15,34 @@ use std::path::Path;
 pub struct AzurePipelinesParser;
 
 impl AzurePipelinesParser {
+    /// Registry entry listed by `list-providers`.
+    pub const INFO: ProviderInfo = ProviderInfo {
+        provider: "azure-pipelines",
+        name: "Azure Pipelines",
+        file_patterns: &["azure-pipelines.yml", "azure-pipelines.yaml"],
+        content_markers: &[],
+        capabilities: ProviderCapabilities {
+            matrix: false,
+            needs: true,
+            includes: false,
+            parallel: false,
+            services: false,
+        },
+    };
+
     /// Parse an Azure Pipelines file into a Pipeline DAG.
-    pub fn parse_file(path: &Path) -> Result<PipelineDag> {
-        let content = std::fs::read_to_string(path)
-            .with_context(|| format!("Failed to read Azure Pipelines file: {}", path.display()))?;
+    pub fn parse_file(path: &Path) -> Result<PipelineDag, PipelineError> {
+        let content = read_pipeline_file(path)?;
         Self::parse(&content, path.to_string_lossy().to_string())
     }
 
     /// Parse Azure Pipelines YAML content into a Pipeline DAG.
-    pub fn parse(content: &str, source_file: String) -> Result<PipelineDag> {
+    pub fn parse(content: &str, source_file: String) -> Result<PipelineDag, PipelineError> {
+        Self::parse_source(content, source_file.clone())
+            .map_err(|e| PipelineError::parse("azure-pipelines", &source_file, e))
+    }
+
+    fn parse_source(content: &str, source_file: String) -> Result<PipelineDag> {
         let yaml: Value = serde_yaml::from_str(content).context("Failed to parse YAML")?;
 
         let name = yaml
@@ -53,6 +74,9 @@ impl AzurePipelinesParser {
                 uses: Some(template.to_string()),
                 run: None,
                 estimated_duration_secs: Some(5.0),
+                with: HashMap::new(),
+                working_directory: None,
+                env: HashMap::new(),
             });
             job.estimated_duration_secs = 5.0;
             dag.add_job(job);
@@ -98,6 +122,9 @@ impl AzurePipelinesParser {
                 uses: Some(template.to_string()),
                 run: None,
                 estimated_duration_secs: Some(5.0),
+                with: HashMap::new(),
+                working_directory: None,
+                env: HashMap::new(),
             });
             job.estimated_duration_secs = 5.0;
             dag.add_job(job);
@@ -174,6 +201,9 @@ impl AzurePipelinesParser {
                 uses: Some(template_path.to_string()),
                 run: None,
                 estimated_duration_secs: Some(5.0),
+                with: HashMap::new(),
+                working_directory: None,
+                env: HashMap::new(),
             });
             job.estimated_duration_secs = 5.0;
             dag.add_job(job);
@@ -205,6 +235,11 @@ impl AzurePipelinesParser {
                     job_aliases,
                 )?;
                 raw_job_needs.insert(parsed.id.clone(), parsed.depends_on_raw.clone());
+                if let Some(&idx) = dag.node_map.get(&parsed.id) {
+                    dag.graph[idx]
+                        .env
+                        .insert("__stage".to_string(), stage_name.clone());
+                }
                 job_ids.push(parsed.id);
             }
         }
@@ -223,6 +258,9 @@ impl AzurePipelinesParser {
                 uses: None,
                 run: Some(format!("stage: {}", stage_name)),
                 estimated_duration_secs: Some(30.0),
+                with: HashMap::new(),
+                working_directory: None,
+                env: HashMap::new(),
             });
             job.estimated_duration_secs = 30.0;
             dag.add_job(job);
@@ -261,6 +299,9 @@ impl AzurePipelinesParser {
                 uses: Some(template_path.to_string()),
                 run: None,
                 estimated_duration_secs: Some(5.0),
+                with: HashMap::new(),
+                working_directory: None,
+                env: HashMap::new(),
             });
             job.estimated_duration_secs = 5.0;
             dag.add_job(job);
@@ -336,15 +377,15 @@ impl AzurePipelinesParser {
             };
 
             match trigger_val {
-                Value::String(s) => {
-                    if s != "none" {
-                        triggers.push(WorkflowTrigger {
-                            event: key.to_string(),
-                            branches: Some(vec![s.clone()]),
-                            paths: None,
-                            paths_ignore: None,
-                        });
-                    }
+                Value::String(s) if s != "none" => {
+                    triggers.push(WorkflowTrigger {
+                        event: key.to_string(),
+                        branches: Some(vec![s.clone()]),
+                        paths: None,
+                        paths_ignore: None,
+                        tags: None,
+                        schedules: Vec::new(),
+                    });
                 }
                 Value::Sequence(seq) => {
                     let branches: Vec<String> = seq
@@ -360,6 +401,8 @@ impl AzurePipelinesParser {
                         },
                         paths: None,
                         paths_ignore: None,
+                        tags: None,
+                        schedules: Vec::new(),
                     });
                 }
                 Value::Mapping(map) => {
@@ -401,6 +444,8 @@ impl AzurePipelinesParser {
                         branches,
                         paths,
                         paths_ignore,
+                        tags: None,
+                        schedules: Vec::new(),
                     });
                 }
                 _ => {}
@@ -507,6 +552,9 @@ fn extract_steps(job_value: &Value) -> Vec<StepInfo> {
             uses: None,
             run: Some("azure job".to_string()),
             estimated_duration_secs: Some(60.0),
+            with: HashMap::new(),
+            working_directory: None,
+            env: HashMap::new(),
         }];
     };
 
@@ -517,7 +565,10 @@ fn extract_steps(job_value: &Value) -> Vec<StepInfo> {
                 name: "script".to_string(),
                 uses: None,
                 run: Some(cmd.clone()),
-                estimated_duration_secs: Some(estimate_cmd_duration(cmd)),
+                estimated_duration_secs: Some(crate::estimation::command_secs(cmd)),
+                with: HashMap::new(),
+                working_directory: None,
+                env: HashMap::new(),
             }),
             Value::Mapping(_) => {
                 if let Some(script) = step.get("script").and_then(|v| v.as_str()) {
@@ -529,21 +580,30 @@ fn extract_steps(job_value: &Value) -> Vec<StepInfo> {
                             .to_string(),
                         uses: None,
                         run: Some(script.to_string()),
-                        estimated_duration_secs: Some(estimate_cmd_duration(script)),
+                        estimated_duration_secs: Some(crate::estimation::command_secs(script)),
+                        with: HashMap::new(),
+                        working_directory: None,
+                        env: HashMap::new(),
                     });
                 } else if let Some(bash) = step.get("bash").and_then(|v| v.as_str()) {
                     parsed.push(StepInfo {
                         name: "bash".to_string(),
                         uses: None,
                         run: Some(bash.to_string()),
-                        estimated_duration_secs: Some(estimate_cmd_duration(bash)),
+                        estimated_duration_secs: Some(crate::estimation::command_secs(bash)),
+                        with: HashMap::new(),
+                        working_directory: None,
+                        env: HashMap::new(),
                     });
                 } else if let Some(pwsh) = step.get("pwsh").and_then(|v| v.as_str()) {
                     parsed.push(StepInfo {
                         name: "pwsh".to_string(),
                         uses: None,
                         run: Some(pwsh.to_string()),
-                        estimated_duration_secs: Some(estimate_cmd_duration(pwsh)),
+                        estimated_duration_secs: Some(crate::estimation::command_secs(pwsh)),
+                        with: HashMap::new(),
+                        working_directory: None,
+                        env: HashMap::new(),
                     });
                 } else if let Some(task) = step.get("task").and_then(|v| v.as_str()) {
                     parsed.push(StepInfo {
@@ -555,6 +615,9 @@ fn extract_steps(job_value: &Value) -> Vec<StepInfo> {
                         uses: Some(task.to_string()),
                         run: None,
                         estimated_duration_secs: Some(estimate_task_duration(task)),
+                        with: HashMap::new(),
+                        working_directory: None,
+                        env: HashMap::new(),
                     });
                 } else if let Some(template) = step.get("template").and_then(|v| v.as_str()) {
                     parsed.push(StepInfo {
@@ -562,6 +625,9 @@ fn extract_steps(job_value: &Value) -> Vec<StepInfo> {
                         uses: Some(template.to_string()),
                         run: None,
                         estimated_duration_secs: Some(5.0),
+                        with: HashMap::new(),
+                        working_directory: None,
+                        env: HashMap::new(),
                     });
                 } else {
                     parsed.push(StepInfo {
@@ -569,6 +635,9 @@ fn extract_steps(job_value: &Value) -> Vec<StepInfo> {
                         uses: None,
                         run: Some("azure step".to_string()),
                         estimated_duration_secs: Some(20.0),
+                        with: HashMap::new(),
+                        working_directory: None,
+                        env: HashMap::new(),
                     });
                 }
             }
@@ -582,6 +651,9 @@ fn extract_steps(job_value: &Value) -> Vec<StepInfo> {
             uses: None,
             run: Some("azure job".to_string()),
             estimated_duration_secs: Some(60.0),
+            with: HashMap::new(),
+            working_directory: None,
+            env: HashMap::new(),
         });
     }
 
@@ -627,32 +699,6 @@ fn estimate_task_duration(task: &str) -> f64 {
     30.0
 }
 
-fn estimate_cmd_duration(cmd: &str) -> f64 {
-    let cmd = cmd.to_lowercase();
-    if cmd.contains("npm ci") || cmd.contains("npm install") {
-        return 180.0;
-    }
-    if cmd.contains("yarn install") || cmd.contains("pnpm install") {
-        return 170.0;
-    }
-    if cmd.contains("pip install") {
-        return 140.0;
-    }
-    if cmd.contains("dotnet restore") {
-        return 120.0;
-    }
-    if cmd.contains("dotnet build") || cmd.contains("npm run build") {
-        return 240.0;
-    }
-    if cmd.contains("pytest") || cmd.contains("npm test") || cmd.contains("dotnet test") {
-        return 300.0;
-    }
-    if cmd.contains("docker build") {
-        return 260.0;
-    }
-    60.0
-}
-
 fn sanitize_id(value: &str) -> String {
     let mut out = String::with_capacity(value.len());
     let mut prev_dash = false;
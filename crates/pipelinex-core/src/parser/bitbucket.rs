@@ -1,26 +1,46 @@
-use crate::parser::dag::{CacheConfig, JobNode, PipelineDag, StepInfo};
+use crate::error::{read_pipeline_file, PipelineError};
+use crate::parser::dag::{CacheConfig, DataFlow, JobNode, PipelineDag, StepInfo};
+use crate::parser::detect::{ProviderCapabilities, ProviderInfo};
 use anyhow::{Context, Result};
 use serde_yaml::Value;
 use std::collections::HashMap;
 use std::path::Path;
 
+/// Image Bitbucket uses when the pipeline does not declare one.
+const DEFAULT_IMAGE: &str = "atlassian/default-image";
+
 /// Parser for Bitbucket Pipelines configuration files (bitbucket-pipelines.yml).
 pub struct BitbucketParser;
 
 impl BitbucketParser {
+    /// Registry entry listed by `list-providers`.
+    pub const INFO: ProviderInfo = ProviderInfo {
+        provider: "bitbucket",
+        name: "Bitbucket Pipelines",
+        file_patterns: &["bitbucket-pipelines.yml", "bitbucket-pipelines.yaml"],
+        content_markers: &[],
+        capabilities: ProviderCapabilities {
+            matrix: false,
+            needs: false,
+            includes: false,
+            parallel: true,
+            services: false,
+        },
+    };
+
     /// Parse a Bitbucket Pipelines config from a file path.
-    pub fn parse_file(path: &Path) -> Result<PipelineDag> {
-        let content = std::fs::read_to_string(path).with_context(|| {
-            format!(
-                "Failed to read Bitbucket Pipelines file: {}",
-                path.display()
-            )
-        })?;
+    pub fn parse_file(path: &Path) -> Result<PipelineDag, PipelineError> {
+        let content = read_pipeline_file(path)?;
         Self::parse(&content, path.display().to_string())
     }
 
     /// Parse a Bitbucket Pipelines config from string content.
-    pub fn parse(content: &str, source: String) -> Result<PipelineDag> {
+    pub fn parse(content: &str, source: String) -> Result<PipelineDag, PipelineError> {
+        Self::parse_source(content, source.clone())
+            .map_err(|e| PipelineError::parse("bitbucket", &source, e))
+    }
+
+    fn parse_source(content: &str, source: String) -> Result<PipelineDag> {
         let yaml: Value =
             serde_yaml::from_str(content).context("Failed to parse Bitbucket Pipelines YAML")?;
 
@@ -34,7 +54,7 @@ impl BitbucketParser {
         let default_image = yaml
             .get("image")
             .and_then(|i| i.as_str())
-            .unwrap_or("atlassian/default-image")
+            .unwrap_or(DEFAULT_IMAGE)
             .to_string();
 
         let pipelines = yaml
@@ -204,6 +224,12 @@ impl BitbucketParser {
             env: HashMap::new(),
             paths_filter: None,
             paths_ignore: None,
+            container_image: (image != DEFAULT_IMAGE).then(|| image.clone()),
+            data_flow: DataFlow::default(),
+            continue_on_error: false,
+            working_directory: None,
+            permissions: None,
+            rules: Vec::new(),
         })
     }
 
@@ -217,7 +243,10 @@ impl BitbucketParser {
                         name: format!("Script {}", i + 1),
                         uses: None,
                         run: Some(cmd_str.to_string()),
-                        estimated_duration_secs: None,
+                        estimated_duration_secs: Some(crate::estimation::command_secs(cmd_str)),
+                        with: HashMap::new(),
+                        working_directory: None,
+                        env: HashMap::new(),
                     });
                 }
             }
@@ -277,6 +306,10 @@ impl BitbucketParser {
     }
 
     fn estimate_duration(name: &str, steps: &[StepInfo]) -> f64 {
+        if let Some(secs) = crate::estimation::estimates().steps_secs(steps) {
+            return secs;
+        }
+
         let name_lower = name.to_lowercase();
 
         let base = if name_lower.contains("deploy") {
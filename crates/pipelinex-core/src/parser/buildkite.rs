@@ -1,4 +1,6 @@
+use crate::error::{read_pipeline_file, PipelineError};
 use crate::parser::dag::*;
+use crate::parser::detect::{ProviderCapabilities, ProviderInfo};
 use anyhow::{Context, Result};
 use serde_yaml::Value;
 use std::collections::{HashMap, HashSet};
@@ -14,15 +16,34 @@ use std::path::Path;
 pub struct BuildkiteParser;
 
 impl BuildkiteParser {
+    /// Registry entry listed by `list-providers`.
+    pub const INFO: ProviderInfo = ProviderInfo {
+        provider: "buildkite",
+        name: "Buildkite",
+        file_patterns: &[".buildkite/pipeline.yml", ".buildkite/pipeline.yaml"],
+        content_markers: &[],
+        capabilities: ProviderCapabilities {
+            matrix: true,
+            needs: true,
+            includes: false,
+            parallel: true,
+            services: false,
+        },
+    };
+
     /// Parse a Buildkite pipeline file into a Pipeline DAG.
-    pub fn parse_file(path: &Path) -> Result<PipelineDag> {
-        let content = std::fs::read_to_string(path)
-            .with_context(|| format!("Failed to read Buildkite file: {}", path.display()))?;
+    pub fn parse_file(path: &Path) -> Result<PipelineDag, PipelineError> {
+        let content = read_pipeline_file(path)?;
         Self::parse(&content, path.to_string_lossy().to_string())
     }
 
     /// Parse Buildkite YAML content into a Pipeline DAG.
-    pub fn parse(content: &str, source_file: String) -> Result<PipelineDag> {
+    pub fn parse(content: &str, source_file: String) -> Result<PipelineDag, PipelineError> {
+        Self::parse_source(content, source_file.clone())
+            .map_err(|e| PipelineError::parse("buildkite", &source_file, e))
+    }
+
+    fn parse_source(content: &str, source_file: String) -> Result<PipelineDag> {
         let yaml: Value = serde_yaml::from_str(content).context("Failed to parse YAML")?;
 
         let name = yaml
@@ -148,6 +169,9 @@ fn parse_step(step: &Value, step_idx: usize, synthetic_idx: &mut usize) -> Resul
             uses: None,
             run: Some("buildkite step".to_string()),
             estimated_duration_secs: Some(45.0),
+            with: HashMap::new(),
+            working_directory: None,
+            env: HashMap::new(),
         });
     }
 
@@ -168,6 +192,8 @@ fn parse_step(step: &Value, step_idx: usize, synthetic_idx: &mut usize) -> Resul
             job.matrix = Some(MatrixStrategy {
                 variables: vars,
                 total_combinations: shards.len(),
+                fail_fast: None,
+                max_parallel: None,
             });
         }
     }
@@ -283,7 +309,10 @@ fn extract_command_steps(step: &Value) -> Vec<StepInfo> {
             name: "command".to_string(),
             uses: None,
             run: Some(command.to_string()),
-            estimated_duration_secs: Some(estimate_cmd_duration(command)),
+            estimated_duration_secs: Some(crate::estimation::command_secs(command)),
+            with: HashMap::new(),
+            working_directory: None,
+            env: HashMap::new(),
         });
     }
 
@@ -294,7 +323,10 @@ fn extract_command_steps(step: &Value) -> Vec<StepInfo> {
                     name: format!("command[{idx}]"),
                     uses: None,
                     run: Some(cmd.to_string()),
-                    estimated_duration_secs: Some(estimate_cmd_duration(cmd)),
+                    estimated_duration_secs: Some(crate::estimation::command_secs(cmd)),
+                    with: HashMap::new(),
+                    working_directory: None,
+                    env: HashMap::new(),
                 });
             }
         }
@@ -308,6 +340,9 @@ fn extract_command_steps(step: &Value) -> Vec<StepInfo> {
                     uses: Some(plugin_str.to_string()),
                     run: None,
                     estimated_duration_secs: Some(10.0),
+                    with: HashMap::new(),
+                    working_directory: None,
+                    env: HashMap::new(),
                 });
             }
         }
@@ -330,6 +365,9 @@ fn extract_plugin_steps(step: &Value) -> Vec<StepInfo> {
                     uses: Some(plugin.to_string()),
                     run: None,
                     estimated_duration_secs: Some(10.0),
+                    with: HashMap::new(),
+                    working_directory: None,
+                    env: HashMap::new(),
                 }),
                 Value::Mapping(map) => {
                     for (plugin_name, _) in map {
@@ -339,6 +377,9 @@ fn extract_plugin_steps(step: &Value) -> Vec<StepInfo> {
                                 uses: Some(plugin_name.to_string()),
                                 run: None,
                                 estimated_duration_secs: Some(10.0),
+                                with: HashMap::new(),
+                                working_directory: None,
+                                env: HashMap::new(),
                             });
                         }
                     }
@@ -354,6 +395,9 @@ fn extract_plugin_steps(step: &Value) -> Vec<StepInfo> {
                     uses: Some(plugin_name.to_string()),
                     run: None,
                     estimated_duration_secs: Some(10.0),
+                    with: HashMap::new(),
+                    working_directory: None,
+                    env: HashMap::new(),
                 });
             }
         }
@@ -410,35 +454,6 @@ fn detect_caches(steps: &[StepInfo], step: &Value) -> Vec<CacheConfig> {
     caches
 }
 
-fn estimate_cmd_duration(cmd: &str) -> f64 {
-    let cmd = cmd.to_lowercase();
-    if cmd.contains("npm ci") || cmd.contains("npm install") {
-        return 180.0;
-    }
-    if cmd.contains("yarn install") || cmd.contains("pnpm install") {
-        return 170.0;
-    }
-    if cmd.contains("pip install") {
-        return 140.0;
-    }
-    if cmd.contains("cargo build") || cmd.contains("go build") {
-        return 260.0;
-    }
-    if cmd.contains("npm run build") || cmd.contains("make build") {
-        return 240.0;
-    }
-    if cmd.contains("npm test") || cmd.contains("pytest") || cmd.contains("cargo test") {
-        return 300.0;
-    }
-    if cmd.contains("docker build") {
-        return 280.0;
-    }
-    if cmd.contains("deploy") {
-        return 180.0;
-    }
-    60.0
-}
-
 fn sanitize_id(value: &str) -> String {
     let mut out = String::with_capacity(value.len());
     let mut prev_dash = false;
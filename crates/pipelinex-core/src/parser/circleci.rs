@@ -1,4 +1,6 @@
-use crate::parser::dag::{CacheConfig, JobNode, PipelineDag, StepInfo};
+use crate::error::{read_pipeline_file, PipelineError};
+use crate::parser::dag::{CacheConfig, DataFlow, JobNode, PipelineDag, StepInfo};
+use crate::parser::detect::{ProviderCapabilities, ProviderInfo};
 use anyhow::{Context, Result};
 use serde_yaml::Value;
 use std::collections::HashMap;
@@ -8,15 +10,34 @@ use std::path::Path;
 pub struct CircleCIParser;
 
 impl CircleCIParser {
+    /// Registry entry listed by `list-providers`.
+    pub const INFO: ProviderInfo = ProviderInfo {
+        provider: "circleci",
+        name: "CircleCI",
+        file_patterns: &[".circleci/config.yml", ".circleci/config.yaml"],
+        content_markers: &[],
+        capabilities: ProviderCapabilities {
+            matrix: false,
+            needs: true,
+            includes: false,
+            parallel: false,
+            services: false,
+        },
+    };
+
     /// Parse a CircleCI config from a file path.
-    pub fn parse_file(path: &Path) -> Result<PipelineDag> {
-        let content = std::fs::read_to_string(path)
-            .with_context(|| format!("Failed to read CircleCI config file: {}", path.display()))?;
+    pub fn parse_file(path: &Path) -> Result<PipelineDag, PipelineError> {
+        let content = read_pipeline_file(path)?;
         Self::parse(&content, path.display().to_string())
     }
 
     /// Parse a CircleCI config from string content.
-    pub fn parse(content: &str, source: String) -> Result<PipelineDag> {
+    pub fn parse(content: &str, source: String) -> Result<PipelineDag, PipelineError> {
+        Self::parse_source(content, source.clone())
+            .map_err(|e| PipelineError::parse("circleci", &source, e))
+    }
+
+    fn parse_source(content: &str, source: String) -> Result<PipelineDag> {
         let yaml: Value = serde_yaml::from_str(content).context("Failed to parse CircleCI YAML")?;
 
         let mut dag = PipelineDag::new(
@@ -35,6 +56,13 @@ impl CircleCIParser {
             let job_name_str = job_name.as_str().unwrap_or("unknown").to_string();
             let steps = Self::extract_steps(job_spec);
             let docker_image = Self::extract_docker_image(job_spec);
+            let container_image = job_spec
+                .get("docker")
+                .and_then(|d| d.as_sequence())
+                .and_then(|d| d.first())
+                .and_then(|first| first.get("image"))
+                .and_then(|i| i.as_str())
+                .map(String::from);
             let estimated_duration = Self::estimate_duration(&job_name_str, &steps);
             let caches = Self::detect_caches(job_spec, &steps);
             let env = Self::extract_environment(job_spec);
@@ -52,6 +80,12 @@ impl CircleCIParser {
                 env,
                 paths_filter: None,
                 paths_ignore: None,
+                container_image,
+                data_flow: DataFlow::default(),
+                continue_on_error: false,
+                working_directory: None,
+                permissions: None,
+                rules: Vec::new(),
             };
 
             dag.add_job(job);
@@ -108,8 +142,13 @@ impl CircleCIParser {
                 steps.push(StepInfo {
                     name: step_name,
                     uses: None,
+                    estimated_duration_secs: run_cmd
+                        .as_deref()
+                        .map(crate::estimation::command_secs),
                     run: run_cmd,
-                    estimated_duration_secs: None,
+                    with: HashMap::new(),
+                    working_directory: None,
+                    env: HashMap::new(),
                 });
             }
         }
@@ -155,6 +194,10 @@ impl CircleCIParser {
     }
 
     fn estimate_duration(job_name: &str, steps: &[StepInfo]) -> f64 {
+        if let Some(secs) = crate::estimation::estimates().steps_secs(steps) {
+            return secs;
+        }
+
         let name_lower = job_name.to_lowercase();
 
         let base = if name_lower.contains("build") {
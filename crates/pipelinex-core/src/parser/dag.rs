@@ -1,7 +1,8 @@
+use crate::error::PipelineError;
 use petgraph::graph::{DiGraph, NodeIndex};
 use petgraph::Direction;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Represents a single step within a CI job.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -10,6 +11,16 @@ pub struct StepInfo {
     pub uses: Option<String>,
     pub run: Option<String>,
     pub estimated_duration_secs: Option<f64>,
+    /// Inputs passed to an action via `with:` (GitHub Actions).
+    #[serde(default)]
+    pub with: HashMap<String, String>,
+    /// Directory the step's commands run in, when the step sets its own
+    /// (`working-directory:`); otherwise see [`JobNode::working_directory`].
+    #[serde(default)]
+    pub working_directory: Option<String>,
+    /// Step-level `env:` (GitHub Actions); values are kept as written, expressions included.
+    #[serde(default)]
+    pub env: HashMap<String, String>,
 }
 
 /// Represents a cache configuration detected or recommended.
@@ -25,6 +36,22 @@ pub struct CacheConfig {
 pub struct MatrixStrategy {
     pub variables: HashMap<String, Vec<String>>,
     pub total_combinations: usize,
+    /// Explicit `fail-fast:` setting; `None` means the provider default.
+    #[serde(default)]
+    pub fail_fast: Option<bool>,
+    /// Cap on variants running at once (`max-parallel:`); `None` means unbounded.
+    #[serde(default)]
+    pub max_parallel: Option<usize>,
+}
+
+impl MatrixStrategy {
+    /// Number of back-to-back batches the variants run in under `max_parallel`.
+    pub fn waves(&self) -> usize {
+        match self.max_parallel {
+            Some(cap) if cap > 0 => self.total_combinations.div_ceil(cap).max(1),
+            _ => 1,
+        }
+    }
 }
 
 /// A node in the Pipeline DAG representing a single job.
@@ -42,6 +69,96 @@ pub struct JobNode {
     pub env: HashMap<String, String>,
     pub paths_filter: Option<Vec<String>>,
     pub paths_ignore: Option<Vec<String>>,
+    /// Container image the job runs in (`container:` / `image:`), if declared.
+    #[serde(default)]
+    pub container_image: Option<String>,
+    /// Outputs and artifacts the job produces and consumes.
+    #[serde(default)]
+    pub data_flow: DataFlow,
+    /// `continue-on-error: true` (GitLab `allow_failure: true`) — failures don't
+    /// fail the pipeline. Expressions (e.g. `${{ matrix.experimental }}`) are left `false`.
+    #[serde(default)]
+    pub continue_on_error: bool,
+    /// Default directory for the job's commands (`defaults.run.working-directory`
+    /// on the job, falling back to the workflow's), relative to the checkout.
+    #[serde(default)]
+    pub working_directory: Option<String>,
+    /// Job-level GitHub Actions `permissions:`; `None` inherits the workflow's.
+    #[serde(default)]
+    pub permissions: Option<Permissions>,
+    /// GitLab `rules:` in declaration order; the first matching rule decides.
+    #[serde(default)]
+    pub rules: Vec<JobRule>,
+}
+
+/// One entry of a GitLab `rules:` list.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct JobRule {
+    /// The `if:` expression, if any.
+    pub condition: Option<String>,
+    /// Paths from `changes:` (or `changes: paths:`); empty when the rule has none.
+    pub changes: Vec<String>,
+    /// The `when:` value (`on_success`, `manual`, `never`, ...), if set.
+    pub when: Option<String>,
+}
+
+impl JobRule {
+    /// Whether a match excludes the job (`when: never`).
+    pub fn excludes(&self) -> bool {
+        self.when.as_deref() == Some("never")
+    }
+}
+
+/// What a job hands to, and takes from, other jobs. Populated by parsers that
+/// can see it (currently GitHub Actions); empty otherwise.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DataFlow {
+    /// Names declared under the job's `outputs:`.
+    pub outputs: Vec<String>,
+    /// Artifact names uploaded by the job's steps.
+    pub artifacts_uploaded: Vec<String>,
+    /// Artifact names downloaded; `*` when a download fetches every artifact.
+    pub artifacts_downloaded: Vec<String>,
+    /// Jobs referenced through `needs.<job>.…` expressions (outputs or result).
+    pub needs_refs: Vec<String>,
+    /// `needs.<job>.outputs.<name>` expressions, in order of appearance.
+    #[serde(default)]
+    pub output_refs: Vec<OutputRef>,
+}
+
+/// A `${{ needs.<job>.outputs.<output> }}` reference.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutputRef {
+    pub job: String,
+    pub output: String,
+}
+
+impl DataFlow {
+    /// Whether this job uses anything `upstream` produces or refers to it explicitly.
+    pub fn consumes_from(&self, upstream_id: &str, upstream: &DataFlow) -> bool {
+        self.needs_refs.iter().any(|j| j == upstream_id)
+            || upstream.artifacts_uploaded.iter().any(|name| {
+                self.artifacts_downloaded
+                    .iter()
+                    .any(|d| d == "*" || d == name)
+            })
+    }
+
+    /// The edge kind for a `needs` dependency on `upstream_id`.
+    pub fn edge_from(&self, upstream_id: &str, upstream: &DataFlow) -> DagEdge {
+        if self.output_refs.iter().any(|r| r.job == upstream_id) {
+            DagEdge::Data
+        } else if self.consumes_from(upstream_id, upstream) {
+            DagEdge::Artifact
+        } else {
+            DagEdge::Dependency
+        }
+    }
+
+    /// Whether the job consumes any data from other jobs.
+    pub fn consumes_anything(&self) -> bool {
+        !self.needs_refs.is_empty() || !self.artifacts_downloaded.is_empty()
+    }
 }
 
 impl JobNode {
@@ -59,17 +176,41 @@ impl JobNode {
             env: HashMap::new(),
             paths_filter: None,
             paths_ignore: None,
+            container_image: None,
+            data_flow: DataFlow::default(),
+            continue_on_error: false,
+            working_directory: None,
+            permissions: None,
+            rules: Vec::new(),
         }
     }
+
+    /// Wall-clock duration of the job including all matrix variants.
+    ///
+    /// `estimated_duration_secs` is one variant; variants run in parallel up
+    /// to `max-parallel`, and the rest wait for a free slot.
+    pub fn wall_clock_secs(&self) -> f64 {
+        let waves = self.matrix.as_ref().map(|m| m.waves()).unwrap_or(1);
+        self.estimated_duration_secs * waves as f64
+    }
+
+    /// Directory `step`'s commands run in: its own `working-directory`, else the job default.
+    pub fn step_working_directory<'a>(&'a self, step: &'a StepInfo) -> Option<&'a str> {
+        step.working_directory
+            .as_deref()
+            .or(self.working_directory.as_deref())
+    }
 }
 
 /// Edge types in the Pipeline DAG.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DagEdge {
     /// Hard dependency — job B cannot start until job A completes.
     Dependency,
     /// Artifact dependency — job B needs artifacts from job A.
     Artifact,
+    /// Data dependency — job B reads job A's `outputs`.
+    Data,
 }
 
 /// Trigger event for the workflow.
@@ -79,6 +220,65 @@ pub struct WorkflowTrigger {
     pub branches: Option<Vec<String>>,
     pub paths: Option<Vec<String>>,
     pub paths_ignore: Option<Vec<String>>,
+    /// Tag filters of a `push` trigger; `Some` with no branches means the
+    /// trigger only fires for tags.
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    /// Cron expressions of a `schedule` trigger.
+    #[serde(default)]
+    pub schedules: Vec<String>,
+}
+
+/// Workflow-level `concurrency:` settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Concurrency {
+    pub group: String,
+    /// Whether a new run cancels the in-progress run of the same group. An
+    /// expression counts as cancelling, since it does so for some refs.
+    pub cancel_in_progress: bool,
+}
+
+/// An `include:` the parser could not merge into the pipeline (GitLab
+/// `remote:`, `project:`, `template:` or `component:` includes, or a missing
+/// local file). Its jobs are unknown, so it contributes none to the DAG.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExternalInclude {
+    /// Include type: `remote`, `project`, `template`, `component` or `local`.
+    pub kind: String,
+    pub location: String,
+}
+
+/// GitHub Actions `permissions:` granted to the `GITHUB_TOKEN`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Permissions {
+    /// `permissions: read-all`
+    ReadAll,
+    /// `permissions: write-all`
+    WriteAll,
+    /// Per-scope access (`contents: read`, `id-token: write`, ...). Scopes not
+    /// listed get no access; `permissions: {}` grants nothing.
+    Scoped(BTreeMap<String, String>),
+    /// Any other value, such as a `${{ ... }}` expression or a typo. The block
+    /// is still declared, but what it grants is unknown.
+    Other(String),
+}
+
+impl Permissions {
+    /// Access level (`read`, `write` or `none`) granted to `scope`, or
+    /// `unknown` for [`Permissions::Other`].
+    pub fn access(&self, scope: &str) -> &str {
+        match self {
+            Self::ReadAll => "read",
+            Self::WriteAll => "write",
+            Self::Scoped(scopes) => scopes.get(scope).map(String::as_str).unwrap_or("none"),
+            Self::Other(_) => "unknown",
+        }
+    }
+
+    /// Whether `scope` may be written.
+    pub fn grants_write(&self, scope: &str) -> bool {
+        self.access(scope) == "write"
+    }
 }
 
 /// The unified Pipeline DAG — the core data structure of PipelineX.
@@ -91,6 +291,11 @@ pub struct PipelineDag {
     pub graph: DiGraph<JobNode, DagEdge>,
     pub node_map: HashMap<String, NodeIndex>,
     pub env: HashMap<String, String>,
+    pub concurrency: Option<Concurrency>,
+    /// Workflow-level GitHub Actions `permissions:`, if declared.
+    pub permissions: Option<Permissions>,
+    /// Includes that were not resolved, in declaration order.
+    pub external_includes: Vec<ExternalInclude>,
 }
 
 impl PipelineDag {
@@ -103,9 +308,17 @@ impl PipelineDag {
             graph: DiGraph::new(),
             node_map: HashMap::new(),
             env: HashMap::new(),
+            concurrency: None,
+            permissions: None,
+            external_includes: Vec::new(),
         }
     }
 
+    /// `permissions:` in effect for `job`: its own block, else the workflow's.
+    pub fn effective_permissions<'a>(&'a self, job: &'a JobNode) -> Option<&'a Permissions> {
+        job.permissions.as_ref().or(self.permissions.as_ref())
+    }
+
     /// Add a job node to the DAG, returning its index.
     pub fn add_job(&mut self, job: JobNode) -> NodeIndex {
         let id = job.id.clone();
@@ -115,19 +328,46 @@ impl PipelineDag {
     }
 
     /// Add a dependency edge between two jobs.
-    pub fn add_dependency(&mut self, from_id: &str, to_id: &str) -> anyhow::Result<()> {
+    pub fn add_dependency(&mut self, from_id: &str, to_id: &str) -> Result<(), PipelineError> {
+        self.add_edge(from_id, to_id, DagEdge::Dependency)
+    }
+
+    /// Add an edge of the given kind between two jobs.
+    pub fn add_edge(
+        &mut self,
+        from_id: &str,
+        to_id: &str,
+        edge: DagEdge,
+    ) -> Result<(), PipelineError> {
         let from_idx = self
             .node_map
             .get(from_id)
-            .ok_or_else(|| anyhow::anyhow!("Job '{}' not found in DAG", from_id))?;
+            .ok_or_else(|| PipelineError::UnknownJob(from_id.to_string()))?;
         let to_idx = self
             .node_map
             .get(to_id)
-            .ok_or_else(|| anyhow::anyhow!("Job '{}' not found in DAG", to_id))?;
-        self.graph.add_edge(*from_idx, *to_idx, DagEdge::Dependency);
+            .ok_or_else(|| PipelineError::UnknownJob(to_id.to_string()))?;
+        self.graph.add_edge(*from_idx, *to_idx, edge);
         Ok(())
     }
 
+    /// Jobs in dependency order, or the jobs forming a cycle if there is none.
+    pub fn topological_order(&self) -> Result<Vec<NodeIndex>, PipelineError> {
+        petgraph::algo::toposort(&self.graph, None).map_err(|_| {
+            let mut jobs: Vec<String> = petgraph::algo::tarjan_scc(&self.graph)
+                .into_iter()
+                .filter(|component| {
+                    component.len() > 1
+                        || self.graph.find_edge(component[0], component[0]).is_some()
+                })
+                .flatten()
+                .map(|idx| self.graph[idx].id.clone())
+                .collect();
+            jobs.sort();
+            PipelineError::Cycle { jobs }
+        })
+    }
+
     /// Get all root jobs (jobs with no dependencies).
     pub fn root_jobs(&self) -> Vec<NodeIndex> {
         self.graph
@@ -169,6 +409,14 @@ impl PipelineDag {
         self.node_map.get(id).map(|idx| &self.graph[*idx])
     }
 
+    /// Kind of the edge from `from_id` to `to_id`, if the jobs are connected.
+    pub fn edge_between(&self, from_id: &str, to_id: &str) -> Option<DagEdge> {
+        let from = *self.node_map.get(from_id)?;
+        let to = *self.node_map.get(to_id)?;
+        let edge = self.graph.find_edge(from, to)?;
+        self.graph.edge_weight(edge).copied()
+    }
+
     /// Get all job IDs.
     pub fn job_ids(&self) -> Vec<String> {
         self.graph.node_weights().map(|j| j.id.clone()).collect()
@@ -176,16 +424,26 @@ impl PipelineDag {
 
     /// Compute the maximum parallelism (max number of jobs that can run concurrently).
     pub fn max_parallelism(&self) -> usize {
-        // BFS level-based approach: jobs at the same depth can run in parallel
-        let mut levels: HashMap<NodeIndex, usize> = HashMap::new();
-        let roots = self.root_jobs();
-
-        for root in &roots {
-            self.compute_levels(*root, 0, &mut levels);
+        // Level-based approach: jobs at the same depth can run in parallel.
+        // A job's depth is its longest dependency chain from a root, computed in
+        // topological order so each edge is visited once.
+        let Ok(order) = petgraph::algo::toposort(&self.graph, None) else {
+            return 0;
+        };
+        if order.is_empty() {
+            return 0;
         }
 
-        if levels.is_empty() {
-            return 0;
+        let mut levels: HashMap<NodeIndex, usize> = HashMap::new();
+        for node in &order {
+            let level = self
+                .graph
+                .neighbors_directed(*node, Direction::Incoming)
+                .filter_map(|pred| levels.get(&pred))
+                .map(|l| l + 1)
+                .max()
+                .unwrap_or(0);
+            levels.insert(*node, level);
         }
 
         let max_level = *levels.values().max().unwrap_or(&0);
@@ -196,23 +454,184 @@ impl PipelineDag {
         level_counts.into_iter().max().unwrap_or(0)
     }
 
-    fn compute_levels(
-        &self,
-        node: NodeIndex,
-        level: usize,
-        levels: &mut HashMap<NodeIndex, usize>,
-    ) {
-        let current = levels.entry(node).or_insert(0);
-        if level > *current {
-            *current = level;
-        }
-        let level = *levels.get(&node).unwrap();
-        let neighbors: Vec<_> = self
+    /// Stable hash of the pipeline's structure, as lowercase hex SHA-256.
+    ///
+    /// Covers the provider, triggers, workflow env, each job's configuration
+    /// and normalized steps, and the dependency edges. Jobs, edges and map
+    /// entries are sorted, so declaration order does not matter. Excluded:
+    /// the source file, the pipeline's display name, step names, duration
+    /// estimates and parser-internal `__` env keys. Unlike `std::hash`, the
+    /// value is stable across processes and platforms, so it can be persisted.
+    pub fn structural_hash(&self) -> String {
+        use sha2::{Digest, Sha256};
+        hex::encode(Sha256::digest(self.canonical_form().to_string()))
+    }
+
+    /// Whether two DAGs have the same structure, in the sense of
+    /// [`structural_hash`](Self::structural_hash).
+    pub fn structurally_eq(&self, other: &PipelineDag) -> bool {
+        self.canonical_form() == other.canonical_form()
+    }
+
+    fn canonical_form(&self) -> serde_json::Value {
+        use serde_json::json;
+        use std::collections::BTreeMap;
+
+        let env = |env: &HashMap<String, String>| -> BTreeMap<String, String> {
+            env.iter()
+                .filter(|(k, _)| !k.starts_with("__"))
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect()
+        };
+        let sorted = |items: &[String]| {
+            let mut items = items.to_vec();
+            items.sort();
+            items
+        };
+
+        let mut triggers: Vec<_> = self
+            .triggers
+            .iter()
+            .map(|t| json!([t.event, t.branches, t.paths, t.paths_ignore]))
+            .collect();
+        triggers.sort_by_key(|t| t.to_string());
+
+        let mut jobs: Vec<&JobNode> = self.graph.node_weights().collect();
+        jobs.sort_by(|a, b| a.id.cmp(&b.id));
+        let jobs: Vec<_> = jobs
+            .into_iter()
+            .map(|job| {
+                let steps: Vec<_> = job
+                    .steps
+                    .iter()
+                    .map(|step| {
+                        let with: BTreeMap<_, _> = step.with.iter().collect();
+                        json!({
+                            "uses": step.uses.as_deref().map(str::trim),
+                            "run": step.run.as_deref().map(normalize_script),
+                            "working_directory": step.working_directory,
+                            "with": with,
+                        })
+                    })
+                    .collect();
+                let matrix = job.matrix.as_ref().map(|m| {
+                    let variables: BTreeMap<_, _> = m.variables.iter().collect();
+                    json!({ "variables": variables, "fail_fast": m.fail_fast })
+                });
+                let mut caches: Vec<_> = job
+                    .caches
+                    .iter()
+                    .map(|c| json!([c.path, c.key_pattern, c.restore_keys]))
+                    .collect();
+                caches.sort_by_key(|c| c.to_string());
+                json!({
+                    "id": job.id,
+                    "name": job.name,
+                    "runs_on": job.runs_on,
+                    "container_image": job.container_image,
+                    "working_directory": job.working_directory,
+                    "condition": job.condition,
+                    "env": env(&job.env),
+                    "matrix": matrix,
+                    "caches": caches,
+                    "paths_filter": job.paths_filter.as_deref().map(sorted),
+                    "paths_ignore": job.paths_ignore.as_deref().map(sorted),
+                    "continue_on_error": job.continue_on_error,
+                    "steps": steps,
+                })
+            })
+            .collect();
+
+        let mut edges: Vec<(&str, &str)> = self
             .graph
-            .neighbors_directed(node, Direction::Outgoing)
+            .edge_indices()
+            .filter_map(|e| self.graph.edge_endpoints(e))
+            .map(|(from, to)| (self.graph[from].id.as_str(), self.graph[to].id.as_str()))
             .collect();
-        for neighbor in neighbors {
-            self.compute_levels(neighbor, level + 1, levels);
-        }
+        edges.sort();
+        edges.dedup();
+
+        json!({
+            "provider": self.provider,
+            "triggers": triggers,
+            "env": env(&self.env),
+            "jobs": jobs,
+            "edges": edges,
+        })
+    }
+}
+
+/// Trim each line of a script and drop blank ones, so indentation and
+/// trailing whitespace do not change the structural hash.
+fn normalize_script(run: &str) -> String {
+    run.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::github::GitHubActionsParser;
+
+    const CI: &str = r#"
+on: push
+jobs:
+  lint:
+    runs-on: ubuntu-latest
+    steps: [{ run: npm run lint }]
+  test:
+    runs-on: ubuntu-latest
+    steps: [{ run: npm test }]
+  deploy:
+    needs: [lint, test]
+    runs-on: ubuntu-latest
+    steps: [{ run: ./deploy.sh }]
+"#;
+
+    #[test]
+    fn test_structural_hash_ignores_declaration_order() {
+        let reordered = r#"
+on: push
+jobs:
+  deploy:
+    needs: [test, lint]
+    runs-on: ubuntu-latest
+    steps:
+      - name: Ship it
+        run: "  ./deploy.sh  "
+  test:
+    runs-on: ubuntu-latest
+    steps: [{ run: npm test }]
+  lint:
+    runs-on: ubuntu-latest
+    steps: [{ run: npm run lint }]
+"#;
+        let a = GitHubActionsParser::parse(CI, "a.yml".to_string()).unwrap();
+        let b = GitHubActionsParser::parse(reordered, "b.yml".to_string()).unwrap();
+        assert_eq!(a.structural_hash(), b.structural_hash());
+        assert!(a.structurally_eq(&b));
+        assert_eq!(a.structural_hash().len(), 64);
+    }
+
+    #[test]
+    fn test_structural_hash_changes_with_edges() {
+        let fewer_edges = CI.replace("needs: [lint, test]", "needs: [test]");
+        let a = GitHubActionsParser::parse(CI, "ci.yml".to_string()).unwrap();
+        let b = GitHubActionsParser::parse(&fewer_edges, "ci.yml".to_string()).unwrap();
+        assert_ne!(a.structural_hash(), b.structural_hash());
+        assert!(!a.structurally_eq(&b));
+    }
+
+    #[test]
+    fn test_structural_hash_changes_with_working_directory() {
+        let moved = CI.replace(
+            "steps: [{ run: ./deploy.sh }]",
+            "steps: [{ run: ./deploy.sh, working-directory: infra }]",
+        );
+        let a = GitHubActionsParser::parse(CI, "ci.yml".to_string()).unwrap();
+        let b = GitHubActionsParser::parse(&moved, "ci.yml".to_string()).unwrap();
+        assert_ne!(a.structural_hash(), b.structural_hash());
     }
 }
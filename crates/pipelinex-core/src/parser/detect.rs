@@ -0,0 +1,516 @@
+//! Provider routing: decide which parser handles a pipeline file.
+//!
+//! Rules are evaluated in order and the first match wins. They are grouped in
+//! three tiers of decreasing confidence:
+//!
+//! 1. Well-known file names and directories (`.gitlab-ci.yml`, `.circleci/`,
+//!    `.buildkite/pipeline.yml`, `.github/workflows/`, ...).
+//! 2. Content sniffing (`tekton.dev`, `argoproj.io`), sharing a single read of the file.
+//! 3. Loose path hints such as a `gitlab` or `jenkins` directory somewhere in the path.
+//!
+//! Anything left over is treated as GitHub Actions.
+
+use super::argo::ArgoWorkflowsParser;
+use super::aws_codepipeline::AwsCodePipelineParser;
+use super::azure::AzurePipelinesParser;
+use super::bitbucket::BitbucketParser;
+use super::buildkite::BuildkiteParser;
+use super::circleci::CircleCIParser;
+use super::dag::PipelineDag;
+use super::drone::DroneParser;
+use super::github::GitHubActionsParser;
+use super::gitlab::GitLabCIParser;
+use super::jenkins::JenkinsParser;
+use super::tekton::TektonParser;
+use crate::error::PipelineError;
+use std::cell::OnceCell;
+use std::path::Path;
+
+/// A pipeline file being routed, with the facts matchers inspect.
+pub struct PipelineSource<'a> {
+    pub path: &'a Path,
+    /// File name as written (case preserved).
+    pub filename: String,
+    /// Full path, lower-cased, with `/` separators.
+    pub path_lower: String,
+    content: OnceCell<Option<String>>,
+}
+
+impl<'a> PipelineSource<'a> {
+    pub fn new(path: &'a Path) -> Self {
+        Self {
+            path,
+            filename: path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("")
+                .to_string(),
+            path_lower: path.to_string_lossy().to_lowercase().replace('\\', "/"),
+            content: OnceCell::new(),
+        }
+    }
+
+    /// Build a source with content already in memory (no file read).
+    pub fn with_content(path: &'a Path, content: &str) -> Self {
+        let source = Self::new(path);
+        let _ = source.content.set(Some(content.to_string()));
+        source
+    }
+
+    /// File content, read at most once. `None` if the file cannot be read.
+    pub fn content(&self) -> Option<&str> {
+        self.content
+            .get_or_init(|| std::fs::read_to_string(self.path).ok())
+            .as_deref()
+    }
+
+    fn filename_is(&self, names: &[&str]) -> bool {
+        names.iter().any(|n| self.filename == *n)
+    }
+
+    /// Whether the file sits directly inside a directory with this name.
+    fn parent_is(&self, dir: &str) -> bool {
+        self.path
+            .parent()
+            .and_then(|p| p.file_name())
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.eq_ignore_ascii_case(dir))
+    }
+
+    fn has_token(&self, token: &str) -> bool {
+        self.path_lower
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .any(|part| part == token)
+    }
+}
+
+/// Features of a CI system that its parser models in the DAG.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct ProviderCapabilities {
+    /// Matrix or sharded jobs are expanded into a [`MatrixStrategy`](super::dag::MatrixStrategy).
+    pub matrix: bool,
+    /// Explicit job dependencies (`needs`, `dependsOn`, `requires`, ...) become edges.
+    pub needs: bool,
+    /// Included or templated config files are resolved.
+    pub includes: bool,
+    /// Explicit parallel blocks or `parallelism` settings are recognised.
+    pub parallel: bool,
+    /// Service containers attached to jobs are recorded.
+    pub services: bool,
+}
+
+/// A supported provider: what files it is routed from and what its parser handles.
+///
+/// Each parser declares its entry as an `INFO` constant; [`PROVIDERS`] collects them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct ProviderInfo {
+    /// Provider identifier, as reported in `PipelineDag::provider`.
+    pub provider: &'static str,
+    pub name: &'static str,
+    /// File globs routed to this parser.
+    pub file_patterns: &'static [&'static str],
+    /// Content that identifies files with generic names (e.g. `tekton.dev`).
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    pub content_markers: &'static [&'static str],
+    pub capabilities: ProviderCapabilities,
+}
+
+/// Every supported provider, in `list-providers` order.
+pub const PROVIDERS: &[ProviderInfo] = &[
+    GitHubActionsParser::INFO,
+    GitLabCIParser::INFO,
+    JenkinsParser::INFO,
+    CircleCIParser::INFO,
+    AzurePipelinesParser::INFO,
+    AwsCodePipelineParser::INFO,
+    BitbucketParser::INFO,
+    BuildkiteParser::INFO,
+    DroneParser::INFO,
+    TektonParser::INFO,
+    ArgoWorkflowsParser::INFO,
+];
+
+/// One routing rule: a matcher and the parser it selects.
+pub struct ProviderRule {
+    /// Provider identifier, as reported in `PipelineDag::provider`.
+    pub provider: &'static str,
+    pub matches: fn(&PipelineSource) -> bool,
+    pub parse: fn(&Path) -> Result<PipelineDag, PipelineError>,
+    /// Parse in-memory content, given a source name for reports.
+    pub parse_content: fn(&str, String) -> Result<PipelineDag, PipelineError>,
+}
+
+/// Routing rules in precedence order. The final rule always matches.
+pub const RULES: &[ProviderRule] = &[
+    // Tier 1: well-known file names and directories.
+    ProviderRule {
+        provider: "gitlab-ci",
+        matches: |s| s.filename_is(&[".gitlab-ci.yml", ".gitlab-ci.yaml"]),
+        parse: GitLabCIParser::parse_file,
+        parse_content: GitLabCIParser::parse,
+    },
+    ProviderRule {
+        provider: "jenkins",
+        matches: |s| {
+            s.filename == "Jenkinsfile"
+                || s.filename.ends_with(".jenkinsfile")
+                || s.filename.ends_with(".groovy")
+        },
+        parse: JenkinsParser::parse_file,
+        parse_content: JenkinsParser::parse,
+    },
+    ProviderRule {
+        provider: "circleci",
+        matches: |s| s.parent_is(".circleci"),
+        parse: CircleCIParser::parse_file,
+        parse_content: CircleCIParser::parse,
+    },
+    ProviderRule {
+        provider: "azure-pipelines",
+        matches: |s| s.filename_is(&["azure-pipelines.yml", "azure-pipelines.yaml"]),
+        parse: AzurePipelinesParser::parse_file,
+        parse_content: AzurePipelinesParser::parse,
+    },
+    ProviderRule {
+        provider: "aws-codepipeline",
+        matches: |s| {
+            s.filename_is(&["codepipeline.json", "codepipeline.yaml", "codepipeline.yml"])
+                || (s.filename == "pipeline.json" && s.path_lower.contains("codepipeline"))
+        },
+        parse: AwsCodePipelineParser::parse_file,
+        parse_content: AwsCodePipelineParser::parse,
+    },
+    ProviderRule {
+        provider: "bitbucket",
+        matches: |s| s.filename_is(&["bitbucket-pipelines.yml", "bitbucket-pipelines.yaml"]),
+        parse: BitbucketParser::parse_file,
+        parse_content: BitbucketParser::parse,
+    },
+    ProviderRule {
+        provider: "buildkite",
+        matches: |s| s.parent_is(".buildkite"),
+        parse: BuildkiteParser::parse_file,
+        parse_content: BuildkiteParser::parse,
+    },
+    ProviderRule {
+        provider: "drone",
+        matches: |s| {
+            s.filename_is(&[
+                ".drone.yml",
+                ".drone.yaml",
+                ".woodpecker.yml",
+                ".woodpecker.yaml",
+            ])
+        },
+        parse: DroneParser::parse_file,
+        parse_content: DroneParser::parse,
+    },
+    ProviderRule {
+        provider: "github-actions",
+        matches: |s| s.path_lower.contains(".github/workflows/"),
+        parse: GitHubActionsParser::parse_file,
+        parse_content: GitHubActionsParser::parse,
+    },
+    // Tier 2: content sniffing.
+    ProviderRule {
+        provider: "tekton",
+        matches: |s| s.content().is_some_and(is_tekton_content),
+        parse: TektonParser::parse_file,
+        parse_content: TektonParser::parse,
+    },
+    ProviderRule {
+        provider: "argo-workflows",
+        matches: |s| s.content().is_some_and(is_argo_content),
+        parse: ArgoWorkflowsParser::parse_file,
+        parse_content: ArgoWorkflowsParser::parse,
+    },
+    // Tier 3: loose path hints.
+    ProviderRule {
+        provider: "gitlab-ci",
+        matches: |s| s.path_lower.contains("gitlab"),
+        parse: GitLabCIParser::parse_file,
+        parse_content: GitLabCIParser::parse,
+    },
+    ProviderRule {
+        provider: "jenkins",
+        matches: |s| s.path_lower.contains("jenkins"),
+        parse: JenkinsParser::parse_file,
+        parse_content: JenkinsParser::parse,
+    },
+    ProviderRule {
+        provider: "circleci",
+        matches: |s| s.path_lower.contains("circleci"),
+        parse: CircleCIParser::parse_file,
+        parse_content: CircleCIParser::parse,
+    },
+    ProviderRule {
+        provider: "azure-pipelines",
+        matches: |s| s.path_lower.contains("azure-pipelines"),
+        parse: AzurePipelinesParser::parse_file,
+        parse_content: AzurePipelinesParser::parse,
+    },
+    ProviderRule {
+        provider: "aws-codepipeline",
+        matches: |s| s.path_lower.contains("aws-codepipeline"),
+        parse: AwsCodePipelineParser::parse_file,
+        parse_content: AwsCodePipelineParser::parse,
+    },
+    ProviderRule {
+        provider: "bitbucket",
+        matches: |s| s.path_lower.contains("bitbucket"),
+        parse: BitbucketParser::parse_file,
+        parse_content: BitbucketParser::parse,
+    },
+    ProviderRule {
+        provider: "buildkite",
+        matches: |s| s.path_lower.contains("buildkite"),
+        parse: BuildkiteParser::parse_file,
+        parse_content: BuildkiteParser::parse,
+    },
+    ProviderRule {
+        provider: "drone",
+        matches: |s| s.path_lower.contains("drone") || s.path_lower.contains("woodpecker"),
+        parse: DroneParser::parse_file,
+        parse_content: DroneParser::parse,
+    },
+    ProviderRule {
+        provider: "tekton",
+        matches: |s| s.has_token("tekton"),
+        parse: TektonParser::parse_file,
+        parse_content: TektonParser::parse,
+    },
+    ProviderRule {
+        provider: "argo-workflows",
+        matches: |s| s.has_token("argo") || s.has_token("argoproj"),
+        parse: ArgoWorkflowsParser::parse_file,
+        parse_content: ArgoWorkflowsParser::parse,
+    },
+    // Fallback.
+    ProviderRule {
+        provider: "github-actions",
+        matches: |_| true,
+        parse: GitHubActionsParser::parse_file,
+        parse_content: GitHubActionsParser::parse,
+    },
+];
+
+/// Select the routing rule for a source.
+pub fn select_rule(source: &PipelineSource) -> &'static ProviderRule {
+    RULES
+        .iter()
+        .find(|rule| (rule.matches)(source))
+        .unwrap_or(&RULES[RULES.len() - 1])
+}
+
+/// Detect the provider for a pipeline file.
+pub fn detect_provider(path: &Path) -> &'static str {
+    select_rule(&PipelineSource::new(path)).provider
+}
+
+/// Parse a pipeline file with the parser its path and content route to.
+///
+/// Fails with [`PipelineError::Cycle`] if the parsed jobs depend on each other circularly.
+pub fn parse_pipeline_file(path: &Path) -> Result<PipelineDag, PipelineError> {
+    let rule = select_rule(&PipelineSource::new(path));
+    parse_with_rule(rule, path)
+}
+
+/// Parse a pipeline file with an explicitly named provider's parser.
+pub fn parse_pipeline_file_as(path: &Path, provider: &str) -> Result<PipelineDag, PipelineError> {
+    let rule = rule_for_provider(provider)?;
+    parse_with_rule(rule, path)
+}
+
+/// Look up the routing rule for a provider identifier (e.g. `gitlab-ci`).
+///
+/// The short form before the first `-` is accepted too (`github`, `gitlab`, `azure`).
+pub fn rule_for_provider(provider: &str) -> Result<&'static ProviderRule, PipelineError> {
+    let name = provider.trim();
+    RULES
+        .iter()
+        .find(|rule| {
+            rule.provider.eq_ignore_ascii_case(name)
+                || rule
+                    .provider
+                    .split('-')
+                    .next()
+                    .is_some_and(|short| short.eq_ignore_ascii_case(name))
+        })
+        .ok_or_else(|| PipelineError::UnsupportedProvider(provider.to_string()))
+}
+
+/// Parse in-memory content with an explicitly named provider's parser.
+pub fn parse_content_as(
+    content: &str,
+    source_file: &str,
+    provider: &str,
+) -> Result<PipelineDag, PipelineError> {
+    let rule = rule_for_provider(provider)?;
+    let dag = (rule.parse_content)(content, source_file.to_string())?;
+    dag.topological_order()?;
+    Ok(dag)
+}
+
+fn parse_with_rule(rule: &ProviderRule, path: &Path) -> Result<PipelineDag, PipelineError> {
+    let dag = (rule.parse)(path)?;
+    dag.topological_order()?;
+    Ok(dag)
+}
+
+/// Check if content looks like a Tekton resource.
+pub fn is_tekton_content(content: &str) -> bool {
+    content.contains("tekton.dev")
+        || (content.contains("kind: Pipeline") && content.contains("tasks:"))
+}
+
+/// Check if content looks like an Argo Workflows resource.
+pub fn is_argo_content(content: &str) -> bool {
+    content.contains("argoproj.io")
+        || (content.contains("kind: Workflow") && content.contains("entrypoint:"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider_for(path: &str, content: &str) -> &'static str {
+        select_rule(&PipelineSource::with_content(Path::new(path), content)).provider
+    }
+
+    const GHA: &str = "on: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n";
+
+    #[test]
+    fn test_buildkite_pipeline_under_ambiguous_parent() {
+        // `jenkins` in the path used to win over the `.buildkite/` convention.
+        assert_eq!(
+            provider_for("/home/jenkins/repo/.buildkite/pipeline.yml", "steps: []"),
+            "buildkite"
+        );
+        assert_eq!(
+            provider_for("repo/.buildkite/pipeline.yaml", "steps: []"),
+            "buildkite"
+        );
+    }
+
+    #[test]
+    fn test_github_workflow_in_gitlab_mirror() {
+        assert_eq!(
+            provider_for("/srv/gitlab-mirror/app/.github/workflows/ci.yml", GHA),
+            "github-actions"
+        );
+    }
+
+    #[test]
+    fn test_codepipeline_pipeline_json() {
+        assert_eq!(
+            provider_for("infra/codepipeline/pipeline.json", "{}"),
+            "aws-codepipeline"
+        );
+        // A plain `pipeline.json` elsewhere is not CodePipeline.
+        assert_eq!(
+            provider_for("infra/deploy/pipeline.json", "{}"),
+            "github-actions"
+        );
+        assert_eq!(provider_for("codepipeline.yml", "{}"), "aws-codepipeline");
+    }
+
+    #[test]
+    fn test_pipeline_yaml_routed_by_content() {
+        let tekton = "apiVersion: tekton.dev/v1\nkind: Pipeline\nspec:\n  tasks: []\n";
+        let argo = "apiVersion: argoproj.io/v1alpha1\nkind: Workflow\nspec:\n  entrypoint: main\n";
+        assert_eq!(provider_for("deploy/pipeline.yaml", tekton), "tekton");
+        assert_eq!(provider_for("deploy/pipeline.yaml", argo), "argo-workflows");
+        // Content beats a loose directory hint.
+        assert_eq!(provider_for("ci/jenkins/pipeline.yaml", tekton), "tekton");
+        assert_eq!(provider_for("deploy/pipeline.yaml", GHA), "github-actions");
+    }
+
+    #[test]
+    fn test_well_known_filenames() {
+        assert_eq!(provider_for(".gitlab-ci.yml", ""), "gitlab-ci");
+        assert_eq!(provider_for("Jenkinsfile", ""), "jenkins");
+        assert_eq!(provider_for(".circleci/config.yml", ""), "circleci");
+        assert_eq!(provider_for("azure-pipelines.yml", ""), "azure-pipelines");
+        assert_eq!(provider_for("bitbucket-pipelines.yml", ""), "bitbucket");
+        assert_eq!(provider_for(".woodpecker.yml", ""), "drone");
+    }
+
+    #[test]
+    fn test_loose_path_hints() {
+        assert_eq!(
+            provider_for("tests/fixtures/gitlab-ci/simple.yml", ""),
+            "gitlab-ci"
+        );
+        assert_eq!(
+            provider_for("tests/fixtures/buildkite/pipeline.yml", ""),
+            "buildkite"
+        );
+        assert_eq!(provider_for("tests/fixtures/tekton/run.yaml", ""), "tekton");
+        assert_eq!(
+            provider_for("tests/fixtures/argo/wf.yml", ""),
+            "argo-workflows"
+        );
+        // `argo` must be a whole path token, not a substring.
+        assert_eq!(provider_for("cargo/ci.yml", GHA), "github-actions");
+    }
+
+    #[test]
+    fn test_provider_registry_lists_patterns_and_capabilities() {
+        let github = PROVIDERS
+            .iter()
+            .find(|p| p.provider == "github-actions")
+            .unwrap();
+        assert_eq!(
+            github.file_patterns,
+            &[".github/workflows/*.yml", ".github/workflows/*.yaml"]
+        );
+        assert!(github.capabilities.matrix && github.capabilities.needs);
+        assert!(!github.capabilities.includes);
+
+        let gitlab = PROVIDERS
+            .iter()
+            .find(|p| p.provider == "gitlab-ci")
+            .unwrap();
+        assert_eq!(gitlab.file_patterns, &[".gitlab-ci.yml", ".gitlab-ci.yaml"]);
+        assert!(gitlab.capabilities.includes && gitlab.capabilities.parallel);
+
+        // Every routing rule resolves to a registered provider, and vice versa
+        for rule in RULES {
+            assert!(PROVIDERS.iter().any(|p| p.provider == rule.provider));
+        }
+        for info in PROVIDERS {
+            assert!(rule_for_provider(info.provider).is_ok());
+        }
+
+        let json = serde_json::to_value(gitlab).unwrap();
+        assert_eq!(json["capabilities"]["includes"], true);
+        assert!(json.get("content_markers").is_none());
+    }
+
+    #[test]
+    fn test_provider_override_bypasses_detection() {
+        let dir = tempfile::tempdir().unwrap();
+        let workflows = dir.path().join(".github/workflows");
+        std::fs::create_dir_all(&workflows).unwrap();
+        let path = workflows.join("ci.yml");
+        std::fs::write(
+            &path,
+            "stages: [build]\nbuild:\n  stage: build\n  script:\n    - make\n",
+        )
+        .unwrap();
+
+        assert_eq!(detect_provider(&path), "github-actions");
+        let dag = parse_pipeline_file_as(&path, "gitlab").unwrap();
+        assert_eq!(dag.provider, "gitlab-ci");
+        assert!(dag.get_job("build").is_some());
+
+        assert_eq!(
+            rule_for_provider("GitHub").unwrap().provider,
+            "github-actions"
+        );
+        assert!(matches!(
+            rule_for_provider("travis"),
+            Err(PipelineError::UnsupportedProvider(_))
+        ));
+    }
+}
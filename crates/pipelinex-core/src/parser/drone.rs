@@ -1,24 +1,51 @@
+use crate::error::{read_pipeline_file, PipelineError};
 use crate::parser::dag::*;
+use crate::parser::detect::{ProviderCapabilities, ProviderInfo};
 use anyhow::{Context, Result};
 use serde::Deserialize;
 use serde_yaml::Value;
+use std::collections::HashMap;
 use std::path::Path;
 
 /// Parser for Drone CI / Woodpecker CI (.drone.yml / .woodpecker.yml).
 pub struct DroneParser;
 
 impl DroneParser {
-    pub fn parse_file(path: &Path) -> Result<PipelineDag> {
-        let content = std::fs::read_to_string(path)
-            .with_context(|| format!("Failed to read Drone CI file: {}", path.display()))?;
+    /// Registry entry listed by `list-providers`.
+    pub const INFO: ProviderInfo = ProviderInfo {
+        provider: "drone",
+        name: "Drone CI / Woodpecker",
+        file_patterns: &[
+            ".drone.yml",
+            ".drone.yaml",
+            ".woodpecker.yml",
+            ".woodpecker.yaml",
+        ],
+        content_markers: &[],
+        capabilities: ProviderCapabilities {
+            matrix: false,
+            needs: true,
+            includes: false,
+            parallel: false,
+            services: false,
+        },
+    };
+
+    pub fn parse_file(path: &Path) -> Result<PipelineDag, PipelineError> {
+        let content = read_pipeline_file(path)?;
         Self::parse(&content, path.to_string_lossy().to_string())
     }
 
-    pub fn parse_content(content: &str, source_name: &str) -> Result<PipelineDag> {
+    pub fn parse_content(content: &str, source_name: &str) -> Result<PipelineDag, PipelineError> {
         Self::parse(content, source_name.to_string())
     }
 
-    pub fn parse(content: &str, source_file: String) -> Result<PipelineDag> {
+    pub fn parse(content: &str, source_file: String) -> Result<PipelineDag, PipelineError> {
+        Self::parse_source(content, source_file.clone())
+            .map_err(|e| PipelineError::parse("drone", &source_file, e))
+    }
+
+    fn parse_source(content: &str, source_file: String) -> Result<PipelineDag> {
         // Drone configs can contain multiple YAML documents (multi-pipeline)
         // Try parsing as multi-doc first
         let docs: Vec<Value> = serde_yaml::Deserializer::from_str(content)
@@ -100,12 +127,8 @@ impl DroneParser {
                     .unwrap_or("unnamed")
                     .to_string();
 
-                if let Some(deps) = step.get("depends_on").and_then(|v| v.as_sequence()) {
-                    for dep in deps {
-                        if let Some(dep_name) = dep.as_str() {
-                            let _ = dag.add_dependency(dep_name, &step_name);
-                        }
-                    }
+                for dep_name in Self::depends_on(step) {
+                    let _ = dag.add_dependency(&dep_name, &step_name);
                 }
             }
         } else {
@@ -116,7 +139,13 @@ impl DroneParser {
                 .collect();
 
             for i in 1..step_names.len() {
-                let _ = dag.add_dependency(&step_names[i - 1], &step_names[i]);
+                if dag
+                    .add_dependency(&step_names[i - 1], &step_names[i])
+                    .is_ok()
+                {
+                    let idx = dag.node_map[&step_names[i]];
+                    dag.graph[idx].needs.push(step_names[i - 1].clone());
+                }
             }
         }
 
@@ -218,6 +247,7 @@ impl DroneParser {
 
         let image = step.get("image").and_then(|v| v.as_str()).unwrap_or("");
         job.runs_on = format!("{} ({})", platform, image);
+        job.container_image = (!image.is_empty()).then(|| image.to_string());
 
         // Commands
         let commands = step
@@ -231,7 +261,10 @@ impl DroneParser {
                     name: cmd.to_string(),
                     uses: Some(image.to_string()),
                     run: Some(cmd.to_string()),
-                    estimated_duration_secs: Some(Self::estimate_command_duration(cmd)),
+                    estimated_duration_secs: Some(crate::estimation::command_secs(cmd)),
+                    with: HashMap::new(),
+                    working_directory: None,
+                    env: HashMap::new(),
                 });
             }
         } else {
@@ -241,6 +274,9 @@ impl DroneParser {
                 uses: Some(image.to_string()),
                 run: None,
                 estimated_duration_secs: Some(Self::estimate_plugin_duration(image)),
+                with: HashMap::new(),
+                working_directory: None,
+                env: HashMap::new(),
             });
         }
 
@@ -262,19 +298,16 @@ impl DroneParser {
             }
         }
 
-        // Conditions
+        // Conditions: event and branch filters become an evaluable expression
         if let Some(when) = step.get("when") {
-            let cond_str = serde_yaml::to_string(when).unwrap_or_default();
-            job.condition = Some(cond_str.trim().to_string());
+            job.condition = Self::when_condition(when).or_else(|| {
+                let cond_str = serde_yaml::to_string(when).unwrap_or_default();
+                Some(cond_str.trim().to_string())
+            });
         }
 
         // depends_on -> needs
-        if let Some(deps) = step.get("depends_on").and_then(|v| v.as_sequence()) {
-            job.needs = deps
-                .iter()
-                .filter_map(|v| v.as_str().map(String::from))
-                .collect();
-        }
+        job.needs = Self::depends_on(step);
 
         job.estimated_duration_secs = job
             .steps
@@ -309,7 +342,7 @@ impl DroneParser {
             });
 
         let duration = if let Some(ref cmds) = commands {
-            Self::estimate_command_duration(cmds)
+            crate::estimation::command_secs(cmds)
         } else if let Some(ref img) = image {
             Self::estimate_plugin_duration(img)
         } else {
@@ -321,7 +354,93 @@ impl DroneParser {
             uses: image,
             run: commands,
             estimated_duration_secs: Some(duration),
+            with: HashMap::new(),
+            working_directory: None,
+            env: HashMap::new(),
+        }
+    }
+
+    /// Step names listed in `depends_on` (a list, or a single name).
+    fn depends_on(step: &Value) -> Vec<String> {
+        match step.get("depends_on") {
+            Some(Value::String(name)) => vec![name.clone()],
+            Some(Value::Sequence(seq)) => seq
+                .iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Translate `when.event` / `when.branch` into a condition over
+    /// `$DRONE_BUILD_EVENT` and `$DRONE_BRANCH`, e.g.
+    /// `($DRONE_BUILD_EVENT == "push") && ($DRONE_BRANCH == "main")`.
+    ///
+    /// Returns `None` when the step filters on neither.
+    fn when_condition(when: &Value) -> Option<String> {
+        let clauses: Vec<String> = [("event", "DRONE_BUILD_EVENT"), ("branch", "DRONE_BRANCH")]
+            .into_iter()
+            .filter_map(|(key, var)| Self::filter_clause(when.get(key)?, var))
+            .collect();
+        (!clauses.is_empty()).then(|| clauses.join(" && "))
+    }
+
+    /// One `when` filter: a value, a list, or an `include`/`exclude` mapping.
+    fn filter_clause(filter: &Value, var: &str) -> Option<String> {
+        let values = |v: Option<&Value>| -> Vec<String> {
+            match v {
+                Some(Value::String(s)) => vec![s.clone()],
+                Some(Value::Sequence(seq)) => seq
+                    .iter()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect(),
+                _ => Vec::new(),
+            }
+        };
+        let (include, exclude) = match filter {
+            Value::Mapping(_) => (values(filter.get("include")), values(filter.get("exclude"))),
+            other => (values(Some(other)), Vec::new()),
+        };
+
+        let mut parts = Vec::new();
+        if !include.is_empty() {
+            let any: Vec<String> = include
+                .iter()
+                .map(|p| Self::matches(var, p, true))
+                .collect();
+            parts.push(format!("({})", any.join(" || ")));
+        }
+        parts.extend(exclude.iter().map(|p| Self::matches(var, p, false)));
+        (!parts.is_empty()).then(|| parts.join(" && "))
+    }
+
+    /// `$VAR == "value"`, or a regex match when `pattern` is a glob.
+    fn matches(var: &str, pattern: &str, positive: bool) -> String {
+        if !pattern.contains(['*', '?']) {
+            let op = if positive { "==" } else { "!=" };
+            return format!("${} {} \"{}\"", var, op, pattern);
+        }
+        let mut regex = String::from("^");
+        let mut chars = pattern.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '*' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    regex.push_str(".*");
+                }
+                '*' => regex.push_str("[^\\/]*"),
+                '?' => regex.push_str("[^\\/]"),
+                '/' => regex.push_str("\\/"),
+                c if c.is_ascii_alphanumeric() || c == '-' || c == '_' => regex.push(c),
+                c => {
+                    regex.push('\\');
+                    regex.push(c);
+                }
+            }
         }
+        regex.push('$');
+        let op = if positive { "=~" } else { "!~" };
+        format!("${} {} /{}/", var, op, regex)
     }
 
     fn parse_trigger(trigger: &Value) -> Vec<WorkflowTrigger> {
@@ -360,6 +479,8 @@ impl DroneParser {
                     branches: branches.clone(),
                     paths: None,
                     paths_ignore: None,
+                    tags: None,
+                    schedules: Vec::new(),
                 });
             }
         }
@@ -370,41 +491,14 @@ impl DroneParser {
                 branches: None,
                 paths: None,
                 paths_ignore: None,
+                tags: None,
+                schedules: Vec::new(),
             });
         }
 
         triggers
     }
 
-    fn estimate_command_duration(cmd: &str) -> f64 {
-        let lower = cmd.to_lowercase();
-        if lower.contains("npm install")
-            || lower.contains("npm ci")
-            || lower.contains("yarn install")
-            || lower.contains("pip install")
-        {
-            180.0
-        } else if lower.contains("cargo build") || lower.contains("go build") {
-            300.0
-        } else if lower.contains("npm run build") || lower.contains("yarn build") {
-            240.0
-        } else if lower.contains("npm test")
-            || lower.contains("pytest")
-            || lower.contains("cargo test")
-            || lower.contains("go test")
-        {
-            300.0
-        } else if lower.contains("lint") || lower.contains("eslint") || lower.contains("clippy") {
-            60.0
-        } else if lower.contains("docker build") {
-            300.0
-        } else if lower.contains("deploy") || lower.contains("kubectl") {
-            120.0
-        } else {
-            30.0
-        }
-    }
-
     fn estimate_plugin_duration(image: &str) -> f64 {
         let lower = image.to_lowercase();
         if lower.contains("docker") || lower.contains("ecr") || lower.contains("gcr") {
@@ -520,4 +614,66 @@ steps:
         assert_eq!(dag.triggers[0].event, "push");
         assert!(dag.triggers[0].branches.is_some());
     }
+
+    #[test]
+    fn test_step_depends_on_edges_and_when_filters() {
+        use crate::analyzer::conditions::{prune_unreachable_jobs, EvaluationContext};
+
+        let yaml = r#"
+kind: pipeline
+name: ci
+steps:
+  - name: build
+    image: golang:1.22
+    commands:
+      - go build ./...
+  - name: unit
+    image: golang:1.22
+    commands:
+      - go test ./...
+    depends_on: [build]
+  - name: vet
+    image: golang:1.22
+    commands:
+      - go vet ./...
+    depends_on: build
+  - name: publish
+    image: plugins/docker
+    depends_on: [unit, vet]
+    when:
+      event: [push, tag]
+      branch:
+        include: [main, release/*]
+        exclude: [release/legacy]
+"#;
+        let dag = DroneParser::parse(yaml, ".drone.yml".into()).unwrap();
+
+        assert!(dag.edge_between("build", "unit").is_some());
+        assert!(dag.edge_between("build", "vet").is_some());
+        assert!(dag.edge_between("unit", "publish").is_some());
+        assert!(dag.edge_between("vet", "publish").is_some());
+        // unit and vet form a parallel branch
+        assert!(dag.edge_between("unit", "vet").is_none());
+        assert!(dag.edge_between("vet", "unit").is_none());
+        assert_eq!(dag.max_parallelism(), 2);
+
+        let publish = dag.get_job("publish").unwrap();
+        assert_eq!(
+            publish.condition.as_deref(),
+            Some(
+                r#"($DRONE_BUILD_EVENT == "push" || $DRONE_BUILD_EVENT == "tag") && ($DRONE_BRANCH == "main" || $DRONE_BRANCH =~ /^release\/[^\/]*$/) && $DRONE_BRANCH != "release/legacy""#
+            )
+        );
+
+        let runs = |event: &str, git_ref: &str| {
+            let pruned =
+                prune_unreachable_jobs(&dag, &EvaluationContext::new(event, Some(git_ref)));
+            pruned.get_job("publish").is_some()
+        };
+        assert!(runs("push", "main"));
+        assert!(runs("push", "release/2.0"));
+        assert!(!runs("push", "release/legacy"));
+        assert!(!runs("push", "feature/x"));
+        assert!(!runs("pull_request", "feature/x"));
+    }
 }
@@ -1,4 +1,6 @@
+use crate::error::{read_pipeline_file, PipelineError};
 use crate::parser::dag::*;
+use crate::parser::detect::{ProviderCapabilities, ProviderInfo};
 use anyhow::{Context, Result};
 use serde_yaml::Value;
 use std::collections::HashMap;
@@ -8,20 +10,39 @@ use std::path::Path;
 pub struct GitHubActionsParser;
 
 impl GitHubActionsParser {
+    /// Registry entry listed by `list-providers`.
+    pub const INFO: ProviderInfo = ProviderInfo {
+        provider: "github-actions",
+        name: "GitHub Actions",
+        file_patterns: &[".github/workflows/*.yml", ".github/workflows/*.yaml"],
+        content_markers: &[],
+        capabilities: ProviderCapabilities {
+            matrix: true,
+            needs: true,
+            includes: false,
+            parallel: false,
+            services: false,
+        },
+    };
+
     /// Parse a GitHub Actions workflow file into a Pipeline DAG.
-    pub fn parse_file(path: &Path) -> Result<PipelineDag> {
-        let content = std::fs::read_to_string(path)
-            .with_context(|| format!("Failed to read workflow file: {}", path.display()))?;
+    pub fn parse_file(path: &Path) -> Result<PipelineDag, PipelineError> {
+        let content = read_pipeline_file(path)?;
         Self::parse(&content, path.to_string_lossy().to_string())
     }
 
     /// Parse GitHub Actions YAML content with a synthetic source file name.
-    pub fn parse_content(content: &str, source_name: &str) -> Result<PipelineDag> {
+    pub fn parse_content(content: &str, source_name: &str) -> Result<PipelineDag, PipelineError> {
         Self::parse(content, source_name.to_string())
     }
 
     /// Parse GitHub Actions YAML content into a Pipeline DAG.
-    pub fn parse(content: &str, source_file: String) -> Result<PipelineDag> {
+    pub fn parse(content: &str, source_file: String) -> Result<PipelineDag, PipelineError> {
+        Self::parse_source(content, source_file.clone())
+            .map_err(|e| PipelineError::parse("github-actions", &source_file, e))
+    }
+
+    fn parse_source(content: &str, source_file: String) -> Result<PipelineDag> {
         let yaml: Value = serde_yaml::from_str(content).context("Failed to parse YAML")?;
 
         let name = yaml
@@ -39,6 +60,8 @@ impl GitHubActionsParser {
         if let Some(env) = yaml.get("env") {
             dag.env = Self::parse_env(env);
         }
+        dag.concurrency = yaml.get("concurrency").and_then(Self::parse_concurrency);
+        dag.permissions = yaml.get("permissions").and_then(Self::parse_permissions);
 
         // Parse jobs
         let jobs = yaml
@@ -46,10 +69,15 @@ impl GitHubActionsParser {
             .and_then(|v| v.as_mapping())
             .context("No 'jobs' section found in workflow")?;
 
+        let default_working_directory = Self::default_working_directory(&yaml);
+
         // First pass: create all job nodes
         for (job_id, job_config) in jobs {
             let job_id = job_id.as_str().unwrap_or("unknown").to_string();
-            let job = Self::parse_job(&job_id, job_config)?;
+            let mut job = Self::parse_job(&job_id, job_config)?;
+            if job.working_directory.is_none() {
+                job.working_directory = default_working_directory.clone();
+            }
             dag.add_job(job);
         }
 
@@ -60,7 +88,13 @@ impl GitHubActionsParser {
             if let Some(needs) = job_config.get("needs") {
                 let deps = Self::parse_needs(needs);
                 for dep in deps {
-                    dag.add_dependency(&dep, &job_id)
+                    let edge = match (dag.get_job(&job_id), dag.get_job(&dep)) {
+                        (Some(job), Some(upstream)) => {
+                            job.data_flow.edge_from(&dep, &upstream.data_flow)
+                        }
+                        _ => DagEdge::Dependency,
+                    };
+                    dag.add_edge(&dep, &job_id, edge)
                         .with_context(|| format!("Failed to add dependency {dep} -> {job_id}"))?;
                 }
             }
@@ -84,6 +118,8 @@ impl GitHubActionsParser {
                     branches: None,
                     paths: None,
                     paths_ignore: None,
+                    tags: None,
+                    schedules: Vec::new(),
                 });
             }
             Value::Sequence(events) => {
@@ -94,6 +130,8 @@ impl GitHubActionsParser {
                             branches: None,
                             paths: None,
                             paths_ignore: None,
+                            tags: None,
+                            schedules: Vec::new(),
                         });
                     }
                 }
@@ -121,6 +159,11 @@ impl GitHubActionsParser {
                                 .filter_map(|v| v.as_str().map(String::from))
                                 .collect()
                         });
+                    let tags = config.get("tags").and_then(|v| v.as_sequence()).map(|seq| {
+                        seq.iter()
+                            .filter_map(|v| v.as_str().map(String::from))
+                            .collect()
+                    });
                     let paths_ignore = config
                         .get("paths-ignore")
                         .and_then(|v| v.as_sequence())
@@ -130,11 +173,24 @@ impl GitHubActionsParser {
                                 .collect()
                         });
 
+                    // `schedule: [{cron: '0 3 * * *'}, ...]`
+                    let schedules = config
+                        .as_sequence()
+                        .map(|seq| {
+                            seq.iter()
+                                .filter_map(|v| v.get("cron").and_then(|c| c.as_str()))
+                                .map(String::from)
+                                .collect()
+                        })
+                        .unwrap_or_default();
+
                     triggers.push(WorkflowTrigger {
                         event: event_name,
                         branches,
                         paths,
                         paths_ignore,
+                        tags,
+                        schedules,
                     });
                 }
             }
@@ -158,6 +214,15 @@ impl GitHubActionsParser {
             job.runs_on = runs_on.to_string();
         }
 
+        // container: either `image` shorthand or a mapping with `image:`
+        job.container_image = config
+            .get("container")
+            .and_then(|c| {
+                c.as_str()
+                    .or_else(|| c.get("image").and_then(|i| i.as_str()))
+            })
+            .map(String::from);
+
         // needs
         if let Some(needs) = config.get("needs") {
             job.needs = Self::parse_needs(needs);
@@ -173,6 +238,15 @@ impl GitHubActionsParser {
             job.env = Self::parse_env(env);
         }
 
+        job.working_directory = Self::default_working_directory(config);
+        job.permissions = config.get("permissions").and_then(Self::parse_permissions);
+
+        // continue-on-error (literal `true` only)
+        job.continue_on_error = config
+            .get("continue-on-error")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
         // matrix strategy
         if let Some(strategy) = config.get("strategy") {
             job.matrix = Self::parse_matrix(strategy);
@@ -189,12 +263,128 @@ impl GitHubActionsParser {
         // Detect caches in steps
         job.caches = Self::detect_caches(&job.steps);
 
+        job.data_flow = Self::parse_data_flow(config, &job.steps);
+
+        // With a dependency cache, installs and builds restore instead of starting cold
+        if !job.caches.is_empty() {
+            for step in &mut job.steps {
+                if let Some(secs) = step.run.as_deref().and_then(Self::cached_step_duration) {
+                    step.estimated_duration_secs = Some(secs);
+                }
+            }
+        }
+
         // Estimate duration based on step analysis
         job.estimated_duration_secs = Self::estimate_job_duration(&job);
 
         Ok(job)
     }
 
+    /// Record declared outputs, artifact uploads/downloads and `needs.<job>` references.
+    fn parse_data_flow(config: &Value, steps: &[StepInfo]) -> DataFlow {
+        let mut flow = DataFlow::default();
+
+        if let Some(outputs) = config.get("outputs").and_then(|v| v.as_mapping()) {
+            flow.outputs = outputs
+                .keys()
+                .filter_map(|k| k.as_str().map(String::from))
+                .collect();
+        }
+
+        for step in steps {
+            let Some(uses) = &step.uses else { continue };
+            if uses.starts_with("actions/upload-artifact") {
+                let name = step.with.get("name").map(String::as_str);
+                flow.artifacts_uploaded
+                    .push(name.unwrap_or("artifact").to_string());
+            } else if uses.starts_with("actions/download-artifact") {
+                // No name (or a pattern) downloads every matching artifact
+                let name = step
+                    .with
+                    .get("name")
+                    .filter(|_| !step.with.contains_key("pattern"));
+                flow.artifacts_downloaded
+                    .push(name.cloned().unwrap_or_else(|| "*".to_string()));
+            }
+        }
+
+        let needs_re = regex::Regex::new(r"needs\.([A-Za-z0-9_-]+)\.").unwrap();
+        let text = serde_yaml::to_string(config).unwrap_or_default();
+        for cap in needs_re.captures_iter(&text) {
+            let job = cap[1].to_string();
+            if !flow.needs_refs.contains(&job) {
+                flow.needs_refs.push(job);
+            }
+        }
+
+        let output_re =
+            regex::Regex::new(r"needs\.([A-Za-z0-9_-]+)\.outputs\.([A-Za-z0-9_-]+)").unwrap();
+        for cap in output_re.captures_iter(&text) {
+            let reference = OutputRef {
+                job: cap[1].to_string(),
+                output: cap[2].to_string(),
+            };
+            if !flow.output_refs.contains(&reference) {
+                flow.output_refs.push(reference);
+            }
+        }
+
+        flow
+    }
+
+    /// `concurrency: <group>` or `concurrency: {group, cancel-in-progress}`.
+    fn parse_concurrency(value: &Value) -> Option<Concurrency> {
+        match value {
+            Value::String(group) => Some(Concurrency {
+                group: group.clone(),
+                cancel_in_progress: false,
+            }),
+            Value::Mapping(_) => Some(Concurrency {
+                group: value
+                    .get("group")
+                    .and_then(|g| g.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+                cancel_in_progress: match value.get("cancel-in-progress") {
+                    Some(Value::Bool(cancel)) => *cancel,
+                    Some(Value::String(expr)) => expr.trim() != "false",
+                    _ => false,
+                },
+            }),
+            _ => None,
+        }
+    }
+
+    /// `permissions: read-all | write-all`, a `{scope: access}` mapping, or any
+    /// other string kept as [`Permissions::Other`].
+    fn parse_permissions(value: &Value) -> Option<Permissions> {
+        match value {
+            Value::String(level) => match level.trim() {
+                "read-all" => Some(Permissions::ReadAll),
+                "write-all" => Some(Permissions::WriteAll),
+                other => Some(Permissions::Other(other.to_string())),
+            },
+            Value::Mapping(map) => Some(Permissions::Scoped(
+                map.iter()
+                    .filter_map(|(scope, access)| {
+                        Some((scope.as_str()?.to_string(), access.as_str()?.to_string()))
+                    })
+                    .collect(),
+            )),
+            _ => None,
+        }
+    }
+
+    /// `defaults.run.working-directory` of a workflow or job.
+    fn default_working_directory(config: &Value) -> Option<String> {
+        config
+            .get("defaults")
+            .and_then(|d| d.get("run"))
+            .and_then(|r| r.get("working-directory"))
+            .and_then(|w| w.as_str())
+            .map(String::from)
+    }
+
     fn parse_needs(needs: &Value) -> Vec<String> {
         match needs {
             Value::String(s) => vec![s.clone()],
@@ -217,6 +407,13 @@ impl GitHubActionsParser {
 
         let run = step.get("run").and_then(|v| v.as_str()).map(String::from);
 
+        let with = step.get("with").map(Self::parse_with).unwrap_or_default();
+
+        let working_directory = step
+            .get("working-directory")
+            .and_then(|v| v.as_str())
+            .map(String::from);
+
         let estimated_duration = Self::estimate_step_duration(&uses, &run);
 
         StepInfo {
@@ -224,9 +421,31 @@ impl GitHubActionsParser {
             uses,
             run,
             estimated_duration_secs: Some(estimated_duration),
+            with,
+            working_directory,
+            env: step.get("env").map(Self::parse_env).unwrap_or_default(),
         }
     }
 
+    fn parse_with(with: &Value) -> HashMap<String, String> {
+        let mut map = HashMap::new();
+        if let Some(mapping) = with.as_mapping() {
+            for (k, v) in mapping {
+                let Some(key) = k.as_str() else {
+                    continue;
+                };
+                let val = match v {
+                    Value::String(s) => s.clone(),
+                    Value::Number(n) => n.to_string(),
+                    Value::Bool(b) => b.to_string(),
+                    _ => continue,
+                };
+                map.insert(key.to_string(), val);
+            }
+        }
+        map
+    }
+
     fn parse_env(env: &Value) -> HashMap<String, String> {
         let mut map = HashMap::new();
         if let Some(mapping) = env.as_mapping() {
@@ -270,6 +489,11 @@ impl GitHubActionsParser {
         Some(MatrixStrategy {
             variables,
             total_combinations: total,
+            fail_fast: strategy.get("fail-fast").and_then(|v| v.as_bool()),
+            max_parallel: strategy
+                .get("max-parallel")
+                .and_then(|v| v.as_u64())
+                .map(|n| n as usize),
         })
     }
 
@@ -278,10 +502,19 @@ impl GitHubActionsParser {
         for step in steps {
             if let Some(uses) = &step.uses {
                 if uses.starts_with("actions/cache") {
+                    let with = |key: &str| step.with.get(key).map(|v| v.trim().to_string());
                     caches.push(CacheConfig {
-                        path: "detected".to_string(),
-                        key_pattern: "detected".to_string(),
-                        restore_keys: Vec::new(),
+                        path: with("path").unwrap_or_else(|| "detected".to_string()),
+                        key_pattern: with("key").unwrap_or_else(|| "detected".to_string()),
+                        restore_keys: with("restore-keys")
+                            .map(|keys| {
+                                keys.lines()
+                                    .map(str::trim)
+                                    .filter(|k| !k.is_empty())
+                                    .map(String::from)
+                                    .collect()
+                            })
+                            .unwrap_or_default(),
                     });
                 }
             }
@@ -317,51 +550,30 @@ impl GitHubActionsParser {
         }
 
         if let Some(run) = run {
-            let cmd = run.to_lowercase();
-            if cmd.contains("npm install")
-                || cmd.contains("npm ci")
-                || cmd.contains("yarn install")
-                || cmd.contains("pnpm install")
-            {
-                return 180.0;
-            }
-            if cmd.contains("pip install") {
-                return 120.0;
-            }
-            if cmd.contains("cargo build") {
-                return 300.0;
-            }
-            if cmd.contains("npm run build")
-                || cmd.contains("yarn build")
-                || cmd.contains("pnpm build")
-            {
-                return 240.0;
-            }
-            if cmd.contains("npm test")
-                || cmd.contains("pytest")
-                || cmd.contains("cargo test")
-                || cmd.contains("jest")
-            {
-                return 300.0;
-            }
-            if cmd.contains("npm run lint") || cmd.contains("eslint") || cmd.contains("clippy") {
-                return 60.0;
-            }
-            if cmd.contains("docker build") {
-                return 300.0;
-            }
-            if cmd.contains("docker push") {
-                return 60.0;
-            }
-            if cmd.contains("deploy") || cmd.contains("kubectl") || cmd.contains("terraform") {
-                return 120.0;
-            }
-            return 30.0; // Generic command
+            return crate::estimation::command_secs(run);
         }
 
         10.0 // Unknown step
     }
 
+    /// Warm-cache estimate for steps that `actions/cache` speeds up, matching the
+    /// savings the cache detector attributes to adding a cache.
+    fn cached_step_duration(run: &str) -> Option<f64> {
+        let cmd = run.to_lowercase();
+        if cmd.contains("npm install")
+            || cmd.contains("npm ci")
+            || cmd.contains("yarn install")
+            || cmd.contains("pnpm install")
+            || cmd.contains("pip install")
+        {
+            Some(30.0)
+        } else if cmd.contains("cargo build") {
+            Some(60.0)
+        } else {
+            None
+        }
+    }
+
     fn estimate_job_duration(job: &JobNode) -> f64 {
         job.steps
             .iter()
@@ -451,4 +663,191 @@ jobs:
         let matrix = test_job.matrix.as_ref().unwrap();
         assert_eq!(matrix.total_combinations, 6);
     }
+
+    #[test]
+    fn test_parse_step_with_inputs() {
+        let yaml = r#"
+name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/setup-node@v4
+        with:
+          node-version: 20
+          cache: npm
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let step = &dag.get_job("build").unwrap().steps[0];
+        assert_eq!(
+            step.with.get("node-version").map(String::as_str),
+            Some("20")
+        );
+        assert_eq!(step.with.get("cache").map(String::as_str), Some("npm"));
+    }
+
+    #[test]
+    fn test_parse_working_directories() {
+        let yaml = r#"
+on: push
+defaults:
+  run:
+    working-directory: packages/app
+jobs:
+  app:
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm test
+      - run: npm run lint
+        working-directory: packages/app/web
+  api:
+    runs-on: ubuntu-latest
+    defaults:
+      run:
+        working-directory: services/api
+    steps:
+      - run: cargo test
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+
+        let app = dag.get_job("app").unwrap();
+        assert_eq!(app.working_directory.as_deref(), Some("packages/app"));
+        assert_eq!(app.steps[0].working_directory, None);
+        assert_eq!(
+            app.steps[1].working_directory.as_deref(),
+            Some("packages/app/web")
+        );
+        assert_eq!(
+            app.step_working_directory(&app.steps[0]),
+            Some("packages/app")
+        );
+
+        // Job defaults override the workflow's
+        let api = dag.get_job("api").unwrap();
+        assert_eq!(api.working_directory.as_deref(), Some("services/api"));
+    }
+
+    #[test]
+    fn test_parse_permissions() {
+        let yaml = r#"
+on: push
+permissions:
+  contents: read
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm test
+  release:
+    runs-on: ubuntu-latest
+    permissions: write-all
+    steps:
+      - run: npm publish
+  lint:
+    runs-on: ubuntu-latest
+    permissions: {}
+    steps:
+      - run: npm run lint
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+
+        let workflow = dag.permissions.as_ref().unwrap();
+        assert_eq!(
+            *workflow,
+            Permissions::Scoped([("contents".to_string(), "read".to_string())].into())
+        );
+        assert_eq!(workflow.access("contents"), "read");
+        assert_eq!(workflow.access("packages"), "none");
+
+        assert_eq!(dag.get_job("test").unwrap().permissions, None);
+        assert_eq!(
+            dag.get_job("release").unwrap().permissions,
+            Some(Permissions::WriteAll)
+        );
+        let lint = dag.get_job("lint").unwrap().permissions.as_ref().unwrap();
+        assert!(!lint.grants_write("contents"));
+        assert_eq!(lint.access("contents"), "none");
+    }
+
+    #[test]
+    fn test_expression_permissions_kept_as_declared() {
+        let yaml = r#"
+on: push
+jobs:
+  release:
+    runs-on: ubuntu-latest
+    permissions: ${{ inputs.permissions }}
+    steps:
+      - run: npm publish
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+
+        let release = dag
+            .get_job("release")
+            .unwrap()
+            .permissions
+            .as_ref()
+            .unwrap();
+        assert_eq!(
+            *release,
+            Permissions::Other("${{ inputs.permissions }}".to_string())
+        );
+        assert_eq!(release.access("contents"), "unknown");
+        assert!(!release.grants_write("contents"));
+    }
+
+    #[test]
+    fn test_output_references_mark_data_edges() {
+        let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    outputs:
+      version: ${{ steps.v.outputs.version }}
+    steps:
+      - id: v
+        run: echo "version=1.0" >> "$GITHUB_OUTPUT"
+      - uses: actions/upload-artifact@v4
+        with:
+          name: dist
+  publish:
+    runs-on: ubuntu-latest
+    needs: build
+    steps:
+      - run: ./publish.sh ${{ needs.build.outputs.version }}
+  package:
+    runs-on: ubuntu-latest
+    needs: build
+    steps:
+      - uses: actions/download-artifact@v4
+        with:
+          name: dist
+  notify:
+    runs-on: ubuntu-latest
+    needs: [publish, package]
+    steps:
+      - run: echo done
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+
+        let publish = dag.get_job("publish").unwrap();
+        assert_eq!(
+            publish.data_flow.output_refs,
+            vec![OutputRef {
+                job: "build".into(),
+                output: "version".into(),
+            }]
+        );
+        assert_eq!(dag.edge_between("build", "publish"), Some(DagEdge::Data));
+        assert_eq!(
+            dag.edge_between("build", "package"),
+            Some(DagEdge::Artifact)
+        );
+        assert_eq!(
+            dag.edge_between("publish", "notify"),
+            Some(DagEdge::Dependency)
+        );
+    }
 }
@@ -1,8 +1,10 @@
+use crate::error::{read_pipeline_file, PipelineError};
 use crate::parser::dag::*;
+use crate::parser::detect::{ProviderCapabilities, ProviderInfo};
 use anyhow::{Context, Result};
 use serde_yaml::Value;
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Parser for GitLab CI `.gitlab-ci.yml` files.
 pub struct GitLabCIParser;
@@ -23,21 +25,49 @@ const RESERVED_KEYWORDS: &[&str] = &[
 ];
 
 impl GitLabCIParser {
+    /// Registry entry listed by `list-providers`.
+    pub const INFO: ProviderInfo = ProviderInfo {
+        provider: "gitlab-ci",
+        name: "GitLab CI",
+        file_patterns: &[".gitlab-ci.yml", ".gitlab-ci.yaml"],
+        content_markers: &[],
+        capabilities: ProviderCapabilities {
+            matrix: true,
+            needs: true,
+            includes: true,
+            parallel: true,
+            services: false,
+        },
+    };
+
     /// Parse a GitLab CI file into a Pipeline DAG.
-    pub fn parse_file(path: &Path) -> Result<PipelineDag> {
-        let content = std::fs::read_to_string(path)
-            .with_context(|| format!("Failed to read GitLab CI file: {}", path.display()))?;
+    pub fn parse_file(path: &Path) -> Result<PipelineDag, PipelineError> {
+        let content = read_pipeline_file(path)?;
         Self::parse(&content, path.to_string_lossy().to_string())
     }
 
     /// Parse GitLab CI YAML content with a synthetic source file name.
-    pub fn parse_content(content: &str, source_name: &str) -> Result<PipelineDag> {
+    pub fn parse_content(content: &str, source_name: &str) -> Result<PipelineDag, PipelineError> {
         Self::parse(content, source_name.to_string())
     }
 
     /// Parse GitLab CI YAML content into a Pipeline DAG.
-    pub fn parse(content: &str, source_file: String) -> Result<PipelineDag> {
+    pub fn parse(content: &str, source_file: String) -> Result<PipelineDag, PipelineError> {
+        Self::parse_source(content, source_file.clone())
+            .map_err(|e| PipelineError::parse("gitlab-ci", &source_file, e))
+    }
+
+    fn parse_source(content: &str, source_file: String) -> Result<PipelineDag> {
         let yaml: Value = serde_yaml::from_str(content).context("Failed to parse YAML")?;
+        let mut unresolved = Vec::new();
+        let source_path = Path::new(&source_file);
+        let mut chain = vec![source_path
+            .canonicalize()
+            .unwrap_or_else(|_| source_path.to_path_buf())];
+        // `local:` includes resolve from the repository root at every level,
+        // taken to be the directory of the top-level file
+        let root = source_path.parent().unwrap_or(Path::new(""));
+        let yaml = Self::expand_includes(yaml, root, &mut chain, &mut unresolved)?;
 
         let mapping = yaml
             .as_mapping()
@@ -72,13 +102,7 @@ impl GitLabCIParser {
                 None => continue,
             };
 
-            // Skip reserved keywords and hidden jobs (starting with .)
-            if RESERVED_KEYWORDS.contains(&key_str) || key_str.starts_with('.') {
-                continue;
-            }
-
-            // Must be a mapping to be a job
-            if !value.is_mapping() {
+            if !Self::is_job_entry(key_str, value) {
                 continue;
             }
 
@@ -101,10 +125,7 @@ impl GitLabCIParser {
                 Some(k) => k,
                 None => continue,
             };
-            if RESERVED_KEYWORDS.contains(&key_str) || key_str.starts_with('.') {
-                continue;
-            }
-            if !value.is_mapping() {
+            if !Self::is_job_entry(key_str, value) {
                 continue;
             }
 
@@ -117,7 +138,8 @@ impl GitLabCIParser {
                     }
                 }
             } else {
-                // Otherwise, depend on all jobs from the previous stage
+                // Otherwise, depend on all jobs from the nearest earlier stage
+                // that has any (empty stages are skipped)
                 let job_stage = value
                     .get("stage")
                     .and_then(|v| v.as_str())
@@ -125,25 +147,159 @@ impl GitLabCIParser {
                     .to_string();
 
                 if let Some(stage_idx) = stages.iter().position(|s| s == &job_stage) {
-                    if stage_idx > 0 {
-                        let prev_stage = &stages[stage_idx - 1];
-                        if let Some(prev_jobs) = jobs_by_stage.get(prev_stage) {
-                            for prev_job in prev_jobs {
-                                let _ = dag.add_dependency(prev_job, key_str);
-                            }
-                        }
+                    let prev_jobs = stages[..stage_idx]
+                        .iter()
+                        .rev()
+                        .find_map(|stage| jobs_by_stage.get(stage).filter(|j| !j.is_empty()));
+                    for prev_job in prev_jobs.into_iter().flatten() {
+                        let _ = dag.add_dependency(prev_job, key_str);
                     }
                 }
             }
         }
 
+        for include in unresolved {
+            if !dag.external_includes.contains(&include) {
+                dag.external_includes.push(include);
+            }
+        }
+
         // Parse triggers from workflow:rules or just mark as generic
         dag.triggers = Self::parse_triggers(&yaml);
 
         Ok(dag)
     }
 
-    fn parse_stages(yaml: &Value) -> Vec<String> {
+    /// Merge `local:` includes into `yaml`, recursively and relative to the
+    /// repository `root`.
+    ///
+    /// Included files are merged in order and the including file wins on
+    /// conflicts, as in GitLab. The parser makes no network calls, so `remote:`,
+    /// `project:`, `template:` and `component:` includes (and missing local files)
+    /// are recorded in `unresolved` instead. `chain` holds the files being
+    /// expanded, to detect circular includes.
+    fn expand_includes(
+        yaml: Value,
+        root: &Path,
+        chain: &mut Vec<PathBuf>,
+        unresolved: &mut Vec<ExternalInclude>,
+    ) -> Result<Value> {
+        let entries = match yaml.get("include") {
+            Some(Value::Sequence(seq)) => seq.clone(),
+            Some(entry) => vec![entry.clone()],
+            None => return Ok(yaml),
+        };
+
+        let mut merged = Value::Mapping(Default::default());
+
+        for entry in &entries {
+            let (kind, location) = match entry {
+                Value::String(s) if s.starts_with("http://") || s.starts_with("https://") => {
+                    ("remote", s.clone())
+                }
+                Value::String(s) => ("local", s.clone()),
+                Value::Mapping(_) => match Self::include_location(entry) {
+                    Some(found) => found,
+                    None => continue,
+                },
+                _ => continue,
+            };
+            if kind != "local" {
+                unresolved.push(ExternalInclude {
+                    kind: kind.to_string(),
+                    location,
+                });
+                continue;
+            }
+
+            let files = Self::local_include_files(root, &location);
+            if files.is_empty() {
+                unresolved.push(ExternalInclude {
+                    kind: kind.to_string(),
+                    location,
+                });
+                continue;
+            }
+            for file in files {
+                let canonical = file.canonicalize().unwrap_or_else(|_| file.clone());
+                if chain.contains(&canonical) {
+                    let cycle: Vec<String> = chain
+                        .iter()
+                        .chain(std::iter::once(&canonical))
+                        .map(|p| p.display().to_string())
+                        .collect();
+                    anyhow::bail!("Circular include: {}", cycle.join(" -> "));
+                }
+                let content = std::fs::read_to_string(&file)
+                    .with_context(|| format!("Failed to read include {}", file.display()))?;
+                let included: Value = serde_yaml::from_str(&content)
+                    .with_context(|| format!("Failed to parse include {}", file.display()))?;
+
+                chain.push(canonical);
+                let included = Self::expand_includes(included, root, chain, unresolved)?;
+                chain.pop();
+                merge_yaml(&mut merged, included);
+            }
+        }
+
+        merge_yaml(&mut merged, yaml);
+        Ok(merged)
+    }
+
+    /// Include type and location of a mapping-form `include:` entry.
+    fn include_location(entry: &Value) -> Option<(&'static str, String)> {
+        let field = |key: &str| entry.get(key).and_then(|v| v.as_str()).map(String::from);
+        if let Some(local) = field("local") {
+            return Some(("local", local));
+        }
+        if let Some(remote) = field("remote") {
+            return Some(("remote", remote));
+        }
+        if let Some(template) = field("template") {
+            return Some(("template", template));
+        }
+        if let Some(component) = field("component") {
+            return Some(("component", component));
+        }
+        let project = field("project")?;
+        let files = match entry.get("file") {
+            Some(Value::Sequence(files)) => files
+                .iter()
+                .filter_map(|f| f.as_str())
+                .collect::<Vec<_>>()
+                .join(","),
+            Some(file) => file.as_str().unwrap_or_default().to_string(),
+            None => String::new(),
+        };
+        Some(("project", format!("{}:{}", project, files)))
+    }
+
+    /// Files matched by a `local:` include, relative to the repository root
+    /// whether or not the path starts with `/`.
+    fn local_include_files(root: &Path, location: &str) -> Vec<PathBuf> {
+        let path = root.join(location.trim_start_matches('/'));
+        if location.contains('*') {
+            let pattern = path.to_string_lossy().to_string();
+            let mut files: Vec<PathBuf> = glob::glob(&pattern)
+                .map(|paths| paths.filter_map(|p| p.ok()).collect())
+                .unwrap_or_default();
+            files.sort();
+            files
+        } else if path.is_file() {
+            vec![path]
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Whether a top-level entry defines a job: a mapping that is neither a
+    /// reserved keyword nor hidden (starting with `.`).
+    pub(crate) fn is_job_entry(key: &str, value: &Value) -> bool {
+        !RESERVED_KEYWORDS.contains(&key) && !key.starts_with('.') && value.is_mapping()
+    }
+
+    /// Stage names in execution order, defaulting to `build`, `test`, `deploy`.
+    pub(crate) fn parse_stages(yaml: &Value) -> Vec<String> {
         yaml.get("stages")
             .and_then(|v| v.as_sequence())
             .map(|seq| {
@@ -160,6 +316,39 @@ impl GitLabCIParser {
             })
     }
 
+    /// Read one `cache:` entry. `key: {files: [...]}` becomes `files:<list>`;
+    /// a missing key is GitLab's shared `default` key.
+    fn parse_cache(cache: &Value) -> CacheConfig {
+        let key_pattern = match cache.get("key") {
+            Some(Value::String(key)) => key.clone(),
+            Some(key) => match key.get("files").and_then(|f| f.as_sequence()) {
+                Some(files) => {
+                    let files: Vec<_> = files.iter().filter_map(|f| f.as_str()).collect();
+                    format!("files:{}", files.join(","))
+                }
+                None => "detected".to_string(),
+            },
+            None => "default".to_string(),
+        };
+        let path = cache
+            .get("paths")
+            .and_then(|p| p.as_sequence())
+            .map(|paths| {
+                paths
+                    .iter()
+                    .filter_map(|p| p.as_str())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .unwrap_or_else(|| "detected".to_string());
+
+        CacheConfig {
+            path,
+            key_pattern,
+            restore_keys: Vec::new(),
+        }
+    }
+
     fn parse_job(
         job_id: &str,
         config: &Value,
@@ -182,9 +371,15 @@ impl GitLabCIParser {
         let image = config
             .get("image")
             .and_then(Self::parse_image)
-            .or_else(|| default_image.clone())
-            .unwrap_or_else(|| "docker".to_string());
-        job.runs_on = image;
+            .or_else(|| default_image.clone());
+        job.container_image = image.clone();
+        job.runs_on = image.unwrap_or_else(|| "docker".to_string());
+
+        // allow_failure (literal `true` only; `exit_codes:` allows specific failures)
+        job.continue_on_error = config
+            .get("allow_failure")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
 
         // Variables
         if let Some(vars) = config.get("variables") {
@@ -193,17 +388,38 @@ impl GitLabCIParser {
             }
         }
 
-        // Rules / only / except → condition
-        if let Some(rules) = config.get("rules") {
-            if let Some(seq) = rules.as_sequence() {
-                let rule_strs: Vec<String> = seq
-                    .iter()
-                    .filter_map(|r| r.get("if").and_then(|v| v.as_str()).map(String::from))
-                    .collect();
-                if !rule_strs.is_empty() {
-                    job.condition = Some(rule_strs.join(" || "));
-                }
+        // Rules / only / except → condition and change-based path scoping
+        if let Some(seq) = config.get("rules").and_then(|v| v.as_sequence()) {
+            job.rules = seq.iter().filter_map(Self::parse_rule).collect();
+            let rule_strs: Vec<String> = job
+                .rules
+                .iter()
+                .filter_map(|r| r.condition.clone())
+                .collect();
+            if !rule_strs.is_empty() {
+                job.condition = Some(rule_strs.join(" || "));
             }
+            let (paths, ignored) = Self::rule_paths(&job.rules);
+            job.paths_filter = paths;
+            job.paths_ignore = ignored;
+        }
+        if job.rules.is_empty() {
+            job.rules = Self::legacy_rules(config);
+            let only: Vec<String> = job
+                .rules
+                .iter()
+                .filter(|r| !r.excludes())
+                .filter_map(|r| r.condition.clone())
+                .collect();
+            if !only.is_empty() {
+                job.condition = Some(only.join(" || "));
+            }
+        }
+        if let Some(changes) = config.get("only").and_then(|v| v.get("changes")) {
+            job.paths_filter = Some(Self::parse_changes(changes));
+        }
+        if let Some(changes) = config.get("except").and_then(|v| v.get("changes")) {
+            job.paths_ignore = Some(Self::parse_changes(changes));
         }
 
         // Needs (explicit dependencies)
@@ -222,7 +438,10 @@ impl GitLabCIParser {
                         name: format!("before_script[{}]", i),
                         uses: None,
                         run: Some(cmd_str.to_string()),
-                        estimated_duration_secs: Some(Self::estimate_cmd_duration(cmd_str)),
+                        estimated_duration_secs: Some(crate::estimation::command_secs(cmd_str)),
+                        with: HashMap::new(),
+                        working_directory: None,
+                        env: HashMap::new(),
                     });
                 }
             }
@@ -236,7 +455,10 @@ impl GitLabCIParser {
                         name: format!("script[{}]", i),
                         uses: None,
                         run: Some(cmd_str.to_string()),
-                        estimated_duration_secs: Some(Self::estimate_cmd_duration(cmd_str)),
+                        estimated_duration_secs: Some(crate::estimation::command_secs(cmd_str)),
+                        with: HashMap::new(),
+                        working_directory: None,
+                        env: HashMap::new(),
                     });
                 }
             }
@@ -250,7 +472,10 @@ impl GitLabCIParser {
                         name: format!("after_script[{}]", i),
                         uses: None,
                         run: Some(cmd_str.to_string()),
-                        estimated_duration_secs: Some(Self::estimate_cmd_duration(cmd_str)),
+                        estimated_duration_secs: Some(crate::estimation::command_secs(cmd_str)),
+                        with: HashMap::new(),
+                        working_directory: None,
+                        env: HashMap::new(),
                     });
                 }
             }
@@ -259,13 +484,14 @@ impl GitLabCIParser {
         job.steps = steps;
 
         // Cache detection
-        let has_cache = config.get("cache").is_some() || global_cache.is_some();
-        if has_cache {
-            job.caches.push(CacheConfig {
-                path: "detected".to_string(),
-                key_pattern: "detected".to_string(),
-                restore_keys: Vec::new(),
-            });
+        if let Some(cache) = config.get("cache").or(global_cache) {
+            let entries = match cache.as_sequence() {
+                Some(list) => list.iter().collect(),
+                None => vec![cache],
+            };
+            for entry in entries {
+                job.caches.push(Self::parse_cache(entry));
+            }
         }
 
         // Artifacts
@@ -288,6 +514,8 @@ impl GitLabCIParser {
             job.matrix = Some(MatrixStrategy {
                 variables: vars,
                 total_combinations: count,
+                fail_fast: None,
+                max_parallel: None,
             });
         }
 
@@ -301,7 +529,7 @@ impl GitLabCIParser {
         Ok(job)
     }
 
-    fn parse_needs(needs: &Value) -> Vec<String> {
+    pub(crate) fn parse_needs(needs: &Value) -> Vec<String> {
         match needs {
             Value::Sequence(seq) => seq
                 .iter()
@@ -354,22 +582,140 @@ impl GitLabCIParser {
         }
     }
 
+    /// Parse one `rules:` entry; `None` for entries that aren't mappings.
+    fn parse_rule(rule: &Value) -> Option<JobRule> {
+        rule.as_mapping()?;
+        let text = |key: &str| rule.get(key).and_then(|v| v.as_str()).map(String::from);
+        Some(JobRule {
+            condition: text("if"),
+            changes: rule
+                .get("changes")
+                .map(Self::parse_changes)
+                .unwrap_or_default(),
+            when: text("when"),
+        })
+    }
+
+    /// The `rules:` equivalent of `only:`/`except:` ref filters and a job-level
+    /// `when: manual | delayed`, so they gate the job like rules do.
+    fn legacy_rules(config: &Value) -> Vec<JobRule> {
+        let when = config
+            .get("when")
+            .and_then(|v| v.as_str())
+            .filter(|w| matches!(*w, "manual" | "delayed"))
+            .map(String::from);
+        let rule = |condition: Option<String>, when: Option<String>| JobRule {
+            condition,
+            changes: Vec::new(),
+            when,
+        };
+
+        let mut rules: Vec<JobRule> = Self::legacy_refs(config.get("except"))
+            .into_iter()
+            .map(|condition| rule(Some(condition), Some("never".to_string())))
+            .collect();
+        let only = Self::legacy_refs(config.get("only"));
+        if only.is_empty() {
+            if !rules.is_empty() || when.is_some() {
+                rules.push(rule(None, when));
+            }
+        } else {
+            rules.extend(
+                only.into_iter()
+                    .map(|condition| rule(Some(condition), when.clone())),
+            );
+        }
+        rules
+    }
+
+    /// Rule conditions for the refs of an `only:` / `except:` list (or its
+    /// `refs:` key): keywords such as `tags`, `/regex/` patterns and branch names.
+    fn legacy_refs(filter: Option<&Value>) -> Vec<String> {
+        let Some(filter) = filter else {
+            return Vec::new();
+        };
+        let refs = match filter {
+            Value::Mapping(_) => filter.get("refs"),
+            _ => Some(filter),
+        };
+        let source = |s: &str| format!("$CI_PIPELINE_SOURCE == \"{}\"", s);
+        refs.and_then(|r| r.as_sequence())
+            .into_iter()
+            .flatten()
+            .filter_map(|r| r.as_str())
+            .map(|r| match r {
+                "tags" => "$CI_COMMIT_TAG".to_string(),
+                "branches" => "$CI_COMMIT_BRANCH".to_string(),
+                "merge_requests" => source("merge_request_event"),
+                "schedules" => source("schedule"),
+                "pushes" => source("push"),
+                "web" | "api" => source(r),
+                "triggers" => source("trigger"),
+                "pipelines" => source("pipeline"),
+                pattern if pattern.len() > 1 && pattern.starts_with('/') => {
+                    format!("$CI_COMMIT_REF_NAME =~ {}", pattern)
+                }
+                name => format!("$CI_COMMIT_REF_NAME == \"{}\"", name),
+            })
+            .collect()
+    }
+
+    /// `changes:` as a list of globs, or the `paths:` list of its mapping form.
+    fn parse_changes(changes: &Value) -> Vec<String> {
+        let list = changes.get("paths").unwrap_or(changes);
+        list.as_sequence()
+            .map(|seq| {
+                seq.iter()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Paths that run and paths that skip, from rules with `changes:`.
+    ///
+    /// Rules are checked in order, so a runnable rule without `changes:` makes
+    /// the job run whatever changed, and rules after it no longer scope it.
+    /// `when: never` rules with `changes:` are ignores.
+    fn rule_paths(rules: &[JobRule]) -> (Option<Vec<String>>, Option<Vec<String>>) {
+        let mut paths = Vec::new();
+        let mut ignored = Vec::new();
+        for rule in rules {
+            if rule.excludes() {
+                ignored.extend(rule.changes.iter().cloned());
+            } else if rule.changes.is_empty() {
+                // Later rules are never reached for changes this one already runs on
+                return (None, (!ignored.is_empty()).then_some(ignored));
+            } else {
+                paths.extend(rule.changes.iter().cloned());
+            }
+        }
+        (
+            (!paths.is_empty()).then_some(paths),
+            (!ignored.is_empty()).then_some(ignored),
+        )
+    }
+
     fn parse_triggers(yaml: &Value) -> Vec<WorkflowTrigger> {
         let mut triggers = Vec::new();
 
         if let Some(workflow) = yaml.get("workflow") {
             if let Some(rules) = workflow.get("rules").and_then(|v| v.as_sequence()) {
-                for rule in rules {
-                    let event = rule
-                        .get("if")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("push")
-                        .to_string();
+                for rule in rules.iter().filter_map(Self::parse_rule) {
+                    let (paths, paths_ignore) = if rule.changes.is_empty() {
+                        (None, None)
+                    } else if rule.excludes() {
+                        (None, Some(rule.changes))
+                    } else {
+                        (Some(rule.changes), None)
+                    };
                     triggers.push(WorkflowTrigger {
-                        event,
+                        event: rule.condition.unwrap_or_else(|| "push".to_string()),
                         branches: None,
-                        paths: None,
-                        paths_ignore: None,
+                        paths,
+                        paths_ignore,
+                        tags: None,
+                        schedules: Vec::new(),
                     });
                 }
             }
@@ -381,55 +727,30 @@ impl GitLabCIParser {
                 branches: None,
                 paths: None,
                 paths_ignore: None,
+                tags: None,
+                schedules: Vec::new(),
             });
         }
 
         triggers
     }
+}
 
-    #[allow(clippy::if_same_then_else)]
-    fn estimate_cmd_duration(cmd: &str) -> f64 {
-        let cmd_lower = cmd.to_lowercase();
-        if cmd_lower.contains("npm ci")
-            || cmd_lower.contains("npm install")
-            || cmd_lower.contains("yarn install")
-        {
-            180.0
-        } else if cmd_lower.contains("pip install") {
-            120.0
-        } else if cmd_lower.contains("cargo build") {
-            300.0
-        } else if cmd_lower.contains("cargo test") {
-            300.0
-        } else if cmd_lower.contains("npm run build") || cmd_lower.contains("yarn build") {
-            240.0
-        } else if cmd_lower.contains("npm test")
-            || cmd_lower.contains("pytest")
-            || cmd_lower.contains("jest")
-            || cmd_lower.contains("rspec")
-        {
-            300.0
-        } else if cmd_lower.contains("eslint")
-            || cmd_lower.contains("rubocop")
-            || cmd_lower.contains("flake8")
-        {
-            60.0
-        } else if cmd_lower.contains("docker build") {
-            300.0
-        } else if cmd_lower.contains("deploy")
-            || cmd_lower.contains("kubectl")
-            || cmd_lower.contains("helm")
-        {
-            120.0
-        } else if cmd_lower.contains("bundle install") {
-            150.0
-        } else if cmd_lower.contains("composer install") {
-            90.0
-        } else if cmd_lower.contains("apt-get") || cmd_lower.contains("apk add") {
-            45.0
-        } else {
-            30.0
+/// Deep-merge `overlay` into `base`: mappings merge key by key, anything else
+/// is replaced.
+fn merge_yaml(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Mapping(base), Value::Mapping(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_yaml(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
         }
+        (base, overlay) => *base = overlay,
     }
 }
 
@@ -573,4 +894,227 @@ build:
         assert!(dag.get_job("build").is_some());
         assert!(dag.get_job(".template").is_none());
     }
+
+    #[test]
+    fn test_gitlab_local_include_and_remote_placeholder() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("ci")).unwrap();
+        std::fs::write(
+            dir.path().join("ci/build.yml"),
+            r#"
+build:
+  stage: build
+  script:
+    - npm ci
+    - npm run build
+"#,
+        )
+        .unwrap();
+        let main = dir.path().join(".gitlab-ci.yml");
+        std::fs::write(
+            &main,
+            r#"
+include:
+  - local: /ci/build.yml
+  - remote: https://example.com/ci/security.yml
+  - template: Jobs/SAST.gitlab-ci.yml
+stages: [build, test]
+test:
+  stage: test
+  script:
+    - npm test
+"#,
+        )
+        .unwrap();
+
+        let dag = GitLabCIParser::parse_file(&main).unwrap();
+        assert!(dag.get_job("build").is_some());
+        // The included job takes part in stage ordering
+        let test = dag.node_map["test"];
+        let upstream: Vec<_> = dag
+            .graph
+            .neighbors_directed(test, petgraph::Direction::Incoming)
+            .map(|i| dag.graph[i].id.as_str())
+            .collect();
+        assert_eq!(upstream, vec!["build"]);
+
+        // Unresolved includes are recorded, not added as jobs
+        assert_eq!(
+            dag.external_includes,
+            vec![
+                ExternalInclude {
+                    kind: "remote".to_string(),
+                    location: "https://example.com/ci/security.yml".to_string(),
+                },
+                ExternalInclude {
+                    kind: "template".to_string(),
+                    location: "Jobs/SAST.gitlab-ci.yml".to_string(),
+                },
+            ]
+        );
+        assert_eq!(dag.job_count(), 2);
+        let report = crate::analyzer::analyze(&dag);
+        assert_eq!(report.job_count, 2);
+        assert_eq!(report.max_parallelism, 1);
+    }
+
+    #[test]
+    fn test_gitlab_nested_local_include_resolves_from_root() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("ci")).unwrap();
+        std::fs::write(
+            dir.path().join("ci/base.yml"),
+            "include:\n  - local: /ci/build.yml\n  - local: ci/lint.yml\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("ci/build.yml"),
+            "build:\n  stage: build\n  script: [make]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("ci/lint.yml"),
+            "lint:\n  stage: test\n  script: [make lint]\n",
+        )
+        .unwrap();
+        let main = dir.path().join(".gitlab-ci.yml");
+        std::fs::write(&main, "include:\n  - local: ci/base.yml\n").unwrap();
+
+        let dag = GitLabCIParser::parse_file(&main).unwrap();
+        assert!(
+            dag.external_includes.is_empty(),
+            "{:?}",
+            dag.external_includes
+        );
+        assert!(dag.get_job("build").is_some());
+        assert!(dag.get_job("lint").is_some());
+    }
+
+    #[test]
+    fn test_gitlab_circular_include_is_error() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("a.yml"),
+            "include: b.yml
+a:
+  script: [echo a]
+",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("b.yml"),
+            "include: a.yml
+b:
+  script: [echo b]
+",
+        )
+        .unwrap();
+
+        let err = GitLabCIParser::parse_file(&dir.path().join("a.yml")).unwrap_err();
+        assert!(err.to_string().contains("Circular include"), "{}", err);
+    }
+
+    #[test]
+    fn test_gitlab_only_except_and_when_become_rules() {
+        let yaml = r#"
+release:
+  script: [make release]
+  only: [tags, /^release-.*$/]
+  when: manual
+nightly:
+  script: [make e2e]
+  except:
+    refs: [merge_requests]
+approve:
+  script: [make approve]
+  when: manual
+build:
+  script: [make]
+  when: on_success
+"#;
+        let dag = GitLabCIParser::parse(yaml, ".gitlab-ci.yml".to_string()).unwrap();
+
+        let release = dag.get_job("release").unwrap();
+        assert_eq!(release.rules.len(), 2);
+        assert!(release
+            .rules
+            .iter()
+            .all(|r| r.when.as_deref() == Some("manual")));
+        assert_eq!(
+            release.condition.as_deref(),
+            Some("$CI_COMMIT_TAG || $CI_COMMIT_REF_NAME =~ /^release-.*$/")
+        );
+
+        let nightly = dag.get_job("nightly").unwrap();
+        assert!(nightly.rules[0].excludes());
+        assert_eq!(
+            nightly.rules[0].condition.as_deref(),
+            Some("$CI_PIPELINE_SOURCE == \"merge_request_event\"")
+        );
+        assert_eq!(nightly.rules[1].condition, None);
+        assert_eq!(nightly.condition, None);
+
+        let approve = dag.get_job("approve").unwrap();
+        assert_eq!(approve.rules.len(), 1);
+        assert_eq!(approve.rules[0].when.as_deref(), Some("manual"));
+        assert!(dag.get_job("build").unwrap().rules.is_empty());
+    }
+
+    #[test]
+    fn test_gitlab_rules_changes_scope_jobs() {
+        let yaml = r#"
+workflow:
+  rules:
+    - changes: [docs/**]
+      when: never
+    - if: $CI_PIPELINE_SOURCE == "merge_request_event"
+
+build:
+  script: [make]
+  rules:
+    - if: $CI_COMMIT_BRANCH == "main"
+      changes:
+        paths: [src/**, Makefile]
+    - changes: [docs/**]
+      when: never
+
+deploy:
+  script: [make deploy]
+  rules:
+    - when: manual
+    - changes: [deploy/**]
+
+legacy:
+  script: [make lint]
+  only:
+    changes: [lint/**]
+"#;
+        let dag = GitLabCIParser::parse(yaml, ".gitlab-ci.yml".to_string()).unwrap();
+
+        let build = dag.get_job("build").unwrap();
+        assert_eq!(build.rules.len(), 2);
+        assert_eq!(
+            build.rules[0].condition.as_deref(),
+            Some("$CI_COMMIT_BRANCH == \"main\"")
+        );
+        assert!(build.rules[1].excludes());
+        assert_eq!(
+            build.paths_filter,
+            Some(vec!["src/**".to_string(), "Makefile".to_string()])
+        );
+        assert_eq!(build.paths_ignore, Some(vec!["docs/**".to_string()]));
+
+        // An earlier rule without `changes:` already runs the job on anything
+        assert_eq!(dag.get_job("deploy").unwrap().paths_filter, None);
+        assert_eq!(
+            dag.get_job("legacy").unwrap().paths_filter,
+            Some(vec!["lint/**".to_string()])
+        );
+
+        assert_eq!(
+            dag.triggers[0].paths_ignore,
+            Some(vec!["docs/**".to_string()])
+        );
+        assert_eq!(dag.triggers[1].paths, None);
+    }
 }
@@ -1,4 +1,6 @@
-use crate::parser::dag::{JobNode, PipelineDag, StepInfo};
+use crate::error::{read_pipeline_file, PipelineError};
+use crate::parser::dag::{DataFlow, JobNode, PipelineDag, StepInfo};
+use crate::parser::detect::{ProviderCapabilities, ProviderInfo};
 use anyhow::{Context, Result};
 use regex::Regex;
 use std::collections::HashMap;
@@ -8,15 +10,34 @@ use std::path::Path;
 pub struct JenkinsParser;
 
 impl JenkinsParser {
+    /// Registry entry listed by `list-providers`.
+    pub const INFO: ProviderInfo = ProviderInfo {
+        provider: "jenkins",
+        name: "Jenkins",
+        file_patterns: &["Jenkinsfile", "*.jenkinsfile", "*.groovy"],
+        content_markers: &[],
+        capabilities: ProviderCapabilities {
+            matrix: false,
+            needs: false,
+            includes: false,
+            parallel: true,
+            services: false,
+        },
+    };
+
     /// Parse a Jenkinsfile from a file path.
-    pub fn parse_file(path: &Path) -> Result<PipelineDag> {
-        let content = std::fs::read_to_string(path)
-            .with_context(|| format!("Failed to read Jenkinsfile: {}", path.display()))?;
+    pub fn parse_file(path: &Path) -> Result<PipelineDag, PipelineError> {
+        let content = read_pipeline_file(path)?;
         Self::parse(&content, path.display().to_string())
     }
 
     /// Parse a Jenkinsfile from string content.
-    pub fn parse(content: &str, source: String) -> Result<PipelineDag> {
+    pub fn parse(content: &str, source: String) -> Result<PipelineDag, PipelineError> {
+        Self::parse_source(content, source.clone())
+            .map_err(|e| PipelineError::parse("jenkins", &source, e))
+    }
+
+    fn parse_source(content: &str, source: String) -> Result<PipelineDag> {
         let mut dag = PipelineDag::new(
             "Jenkins Pipeline".to_string(),
             source,
@@ -54,6 +75,12 @@ impl JenkinsParser {
                 env: stage.environment,
                 paths_filter: None,
                 paths_ignore: None,
+                container_image: None,
+                data_flow: DataFlow::default(),
+                continue_on_error: false,
+                working_directory: None,
+                permissions: None,
+                rules: Vec::new(),
             };
 
             dag.add_job(job);
@@ -154,10 +181,12 @@ impl JenkinsParser {
         let mut steps = Vec::new();
 
         // Match steps block: steps { ... }
-        if let Some(steps_start) = block_content.find("steps") {
-            if let Some(steps_block) =
-                Self::extract_block_after_match(block_content, steps_start + 5)
-            {
+        if let Some(steps_open) = block_content.find("steps").and_then(|start| {
+            block_content[start..]
+                .find('{')
+                .map(|brace| start + brace + 1)
+        }) {
+            if let Some(steps_block) = Self::extract_block_after_match(block_content, steps_open) {
                 // Extract individual commands
                 let commands = Self::extract_commands(&steps_block);
                 for (i, cmd) in commands.iter().enumerate() {
@@ -165,7 +194,10 @@ impl JenkinsParser {
                         name: format!("Step {}", i + 1),
                         run: Some(cmd.clone()),
                         uses: None,
-                        estimated_duration_secs: None,
+                        estimated_duration_secs: Some(crate::estimation::command_secs(cmd)),
+                        with: HashMap::new(),
+                        working_directory: None,
+                        env: HashMap::new(),
                     });
                 }
             }
@@ -236,6 +268,10 @@ impl JenkinsParser {
     }
 
     fn estimate_stage_duration(stage_name: &str, steps: &[StepInfo]) -> f64 {
+        if let Some(secs) = crate::estimation::estimates().steps_secs(steps) {
+            return secs;
+        }
+
         let name_lower = stage_name.to_lowercase();
 
         // Heuristics based on stage name
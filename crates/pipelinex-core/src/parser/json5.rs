@@ -0,0 +1,130 @@
+//! Lenient reading of JSON files that carry comments or trailing commas
+//! (JSON5 / "commented JSON"), as some tools generate for CodePipeline and Argo.
+
+/// Whether `content` looks like a JSON document rather than YAML.
+pub fn looks_like_json(content: &str) -> bool {
+    matches!(content.trim_start().chars().next(), Some('{' | '['))
+}
+
+/// Whether `content` parses as strict JSON.
+pub fn is_strict_json(content: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(content).is_ok()
+}
+
+/// `content` with `//` and `/* */` comments and trailing commas removed,
+/// outside of string literals. Line breaks are kept so parse errors still
+/// point at the original lines. Unquoted keys and single-quoted strings are
+/// left alone: the YAML parser already accepts them in flow style.
+pub fn normalize(content: &str) -> String {
+    let chars: Vec<char> = content.chars().collect();
+    let mut out = String::with_capacity(content.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '"' | '\'' => {
+                // Copy the string literal verbatim, honouring escapes
+                out.push(c);
+                i += 1;
+                while i < chars.len() {
+                    out.push(chars[i]);
+                    if chars[i] == '\\' && i + 1 < chars.len() {
+                        out.push(chars[i + 1]);
+                        i += 2;
+                        continue;
+                    }
+                    i += 1;
+                    if chars[i - 1] == c {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    if chars[i] == '\n' {
+                        out.push('\n');
+                    }
+                    i += 1;
+                }
+                i += 2;
+            }
+            ',' if next_significant(&chars, i + 1).is_some_and(|n| n == '}' || n == ']') => {
+                i += 1;
+            }
+            _ => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    out
+}
+
+/// The next character from `start` that is not whitespace or inside a comment.
+fn next_significant(chars: &[char], start: usize) -> Option<char> {
+    let mut i = start;
+    while i < chars.len() {
+        match chars[i] {
+            c if c.is_whitespace() => i += 1,
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i += 2;
+            }
+            c => return Some(c),
+        }
+    }
+    None
+}
+
+/// Parse `content` as YAML (which covers strict JSON), falling back to the
+/// [`normalize`]d text for JSON documents with comments or trailing commas.
+pub fn from_str_lenient<T: serde::de::DeserializeOwned>(
+    content: &str,
+) -> Result<T, serde_yaml::Error> {
+    match serde_yaml::from_str(content) {
+        Ok(value) => Ok(value),
+        Err(err) if looks_like_json(content) => {
+            serde_yaml::from_str(&normalize(content)).or(Err(err))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_strips_comments_and_trailing_commas() {
+        let input = r#"{
+  // pipeline name
+  "name": "a // not a comment", /* inline */
+  "urls": ["https://example.com/*x*/",],
+  "quote": "say \"hi\",}",
+}"#;
+        let normalized = normalize(input);
+        assert!(!is_strict_json(input));
+        assert!(is_strict_json(&normalized), "{}", normalized);
+        let value: serde_json::Value = serde_json::from_str(&normalized).unwrap();
+        assert_eq!(value["name"], "a // not a comment");
+        assert_eq!(value["urls"][0], "https://example.com/*x*/");
+        assert_eq!(value["quote"], "say \"hi\",}");
+        assert_eq!(normalized.lines().count(), input.lines().count());
+    }
+}
@@ -5,8 +5,11 @@ pub mod bitbucket;
 pub mod buildkite;
 pub mod circleci;
 pub mod dag;
+pub mod detect;
 pub mod drone;
 pub mod github;
 pub mod gitlab;
 pub mod jenkins;
+pub mod json5;
+pub mod render;
 pub mod tekton;
@@ -0,0 +1,173 @@
+//! Preprocessing for templated pipeline configs (`--render`).
+//!
+//! Files such as `ci.yml.j2` are piped through an external command that prints
+//! the rendered YAML, and the result is routed and parsed as if it had been read
+//! from the file without its template extension.
+
+use super::dag::PipelineDag;
+use super::detect::{rule_for_provider, select_rule, PipelineSource};
+use crate::error::{read_pipeline_file, PipelineError};
+use crate::plugins::run_piped;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Extensions stripped from a template's file name before provider detection.
+pub const TEMPLATE_EXTENSIONS: &[&str] = &["j2", "jinja", "jinja2", "tmpl", "tpl"];
+
+/// Environment variable holding the template path while the renderer runs.
+pub const RENDER_FILE_ENV: &str = "PIPELINEX_RENDER_FILE";
+
+/// Run `renderer` over a pipeline file and return the rendered text.
+///
+/// The command runs through the platform shell with the file content on stdin.
+/// The path is passed as `$1` and in [`RENDER_FILE_ENV`], so renderers that
+/// expect a file name (`jinja2 "$1"`) work as well as filters (`sed ...`).
+pub fn render_file(path: &Path, renderer: &str) -> Result<String, PipelineError> {
+    let content = read_pipeline_file(path)?;
+    let fail = |detail: String| PipelineError::Render {
+        command: renderer.to_string(),
+        path: path.to_path_buf(),
+        detail,
+    };
+
+    let output = run_piped(&mut shell_command(renderer, path), content.as_bytes())
+        .map_err(|(stage, error)| fail(format!("failed to {} renderer: {}", stage, error)))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stderr = stderr.trim();
+        return Err(fail(if stderr.is_empty() {
+            format!("exited with {}", output.status)
+        } else {
+            format!("exited with {}: {}", output.status, stderr)
+        }));
+    }
+
+    let rendered = String::from_utf8(output.stdout)
+        .map_err(|error| fail(format!("output is not UTF-8: {}", error)))?;
+    if rendered.trim().is_empty() {
+        return Err(fail("produced no output".to_string()));
+    }
+    Ok(rendered)
+}
+
+/// Render a pipeline file and parse the result.
+///
+/// Without an explicit `provider`, detection uses the path with its template
+/// extension removed plus the rendered content. Reports keep the original path.
+pub fn parse_rendered_file(
+    path: &Path,
+    renderer: &str,
+    provider: Option<&str>,
+) -> Result<PipelineDag, PipelineError> {
+    let rendered = render_file(path, renderer)?;
+    let rule = match provider {
+        Some(provider) => rule_for_provider(provider)?,
+        None => {
+            let detect_path = strip_template_extension(path);
+            select_rule(&PipelineSource::with_content(&detect_path, &rendered))
+        }
+    };
+
+    let dag = (rule.parse_content)(&rendered, path.display().to_string())?;
+    dag.topological_order()?;
+    Ok(dag)
+}
+
+/// `ci.yml.j2` -> `ci.yml`; paths without a template extension are unchanged.
+pub fn strip_template_extension(path: &Path) -> PathBuf {
+    let is_template = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| TEMPLATE_EXTENSIONS.contains(&e.to_lowercase().as_str()));
+    if is_template {
+        path.with_extension("")
+    } else {
+        path.to_path_buf()
+    }
+}
+
+#[cfg(not(windows))]
+fn shell_command(renderer: &str, path: &Path) -> Command {
+    let mut command = Command::new("sh");
+    command
+        .arg("-c")
+        .arg(renderer)
+        .arg("pipelinex-render")
+        .arg(path)
+        .env(RENDER_FILE_ENV, path);
+    command
+}
+
+#[cfg(windows)]
+fn shell_command(renderer: &str, path: &Path) -> Command {
+    let mut command = Command::new("cmd");
+    command.arg("/C").arg(renderer).env(RENDER_FILE_ENV, path);
+    command
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str = r#"
+name: CI
+on: push
+jobs:
+  build:
+    runs-on: __RUNNER__
+    steps:
+      - uses: actions/checkout@v4
+      - run: make
+"#;
+
+    fn write_template(dir: &Path) -> PathBuf {
+        let workflows = dir.join(".github/workflows");
+        std::fs::create_dir_all(&workflows).unwrap();
+        let path = workflows.join("ci.yml.j2");
+        std::fs::write(&path, TEMPLATE).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_render_substitutes_before_parsing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_template(dir.path());
+
+        let dag = parse_rendered_file(&path, "sed 's/__RUNNER__/ubuntu-latest/'", None).unwrap();
+        assert_eq!(dag.provider, "github-actions");
+        assert_eq!(dag.source_file, path.display().to_string());
+        assert_eq!(dag.get_job("build").unwrap().runs_on, "ubuntu-latest");
+
+        // The path is available as `$1` for renderers that read the file themselves.
+        let dag = parse_rendered_file(&path, "sed 's/__RUNNER__/macos-14/' \"$1\"", None).unwrap();
+        assert_eq!(dag.get_job("build").unwrap().runs_on, "macos-14");
+    }
+
+    #[test]
+    fn test_renderer_failure_is_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_template(dir.path());
+
+        let err = parse_rendered_file(&path, "echo 'undefined variable RUNNER' >&2; exit 3", None)
+            .unwrap_err();
+        match &err {
+            PipelineError::Render {
+                command, detail, ..
+            } => {
+                assert!(command.starts_with("echo"));
+                assert!(detail.contains("undefined variable RUNNER"));
+            }
+            other => panic!("expected Render, got {:?}", other),
+        }
+
+        assert!(matches!(
+            render_file(&path, "true"),
+            Err(PipelineError::Render { .. })
+        ));
+        assert_eq!(
+            strip_template_extension(&path),
+            dir.path().join(".github/workflows/ci.yml")
+        );
+    }
+}
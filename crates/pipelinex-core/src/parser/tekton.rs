@@ -1,24 +1,53 @@
+use crate::error::{read_pipeline_file, PipelineError};
 use crate::parser::dag::*;
+use crate::parser::detect::{ProviderCapabilities, ProviderInfo};
 use anyhow::{Context, Result};
 use serde::Deserialize;
 use serde_yaml::Value;
+use std::collections::HashMap;
 use std::path::Path;
 
 /// Parser for Tekton Pipeline, Task, and PipelineRun CRDs.
+///
+/// A file may hold several `---`-separated documents. The Pipeline (or else the
+/// PipelineRun, or else a Task) becomes the DAG, and `taskRef`s to Tasks defined
+/// in the same file are expanded into those Tasks' steps.
 pub struct TektonParser;
 
+/// Task documents in the file, by `metadata.name`.
+type TaskDefs<'a> = HashMap<&'a str, &'a Value>;
+
 impl TektonParser {
-    pub fn parse_file(path: &Path) -> Result<PipelineDag> {
-        let content = std::fs::read_to_string(path)
-            .with_context(|| format!("Failed to read Tekton file: {}", path.display()))?;
+    /// Registry entry listed by `list-providers`.
+    pub const INFO: ProviderInfo = ProviderInfo {
+        provider: "tekton",
+        name: "Tekton",
+        file_patterns: &["*.yml", "*.yaml"],
+        content_markers: &["tekton.dev"],
+        capabilities: ProviderCapabilities {
+            matrix: false,
+            needs: true,
+            includes: false,
+            parallel: false,
+            services: false,
+        },
+    };
+
+    pub fn parse_file(path: &Path) -> Result<PipelineDag, PipelineError> {
+        let content = read_pipeline_file(path)?;
         Self::parse(&content, path.to_string_lossy().to_string())
     }
 
-    pub fn parse_content(content: &str, source_name: &str) -> Result<PipelineDag> {
+    pub fn parse_content(content: &str, source_name: &str) -> Result<PipelineDag, PipelineError> {
         Self::parse(content, source_name.to_string())
     }
 
-    pub fn parse(content: &str, source_file: String) -> Result<PipelineDag> {
+    pub fn parse(content: &str, source_file: String) -> Result<PipelineDag, PipelineError> {
+        Self::parse_source(content, source_file.clone())
+            .map_err(|e| PipelineError::parse("tekton", &source_file, e))
+    }
+
+    fn parse_source(content: &str, source_file: String) -> Result<PipelineDag> {
         let docs: Vec<Value> = serde_yaml::Deserializer::from_str(content)
             .map(Value::deserialize)
             .collect::<std::result::Result<Vec<_>, _>>()
@@ -32,16 +61,30 @@ impl TektonParser {
             anyhow::bail!("No Tekton Pipeline/Task/PipelineRun document found");
         }
 
+        let task_defs: TaskDefs = docs
+            .iter()
+            .filter(|doc| {
+                matches!(
+                    doc.get("kind").and_then(|v| v.as_str()),
+                    Some("Task" | "ClusterTask")
+                )
+            })
+            .filter_map(|doc| {
+                let name = doc.get("metadata")?.get("name")?.as_str()?;
+                Some((name, doc))
+            })
+            .collect();
+
         let kind = selected
             .get("kind")
             .and_then(|v| v.as_str())
             .unwrap_or("Pipeline");
 
         match kind {
-            "Pipeline" => Self::parse_pipeline(selected, source_file),
-            "PipelineRun" => Self::parse_pipeline_run(selected, source_file),
+            "Pipeline" => Self::parse_pipeline(selected, source_file, &task_defs),
+            "PipelineRun" => Self::parse_pipeline_run(selected, source_file, &task_defs),
             "Task" => Self::parse_task_as_pipeline(selected, source_file),
-            _ => Self::parse_pipeline(selected, source_file),
+            _ => Self::parse_pipeline(selected, source_file, &task_defs),
         }
     }
 
@@ -68,7 +111,11 @@ impl TektonParser {
         }
     }
 
-    fn parse_pipeline(yaml: &Value, source_file: String) -> Result<PipelineDag> {
+    fn parse_pipeline(
+        yaml: &Value,
+        source_file: String,
+        task_defs: &TaskDefs,
+    ) -> Result<PipelineDag> {
         let metadata = yaml.get("metadata").unwrap_or(yaml);
         let name = metadata
             .get("name")
@@ -98,12 +145,12 @@ impl TektonParser {
 
         // First pass: create all task nodes
         for task in &tasks {
-            let job = Self::parse_pipeline_task(task)?;
+            let job = Self::parse_pipeline_task(task, task_defs)?;
             dag.add_job(job);
         }
 
         for task in &finally_tasks {
-            let mut job = Self::parse_pipeline_task(task)?;
+            let mut job = Self::parse_pipeline_task(task, task_defs)?;
             job.condition = Some("finally".to_string());
             dag.add_job(job);
         }
@@ -163,7 +210,7 @@ impl TektonParser {
         Ok(dag)
     }
 
-    fn parse_pipeline_task(task: &Value) -> Result<JobNode> {
+    fn parse_pipeline_task(task: &Value, task_defs: &TaskDefs) -> Result<JobNode> {
         let name = task
             .get("name")
             .and_then(|v| v.as_str())
@@ -172,19 +219,31 @@ impl TektonParser {
 
         let mut job = JobNode::new(name.clone(), name);
 
-        // Task reference
+        // Task reference: expand a Task defined in the same file, else estimate by name
         if let Some(task_ref) = task.get("taskRef") {
             let ref_name = task_ref
                 .get("name")
                 .and_then(|v| v.as_str())
                 .unwrap_or("unknown");
 
-            job.steps.push(StepInfo {
-                name: format!("taskRef: {}", ref_name),
-                uses: Some(ref_name.to_string()),
-                run: None,
-                estimated_duration_secs: Some(Self::estimate_task_duration(ref_name)),
-            });
+            let defined_steps = task_defs
+                .get(ref_name)
+                .and_then(|def| def.get("spec"))
+                .and_then(|spec| spec.get("steps"))
+                .and_then(|v| v.as_sequence());
+            if let Some(steps) = defined_steps {
+                job.steps.extend(steps.iter().map(Self::parse_step));
+            } else {
+                job.steps.push(StepInfo {
+                    name: format!("taskRef: {}", ref_name),
+                    uses: Some(ref_name.to_string()),
+                    run: None,
+                    estimated_duration_secs: Some(Self::estimate_task_duration(ref_name)),
+                    with: HashMap::new(),
+                    working_directory: None,
+                    env: HashMap::new(),
+                });
+            }
         }
 
         // Inline task spec (taskSpec)
@@ -268,10 +327,17 @@ impl TektonParser {
             uses: image,
             run,
             estimated_duration_secs: Some(estimated_duration),
+            with: HashMap::new(),
+            working_directory: None,
+            env: HashMap::new(),
         }
     }
 
-    fn parse_pipeline_run(yaml: &Value, source_file: String) -> Result<PipelineDag> {
+    fn parse_pipeline_run(
+        yaml: &Value,
+        source_file: String,
+        task_defs: &TaskDefs,
+    ) -> Result<PipelineDag> {
         let metadata = yaml.get("metadata").unwrap_or(yaml);
         let name = metadata
             .get("name")
@@ -290,7 +356,7 @@ impl TektonParser {
                 m.insert(Value::String("name".into()), Value::String(name));
                 Value::Mapping(m)
             });
-            return Self::parse_pipeline(&Value::Mapping(wrapper), source_file);
+            return Self::parse_pipeline(&Value::Mapping(wrapper), source_file, task_defs);
         }
 
         // Otherwise just create an empty DAG with the pipeline reference
@@ -351,6 +417,10 @@ impl TektonParser {
 
     fn estimate_step_duration(image: &Option<String>, run: &Option<String>) -> f64 {
         if let Some(run) = run {
+            if let Some(secs) = crate::estimation::estimates().match_command(run) {
+                return secs;
+            }
+            // Tekton scripts often wrap tools in custom wrappers; fall back to keywords
             let cmd = run.to_lowercase();
             if cmd.contains("build")
                 || cmd.contains("compile")
@@ -534,4 +604,70 @@ spec:
         assert!(dag.get_job("build").is_some());
         assert!(dag.get_job("test").is_some());
     }
+
+    #[test]
+    fn test_parse_tekton_multi_document_expands_task_refs() {
+        let yaml = r#"
+apiVersion: tekton.dev/v1
+kind: Pipeline
+metadata:
+  name: release
+spec:
+  tasks:
+    - name: image
+      taskRef:
+        name: image-task
+    - name: verify
+      taskRef:
+        name: verify-task
+      runAfter: [image]
+    - name: notify
+      taskRef:
+        name: slack-notify
+      runAfter: [verify]
+---
+apiVersion: tekton.dev/v1
+kind: Task
+metadata:
+  name: image-task
+spec:
+  steps:
+    - name: build
+      image: gcr.io/kaniko-project/executor:latest
+    - name: sign
+      image: cosign
+      script: cosign sign $(params.image)
+---
+apiVersion: tekton.dev/v1
+kind: Task
+metadata:
+  name: verify-task
+spec:
+  steps:
+    - name: unit
+      image: golang:1.22
+      script: go test ./...
+    - name: vet
+      image: golang:1.22
+      script: go vet ./... && golangci-lint run
+"#;
+        let dag = TektonParser::parse(yaml, "release.yaml".into()).unwrap();
+        assert_eq!(dag.name, "release");
+        assert_eq!(dag.job_count(), 3);
+
+        // Steps and durations come from the Task documents, not the name heuristic
+        let image = dag.get_job("image").unwrap();
+        assert_eq!(image.steps.len(), 2);
+        assert_eq!(image.steps[0].name, "build");
+        assert_eq!(image.estimated_duration_secs, 300.0 + 30.0);
+
+        let verify = dag.get_job("verify").unwrap();
+        assert_eq!(verify.steps.len(), 2);
+        assert_eq!(verify.estimated_duration_secs, 300.0 + 60.0);
+
+        // Tasks not in the file still fall back to a name-based estimate
+        let notify = dag.get_job("notify").unwrap();
+        assert_eq!(notify.steps[0].name, "taskRef: slack-notify");
+        assert_eq!(notify.estimated_duration_secs, 60.0);
+    }
 }
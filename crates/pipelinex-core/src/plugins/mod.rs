@@ -1,9 +1,18 @@
 use crate::analyzer::report::{Finding, FindingCategory, Severity};
 use crate::parser::dag::PipelineDag;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::process::{Command, Stdio};
+use std::process::{Command, Output, Stdio};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+/// Version of the analyzer input schema (`PluginRunInput`) this build sends.
+pub const PLUGIN_PROTOCOL_VERSION: u32 = 1;
+
+/// Argument passed to a plugin to ask for its [`PluginCapabilities`].
+pub const CAPABILITIES_ARG: &str = "--capabilities";
 
 /// Manifest format for external plugins.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -95,6 +104,43 @@ struct PluginFinding {
     auto_fixable: Option<bool>,
 }
 
+/// Manifest a plugin prints when invoked with `--capabilities`.
+///
+/// The handshake is optional: a plugin that exits non-zero or prints anything
+/// else is treated as declaring nothing and always runs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PluginCapabilities {
+    pub id: String,
+    /// Finding category ids the plugin produces.
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// The plugin's own version.
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Input schema version the plugin was written against.
+    pub input_schema_version: u32,
+    /// Providers the plugin understands; empty means all.
+    #[serde(default)]
+    pub providers: Vec<String>,
+}
+
+impl PluginCapabilities {
+    /// Whether the plugin has anything to say about a `provider` pipeline.
+    pub fn supports_provider(&self, provider: &str) -> bool {
+        self.providers.is_empty() || self.providers.iter().any(|p| p == provider)
+    }
+
+    /// Warning text when the plugin expects a different input schema.
+    pub fn protocol_mismatch(&self) -> Option<String> {
+        (self.input_schema_version != PLUGIN_PROTOCOL_VERSION).then(|| {
+            format!(
+                "Plugin '{}' expects input schema version {}, but this pipelinex sends version {}",
+                self.id, self.input_schema_version, PLUGIN_PROTOCOL_VERSION
+            )
+        })
+    }
+}
+
 fn default_timeout_ms() -> u64 {
     10_000
 }
@@ -177,6 +223,14 @@ pub fn run_external_analyzer_plugins_with_manifest(
 
     let mut findings = Vec::new();
     for plugin in manifest.analyzers.iter().filter(|plugin| plugin.enabled) {
+        if let Some(capabilities) = plugin_capabilities(plugin) {
+            if !capabilities.supports_provider(&dag.provider) {
+                continue;
+            }
+            if let Some(warning) = capabilities.protocol_mismatch() {
+                findings.push(plugin_error_finding(plugin.id.clone(), warning));
+            }
+        }
         match run_single_analyzer_plugin(plugin, &input_json) {
             Ok(plugin_findings) => findings.extend(plugin_findings),
             Err(error) => findings.push(plugin_error_finding(plugin.id.clone(), error)),
@@ -214,26 +268,11 @@ fn run_single_analyzer_plugin(
     plugin: &ExternalAnalyzerPlugin,
     input_json: &str,
 ) -> Result<Vec<Finding>, String> {
-    let mut child = Command::new(&plugin.command)
-        .args(&plugin.args)
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|error| format!("Failed to spawn plugin '{}': {}", plugin.id, error))?;
-
-    if let Some(stdin) = child.stdin.as_mut() {
-        stdin.write_all(input_json.as_bytes()).map_err(|error| {
-            format!(
-                "Failed to write stdin for plugin '{}': {}",
-                plugin.id, error
-            )
-        })?;
-    }
-
-    let output = child
-        .wait_with_output()
-        .map_err(|error| format!("Failed to wait on plugin '{}': {}", plugin.id, error))?;
+    let output = run_piped(
+        Command::new(&plugin.command).args(&plugin.args),
+        input_json.as_bytes(),
+    )
+    .map_err(|(stage, error)| format!("Failed to {} plugin '{}': {}", stage, plugin.id, error))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -251,6 +290,89 @@ fn run_single_analyzer_plugin(
     parse_plugin_output(plugin, &stdout)
 }
 
+/// Capabilities `plugin` declares, queried once per command line and cached.
+pub fn plugin_capabilities(plugin: &ExternalAnalyzerPlugin) -> Option<PluginCapabilities> {
+    static CACHE: OnceLock<Mutex<HashMap<Vec<String>, Option<PluginCapabilities>>>> =
+        OnceLock::new();
+
+    let key: Vec<String> = std::iter::once(plugin.command.clone())
+        .chain(plugin.args.iter().cloned())
+        .collect();
+    let cache = CACHE.get_or_init(Default::default);
+    if let Some(cached) = cache.lock().ok().and_then(|c| c.get(&key).cloned()) {
+        return cached;
+    }
+
+    let capabilities = query_capabilities(plugin);
+    if let Ok(mut cache) = cache.lock() {
+        cache.insert(key, capabilities.clone());
+    }
+    capabilities
+}
+
+fn query_capabilities(plugin: &ExternalAnalyzerPlugin) -> Option<PluginCapabilities> {
+    let output = crate::process::output_with_timeout(
+        Command::new(&plugin.command)
+            .args(&plugin.args)
+            .arg(CAPABILITIES_ARG),
+        Duration::from_millis(plugin.timeout_ms),
+    )
+    .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_capabilities(&String::from_utf8_lossy(&output.stdout)).ok()
+}
+
+/// Parse the JSON a plugin prints for `--capabilities`.
+pub fn parse_capabilities(stdout: &str) -> Result<PluginCapabilities, String> {
+    serde_json::from_str(stdout.trim())
+        .map_err(|error| format!("Invalid plugin capabilities JSON: {}", error))
+}
+
+/// Spawn `command`, feed `input` on stdin and collect stdout and stderr.
+///
+/// Stdin is written from a separate thread so a child that streams output
+/// while still reading cannot deadlock on full pipes. On failure the error
+/// names the stage (`spawn`, `write stdin for`, `wait on`).
+pub(crate) fn run_piped(
+    command: &mut Command,
+    input: &[u8],
+) -> Result<Output, (&'static str, std::io::Error)> {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| ("spawn", error))?;
+
+    let writer = child.stdin.take().map(|mut stdin| {
+        let input = input.to_vec();
+        std::thread::spawn(move || stdin.write_all(&input))
+    });
+
+    let output = child
+        .wait_with_output()
+        .map_err(|error| ("wait on", error))?;
+
+    if let Some(writer) = writer {
+        match writer.join() {
+            Ok(Ok(())) => {}
+            // The child may exit without reading all of stdin; that is its call.
+            Ok(Err(error)) if error.kind() == std::io::ErrorKind::BrokenPipe => {}
+            Ok(Err(error)) => return Err(("write stdin for", error)),
+            Err(_) => {
+                return Err((
+                    "write stdin for",
+                    std::io::Error::other("stdin writer panicked"),
+                ))
+            }
+        }
+    }
+
+    Ok(output)
+}
+
 fn parse_plugin_output(
     plugin: &ExternalAnalyzerPlugin,
     stdout: &str,
@@ -282,42 +404,31 @@ fn parse_plugin_output(
 }
 
 fn plugin_finding_to_core(plugin: &ExternalAnalyzerPlugin, finding: PluginFinding) -> Finding {
-    let category = match finding
-        .category
-        .as_deref()
-        .unwrap_or("")
-        .to_lowercase()
-        .as_str()
-    {
-        "criticalpath" | "critical_path" => FindingCategory::CriticalPath,
-        "missingcache" | "missing_cache" => FindingCategory::MissingCache,
-        "serialbottleneck" | "serial_bottleneck" => FindingCategory::SerialBottleneck,
-        "missingpathfilter" | "missing_path_filter" => FindingCategory::MissingPathFilter,
-        "shallowclone" | "shallow_clone" => FindingCategory::ShallowClone,
-        "redundantsteps" | "redundant_steps" => FindingCategory::RedundantSteps,
-        "dockeroptimization" | "docker_optimization" => FindingCategory::DockerOptimization,
-        "matrixoptimization" | "matrix_optimization" => FindingCategory::MatrixOptimization,
-        "flakytest" | "flaky_test" => FindingCategory::FlakyTest,
-        "concurrencycontrol" | "concurrency_control" => FindingCategory::ConcurrencyControl,
-        "artifactreuse" | "artifact_reuse" => FindingCategory::ArtifactReuse,
-        "runnersizing" | "runner_sizing" | "right_sizing" => FindingCategory::RunnerSizing,
-        _ => FindingCategory::CustomPlugin,
+    let category = match finding.category.as_deref().map(str::trim) {
+        Some("right_sizing") => FindingCategory::RunnerSizing,
+        Some(id) => FindingCategory::from_id(id).unwrap_or(FindingCategory::CustomPlugin),
+        None => FindingCategory::CustomPlugin,
     };
 
-    Finding {
-        severity: parse_severity(&finding.severity),
+    let mut builder = Finding::builder(
         category,
-        title: format!("[plugin:{}] {}", plugin.id, finding.title),
-        description: finding.description,
-        affected_jobs: finding.affected_jobs.unwrap_or_default(),
-        recommendation: finding
+        format!("[plugin:{}] {}", plugin.id, finding.title),
+    )
+    .severity(parse_severity(&finding.severity))
+    .description(finding.description)
+    .affected_jobs(finding.affected_jobs.unwrap_or_default())
+    .recommendation(
+        finding
             .recommendation
             .unwrap_or_else(|| "See plugin output for details.".to_string()),
-        fix_command: finding.fix_command,
-        estimated_savings_secs: finding.estimated_savings_secs,
-        confidence: finding.confidence.unwrap_or(0.7).clamp(0.0, 1.0),
-        auto_fixable: finding.auto_fixable.unwrap_or(false),
+    )
+    .savings_secs(finding.estimated_savings_secs)
+    .confidence(finding.confidence.unwrap_or(0.7))
+    .auto_fixable(finding.auto_fixable.unwrap_or(false));
+    if let Some(command) = finding.fix_command {
+        builder = builder.fix_command(command);
     }
+    builder.build()
 }
 
 fn parse_severity(value: &str) -> Severity {
@@ -331,18 +442,15 @@ fn parse_severity(value: &str) -> Severity {
 }
 
 fn plugin_error_finding(plugin_id: String, message: String) -> Finding {
-    Finding {
-        severity: Severity::Info,
-        category: FindingCategory::CustomPlugin,
-        title: format!("[plugin:{plugin_id}] plugin execution issue"),
-        description: message,
-        affected_jobs: Vec::new(),
-        recommendation: "Fix plugin command or manifest configuration.".to_string(),
-        fix_command: None,
-        estimated_savings_secs: None,
-        confidence: 0.3,
-        auto_fixable: false,
-    }
+    Finding::builder(
+        FindingCategory::CustomPlugin,
+        format!("[plugin:{plugin_id}] plugin execution issue"),
+    )
+    .severity(Severity::Info)
+    .description(message)
+    .recommendation("Fix plugin command or manifest configuration.")
+    .confidence(0.3)
+    .build()
 }
 
 /// Returns optimizer plugin entries declared in the manifest for future optimizer orchestration.
@@ -443,4 +551,77 @@ mod tests {
         assert_eq!(findings.len(), 1);
         assert_eq!(findings[0].category, FindingCategory::CustomPlugin);
     }
+
+    #[test]
+    fn test_parse_capabilities_response() {
+        let capabilities = parse_capabilities(
+            r#"{"id":"gha-secrets","categories":["custom_plugin"],"version":"0.3.1","input_schema_version":1,"providers":["github-actions"]}"#,
+        )
+        .unwrap();
+        assert_eq!(capabilities.id, "gha-secrets");
+        assert_eq!(capabilities.categories, vec!["custom_plugin"]);
+        assert_eq!(capabilities.version.as_deref(), Some("0.3.1"));
+        assert!(capabilities.supports_provider("github-actions"));
+        assert!(!capabilities.supports_provider("gitlab-ci"));
+        assert!(capabilities.protocol_mismatch().is_none());
+
+        let minimal = parse_capabilities(r#"{"id":"any","input_schema_version":1}"#).unwrap();
+        assert!(minimal.supports_provider("gitlab-ci"));
+
+        assert!(parse_capabilities("[]").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_protocol_version_mismatch_warns_and_provider_filter_skips() {
+        let mut dag = PipelineDag::new(
+            "test".to_string(),
+            "test.yml".to_string(),
+            "github-actions".to_string(),
+        );
+        dag.add_job(crate::parser::dag::JobNode::new(
+            "build".to_string(),
+            "build".to_string(),
+        ));
+
+        let script = |capabilities: &str| ExternalAnalyzerPlugin {
+            id: "scripted".to_string(),
+            command: "sh".to_string(),
+            args: vec![
+                "-c".to_string(),
+                format!(
+                    "if [ \"$1\" = --capabilities ]; then echo '{}'; else \
+                    echo '[{{\"severity\":\"low\",\"title\":\"ran\",\"description\":\"d\"}}]'; fi",
+                    capabilities
+                ),
+                "plugin".to_string(),
+            ],
+            timeout_ms: 5000,
+            enabled: true,
+        };
+
+        let future = script(r#"{"id":"future","input_schema_version":99}"#);
+        let capabilities = plugin_capabilities(&future).unwrap();
+        assert!(capabilities
+            .protocol_mismatch()
+            .unwrap()
+            .contains("version 99"));
+
+        let manifest = PluginManifest {
+            analyzers: vec![future],
+            optimizers: Vec::new(),
+        };
+        let findings = run_external_analyzer_plugins_with_manifest(&dag, &manifest);
+        assert_eq!(findings.len(), 2);
+        assert!(findings[0].description.contains("input schema version 99"));
+        assert!(findings[1].title.ends_with("ran"));
+
+        let gitlab_only = PluginManifest {
+            analyzers: vec![script(
+                r#"{"id":"gl","input_schema_version":1,"providers":["gitlab-ci"]}"#,
+            )],
+            optimizers: Vec::new(),
+        };
+        assert!(run_external_analyzer_plugins_with_manifest(&dag, &gitlab_only).is_empty());
+    }
 }
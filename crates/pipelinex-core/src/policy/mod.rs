@@ -1,3 +1,5 @@
+use crate::analyzer::cache_detector;
+use crate::analyzer::report::{Finding, FindingCategory, Severity};
 use crate::parser::dag::PipelineDag;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
@@ -40,6 +42,12 @@ pub struct PolicyRules {
 
     /// Minimum checkout version allowed (e.g., "v4")
     pub min_checkout_version: Option<String>,
+
+    /// Maximum number of jobs in one workflow
+    pub max_total_jobs: Option<usize>,
+
+    /// Maximum direct dependents of any single job
+    pub max_fan_out: Option<usize>,
 }
 
 /// A policy violation.
@@ -49,6 +57,44 @@ pub struct PolicyViolation {
     pub message: String,
     pub affected_jobs: Vec<String>,
     pub severity: PolicySeverity,
+    /// Already present in the `--baseline` report, so downgraded to a warning.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub in_baseline: bool,
+}
+
+impl PolicyViolation {
+    /// Identity used for baseline matching.
+    fn baseline_key(&self) -> (&str, &[String], &str) {
+        (&self.rule, &self.affected_jobs, &self.message)
+    }
+
+    /// The violation as an analysis finding, for `analyze --include-policy`.
+    ///
+    /// Errors map to high severity; warnings, including baselined violations,
+    /// to low.
+    pub fn to_finding(&self) -> Finding {
+        let severity = match self.severity {
+            PolicySeverity::Error => Severity::High,
+            PolicySeverity::Warning => Severity::Low,
+        };
+        Finding {
+            severity,
+            category: FindingCategory::Policy,
+            title: format!("Policy rule '{}' violated", self.rule),
+            description: self.message.clone(),
+            affected_jobs: self.affected_jobs.clone(),
+            recommendation: format!(
+                "Bring the pipeline into line with the '{}' rule, or adjust the policy file.",
+                self.rule
+            ),
+            fix_command: None,
+            estimated_savings_secs: None,
+            confidence: 1.0,
+            auto_fixable: false,
+            documentation_url: None,
+            blame: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -66,12 +112,78 @@ impl PolicySeverity {
     }
 }
 
+/// Outcome of one enabled policy rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleStatus {
+    Passed,
+    Failed,
+    /// The rule is enabled but nothing in the pipeline is subject to it.
+    NotApplicable,
+}
+
+impl RuleStatus {
+    pub fn symbol(&self) -> &str {
+        match self {
+            RuleStatus::Passed => "PASS",
+            RuleStatus::Failed => "FAIL",
+            RuleStatus::NotApplicable => "N/A",
+        }
+    }
+}
+
+/// Per-rule result, recorded for every rule the policy enables.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleResult {
+    pub rule: String,
+    pub status: RuleStatus,
+    /// What was checked, or why the rule did not apply.
+    pub detail: String,
+    pub violation_count: usize,
+}
+
+impl RuleResult {
+    /// Result for a rule whose violations are `new_violations`.
+    fn checked(rule: &str, checked: usize, unit: &str, new_violations: &[PolicyViolation]) -> Self {
+        let status = if !new_violations.is_empty() {
+            RuleStatus::Failed
+        } else if checked == 0 {
+            RuleStatus::NotApplicable
+        } else {
+            RuleStatus::Passed
+        };
+        let detail = if checked == 0 {
+            format!("no {} to check", unit)
+        } else {
+            format!("{} {} checked", checked, unit)
+        };
+        Self {
+            rule: rule.to_string(),
+            status,
+            detail,
+            violation_count: new_violations.len(),
+        }
+    }
+
+    fn not_applicable(rule: &str, detail: &str) -> Self {
+        Self {
+            rule: rule.to_string(),
+            status: RuleStatus::NotApplicable,
+            detail: detail.to_string(),
+            violation_count: 0,
+        }
+    }
+}
+
 /// Policy check result.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PolicyReport {
     pub source_file: String,
     pub violations: Vec<PolicyViolation>,
     pub passed: bool,
+    /// Every enabled rule with its pass/fail/not-applicable status.
+    #[serde(default)]
+    pub evaluated_rules: Vec<RuleResult>,
 }
 
 /// Load policy configuration from a TOML file.
@@ -83,12 +195,60 @@ pub fn load_policy(path: &Path) -> anyhow::Result<PolicyConfig> {
     Ok(config)
 }
 
+/// Load a baseline from earlier `policy check --format json` output.
+///
+/// Accepts a single report, an array of reports, or several reports
+/// concatenated (what the CLI prints for a directory).
+pub fn load_policy_baseline(path: &Path) -> anyhow::Result<Vec<PolicyReport>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read baseline '{}': {}", path.display(), e))?;
+    let parse_err = |e: serde_json::Error| {
+        anyhow::anyhow!("Failed to parse baseline '{}': {}", path.display(), e)
+    };
+
+    if content.trim_start().starts_with('[') {
+        return serde_json::from_str(&content).map_err(parse_err);
+    }
+    serde_json::Deserializer::from_str(&content)
+        .into_iter::<PolicyReport>()
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(parse_err)
+}
+
+/// Downgrade violations already present in `baseline` to warnings.
+///
+/// Violations match on `(rule, affected_jobs, message)`. Only violations not in
+/// the baseline can fail the check, which lets a strict policy be adopted
+/// without first fixing every existing violation.
+pub fn apply_baseline(report: &mut PolicyReport, baseline: &[PolicyReport]) {
+    let known: std::collections::HashSet<_> = baseline
+        .iter()
+        .flat_map(|r| &r.violations)
+        .map(PolicyViolation::baseline_key)
+        .collect();
+
+    for violation in &mut report.violations {
+        if known.contains(&violation.baseline_key()) {
+            violation.in_baseline = true;
+            violation.severity = PolicySeverity::Warning;
+        }
+    }
+
+    report.passed = report
+        .violations
+        .iter()
+        .all(|v| v.severity != PolicySeverity::Error);
+}
+
 /// Check a pipeline DAG against a policy configuration.
 pub fn check_policy(dag: &PipelineDag, policy: &PolicyConfig) -> PolicyReport {
     let mut violations = Vec::new();
+    let mut evaluated_rules = Vec::new();
 
     // Check SHA pinning
     if policy.rules.require_sha_pinning {
+        let before = violations.len();
+        let mut checked = 0;
         let sha_re = regex::Regex::new(r"@[0-9a-f]{40}$").unwrap();
         for node in dag.graph.node_weights() {
             for step in &node.steps {
@@ -96,6 +256,7 @@ pub fn check_policy(dag: &PipelineDag, policy: &PolicyConfig) -> PolicyReport {
                     if uses.starts_with("./") || uses.starts_with("docker://") {
                         continue;
                     }
+                    checked += 1;
                     if !sha_re.is_match(uses) {
                         violations.push(PolicyViolation {
                             rule: "require_sha_pinning".to_string(),
@@ -105,15 +266,23 @@ pub fn check_policy(dag: &PipelineDag, policy: &PolicyConfig) -> PolicyReport {
                             ),
                             affected_jobs: vec![node.id.clone()],
                             severity: PolicySeverity::Error,
+                            in_baseline: false,
                         });
                     }
                 }
             }
         }
+        evaluated_rules.push(RuleResult::checked(
+            "require_sha_pinning",
+            checked,
+            "action references",
+            &violations[before..],
+        ));
     }
 
     // Check banned runners
     if !policy.rules.banned_runners.is_empty() {
+        let before = violations.len();
         for node in dag.graph.node_weights() {
             if policy.rules.banned_runners.contains(&node.runs_on) {
                 violations.push(PolicyViolation {
@@ -121,13 +290,21 @@ pub fn check_policy(dag: &PipelineDag, policy: &PolicyConfig) -> PolicyReport {
                     message: format!("Job '{}' uses banned runner '{}'", node.id, node.runs_on),
                     affected_jobs: vec![node.id.clone()],
                     severity: PolicySeverity::Error,
+                    in_baseline: false,
                 });
             }
         }
+        evaluated_rules.push(RuleResult::checked(
+            "banned_runners",
+            dag.job_count(),
+            "jobs",
+            &violations[before..],
+        ));
     }
 
     // Check max duration
     if let Some(max_minutes) = policy.rules.max_duration_minutes {
+        let before = violations.len();
         let max_secs = max_minutes as f64 * 60.0;
         for node in dag.graph.node_weights() {
             if node.estimated_duration_secs > max_secs {
@@ -139,52 +316,151 @@ pub fn check_policy(dag: &PipelineDag, policy: &PolicyConfig) -> PolicyReport {
                     ),
                     affected_jobs: vec![node.id.clone()],
                     severity: PolicySeverity::Warning,
+                    in_baseline: false,
                 });
             }
         }
+        evaluated_rules.push(RuleResult::checked(
+            "max_duration_minutes",
+            dag.job_count(),
+            "jobs",
+            &violations[before..],
+        ));
+    }
+
+    // Check max total jobs
+    if let Some(max_jobs) = policy.rules.max_total_jobs {
+        let before = violations.len();
+        if dag.job_count() > max_jobs {
+            violations.push(PolicyViolation {
+                rule: "max_total_jobs".to_string(),
+                message: format!(
+                    "Workflow has {} jobs, exceeding the maximum of {}",
+                    dag.job_count(),
+                    max_jobs
+                ),
+                affected_jobs: Vec::new(),
+                severity: PolicySeverity::Error,
+                in_baseline: false,
+            });
+        }
+        evaluated_rules.push(RuleResult::checked(
+            "max_total_jobs",
+            1,
+            "workflows",
+            &violations[before..],
+        ));
+    }
+
+    // Check max fan-out (direct dependents, i.e. out-degree in the DAG)
+    if let Some(max_fan_out) = policy.rules.max_fan_out {
+        let before = violations.len();
+        let mut fan_outs: Vec<_> = dag
+            .graph
+            .node_indices()
+            .map(|idx| {
+                let dependents = dag
+                    .graph
+                    .neighbors_directed(idx, petgraph::Direction::Outgoing)
+                    .count();
+                (&dag.graph[idx].id, dependents)
+            })
+            .filter(|(_, dependents)| *dependents > max_fan_out)
+            .collect();
+        fan_outs.sort();
+        for (id, dependents) in fan_outs {
+            violations.push(PolicyViolation {
+                rule: "max_fan_out".to_string(),
+                message: format!(
+                    "Job '{}' has {} direct dependents, exceeding the maximum fan-out of {}",
+                    id, dependents, max_fan_out
+                ),
+                affected_jobs: vec![id.clone()],
+                severity: PolicySeverity::Warning,
+                in_baseline: false,
+            });
+        }
+        evaluated_rules.push(RuleResult::checked(
+            "max_fan_out",
+            dag.job_count(),
+            "jobs",
+            &violations[before..],
+        ));
     }
 
     // Check require_cache
     if !policy.rules.require_cache.is_empty() {
+        let before = violations.len();
+        let mut checked = 0;
         for node in dag.graph.node_weights() {
             for pm in &policy.rules.require_cache {
-                let uses_pm = node.steps.iter().any(|s| {
-                    if let Some(run) = &s.run {
-                        match pm.as_str() {
-                            "npm" => run.contains("npm ci") || run.contains("npm install"),
-                            "yarn" => run.contains("yarn install") || run.contains("yarn --frozen"),
-                            "pip" => run.contains("pip install"),
-                            "cargo" => run.contains("cargo build") || run.contains("cargo test"),
-                            _ => false,
-                        }
-                    } else {
-                        false
-                    }
-                });
-
-                if uses_pm && node.caches.is_empty() {
-                    let has_cache_action = node
-                        .steps
-                        .iter()
-                        .any(|s| s.uses.as_ref().is_some_and(|u| u.contains("cache")));
-                    if !has_cache_action {
-                        violations.push(PolicyViolation {
-                            rule: "require_cache".to_string(),
-                            message: format!(
-                                "Job '{}' uses {} but has no cache configured",
-                                node.id, pm
-                            ),
-                            affected_jobs: vec![node.id.clone()],
-                            severity: PolicySeverity::Error,
+                // Build tools share the cache detector's matching, so the two agree
+                let (uses_pm, cached) = match cache_detector::build_tool(pm) {
+                    Some(tool) => (
+                        cache_detector::runs_tool(node, tool),
+                        cache_detector::caches_tool(node, tool),
+                    ),
+                    None => {
+                        let uses_pm = node.steps.iter().any(|s| {
+                            if let Some(run) = &s.run {
+                                match pm.as_str() {
+                                    "npm" => run.contains("npm ci") || run.contains("npm install"),
+                                    "yarn" => {
+                                        run.contains("yarn install")
+                                            || run.contains("yarn --frozen")
+                                    }
+                                    "pip" => run.contains("pip install"),
+                                    "cargo" => {
+                                        run.contains("cargo build") || run.contains("cargo test")
+                                    }
+                                    _ => false,
+                                }
+                            } else {
+                                false
+                            }
                         });
+                        let has_cache_action = node
+                            .steps
+                            .iter()
+                            .any(|s| s.uses.as_ref().is_some_and(|u| u.contains("cache")));
+                        (uses_pm, !node.caches.is_empty() || has_cache_action)
                     }
+                };
+
+                if uses_pm {
+                    checked += 1;
+                }
+                if uses_pm && !cached {
+                    violations.push(PolicyViolation {
+                        rule: "require_cache".to_string(),
+                        message: format!(
+                            "Job '{}' uses {} but has no cache configured",
+                            node.id, pm
+                        ),
+                        affected_jobs: vec![node.id.clone()],
+                        severity: PolicySeverity::Error,
+                        in_baseline: false,
+                    });
                 }
             }
         }
+        evaluated_rules.push(RuleResult::checked(
+            "require_cache",
+            checked,
+            "package manager installs",
+            &violations[before..],
+        ));
     }
 
     // Check require_concurrency (GitHub Actions specific)
+    if policy.rules.require_concurrency && dag.provider != "github-actions" {
+        evaluated_rules.push(RuleResult::not_applicable(
+            "require_concurrency",
+            "only checked for GitHub Actions workflows",
+        ));
+    }
     if policy.rules.require_concurrency && dag.provider == "github-actions" {
+        let before = violations.len();
         // We check if the DAG name or env has concurrency info
         // Since we don't parse concurrency block into DAG, check as best effort
         let has_concurrency_env = dag.env.keys().any(|k| k.contains("concurrency"));
@@ -194,8 +470,68 @@ pub fn check_policy(dag: &PipelineDag, policy: &PolicyConfig) -> PolicyReport {
                 message: "Workflow does not have a concurrency control block".to_string(),
                 affected_jobs: dag.job_ids(),
                 severity: PolicySeverity::Warning,
+                in_baseline: false,
+            });
+        }
+        evaluated_rules.push(RuleResult::checked(
+            "require_concurrency",
+            1,
+            "workflows",
+            &violations[before..],
+        ));
+    }
+
+    // Check require_permissions_block (GitHub Actions specific): every job
+    // needs its own block or the workflow's
+    if policy.rules.require_permissions_block && dag.provider != "github-actions" {
+        evaluated_rules.push(RuleResult::not_applicable(
+            "require_permissions_block",
+            "only checked for GitHub Actions workflows",
+        ));
+    }
+    if policy.rules.require_permissions_block && dag.provider == "github-actions" {
+        let before = violations.len();
+        let undeclared: Vec<String> = dag
+            .graph
+            .node_weights()
+            .filter(|job| dag.effective_permissions(job).is_none())
+            .map(|job| job.id.clone())
+            .collect();
+        if !undeclared.is_empty() {
+            violations.push(PolicyViolation {
+                rule: "require_permissions_block".to_string(),
+                message: "Workflow does not declare an explicit permissions block".to_string(),
+                affected_jobs: undeclared,
+                severity: PolicySeverity::Warning,
+                in_baseline: false,
             });
         }
+        evaluated_rules.push(RuleResult::checked(
+            "require_permissions_block",
+            1,
+            "workflows",
+            &violations[before..],
+        ));
+    }
+
+    // Configurable, but not enforced by `check_policy` yet; listed so coverage
+    // reports don't imply they passed.
+    for (rule, enabled) in [
+        (
+            "block_hardcoded_secrets",
+            policy.rules.block_hardcoded_secrets,
+        ),
+        (
+            "min_checkout_version",
+            policy.rules.min_checkout_version.is_some(),
+        ),
+    ] {
+        if enabled {
+            evaluated_rules.push(RuleResult::not_applicable(
+                rule,
+                "not enforced by policy check",
+            ));
+        }
     }
 
     let passed = violations
@@ -206,6 +542,7 @@ pub fn check_policy(dag: &PipelineDag, policy: &PolicyConfig) -> PolicyReport {
         source_file: dag.source_file.clone(),
         violations,
         passed,
+        evaluated_rules,
     }
 }
 
@@ -227,6 +564,13 @@ require_cache = []
 # Maximum allowed pipeline duration (minutes)
 # max_duration_minutes = 30
 
+# Maximum number of jobs in a single workflow (violations are errors)
+# max_total_jobs = 50
+
+# Maximum direct dependents of any one job, i.e. how many jobs may wait on
+# it (violations are warnings)
+# max_fan_out = 20
+
 # Require explicit permissions block (GitHub Actions)
 require_permissions_block = false
 
@@ -243,6 +587,7 @@ block_hardcoded_secrets = true
 mod tests {
     use super::*;
     use crate::parser::dag::{JobNode, PipelineDag, StepInfo};
+    use std::collections::HashMap;
 
     fn make_test_dag() -> PipelineDag {
         let mut dag = PipelineDag::new("ci".into(), "ci.yml".into(), "github-actions".into());
@@ -252,12 +597,18 @@ mod tests {
             uses: Some("actions/checkout@v4".into()),
             run: None,
             estimated_duration_secs: None,
+            with: HashMap::new(),
+            working_directory: None,
+            env: HashMap::new(),
         });
         job.steps.push(StepInfo {
             name: "Build".into(),
             uses: None,
             run: Some("npm ci && npm run build".into()),
             estimated_duration_secs: None,
+            with: HashMap::new(),
+            working_directory: None,
+            env: HashMap::new(),
         });
         dag.add_job(job);
         dag
@@ -308,6 +659,263 @@ mod tests {
         assert!(report.violations.iter().any(|v| v.rule == "require_cache"));
     }
 
+    #[test]
+    fn test_require_go_cache_ignores_cargo_jobs() {
+        let mut dag = PipelineDag::new("ci".into(), "ci.yml".into(), "github-actions".into());
+        let mut rust = JobNode::new("rust".into(), "Rust".into());
+        rust.steps.push(StepInfo {
+            name: "Test".into(),
+            uses: None,
+            run: Some("cargo build --release && cargo test".into()),
+            estimated_duration_secs: None,
+            with: HashMap::new(),
+            working_directory: None,
+            env: HashMap::new(),
+        });
+        dag.add_job(rust);
+        let mut go = JobNode::new("go".into(), "Go".into());
+        go.steps.push(StepInfo {
+            name: "Setup Go".into(),
+            uses: Some("actions/setup-go@v5".into()),
+            run: None,
+            estimated_duration_secs: None,
+            with: HashMap::new(),
+            working_directory: None,
+            env: HashMap::new(),
+        });
+        go.steps.push(StepInfo {
+            name: "Test".into(),
+            uses: None,
+            run: Some("go test ./...".into()),
+            estimated_duration_secs: None,
+            with: HashMap::new(),
+            working_directory: None,
+            env: HashMap::new(),
+        });
+        dag.add_job(go);
+
+        let policy = PolicyConfig {
+            rules: PolicyRules {
+                require_cache: vec!["go".into(), "gradle".into(), "maven".into()],
+                ..Default::default()
+            },
+        };
+        let report = check_policy(&dag, &policy);
+        // setup-go v4+ caches by default, and the Rust job runs none of the tools
+        assert!(report.passed, "{:?}", report.violations);
+    }
+
+    #[test]
+    fn test_evaluated_rules_report_passing_rule() {
+        let mut dag = make_test_dag();
+        for job in dag.graph.node_weights_mut() {
+            job.steps[0].uses =
+                Some("actions/checkout@11bd71901bbe5b1630ceea73d27597364c9af683".into());
+        }
+        let policy = PolicyConfig {
+            rules: PolicyRules {
+                require_sha_pinning: true,
+                require_concurrency: true,
+                block_hardcoded_secrets: true,
+                ..Default::default()
+            },
+        };
+        let report = check_policy(&dag, &policy);
+
+        let sha = report
+            .evaluated_rules
+            .iter()
+            .find(|r| r.rule == "require_sha_pinning")
+            .unwrap();
+        assert_eq!(sha.status, RuleStatus::Passed);
+        assert_eq!(sha.violation_count, 0);
+
+        let status_of = |rule: &str| {
+            report
+                .evaluated_rules
+                .iter()
+                .find(|r| r.rule == rule)
+                .map(|r| r.status)
+        };
+        assert_eq!(status_of("require_concurrency"), Some(RuleStatus::Failed));
+        assert_eq!(
+            status_of("block_hardcoded_secrets"),
+            Some(RuleStatus::NotApplicable)
+        );
+        assert_eq!(status_of("banned_runners"), None);
+    }
+
+    #[test]
+    fn test_require_permissions_block() {
+        let yaml = r#"
+on: push
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    permissions:
+      contents: read
+    steps:
+      - run: npm test
+  deploy:
+    runs-on: ubuntu-latest
+    steps:
+      - run: ./deploy.sh
+"#;
+        let policy = PolicyConfig {
+            rules: PolicyRules {
+                require_permissions_block: true,
+                ..Default::default()
+            },
+        };
+        let mut dag =
+            crate::parser::github::GitHubActionsParser::parse(yaml, "ci.yml".into()).unwrap();
+        let report = check_policy(&dag, &policy);
+        let violation = report
+            .violations
+            .iter()
+            .find(|v| v.rule == "require_permissions_block")
+            .unwrap();
+        assert_eq!(violation.affected_jobs, vec!["deploy"]);
+
+        // A workflow-level block covers every job
+        dag.permissions = Some(crate::parser::dag::Permissions::ReadAll);
+        let report = check_policy(&dag, &policy);
+        assert!(report.violations.is_empty());
+        assert_eq!(
+            report.evaluated_rules[0].status,
+            RuleStatus::Passed,
+            "{:?}",
+            report.evaluated_rules
+        );
+    }
+
+    #[test]
+    fn test_max_total_jobs_violation() {
+        let yaml = crate::synthetic::github_workflow_yaml(12, 4);
+        let dag =
+            crate::parser::github::GitHubActionsParser::parse(&yaml, "ci.yml".into()).unwrap();
+        let policy = PolicyConfig {
+            rules: PolicyRules {
+                max_total_jobs: Some(10),
+                ..Default::default()
+            },
+        };
+        let report = check_policy(&dag, &policy);
+        assert!(!report.passed);
+        let violation = report
+            .violations
+            .iter()
+            .find(|v| v.rule == "max_total_jobs")
+            .unwrap();
+        assert!(violation.message.contains("12 jobs"));
+        assert_eq!(violation.severity, PolicySeverity::Error);
+
+        let lenient = PolicyConfig {
+            rules: PolicyRules {
+                max_total_jobs: Some(12),
+                ..Default::default()
+            },
+        };
+        assert!(check_policy(&dag, &lenient).passed);
+    }
+
+    #[test]
+    fn test_max_fan_out_violation() {
+        let mut dag = PipelineDag::new("ci".into(), "ci.yml".into(), "github-actions".into());
+        dag.add_job(JobNode::new("setup".into(), "Setup".into()));
+        for i in 0..6 {
+            let id = format!("shard-{}", i);
+            dag.add_job(JobNode::new(id.clone(), id.clone()));
+            dag.add_dependency("setup", &id).unwrap();
+        }
+        let policy = PolicyConfig {
+            rules: PolicyRules {
+                max_fan_out: Some(5),
+                ..Default::default()
+            },
+        };
+        let report = check_policy(&dag, &policy);
+        let fan_out: Vec<_> = report
+            .violations
+            .iter()
+            .filter(|v| v.rule == "max_fan_out")
+            .collect();
+        assert_eq!(fan_out.len(), 1);
+        assert_eq!(fan_out[0].affected_jobs, vec!["setup"]);
+        assert_eq!(fan_out[0].severity, PolicySeverity::Warning);
+        // Warnings alone do not fail the check.
+        assert!(report.passed);
+    }
+
+    #[test]
+    fn test_baseline_only_fails_on_new_violations() {
+        let dag = make_test_dag();
+        let policy = PolicyConfig {
+            rules: PolicyRules {
+                require_sha_pinning: true,
+                ..Default::default()
+            },
+        };
+        let baseline = check_policy(&dag, &policy);
+        assert!(!baseline.passed);
+
+        // Same violations as the baseline: reported, but as warnings.
+        let mut report = check_policy(&dag, &policy);
+        apply_baseline(&mut report, std::slice::from_ref(&baseline));
+        assert!(report.passed);
+        assert!(report
+            .violations
+            .iter()
+            .all(|v| v.in_baseline && v.severity == PolicySeverity::Warning));
+
+        // A newly introduced unpinned action still fails.
+        let mut changed = make_test_dag();
+        for job in changed.graph.node_weights_mut() {
+            job.steps.push(StepInfo {
+                name: "Setup".into(),
+                uses: Some("actions/setup-node@v4".into()),
+                run: None,
+                estimated_duration_secs: None,
+                with: HashMap::new(),
+                working_directory: None,
+                env: HashMap::new(),
+            });
+        }
+        let mut report = check_policy(&changed, &policy);
+        apply_baseline(&mut report, &[baseline]);
+        assert!(!report.passed);
+        let new: Vec<_> = report
+            .violations
+            .iter()
+            .filter(|v| !v.in_baseline)
+            .collect();
+        assert_eq!(new.len(), 1);
+        assert!(new[0].message.contains("setup-node"));
+    }
+
+    #[test]
+    fn test_load_concatenated_baseline() {
+        let dag = make_test_dag();
+        let report = check_policy(
+            &dag,
+            &PolicyConfig {
+                rules: PolicyRules {
+                    banned_runners: vec!["ubuntu-latest".into()],
+                    ..Default::default()
+                },
+            },
+        );
+        let json = serde_json::to_string_pretty(&report).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("baseline.json");
+        std::fs::write(&path, format!("{}\n{}\n", json, json)).unwrap();
+        assert_eq!(load_policy_baseline(&path).unwrap().len(), 2);
+
+        std::fs::write(&path, format!("[{}]", json)).unwrap();
+        let loaded = load_policy_baseline(&path).unwrap();
+        assert_eq!(loaded[0].violations.len(), 1);
+    }
+
     #[test]
     fn test_empty_policy_passes() {
         let dag = make_test_dag();
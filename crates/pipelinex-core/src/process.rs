@@ -0,0 +1,117 @@
+//! Running external commands with a deadline.
+
+use std::io::{self, Read};
+use std::process::{Child, Command, ExitStatus, Output, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Longest pause between exit checks while waiting on a child.
+const MAX_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Wait for `child` to exit, killing it once `timeout` has passed.
+///
+/// On timeout the child is killed and reaped, and an error of kind
+/// [`io::ErrorKind::TimedOut`] is returned.
+pub fn wait_with_timeout(child: &mut Child, timeout: Duration) -> io::Result<ExitStatus> {
+    let deadline = Instant::now() + timeout;
+    let mut interval = Duration::from_millis(1);
+
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            // The child may have exited in the meantime; either way reap it.
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("timed out after {}", format_timeout(timeout)),
+            ));
+        }
+        thread::sleep(interval.min(deadline - now));
+        interval = (interval * 2).min(MAX_POLL_INTERVAL);
+    }
+}
+
+/// Like [`Command::output`], but kills the command once `timeout` has passed.
+///
+/// Stdin is closed; stdout and stderr are collected on background threads so a
+/// chatty child cannot block on a full pipe.
+pub fn output_with_timeout(command: &mut Command, timeout: Duration) -> io::Result<Output> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let stdout = child.stdout.take().map(read_to_end_in_background);
+    let stderr = child.stderr.take().map(read_to_end_in_background);
+
+    // On timeout the readers are left behind: a grandchild may still hold the pipes.
+    let status = wait_with_timeout(&mut child, timeout)?;
+
+    let collect = |reader: Option<thread::JoinHandle<Vec<u8>>>| {
+        reader
+            .map(|r| r.join().unwrap_or_default())
+            .unwrap_or_default()
+    };
+    Ok(Output {
+        status,
+        stdout: collect(stdout),
+        stderr: collect(stderr),
+    })
+}
+
+fn read_to_end_in_background<R: Read + Send + 'static>(mut pipe: R) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = pipe.read_to_end(&mut buf);
+        buf
+    })
+}
+
+fn format_timeout(timeout: Duration) -> String {
+    if timeout.subsec_millis() == 0 {
+        format!("{}s", timeout.as_secs())
+    } else {
+        format!("{:.1}s", timeout.as_secs_f64())
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sleeping_command_times_out() {
+        let started = Instant::now();
+        let err = output_with_timeout(
+            Command::new("sh").args(["-c", "sleep 5"]),
+            Duration::from_millis(200),
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        assert_eq!(err.to_string(), "timed out after 0.2s");
+        assert!(started.elapsed() < Duration::from_secs(3));
+
+        let mut child = Command::new("sleep").arg("5").spawn().unwrap();
+        let err = wait_with_timeout(&mut child, Duration::from_millis(100)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        // Killed and reaped: the exit status is already collected
+        assert!(child.try_wait().unwrap().is_some());
+    }
+
+    #[test]
+    fn test_fast_command_output_is_collected() {
+        let output = output_with_timeout(
+            Command::new("sh").args(["-c", "echo out; echo err >&2; exit 3"]),
+            Duration::from_secs(10),
+        )
+        .unwrap();
+        assert_eq!(output.status.code(), Some(3));
+        assert_eq!(output.stdout, b"out\n");
+        assert_eq!(output.stderr, b"err\n");
+    }
+}
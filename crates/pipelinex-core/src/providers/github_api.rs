@@ -10,8 +10,12 @@ pub struct GitHubClient {
     #[allow(dead_code)]
     token: Option<String>,
     base_url: String,
+    timeout: Duration,
 }
 
+/// Per-request timeout used by [`GitHubClient::new`].
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
 /// Workflow run from GitHub Actions API
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkflowRun {
@@ -118,6 +122,11 @@ pub struct PullRequest {
 impl GitHubClient {
     /// Create a new GitHub API client
     pub fn new(token: Option<String>) -> Result<Self> {
+        Self::with_timeout(token, DEFAULT_TIMEOUT)
+    }
+
+    /// Like [`GitHubClient::new`], failing any request that takes longer than `timeout`.
+    pub fn with_timeout(token: Option<String>, timeout: Duration) -> Result<Self> {
         let mut headers = HeaderMap::new();
         headers.insert(
             ACCEPT,
@@ -134,7 +143,7 @@ impl GitHubClient {
 
         let client = reqwest::Client::builder()
             .default_headers(headers)
-            .timeout(Duration::from_secs(30))
+            .timeout(timeout)
             .build()
             .context("Failed to build HTTP client")?;
 
@@ -142,6 +151,7 @@ impl GitHubClient {
             client,
             token,
             base_url: "https://api.github.com".to_string(),
+            timeout,
         })
     }
 
@@ -411,7 +421,16 @@ impl GitHubClient {
             .json(&request)
             .send()
             .await
-            .context("Failed to create pull request")?
+            .map_err(|error| {
+                if error.is_timeout() {
+                    anyhow::anyhow!(
+                        "GitHub API did not respond within {}s while creating the pull request",
+                        self.timeout.as_secs()
+                    )
+                } else {
+                    anyhow::Error::new(error).context("Failed to create pull request")
+                }
+            })?
             .error_for_status()
             .context("GitHub API returned error when creating PR")?
             .json()
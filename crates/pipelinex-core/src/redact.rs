@@ -1,5 +1,63 @@
 use crate::analyzer::report::{AnalysisReport, Finding};
 use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Kinds of identifier a [`RedactionPolicy`] can replace with placeholder tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RedactionTarget {
+    /// The analyzed file and absolute paths in finding text (`<path:N>`).
+    Paths,
+    /// `owner/repo` on GitHub, GitLab and Bitbucket, and the commit (`<repo:N>`, `<commit:N>`).
+    RepoIds,
+    /// Environment variable references such as `$API_HOST` or `${{ env.REGION }}` (`<env:N>`).
+    Env,
+    /// Job ids in the critical path, slack table and findings (`<job:N>`).
+    JobNames,
+}
+
+/// What to hide when a report is shared externally.
+///
+/// Secrets and internal URLs are always redacted. On top of that, each
+/// identifier of a `redact` kind, and each match of `patterns`, becomes a
+/// numbered placeholder such as `<path:1>`. The same value always maps to
+/// the same token within a report, so cross-references stay readable.
+/// Values fully matching a `preserve` pattern are left as they are.
+///
+/// ```toml
+/// redact = ["paths", "repo-ids", "env", "job-names"]
+/// patterns = ["acme-[a-z]+"]
+/// preserve = ["^(build|test|lint)$"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RedactionPolicy {
+    pub redact: Vec<RedactionTarget>,
+    /// Extra regexes whose matches are replaced with `<redacted:N>`.
+    pub patterns: Vec<String>,
+    /// Regexes for values that are never replaced.
+    pub preserve: Vec<String>,
+}
+
+/// Load a redaction policy from a TOML file.
+pub fn load_redaction_policy(path: &Path) -> anyhow::Result<RedactionPolicy> {
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        anyhow::anyhow!(
+            "Failed to read redaction policy '{}': {}",
+            path.display(),
+            e
+        )
+    })?;
+    toml::from_str(&content).map_err(|e| {
+        anyhow::anyhow!(
+            "Failed to parse redaction policy '{}': {}",
+            path.display(),
+            e
+        )
+    })
+}
 
 /// Redact sensitive information from an analysis report.
 pub fn redact_report(report: &AnalysisReport) -> AnalysisReport {
@@ -17,6 +75,191 @@ pub fn redact_report(report: &AnalysisReport) -> AnalysisReport {
     redacted
 }
 
+/// Redact a report under `policy`: the fixed redaction of [`redact_report`],
+/// then placeholder tokens for everything the policy selects.
+///
+/// Fails if a `patterns` or `preserve` entry is not a valid regex.
+pub fn redact_report_with(
+    report: &AnalysisReport,
+    policy: &RedactionPolicy,
+) -> anyhow::Result<AnalysisReport> {
+    let mut tokens = Tokenizer::new(policy)?;
+    let mut redacted = redact_report(report);
+
+    if policy.redact.contains(&RedactionTarget::Paths) {
+        // Mentions of the file in text use either the original or the shortened form
+        let original = report.source_file.clone();
+        let short = redacted.source_file.clone();
+        let token = tokens.token("path", &original);
+        redacted.source_file = token.clone();
+        if token != original {
+            let mentions = [original, short];
+            redacted.map_text(|text| {
+                mentions
+                    .iter()
+                    .filter(|m| !m.is_empty())
+                    .fold(text.to_string(), |t, m| t.replace(m.as_str(), &token))
+            });
+        }
+        let absolute = Regex::new(r#"(^|[\s'"`(=])((?:~|/)[\w.-]+(?:/[\w.*-]+)+)"#).unwrap();
+        redacted
+            .map_text(|text| replace_group(&absolute, text, 2, |path| tokens.token("path", path)));
+    }
+
+    if policy.redact.contains(&RedactionTarget::RepoIds) {
+        if let Some(commit) = &redacted.metadata.git_commit {
+            redacted.metadata.git_commit = Some(tokens.token("commit", commit));
+        }
+        let repo =
+            Regex::new(r"(?:github\.com|gitlab\.com|bitbucket\.org)[/:]([\w.-]+/[\w.-]*[\w-])")
+                .unwrap();
+        redacted.map_text(|text| replace_group(&repo, text, 1, |id| tokens.token("repo", id)));
+    }
+
+    if policy.redact.contains(&RedactionTarget::Env) {
+        let env = Regex::new(r"\$\{\{\s*env\.\w+\s*\}\}|\$\{?[A-Z_][A-Z0-9_]*\}?").unwrap();
+        redacted.map_text(|text| replace_group(&env, text, 0, |var| tokens.token("env", var)));
+    }
+
+    if policy.redact.contains(&RedactionTarget::JobNames) {
+        let mut jobs: Vec<String> = Vec::new();
+        let named = redacted
+            .critical_path
+            .iter()
+            .chain(redacted.job_slack.iter().map(|s| &s.job_id))
+            .chain(redacted.findings.iter().flat_map(|f| &f.affected_jobs));
+        for job in named {
+            if !jobs.contains(job) {
+                jobs.push(job.clone());
+            }
+        }
+        let mapping: HashMap<String, String> = jobs
+            .iter()
+            .map(|job| (job.clone(), tokens.token("job", job)))
+            .collect();
+        let rename = |job: &mut String| {
+            if let Some(token) = mapping.get(job.as_str()) {
+                *job = token.clone();
+            }
+        };
+        redacted.critical_path.iter_mut().for_each(rename);
+        redacted
+            .job_slack
+            .iter_mut()
+            .for_each(|s| rename(&mut s.job_id));
+        for finding in &mut redacted.findings {
+            finding.affected_jobs.iter_mut().for_each(rename);
+        }
+
+        // Longest names first so `build` doesn't eat part of `build-docs`
+        jobs.sort_by_key(|job| std::cmp::Reverse(job.len()));
+        for job in jobs.iter().filter(|job| mapping[*job] != **job) {
+            let mention =
+                Regex::new(&format!(r"(^|[^\w-]){}($|[^\w-])", regex::escape(job))).unwrap();
+            redacted.map_text(|text| {
+                mention
+                    .replace_all(text, |caps: &regex::Captures| {
+                        format!("{}{}{}", &caps[1], mapping[job], &caps[2])
+                    })
+                    .to_string()
+            });
+        }
+    }
+
+    let patterns = std::mem::take(&mut tokens.patterns);
+    for pattern in &patterns {
+        redacted.map_text(|text| {
+            replace_group(pattern, text, 0, |value| tokens.token("redacted", value))
+        });
+    }
+
+    Ok(redacted)
+}
+
+/// Assigns stable, numbered placeholders per kind, in order of first use.
+struct Tokenizer {
+    patterns: Vec<Regex>,
+    preserve: Vec<Regex>,
+    assigned: HashMap<(&'static str, String), String>,
+    counts: HashMap<&'static str, usize>,
+}
+
+impl Tokenizer {
+    fn new(policy: &RedactionPolicy) -> anyhow::Result<Self> {
+        let compile = |patterns: &[String], field: &str| {
+            patterns
+                .iter()
+                .map(|p| {
+                    Regex::new(p).map_err(|e| {
+                        anyhow::anyhow!("Invalid redaction {} pattern '{}': {}", field, p, e)
+                    })
+                })
+                .collect::<anyhow::Result<Vec<_>>>()
+        };
+        Ok(Self {
+            patterns: compile(&policy.patterns, "patterns")?,
+            preserve: compile(&policy.preserve, "preserve")?,
+            assigned: HashMap::new(),
+            counts: HashMap::new(),
+        })
+    }
+
+    /// The placeholder for `value`, or `value` itself if it is preserved.
+    fn token(&mut self, kind: &'static str, value: &str) -> String {
+        let preserved = self.preserve.iter().any(|re| {
+            re.find(value)
+                .is_some_and(|m| m.start() == 0 && m.end() == value.len())
+        });
+        if preserved {
+            return value.to_string();
+        }
+        let counts = &mut self.counts;
+        self.assigned
+            .entry((kind, value.to_string()))
+            .or_insert_with(|| {
+                let n = counts.entry(kind).or_insert(0);
+                *n += 1;
+                format!("<{}:{}>", kind, n)
+            })
+            .clone()
+    }
+}
+
+/// Replace capture group `group` of every match of `re` in `text`.
+fn replace_group(
+    re: &Regex,
+    text: &str,
+    group: usize,
+    mut replace: impl FnMut(&str) -> String,
+) -> String {
+    re.replace_all(text, |caps: &regex::Captures| {
+        let whole = caps.get(0).unwrap();
+        let target = caps.get(group).unwrap();
+        format!(
+            "{}{}{}",
+            &text[whole.start()..target.start()],
+            replace(target.as_str()),
+            &text[target.end()..whole.end()]
+        )
+    })
+    .to_string()
+}
+
+impl AnalysisReport {
+    /// Apply `f` to every free-text field of the report.
+    fn map_text(&mut self, mut f: impl FnMut(&str) -> String) {
+        self.pipeline_name = f(&self.pipeline_name);
+        for finding in &mut self.findings {
+            finding.title = f(&finding.title);
+            finding.description = f(&finding.description);
+            finding.recommendation = f(&finding.recommendation);
+            if let Some(cmd) = &finding.fix_command {
+                finding.fix_command = Some(f(cmd));
+            }
+        }
+    }
+}
+
 fn redact_finding(mut finding: Finding) -> Finding {
     finding.description = redact_secrets_in_text(&finding.description);
     finding.recommendation = redact_secrets_in_text(&finding.recommendation);
@@ -114,6 +357,88 @@ mod tests {
         assert!(!redacted.contains("internal.corp.com"));
     }
 
+    fn report_with_paths() -> AnalysisReport {
+        let dag = crate::parser::github::GitHubActionsParser::parse(
+            r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm ci
+  test:
+    needs: build
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm ci
+"#,
+            "/home/alice/acme-app/.github/workflows/ci.yml".to_string(),
+        )
+        .unwrap();
+        let mut report = crate::analyzer::analyze(&dag);
+        report.findings[0].description = format!(
+            "{} reads /home/alice/acme-app/config/ci.env; see \
+            https://github.com/acme/app and /home/alice/acme-app/config/ci.env",
+            report.source_file
+        );
+        report
+    }
+
+    #[test]
+    fn test_policy_tokenizes_paths_and_preserves_job_names() {
+        let report = report_with_paths();
+        let policy = RedactionPolicy {
+            redact: vec![
+                RedactionTarget::Paths,
+                RedactionTarget::RepoIds,
+                RedactionTarget::JobNames,
+            ],
+            patterns: Vec::new(),
+            preserve: vec!["^(build|test)$".into()],
+        };
+        let redacted = redact_report_with(&report, &policy).unwrap();
+
+        assert_eq!(redacted.source_file, "<path:1>");
+        assert_eq!(
+            redacted.findings[0].description,
+            "<path:1> reads <path:2>; see https://github.com/<repo:1> and <path:2>"
+        );
+        assert_eq!(redacted.critical_path, report.critical_path);
+        assert!(redacted.critical_path.contains(&"build".to_string()));
+        let json = serde_json::to_string(&redacted).unwrap();
+        assert!(!json.contains("alice"));
+
+        // Deterministic: the same report redacts identically
+        let again = redact_report_with(&report, &policy).unwrap();
+        assert_eq!(
+            serde_json::to_string(&again).unwrap(),
+            serde_json::to_string(&redacted).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_policy_tokenizes_job_names_everywhere() {
+        let report = report_with_paths();
+        let policy = RedactionPolicy {
+            redact: vec![RedactionTarget::JobNames],
+            ..Default::default()
+        };
+        let redacted = redact_report_with(&report, &policy).unwrap();
+        assert_eq!(redacted.critical_path, vec!["<job:1>", "<job:2>"]);
+        assert!(redacted
+            .job_slack
+            .iter()
+            .all(|s| s.job_id.starts_with("<job:")));
+        let json = serde_json::to_string(&redacted.findings).unwrap();
+        assert!(!json.contains("'build'") && !json.contains("'test'"));
+
+        let invalid = RedactionPolicy {
+            patterns: vec!["(".into()],
+            ..Default::default()
+        };
+        assert!(redact_report_with(&report, &invalid).is_err());
+    }
+
     #[test]
     fn test_preserve_github_urls() {
         let text = "See https://github.com/actions/checkout for details";
@@ -176,6 +176,7 @@ fn extract_docker_images(run: &str) -> Vec<SbomComponent> {
 mod tests {
     use super::*;
     use crate::parser::dag::{JobNode, PipelineDag, StepInfo};
+    use std::collections::HashMap;
 
     #[test]
     fn test_parse_github_action() {
@@ -208,12 +209,18 @@ mod tests {
             uses: Some("actions/checkout@v4".into()),
             run: None,
             estimated_duration_secs: None,
+            with: HashMap::new(),
+            working_directory: None,
+            env: HashMap::new(),
         });
         job.steps.push(StepInfo {
             name: "Build".into(),
             uses: None,
             run: Some("docker run node:20 npm test".into()),
             estimated_duration_secs: None,
+            with: HashMap::new(),
+            working_directory: None,
+            env: HashMap::new(),
         });
         dag.add_job(job);
 
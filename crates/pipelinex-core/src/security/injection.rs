@@ -1,5 +1,5 @@
 use crate::analyzer::report::{Finding, FindingCategory, Severity};
-use crate::parser::dag::PipelineDag;
+use crate::parser::dag::{PipelineDag, StepInfo};
 
 /// Dangerous GitHub Actions expression contexts that can be attacker-controlled.
 const DANGEROUS_CONTEXTS: &[&str] = &[
@@ -33,7 +33,7 @@ pub fn detect_injection(dag: &PipelineDag) -> Vec<Finding> {
                     if run.contains(&expression) {
                         findings.push(Finding {
                             severity: Severity::Critical,
-                            category: FindingCategory::CustomPlugin,
+                            category: FindingCategory::Security,
                             title: format!("Expression injection via {}", ctx),
                             description: format!(
                                 "Job '{}', step '{}' uses `{}` directly in a `run:` step. \
@@ -51,6 +51,8 @@ pub fn detect_injection(dag: &PipelineDag) -> Vec<Finding> {
                             estimated_savings_secs: None,
                             confidence: 0.95,
                             auto_fixable: false,
+                            documentation_url: None,
+                            blame: None,
                         });
                     }
                 }
@@ -58,13 +60,107 @@ pub fn detect_injection(dag: &PipelineDag) -> Vec<Finding> {
         }
     }
 
+    findings.extend(detect_untrusted_checkout(dag));
+
+    findings
+}
+
+/// `actions/checkout` refs that point at the pull request's own code.
+const PR_HEAD_REFS: &[&str] = &[
+    "github.event.pull_request.head.sha",
+    "github.event.pull_request.head.ref",
+    "github.head_ref",
+    "refs/pull/",
+];
+
+/// Detect `pull_request_target` workflows that check out the pull request's
+/// head and then use secrets ("pwn request").
+///
+/// `pull_request_target` runs with the base repository's secrets and a
+/// write token even for forks. Checking out the head ref puts attacker code
+/// in the workspace, where any later step that builds, tests or installs it
+/// runs that code next to the secrets.
+fn detect_untrusted_checkout(dag: &PipelineDag) -> Vec<Finding> {
+    if !dag
+        .triggers
+        .iter()
+        .any(|t| t.event == "pull_request_target")
+    {
+        return Vec::new();
+    }
+
+    let mut findings = Vec::new();
+    for job in dag.graph.node_weights() {
+        let Some(checkout) = job.steps.iter().position(checks_out_pr_head) else {
+            continue;
+        };
+        let job_secrets = job.env.values().any(|v| uses_secrets(v));
+        let secret_steps: Vec<&str> = job.steps[checkout + 1..]
+            .iter()
+            .filter(|step| {
+                job_secrets
+                    || step.run.as_deref().is_some_and(uses_secrets)
+                    || step.with.values().any(|v| uses_secrets(v))
+                    || step.env.values().any(|v| uses_secrets(v))
+            })
+            .map(|step| step.name.as_str())
+            .collect();
+        if secret_steps.is_empty() {
+            continue;
+        }
+
+        let checkout_ref = &job.steps[checkout].with["ref"];
+        findings.push(
+            Finding::builder(
+                FindingCategory::Security,
+                format!(
+                    "pull_request_target in '{}' runs untrusted PR code with secrets",
+                    job.id
+                ),
+            )
+            .severity(Severity::Critical)
+            .description(format!(
+                "The workflow runs on `pull_request_target`, which has access to repository \
+                secrets even for pull requests from forks. Job '{}' checks out `{}` and \
+                then uses secrets in: {}. Code from the pull request can read them.",
+                job.id,
+                checkout_ref.trim(),
+                secret_steps.join(", ")
+            ))
+            .affected_jobs([job.id.as_str()])
+            .recommendation(
+                "Run untrusted code under `on: pull_request`, which gets no secrets for \
+                forks, and move steps that need secrets into a separate `workflow_run` \
+                workflow that only consumes the PR's artifacts. If this job must stay on \
+                `pull_request_target`, check out the base ref (drop `ref:`) and do not \
+                execute code from the PR.",
+            )
+            .confidence(0.9)
+            .build(),
+        );
+    }
     findings
 }
 
+fn checks_out_pr_head(step: &StepInfo) -> bool {
+    step.uses
+        .as_deref()
+        .is_some_and(|u| u.starts_with("actions/checkout"))
+        && step
+            .with
+            .get("ref")
+            .is_some_and(|r| PR_HEAD_REFS.iter().any(|head| r.contains(head)))
+}
+
+fn uses_secrets(value: &str) -> bool {
+    value.contains("secrets.")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::parser::dag::{JobNode, PipelineDag, StepInfo};
+    use std::collections::HashMap;
 
     #[test]
     fn test_detect_title_injection() {
@@ -75,6 +171,9 @@ mod tests {
             uses: None,
             run: Some("echo \"${{ github.event.issue.title }}\"".into()),
             estimated_duration_secs: None,
+            with: HashMap::new(),
+            working_directory: None,
+            env: HashMap::new(),
         });
         dag.add_job(job);
 
@@ -93,6 +192,9 @@ mod tests {
             uses: None,
             run: Some("echo ${{ github.sha }}".into()),
             estimated_duration_secs: None,
+            with: HashMap::new(),
+            working_directory: None,
+            env: HashMap::new(),
         });
         dag.add_job(job);
 
@@ -109,10 +211,60 @@ mod tests {
             uses: None,
             run: Some("echo \"${{ github.event.issue.title }}\"".into()),
             estimated_duration_secs: None,
+            with: HashMap::new(),
+            working_directory: None,
+            env: HashMap::new(),
         });
         dag.add_job(job);
 
         let findings = detect_injection(&dag);
         assert!(findings.is_empty());
     }
+
+    const PR_TARGET_WORKFLOW: &str = r#"
+on: TRIGGER
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+        with:
+          ref: ${{ github.event.pull_request.head.sha }}
+      - run: npm ci
+      - name: Publish preview
+        run: npm run preview
+        env:
+          DEPLOY_TOKEN: ${{ secrets.DEPLOY_TOKEN }}
+"#;
+
+    #[test]
+    fn test_pull_request_target_head_checkout_with_secrets() {
+        let yaml = PR_TARGET_WORKFLOW.replace("TRIGGER", "pull_request_target");
+        let dag =
+            crate::parser::github::GitHubActionsParser::parse(&yaml, "ci.yml".into()).unwrap();
+        let findings = detect_injection(&dag);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Critical);
+        assert_eq!(findings[0].affected_jobs, vec!["test"]);
+        assert!(findings[0].description.contains("Publish preview"));
+    }
+
+    #[test]
+    fn test_pull_request_head_checkout_is_safe() {
+        let yaml = PR_TARGET_WORKFLOW.replace("TRIGGER", "pull_request");
+        let dag =
+            crate::parser::github::GitHubActionsParser::parse(&yaml, "ci.yml".into()).unwrap();
+        assert!(detect_injection(&dag).is_empty());
+
+        // pull_request_target that keeps the base checkout is also fine
+        let yaml = PR_TARGET_WORKFLOW
+            .replace("TRIGGER", "pull_request_target")
+            .replace(
+                "ref: ${{ github.event.pull_request.head.sha }}",
+                "fetch-depth: 1",
+            );
+        let dag =
+            crate::parser::github::GitHubActionsParser::parse(&yaml, "ci.yml".into()).unwrap();
+        assert!(detect_injection(&dag).is_empty());
+    }
 }
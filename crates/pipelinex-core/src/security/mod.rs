@@ -14,4 +14,7 @@ pub fn scan(dag: &PipelineDag) -> Vec<Finding> {
     findings.extend(injection::detect_injection(dag));
     findings.extend(supply_chain::assess_supply_chain(dag));
     findings
+        .iter_mut()
+        .for_each(Finding::fill_documentation_url);
+    findings
 }
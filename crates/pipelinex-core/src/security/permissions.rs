@@ -1,7 +1,36 @@
 use crate::analyzer::report::{Finding, FindingCategory, Severity};
-use crate::parser::dag::PipelineDag;
+use crate::parser::dag::{JobNode, Permissions, PipelineDag};
 
-/// Audit workflow permissions for overly broad access.
+/// Actions that push commits, tags or releases and so need `contents: write`.
+const CONTENTS_WRITE_ACTIONS: &[&str] = &[
+    "create-release",
+    "upload-release-asset",
+    "action-gh-release",
+    "git-auto-commit",
+    "create-pull-request",
+    "release-please",
+    "changesets/action",
+    "github-push-action",
+    "actions-gh-pages",
+    "github-pages-deploy-action",
+    "goreleaser-action",
+];
+
+/// Commands that push commits, tags or releases.
+const CONTENTS_WRITE_COMMANDS: &[&str] = &[
+    "git push",
+    "gh release create",
+    "gh release upload",
+    "gh release edit",
+    "gh release delete",
+    "semantic-release",
+];
+
+/// Audit workflow permissions for missing or overly broad access.
+///
+/// Without a `permissions:` block the `GITHUB_TOKEN` gets the repository's
+/// default permissions, which are read/write for older repositories and
+/// organizations.
 pub fn audit_permissions(dag: &PipelineDag) -> Vec<Finding> {
     let mut findings = Vec::new();
 
@@ -10,97 +39,167 @@ pub fn audit_permissions(dag: &PipelineDag) -> Vec<Finding> {
         return findings;
     }
 
-    // Check for write-all or broad permissions in workflow-level env
-    // Since we don't parse permissions block directly yet, check steps for
-    // indicators of missing or overly broad permissions
-    let has_permissions_indicator = dag.graph.node_weights().any(|job| {
-        job.env
-            .keys()
-            .any(|k| k.to_lowercase().contains("permissions"))
-    });
+    let jobs: Vec<&JobNode> = dag.graph.node_weights().collect();
+
+    let undeclared: Vec<&JobNode> = jobs
+        .iter()
+        .copied()
+        .filter(|job| dag.effective_permissions(job).is_none())
+        .collect();
+    if !undeclared.is_empty() {
+        findings.extend(missing_permissions(&undeclared));
+    }
+
+    let write_all: Vec<String> = jobs
+        .iter()
+        .filter(|job| dag.effective_permissions(job) == Some(&Permissions::WriteAll))
+        .map(|job| job.id.clone())
+        .collect();
+    if !write_all.is_empty() {
+        findings.push(
+            Finding::builder(FindingCategory::Security, "`write-all` permissions granted")
+                .severity(Severity::High)
+                .description(format!(
+                    "Jobs {} run with `permissions: write-all`, giving the GITHUB_TOKEN write \
+                    access to every scope. Any compromised step or action can push code, \
+                    publish packages or change repository settings.",
+                    write_all.join(", ")
+                ))
+                .affected_jobs(write_all)
+                .recommendation(
+                    "Replace `write-all` with only the scopes the job needs:\n  permissions:\n    contents: read",
+                )
+                .confidence(0.9)
+                .build(),
+        );
+    }
+
+    let needless_write: Vec<String> = jobs
+        .iter()
+        .filter(|job| {
+            matches!(dag.effective_permissions(job), Some(perms @ Permissions::Scoped(_)) if perms.grants_write("contents"))
+                && !needs_contents_write(job)
+        })
+        .map(|job| job.id.clone())
+        .collect();
+    if !needless_write.is_empty() {
+        findings.push(
+            Finding::builder(
+                FindingCategory::Security,
+                "`contents: write` granted where not needed",
+            )
+            .severity(Severity::Medium)
+            .description(format!(
+                "Jobs {} can push to the repository but run no step that commits, tags \
+                or publishes a release.",
+                needless_write.join(", ")
+            ))
+            .affected_jobs(needless_write)
+            .recommendation("Downgrade to read access:\n  permissions:\n    contents: read")
+            .confidence(0.75)
+            .build(),
+        );
+    }
+
+    findings
+}
 
-    if !has_permissions_indicator {
-        // Check what actions are used to suggest minimal permissions
-        let mut needs_contents_write = false;
-        let mut needs_packages_write = false;
-        let mut needs_security_events_write = false;
-        let mut uses_third_party_with_token = false;
-
-        for node in dag.graph.node_weights() {
-            for step in &node.steps {
-                if let Some(uses) = &step.uses {
-                    if uses.contains("create-release")
-                        || uses.contains("upload-release-asset")
-                        || uses.contains("push")
-                    {
-                        needs_contents_write = true;
-                    }
-                    if uses.contains("docker/build-push-action")
-                        || uses.contains("publish-packages")
-                    {
-                        needs_packages_write = true;
-                    }
-                    if uses.contains("codeql-action/upload-sarif") {
-                        needs_security_events_write = true;
-                    }
-                    // Third-party actions that receive GITHUB_TOKEN
-                    if !uses.starts_with("actions/") && !uses.starts_with("github/") {
-                        uses_third_party_with_token = true;
-                    }
+/// Findings for jobs with neither a job- nor a workflow-level `permissions:` block.
+fn missing_permissions(jobs: &[&JobNode]) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let affected: Vec<String> = jobs.iter().map(|job| job.id.clone()).collect();
+
+    // Check what actions are used to suggest minimal permissions
+    let needs_contents_write = jobs.iter().any(|job| needs_contents_write(job));
+    let mut needs_packages_write = false;
+    let mut needs_security_events_write = false;
+    let mut uses_third_party_with_token = false;
+
+    for node in jobs {
+        for step in &node.steps {
+            if let Some(uses) = &step.uses {
+                if uses.contains("docker/build-push-action") || uses.contains("publish-packages") {
+                    needs_packages_write = true;
+                }
+                if uses.contains("codeql-action/upload-sarif") {
+                    needs_security_events_write = true;
+                }
+                // Third-party actions that receive GITHUB_TOKEN
+                if !uses.starts_with("actions/") && !uses.starts_with("github/") {
+                    uses_third_party_with_token = true;
                 }
             }
         }
+    }
 
-        let mut suggested_perms = vec!["contents: read".to_string()];
-        if needs_contents_write {
-            suggested_perms[0] = "contents: write".to_string();
-        }
-        if needs_packages_write {
-            suggested_perms.push("packages: write".to_string());
-        }
-        if needs_security_events_write {
-            suggested_perms.push("security-events: write".to_string());
-        }
+    let mut suggested_perms = vec!["contents: read".to_string()];
+    if needs_contents_write {
+        suggested_perms[0] = "contents: write".to_string();
+    }
+    if needs_packages_write {
+        suggested_perms.push("packages: write".to_string());
+    }
+    if needs_security_events_write {
+        suggested_perms.push("security-events: write".to_string());
+    }
 
+    findings.push(Finding {
+        severity: Severity::Medium,
+        category: FindingCategory::Security,
+        title: "Missing explicit permissions block".to_string(),
+        description: "Workflow does not declare a permissions block. Without explicit permissions, the GITHUB_TOKEN may have broader access than needed.".to_string(),
+        affected_jobs: affected.clone(),
+        recommendation: format!(
+            "Add a permissions block to your workflow:\n  permissions:\n    {}",
+            suggested_perms.join("\n    ")
+        ),
+        fix_command: None,
+        estimated_savings_secs: None,
+        confidence: 0.70,
+        auto_fixable: true,
+        documentation_url: None,
+        blame: None,
+    });
+
+    if uses_third_party_with_token {
         findings.push(Finding {
             severity: Severity::Medium,
-            category: FindingCategory::CustomPlugin,
-            title: "Missing explicit permissions block".to_string(),
-            description: "Workflow does not declare a permissions block. Without explicit permissions, the GITHUB_TOKEN may have broader access than needed.".to_string(),
-            affected_jobs: dag.job_ids(),
-            recommendation: format!(
-                "Add a permissions block to your workflow:\n  permissions:\n    {}",
-                suggested_perms.join("\n    ")
-            ),
+            category: FindingCategory::Security,
+            title: "GITHUB_TOKEN exposed to third-party actions".to_string(),
+            description: "Third-party actions have access to the GITHUB_TOKEN. Consider restricting token permissions to minimize risk.".to_string(),
+            affected_jobs: affected,
+            recommendation: "Pin third-party actions to full SHA commits and restrict permissions to the minimum required.".to_string(),
             fix_command: None,
             estimated_savings_secs: None,
-            confidence: 0.70,
-            auto_fixable: true,
+            confidence: 0.65,
+            auto_fixable: false,
+            documentation_url: None,
+            blame: None,
         });
-
-        if uses_third_party_with_token {
-            findings.push(Finding {
-                severity: Severity::Medium,
-                category: FindingCategory::CustomPlugin,
-                title: "GITHUB_TOKEN exposed to third-party actions".to_string(),
-                description: "Third-party actions have access to the GITHUB_TOKEN. Consider restricting token permissions to minimize risk.".to_string(),
-                affected_jobs: dag.job_ids(),
-                recommendation: "Pin third-party actions to full SHA commits and restrict permissions to the minimum required.".to_string(),
-                fix_command: None,
-                estimated_savings_secs: None,
-                confidence: 0.65,
-                auto_fixable: false,
-            });
-        }
     }
 
     findings
 }
 
+/// Whether any step of `job` pushes commits, tags or releases.
+fn needs_contents_write(job: &JobNode) -> bool {
+    job.steps.iter().any(|step| {
+        step.uses
+            .as_deref()
+            .is_some_and(|uses| CONTENTS_WRITE_ACTIONS.iter().any(|a| uses.contains(a)))
+            || step
+                .run
+                .as_deref()
+                .is_some_and(|run| CONTENTS_WRITE_COMMANDS.iter().any(|c| run.contains(c)))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::parser::dag::{JobNode, PipelineDag, StepInfo};
+    use crate::parser::dag::StepInfo;
+    use crate::parser::github::GitHubActionsParser;
+    use std::collections::HashMap;
 
     #[test]
     fn test_missing_permissions_detected() {
@@ -111,6 +210,9 @@ mod tests {
             uses: Some("actions/checkout@v4".into()),
             run: None,
             estimated_duration_secs: None,
+            with: HashMap::new(),
+            working_directory: None,
+            env: HashMap::new(),
         });
         dag.add_job(job);
 
@@ -119,6 +221,116 @@ mod tests {
         assert!(findings.iter().any(|f| f.title.contains("permissions")));
     }
 
+    #[test]
+    fn test_write_all_job_flagged() {
+        let yaml = r#"
+on: push
+permissions:
+  contents: write
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm test
+  release:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: softprops/action-gh-release@v2
+  deploy:
+    runs-on: ubuntu-latest
+    permissions: write-all
+    steps:
+      - run: ./deploy.sh
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let findings = audit_permissions(&dag);
+
+        assert!(!findings
+            .iter()
+            .any(|f| f.title == "Missing explicit permissions block"));
+        let write_all = findings
+            .iter()
+            .find(|f| f.title.contains("write-all"))
+            .unwrap();
+        assert_eq!(write_all.severity, Severity::High);
+        assert_eq!(write_all.affected_jobs, vec!["deploy"]);
+
+        // `release` publishes a release, so only `test` is over-privileged
+        let contents = findings
+            .iter()
+            .find(|f| f.title.contains("contents: write"))
+            .unwrap();
+        assert_eq!(contents.severity, Severity::Medium);
+        assert_eq!(contents.affected_jobs, vec!["test"]);
+    }
+
+    #[test]
+    fn test_unrecognised_permissions_count_as_declared() {
+        let yaml = r#"
+on: push
+permissions: ${{ inputs.token-permissions }}
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm ci
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        assert!(audit_permissions(&dag).is_empty());
+    }
+
+    #[test]
+    fn test_reading_releases_does_not_need_contents_write() {
+        let yaml = r#"
+on: push
+permissions:
+  contents: write
+jobs:
+  fetch:
+    runs-on: ubuntu-latest
+    steps:
+      - run: gh release download v1.2.0 --pattern '*.tar.gz'
+  publish:
+    runs-on: ubuntu-latest
+    steps:
+      - run: gh release upload v1.2.0 dist/app.tar.gz
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let findings = audit_permissions(&dag);
+
+        let contents = findings
+            .iter()
+            .find(|f| f.title.contains("contents: write"))
+            .unwrap();
+        assert_eq!(contents.affected_jobs, vec!["fetch"]);
+    }
+
+    #[test]
+    fn test_pages_and_goreleaser_actions_need_contents_write() {
+        let yaml = r#"
+on: push
+permissions:
+  contents: write
+jobs:
+  docs:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: peaceiris/actions-gh-pages@v4
+  site:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: JamesIves/github-pages-deploy-action@v4
+  release:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: goreleaser/goreleaser-action@v6
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        assert!(!audit_permissions(&dag)
+            .iter()
+            .any(|f| f.title.contains("contents: write")));
+    }
+
     #[test]
     fn test_non_github_skipped() {
         let dag = PipelineDag::new("ci".into(), "ci.yml".into(), "gitlab-ci".into());
@@ -68,7 +68,7 @@ pub fn detect_secrets(dag: &PipelineDag) -> Vec<Finding> {
                         let redacted = redact_value(value);
                         findings.push(Finding {
                             severity: pattern.severity,
-                            category: FindingCategory::CustomPlugin,
+                            category: FindingCategory::Security,
                             title: format!("Secret exposure: {}", pattern.description),
                             description: format!(
                                 "Job '{}' env var '{}' contains what appears to be a hardcoded secret ({}...)",
@@ -83,6 +83,8 @@ pub fn detect_secrets(dag: &PipelineDag) -> Vec<Finding> {
                             estimated_savings_secs: None,
                             confidence: 0.85,
                             auto_fixable: false,
+                            documentation_url: None,
+                            blame: None,
                         });
                     }
                 }
@@ -97,7 +99,7 @@ pub fn detect_secrets(dag: &PipelineDag) -> Vec<Finding> {
                         if re.is_match(run) {
                             findings.push(Finding {
                                 severity: pattern.severity,
-                                category: FindingCategory::CustomPlugin,
+                                category: FindingCategory::Security,
                                 title: format!("Secret exposure: {}", pattern.description),
                                 description: format!(
                                     "Job '{}', step '{}' contains a potential hardcoded secret [{}]",
@@ -109,6 +111,8 @@ pub fn detect_secrets(dag: &PipelineDag) -> Vec<Finding> {
                                 estimated_savings_secs: None,
                                 confidence: 0.80,
                                 auto_fixable: false,
+                                documentation_url: None,
+                                blame: None,
                             });
                         }
                     }
@@ -151,6 +155,9 @@ mod tests {
             uses: None,
             run: Some(run_cmd.into()),
             estimated_duration_secs: None,
+            with: HashMap::new(),
+            working_directory: None,
+            env: HashMap::new(),
         });
         dag.add_job(job);
         dag
@@ -1,6 +1,7 @@
 use crate::analyzer::report::{Finding, FindingCategory, Severity};
 use crate::parser::dag::PipelineDag;
 use regex::Regex;
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, PartialEq)]
 enum PinningRisk {
@@ -91,7 +92,7 @@ pub fn assess_supply_chain(dag: &PipelineDag) -> Vec<Finding> {
                     if uses.contains(risky_action) {
                         findings.push(Finding {
                             severity: Severity::Critical,
-                            category: FindingCategory::CustomPlugin,
+                            category: FindingCategory::Security,
                             title: format!("Known supply chain risk: {}", risky_action),
                             description: format!("Job '{}' uses '{}'. {}", node.id, uses, warning),
                             affected_jobs: vec![node.id.clone()],
@@ -103,15 +104,22 @@ pub fn assess_supply_chain(dag: &PipelineDag) -> Vec<Finding> {
                             estimated_savings_secs: None,
                             confidence: 0.95,
                             auto_fixable: false,
+                            documentation_url: None,
+                            blame: None,
                         });
                     }
                 }
 
+                // Flag secrets handed to third-party code
+                if !is_first_party {
+                    findings.extend(detect_secret_inputs(&node.id, &step.with, uses, &pinning));
+                }
+
                 // Flag non-SHA-pinned third-party actions
                 if !is_first_party && pinning != PinningRisk::Sha {
                     findings.push(Finding {
                         severity: pinning.severity(),
-                        category: FindingCategory::CustomPlugin,
+                        category: FindingCategory::Security,
                         title: format!(
                             "Third-party action {} is {}",
                             extract_action_name(uses),
@@ -130,15 +138,139 @@ pub fn assess_supply_chain(dag: &PipelineDag) -> Vec<Finding> {
                         estimated_savings_secs: None,
                         confidence: 0.90,
                         auto_fixable: false,
+                        documentation_url: None,
+                        blame: None,
                     });
                 }
             }
         }
+
+        if let Some(image) = &node.container_image {
+            findings.extend(check_container_image(&node.id, image));
+        }
     }
 
     findings
 }
 
+/// Flag a job container image whose tag can change underneath the pipeline.
+///
+/// Digest-pinned images and fully specified versions (`node:20.11.1`) pass;
+/// `latest`, a missing tag, or a floating major (`python:3`, `node:20-alpine`) are flagged.
+fn check_container_image(job_id: &str, image: &str) -> Option<Finding> {
+    if image.contains("@sha256:") || image.contains('$') {
+        return None;
+    }
+
+    let name_start = image.rfind('/').map(|i| i + 1).unwrap_or(0);
+    let (repository, tag) = match image[name_start..].rfind(':') {
+        Some(i) => (&image[..name_start + i], Some(&image[name_start + i + 1..])),
+        None => (image, None),
+    };
+
+    let floating_re = Regex::new(r"^v?\d+([-_].*)?$").unwrap();
+    let (severity, label) = match tag {
+        None => (
+            Severity::Medium,
+            "untagged (resolves to latest)".to_string(),
+        ),
+        Some("latest") => (Severity::Medium, "tagged latest".to_string()),
+        Some(t) if floating_re.is_match(t) => {
+            (Severity::Low, format!("on floating major tag '{}'", t))
+        }
+        Some(_) => return None,
+    };
+
+    Some(Finding {
+        severity,
+        category: FindingCategory::Security,
+        title: format!("Container image {} is {}", repository, label),
+        description: format!(
+            "Job '{}' runs in container '{}', which is {}. The image behind a mutable tag \
+            changes whenever it is republished, so builds are not reproducible and a \
+            compromised upstream image is picked up automatically.",
+            job_id, image, label
+        ),
+        affected_jobs: vec![job_id.to_string()],
+        recommendation: format!(
+            "Pin the image by digest: `{}@sha256:<digest>` (get it with `docker buildx \
+            imagetools inspect {}`), and let Dependabot or Renovate bump it.",
+            repository, image
+        ),
+        fix_command: None,
+        estimated_savings_secs: None,
+        confidence: 0.85,
+        auto_fixable: false,
+        documentation_url: None,
+        blame: None,
+    })
+}
+
+/// Detect `secrets.*` values passed as inputs to a third-party action.
+fn detect_secret_inputs(
+    job_id: &str,
+    with: &HashMap<String, String>,
+    uses: &str,
+    pinning: &PinningRisk,
+) -> Vec<Finding> {
+    let secret_re = Regex::new(r"secrets\.([A-Za-z0-9_]+)").unwrap();
+
+    let mut secrets: Vec<(String, String)> = with
+        .iter()
+        .filter_map(|(input, value)| {
+            secret_re
+                .captures(value)
+                .map(|c| (input.clone(), c[1].to_string()))
+        })
+        .collect();
+    secrets.sort();
+
+    secrets
+        .into_iter()
+        .map(|(input, secret)| {
+            let severity = if *pinning == PinningRisk::Sha {
+                Severity::Low
+            } else if secret == "GITHUB_TOKEN" {
+                Severity::Medium
+            } else {
+                Severity::High
+            };
+            Finding {
+                severity,
+                category: FindingCategory::Security,
+                title: format!(
+                    "Secret {} passed to third-party action {}",
+                    secret,
+                    extract_action_name(uses)
+                ),
+                description: format!(
+                    "Job '{}' passes `secrets.{}` as input '{}' to '{}' ({}). Third-party action \
+                    code runs with full access to this value and could exfiltrate it if the \
+                    action is compromised.",
+                    job_id,
+                    secret,
+                    input,
+                    uses,
+                    pinning.label()
+                ),
+                affected_jobs: vec![job_id.to_string()],
+                recommendation: format!(
+                    "Pin '{}' to a full commit SHA you have reviewed, scope the secret to the \
+                    minimum permissions required, or replace the action with a first-party \
+                    alternative.",
+                    extract_action_name(uses)
+                ),
+                fix_command: None,
+                estimated_savings_secs: None,
+                confidence: 0.85,
+                auto_fixable: false,
+                documentation_url: None,
+                blame: None,
+            }
+        })
+        .collect()
+}
+
 fn extract_action_name(uses: &str) -> &str {
     uses.split('@').next().unwrap_or(uses)
 }
@@ -175,6 +307,9 @@ mod tests {
             uses: Some("some-org/some-action@v1".into()),
             run: None,
             estimated_duration_secs: None,
+            with: HashMap::new(),
+            working_directory: None,
+            env: HashMap::new(),
         });
         dag.add_job(job);
 
@@ -192,6 +327,9 @@ mod tests {
             uses: Some("actions/checkout@v4".into()),
             run: None,
             estimated_duration_secs: None,
+            with: HashMap::new(),
+            working_directory: None,
+            env: HashMap::new(),
         });
         dag.add_job(job);
 
@@ -208,6 +346,9 @@ mod tests {
             uses: Some("tj-actions/changed-files@v35".into()),
             run: None,
             estimated_duration_secs: None,
+            with: HashMap::new(),
+            working_directory: None,
+            env: HashMap::new(),
         });
         dag.add_job(job);
 
@@ -216,4 +357,97 @@ mod tests {
             .iter()
             .any(|f| f.severity == Severity::Critical && f.title.contains("supply chain risk")));
     }
+
+    fn dag_with_secret_input(uses: &str) -> PipelineDag {
+        let mut dag = PipelineDag::new("ci".into(), "ci.yml".into(), "github-actions".into());
+        let mut job = JobNode::new("publish".into(), "Publish".into());
+        let mut with = HashMap::new();
+        with.insert("token".to_string(), "${{ secrets.NPM_TOKEN }}".to_string());
+        job.steps.push(StepInfo {
+            name: "Publish".into(),
+            uses: Some(uses.into()),
+            run: None,
+            estimated_duration_secs: None,
+            with,
+            working_directory: None,
+            env: HashMap::new(),
+        });
+        dag.add_job(job);
+        dag
+    }
+
+    #[test]
+    fn test_secret_passed_to_third_party_action_flagged() {
+        let findings = assess_supply_chain(&dag_with_secret_input("some-org/action@v1"));
+        let secret_finding = findings
+            .iter()
+            .find(|f| f.title.contains("Secret NPM_TOKEN"))
+            .expect("secret input should be flagged");
+        assert_eq!(secret_finding.severity, Severity::High);
+        assert!(secret_finding.description.contains("'token'"));
+    }
+
+    #[test]
+    fn test_secret_passed_to_first_party_action_not_flagged() {
+        let findings = assess_supply_chain(&dag_with_secret_input(
+            "actions/setup-node@a5ac7e51b41094c92402da3b24376905380afc29",
+        ));
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_secret_passed_to_sha_pinned_action_is_low() {
+        let findings = assess_supply_chain(&dag_with_secret_input(
+            "some-org/action@a5ac7e51b41094c92402da3b24376905380afc29",
+        ));
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Low);
+    }
+
+    #[test]
+    fn test_container_image_latest_flagged() {
+        let yaml = r#"
+on: push
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    container: node:latest
+    steps:
+      - run: npm test
+  lint:
+    runs-on: ubuntu-latest
+    container:
+      image: python:3
+    steps:
+      - run: ruff check
+"#;
+        let dag =
+            crate::parser::github::GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        assert_eq!(
+            dag.get_job("test").unwrap().container_image.as_deref(),
+            Some("node:latest")
+        );
+
+        let findings = assess_supply_chain(&dag);
+        let latest = findings
+            .iter()
+            .find(|f| f.affected_jobs == vec!["test".to_string()])
+            .expect("node:latest should be flagged");
+        assert_eq!(latest.severity, Severity::Medium);
+        assert!(latest.recommendation.contains("node@sha256:"));
+        assert!(findings
+            .iter()
+            .any(|f| f.affected_jobs == vec!["lint".to_string()] && f.severity == Severity::Low));
+    }
+
+    #[test]
+    fn test_container_image_pinned_passes() {
+        assert!(check_container_image(
+            "test",
+            "node@sha256:4f1a3c5e9b0d8a7f6e5d4c3b2a1908f7e6d5c4b3a2918f7e6d5c4b3a2918f7e6"
+        )
+        .is_none());
+        assert!(check_container_image("test", "registry:5000/team/node:20.11.1").is_none());
+        assert!(check_container_image("test", "registry:5000/team/node").is_some());
+    }
 }
@@ -0,0 +1,163 @@
+use super::{percentile, simulate_internal, FailureModel, HistogramOptions};
+use crate::parser::dag::PipelineDag;
+use serde::{Deserialize, Serialize};
+
+/// Smallest batch in a convergence sweep.
+pub const MIN_CONVERGENCE_RUNS: usize = 100;
+/// Percentiles count as stable once their standard error is within this
+/// fraction of the estimate.
+pub const CONVERGENCE_TOLERANCE: f64 = 0.01;
+/// z-score of a 95% confidence interval.
+const Z_95: f64 = 1.96;
+
+/// Percentile estimates and their standard errors at one run count.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConvergenceStep {
+    pub runs: usize,
+    pub p50_secs: f64,
+    pub p90_secs: f64,
+    pub p99_secs: f64,
+    pub p50_std_error_secs: f64,
+    pub p90_std_error_secs: f64,
+    pub p99_std_error_secs: f64,
+}
+
+impl ConvergenceStep {
+    /// Whether every percentile's standard error is within `tolerance` of its estimate.
+    pub fn is_stable(&self, tolerance: f64) -> bool {
+        [
+            (self.p50_secs, self.p50_std_error_secs),
+            (self.p90_secs, self.p90_std_error_secs),
+            (self.p99_secs, self.p99_std_error_secs),
+        ]
+        .iter()
+        .all(|(estimate, error)| *error <= estimate.abs() * tolerance)
+    }
+}
+
+/// How the percentile estimates settle as the run count grows.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConvergenceReport {
+    pub steps: Vec<ConvergenceStep>,
+    /// Relative standard error a percentile must reach to count as stable.
+    pub tolerance: f64,
+    /// Fewest runs tried at which every percentile was stable, if any.
+    pub recommended_runs: Option<usize>,
+}
+
+/// Simulate `dag` at doubling run counts from [`MIN_CONVERGENCE_RUNS`] up to
+/// `max_runs`, all with the same seed, and report the standard error of the
+/// p50/p90/p99 estimates at each size.
+pub fn convergence(
+    dag: &PipelineDag,
+    max_runs: usize,
+    variance_factor: f64,
+    failures: &FailureModel,
+) -> ConvergenceReport {
+    let max_runs = max_runs.max(MIN_CONVERGENCE_RUNS);
+    let mut sizes = Vec::new();
+    let mut runs = MIN_CONVERGENCE_RUNS;
+    while runs < max_runs {
+        sizes.push(runs);
+        runs *= 2;
+    }
+    sizes.push(max_runs);
+
+    let histogram = HistogramOptions::default();
+    let steps: Vec<ConvergenceStep> = sizes
+        .into_iter()
+        .map(|runs| {
+            let (_, mut durations) = simulate_internal(
+                dag,
+                runs,
+                variance_factor,
+                &histogram,
+                failures,
+                None::<fn(usize, usize)>,
+            );
+            durations.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            ConvergenceStep {
+                runs,
+                p50_secs: percentile(&durations, 50.0),
+                p90_secs: percentile(&durations, 90.0),
+                p99_secs: percentile(&durations, 99.0),
+                p50_std_error_secs: quantile_std_error(&durations, 0.50),
+                p90_std_error_secs: quantile_std_error(&durations, 0.90),
+                p99_std_error_secs: quantile_std_error(&durations, 0.99),
+            }
+        })
+        .collect();
+
+    let recommended_runs = steps
+        .iter()
+        .find(|step| step.is_stable(CONVERGENCE_TOLERANCE))
+        .map(|step| step.runs);
+
+    ConvergenceReport {
+        steps,
+        tolerance: CONVERGENCE_TOLERANCE,
+        recommended_runs,
+    }
+}
+
+/// Distribution-free standard error of the `q` quantile of `sorted`.
+///
+/// The ranks `n·q ± 1.96·√(n·q·(1−q))` bound a 95% confidence interval for
+/// the quantile; the interval's width divided by 2 × 1.96 is its standard error.
+fn quantile_std_error(sorted: &[f64], q: f64) -> f64 {
+    let n = sorted.len();
+    if n < 2 {
+        return 0.0;
+    }
+    let center = n as f64 * q;
+    let spread = Z_95 * (n as f64 * q * (1.0 - q)).sqrt();
+    let rank = |r: f64| (r.round().max(0.0) as usize).min(n - 1);
+    let lower = sorted[rank(center - spread)];
+    let upper = sorted[rank(center + spread)];
+    (upper - lower) / (2.0 * Z_95)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::github::GitHubActionsParser;
+
+    #[test]
+    fn test_standard_error_shrinks_with_more_runs() {
+        let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm ci
+      - run: npm run build
+  test:
+    needs: build
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm test
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let report = convergence(&dag, 6400, 0.2, &FailureModel::default());
+
+        let sizes: Vec<usize> = report.steps.iter().map(|s| s.runs).collect();
+        assert_eq!(sizes, vec![100, 200, 400, 800, 1600, 3200, 6400]);
+
+        let first = &report.steps[0];
+        let last = report.steps.last().unwrap();
+        assert!(last.p50_std_error_secs < first.p50_std_error_secs / 4.0);
+        assert!(last.p90_std_error_secs < first.p90_std_error_secs / 4.0);
+        assert!(last.p99_std_error_secs < first.p99_std_error_secs);
+        // Quadrupling the runs roughly halves the error
+        for pair in report.steps.windows(3) {
+            assert!(pair[2].p50_std_error_secs < pair[0].p50_std_error_secs);
+            assert!(pair[2].p90_std_error_secs < pair[0].p90_std_error_secs);
+        }
+
+        let recommended = report.recommended_runs.unwrap();
+        let step = report.steps.iter().find(|s| s.runs == recommended).unwrap();
+        assert!(step.is_stable(report.tolerance));
+        assert!(!first.is_stable(report.tolerance));
+    }
+}
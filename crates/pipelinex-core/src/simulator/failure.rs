@@ -0,0 +1,72 @@
+use crate::parser::dag::JobNode;
+use crate::providers::github_api::PipelineStatistics;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How many times a pipeline is run before a simulated run is given up as failed.
+pub const MAX_PIPELINE_ATTEMPTS: usize = 3;
+
+/// Per-job failure probabilities for the simulator, and how failures are recovered.
+///
+/// A required job that fails is retried in place up to `retries` times, each
+/// retry costing a freshly sampled duration. If it still fails, the pipeline
+/// aborts and is rerun from the start. Jobs marked `continue-on-error` (GitLab
+/// `allow_failure`) never abort the pipeline.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FailureModel {
+    /// Probability in `[0, 1]` that one attempt of a job fails, by job id or name.
+    pub job_failure_rates: HashMap<String, f64>,
+    /// Failure probability of jobs not listed in `job_failure_rates`.
+    pub default_failure_rate: f64,
+    /// In-place retries of a failed job before the pipeline aborts.
+    pub retries: u32,
+}
+
+impl FailureModel {
+    /// Failure rates observed in `history --format json` statistics.
+    pub fn from_history(stats: &PipelineStatistics) -> Self {
+        let job_failure_rates = stats
+            .job_timings
+            .iter()
+            .filter_map(|job| {
+                let total = job.success_count + job.failure_count;
+                (total > 0).then(|| {
+                    (
+                        job.job_name.clone(),
+                        job.failure_count as f64 / total as f64,
+                    )
+                })
+            })
+            .collect();
+        Self {
+            job_failure_rates,
+            ..Self::default()
+        }
+    }
+
+    /// Whether any job can fail at all.
+    pub fn is_enabled(&self) -> bool {
+        self.default_failure_rate > 0.0 || self.job_failure_rates.values().any(|r| *r > 0.0)
+    }
+
+    /// Probability that one attempt of `job` fails.
+    pub fn failure_rate(&self, job: &JobNode) -> f64 {
+        self.job_failure_rates
+            .get(&job.id)
+            .or_else(|| self.job_failure_rates.get(&job.name))
+            .copied()
+            .unwrap_or(self.default_failure_rate)
+            .clamp(0.0, 1.0)
+    }
+}
+
+/// How failures and reruns affected a simulation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailureStats {
+    /// Percentage of runs where the pipeline aborted at least once and was rerun.
+    pub rerun_pct: f64,
+    /// Percentage of runs still failing after [`MAX_PIPELINE_ATTEMPTS`] attempts.
+    pub failed_pct: f64,
+    /// Mean wall-clock time per run added by job retries and pipeline reruns.
+    pub mean_overhead_secs: f64,
+}
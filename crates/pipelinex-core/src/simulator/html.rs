@@ -0,0 +1,212 @@
+use super::SimulationResult;
+use crate::analyzer::html_report::escape_html;
+use crate::analyzer::report::format_duration;
+use std::fmt::Write;
+
+/// Size of the histogram's SVG viewBox.
+const CHART_WIDTH: f64 = 800.0;
+const CHART_HEIGHT: f64 = 260.0;
+/// Room below the bars for the axis labels.
+const AXIS_HEIGHT: f64 = 30.0;
+
+/// Generate a self-contained HTML page for a Monte Carlo simulation: the
+/// percentile summary, the duration histogram as inline SVG, and the per-job
+/// critical-path table.
+pub fn generate_simulation_html(pipeline_name: &str, result: &SimulationResult) -> String {
+    let percentiles = [
+        ("Min", result.min_duration_secs),
+        ("p50", result.p50_duration_secs),
+        ("p75", result.p75_duration_secs),
+        ("p90", result.p90_duration_secs),
+        ("p99", result.p99_duration_secs),
+        ("Max", result.max_duration_secs),
+    ];
+    let mut cards = String::new();
+    for (label, secs) in percentiles {
+        let _ = write!(
+            cards,
+            r#"<div class="stat-card" data-percentile="{label}" data-secs="{secs:.1}"><div class="stat-label">{label}</div><div class="stat-value">{value}</div></div>"#,
+            label = label,
+            secs = secs,
+            value = format_duration(secs),
+        );
+    }
+
+    let mut rows = String::new();
+    for job in &result.job_stats {
+        let _ = write!(
+            rows,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{:.0}%</td></tr>",
+            escape_html(&job.job_id),
+            format_duration(job.mean_duration_secs),
+            format_duration(job.p50_duration_secs),
+            format_duration(job.p90_duration_secs),
+            job.on_critical_path_pct
+        );
+    }
+
+    let histogram_json =
+        serde_json::to_string(&result.histogram).unwrap_or_else(|_| "[]".to_string());
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>PipelineX Simulation - {name}</title>
+    <style>
+        * {{ margin: 0; padding: 0; box-sizing: border-box; }}
+        body {{
+            font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, Oxygen, Ubuntu, sans-serif;
+            background: #ffffff;
+            color: #1e293b;
+            line-height: 1.6;
+            padding: 2rem;
+        }}
+        .container {{ max-width: 1200px; margin: 0 auto; }}
+        h1 {{ font-size: 2rem; margin-bottom: 0.25rem; }}
+        h2 {{ font-size: 1.25rem; margin: 2rem 0 1rem; }}
+        .subtitle {{ color: #64748b; margin-bottom: 2rem; }}
+        .stats-grid {{
+            display: grid;
+            grid-template-columns: repeat(auto-fit, minmax(140px, 1fr));
+            gap: 1rem;
+        }}
+        .stat-card {{
+            padding: 1rem;
+            border-radius: 0.75rem;
+            border: 1px solid #e2e8f0;
+            box-shadow: 0 1px 3px rgba(0,0,0,0.1);
+        }}
+        .stat-label {{ font-size: 0.875rem; color: #64748b; }}
+        .stat-value {{ font-size: 1.5rem; font-weight: 700; color: #3b82f6; }}
+        .chart {{ border: 1px solid #e2e8f0; border-radius: 0.75rem; padding: 1rem; }}
+        .chart rect {{ fill: #3b82f6; }}
+        .chart rect:hover {{ fill: #1d4ed8; }}
+        .chart text {{ font-size: 11px; fill: #64748b; }}
+        table {{ width: 100%; border-collapse: collapse; }}
+        th, td {{ text-align: left; padding: 0.5rem; border-bottom: 1px solid #e2e8f0; }}
+        th {{ color: #64748b; font-weight: 600; }}
+    </style>
+</head>
+<body>
+    <div class="container">
+        <h1>Simulation: {name}</h1>
+        <p class="subtitle">{runs} Monte Carlo runs · mean {mean} · std dev {std_dev}</p>
+
+        <h2>Duration Percentiles</h2>
+        <div class="stats-grid">{cards}</div>
+
+        <h2>Duration Histogram</h2>
+        <div class="chart">{chart}</div>
+
+        <h2>Jobs</h2>
+        <table>
+            <thead><tr><th>Job</th><th>Mean</th><th>p50</th><th>p90</th><th>On critical path</th></tr></thead>
+            <tbody>{rows}</tbody>
+        </table>
+    </div>
+    <script type="application/json" id="histogram-data">{histogram_json}</script>
+</body>
+</html>
+"#,
+        name = escape_html(pipeline_name),
+        runs = result.runs,
+        mean = format_duration(result.mean_duration_secs),
+        std_dev = format_duration(result.std_dev_secs),
+        cards = cards,
+        chart = histogram_svg(result),
+        rows = rows,
+        histogram_json = histogram_json.replace("</", "<\\/"),
+    )
+}
+
+/// The histogram as an inline SVG bar chart, one bar per bucket.
+fn histogram_svg(result: &SimulationResult) -> String {
+    let buckets = &result.histogram;
+    if buckets.is_empty() {
+        return "<p>No runs recorded.</p>".to_string();
+    }
+
+    let max_count = buckets.iter().map(|b| b.count).max().unwrap_or(0).max(1) as f64;
+    let plot_height = CHART_HEIGHT - AXIS_HEIGHT;
+    let slot = CHART_WIDTH / buckets.len() as f64;
+
+    let mut svg = format!(
+        r#"<svg viewBox="0 0 {} {}" width="100%" role="img" aria-label="Duration histogram">"#,
+        CHART_WIDTH, CHART_HEIGHT
+    );
+    for (i, bucket) in buckets.iter().enumerate() {
+        let height = bucket.count as f64 / max_count * plot_height;
+        let x = i as f64 * slot;
+        let _ = write!(
+            svg,
+            r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" data-count="{}"><title>{} – {}: {} runs</title></rect>"#,
+            x + 1.0,
+            plot_height - height,
+            (slot - 2.0).max(1.0),
+            height,
+            bucket.count,
+            format_duration(bucket.lower_bound_secs),
+            format_duration(bucket.upper_bound_secs),
+            bucket.count
+        );
+    }
+
+    let first = &buckets[0];
+    let last = &buckets[buckets.len() - 1];
+    let _ = write!(
+        svg,
+        r#"<text x="0" y="{y:.0}">{}</text><text x="{w:.0}" y="{y:.0}" text-anchor="end">{}</text></svg>"#,
+        format_duration(first.lower_bound_secs),
+        format_duration(last.upper_bound_secs),
+        y = CHART_HEIGHT - 8.0,
+        w = CHART_WIDTH,
+    );
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::github::GitHubActionsParser;
+    use crate::simulator;
+
+    #[test]
+    fn test_simulation_html_contains_histogram_and_percentiles() {
+        let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm ci
+      - run: npm run build
+  test:
+    runs-on: ubuntu-latest
+    needs: build
+    steps:
+      - run: npm test
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let result = simulator::simulate(&dag, 200, 0.2);
+        let html = generate_simulation_html("CI <main>", &result);
+
+        assert!(html.contains("Simulation: CI &lt;main&gt;"));
+        assert!(html.contains(&format!(
+            r#"data-percentile="p90" data-secs="{:.1}""#,
+            result.p90_duration_secs
+        )));
+        assert!(html.contains(&format_duration(result.p50_duration_secs)));
+
+        let bars = html.matches("<rect ").count();
+        assert_eq!(bars, result.histogram.len());
+        let busiest = result.histogram.iter().map(|b| b.count).max().unwrap();
+        assert!(html.contains(&format!(r#"data-count="{}""#, busiest)));
+        assert!(html.contains(r#"<script type="application/json" id="histogram-data">"#));
+
+        assert!(html.contains("<td>build</td>"));
+        assert!(html.contains("<td>test</td>"));
+    }
+}
@@ -1,3 +1,15 @@
+mod convergence;
+mod failure;
+mod html;
+mod shift;
+
+pub use convergence::{
+    convergence, ConvergenceReport, ConvergenceStep, CONVERGENCE_TOLERANCE, MIN_CONVERGENCE_RUNS,
+};
+pub use failure::{FailureModel, FailureStats, MAX_PIPELINE_ATTEMPTS};
+pub use html::generate_simulation_html;
+pub use shift::{compare_results, DistributionShift};
+
 use crate::parser::dag::PipelineDag;
 use petgraph::graph::NodeIndex;
 use petgraph::Direction;
@@ -20,6 +32,9 @@ pub struct SimulationResult {
     pub job_stats: Vec<JobSimStats>,
     /// Distribution histogram buckets (for visualization)
     pub histogram: Vec<HistogramBucket>,
+    /// Retry and rerun overhead, when job failures were modeled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failures: Option<FailureStats>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,6 +54,26 @@ pub struct HistogramBucket {
     pub bar: String,
 }
 
+/// How the distribution histogram is bucketed and drawn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HistogramOptions {
+    pub buckets: usize,
+    /// Width in characters of the longest bar.
+    pub bar_width: usize,
+    /// Draw bars with Unicode eighth-blocks instead of `#`.
+    pub unicode: bool,
+}
+
+impl Default for HistogramOptions {
+    fn default() -> Self {
+        Self {
+            buckets: 20,
+            bar_width: 40,
+            unicode: false,
+        }
+    }
+}
+
 /// Simple pseudo-random number generator (xorshift64) — no external dependency needed.
 struct Rng {
     state: u64,
@@ -78,7 +113,15 @@ impl Rng {
 /// estimated duration (with configurable variance), then computes the total
 /// pipeline time by finding the critical path through the sampled DAG.
 pub fn simulate(dag: &PipelineDag, num_runs: usize, variance_factor: f64) -> SimulationResult {
-    simulate_internal(dag, num_runs, variance_factor, None::<fn(usize, usize)>)
+    simulate_internal(
+        dag,
+        num_runs,
+        variance_factor,
+        &HistogramOptions::default(),
+        &FailureModel::default(),
+        None::<fn(usize, usize)>,
+    )
+    .0
 }
 
 /// Run a Monte Carlo simulation and report progress at regular intervals.
@@ -91,15 +134,147 @@ pub fn simulate_with_progress<F>(
 where
     F: FnMut(usize, usize),
 {
-    simulate_internal(dag, num_runs, variance_factor, Some(on_progress))
+    simulate_internal(
+        dag,
+        num_runs,
+        variance_factor,
+        &HistogramOptions::default(),
+        &FailureModel::default(),
+        Some(on_progress),
+    )
+    .0
+}
+
+/// Run a Monte Carlo simulation with custom histogram rendering and optional progress.
+pub fn simulate_with_histogram<F>(
+    dag: &PipelineDag,
+    num_runs: usize,
+    variance_factor: f64,
+    histogram: &HistogramOptions,
+    on_progress: Option<F>,
+) -> SimulationResult
+where
+    F: FnMut(usize, usize),
+{
+    simulate_internal(
+        dag,
+        num_runs,
+        variance_factor,
+        histogram,
+        &FailureModel::default(),
+        on_progress,
+    )
+    .0
+}
+
+/// Run a Monte Carlo simulation in which jobs fail with the probabilities of
+/// `failures`, so the distribution includes retry and rerun overhead.
+pub fn simulate_with_failures<F>(
+    dag: &PipelineDag,
+    num_runs: usize,
+    variance_factor: f64,
+    histogram: &HistogramOptions,
+    failures: &FailureModel,
+    on_progress: Option<F>,
+) -> SimulationResult
+where
+    F: FnMut(usize, usize),
+{
+    simulate_internal(
+        dag,
+        num_runs,
+        variance_factor,
+        histogram,
+        failures,
+        on_progress,
+    )
+    .0
+}
+
+/// Monte Carlo results for a pipeline before and after optimization.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationComparison {
+    pub original: SimulationResult,
+    pub optimized: SimulationResult,
+    /// Distribution of per-run savings (original minus optimized duration).
+    pub delta: DeltaStats,
+}
+
+/// Percentiles of the paired per-run savings. Positive values mean the optimized
+/// pipeline finished sooner.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeltaStats {
+    pub p50_secs: f64,
+    pub p90_secs: f64,
+    pub p99_secs: f64,
+    pub mean_secs: f64,
+    pub min_secs: f64,
+    pub max_secs: f64,
+}
+
+/// Simulate two versions of a pipeline with the same seed and variance.
+///
+/// Runs are paired by index, so when both DAGs have the same jobs each run
+/// samples identical noise and the deltas isolate the effect of the change.
+pub fn compare(
+    original: &PipelineDag,
+    optimized: &PipelineDag,
+    num_runs: usize,
+    variance_factor: f64,
+) -> SimulationComparison {
+    let histogram = HistogramOptions::default();
+    let (original_result, original_runs) = simulate_internal(
+        original,
+        num_runs,
+        variance_factor,
+        &histogram,
+        &FailureModel::default(),
+        None::<fn(usize, usize)>,
+    );
+    let (optimized_result, optimized_runs) = simulate_internal(
+        optimized,
+        num_runs,
+        variance_factor,
+        &histogram,
+        &FailureModel::default(),
+        None::<fn(usize, usize)>,
+    );
+
+    let mut deltas: Vec<f64> = original_runs
+        .iter()
+        .zip(&optimized_runs)
+        .map(|(before, after)| before - after)
+        .collect();
+    deltas.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let delta = DeltaStats {
+        p50_secs: percentile(&deltas, 50.0),
+        p90_secs: percentile(&deltas, 90.0),
+        p99_secs: percentile(&deltas, 99.0),
+        mean_secs: if deltas.is_empty() {
+            0.0
+        } else {
+            deltas.iter().sum::<f64>() / deltas.len() as f64
+        },
+        min_secs: deltas.first().copied().unwrap_or(0.0),
+        max_secs: deltas.last().copied().unwrap_or(0.0),
+    };
+
+    SimulationComparison {
+        original: original_result,
+        optimized: optimized_result,
+        delta,
+    }
 }
 
 fn simulate_internal<F>(
     dag: &PipelineDag,
     num_runs: usize,
     variance_factor: f64,
+    histogram_options: &HistogramOptions,
+    failures: &FailureModel,
     mut on_progress: Option<F>,
-) -> SimulationResult
+) -> (SimulationResult, Vec<f64>)
 where
     F: FnMut(usize, usize),
 {
@@ -117,54 +292,89 @@ where
 
     let topo = match petgraph::algo::toposort(&dag.graph, None) {
         Ok(t) => t,
-        Err(_) => return empty_result(num_runs),
+        Err(_) => return (empty_result(num_runs), Vec::new()),
     };
 
-    for run_idx in 0..num_runs {
-        // Sample durations for each job
-        let mut sampled: HashMap<NodeIndex, f64> = HashMap::new();
-        for idx in dag.graph.node_indices() {
-            let job = &dag.graph[idx];
-            let base = job.estimated_duration_secs;
-            let std_dev = base * variance_factor;
-            let duration = rng.next_normal(base, std_dev).max(base * 0.1); // Floor at 10% of base
-            sampled.insert(idx, duration);
-
-            job_durations.get_mut(&job.id).unwrap().push(duration);
-        }
+    let model_failures = failures.is_enabled();
+    let mut rerun_runs = 0usize;
+    let mut failed_runs = 0usize;
+    let mut total_overhead = 0.0;
 
-        // Compute critical path for this run
-        let mut finish_time: HashMap<NodeIndex, f64> = HashMap::new();
-        let mut predecessor: HashMap<NodeIndex, Option<NodeIndex>> = HashMap::new();
+    for run_idx in 0..num_runs {
+        // Time spent in attempts that aborted on a failed job
+        let mut aborted_secs = 0.0;
+        let mut attempt = 1;
+        let (sampled, first_try, finish_time, predecessor) = loop {
+            // Sample durations for each job, including in-place retries
+            let mut sampled: HashMap<NodeIndex, f64> = HashMap::new();
+            let mut first_try: HashMap<NodeIndex, f64> = HashMap::new();
+            let mut failed: Vec<NodeIndex> = Vec::new();
+            for idx in dag.graph.node_indices() {
+                let job = &dag.graph[idx];
+                let base = job.wall_clock_secs();
+                let std_dev = base * variance_factor;
+                let duration = rng.next_normal(base, std_dev).max(base * 0.1); // Floor at 10% of base
+                let mut total = duration;
+
+                if model_failures && !job.continue_on_error {
+                    let rate = failures.failure_rate(job);
+                    let mut retries = 0;
+                    while rate > 0.0 && rng.next_f64() < rate {
+                        if retries == failures.retries {
+                            failed.push(idx);
+                            break;
+                        }
+                        retries += 1;
+                        total += rng.next_normal(base, std_dev).max(base * 0.1);
+                    }
+                }
+                sampled.insert(idx, total);
+                first_try.insert(idx, duration);
+            }
 
-        for &node in &topo {
-            let deps: Vec<_> = dag
-                .graph
-                .neighbors_directed(node, Direction::Incoming)
-                .collect();
-            let start_time = deps
+            let (finish_time, predecessor) = schedule(dag, &topo, &sampled);
+            // The pipeline aborts when its first required job gives up
+            let abort_at = failed
                 .iter()
-                .map(|dep| finish_time.get(dep).copied().unwrap_or(0.0))
-                .fold(0.0f64, f64::max);
-
-            let duration = sampled[&node];
-            finish_time.insert(node, start_time + duration);
+                .map(|idx| finish_time[idx])
+                .fold(None, |min: Option<f64>, t| {
+                    Some(min.map_or(t, |m| m.min(t)))
+                });
+            match abort_at {
+                Some(at) if attempt < MAX_PIPELINE_ATTEMPTS => {
+                    aborted_secs += at;
+                    attempt += 1;
+                }
+                Some(at) => {
+                    failed_runs += 1;
+                    // Nothing after the abort runs
+                    let finish_time = finish_time
+                        .into_iter()
+                        .map(|(idx, t)| (idx, t.min(at)))
+                        .collect();
+                    break (sampled, first_try, finish_time, predecessor);
+                }
+                None => break (sampled, first_try, finish_time, predecessor),
+            }
+        };
 
-            let pred = deps
-                .iter()
-                .max_by(|a, b| {
-                    finish_time
-                        .get(a)
-                        .unwrap_or(&0.0)
-                        .partial_cmp(finish_time.get(b).unwrap_or(&0.0))
-                        .unwrap()
-                })
-                .copied();
-            predecessor.insert(node, pred);
+        for (idx, duration) in &sampled {
+            job_durations
+                .get_mut(&dag.graph[*idx].id)
+                .unwrap()
+                .push(*duration);
         }
 
-        let total = finish_time.values().fold(0.0f64, |a, &b| a.max(b));
+        let total = aborted_secs + finish_time.values().fold(0.0f64, |a, &b| a.max(b));
         run_durations.push(total);
+        if model_failures {
+            if attempt > 1 {
+                rerun_runs += 1;
+            }
+            let (clean_finish, _) = schedule(dag, &topo, &first_try);
+            let clean_total = clean_finish.values().fold(0.0f64, |a, &b| a.max(b));
+            total_overhead += (total - clean_total).max(0.0);
+        }
 
         // Track which jobs are on the critical path
         if let Some((&end_node, _)) = finish_time
@@ -192,6 +402,7 @@ where
     }
 
     // Compute statistics
+    let runs_in_order = run_durations.clone();
     run_durations.sort_by(|a, b| a.partial_cmp(b).unwrap());
 
     let mean = run_durations.iter().sum::<f64>() / num_runs as f64;
@@ -235,9 +446,9 @@ where
     });
 
     // Build histogram
-    let histogram = build_histogram(&run_durations, 20);
+    let histogram = build_histogram(&run_durations, histogram_options);
 
-    SimulationResult {
+    let result = SimulationResult {
         runs: num_runs,
         p50_duration_secs: p50,
         p75_duration_secs: p75,
@@ -249,7 +460,55 @@ where
         std_dev_secs: std_dev,
         job_stats,
         histogram,
+        failures: model_failures.then(|| FailureStats {
+            rerun_pct: rerun_runs as f64 / num_runs as f64 * 100.0,
+            failed_pct: failed_runs as f64 / num_runs as f64 * 100.0,
+            mean_overhead_secs: total_overhead / num_runs as f64,
+        }),
+    };
+    (result, runs_in_order)
+}
+
+/// Finish time of every job given its duration, and the dependency that
+/// finished last before it started (its critical-path predecessor).
+#[allow(clippy::type_complexity)]
+fn schedule(
+    dag: &PipelineDag,
+    topo: &[NodeIndex],
+    durations: &HashMap<NodeIndex, f64>,
+) -> (
+    HashMap<NodeIndex, f64>,
+    HashMap<NodeIndex, Option<NodeIndex>>,
+) {
+    let mut finish_time: HashMap<NodeIndex, f64> = HashMap::new();
+    let mut predecessor: HashMap<NodeIndex, Option<NodeIndex>> = HashMap::new();
+
+    for &node in topo {
+        let deps: Vec<_> = dag
+            .graph
+            .neighbors_directed(node, Direction::Incoming)
+            .collect();
+        let start_time = deps
+            .iter()
+            .map(|dep| finish_time.get(dep).copied().unwrap_or(0.0))
+            .fold(0.0f64, f64::max);
+
+        finish_time.insert(node, start_time + durations[&node]);
+
+        let pred = deps
+            .iter()
+            .max_by(|a, b| {
+                finish_time
+                    .get(a)
+                    .unwrap_or(&0.0)
+                    .partial_cmp(finish_time.get(b).unwrap_or(&0.0))
+                    .unwrap()
+            })
+            .copied();
+        predecessor.insert(node, pred);
     }
+
+    (finish_time, predecessor)
 }
 
 fn percentile(sorted: &[f64], pct: f64) -> f64 {
@@ -260,11 +519,12 @@ fn percentile(sorted: &[f64], pct: f64) -> f64 {
     sorted[idx.min(sorted.len() - 1)]
 }
 
-fn build_histogram(sorted: &[f64], num_buckets: usize) -> Vec<HistogramBucket> {
+fn build_histogram(sorted: &[f64], options: &HistogramOptions) -> Vec<HistogramBucket> {
     if sorted.is_empty() {
         return Vec::new();
     }
 
+    let num_buckets = options.buckets.max(1);
     let min = sorted.first().unwrap();
     let max = sorted.last().unwrap();
     let range = max - min;
@@ -273,7 +533,7 @@ fn build_histogram(sorted: &[f64], num_buckets: usize) -> Vec<HistogramBucket> {
             lower_bound_secs: *min,
             upper_bound_secs: *max,
             count: sorted.len(),
-            bar: "#".repeat(40),
+            bar: render_bar(1.0, options),
         }];
     }
 
@@ -303,17 +563,53 @@ fn build_histogram(sorted: &[f64], num_buckets: usize) -> Vec<HistogramBucket> {
 
     let max_count = buckets.iter().map(|b| b.count).max().unwrap_or(1);
     for bucket in &mut buckets {
-        let bar_len = if max_count > 0 {
-            (bucket.count as f64 / max_count as f64 * 40.0).round() as usize
+        let fraction = if max_count > 0 {
+            bucket.count as f64 / max_count as f64
         } else {
-            0
+            0.0
         };
-        bucket.bar = "#".repeat(bar_len);
+        bucket.bar = render_bar(fraction, options);
     }
 
     buckets
 }
 
+/// Left-aligned eighth blocks, from one eighth to seven eighths of a cell.
+const PARTIAL_BLOCKS: [char; 7] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+/// Draw a bar `fraction` of `bar_width` long. Unicode mode resolves eighths of a cell.
+fn render_bar(fraction: f64, options: &HistogramOptions) -> String {
+    if !options.unicode {
+        let len = (fraction * options.bar_width as f64).round() as usize;
+        return "#".repeat(len);
+    }
+
+    let eighths = (fraction * options.bar_width as f64 * 8.0).round() as usize;
+    let mut bar = "█".repeat(eighths / 8);
+    let remainder = eighths % 8;
+    if remainder > 0 {
+        bar.push(PARTIAL_BLOCKS[remainder - 1]);
+    }
+    bar
+}
+
+/// One-line summary of the histogram shape using vertical blocks (`▁▂▃▄▅▆▇█`).
+pub fn sparkline(histogram: &[HistogramBucket]) -> String {
+    const LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let max_count = histogram.iter().map(|b| b.count).max().unwrap_or(0);
+    histogram
+        .iter()
+        .map(|b| {
+            if b.count == 0 || max_count == 0 {
+                ' '
+            } else {
+                let level = (b.count * (LEVELS.len() - 1)).div_ceil(max_count);
+                LEVELS[level.min(LEVELS.len() - 1)]
+            }
+        })
+        .collect()
+}
+
 fn empty_result(runs: usize) -> SimulationResult {
     SimulationResult {
         runs,
@@ -327,6 +623,7 @@ fn empty_result(runs: usize) -> SimulationResult {
         std_dev_secs: 0.0,
         job_stats: Vec::new(),
         histogram: Vec::new(),
+        failures: None,
     }
 }
 
@@ -411,4 +708,163 @@ jobs:
 
         assert!(parallel_result.mean_duration_secs < serial_result.mean_duration_secs);
     }
+
+    #[test]
+    fn test_compare_optimized_is_not_slower() {
+        let yaml = r#"
+name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - run: npm ci
+      - run: npm run build
+  test:
+    needs: build
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - run: npm ci
+      - run: npm test
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let optimized = crate::optimizer::Optimizer::optimize_dag(yaml, &dag).unwrap();
+        let comparison = compare(&dag, &optimized, 500, 0.15);
+
+        assert_eq!(comparison.original.runs, 500);
+        assert!(
+            comparison.optimized.p50_duration_secs < comparison.original.p50_duration_secs,
+            "optimized p50 {} should beat original {}",
+            comparison.optimized.p50_duration_secs,
+            comparison.original.p50_duration_secs
+        );
+        assert!(comparison.delta.p50_secs > 0.0);
+        assert!(comparison.delta.min_secs <= comparison.delta.max_secs);
+    }
+
+    fn sample_dag() -> PipelineDag {
+        let yaml = r#"
+name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm run build
+  test:
+    needs: build
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm test
+"#;
+        GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_failing_required_job_widens_distribution() {
+        let yaml = r#"
+stages: [build, test]
+build:
+  stage: build
+  script: [make]
+test:
+  stage: test
+  script: [make test]
+lint:
+  stage: test
+  allow_failure: true
+  script: [make lint]
+"#;
+        let dag = crate::parser::gitlab::GitLabCIParser::parse(yaml, ".gitlab-ci.yml".to_string())
+            .unwrap();
+        assert!(dag.get_job("lint").unwrap().continue_on_error);
+        let histogram = HistogramOptions::default();
+        let baseline =
+            simulate_with_histogram(&dag, 500, 0.1, &histogram, None::<fn(usize, usize)>);
+        assert!(baseline.failures.is_none());
+
+        let failures = FailureModel {
+            job_failure_rates: HashMap::from([("test".to_string(), 0.5)]),
+            retries: 1,
+            ..FailureModel::default()
+        };
+        let flaky = simulate_with_failures(
+            &dag,
+            500,
+            0.1,
+            &histogram,
+            &failures,
+            None::<fn(usize, usize)>,
+        );
+        let stats = flaky.failures.as_ref().unwrap();
+        assert!(stats.rerun_pct > 0.0);
+        assert!(stats.mean_overhead_secs > 0.0);
+        assert!(flaky.std_dev_secs > baseline.std_dev_secs * 2.0);
+        assert!(flaky.p90_duration_secs > baseline.p90_duration_secs);
+        assert!(flaky.mean_duration_secs > baseline.mean_duration_secs);
+
+        // allow_failure jobs never abort the pipeline
+        let tolerated = FailureModel {
+            job_failure_rates: HashMap::from([("lint".to_string(), 0.9)]),
+            ..FailureModel::default()
+        };
+        let result = simulate_with_failures(
+            &dag,
+            500,
+            0.1,
+            &histogram,
+            &tolerated,
+            None::<fn(usize, usize)>,
+        );
+        let stats = result.failures.unwrap();
+        assert_eq!(stats.rerun_pct, 0.0);
+        assert_eq!(stats.mean_overhead_secs, 0.0);
+    }
+
+    #[test]
+    fn test_histogram_bucket_count_and_width() {
+        let options = HistogramOptions {
+            buckets: 7,
+            bar_width: 25,
+            unicode: false,
+        };
+        let result =
+            simulate_with_histogram(&sample_dag(), 500, 0.2, &options, None::<fn(usize, usize)>);
+        assert_eq!(result.histogram.len(), 7);
+        let longest = result.histogram.iter().map(|b| b.bar.len()).max().unwrap();
+        assert_eq!(longest, 25);
+        assert!(result
+            .histogram
+            .iter()
+            .all(|b| b.bar.chars().all(|c| c == '#')));
+    }
+
+    #[test]
+    fn test_histogram_unicode_uses_block_chars() {
+        let options = HistogramOptions {
+            buckets: 10,
+            bar_width: 12,
+            unicode: true,
+        };
+        let result =
+            simulate_with_histogram(&sample_dag(), 500, 0.2, &options, None::<fn(usize, usize)>);
+        assert_eq!(result.histogram.len(), 10);
+        assert!(result
+            .histogram
+            .iter()
+            .any(|b| b.bar.chars().count() == 12 && b.bar.chars().all(|c| c == '█')));
+        assert!(result
+            .histogram
+            .iter()
+            .flat_map(|b| b.bar.chars())
+            .all(|c| c == '█' || PARTIAL_BLOCKS.contains(&c)));
+
+        assert_eq!(render_bar(0.5 / 12.0, &options), "▌");
+
+        let spark = sparkline(&result.histogram);
+        assert_eq!(spark.chars().count(), 10);
+        assert!(spark.contains('█'));
+    }
 }
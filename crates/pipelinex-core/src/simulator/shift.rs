@@ -0,0 +1,131 @@
+//! Comparing two saved simulation results (`simulate --compare-file`).
+//!
+//! Saved results keep percentiles and a histogram rather than raw samples,
+//! so the distribution tests work on the piecewise-linear CDF of each
+//! histogram.
+
+use super::{HistogramBucket, SimulationResult};
+use serde::{Deserialize, Serialize};
+
+/// Kolmogorov-Smirnov coefficient for a 5% significance level.
+const KS_ALPHA_05: f64 = 1.358;
+
+/// How the timing distribution moved from `previous` to `current`.
+///
+/// Deltas are `current - previous`, so negative values mean the pipeline got faster.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DistributionShift {
+    pub p50_delta_secs: f64,
+    pub p90_delta_secs: f64,
+    pub p99_delta_secs: f64,
+    pub mean_delta_secs: f64,
+    /// Largest gap between the two CDFs (two-sample KS statistic, 0.0-1.0).
+    pub ks_statistic: f64,
+    /// KS statistic above which the shift is significant at the 5% level.
+    pub ks_critical_value: f64,
+    /// Shared probability mass of the two distributions (1.0 = identical).
+    pub overlap: f64,
+    /// The shift exceeds what sampling noise would explain.
+    pub significant: bool,
+}
+
+/// Compare two simulation results, e.g. before and after a pipeline edit.
+pub fn compare_results(
+    previous: &SimulationResult,
+    current: &SimulationResult,
+) -> DistributionShift {
+    let mut grid: Vec<f64> = previous
+        .histogram
+        .iter()
+        .chain(&current.histogram)
+        .flat_map(|b| [b.lower_bound_secs, b.upper_bound_secs])
+        .collect();
+    grid.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    grid.dedup();
+
+    let mut ks_statistic: f64 = 0.0;
+    let mut overlap = 0.0;
+    let (mut last_prev, mut last_curr) = (0.0, 0.0);
+    for &x in &grid {
+        let (f_prev, f_curr) = (cdf(&previous.histogram, x), cdf(&current.histogram, x));
+        ks_statistic = ks_statistic.max((f_prev - f_curr).abs());
+        overlap += f64::min(f_prev - last_prev, f_curr - last_curr);
+        (last_prev, last_curr) = (f_prev, f_curr);
+    }
+
+    let (n, m) = (previous.runs as f64, current.runs as f64);
+    let ks_critical_value = if n > 0.0 && m > 0.0 {
+        KS_ALPHA_05 * ((n + m) / (n * m)).sqrt()
+    } else {
+        1.0
+    };
+
+    DistributionShift {
+        p50_delta_secs: current.p50_duration_secs - previous.p50_duration_secs,
+        p90_delta_secs: current.p90_duration_secs - previous.p90_duration_secs,
+        p99_delta_secs: current.p99_duration_secs - previous.p99_duration_secs,
+        mean_delta_secs: current.mean_duration_secs - previous.mean_duration_secs,
+        ks_statistic,
+        ks_critical_value,
+        overlap: overlap.clamp(0.0, 1.0),
+        significant: ks_statistic > ks_critical_value,
+    }
+}
+
+/// Fraction of runs at or below `x`, interpolating linearly inside buckets.
+fn cdf(histogram: &[HistogramBucket], x: f64) -> f64 {
+    let total: usize = histogram.iter().map(|b| b.count).sum();
+    if total == 0 {
+        return 0.0;
+    }
+
+    let mut below = 0.0;
+    for bucket in histogram {
+        if x >= bucket.upper_bound_secs {
+            below += bucket.count as f64;
+        } else if x > bucket.lower_bound_secs {
+            let width = bucket.upper_bound_secs - bucket.lower_bound_secs;
+            below += bucket.count as f64 * (x - bucket.lower_bound_secs) / width;
+            break;
+        } else {
+            break;
+        }
+    }
+    below / total as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulator::simulate;
+    use crate::synthetic::layered_dag;
+
+    #[test]
+    fn test_faster_pipeline_is_a_significant_shift() {
+        // Same jobs, but one layer deep instead of four: clearly faster.
+        let before = simulate(&layered_dag(2, 4), 1000, 0.15);
+        let after = simulate(&layered_dag(8, 1), 1000, 0.15);
+
+        let shift = compare_results(&before, &after);
+        assert!(shift.p50_delta_secs < 0.0);
+        assert!(shift.p90_delta_secs < 0.0);
+        assert!(shift.p99_delta_secs < 0.0);
+        assert!(
+            (shift.mean_delta_secs - (after.mean_duration_secs - before.mean_duration_secs)).abs()
+                < 1e-9
+        );
+        assert!(shift.significant);
+        assert!(shift.ks_statistic > 0.9);
+        assert!(shift.overlap < 0.1);
+    }
+
+    #[test]
+    fn test_identical_results_are_noise() {
+        let result = simulate(&layered_dag(3, 3), 500, 0.2);
+        let shift = compare_results(&result, &result);
+        assert_eq!(shift.p50_delta_secs, 0.0);
+        assert!(shift.ks_statistic < 1e-9);
+        assert!((shift.overlap - 1.0).abs() < 1e-9);
+        assert!(!shift.significant);
+    }
+}
@@ -0,0 +1,171 @@
+//! One-paragraph executive summaries (`pipelinex summary`).
+//!
+//! A fixed template over the analysis report, badge score and recoverable
+//! cost, meant for status emails rather than for acting on findings.
+
+use crate::analyzer::report::{AnalysisReport, Severity};
+use crate::badge::{generate_badge, SavingsParams};
+use serde::Serialize;
+
+/// The figures behind a summary paragraph, for `--format json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExecutiveSummary {
+    pub pipeline_name: String,
+    pub source_file: String,
+    pub duration_secs: f64,
+    pub finding_count: usize,
+    pub critical_count: usize,
+    pub high_count: usize,
+    /// Share of the current duration the findings could save, 0-100.
+    pub optimizable_pct: f64,
+    pub health_grade: String,
+    pub health_score: u8,
+    /// Recoverable compute and developer waiting cost, as for `badge --savings`.
+    pub monthly_recoverable_usd: f64,
+    pub paragraph: String,
+}
+
+/// Summarize `report` in one paragraph, pricing waste with `params`.
+pub fn executive_summary(report: &AnalysisReport, params: &SavingsParams) -> ExecutiveSummary {
+    let badge = generate_badge(report, Some(params));
+    let monthly_recoverable_usd = badge
+        .savings
+        .as_ref()
+        .map(|s| s.monthly_savings_usd)
+        .unwrap_or(0.0);
+    let critical_count = report.critical_count();
+    let high_count = report.high_count();
+    let finding_count = report.findings.len();
+
+    let severe = match (critical_count, high_count) {
+        (0, 0) => String::new(),
+        (0, high) => format!(" ({} high)", high),
+        (critical, 0) => format!(" ({} critical)", critical),
+        (critical, high) => format!(" ({} critical, {} high)", critical, high),
+    };
+    let mut paragraph = format!(
+        "{} takes ~{} per run across {} job{}, with {} finding{}{}. \
+        ~{:.0}% of that time is optimizable, health grade {} ({}/100), \
+        est. ${:.0}/mo recoverable.",
+        report.pipeline_name,
+        approximate_duration(report.total_estimated_duration_secs),
+        report.job_count,
+        plural(report.job_count),
+        finding_count,
+        plural(finding_count),
+        severe,
+        badge.optimization_pct,
+        badge.grade,
+        badge.score,
+        monthly_recoverable_usd,
+    );
+    let top = report
+        .findings
+        .iter()
+        .filter(|f| f.severity.priority() >= Severity::High.priority())
+        .max_by(|a, b| {
+            a.estimated_savings_secs
+                .unwrap_or(0.0)
+                .total_cmp(&b.estimated_savings_secs.unwrap_or(0.0))
+        });
+    if let Some(top) = top {
+        paragraph.push_str(&format!(" Top issue: {}.", top.title));
+    }
+
+    ExecutiveSummary {
+        pipeline_name: report.pipeline_name.clone(),
+        source_file: report.source_file.clone(),
+        duration_secs: report.total_estimated_duration_secs,
+        finding_count,
+        critical_count,
+        high_count,
+        optimizable_pct: badge.optimization_pct,
+        health_grade: badge.grade,
+        health_score: badge.score,
+        monthly_recoverable_usd,
+        paragraph,
+    }
+}
+
+/// Rounded duration for prose: `45s`, `12m`, `1h`, `1h 5m`.
+///
+/// The value is rounded to the unit first, so 3599s reads `1h` rather than
+/// `60m`.
+fn approximate_duration(secs: f64) -> String {
+    let secs = secs.max(0.0).round() as u64;
+    if secs < 60 {
+        return format!("{}s", secs);
+    }
+    let minutes = (secs + 30) / 60;
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{}m", m),
+        (h, 0) => format!("{}h", h),
+        (h, m) => format!("{}h {}m", h, m),
+    }
+}
+
+fn plural(count: usize) -> &'static str {
+    if count == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::github::GitHubActionsParser;
+
+    #[test]
+    fn test_summary_paragraph_has_duration_findings_and_cost() {
+        let yaml = r#"
+name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - run: npm ci
+      - run: npm run build
+  test:
+    needs: build
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - run: npm ci
+      - run: npm test
+"#;
+        let dag = GitHubActionsParser::parse(yaml, "ci.yml".to_string()).unwrap();
+        let report = crate::analyzer::analyze(&dag);
+        let params = SavingsParams {
+            runs_per_month: 500,
+            hourly_rate: 150.0,
+            team_size: 10,
+            runner_type: "ubuntu-latest".to_string(),
+        };
+        let summary = executive_summary(&report, &params);
+
+        assert!(summary.finding_count > 0);
+        assert!(summary.monthly_recoverable_usd > 0.0);
+        let p = &summary.paragraph;
+        assert!(p.starts_with("CI takes ~"), "{}", p);
+        assert!(p.contains(&format!(
+            "~{} per run",
+            approximate_duration(report.total_estimated_duration_secs)
+        )));
+        assert!(p.contains(&format!("with {} findings", summary.finding_count)));
+        assert!(p.contains(&format!(
+            "est. ${:.0}/mo recoverable",
+            summary.monthly_recoverable_usd
+        )));
+        assert!(!p.contains('\n'));
+
+        assert_eq!(approximate_duration(45.0), "45s");
+        assert_eq!(approximate_duration(725.0), "12m");
+        assert_eq!(approximate_duration(3900.0), "1h 5m");
+        assert_eq!(approximate_duration(3599.0), "1h");
+        assert_eq!(approximate_duration(7170.0), "2h");
+    }
+}
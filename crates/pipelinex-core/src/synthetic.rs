@@ -0,0 +1,107 @@
+//! Synthetic pipelines of parametric size, used by the benchmarks and stress tests.
+
+use crate::parser::dag::{JobNode, PipelineDag, StepInfo};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Generate a GitHub Actions workflow with `jobs` jobs arranged in layers of `width`.
+///
+/// Every job in a layer depends on every job of the previous layer, so the YAML
+/// exercises `needs:` resolution as well as step parsing.
+pub fn github_workflow_yaml(jobs: usize, width: usize) -> String {
+    let width = width.max(1);
+    let mut yaml = String::from(
+        "name: Synthetic\non:\n  push:\n    branches: [main]\n  pull_request:\njobs:\n",
+    );
+
+    for i in 0..jobs {
+        let layer = i / width;
+        let _ = writeln!(yaml, "  job-{}:", i);
+        let _ = writeln!(yaml, "    runs-on: ubuntu-latest");
+        if layer > 0 {
+            let needs: Vec<String> = ((layer - 1) * width..layer * width)
+                .map(|j| format!("job-{}", j))
+                .collect();
+            let _ = writeln!(yaml, "    needs: [{}]", needs.join(", "));
+        }
+        yaml.push_str("    steps:\n");
+        yaml.push_str("      - uses: actions/checkout@v4\n");
+        yaml.push_str("      - uses: actions/setup-node@v4\n");
+        yaml.push_str("        with:\n          node-version: 20\n");
+        yaml.push_str("      - run: npm ci\n");
+        let _ = writeln!(yaml, "      - run: npm run build --workspace pkg-{}", i);
+        yaml.push_str("      - run: npm test\n");
+    }
+
+    yaml
+}
+
+/// Build a layered DAG directly: `depth` layers of `width` jobs each, fully
+/// connected between adjacent layers.
+pub fn layered_dag(width: usize, depth: usize) -> PipelineDag {
+    let mut dag = PipelineDag::new(
+        "synthetic".to_string(),
+        "synthetic.yml".to_string(),
+        "github-actions".to_string(),
+    );
+
+    for layer in 0..depth {
+        for col in 0..width {
+            let id = format!("job-{}-{}", layer, col);
+            let mut job = JobNode::new(id.clone(), id);
+            job.estimated_duration_secs = 30.0 + ((layer * 7 + col * 13) % 50) as f64 * 6.0;
+            job.steps = vec![StepInfo {
+                name: "build".to_string(),
+                uses: None,
+                run: Some("make build".to_string()),
+                estimated_duration_secs: Some(job.estimated_duration_secs),
+                with: HashMap::new(),
+                working_directory: None,
+                env: HashMap::new(),
+            }];
+            if layer > 0 {
+                job.needs = (0..width)
+                    .map(|c| format!("job-{}-{}", layer - 1, c))
+                    .collect();
+            }
+            dag.add_job(job);
+        }
+    }
+
+    for layer in 1..depth {
+        for col in 0..width {
+            for prev in 0..width {
+                let _ = dag.add_dependency(
+                    &format!("job-{}-{}", layer - 1, prev),
+                    &format!("job-{}-{}", layer, col),
+                );
+            }
+        }
+    }
+
+    dag
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::github::GitHubActionsParser;
+
+    #[test]
+    fn test_generated_workflow_parses() {
+        let yaml = github_workflow_yaml(100, 10);
+        let dag = GitHubActionsParser::parse(&yaml, "synthetic.yml".to_string()).unwrap();
+        assert_eq!(dag.job_count(), 100);
+        assert_eq!(dag.graph.edge_count(), 9 * 10 * 10);
+        assert_eq!(dag.max_parallelism(), 10);
+    }
+
+    #[test]
+    fn test_layered_dag_shape() {
+        let dag = layered_dag(50, 4);
+        assert_eq!(dag.job_count(), 200);
+        assert_eq!(dag.graph.edge_count(), 3 * 50 * 50);
+        assert_eq!(dag.root_jobs().len(), 50);
+        assert_eq!(dag.leaf_jobs().len(), 50);
+    }
+}
@@ -0,0 +1,309 @@
+//! Module dependency graph from source imports (`select-tests --deps`).
+//!
+//! Only imports that resolve to files inside the repository are kept:
+//! relative JS/TS specifiers, Python modules found under the root, and Go
+//! packages under the module path declared in the root `go.mod`.
+
+use regex::Regex;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::{Component, Path, PathBuf};
+use std::sync::OnceLock;
+
+const JS_EXTENSIONS: &[&str] = &["ts", "tsx", "js", "jsx", "mjs", "cjs"];
+
+/// Reverse import edges between repository files, with paths relative to the root.
+#[derive(Debug, Clone, Default)]
+pub struct ImportGraph {
+    /// file -> files that import it
+    dependents: HashMap<PathBuf, BTreeSet<PathBuf>>,
+}
+
+impl ImportGraph {
+    /// Scan `root` for JS/TS, Python and Go sources, skipping `exclude_dirs`.
+    pub fn build(root: &Path, exclude_dirs: &[String]) -> Self {
+        let mut files = Vec::new();
+        collect_sources(root, Path::new(""), exclude_dirs, &mut files);
+        let known: HashSet<PathBuf> = files.iter().cloned().collect();
+        let go_module = go_module_path(root);
+
+        let mut graph = Self::default();
+        for file in &files {
+            let Ok(content) = std::fs::read_to_string(root.join(file)) else {
+                continue;
+            };
+            let ext = file.extension().and_then(|e| e.to_str()).unwrap_or("");
+            let imported = match ext {
+                "py" => resolve_python(file, &content, &known),
+                "go" => match &go_module {
+                    Some(module) => resolve_go(&content, module, &known),
+                    None => Vec::new(),
+                },
+                _ => resolve_js(file, &content, &known),
+            };
+            for target in imported {
+                if &target != file {
+                    graph.add_edge(target, file.clone());
+                }
+            }
+        }
+        graph
+    }
+
+    /// Record that `importer` depends on `imported`.
+    pub fn add_edge(&mut self, imported: PathBuf, importer: PathBuf) {
+        self.dependents
+            .entry(imported)
+            .or_default()
+            .insert(importer);
+    }
+
+    /// Every file that imports `file` directly or through other files, sorted.
+    pub fn transitive_dependents(&self, file: &Path) -> Vec<PathBuf> {
+        let mut seen = BTreeSet::new();
+        let mut queue = VecDeque::from([file.to_path_buf()]);
+        while let Some(current) = queue.pop_front() {
+            for dependent in self.dependents.get(&current).into_iter().flatten() {
+                if dependent != file && seen.insert(dependent.clone()) {
+                    queue.push_back(dependent.clone());
+                }
+            }
+        }
+        seen.into_iter().collect()
+    }
+}
+
+fn collect_sources(root: &Path, dir: &Path, exclude_dirs: &[String], files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(root.join(dir)) else {
+        return;
+    };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        let relative = dir.join(&*name);
+        // `file_type` does not follow symlinks, so linked directories cannot loop.
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            if !name.starts_with('.') && !exclude_dirs.iter().any(|ex| *ex == name) {
+                collect_sources(root, &relative, exclude_dirs, files);
+            }
+        } else if file_type.is_file() {
+            let ext = relative.extension().and_then(|e| e.to_str()).unwrap_or("");
+            if ext == "py" || ext == "go" || JS_EXTENSIONS.contains(&ext) {
+                files.push(relative);
+            }
+        }
+    }
+}
+
+/// Resolve `.` and `..` without touching the filesystem.
+fn normalize(path: &Path) -> Option<PathBuf> {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !out.pop() {
+                    return None;
+                }
+            }
+            Component::Normal(part) => out.push(part),
+            _ => return None,
+        }
+    }
+    Some(out)
+}
+
+fn resolve_js(file: &Path, content: &str, known: &HashSet<PathBuf>) -> Vec<PathBuf> {
+    static IMPORT: OnceLock<Regex> = OnceLock::new();
+    let re = IMPORT.get_or_init(|| {
+        Regex::new(r#"(?:\bfrom\s*|\bimport\s*\(?\s*|\brequire\s*\(\s*)['"](\.{1,2}/[^'"]+)['"]"#)
+            .expect("valid regex")
+    });
+    let dir = file.parent().unwrap_or(Path::new(""));
+
+    re.captures_iter(content)
+        .filter_map(|c| {
+            let base = normalize(&dir.join(&c[1]))?;
+            let mut candidates = vec![base.clone()];
+            for ext in JS_EXTENSIONS {
+                candidates.push(PathBuf::from(format!("{}.{}", base.display(), ext)));
+            }
+            for ext in JS_EXTENSIONS {
+                candidates.push(base.join(format!("index.{}", ext)));
+            }
+            candidates.into_iter().find(|p| known.contains(p))
+        })
+        .collect()
+}
+
+fn resolve_python(file: &Path, content: &str, known: &HashSet<PathBuf>) -> Vec<PathBuf> {
+    static FROM: OnceLock<Regex> = OnceLock::new();
+    static IMPORT: OnceLock<Regex> = OnceLock::new();
+    let from_re = FROM.get_or_init(|| {
+        Regex::new(r"(?m)^\s*from\s+(\.*)([\w.]*)\s+import\s+\(?([\w\s,]+)").expect("valid regex")
+    });
+    let import_re = IMPORT.get_or_init(|| {
+        Regex::new(r"(?m)^\s*import\s+([\w.]+(?:\s*,\s*[\w.]+)*)").expect("valid regex")
+    });
+
+    let module_file = |base: &Path, dotted: &str| -> Option<PathBuf> {
+        let path = dotted
+            .split('.')
+            .filter(|p| !p.is_empty())
+            .fold(base.to_path_buf(), |acc, part| acc.join(part));
+        [path.with_extension("py"), path.join("__init__.py")]
+            .into_iter()
+            .find(|p| known.contains(p))
+    };
+
+    let mut found = Vec::new();
+    for c in import_re.captures_iter(content) {
+        for module in c[1].split(',') {
+            found.extend(module_file(Path::new(""), module.trim()));
+        }
+    }
+    for c in from_re.captures_iter(content) {
+        let dots = c[1].len();
+        let base = if dots == 0 {
+            Some(PathBuf::new())
+        } else {
+            // One dot is the current package; each extra dot goes up a level.
+            let mut base = file.parent().map(Path::to_path_buf).unwrap_or_default();
+            (1..dots)
+                .try_for_each(|_| base.pop().then_some(()))
+                .map(|_| base)
+        };
+        let Some(base) = base else { continue };
+        let module = &c[2];
+        let mut matched = false;
+        // `from pkg import mod` may name submodules rather than attributes.
+        for name in c[3].split(',').map(str::trim).filter(|n| !n.is_empty()) {
+            let name = name.split_whitespace().next().unwrap_or(name);
+            if let Some(path) = module_file(&base, &format!("{}.{}", module, name)) {
+                found.push(path);
+                matched = true;
+            }
+        }
+        if !matched && !module.is_empty() {
+            found.extend(module_file(&base, module));
+        }
+    }
+    found
+}
+
+fn go_module_path(root: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(root.join("go.mod")).ok()?;
+    content.lines().find_map(|line| {
+        line.trim()
+            .strip_prefix("module ")
+            .map(|m| m.trim().trim_matches('"').to_string())
+    })
+}
+
+fn resolve_go(content: &str, module: &str, known: &HashSet<PathBuf>) -> Vec<PathBuf> {
+    static IMPORT: OnceLock<Regex> = OnceLock::new();
+    static BLOCK: OnceLock<Regex> = OnceLock::new();
+    let single = IMPORT.get_or_init(|| {
+        Regex::new(r#"(?m)^\s*import\s+(?:\w+\s+)?"([^"]+)""#).expect("valid regex")
+    });
+    let block = BLOCK.get_or_init(|| Regex::new(r"(?s)import\s*\((.*?)\)").expect("valid regex"));
+
+    let mut packages: Vec<String> = single
+        .captures_iter(content)
+        .map(|c| c[1].to_string())
+        .collect();
+    for c in block.captures_iter(content) {
+        packages.extend(c[1].split('"').skip(1).step_by(2).map(str::to_string));
+    }
+
+    // A Go import names a package directory; every non-test file in it is a dependency.
+    let mut found = Vec::new();
+    for package in packages {
+        let Some(rest) = package.strip_prefix(module) else {
+            continue;
+        };
+        let dir = PathBuf::from(rest.trim_start_matches('/'));
+        found.extend(
+            known
+                .iter()
+                .filter(|p| {
+                    p.parent() == Some(dir.as_path())
+                        && p.extension().is_some_and(|e| e == "go")
+                        && !p.to_string_lossy().ends_with("_test.go")
+                })
+                .cloned(),
+        );
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let full = root.join(path);
+        std::fs::create_dir_all(full.parent().unwrap()).unwrap();
+        std::fs::write(full, content).unwrap();
+    }
+
+    #[test]
+    fn test_transitive_js_and_python_imports() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "src/util/format.ts", "export const f = 1;\n");
+        write(
+            root,
+            "src/api/client.ts",
+            "import { f } from '../util/format';\n",
+        );
+        write(root, "src/app.js", "const c = require('./api/client');\n");
+        write(
+            root,
+            "node_modules/lib/index.js",
+            "require('../../src/util/format');\n",
+        );
+        write(root, "shop/__init__.py", "");
+        write(root, "shop/helpers.py", "X = 1\n");
+        write(root, "shop/cart.py", "from .helpers import X\n");
+        write(root, "tests/test_cart.py", "from shop import cart\n");
+
+        let graph = ImportGraph::build(root, &["node_modules".to_string()]);
+        assert_eq!(
+            graph.transitive_dependents(Path::new("src/util/format.ts")),
+            vec![
+                PathBuf::from("src/api/client.ts"),
+                PathBuf::from("src/app.js")
+            ]
+        );
+        assert_eq!(
+            graph.transitive_dependents(Path::new("shop/helpers.py")),
+            vec![
+                PathBuf::from("shop/cart.py"),
+                PathBuf::from("tests/test_cart.py")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_go_package_imports() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "go.mod", "module example.com/app\n\ngo 1.22\n");
+        write(root, "internal/strutil/strutil.go", "package strutil\n");
+        write(
+            root,
+            "api/handler.go",
+            "package api\n\nimport (\n\t\"fmt\"\n\t\"example.com/app/internal/strutil\"\n)\n",
+        );
+        write(root, "api/handler_test.go", "package api\n");
+
+        let graph = ImportGraph::build(root, &[]);
+        assert_eq!(
+            graph.transitive_dependents(Path::new("internal/strutil/strutil.go")),
+            vec![PathBuf::from("api/handler.go")]
+        );
+    }
+}
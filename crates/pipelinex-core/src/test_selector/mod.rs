@@ -0,0 +1,895 @@
+pub mod imports;
+
+use anyhow::{Context, Result};
+use imports::ImportGraph;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Represents a test selection strategy based on code changes.
+#[derive(Debug, Clone, Serialize)]
+pub struct TestSelection {
+    /// Files that have changed
+    pub changed_files: Vec<PathBuf>,
+    /// Tests that should be run based on changes
+    pub selected_tests: Vec<String>,
+    /// Test patterns for CI configuration
+    pub test_patterns: Vec<String>,
+    /// Percentage of total tests selected
+    pub selection_ratio: f64,
+    /// Reasoning for the selection
+    pub reasoning: Vec<String>,
+    /// Detected language of each changed source or test file
+    pub languages: Vec<FileLanguage>,
+    /// Framework-specific commands that run the selected tests
+    pub test_commands: Vec<String>,
+}
+
+/// The language of a changed file and the nearest manifest that owns it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileLanguage {
+    pub file: PathBuf,
+    pub language: String,
+    /// Closest `Cargo.toml`, `go.mod`, `pyproject.toml`, `setup.py` or `package.json`
+    pub manifest: Option<PathBuf>,
+}
+
+/// Manifest files that mark a project root, by language.
+const MANIFESTS: &[(&str, &[&str])] = &[
+    ("rust", &["Cargo.toml"]),
+    ("go", &["go.mod"]),
+    ("python", &["pyproject.toml", "setup.py", "setup.cfg"]),
+    ("javascript", &["package.json"]),
+    ("typescript", &["package.json"]),
+];
+
+fn language_for_extension(ext: &str) -> Option<&'static str> {
+    Some(match ext {
+        "rs" => "rust",
+        "js" | "jsx" | "mjs" | "cjs" => "javascript",
+        "ts" | "tsx" => "typescript",
+        "py" => "python",
+        "go" => "go",
+        "java" => "java",
+        "rb" => "ruby",
+        _ => return None,
+    })
+}
+
+/// Configuration for test selection behavior.
+#[derive(Debug, Clone)]
+pub struct TestSelectorConfig {
+    /// Always run these test patterns regardless of changes
+    pub always_run: Vec<String>,
+    /// Language-specific test file patterns
+    pub test_patterns: HashMap<String, Vec<String>>,
+    /// Directories to exclude from analysis
+    pub exclude_dirs: Vec<String>,
+    /// Minimum tests to run (even if changes are minimal)
+    pub min_tests: usize,
+    /// Custom path-to-test rules, checked before the built-in heuristics
+    pub test_map: Vec<TestMapRule>,
+    /// Also select tests for files that transitively import a changed file
+    pub follow_imports: bool,
+}
+
+/// Default location of the test mapping file, relative to the repository root.
+pub const DEFAULT_TEST_MAP_PATH: &str = ".pipelinex/test-map.toml";
+
+/// A custom mapping from changed source paths to the tests they affect.
+///
+/// ```toml
+/// [[rules]]
+/// path_glob = "services/foo/**"
+/// test_patterns = ["qa/foo/**"]
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct TestMapRule {
+    pub path_glob: String,
+    pub test_patterns: Vec<String>,
+}
+
+impl TestMapRule {
+    fn matches(&self, path: &Path) -> bool {
+        glob::Pattern::new(&self.path_glob)
+            .map(|p| p.matches_path(path))
+            .unwrap_or(false)
+    }
+}
+
+#[derive(Deserialize)]
+struct TestMapFile {
+    #[serde(default)]
+    rules: Vec<TestMapRule>,
+}
+
+/// Load the `[[rules]]` of a test mapping file, rejecting invalid globs.
+pub fn load_test_map(path: &Path) -> Result<Vec<TestMapRule>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read test map {}", path.display()))?;
+    let file: TestMapFile = toml::from_str(&content)
+        .with_context(|| format!("Failed to parse test map {}", path.display()))?;
+    for rule in &file.rules {
+        glob::Pattern::new(&rule.path_glob).with_context(|| {
+            format!(
+                "Invalid path_glob '{}' in {}",
+                rule.path_glob,
+                path.display()
+            )
+        })?;
+    }
+    Ok(file.rules)
+}
+
+impl Default for TestSelectorConfig {
+    fn default() -> Self {
+        let mut test_patterns = HashMap::new();
+
+        // Common test patterns by language
+        test_patterns.insert(
+            "rust".to_string(),
+            vec!["**/*_test.rs".to_string(), "**/tests/**/*.rs".to_string()],
+        );
+
+        test_patterns.insert(
+            "javascript".to_string(),
+            vec![
+                "**/*.test.js".to_string(),
+                "**/*.spec.js".to_string(),
+                "**/__tests__/**/*.js".to_string(),
+            ],
+        );
+
+        test_patterns.insert(
+            "typescript".to_string(),
+            vec![
+                "**/*.test.ts".to_string(),
+                "**/*.spec.ts".to_string(),
+                "**/__tests__/**/*.ts".to_string(),
+            ],
+        );
+
+        test_patterns.insert(
+            "python".to_string(),
+            vec![
+                "**/test_*.py".to_string(),
+                "**/*_test.py".to_string(),
+                "**/tests/**/*.py".to_string(),
+            ],
+        );
+
+        test_patterns.insert("go".to_string(), vec!["**/*_test.go".to_string()]);
+
+        Self {
+            always_run: vec!["e2e".to_string(), "integration".to_string()],
+            test_patterns,
+            exclude_dirs: vec![
+                "node_modules".to_string(),
+                ".git".to_string(),
+                "target".to_string(),
+                "dist".to_string(),
+                "build".to_string(),
+            ],
+            min_tests: 3,
+            test_map: Vec::new(),
+            follow_imports: false,
+        }
+    }
+}
+
+/// Main test selection engine.
+pub struct TestSelector {
+    config: TestSelectorConfig,
+}
+
+impl TestSelector {
+    /// Create a new test selector with default configuration.
+    pub fn new() -> Self {
+        Self {
+            config: TestSelectorConfig::default(),
+        }
+    }
+
+    /// Create a test selector with custom configuration.
+    pub fn with_config(config: TestSelectorConfig) -> Self {
+        Self { config }
+    }
+
+    /// Select tests based on git diff between two commits.
+    pub fn select_from_git_diff(
+        &self,
+        base: &str,
+        head: &str,
+        repo_path: Option<&Path>,
+    ) -> Result<TestSelection> {
+        let changed_files = self.get_changed_files(base, head, repo_path)?;
+        self.select_from_changes(&changed_files, repo_path)
+    }
+
+    /// Select tests based on a list of changed files.
+    pub fn select_from_changes(
+        &self,
+        changed_files: &[PathBuf],
+        repo_path: Option<&Path>,
+    ) -> Result<TestSelection> {
+        let mut selected_tests = HashSet::new();
+        let mut test_patterns = HashSet::new();
+        let mut reasoning = Vec::new();
+
+        // Filter out excluded directories
+        let relevant_files: Vec<_> = changed_files
+            .iter()
+            .filter(|f| !self.is_excluded(f))
+            .collect();
+
+        if relevant_files.is_empty() {
+            reasoning.push("No relevant code changes detected".to_string());
+            return Ok(TestSelection {
+                changed_files: changed_files.to_vec(),
+                selected_tests: vec![],
+                test_patterns: vec![],
+                selection_ratio: 0.0,
+                reasoning,
+                languages: vec![],
+                test_commands: vec![],
+            });
+        }
+
+        // Detect language from file extensions
+        let languages = self.detect_languages(&relevant_files);
+        let root = repo_path.unwrap_or(Path::new("."));
+        let file_languages: Vec<FileLanguage> = relevant_files
+            .iter()
+            .filter_map(|f| detect_file_language(f, root))
+            .collect();
+
+        // 1. Direct test files that changed
+        for file in &relevant_files {
+            if self.is_test_file(file) {
+                let test_name = self.file_to_test_name(file);
+                selected_tests.insert(test_name.clone());
+                reasoning.push(format!("Direct: {} (test file changed)", test_name));
+            }
+        }
+
+        // 2. Custom test-map rules; a mapped file skips the heuristics below
+        let mut mapped_files = HashSet::new();
+        for file in &relevant_files {
+            for rule in self.config.test_map.iter().filter(|r| r.matches(file)) {
+                mapped_files.insert(*file);
+                for pattern in &rule.test_patterns {
+                    if selected_tests.insert(pattern.clone()) {
+                        reasoning.push(format!(
+                            "Mapped: {} ({} matches {})",
+                            pattern,
+                            file.display(),
+                            rule.path_glob
+                        ));
+                    }
+                }
+            }
+        }
+
+        // 3. Tests for changed source files
+        for file in &relevant_files {
+            if !self.is_test_file(file) && !mapped_files.contains(file) {
+                if let Some(test_file) = self.find_test_for_source(file, &languages, repo_path) {
+                    let test_name = self.file_to_test_name(&test_file);
+                    if selected_tests.insert(test_name.clone()) {
+                        reasoning.push(format!(
+                            "Affected: {} (source file {} changed)",
+                            test_name,
+                            file.display()
+                        ));
+                    }
+                }
+            }
+        }
+
+        // 4. Tests for files that import a changed file, directly or transitively
+        let mut dependents = BTreeSet::new();
+        if self.config.follow_imports {
+            let graph = ImportGraph::build(root, &self.config.exclude_dirs);
+            for file in relevant_files.iter().filter(|f| !self.is_test_file(f)) {
+                for dependent in graph.transitive_dependents(file) {
+                    let test_file = if self.is_test_file(&dependent) {
+                        Some(dependent.clone())
+                    } else {
+                        let dep_languages = self.detect_languages(&[&dependent]);
+                        self.find_test_for_source(&dependent, &dep_languages, repo_path)
+                    };
+                    if let Some(test_file) = test_file {
+                        let test_name = self.file_to_test_name(&test_file);
+                        if selected_tests.insert(test_name.clone()) {
+                            reasoning.push(format!(
+                                "Dependent: {} (imports changed {})",
+                                test_name,
+                                file.display()
+                            ));
+                        }
+                    }
+                    dependents.insert(dependent);
+                }
+            }
+        }
+
+        // 5. Add always-run tests (integration, e2e)
+        for pattern in &self.config.always_run {
+            test_patterns.insert(pattern.clone());
+            reasoning.push(format!("Always-run: {}", pattern));
+        }
+
+        // 6. If changes are in critical paths (config, CI), run all tests
+        let unmapped: Vec<_> = relevant_files
+            .iter()
+            .copied()
+            .filter(|f| !mapped_files.contains(f))
+            .collect();
+        if self.has_critical_changes(&unmapped) {
+            reasoning.push(
+                "Critical files changed (CI config, dependencies) — running all tests".to_string(),
+            );
+            let mut test_commands: Vec<String> = file_languages
+                .iter()
+                .filter_map(full_suite_command)
+                .collect();
+            test_commands.sort();
+            test_commands.dedup();
+            return Ok(TestSelection {
+                changed_files: changed_files.to_vec(),
+                selected_tests: vec!["all".to_string()],
+                test_patterns: vec!["**/*".to_string()],
+                selection_ratio: 1.0,
+                reasoning,
+                languages: file_languages,
+                test_commands,
+            });
+        }
+
+        // Generate test patterns from selected tests
+        for test in &selected_tests {
+            test_patterns.insert(test.clone());
+        }
+
+        // Ensure minimum test coverage
+        let selected_vec: Vec<_> = selected_tests.into_iter().collect();
+        let selection_ratio = if selected_vec.is_empty() {
+            0.0
+        } else {
+            // Estimate based on patterns
+            0.15 // Conservative estimate: 15% of tests
+        };
+
+        let dependent_languages: Vec<FileLanguage> = dependents
+            .iter()
+            .filter_map(|f| detect_file_language(f, root))
+            .collect();
+        let mut test_commands: Vec<String> = file_languages
+            .iter()
+            .chain(&dependent_languages)
+            .filter_map(|fl| {
+                let is_test = self.is_test_file(&fl.file);
+                targeted_command(fl, is_test, root)
+            })
+            .collect();
+        test_commands.sort();
+        test_commands.dedup();
+
+        Ok(TestSelection {
+            changed_files: changed_files.to_vec(),
+            selected_tests: selected_vec,
+            test_patterns: test_patterns.into_iter().collect(),
+            selection_ratio,
+            reasoning,
+            languages: file_languages,
+            test_commands,
+        })
+    }
+
+    /// Get changed files between two git refs.
+    fn get_changed_files(
+        &self,
+        base: &str,
+        head: &str,
+        repo_path: Option<&Path>,
+    ) -> Result<Vec<PathBuf>> {
+        let mut cmd = Command::new("git");
+
+        if let Some(path) = repo_path {
+            cmd.current_dir(path);
+        }
+
+        let output = cmd
+            .args(["diff", "--name-only", base, head])
+            .output()
+            .context("Failed to run git diff")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "git diff failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        let files = String::from_utf8(output.stdout)?
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(PathBuf::from)
+            .collect();
+
+        Ok(files)
+    }
+
+    /// Check if a file is excluded from analysis.
+    fn is_excluded(&self, path: &Path) -> bool {
+        path.components().any(|c| {
+            self.config
+                .exclude_dirs
+                .iter()
+                .any(|ex| c.as_os_str() == ex.as_str())
+        })
+    }
+
+    /// Check if a file is a test file.
+    fn is_test_file(&self, path: &Path) -> bool {
+        let path_str = path.to_string_lossy();
+
+        // Check common test patterns
+        path_str.contains("test")
+            || path_str.contains("spec")
+            || path_str.contains("__tests__")
+            || path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .map(|n| {
+                    n.starts_with("test_")
+                        || n.ends_with("_test.rs")
+                        || n.ends_with("_test.go")
+                        || n.ends_with(".test.js")
+                        || n.ends_with(".test.ts")
+                        || n.ends_with(".spec.js")
+                        || n.ends_with(".spec.ts")
+                })
+                .unwrap_or(false)
+    }
+
+    /// Detect languages from file extensions.
+    fn detect_languages(&self, files: &[&PathBuf]) -> HashSet<String> {
+        let mut languages = HashSet::new();
+
+        for file in files {
+            if let Some(lang) = file
+                .extension()
+                .and_then(|e| e.to_str())
+                .and_then(language_for_extension)
+            {
+                languages.insert(lang.to_string());
+            }
+        }
+
+        languages
+    }
+
+    /// Find test file for a source file.
+    fn find_test_for_source(
+        &self,
+        source: &Path,
+        languages: &HashSet<String>,
+        repo_path: Option<&Path>,
+    ) -> Option<PathBuf> {
+        let source_stem = source.file_stem()?.to_str()?;
+        let parent = source.parent()?;
+
+        // Try common patterns
+        for lang in languages {
+            // For Rust: src/foo.rs -> tests/foo_test.rs or src/foo.rs -> src/foo/tests.rs
+            if lang == "rust" {
+                let test_file = parent.join(format!("{}_test.rs", source_stem));
+                if test_file.exists() {
+                    return Some(test_file);
+                }
+
+                let test_dir = parent.join("tests").join(format!("{}.rs", source_stem));
+                if test_dir.exists() {
+                    return Some(test_dir);
+                }
+            }
+
+            // For JS/TS: src/foo.js -> src/foo.test.js or src/__tests__/foo.test.js
+            if lang == "javascript" || lang == "typescript" {
+                let ext = source.extension()?.to_str()?;
+                let test_file = parent.join(format!("{}.test.{}", source_stem, ext));
+                if test_file.exists() {
+                    return Some(test_file);
+                }
+
+                let test_dir = parent
+                    .join("__tests__")
+                    .join(format!("{}.test.{}", source_stem, ext));
+                if test_dir.exists() {
+                    return Some(test_dir);
+                }
+            }
+
+            // For Python: src/foo.py -> tests/test_foo.py
+            if lang == "python" {
+                let test_file = Path::new("tests").join(format!("test_{}.py", source_stem));
+                if let Some(repo) = repo_path {
+                    let full_path = repo.join(&test_file);
+                    if full_path.exists() {
+                        return Some(test_file);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Check if changes include critical files that require full test suite.
+    fn has_critical_changes(&self, files: &[&PathBuf]) -> bool {
+        files.iter().any(|f| {
+            let path_str = f.to_string_lossy();
+            path_str.contains(".github/workflows")
+                || path_str.contains(".gitlab-ci")
+                || path_str.contains("Jenkinsfile")
+                || path_str.contains(".circleci")
+                || path_str.contains("package.json")
+                || path_str.contains("Cargo.toml")
+                || path_str.contains("go.mod")
+                || path_str.contains("requirements.txt")
+                || path_str.contains("pom.xml")
+                || path_str.contains("build.gradle")
+        })
+    }
+
+    /// Convert file path to test name/pattern.
+    fn file_to_test_name(&self, path: &Path) -> String {
+        // Convert path to a test identifier
+        path.to_string_lossy().to_string()
+    }
+}
+
+impl Default for TestSelector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Detect a file's language by extension and find its nearest manifest under `root`.
+fn detect_file_language(file: &Path, root: &Path) -> Option<FileLanguage> {
+    let language = language_for_extension(file.extension()?.to_str()?)?;
+    let names = MANIFESTS
+        .iter()
+        .find(|(lang, _)| *lang == language)
+        .map(|(_, names)| *names)
+        .unwrap_or_default();
+
+    let manifest = file.ancestors().skip(1).find_map(|dir| {
+        names
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|candidate| root.join(candidate).is_file())
+    });
+
+    Some(FileLanguage {
+        file: file.to_path_buf(),
+        language: language.to_string(),
+        manifest,
+    })
+}
+
+/// Directory of the manifest relative to the repository root (`""` for the root).
+fn project_dir(fl: &FileLanguage) -> Option<&Path> {
+    fl.manifest
+        .as_deref()
+        .and_then(Path::parent)
+        .filter(|dir| !dir.as_os_str().is_empty())
+}
+
+/// Prefix `command` with a `cd` into the project directory when it is not the root.
+fn in_project(fl: &FileLanguage, command: String) -> String {
+    match project_dir(fl) {
+        Some(dir) => format!("cd {} && {}", dir.display(), command),
+        None => command,
+    }
+}
+
+/// `[package] name` from a Cargo manifest.
+fn cargo_package_name(manifest: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(manifest).ok()?;
+    let value: toml::Value = toml::from_str(&content).ok()?;
+    value
+        .get("package")?
+        .get("name")?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// Command that runs the tests affected by one changed file.
+fn targeted_command(fl: &FileLanguage, is_test: bool, root: &Path) -> Option<String> {
+    let relative = |path: &Path| -> PathBuf {
+        match project_dir(fl) {
+            Some(dir) => path.strip_prefix(dir).unwrap_or(path).to_path_buf(),
+            None => path.to_path_buf(),
+        }
+    };
+    let parent = fl.file.parent().map(relative).unwrap_or_default();
+    let parent = if parent.as_os_str().is_empty() {
+        ".".to_string()
+    } else {
+        parent.display().to_string()
+    };
+
+    let command = match fl.language.as_str() {
+        "go" => {
+            let package = if parent == "." {
+                "./...".to_string()
+            } else {
+                format!("./{}/...", parent)
+            };
+            format!("go test {}", package)
+        }
+        "python" if is_test => format!("pytest {}", relative(&fl.file).display()),
+        "python" => format!("pytest {}/test_*.py", parent),
+        "rust" => {
+            // `-p` resolves from anywhere in the workspace, so no `cd` is needed
+            let package = fl
+                .manifest
+                .as_ref()
+                .and_then(|m| cargo_package_name(&root.join(m)));
+            return Some(match package {
+                Some(name) => format!("cargo test -p {}", name),
+                None => "cargo test".to_string(),
+            });
+        }
+        "javascript" | "typescript" if is_test => {
+            format!("npm test -- {}", relative(&fl.file).display())
+        }
+        "javascript" | "typescript" => "npm test".to_string(),
+        _ => return None,
+    };
+    Some(in_project(fl, command))
+}
+
+/// Command that runs the whole suite of the project owning a changed file.
+fn full_suite_command(fl: &FileLanguage) -> Option<String> {
+    let command = match fl.language.as_str() {
+        "go" => "go test ./...",
+        "python" => "pytest",
+        "rust" => return Some("cargo test --workspace".to_string()),
+        "javascript" | "typescript" => "npm test",
+        _ => return None,
+    };
+    Some(in_project(fl, command.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_test_file() {
+        let selector = TestSelector::new();
+
+        assert!(selector.is_test_file(Path::new("src/foo_test.rs")));
+        assert!(selector.is_test_file(Path::new("tests/integration.rs")));
+        assert!(selector.is_test_file(Path::new("src/foo.test.ts")));
+        assert!(selector.is_test_file(Path::new("src/__tests__/foo.js")));
+        assert!(selector.is_test_file(Path::new("test_foo.py")));
+
+        assert!(!selector.is_test_file(Path::new("src/main.rs")));
+        assert!(!selector.is_test_file(Path::new("src/lib.js")));
+    }
+
+    #[test]
+    fn test_detect_languages() {
+        let selector = TestSelector::new();
+        let files = [
+            PathBuf::from("src/main.rs"),
+            PathBuf::from("src/lib.rs"),
+            PathBuf::from("index.js"),
+        ];
+        let file_refs: Vec<_> = files.iter().collect();
+
+        let languages = selector.detect_languages(&file_refs);
+        assert!(languages.contains("rust"));
+        assert!(languages.contains("javascript"));
+        assert!(!languages.contains("python"));
+    }
+
+    #[test]
+    fn test_is_excluded() {
+        let selector = TestSelector::new();
+
+        assert!(selector.is_excluded(Path::new("node_modules/foo/bar.js")));
+        assert!(selector.is_excluded(Path::new(".git/config")));
+        assert!(selector.is_excluded(Path::new("target/debug/build")));
+
+        assert!(!selector.is_excluded(Path::new("src/main.rs")));
+        assert!(!selector.is_excluded(Path::new("tests/integration.rs")));
+    }
+
+    #[test]
+    fn test_has_critical_changes() {
+        let selector = TestSelector::new();
+
+        let files = [PathBuf::from(".github/workflows/ci.yml")];
+        let file_refs: Vec<_> = files.iter().collect();
+        assert!(selector.has_critical_changes(&file_refs));
+
+        let files = [PathBuf::from("package.json")];
+        let file_refs: Vec<_> = files.iter().collect();
+        assert!(selector.has_critical_changes(&file_refs));
+
+        let files = [PathBuf::from("src/main.rs")];
+        let file_refs: Vec<_> = files.iter().collect();
+        assert!(!selector.has_critical_changes(&file_refs));
+    }
+
+    #[test]
+    fn test_test_map_selects_custom_layout() {
+        let dir = tempfile::tempdir().unwrap();
+        let map = dir.path().join("test-map.toml");
+        std::fs::write(
+            &map,
+            r#"
+[[rules]]
+path_glob = "services/foo/**"
+test_patterns = ["qa/foo/**"]
+
+[[rules]]
+path_glob = "services/bar/**"
+test_patterns = ["qa/bar/**"]
+"#,
+        )
+        .unwrap();
+
+        let changed = vec![PathBuf::from("services/foo/handler.go")];
+
+        // The heuristics find nothing for this layout
+        let heuristic = TestSelector::new()
+            .select_from_changes(&changed, None)
+            .unwrap();
+        assert!(heuristic.selected_tests.is_empty());
+
+        let config = TestSelectorConfig {
+            test_map: load_test_map(&map).unwrap(),
+            ..TestSelectorConfig::default()
+        };
+        let selection = TestSelector::with_config(config)
+            .select_from_changes(&changed, None)
+            .unwrap();
+        assert_eq!(selection.selected_tests, vec!["qa/foo/**"]);
+        assert!(selection.test_patterns.contains(&"qa/foo/**".to_string()));
+        assert!(!selection.test_patterns.contains(&"qa/bar/**".to_string()));
+        assert!(selection
+            .reasoning
+            .iter()
+            .any(|r| r.starts_with("Mapped: qa/foo/**")));
+    }
+
+    #[test]
+    fn test_test_map_overrides_critical_files() {
+        let config = TestSelectorConfig {
+            test_map: vec![TestMapRule {
+                path_glob: "services/foo/**".to_string(),
+                test_patterns: vec!["qa/foo/**".to_string()],
+            }],
+            ..TestSelectorConfig::default()
+        };
+        let selector = TestSelector::with_config(config);
+
+        // A service-local package.json no longer forces the full suite
+        let changed = vec![PathBuf::from("services/foo/package.json")];
+        let selection = selector.select_from_changes(&changed, None).unwrap();
+        assert_eq!(selection.selected_tests, vec!["qa/foo/**"]);
+
+        let dir = tempfile::tempdir().unwrap();
+        let bad = dir.path().join("test-map.toml");
+        std::fs::write(&bad, "[[rules]]\npath_glob = \"[\"\ntest_patterns = []\n").unwrap();
+        assert!(load_test_map(&bad).is_err());
+    }
+
+    #[test]
+    fn test_framework_commands_per_language() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join("go.mod"), "module example.com/app\n").unwrap();
+        std::fs::create_dir_all(root.join("ml")).unwrap();
+        std::fs::write(root.join("ml/pyproject.toml"), "[project]\nname = \"ml\"\n").unwrap();
+        std::fs::create_dir_all(root.join("crates/core")).unwrap();
+        std::fs::write(
+            root.join("crates/core/Cargo.toml"),
+            "[package]\nname = \"app-core\"\n",
+        )
+        .unwrap();
+
+        let changed = vec![
+            PathBuf::from("pkg/store/db.go"),
+            PathBuf::from("ml/models/train.py"),
+            PathBuf::from("ml/tests/test_train.py"),
+            PathBuf::from("crates/core/src/lib.rs"),
+        ];
+        let selection = TestSelector::new()
+            .select_from_changes(&changed, Some(root))
+            .unwrap();
+
+        let go = &selection.languages[0];
+        assert_eq!(go.language, "go");
+        assert_eq!(go.manifest, Some(PathBuf::from("go.mod")));
+        assert_eq!(
+            selection.languages[1].manifest,
+            Some(PathBuf::from("ml/pyproject.toml"))
+        );
+
+        let commands = &selection.test_commands;
+        assert!(commands.contains(&"go test ./pkg/store/...".to_string()));
+        assert!(commands.contains(&"cd ml && pytest models/test_*.py".to_string()));
+        assert!(commands.contains(&"cd ml && pytest tests/test_train.py".to_string()));
+        assert!(commands.contains(&"cargo test -p app-core".to_string()));
+    }
+
+    #[test]
+    fn test_languages_without_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let changed = vec![PathBuf::from("svc/handler.go"), PathBuf::from("README.md")];
+        let selection = TestSelector::new()
+            .select_from_changes(&changed, Some(dir.path()))
+            .unwrap();
+
+        assert_eq!(
+            selection.languages,
+            vec![FileLanguage {
+                file: PathBuf::from("svc/handler.go"),
+                language: "go".to_string(),
+                manifest: None,
+            }]
+        );
+        assert_eq!(selection.test_commands, vec!["go test ./svc/..."]);
+    }
+
+    #[test]
+    fn test_deps_selects_tests_of_dependents() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let write = |path: &str, content: &str| {
+            let full = root.join(path);
+            std::fs::create_dir_all(full.parent().unwrap()).unwrap();
+            std::fs::write(full, content).unwrap();
+        };
+        write("src/util/format.ts", "export const fmt = () => '';\n");
+        write(
+            "src/api/client.ts",
+            "import { fmt } from '../util/format';\n",
+        );
+        write(
+            "src/api/client.spec.ts",
+            "import { client } from './client';\n",
+        );
+        write("src/other.spec.ts", "export {};\n");
+
+        let changed = vec![PathBuf::from("src/util/format.ts")];
+        let plain = TestSelector::new()
+            .select_from_changes(&changed, Some(root))
+            .unwrap();
+        assert!(plain.selected_tests.is_empty());
+
+        let config = TestSelectorConfig {
+            follow_imports: true,
+            ..TestSelectorConfig::default()
+        };
+        let selection = TestSelector::with_config(config)
+            .select_from_changes(&changed, Some(root))
+            .unwrap();
+        assert_eq!(selection.selected_tests, vec!["src/api/client.spec.ts"]);
+        assert!(
+            selection
+                .reasoning
+                .iter()
+                .any(|r| r
+                    == "Dependent: src/api/client.spec.ts (imports changed src/util/format.ts)")
+        );
+    }
+}
@@ -0,0 +1,118 @@
+//! Phase timings in the folded-stack format used by flamegraph tools.
+//!
+//! Each line of [`SpanTimer::to_folded`] is a `;`-separated stack of frames
+//! followed by a sample count, here elapsed microseconds:
+//!
+//! ```text
+//! discover;scan 18234
+//! discover;parse;apps/web/.github/workflows/ci.yml 912
+//! ```
+//!
+//! `inferno-flamegraph` and `flamegraph.pl` render this directly.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+/// Accumulates elapsed time per stack of frames.
+#[derive(Debug, Default)]
+pub struct SpanTimer {
+    /// Total microseconds per stack, sorted by stack as flamegraph tools expect.
+    spans: BTreeMap<String, u64>,
+}
+
+impl SpanTimer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `f`, charging its wall-clock time to `stack`.
+    pub fn time<T>(&mut self, stack: &[&str], f: impl FnOnce() -> T) -> T {
+        let started = Instant::now();
+        let result = f();
+        self.record(stack, started.elapsed());
+        result
+    }
+
+    /// Add `elapsed` to `stack`. Repeated stacks are summed into one line.
+    pub fn record(&mut self, stack: &[&str], elapsed: Duration) {
+        let key = stack
+            .iter()
+            .map(|frame| sanitize_frame(frame))
+            .collect::<Vec<_>>()
+            .join(";");
+        // A span that ran at all should show up, even below a microsecond
+        let micros = (elapsed.as_micros() as u64).max(1);
+        *self.spans.entry(key).or_default() += micros;
+    }
+
+    /// The recorded spans as folded stacks, one per line.
+    pub fn to_folded(&self) -> String {
+        self.spans
+            .iter()
+            .map(|(stack, micros)| format!("{} {}\n", stack, micros))
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+}
+
+/// `;` separates frames and the last space separates the count, so neither
+/// may appear inside a frame.
+fn sanitize_frame(frame: &str) -> String {
+    frame
+        .chars()
+        .map(|c| {
+            if c == ';' || c.is_whitespace() {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_folded_output_has_stacks_and_counts() {
+        let mut timer = SpanTimer::new();
+        timer.time(&["discover", "scan"], || ());
+        let parsed = timer.time(&["discover", "parse", "apps/web/ci.yml"], || 42);
+        assert_eq!(parsed, 42);
+        timer.record(
+            &["discover", "parse", "apps/web/ci.yml"],
+            Duration::from_micros(500),
+        );
+        timer.record(
+            &["discover", "analyze", "my app;v2"],
+            Duration::from_millis(2),
+        );
+
+        let folded = timer.to_folded();
+        let lines: Vec<&str> = folded.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "discover;analyze;my_app_v2 2000");
+        assert!(lines[1].starts_with("discover;parse;apps/web/ci.yml "));
+        assert!(lines[2].starts_with("discover;scan "));
+
+        for line in lines {
+            let (stack, count) = line.rsplit_once(' ').unwrap();
+            assert!(!stack.contains(' '));
+            assert!(count.parse::<u64>().unwrap() >= 1);
+        }
+        let parse_micros: u64 = folded
+            .lines()
+            .nth(1)
+            .unwrap()
+            .rsplit_once(' ')
+            .unwrap()
+            .1
+            .parse()
+            .unwrap();
+        assert!(parse_micros >= 501);
+    }
+}
@@ -0,0 +1,84 @@
+//! Change tracking for `watch`.
+//!
+//! File watchers fire on saves that leave the content untouched (editors
+//! rewrite files, `touch`, checkouts of the same revision). [`ContentHashes`]
+//! remembers a SHA-256 of each file so only real edits trigger a re-analysis.
+
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Content hash per file, keyed by absolute path.
+#[derive(Debug, Default)]
+pub struct ContentHashes {
+    hashes: HashMap<PathBuf, String>,
+}
+
+impl ContentHashes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the current content of `files` and return those that are new,
+    /// changed or deleted since the last call, in input order.
+    ///
+    /// A file that exists on disk with the same bytes as before is skipped.
+    pub fn changed_files(&mut self, files: &[PathBuf]) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        for file in files {
+            let key = std::path::absolute(file).unwrap_or_else(|_| file.clone());
+            let hash = hash_file(file);
+            let previous = match &hash {
+                Some(hash) => self.hashes.insert(key, hash.clone()),
+                None => self.hashes.remove(&key),
+            };
+            if previous != hash {
+                changed.push(file.clone());
+            }
+        }
+        changed
+    }
+
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+}
+
+fn hash_file(path: &Path) -> Option<String> {
+    let bytes = std::fs::read(path).ok()?;
+    Some(hex::encode(Sha256::digest(&bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_touch_without_content_change_is_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let ci = dir.path().join("ci.yml");
+        let other = dir.path().join("release.yml");
+        std::fs::write(&ci, "on: push\n").unwrap();
+        std::fs::write(&other, "on: release\n").unwrap();
+        let files = vec![ci.clone(), other.clone()];
+
+        let mut hashes = ContentHashes::new();
+        assert_eq!(hashes.changed_files(&files), files);
+
+        // Rewriting identical bytes (what editors and `touch` do) is a no-op
+        std::fs::write(&ci, "on: push\n").unwrap();
+        assert!(hashes.changed_files(&files).is_empty());
+
+        std::fs::write(&other, "on: [release]\n").unwrap();
+        assert_eq!(hashes.changed_files(&files), vec![other.clone()]);
+
+        std::fs::remove_file(&ci).unwrap();
+        assert_eq!(hashes.changed_files(&files), vec![ci.clone()]);
+        assert!(hashes.changed_files(&files).is_empty());
+        assert_eq!(hashes.len(), 1);
+    }
+}
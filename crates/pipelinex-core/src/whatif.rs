@@ -395,6 +395,9 @@ mod tests {
             uses: Some("actions/checkout@v4".into()),
             run: None,
             estimated_duration_secs: Some(15.0),
+            with: HashMap::new(),
+            working_directory: None,
+            env: HashMap::new(),
         });
         dag.add_job(checkout);
 
@@ -406,6 +409,9 @@ mod tests {
             uses: None,
             run: Some("npm run build".into()),
             estimated_duration_secs: Some(300.0),
+            with: HashMap::new(),
+            working_directory: None,
+            env: HashMap::new(),
         });
         dag.add_job(build);
 
@@ -417,6 +423,9 @@ mod tests {
             uses: None,
             run: Some("npm test".into()),
             estimated_duration_secs: Some(300.0),
+            with: HashMap::new(),
+            working_directory: None,
+            env: HashMap::new(),
         });
         dag.add_job(test);
 
@@ -428,6 +437,9 @@ mod tests {
             uses: None,
             run: Some("deploy.sh".into()),
             estimated_duration_secs: Some(120.0),
+            with: HashMap::new(),
+            working_directory: None,
+            env: HashMap::new(),
         });
         dag.add_job(deploy);
 
@@ -1,4 +1,5 @@
 use pipelinex_core::analyzer;
+use pipelinex_core::analyzer::report::{FindingCategory, Severity};
 use pipelinex_core::optimizer::docker_opt;
 use pipelinex_core::optimizer::Optimizer;
 use pipelinex_core::parser::argo::ArgoWorkflowsParser;
@@ -605,3 +606,63 @@ fn test_analyze_drone_pipeline() {
     let deploy = dag.get_job("deploy").unwrap();
     assert_eq!(deploy.needs.len(), 2);
 }
+
+#[test]
+fn test_json_lines_one_report_per_line() {
+    let dir = tempfile::tempdir().unwrap();
+    for name in ["unoptimized-fullstack.yml", "rust-project.yml"] {
+        std::fs::copy(github_fixture(name), dir.path().join(name)).unwrap();
+    }
+
+    let files = pipelinex_core::discovery::walk_pipeline_files(dir.path(), 3).unwrap();
+    let mut output = String::new();
+    for file in &files {
+        let dag = GitHubActionsParser::parse_file(file).unwrap();
+        let report = analyzer::analyze(&dag);
+        output.push_str(&report.to_json_line().unwrap());
+        output.push('\n');
+    }
+
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 2);
+    for line in lines {
+        let value: serde_json::Value = serde_json::from_str(line).unwrap();
+        assert!(value.is_object());
+        assert!(value["findings"].is_array());
+    }
+}
+
+#[test]
+fn test_security_and_policy_findings_merge_into_report() {
+    let dag = GitHubActionsParser::parse_file(&github_fixture("docker-publish.yml")).unwrap();
+    let mut report = analyzer::analyze(&dag);
+    let analysis_count = report.findings.len();
+
+    let security = pipelinex_core::security_scan(&dag);
+    assert!(!security.is_empty());
+    let security_count = security.len();
+    report.merge_findings(security);
+
+    let mut policy = pipelinex_core::PolicyConfig::default();
+    policy.rules.require_sha_pinning = true;
+    let policy_report = pipelinex_core::check_policy(&dag, &policy);
+    assert!(!policy_report.violations.is_empty());
+    report.merge_findings(policy_report.violations.iter().map(|v| v.to_finding()));
+
+    assert_eq!(
+        report.findings.len(),
+        analysis_count + security_count + policy_report.violations.len()
+    );
+    assert!(report
+        .findings
+        .iter()
+        .any(|f| f.category == FindingCategory::Security));
+    assert!(report
+        .findings
+        .iter()
+        .any(|f| f.category == FindingCategory::Policy && f.severity == Severity::High));
+    assert!(report
+        .findings
+        .windows(2)
+        .all(|w| w[0].severity.priority() >= w[1].severity.priority()));
+}